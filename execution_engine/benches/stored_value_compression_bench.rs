@@ -0,0 +1,107 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use lmdb::{Cursor, DatabaseFlags};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use tempfile::TempDir;
+
+use casper_execution_engine::storage::{
+    store::Store,
+    transaction_source::{lmdb::LmdbEnvironment, Transaction, TransactionSource},
+    trie::Trie,
+    trie_store::lmdb::LmdbTrieStore,
+};
+use casper_hashing::Digest;
+use casper_types::{bytesrepr::ToBytes, ContractWasm, Key, StoredValue};
+
+const MAP_SIZE: usize = 4096 * 256 * 1024;
+const MAX_READERS: u32 = 512;
+const LEAF_COUNT: usize = 100;
+const WASM_SIZE: usize = 64 * 1024;
+const COMPRESSION_THRESHOLD: usize = 1024;
+
+/// Creates leaves holding pseudo-random Wasm-like payloads, drawn from a small alphabet of
+/// opcodes with occasional arbitrary immediates.
+fn wasm_leaves() -> Vec<(Digest, Trie<Key, StoredValue>)> {
+    let mut rng = StdRng::seed_from_u64(0);
+    (0..LEAF_COUNT)
+        .map(|index| {
+            let bytes = (0..WASM_SIZE)
+                .map(|_| {
+                    if rng.gen_ratio(1, 8) {
+                        rng.gen()
+                    } else {
+                        rng.gen_range(0x20..0x30)
+                    }
+                })
+                .collect();
+            let leaf = Trie::Leaf {
+                key: Key::Hash([index as u8; 32]),
+                value: StoredValue::ContractWasm(ContractWasm::new(bytes)),
+            };
+            (Digest::hash(leaf.to_bytes().unwrap()), leaf)
+        })
+        .collect()
+}
+
+fn new_store(compression: bool) -> (TempDir, LmdbEnvironment, LmdbTrieStore) {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let env = LmdbEnvironment::new(tmp_dir.path(), MAP_SIZE, MAX_READERS, true).unwrap();
+    let mut store = LmdbTrieStore::new(&env, None, DatabaseFlags::empty()).unwrap();
+    if compression {
+        store = store.with_compression_threshold(COMPRESSION_THRESHOLD);
+    }
+    (tmp_dir, env, store)
+}
+
+fn commit_leaves(
+    env: &LmdbEnvironment,
+    store: &LmdbTrieStore,
+    leaves: &[(Digest, Trie<Key, StoredValue>)],
+) {
+    let mut txn = env.create_read_write_txn().unwrap();
+    for (hash, leaf) in leaves {
+        store.put(&mut txn, hash, leaf).unwrap();
+    }
+    txn.commit().unwrap();
+}
+
+/// Returns the total number of bytes stored as values in the trie store.
+fn stored_size(env: &LmdbEnvironment, store: &LmdbTrieStore) -> usize {
+    let txn = env.env().begin_ro_txn().unwrap();
+    let mut cursor = lmdb::Transaction::open_ro_cursor(&txn, store.get_db()).unwrap();
+    cursor.iter().map(|row| row.unwrap().1.len()).sum::<usize>()
+}
+
+fn stored_value_compression_bench(c: &mut Criterion) {
+    let leaves = wasm_leaves();
+
+    for compression in [false, true] {
+        let (_tmp_dir, env, store) = new_store(compression);
+        commit_leaves(&env, &store, &leaves);
+        println!(
+            "compression {}: {} leaves stored in {} bytes",
+            if compression { "enabled" } else { "disabled" },
+            LEAF_COUNT,
+            stored_size(&env, &store)
+        );
+    }
+
+    let mut group = c.benchmark_group("commit_wasm_leaves");
+    for compression in [false, true] {
+        let name = if compression {
+            "compressed"
+        } else {
+            "uncompressed"
+        };
+        group.bench_function(name, |b| {
+            b.iter_batched(
+                || new_store(compression),
+                |(_tmp_dir, env, store)| commit_leaves(&env, &store, &leaves),
+                BatchSize::PerIteration,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, stored_value_compression_bench);
+criterion_main!(benches);
@@ -7,6 +7,7 @@ use std::collections::BTreeSet;
 
 use num_rational::Ratio;
 use num_traits::One;
+use serde::Serialize;
 
 use casper_types::{account::AccountHash, PublicKey};
 
@@ -47,9 +48,21 @@ pub const DEFAULT_REFUND_HANDLING: RefundHandling = RefundHandling::Refund {
 };
 /// Default fee handling.
 pub const DEFAULT_FEE_HANDLING: FeeHandling = FeeHandling::PayToProposer;
+/// Default value for checking the entry point of a stored contract before execution.
+pub const DEFAULT_ENTRY_POINT_PRECHECK: bool = false;
+/// Default minimum balance an account's main purse must retain after a transfer.
+pub const DEFAULT_MINIMUM_REMAINING_BALANCE: u64 = 0;
+/// Default number of consecutive eras a validator must be inactive for to be evicted.
+pub const DEFAULT_VALIDATOR_EVICTION_THRESHOLD: u64 = 1;
+/// Default value for deduplicating writes in execution effects.
+pub const DEFAULT_DEDUPLICATE_WRITES: bool = false;
+/// Default value for isolating deploys which hit an internal error from the rest of the block.
+pub const DEFAULT_ISOLATE_DEPLOY_FAILURES: bool = false;
+/// Default value for recording error codes in the execution results of a block.
+pub const DEFAULT_RECORD_EXECUTION_ERROR_CODES: bool = false;
 
 /// The runtime configuration of the execution engine
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct EngineConfig {
     /// Max query depth of the engine.
     pub(crate) max_query_depth: u64,
@@ -81,6 +94,26 @@ pub struct EngineConfig {
     pub(crate) refund_handling: RefundHandling,
     /// Fee handling.
     pub(crate) fee_handling: FeeHandling,
+    /// If set to `true`, the entry point targeted by a stored contract call is checked to exist,
+    /// and with strict argument checking its required arguments to be provided, before execution.
+    pub(crate) entry_point_precheck: bool,
+    /// The minimum balance, in motes, an account's main purse must retain after a transfer made
+    /// from it during session execution.
+    pub(crate) minimum_remaining_balance: u64,
+    /// The number of consecutive eras a validator must be reported inactive for before its bid is
+    /// deactivated.  0 disables eviction.
+    pub(crate) validator_eviction_threshold: u64,
+    /// If set to `true`, writes which leave a value unchanged are omitted from execution effects,
+    /// and consecutive writes to the same key are coalesced.  If `false`, every write is recorded.
+    pub(crate) deduplicate_writes: bool,
+    /// If set to `true`, a deploy whose execution hits an internal error which every node would
+    /// hit alike is recorded as failed and charged as for an error in its payment code, rather
+    /// than the error aborting execution of the rest of the block.
+    pub(crate) isolate_deploy_failures: bool,
+    /// If set to `true`, the execution results of a block record the code identifying the error
+    /// of each failed deploy.  This changes the execution results checksum of blocks with failed
+    /// deploys, so must only be enabled via a protocol upgrade.
+    pub(crate) record_execution_error_codes: bool,
 }
 
 impl Default for EngineConfig {
@@ -100,6 +133,12 @@ impl Default for EngineConfig {
             allow_unrestricted_transfers: DEFAULT_ALLOW_UNRESTRICTED_TRANSFERS,
             refund_handling: DEFAULT_REFUND_HANDLING,
             fee_handling: DEFAULT_FEE_HANDLING,
+            entry_point_precheck: DEFAULT_ENTRY_POINT_PRECHECK,
+            minimum_remaining_balance: DEFAULT_MINIMUM_REMAINING_BALANCE,
+            validator_eviction_threshold: DEFAULT_VALIDATOR_EVICTION_THRESHOLD,
+            deduplicate_writes: DEFAULT_DEDUPLICATE_WRITES,
+            isolate_deploy_failures: DEFAULT_ISOLATE_DEPLOY_FAILURES,
+            record_execution_error_codes: DEFAULT_RECORD_EXECUTION_ERROR_CODES,
         }
     }
 }
@@ -140,6 +179,12 @@ impl EngineConfig {
             allow_unrestricted_transfers: DEFAULT_ALLOW_UNRESTRICTED_TRANSFERS,
             refund_handling: DEFAULT_REFUND_HANDLING,
             fee_handling: DEFAULT_FEE_HANDLING,
+            entry_point_precheck: DEFAULT_ENTRY_POINT_PRECHECK,
+            minimum_remaining_balance: DEFAULT_MINIMUM_REMAINING_BALANCE,
+            validator_eviction_threshold: DEFAULT_VALIDATOR_EVICTION_THRESHOLD,
+            deduplicate_writes: DEFAULT_DEDUPLICATE_WRITES,
+            isolate_deploy_failures: DEFAULT_ISOLATE_DEPLOY_FAILURES,
+            record_execution_error_codes: DEFAULT_RECORD_EXECUTION_ERROR_CODES,
         }
     }
 
@@ -212,6 +257,37 @@ impl EngineConfig {
     pub fn fee_handling(&self) -> FeeHandling {
         self.fee_handling
     }
+
+    /// Returns true if stored contract calls are checked before execution.
+    pub fn entry_point_precheck(&self) -> bool {
+        self.entry_point_precheck
+    }
+
+    /// Returns the minimum balance an account's main purse must retain after a transfer.
+    pub fn minimum_remaining_balance(&self) -> u64 {
+        self.minimum_remaining_balance
+    }
+
+    /// Returns the number of consecutive inactive eras after which a validator is evicted.
+    pub fn validator_eviction_threshold(&self) -> u64 {
+        self.validator_eviction_threshold
+    }
+
+    /// Returns true if writes which leave a value unchanged are omitted from execution effects.
+    pub fn deduplicate_writes(&self) -> bool {
+        self.deduplicate_writes
+    }
+
+    /// Returns true if deploys which hit an internal error are recorded as failed rather than
+    /// aborting execution of the block.
+    pub fn isolate_deploy_failures(&self) -> bool {
+        self.isolate_deploy_failures
+    }
+
+    /// Returns true if the execution results of a block record the error codes of failed deploys.
+    pub fn record_execution_error_codes(&self) -> bool {
+        self.record_execution_error_codes
+    }
 }
 
 /// A builder for an [`EngineConfig`].
@@ -234,6 +310,12 @@ pub struct EngineConfigBuilder {
     allow_unrestricted_transfers: Option<bool>,
     refund_handling: Option<RefundHandling>,
     fee_handling: Option<FeeHandling>,
+    entry_point_precheck: Option<bool>,
+    minimum_remaining_balance: Option<u64>,
+    validator_eviction_threshold: Option<u64>,
+    deduplicate_writes: Option<bool>,
+    isolate_deploy_failures: Option<bool>,
+    record_execution_error_codes: Option<bool>,
 }
 
 impl EngineConfigBuilder {
@@ -348,6 +430,42 @@ impl EngineConfigBuilder {
         self
     }
 
+    /// Sets the entry point precheck config option.
+    pub fn with_entry_point_precheck(mut self, entry_point_precheck: bool) -> Self {
+        self.entry_point_precheck = Some(entry_point_precheck);
+        self
+    }
+
+    /// Sets the minimum remaining balance config option.
+    pub fn with_minimum_remaining_balance(mut self, minimum_remaining_balance: u64) -> Self {
+        self.minimum_remaining_balance = Some(minimum_remaining_balance);
+        self
+    }
+
+    /// Sets the validator eviction threshold config option.
+    pub fn with_validator_eviction_threshold(mut self, validator_eviction_threshold: u64) -> Self {
+        self.validator_eviction_threshold = Some(validator_eviction_threshold);
+        self
+    }
+
+    /// Sets the deduplicate writes config option.
+    pub fn with_deduplicate_writes(mut self, deduplicate_writes: bool) -> Self {
+        self.deduplicate_writes = Some(deduplicate_writes);
+        self
+    }
+
+    /// Sets the isolate deploy failures config option.
+    pub fn with_isolate_deploy_failures(mut self, isolate_deploy_failures: bool) -> Self {
+        self.isolate_deploy_failures = Some(isolate_deploy_failures);
+        self
+    }
+
+    /// Sets the record execution error codes config option.
+    pub fn with_record_execution_error_codes(mut self, record_execution_error_codes: bool) -> Self {
+        self.record_execution_error_codes = Some(record_execution_error_codes);
+        self
+    }
+
     /// Builds a new [`EngineConfig`] object.
     pub fn build(self) -> EngineConfig {
         let max_query_depth = self.max_query_depth.unwrap_or(DEFAULT_MAX_QUERY_DEPTH);
@@ -377,6 +495,24 @@ impl EngineConfigBuilder {
             .unwrap_or(DEFAULT_ALLOW_UNRESTRICTED_TRANSFERS);
         let refund_handling = self.refund_handling.unwrap_or(DEFAULT_REFUND_HANDLING);
         let fee_handling = self.fee_handling.unwrap_or(DEFAULT_FEE_HANDLING);
+        let entry_point_precheck = self
+            .entry_point_precheck
+            .unwrap_or(DEFAULT_ENTRY_POINT_PRECHECK);
+        let minimum_remaining_balance = self
+            .minimum_remaining_balance
+            .unwrap_or(DEFAULT_MINIMUM_REMAINING_BALANCE);
+        let validator_eviction_threshold = self
+            .validator_eviction_threshold
+            .unwrap_or(DEFAULT_VALIDATOR_EVICTION_THRESHOLD);
+        let deduplicate_writes = self
+            .deduplicate_writes
+            .unwrap_or(DEFAULT_DEDUPLICATE_WRITES);
+        let isolate_deploy_failures = self
+            .isolate_deploy_failures
+            .unwrap_or(DEFAULT_ISOLATE_DEPLOY_FAILURES);
+        let record_execution_error_codes = self
+            .record_execution_error_codes
+            .unwrap_or(DEFAULT_RECORD_EXECUTION_ERROR_CODES);
 
         let strict_argument_checking = self
             .strict_argument_checking
@@ -398,6 +534,12 @@ impl EngineConfigBuilder {
             allow_unrestricted_transfers,
             refund_handling,
             fee_handling,
+            entry_point_precheck,
+            minimum_remaining_balance,
+            validator_eviction_threshold,
+            deduplicate_writes,
+            isolate_deploy_failures,
+            record_execution_error_codes,
             strict_argument_checking,
             vesting_schedule_period_millis,
             max_delegators_per_validator,
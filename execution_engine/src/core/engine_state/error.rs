@@ -3,7 +3,10 @@ use datasize::DataSize;
 use thiserror::Error;
 
 use casper_hashing::Digest;
-use casper_types::{bytesrepr, system::mint, ApiError, ProtocolVersion};
+use casper_types::{
+    bytesrepr, system::mint, ApiError, ContractHash, ErrorCategory, ExecutionErrorCode,
+    ProtocolVersion,
+};
 
 use crate::{
     core::{
@@ -106,6 +109,20 @@ pub enum Error {
     /// Failed to retrieve accumulation purse from handle payment system contract.
     #[error("Failed to retrieve accumulation purse from the handle payment contract")]
     FailedToRetrieveAccumulationPurse,
+    /// The entry point targeted by a stored contract call doesn't exist.
+    #[error("Contract {contract_hash} has no entry point named {entry_point_name}")]
+    MissingEntryPoint {
+        /// The hash of the called contract.
+        contract_hash: ContractHash,
+        /// The name of the missing entry point.
+        entry_point_name: String,
+    },
+    /// The query was rejected as too many queries are already waiting to run.
+    #[error("Query rejected as {max_queued_queries} queries are already waiting to run")]
+    QueryQueueFull {
+        /// The maximum number of queries permitted to wait to run.
+        max_queued_queries: usize,
+    },
 }
 
 impl Error {
@@ -117,6 +134,88 @@ impl Error {
     pub fn reverter(api_error: impl Into<ApiError>) -> Error {
         Error::Exec(execution::Error::Revert(api_error.into()))
     }
+
+    /// Returns the stable error code identifying this error.
+    ///
+    /// Execution errors are reported using the code of the wrapped [`execution::Error`].
+    pub fn error_code(&self) -> ExecutionErrorCode {
+        let (category, code) = match self {
+            Error::Exec(error) => return error.error_code(),
+            Error::RootNotFound(_) => (ErrorCategory::Precondition, 1),
+            Error::InvalidProtocolVersion(_) => (ErrorCategory::Precondition, 2),
+            Error::Genesis(_) => (ErrorCategory::Internal, 3),
+            Error::WasmPreprocessing(_) => (ErrorCategory::Precondition, 4),
+            Error::WasmSerialization(_) => (ErrorCategory::Precondition, 5),
+            Error::Storage(_) => (ErrorCategory::Internal, 6),
+            Error::Authorization => (ErrorCategory::Authorization, 7),
+            Error::InsufficientPayment => (ErrorCategory::Precondition, 8),
+            Error::GasConversionOverflow => (ErrorCategory::Precondition, 9),
+            Error::Deploy => (ErrorCategory::Precondition, 10),
+            Error::Finalization => (ErrorCategory::Internal, 11),
+            Error::Bytesrepr(_) => (ErrorCategory::Internal, 12),
+            Error::Mint(_) => (ErrorCategory::Internal, 13),
+            Error::InvalidKeyVariant => (ErrorCategory::Precondition, 14),
+            Error::ProtocolUpgrade(_) => (ErrorCategory::Internal, 15),
+            Error::InvalidDeployItemVariant(_) => (ErrorCategory::Precondition, 16),
+            Error::CommitError(_) => (ErrorCategory::Internal, 17),
+            Error::MissingSystemContractRegistry => (ErrorCategory::Internal, 18),
+            Error::MissingSystemContractHash(_) => (ErrorCategory::Internal, 19),
+            Error::MissingChecksumRegistry => (ErrorCategory::Internal, 20),
+            Error::RuntimeStackOverflow => (ErrorCategory::RuntimeTrap, 21),
+            Error::FailedToGetWithdrawKeys => (ErrorCategory::Internal, 22),
+            Error::FailedToGetStoredWithdraws => (ErrorCategory::Internal, 23),
+            Error::FailedToGetWithdrawPurses => (ErrorCategory::Internal, 24),
+            Error::FailedToRetrieveUnbondingDelay => (ErrorCategory::Internal, 25),
+            Error::FailedToRetrieveEraId => (ErrorCategory::Internal, 26),
+            Error::MissingTrieNodeChildren(_) => (ErrorCategory::Internal, 27),
+            Error::FailedToRetrieveAccumulationPurse => (ErrorCategory::Internal, 28),
+            Error::MissingEntryPoint { .. } => (ErrorCategory::Precondition, 29),
+            Error::QueryQueueFull { .. } => (ErrorCategory::Precondition, 31),
+        };
+        ExecutionErrorCode::new(category, code)
+    }
+
+    /// Returns `true` if the error is determined by the deploy and the global state it executes
+    /// against, so that every node executing the deploy hits the error alike.
+    ///
+    /// Errors which may have been caused by the node running the execution, e.g. by a failure of
+    /// its storage, must never be recorded as the outcome of a deploy.  New variants must be
+    /// classified explicitly, and only errors known to be deterministic may be listed as such.
+    pub fn is_deterministic(&self) -> bool {
+        match self {
+            Error::Exec(error) => error.is_deterministic(),
+            Error::InvalidProtocolVersion(_)
+            | Error::WasmPreprocessing(_)
+            | Error::WasmSerialization(_)
+            | Error::Authorization
+            | Error::InsufficientPayment
+            | Error::GasConversionOverflow
+            | Error::Deploy
+            | Error::Finalization
+            | Error::Bytesrepr(_)
+            | Error::Mint(_)
+            | Error::InvalidKeyVariant
+            | Error::InvalidDeployItemVariant(_)
+            | Error::MissingSystemContractRegistry
+            | Error::MissingSystemContractHash(_)
+            | Error::MissingChecksumRegistry
+            | Error::RuntimeStackOverflow
+            | Error::MissingEntryPoint { .. } => true,
+            Error::RootNotFound(_)
+            | Error::Genesis(_)
+            | Error::Storage(_)
+            | Error::ProtocolUpgrade(_)
+            | Error::CommitError(_)
+            | Error::FailedToGetWithdrawKeys
+            | Error::FailedToGetStoredWithdraws
+            | Error::FailedToGetWithdrawPurses
+            | Error::FailedToRetrieveUnbondingDelay
+            | Error::FailedToRetrieveEraId
+            | Error::MissingTrieNodeChildren(_)
+            | Error::FailedToRetrieveAccumulationPurse
+            | Error::QueryQueueFull { .. } => false,
+        }
+    }
 }
 
 impl From<execution::Error> for Error {
@@ -11,6 +11,7 @@ use std::{
 use datasize::DataSize;
 use hex_buffer_serde::{Hex, HexForm};
 use hex_fmt::HexFmt;
+use once_cell::sync::Lazy;
 use rand::{
     distributions::{Alphanumeric, Distribution, Standard},
     Rng,
@@ -22,7 +23,14 @@ use casper_hashing::Digest;
 use casper_types::{
     bytesrepr::{self, Bytes, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
     contracts::{ContractVersion, NamedKeys, DEFAULT_ENTRY_POINT_NAME},
-    system::mint::ARG_AMOUNT,
+    system::{
+        auction::{
+            METHOD_ACTIVATE_BID, METHOD_CANCEL_UNBONDING, METHOD_DELEGATE, METHOD_REDELEGATE,
+            METHOD_UNDELEGATE,
+        },
+        mint::ARG_AMOUNT,
+        AUCTION,
+    },
     CLValue, ContractHash, ContractPackage, ContractPackageHash, ContractVersionKey, Gas, Key,
     Motes, Phase, ProtocolVersion, RuntimeArgs, StoredValue, U512,
 };
@@ -44,6 +52,16 @@ const STORED_CONTRACT_BY_NAME_TAG: u8 = 2;
 const STORED_VERSIONED_CONTRACT_BY_HASH_TAG: u8 = 3;
 const STORED_VERSIONED_CONTRACT_BY_NAME_TAG: u8 = 4;
 const TRANSFER_TAG: u8 = 5;
+const MULTI_TRANSFER_TAG: u8 = 6;
+const NATIVE_AUCTION_TAG: u8 = 7;
+
+const DELEGATE_TAG: u8 = 0;
+const UNDELEGATE_TAG: u8 = 1;
+const REDELEGATE_TAG: u8 = 2;
+const ACTIVATE_BID_TAG: u8 = 3;
+const CANCEL_UNBONDING_TAG: u8 = 4;
+
+static EMPTY_RUNTIME_ARGS: Lazy<RuntimeArgs> = Lazy::new(RuntimeArgs::new);
 
 /// Possible ways to identify the `ExecutableDeployItem`.
 #[derive(
@@ -58,6 +76,10 @@ pub enum ExecutableDeployItemIdentifier {
     Package(ContractPackageIdentifier),
     /// The deploy item is a native transfer.
     Transfer,
+    /// The deploy item is a batch of native transfers.
+    MultiTransfer,
+    /// The deploy item is a native call to the auction contract.
+    NativeAuction,
 }
 
 /// Possible ways to identify the contract object within an `ExecutableDeployItem`.
@@ -92,6 +114,85 @@ pub enum ContractPackageIdentifier {
     },
 }
 
+/// An auction contract entry point which can be called natively, without session Wasm.
+#[derive(
+    Clone,
+    Copy,
+    DataSize,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+pub enum NativeAuctionEntryPoint {
+    /// Delegates to a validator.
+    Delegate,
+    /// Undelegates from a validator.
+    Undelegate,
+    /// Undelegates from a validator and, once unbonded, delegates to another.
+    Redelegate,
+    /// Reactivates a validator's bid after it has been evicted.
+    ActivateBid,
+    /// Cancels pending unbonding requests, adding the amount back to the stake.
+    CancelUnbonding,
+}
+
+impl NativeAuctionEntryPoint {
+    /// Returns the name of the auction contract entry point.
+    pub fn name(&self) -> &'static str {
+        match self {
+            NativeAuctionEntryPoint::Delegate => METHOD_DELEGATE,
+            NativeAuctionEntryPoint::Undelegate => METHOD_UNDELEGATE,
+            NativeAuctionEntryPoint::Redelegate => METHOD_REDELEGATE,
+            NativeAuctionEntryPoint::ActivateBid => METHOD_ACTIVATE_BID,
+            NativeAuctionEntryPoint::CancelUnbonding => METHOD_CANCEL_UNBONDING,
+        }
+    }
+}
+
+impl Display for NativeAuctionEntryPoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl ToBytes for NativeAuctionEntryPoint {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let tag = match self {
+            NativeAuctionEntryPoint::Delegate => DELEGATE_TAG,
+            NativeAuctionEntryPoint::Undelegate => UNDELEGATE_TAG,
+            NativeAuctionEntryPoint::Redelegate => REDELEGATE_TAG,
+            NativeAuctionEntryPoint::ActivateBid => ACTIVATE_BID_TAG,
+            NativeAuctionEntryPoint::CancelUnbonding => CANCEL_UNBONDING_TAG,
+        };
+        Ok(vec![tag])
+    }
+
+    fn serialized_length(&self) -> usize {
+        TAG_LENGTH
+    }
+}
+
+impl FromBytes for NativeAuctionEntryPoint {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        let entry_point = match tag {
+            DELEGATE_TAG => NativeAuctionEntryPoint::Delegate,
+            UNDELEGATE_TAG => NativeAuctionEntryPoint::Undelegate,
+            REDELEGATE_TAG => NativeAuctionEntryPoint::Redelegate,
+            ACTIVATE_BID_TAG => NativeAuctionEntryPoint::ActivateBid,
+            CANCEL_UNBONDING_TAG => NativeAuctionEntryPoint::CancelUnbonding,
+            _ => return Err(bytesrepr::Error::Formatting),
+        };
+        Ok((entry_point, remainder))
+    }
+}
+
 impl ContractPackageIdentifier {
     /// Returns the version of the contract package specified in the deploy item.
     pub fn version(&self) -> Option<ContractVersion> {
@@ -175,6 +276,20 @@ pub enum ExecutableDeployItem {
         /// Runtime arguments.
         args: RuntimeArgs,
     },
+    /// A batch of native transfers from a single source purse, paid for by a single fee and
+    /// executed atomically: either all of them succeed or none do.
+    MultiTransfer {
+        /// Runtime arguments of each transfer, as for [`ExecutableDeployItem::Transfer`].
+        transfers: Vec<RuntimeArgs>,
+    },
+    /// A call to an entry point of the auction contract which does not contain or reference WASM
+    /// code.
+    NativeAuction {
+        /// The auction contract entry point.
+        entry_point: NativeAuctionEntryPoint,
+        /// Runtime arguments.
+        args: RuntimeArgs,
+    },
 }
 
 mod contract_hash_as_digest {
@@ -217,13 +332,14 @@ impl ExecutableDeployItem {
     /// Returns the entry point name.
     pub fn entry_point_name(&self) -> &str {
         match self {
-            ExecutableDeployItem::ModuleBytes { .. } | ExecutableDeployItem::Transfer { .. } => {
-                DEFAULT_ENTRY_POINT_NAME
-            }
+            ExecutableDeployItem::ModuleBytes { .. }
+            | ExecutableDeployItem::Transfer { .. }
+            | ExecutableDeployItem::MultiTransfer { .. } => DEFAULT_ENTRY_POINT_NAME,
             ExecutableDeployItem::StoredVersionedContractByName { entry_point, .. }
             | ExecutableDeployItem::StoredVersionedContractByHash { entry_point, .. }
             | ExecutableDeployItem::StoredContractByHash { entry_point, .. }
             | ExecutableDeployItem::StoredContractByName { entry_point, .. } => entry_point,
+            ExecutableDeployItem::NativeAuction { entry_point, .. } => entry_point.name(),
         }
     }
 
@@ -250,6 +366,12 @@ impl ExecutableDeployItem {
                 })
             }
             ExecutableDeployItem::Transfer { .. } => ExecutableDeployItemIdentifier::Transfer,
+            ExecutableDeployItem::MultiTransfer { .. } => {
+                ExecutableDeployItemIdentifier::MultiTransfer
+            }
+            ExecutableDeployItem::NativeAuction { .. } => {
+                ExecutableDeployItemIdentifier::NativeAuction
+            }
         }
     }
 
@@ -259,7 +381,9 @@ impl ExecutableDeployItem {
             ExecutableDeployItem::ModuleBytes { .. }
             | ExecutableDeployItem::StoredVersionedContractByHash { .. }
             | ExecutableDeployItem::StoredVersionedContractByName { .. }
-            | ExecutableDeployItem::Transfer { .. } => None,
+            | ExecutableDeployItem::Transfer { .. }
+            | ExecutableDeployItem::MultiTransfer { .. }
+            | ExecutableDeployItem::NativeAuction { .. } => None,
 
             ExecutableDeployItem::StoredContractByName { name, .. } => {
                 Some(ContractIdentifier::Name(name.to_string()))
@@ -276,7 +400,9 @@ impl ExecutableDeployItem {
             ExecutableDeployItem::ModuleBytes { .. }
             | ExecutableDeployItem::StoredContractByHash { .. }
             | ExecutableDeployItem::StoredContractByName { .. }
-            | ExecutableDeployItem::Transfer { .. } => None,
+            | ExecutableDeployItem::Transfer { .. }
+            | ExecutableDeployItem::MultiTransfer { .. }
+            | ExecutableDeployItem::NativeAuction { .. } => None,
 
             ExecutableDeployItem::StoredVersionedContractByName { name, version, .. } => {
                 Some(ContractPackageIdentifier::Name {
@@ -294,6 +420,9 @@ impl ExecutableDeployItem {
     }
 
     /// Returns the runtime arguments.
+    ///
+    /// These are empty for [`ExecutableDeployItem::MultiTransfer`], the arguments of whose
+    /// transfers are returned by [`ExecutableDeployItem::transfer_args`].
     pub fn args(&self) -> &RuntimeArgs {
         match self {
             ExecutableDeployItem::ModuleBytes { args, .. }
@@ -301,7 +430,24 @@ impl ExecutableDeployItem {
             | ExecutableDeployItem::StoredContractByName { args, .. }
             | ExecutableDeployItem::StoredVersionedContractByHash { args, .. }
             | ExecutableDeployItem::StoredVersionedContractByName { args, .. }
-            | ExecutableDeployItem::Transfer { args } => args,
+            | ExecutableDeployItem::Transfer { args }
+            | ExecutableDeployItem::NativeAuction { args, .. } => args,
+            ExecutableDeployItem::MultiTransfer { .. } => &EMPTY_RUNTIME_ARGS,
+        }
+    }
+
+    /// Returns the runtime arguments of each native transfer in the deploy item, or an empty slice
+    /// if it is not a native transfer.
+    pub fn transfer_args(&self) -> &[RuntimeArgs] {
+        match self {
+            ExecutableDeployItem::Transfer { args } => std::slice::from_ref(args),
+            ExecutableDeployItem::MultiTransfer { transfers } => transfers,
+            ExecutableDeployItem::ModuleBytes { .. }
+            | ExecutableDeployItem::StoredContractByHash { .. }
+            | ExecutableDeployItem::StoredContractByName { .. }
+            | ExecutableDeployItem::StoredVersionedContractByHash { .. }
+            | ExecutableDeployItem::StoredVersionedContractByName { .. }
+            | ExecutableDeployItem::NativeAuction { .. } => &[],
         }
     }
 
@@ -313,9 +459,12 @@ impl ExecutableDeployItem {
         }
     }
 
-    /// Checks if this deploy item is a native transfer.
+    /// Checks if this deploy item is a native transfer or a batch of native transfers.
     pub fn is_transfer(&self) -> bool {
-        matches!(self, ExecutableDeployItem::Transfer { .. })
+        matches!(
+            self,
+            ExecutableDeployItem::Transfer { .. } | ExecutableDeployItem::MultiTransfer { .. }
+        )
     }
 
     /// Checks if this deploy is a standard payment.
@@ -350,7 +499,9 @@ impl ExecutableDeployItem {
             ExecutableDeployItem::ModuleBytes { .. }
             | ExecutableDeployItem::StoredContractByHash { .. }
             | ExecutableDeployItem::StoredVersionedContractByHash { .. }
-            | ExecutableDeployItem::Transfer { .. } => None,
+            | ExecutableDeployItem::Transfer { .. }
+            | ExecutableDeployItem::MultiTransfer { .. }
+            | ExecutableDeployItem::NativeAuction { .. } => None,
         }
     }
 
@@ -436,6 +587,15 @@ impl ToBytes for ExecutableDeployItem {
                 buffer.insert(0, TRANSFER_TAG);
                 buffer.extend(args.to_bytes()?)
             }
+            ExecutableDeployItem::MultiTransfer { transfers } => {
+                buffer.insert(0, MULTI_TRANSFER_TAG);
+                buffer.extend(transfers.to_bytes()?)
+            }
+            ExecutableDeployItem::NativeAuction { entry_point, args } => {
+                buffer.insert(0, NATIVE_AUCTION_TAG);
+                buffer.extend(entry_point.to_bytes()?);
+                buffer.extend(args.to_bytes()?)
+            }
         }
         Ok(buffer)
     }
@@ -487,6 +647,10 @@ impl ToBytes for ExecutableDeployItem {
                         + args.serialized_length()
                 }
                 ExecutableDeployItem::Transfer { args } => args.serialized_length(),
+                ExecutableDeployItem::MultiTransfer { transfers } => transfers.serialized_length(),
+                ExecutableDeployItem::NativeAuction { entry_point, args } => {
+                    entry_point.serialized_length() + args.serialized_length()
+                }
             }
     }
 }
@@ -563,6 +727,18 @@ impl FromBytes for ExecutableDeployItem {
                 let (args, remainder) = FromBytes::from_bytes(remainder)?;
                 Ok((ExecutableDeployItem::Transfer { args }, remainder))
             }
+            MULTI_TRANSFER_TAG => {
+                let (transfers, remainder) = FromBytes::from_bytes(remainder)?;
+                Ok((ExecutableDeployItem::MultiTransfer { transfers }, remainder))
+            }
+            NATIVE_AUCTION_TAG => {
+                let (entry_point, remainder) = FromBytes::from_bytes(remainder)?;
+                let (args, remainder) = FromBytes::from_bytes(remainder)?;
+                Ok((
+                    ExecutableDeployItem::NativeAuction { entry_point, args },
+                    remainder,
+                ))
+            }
             _ => Err(bytesrepr::Error::Formatting),
         }
     }
@@ -627,6 +803,12 @@ impl Display for ExecutableDeployItem {
                 name, entry_point,
             ),
             ExecutableDeployItem::Transfer { .. } => write!(f, "transfer"),
+            ExecutableDeployItem::MultiTransfer { transfers } => {
+                write!(f, "multi-transfer [{} transfers]", transfers.len())
+            }
+            ExecutableDeployItem::NativeAuction { entry_point, .. } => {
+                write!(f, "native-auction, entry-point: {}", entry_point)
+            }
         }
     }
 }
@@ -686,6 +868,15 @@ impl Debug for ExecutableDeployItem {
             ExecutableDeployItem::Transfer { args } => {
                 f.debug_struct("Transfer").field("args", args).finish()
             }
+            ExecutableDeployItem::MultiTransfer { transfers } => f
+                .debug_struct("MultiTransfer")
+                .field("transfers", transfers)
+                .finish(),
+            ExecutableDeployItem::NativeAuction { entry_point, args } => f
+                .debug_struct("NativeAuction")
+                .field("entry_point", entry_point)
+                .field("args", args)
+                .finish(),
         }
     }
 }
@@ -814,6 +1005,24 @@ impl ExecutionKind {
             ExecutableDeployItem::Transfer { .. } => {
                 Err(Error::InvalidDeployItemVariant("Transfer".into()))
             }
+            ExecutableDeployItem::MultiTransfer { .. } => {
+                Err(Error::InvalidDeployItemVariant("MultiTransfer".into()))
+            }
+            ExecutableDeployItem::NativeAuction { .. } if is_payment_phase => Err(
+                Error::InvalidDeployItemVariant("NativeAuction for payment".into()),
+            ),
+            ExecutableDeployItem::NativeAuction { entry_point, .. } => {
+                let auction_hash = tracking_copy
+                    .borrow_mut()
+                    .get_system_contracts(correlation_id)?
+                    .get(AUCTION)
+                    .copied()
+                    .ok_or_else(|| Error::MissingSystemContractHash(AUCTION.to_string()))?;
+                Ok(ExecutionKind::new_contract(
+                    auction_hash,
+                    entry_point.name().to_string(),
+                ))
+            }
             ExecutableDeployItem::ModuleBytes { module_bytes, .. }
                 if module_bytes.is_empty() && is_payment_phase =>
             {
@@ -926,6 +1135,56 @@ impl ExecutionKind {
             }
         }
     }
+
+    /// Checks that the entry point targeted by a stored contract call exists, and if
+    /// `strict_argument_checking` is set that all of its non-optional arguments are provided.
+    ///
+    /// This allows such calls to be rejected before the contract's Wasm is loaded and executed.
+    /// Module bytes are not checked.
+    pub(crate) fn precheck_entry_point<R>(
+        &self,
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+        correlation_id: CorrelationId,
+        args: &RuntimeArgs,
+        strict_argument_checking: bool,
+    ) -> Result<(), Error>
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<ExecError>,
+    {
+        let (contract_hash, entry_point_name) = match self {
+            ExecutionKind::Module(_) => return Ok(()),
+            ExecutionKind::Contract {
+                contract_hash,
+                entry_point_name,
+            } => (*contract_hash, entry_point_name),
+        };
+
+        let contract = tracking_copy
+            .borrow_mut()
+            .get_contract(correlation_id, contract_hash)?;
+        let entry_point =
+            contract
+                .entry_point(entry_point_name)
+                .ok_or_else(|| Error::MissingEntryPoint {
+                    contract_hash,
+                    entry_point_name: entry_point_name.clone(),
+                })?;
+
+        if strict_argument_checking {
+            if let Some(param) = entry_point
+                .args()
+                .iter()
+                .find(|param| !param.cl_type().is_option() && args.get(param.name()).is_none())
+            {
+                return Err(Error::Exec(execution::Error::MissingArgument {
+                    name: param.name().to_string(),
+                }));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -940,4 +1199,40 @@ mod tests {
             bytesrepr::test_serialization_roundtrip(&executable_deploy_item);
         }
     }
+
+    #[test]
+    fn multi_transfer_serialization_roundtrip() {
+        let transfers = (1..=3u64)
+            .map(|amount| {
+                let mut args = RuntimeArgs::new();
+                args.insert(ARG_AMOUNT, U512::from(amount)).unwrap();
+                args
+            })
+            .collect::<Vec<_>>();
+        let executable_deploy_item = ExecutableDeployItem::MultiTransfer { transfers };
+        bytesrepr::test_serialization_roundtrip(&executable_deploy_item);
+        assert!(executable_deploy_item.is_transfer());
+        assert_eq!(executable_deploy_item.transfer_args().len(), 3);
+        assert!(executable_deploy_item.args().is_empty());
+    }
+
+    #[test]
+    fn native_auction_serialization_roundtrip() {
+        for entry_point in [
+            NativeAuctionEntryPoint::Delegate,
+            NativeAuctionEntryPoint::Undelegate,
+            NativeAuctionEntryPoint::Redelegate,
+            NativeAuctionEntryPoint::ActivateBid,
+            NativeAuctionEntryPoint::CancelUnbonding,
+        ] {
+            let mut args = RuntimeArgs::new();
+            args.insert(ARG_AMOUNT, U512::from(1)).unwrap();
+            let executable_deploy_item = ExecutableDeployItem::NativeAuction { entry_point, args };
+            bytesrepr::test_serialization_roundtrip(&executable_deploy_item);
+            assert_eq!(
+                executable_deploy_item.entry_point_name(),
+                entry_point.name()
+            );
+        }
+    }
 }
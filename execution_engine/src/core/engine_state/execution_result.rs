@@ -35,6 +35,16 @@ fn make_payment_error_effects(
     ]))
 }
 
+/// The fee charged for executing a deploy and the refund returned to its payer, as settled by
+/// the engine when finalizing payment or charging for a payment code error.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PaymentSettlement {
+    /// The amount transferred to the proposer or the accumulation purse.
+    pub fee: Motes,
+    /// The amount returned to the payer.  Zero if unspent payment is burned rather than refunded.
+    pub refund: Motes,
+}
+
 /// Represents the result of an execution specified by
 /// [`crate::core::engine_state::ExecuteRequest`].
 #[derive(Clone, Debug)]
@@ -49,6 +59,9 @@ pub enum ExecutionResult {
         cost: Gas,
         /// Journal of execution.
         execution_journal: ExecutionJournal,
+        /// How payment for the deploy was settled.  Only set on the result of a whole deploy
+        /// which was charged.
+        payment_settlement: Option<PaymentSettlement>,
     },
     /// Execution was finished successfully
     Success {
@@ -58,6 +71,8 @@ pub enum ExecutionResult {
         cost: Gas,
         /// Journal of execution.
         execution_journal: ExecutionJournal,
+        /// How payment for the deploy was settled.  Only set on the result of a whole deploy.
+        payment_settlement: Option<PaymentSettlement>,
     },
 }
 
@@ -67,6 +82,7 @@ impl Default for ExecutionResult {
             execution_journal: Default::default(),
             transfers: Default::default(),
             cost: Default::default(),
+            payment_settlement: None,
         }
     }
 }
@@ -94,6 +110,7 @@ impl ExecutionResult {
             transfers: Vec::default(),
             cost: Gas::default(),
             execution_journal: Default::default(),
+            payment_settlement: None,
         }
     }
 
@@ -166,21 +183,25 @@ impl ExecutionResult {
                 error,
                 transfers,
                 execution_journal,
+                payment_settlement,
                 ..
             } => ExecutionResult::Failure {
                 error,
                 transfers,
                 cost,
                 execution_journal,
+                payment_settlement,
             },
             ExecutionResult::Success {
                 transfers,
                 execution_journal,
+                payment_settlement,
                 ..
             } => ExecutionResult::Success {
                 transfers,
                 cost,
                 execution_journal,
+                payment_settlement,
             },
         }
     }
@@ -195,21 +216,25 @@ impl ExecutionResult {
                 error,
                 cost,
                 execution_journal,
+                payment_settlement,
                 ..
             } => ExecutionResult::Failure {
                 error,
                 transfers,
                 cost,
                 execution_journal,
+                payment_settlement,
             },
             ExecutionResult::Success {
                 cost,
                 execution_journal,
+                payment_settlement,
                 ..
             } => ExecutionResult::Success {
                 transfers,
                 cost,
                 execution_journal,
+                payment_settlement,
             },
         }
     }
@@ -225,24 +250,41 @@ impl ExecutionResult {
                 transfers,
                 cost,
                 execution_journal: _,
+                payment_settlement,
             } => ExecutionResult::Failure {
                 error,
                 transfers,
                 cost,
                 execution_journal,
+                payment_settlement,
             },
             ExecutionResult::Success {
                 transfers,
                 cost,
                 execution_journal: _,
+                payment_settlement,
             } => ExecutionResult::Success {
                 transfers,
                 cost,
                 execution_journal,
+                payment_settlement,
             },
         }
     }
 
+    /// Returns how payment for the deploy was settled, if this is the result of a whole deploy
+    /// which was charged.
+    pub fn payment_settlement(&self) -> Option<PaymentSettlement> {
+        match self {
+            ExecutionResult::Failure {
+                payment_settlement, ..
+            }
+            | ExecutionResult::Success {
+                payment_settlement, ..
+            } => *payment_settlement,
+        }
+    }
+
     /// Returns error value, if possible.
     ///
     /// Returns a reference to a wrapped [`error::Error`] instance if the object is a failure
@@ -323,11 +365,17 @@ impl ExecutionResult {
             proposer_main_purse_balance_key,
         )?;
         let transfers = Vec::default();
+        // The whole of `max_payment_cost` is charged, with nothing refunded.
+        let payment_settlement = Some(PaymentSettlement {
+            fee: max_payment_cost,
+            refund: Motes::default(),
+        });
         Ok(ExecutionResult::Failure {
             error,
             execution_journal,
             transfers,
             cost: gas_cost,
+            payment_settlement,
         })
     }
 
@@ -349,6 +397,7 @@ impl From<&ExecutionResult> for casper_types::ExecutionResult {
                 transfers,
                 cost,
                 execution_journal,
+                ..
             } => casper_types::ExecutionResult::Success {
                 effect: execution_journal.into(),
                 transfers: transfers.clone(),
@@ -359,11 +408,13 @@ impl From<&ExecutionResult> for casper_types::ExecutionResult {
                 transfers,
                 cost,
                 execution_journal,
+                ..
             } => casper_types::ExecutionResult::Failure {
                 effect: execution_journal.into(),
                 transfers: transfers.clone(),
                 cost: cost.value(),
                 error_message: error.to_string(),
+                error_code: Some(error.error_code()),
             },
         }
     }
@@ -376,6 +427,7 @@ impl From<ExecutionResult> for casper_types::ExecutionResult {
                 transfers,
                 cost,
                 execution_journal,
+                ..
             } => casper_types::ExecutionResult::Success {
                 effect: execution_journal.into(),
                 transfers,
@@ -386,11 +438,13 @@ impl From<ExecutionResult> for casper_types::ExecutionResult {
                 transfers,
                 cost,
                 execution_journal,
+                ..
             } => casper_types::ExecutionResult::Failure {
                 effect: execution_journal.into(),
                 transfers,
                 cost: cost.value(),
                 error_message: error.to_string(),
+                error_code: Some(error.error_code()),
             },
         }
     }
@@ -414,6 +468,7 @@ pub struct ExecutionResultBuilder {
     payment_execution_result: Option<ExecutionResult>,
     session_execution_result: Option<ExecutionResult>,
     finalize_execution_result: Option<ExecutionResult>,
+    payment_settlement: Option<PaymentSettlement>,
 }
 
 impl ExecutionResultBuilder {
@@ -446,6 +501,15 @@ impl ExecutionResultBuilder {
         self
     }
 
+    /// Sets how payment for the deploy was settled by the finalize execution.
+    pub fn set_payment_settlement(
+        &mut self,
+        payment_settlement: PaymentSettlement,
+    ) -> &mut ExecutionResultBuilder {
+        self.payment_settlement = Some(payment_settlement);
+        self
+    }
+
     /// Calculates the total gas cost of the execution result.
     ///
     /// Takes a payment execution result, and a session execution result and returns a sum. If
@@ -500,8 +564,7 @@ impl ExecutionResultBuilder {
             Some(ExecutionResult::Failure {
                 error: session_error,
                 transfers: session_transfers,
-                execution_journal: _,
-                cost: _,
+                ..
             }) => {
                 error = Some(session_error);
                 transfers = session_transfers;
@@ -525,17 +588,20 @@ impl ExecutionResultBuilder {
             None => return Err(ExecutionResultBuilderError::MissingFinalizeExecutionResult),
         }
 
+        let payment_settlement = self.payment_settlement;
         match error {
             None => Ok(ExecutionResult::Success {
                 transfers,
                 cost,
                 execution_journal: journal,
+                payment_settlement,
             }),
             Some(error) => Ok(ExecutionResult::Failure {
                 error,
                 transfers,
                 cost,
                 execution_journal: journal,
+                payment_settlement,
             }),
         }
     }
@@ -1,6 +1,8 @@
 //! Support for obtaining current bids from the auction system.
+use std::collections::BTreeMap;
+
 use casper_hashing::Digest;
-use casper_types::system::auction::Bids;
+use casper_types::{bytesrepr::Bytes, system::auction::Bids, PublicKey};
 
 /// Represents a request to obtain current bids in the auction system.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -29,13 +31,15 @@ pub enum GetBidsResult {
     Success {
         /// Current bids.
         bids: Bids,
+        /// Metadata set by validators for their bids.
+        bid_metadata: BTreeMap<PublicKey, Bytes>,
     },
 }
 
 impl GetBidsResult {
     /// Returns wrapped [`Bids`] if this represents a successful query result.
     pub fn into_success(self) -> Option<Bids> {
-        if let Self::Success { bids } = self {
+        if let Self::Success { bids, .. } = self {
             Some(bids)
         } else {
             None
@@ -0,0 +1,116 @@
+//! Support for obtaining parsed contracts and contract packages from global state.
+use casper_hashing::Digest;
+use casper_types::{Contract, ContractHash, ContractPackage, ContractPackageHash, EntryPoints};
+
+/// Represents a request to obtain a contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetContractRequest {
+    state_hash: Digest,
+    contract_hash: ContractHash,
+}
+
+impl GetContractRequest {
+    /// Creates new request.
+    pub fn new(state_hash: Digest, contract_hash: ContractHash) -> Self {
+        GetContractRequest {
+            state_hash,
+            contract_hash,
+        }
+    }
+
+    /// Returns state root hash.
+    pub fn state_hash(&self) -> Digest {
+        self.state_hash
+    }
+
+    /// Returns the hash of the requested contract.
+    pub fn contract_hash(&self) -> ContractHash {
+        self.contract_hash
+    }
+}
+
+/// Represents a result of a `get_contract` request.
+#[derive(Debug)]
+pub enum GetContractResult {
+    /// Invalid state root hash.
+    RootNotFound,
+    /// No value is stored under the contract hash.
+    NotFound,
+    /// Contains the contract returned from the global state.
+    Success {
+        /// The contract.
+        contract: Box<Contract>,
+    },
+}
+
+impl GetContractResult {
+    /// Returns wrapped [`Contract`] if this represents a successful query result.
+    pub fn into_success(self) -> Option<Contract> {
+        if let Self::Success { contract } = self {
+            Some(*contract)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the entry points of the contract if this represents a successful query result.
+    pub fn entry_points(&self) -> Option<&EntryPoints> {
+        if let Self::Success { contract } = self {
+            Some(contract.entry_points())
+        } else {
+            None
+        }
+    }
+}
+
+/// Represents a request to obtain a contract package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetContractPackageRequest {
+    state_hash: Digest,
+    contract_package_hash: ContractPackageHash,
+}
+
+impl GetContractPackageRequest {
+    /// Creates new request.
+    pub fn new(state_hash: Digest, contract_package_hash: ContractPackageHash) -> Self {
+        GetContractPackageRequest {
+            state_hash,
+            contract_package_hash,
+        }
+    }
+
+    /// Returns state root hash.
+    pub fn state_hash(&self) -> Digest {
+        self.state_hash
+    }
+
+    /// Returns the hash of the requested contract package.
+    pub fn contract_package_hash(&self) -> ContractPackageHash {
+        self.contract_package_hash
+    }
+}
+
+/// Represents a result of a `get_contract_package` request.
+#[derive(Debug)]
+pub enum GetContractPackageResult {
+    /// Invalid state root hash.
+    RootNotFound,
+    /// No value is stored under the contract package hash.
+    NotFound,
+    /// Contains the contract package returned from the global state.
+    Success {
+        /// The contract package.
+        contract_package: Box<ContractPackage>,
+    },
+}
+
+impl GetContractPackageResult {
+    /// Returns wrapped [`ContractPackage`] if this represents a successful query result.
+    pub fn into_success(self) -> Option<ContractPackage> {
+        if let Self::Success { contract_package } = self {
+            Some(*contract_package)
+        } else {
+            None
+        }
+    }
+}
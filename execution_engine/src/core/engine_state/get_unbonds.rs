@@ -0,0 +1,87 @@
+//! Support for obtaining the pending unbonding requests of a validator or delegator.
+use casper_hashing::Digest;
+use casper_types::{system::auction::UnbondingPurse, EraId, PublicKey, U512};
+
+/// Represents a request to obtain the pending unbonding requests made by a public key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetUnbondsRequest {
+    state_hash: Digest,
+    public_key: PublicKey,
+}
+
+impl GetUnbondsRequest {
+    /// Creates new request.
+    pub fn new(state_hash: Digest, public_key: PublicKey) -> Self {
+        GetUnbondsRequest {
+            state_hash,
+            public_key,
+        }
+    }
+
+    /// Returns state root hash.
+    pub fn state_hash(&self) -> Digest {
+        self.state_hash
+    }
+
+    /// Returns the public key whose unbonding requests are requested.
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+}
+
+/// An unbonding request which has not yet been paid out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingUnbond {
+    unbonding_purse: UnbondingPurse,
+    era_available: EraId,
+}
+
+impl PendingUnbond {
+    pub(crate) fn new(unbonding_purse: UnbondingPurse, unbonding_delay: u64) -> Self {
+        let era_available = unbonding_purse.era_of_creation() + unbonding_delay;
+        PendingUnbond {
+            unbonding_purse,
+            era_available,
+        }
+    }
+
+    /// Returns the unbonding request as stored in global state.
+    pub fn unbonding_purse(&self) -> &UnbondingPurse {
+        &self.unbonding_purse
+    }
+
+    /// Returns the amount being unbonded.
+    pub fn amount(&self) -> &U512 {
+        self.unbonding_purse.amount()
+    }
+
+    /// Returns the era in which the amount is paid out, or redelegated if the request is a
+    /// redelegation.
+    pub fn era_available(&self) -> EraId {
+        self.era_available
+    }
+}
+
+/// Represents a result of a `get_unbonds` request.
+#[derive(Debug)]
+pub enum GetUnbondsResult {
+    /// Invalid state root hash.
+    RootNotFound,
+    /// Contains the pending unbonding requests, ordered by the validator's account hash and then
+    /// from oldest to newest.
+    Success {
+        /// The pending unbonding requests.
+        pending_unbonds: Vec<PendingUnbond>,
+    },
+}
+
+impl GetUnbondsResult {
+    /// Returns the pending unbonding requests if this represents a successful query result.
+    pub fn into_success(self) -> Option<Vec<PendingUnbond>> {
+        if let Self::Success { pending_unbonds } = self {
+            Some(pending_unbonds)
+        } else {
+            None
+        }
+    }
+}
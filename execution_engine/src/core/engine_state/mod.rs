@@ -12,6 +12,8 @@ pub mod execution_effect;
 pub mod execution_result;
 pub mod genesis;
 pub mod get_bids;
+pub mod get_contract;
+pub mod get_unbonds;
 pub mod op;
 mod prune;
 pub mod query;
@@ -36,7 +38,7 @@ use tracing::{debug, error, trace, warn};
 use casper_hashing::Digest;
 use casper_types::{
     account::{Account, AccountHash},
-    bytesrepr::ToBytes,
+    bytesrepr::{Bytes, ToBytes},
     contracts::NamedKeys,
     system::{
         auction::{
@@ -64,12 +66,18 @@ pub use self::{
     },
     era_validators::{GetEraValidatorsError, GetEraValidatorsRequest},
     error::Error,
-    executable_deploy_item::{ExecutableDeployItem, ExecutableDeployItemIdentifier},
+    executable_deploy_item::{
+        ExecutableDeployItem, ExecutableDeployItemIdentifier, NativeAuctionEntryPoint,
+    },
     execute_request::ExecuteRequest,
     execution::Error as ExecError,
-    execution_result::{ExecutionResult, ForcedTransferResult},
+    execution_result::{ExecutionResult, ForcedTransferResult, PaymentSettlement},
     genesis::{ExecConfig, GenesisAccount, GenesisConfig, GenesisSuccess},
     get_bids::{GetBidsRequest, GetBidsResult},
+    get_contract::{
+        GetContractPackageRequest, GetContractPackageResult, GetContractRequest, GetContractResult,
+    },
+    get_unbonds::{GetUnbondsRequest, GetUnbondsResult, PendingUnbond},
     prune::{PruneConfig, PruneResult},
     query::{QueryRequest, QueryResult},
     run_genesis_request::RunGenesisRequest,
@@ -78,7 +86,10 @@ pub use self::{
     transfer::{TransferArgs, TransferRuntimeArgsBuilder, TransferTargetMode},
     upgrade::{UpgradeConfig, UpgradeSuccess},
 };
-use self::{engine_config::FeeHandling, transfer::NewTransferTargetMode};
+use self::{
+    engine_config::{FeeHandling, RefundHandling},
+    transfer::NewTransferTargetMode,
+};
 use crate::{
     core::{
         engine_state::{
@@ -87,20 +98,20 @@ use crate::{
             genesis::GenesisInstaller,
             upgrade::{ProtocolUpgradeError, SystemUpgrader},
         },
-        execution::{self, DirectSystemContractCall, Executor},
+        execution::{self, AddressGenerator, DirectSystemContractCall, Executor},
         runtime::RuntimeStack,
         tracking_copy::{TrackingCopy, TrackingCopyExt},
     },
     shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
     storage::{
         global_state::{
-            lmdb::LmdbGlobalState, scratch::ScratchGlobalState, CommitProvider, StateProvider,
-            StateReader,
+            lmdb::LmdbGlobalState, recording::RecordingGlobalState, scratch::ScratchGlobalState,
+            CommitProvider, StateProvider, StateReader,
         },
         trie::{merkle_proof::TrieMerkleProof, TrieRaw},
         trie_store::operations::DeleteResult,
     },
-    system::auction,
+    system::{auction, handle_payment::calculate_refund_and_fee},
 };
 
 /// The maximum amount of motes that payment code execution can cost.
@@ -132,6 +143,40 @@ impl EngineState<ScratchGlobalState> {
     pub fn into_inner(self) -> ScratchGlobalState {
         self.state
     }
+
+    /// Prunes the keys given in `prune_config` from the cached state, without writing to LMDB.
+    ///
+    /// As with [`EngineState::commit_prune`], nothing is pruned unless every key exists.  The keys
+    /// are deleted from the trie once the cached state is written, so the post-state hash returned
+    /// on success is the pre-state hash, as for any commit to cached state.
+    pub fn prune_cached(
+        &self,
+        correlation_id: CorrelationId,
+        prune_config: PruneConfig,
+    ) -> Result<PruneResult, Error> {
+        let state_root_hash = prune_config.pre_state_hash();
+        let mut tracking_copy = match self.tracking_copy(state_root_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(PruneResult::RootNotFound),
+        };
+
+        let mut effects = AdditiveMap::new();
+        for key in prune_config.keys_to_prune() {
+            if tracking_copy.read(correlation_id, key)?.is_none() {
+                return Ok(PruneResult::DoesNotExist);
+            }
+            effects.insert(*key, Transform::Prune(*key));
+        }
+        let post_state_hash = self.apply_effect(correlation_id, state_root_hash, effects)?;
+        Ok(PruneResult::Success { post_state_hash })
+    }
+}
+
+impl<S> EngineState<RecordingGlobalState<S>> {
+    /// Returns the inner state
+    pub fn into_inner(self) -> RecordingGlobalState<S> {
+        self.state
+    }
 }
 
 impl EngineState<LmdbGlobalState> {
@@ -156,16 +201,61 @@ impl EngineState<LmdbGlobalState> {
         }
     }
 
+    /// Returns the state root hash which [`EngineState::write_scratch_to_db`] would produce for
+    /// the given cached state, without writing anything to LMDB or publishing any changes.
+    pub fn compute_scratch_post_state_hash(
+        &self,
+        state_root_hash: Digest,
+        scratch_global_state: ScratchGlobalState,
+    ) -> Result<Digest, Error> {
+        let (stored_values, pruned_keys) = scratch_global_state.into_inner();
+        let post_state_hash = self.state.compute_post_state_hash(
+            CorrelationId::new(),
+            state_root_hash,
+            stored_values,
+            &pruned_keys,
+        )?;
+        Ok(post_state_hash)
+    }
+
     /// Writes state cached in an `EngineState<ScratchEngineState>` to LMDB.
     pub fn write_scratch_to_db(
         &self,
         state_root_hash: Digest,
         scratch_global_state: ScratchGlobalState,
     ) -> Result<Digest, Error> {
-        let stored_values = scratch_global_state.into_inner();
-        self.state
-            .put_stored_values(CorrelationId::new(), state_root_hash, stored_values)
-            .map_err(Into::into)
+        let correlation_id = CorrelationId::new();
+        let (stored_values, pruned_keys) = scratch_global_state.into_inner();
+        let changed_keys: Vec<Key> = if self.state.change_feed().has_sinks() {
+            stored_values
+                .keys()
+                .chain(pruned_keys.iter())
+                .copied()
+                .collect()
+        } else {
+            vec![]
+        };
+        let mut post_state_hash =
+            self.state
+                .put_stored_values(correlation_id, state_root_hash, stored_values)?;
+        for key in pruned_keys {
+            match self
+                .state
+                .delete_keys(correlation_id, post_state_hash, &[key])
+                .map_err(|error| Error::Exec(error.into()))?
+            {
+                DeleteResult::Deleted(root_hash) => post_state_hash = root_hash,
+                DeleteResult::DoesNotExist => (),
+                DeleteResult::RootNotFound => return Err(Error::RootNotFound(post_state_hash)),
+            }
+        }
+        self.state.publish_changes(
+            correlation_id,
+            state_root_hash,
+            post_state_hash,
+            changed_keys,
+        );
+        Ok(post_state_hash)
     }
 }
 
@@ -283,6 +373,15 @@ where
             return Err(Error::InvalidProtocolVersion(new_protocol_version));
         }
 
+        if let Some(new_round_seigniorage_rate) = upgrade_config.new_round_seigniorage_rate() {
+            if *new_round_seigniorage_rate.denom() == 0 {
+                error!("Round seigniorage rate has a zero denominator");
+                return Err(Error::ProtocolUpgrade(
+                    ProtocolUpgradeError::InvalidUpgradeConfig,
+                ));
+            }
+        }
+
         let registry = if let Ok(registry) = tracking_copy
             .borrow_mut()
             .get_system_contracts(correlation_id)
@@ -504,10 +603,20 @@ where
         }
     }
 
+    /// Returns `true` if the given state root is present in global state.
+    ///
+    /// Cheaper than [`EngineState::tracking_copy`] when only the presence of the root is of
+    /// interest, as neither the root nor a reader are constructed.
+    pub fn has_root(&self, state_root_hash: Digest) -> Result<bool, Error> {
+        self.state.has_root(state_root_hash).map_err(Into::into)
+    }
+
     /// Creates a new tracking copy instance.
     pub fn tracking_copy(&self, hash: Digest) -> Result<Option<TrackingCopy<S::Reader>>, Error> {
         match self.state.checkout(hash).map_err(Into::into)? {
-            Some(tc) => Ok(Some(TrackingCopy::new(tc))),
+            Some(tc) => Ok(Some(
+                TrackingCopy::new(tc).with_write_deduplication(self.config.deduplicate_writes()),
+            )),
             None => Ok(None),
         }
     }
@@ -552,14 +661,21 @@ where
         correlation_id: CorrelationId,
         mut exec_request: ExecuteRequest,
     ) -> Result<ExecutionResults, Error> {
+        if !self.has_root(exec_request.parent_state_hash)? {
+            return Err(Error::RootNotFound(exec_request.parent_state_hash));
+        }
+
         let executor = Executor::new(self.config().clone());
 
         let deploys = exec_request.take_deploys();
         let mut results = ExecutionResults::with_capacity(deploys.len());
 
         for deploy_item in deploys {
+            let account_hash = deploy_item.address;
+            let gas_price = deploy_item.gas_price;
             let result = match deploy_item.session {
-                ExecutableDeployItem::Transfer { .. } => self.transfer(
+                ExecutableDeployItem::Transfer { .. }
+                | ExecutableDeployItem::MultiTransfer { .. } => self.transfer(
                     correlation_id,
                     &executor,
                     exec_request.protocol_version,
@@ -580,6 +696,27 @@ where
             };
             match result {
                 Ok(result) => results.push_back(result),
+                Err(error) if self.config.isolate_deploy_failures() && error.is_deterministic() => {
+                    // Every node hits this error alike, so charge for the deploy as for an error in
+                    // its payment code rather than aborting execution of the rest of the block.
+                    error!(%error, "deploy execution failed with an internal error");
+                    let result = match self.charge_for_isolated_failure(
+                        correlation_id,
+                        exec_request.parent_state_hash,
+                        account_hash,
+                        gas_price,
+                        exec_request.proposer.clone(),
+                        error.clone(),
+                    ) {
+                        Ok(result) => result,
+                        // The deploy can't be charged for, e.g. as its account doesn't exist.
+                        Err(charge_error) if charge_error.is_deterministic() => {
+                            ExecutionResult::precondition_failure(error)
+                        }
+                        Err(charge_error) => return Err(charge_error),
+                    };
+                    results.push_back(result);
+                }
                 Err(error) => {
                     return Err(error);
                 }
@@ -589,6 +726,43 @@ where
         Ok(results)
     }
 
+    /// Creates the result of a deploy whose execution hit the given deterministic error, charging
+    /// its account `MAX_PAYMENT` as for an error in its payment code.
+    fn charge_for_isolated_failure(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Digest,
+        account_hash: AccountHash,
+        gas_price: u64,
+        proposer: PublicKey,
+        error: Error,
+    ) -> Result<ExecutionResult, Error> {
+        let mut tracking_copy = self
+            .tracking_copy(prestate_hash)?
+            .ok_or(Error::RootNotFound(prestate_hash))?;
+        let account = tracking_copy.get_account(correlation_id, account_hash)?;
+        let account_main_purse_balance_key =
+            tracking_copy.get_purse_balance_key(correlation_id, account.main_purse().into())?;
+        let account_main_purse_balance =
+            tracking_copy.get_purse_balance(correlation_id, account_main_purse_balance_key)?;
+        let rewards_target_purse =
+            self.get_rewards_purse(correlation_id, proposer, prestate_hash)?;
+        let rewards_target_purse_balance_key =
+            tracking_copy.get_purse_balance_key(correlation_id, rewards_target_purse.into())?;
+
+        let max_payment_cost = Motes::new(*MAX_PAYMENT);
+        let gas_cost =
+            Gas::from_motes(max_payment_cost, gas_price).ok_or(Error::GasConversionOverflow)?;
+        ExecutionResult::new_payment_code_error(
+            error,
+            max_payment_cost,
+            account_main_purse_balance,
+            gas_cost,
+            account_main_purse_balance_key,
+            rewards_target_purse_balance_key,
+        )
+    }
+
     fn get_authorized_account(
         &self,
         correlation_id: CorrelationId,
@@ -651,6 +825,9 @@ where
     /// Native transfers do not involve WASM at all, and also skip executing payment code.
     /// Therefore this is the fastest and cheapest way to transfer tokens from account to account.
     ///
+    /// A [`ExecutableDeployItem::MultiTransfer`] makes several such transfers out of the same
+    /// purse, for the cost of a native transfer each. Either all of them are made or none is.
+    ///
     /// Returns an [`ExecutionResult`] for a successful native transfer.
     #[allow(clippy::too_many_arguments)]
     pub fn transfer(
@@ -717,9 +894,18 @@ where
 
         let gas_limit = Gas::new(U512::from(std::u64::MAX));
 
-        let wasmless_transfer_gas_cost = Gas::new(U512::from(
-            self.config().system_config().wasmless_transfer_cost(),
-        ));
+        let transfer_entries = deploy_item.session.transfer_args().to_vec();
+        if transfer_entries.is_empty() {
+            return Ok(ExecutionResult::precondition_failure(
+                Error::InvalidDeployItemVariant("MultiTransfer without transfers".to_string()),
+            ));
+        }
+
+        // Each transfer of a batch costs as much as a single native transfer.
+        let wasmless_transfer_gas_cost = Gas::new(
+            U512::from(self.config().system_config().wasmless_transfer_cost())
+                * U512::from(transfer_entries.len()),
+        );
 
         let wasmless_transfer_motes = match Motes::from_gas(
             wasmless_transfer_gas_cost,
@@ -791,111 +977,157 @@ where
         // All wasmless transfer preconditions are met.
         // Any error that occurs in logic below this point would result in a charge for user error.
 
-        let mut runtime_args_builder =
-            TransferRuntimeArgsBuilder::new(deploy_item.session.args().clone());
-
-        let transfer_target_mode = match runtime_args_builder
-            .resolve_transfer_target_mode(correlation_id, Rc::clone(&tracking_copy))
-        {
-            Ok(transfer_target_mode) => transfer_target_mode,
-            Err(error) => return Ok(make_charged_execution_failure(error)),
+        // A single transfer generates addresses with a new generator for each system contract call,
+        // while the calls made for a batch of transfers share one, so that they don't generate the
+        // same purses and transfer addresses.
+        let deploy_hash = deploy_item.deploy_hash;
+        let shared_address_generator = match deploy_item.session {
+            ExecutableDeployItem::MultiTransfer { .. } => Some(Rc::new(RefCell::new(
+                AddressGenerator::new(deploy_hash.as_bytes(), Phase::Session),
+            ))),
+            _ => None,
+        };
+        let session_address_generator = || match &shared_address_generator {
+            Some(address_generator) => Rc::clone(address_generator),
+            None => Rc::new(RefCell::new(AddressGenerator::new(
+                deploy_hash.as_bytes(),
+                Phase::Session,
+            ))),
         };
 
-        // At this point we know target refers to either a purse on an existing account or an
-        // account which has to be created.
+        // Targets are resolved, and target accounts created, one transfer at a time, so that later
+        // transfers of a batch find the accounts created for earlier ones.
+        let mut transfer_args_list = Vec::with_capacity(transfer_entries.len());
+        for runtime_args in transfer_entries {
+            let mut runtime_args_builder = TransferRuntimeArgsBuilder::new(runtime_args);
 
-        if !self.config.allow_unrestricted_transfers()
-            && !self.config.is_administrator(&account_hash)
-        {
-            // We need to make sure that source or target has to be admin.
-            match transfer_target_mode {
-                NewTransferTargetMode::ExistingAccount {
-                    target_account_hash,
-                    ..
-                }
-                | NewTransferTargetMode::CreateAccount(target_account_hash) => {
-                    let is_target_system_account =
-                        target_account_hash == PublicKey::System.to_account_hash();
-                    let is_target_administrator =
-                        self.config.is_administrator(&target_account_hash);
-                    if !(is_target_system_account || is_target_administrator) {
-                        // Transferring from normal account to a purse doesn't work.
+            let transfer_target_mode = match runtime_args_builder
+                .resolve_transfer_target_mode(correlation_id, Rc::clone(&tracking_copy))
+            {
+                Ok(transfer_target_mode) => transfer_target_mode,
+                Err(error) => return Ok(make_charged_execution_failure(error)),
+            };
+
+            // At this point we know target refers to either a purse on an existing account or an
+            // account which has to be created.
+
+            if !self.config.allow_unrestricted_transfers()
+                && !self.config.is_administrator(&account_hash)
+            {
+                // We need to make sure that source or target has to be admin.
+                match transfer_target_mode {
+                    NewTransferTargetMode::ExistingAccount {
+                        target_account_hash,
+                        ..
+                    }
+                    | NewTransferTargetMode::CreateAccount(target_account_hash) => {
+                        let is_target_system_account =
+                            target_account_hash == PublicKey::System.to_account_hash();
+                        let is_target_administrator =
+                            self.config.is_administrator(&target_account_hash);
+                        if !(is_target_system_account || is_target_administrator) {
+                            // Transferring from normal account to a purse doesn't work.
+                            return Ok(make_charged_execution_failure(
+                                execution::Error::DisabledUnrestrictedTransfers.into(),
+                            ));
+                        }
+                    }
+                    NewTransferTargetMode::PurseExists(_) => {
+                        // We don't know who is the target and we can't simply reverse search
+                        // account/contract that owns it. We also can't know if purse is owned
+                        // exactly by one entity in the system.
                         return Ok(make_charged_execution_failure(
                             execution::Error::DisabledUnrestrictedTransfers.into(),
                         ));
                     }
                 }
-                NewTransferTargetMode::PurseExists(_) => {
-                    // We don't know who is the target and we can't simply reverse search
-                    // account/contract that owns it. We also can't know if purse is owned exactly
-                    // by one entity in the system.
-                    return Ok(make_charged_execution_failure(
-                        execution::Error::DisabledUnrestrictedTransfers.into(),
-                    ));
-                }
             }
-        }
 
-        match transfer_target_mode {
-            NewTransferTargetMode::ExistingAccount { .. }
-            | NewTransferTargetMode::PurseExists(_) => {
-                // Noop
-            }
-            NewTransferTargetMode::CreateAccount(account_hash) => {
-                let create_purse_stack = self.get_new_system_call_stack();
-                let (maybe_uref, execution_result): (Option<URef>, ExecutionResult) = executor
-                    .call_system_contract(
-                        DirectSystemContractCall::CreatePurse,
-                        RuntimeArgs::new(), // mint create takes no arguments
-                        &account,
-                        authorization_keys.clone(),
-                        blocktime,
-                        deploy_item.deploy_hash,
-                        gas_limit,
-                        protocol_version,
-                        correlation_id,
-                        Rc::clone(&tracking_copy),
-                        Phase::Session,
-                        create_purse_stack,
-                        // We're just creating a purse.
-                        U512::zero(),
-                    );
-                match maybe_uref {
-                    Some(main_purse) => {
-                        let new_account = {
-                            let named_keys = NamedKeys::default();
-                            Account::create(account_hash, named_keys, main_purse)
-                        };
-                        // write new account
-                        tracking_copy.borrow_mut().write(
-                            Key::Account(account_hash),
-                            StoredValue::Account(new_account),
+            match transfer_target_mode {
+                NewTransferTargetMode::ExistingAccount { .. }
+                | NewTransferTargetMode::PurseExists(_) => {
+                    // Noop
+                }
+                NewTransferTargetMode::CreateAccount(account_hash) => {
+                    let create_purse_stack = self.get_new_system_call_stack();
+                    let (maybe_uref, execution_result): (Option<URef>, ExecutionResult) = executor
+                        .call_system_contract_with_address_generator(
+                            DirectSystemContractCall::CreatePurse,
+                            RuntimeArgs::new(), // mint create takes no arguments
+                            &account,
+                            authorization_keys.clone(),
+                            blocktime,
+                            deploy_hash,
+                            gas_limit,
+                            protocol_version,
+                            correlation_id,
+                            Rc::clone(&tracking_copy),
+                            Phase::Session,
+                            session_address_generator(),
+                            create_purse_stack,
+                            // We're just creating a purse.
+                            U512::zero(),
                         );
-                    }
-                    None => {
-                        // This case implies that the execution_result is a failure variant as
-                        // implemented inside host_exec().
-                        let error = execution_result
-                            .take_error()
-                            .unwrap_or(Error::InsufficientPayment);
-                        return Ok(make_charged_execution_failure(error));
+                    match maybe_uref {
+                        Some(main_purse) => {
+                            let new_account = {
+                                let named_keys = NamedKeys::default();
+                                Account::create(account_hash, named_keys, main_purse)
+                            };
+                            // write new account
+                            tracking_copy.borrow_mut().write(
+                                Key::Account(account_hash),
+                                StoredValue::Account(new_account),
+                            );
+                        }
+                        None => {
+                            // This case implies that the execution_result is a failure variant as
+                            // implemented inside host_exec().
+                            let error = execution_result
+                                .take_error()
+                                .unwrap_or(Error::InsufficientPayment);
+                            return Ok(make_charged_execution_failure(error));
+                        }
                     }
                 }
             }
-        }
 
-        let transfer_args =
-            match runtime_args_builder.build(&account, correlation_id, Rc::clone(&tracking_copy)) {
+            let transfer_args = match runtime_args_builder.build(
+                &account,
+                correlation_id,
+                Rc::clone(&tracking_copy),
+            ) {
                 Ok(transfer_args) => transfer_args,
                 Err(error) => return Ok(make_charged_execution_failure(error)),
             };
+            transfer_args_list.push(transfer_args);
+        }
+
+        // All transfers of a batch are paid for from, and must be made from, the same purse.
+        let source_uref = transfer_args_list[0].source();
+        if transfer_args_list
+            .iter()
+            .any(|transfer_args| transfer_args.source() != source_uref)
+        {
+            return Ok(make_charged_execution_failure(Error::Exec(
+                ExecError::Revert(ApiError::InvalidPurse),
+            )));
+        }
+        let total_transfer_amount = match transfer_args_list
+            .iter()
+            .try_fold(U512::zero(), |total, transfer_args| {
+                total.checked_add(transfer_args.amount())
+            }) {
+            Some(total_transfer_amount) => total_transfer_amount,
+            // When trying to send too much that could cause an overflow.
+            None => return Ok(make_charged_execution_failure(Error::InsufficientPayment)),
+        };
 
         let payment_uref;
 
         // Construct a payment code that will put cost of wasmless payment into payment purse
         let payment_result = {
             // Check source purses minimum balance
-            let source_uref = transfer_args.source();
             let source_purse_balance = if source_uref != account_main_purse {
                 let source_purse_balance_key = match tracking_copy
                     .borrow_mut()
@@ -917,7 +1149,7 @@ where
                 account_main_purse_balance
             };
 
-            let transfer_amount_motes = Motes::new(transfer_args.amount());
+            let transfer_amount_motes = Motes::new(total_transfer_amount);
 
             match wasmless_transfer_motes.checked_add(transfer_amount_motes) {
                 Some(total_amount) if source_purse_balance < total_amount => {
@@ -929,6 +1161,17 @@ where
                     // When trying to send too much that could cause an overflow.
                     return Ok(make_charged_execution_failure(Error::InsufficientPayment));
                 }
+                Some(total_amount)
+                    if source_uref == account_main_purse
+                        && (source_purse_balance - total_amount).value()
+                            < U512::from(self.config.minimum_remaining_balance()) =>
+                {
+                    // The mint would reject the transfer out of the main purse after the payment
+                    // has been taken, so we fail early with the same error.
+                    return Ok(make_charged_execution_failure(Error::Exec(
+                        ExecError::Revert(mint::Error::InsufficientRemainingBalance.into()),
+                    )));
+                }
                 Some(_) => {}
             }
 
@@ -963,11 +1206,11 @@ where
             // Create a new arguments to transfer cost of wasmless transfer into the payment purse.
 
             let new_transfer_args = TransferArgs::new(
-                transfer_args.to(),
-                transfer_args.source(),
+                transfer_args_list[0].to(),
+                source_uref,
                 payment_uref,
                 wasmless_transfer_motes.value(),
-                transfer_args.arg_id(),
+                transfer_args_list[0].arg_id(),
             );
 
             let runtime_args = match RuntimeArgs::try_from(new_transfer_args) {
@@ -1052,34 +1295,67 @@ where
             payment_result.with_cost(payment_gas)
         };
 
-        let runtime_args = match RuntimeArgs::try_from(transfer_args) {
-            Ok(runtime_args) => runtime_args,
-            Err(error) => {
-                return Ok(make_charged_execution_failure(
-                    ExecError::from(error).into(),
-                ))
+        let is_batch = shared_address_generator.is_some();
+        let mut transfers = Vec::new();
+        let mut maybe_session_result = None;
+        for transfer_args in transfer_args_list {
+            let runtime_args = match RuntimeArgs::try_from(transfer_args) {
+                Ok(runtime_args) => runtime_args,
+                Err(error) => {
+                    return Ok(make_charged_execution_failure(
+                        ExecError::from(error).into(),
+                    ))
+                }
+            };
+
+            let transfer_stack = self.get_new_system_call_stack();
+            let (transfer_result, session_result): (Option<Result<(), u8>>, ExecutionResult) =
+                executor.call_system_contract_with_address_generator(
+                    DirectSystemContractCall::Transfer,
+                    runtime_args,
+                    &account,
+                    authorization_keys.clone(),
+                    blocktime,
+                    deploy_hash,
+                    gas_limit,
+                    protocol_version,
+                    correlation_id,
+                    Rc::clone(&tracking_copy),
+                    Phase::Session,
+                    session_address_generator(),
+                    transfer_stack,
+                    // We limit native transfer to the amount that user signed over as `amount`
+                    // argument.
+                    transfer_args.amount(),
+                );
+
+            if is_batch {
+                // A batch of transfers is atomic: if any of them fails, the effects of all of them
+                // are discarded and the user is charged as for any other user error.
+                if let Some(error) = session_result.as_error().cloned() {
+                    return Ok(make_charged_execution_failure(error));
+                }
+                let api_error = match transfer_result {
+                    Some(Ok(())) => None,
+                    Some(Err(mint_error)) => match mint::Error::try_from(mint_error) {
+                        Ok(mint_error) => Some(ApiError::from(mint_error)),
+                        Err(_) => Some(ApiError::Transfer),
+                    },
+                    None => Some(ApiError::Transfer),
+                };
+                if let Some(api_error) = api_error {
+                    return Ok(make_charged_execution_failure(Error::Exec(
+                        ExecError::Revert(api_error),
+                    )));
+                }
             }
-        };
 
-        let transfer_stack = self.get_new_system_call_stack();
-        let (_, mut session_result): (Option<Result<(), u8>>, ExecutionResult) = executor
-            .call_system_contract(
-                DirectSystemContractCall::Transfer,
-                runtime_args,
-                &account,
-                authorization_keys.clone(),
-                blocktime,
-                deploy_item.deploy_hash,
-                gas_limit,
-                protocol_version,
-                correlation_id,
-                Rc::clone(&tracking_copy),
-                Phase::Session,
-                transfer_stack,
-                // We limit native transfer to the amount that user signed over as `amount`
-                // argument.
-                transfer_args.amount(),
-            );
+            transfers.extend(session_result.transfers().iter().copied());
+            maybe_session_result = Some(session_result);
+        }
+        let mut session_result = maybe_session_result
+            .expect("should have at least one transfer")
+            .with_transfers(transfers);
 
         // User is already charged fee for wasmless contract, and we need to make sure we will not
         // charge for anything that happens while calling transfer entrypoint.
@@ -1134,7 +1410,7 @@ where
                     Phase::FinalizePayment,
                     finalize_payment_stack,
                     // Spending limit is cost of wasmless execution.
-                    U512::from(self.config().system_config().wasmless_transfer_cost()),
+                    wasmless_transfer_motes.value(),
                 );
 
             finalize_result
@@ -1165,6 +1441,11 @@ where
         execution_result_builder.set_payment_execution_result(payment_result);
         execution_result_builder.set_session_execution_result(session_result);
         execution_result_builder.set_finalize_execution_result(finalize_result);
+        // The payment purse holds exactly the cost of the transfer, so nothing is refunded.
+        execution_result_builder.set_payment_settlement(PaymentSettlement {
+            fee: wasmless_transfer_motes,
+            refund: Motes::default(),
+        });
 
         let execution_result = execution_result_builder
             .build()
@@ -1173,6 +1454,60 @@ where
         Ok(execution_result)
     }
 
+    /// Checks the entry points targeted by a deploy's session and custom payment code, as is done
+    /// before executing it if the entry point precheck is enabled, without executing or charging
+    /// for the deploy.
+    ///
+    /// Intended for speculative execution, where a deploy failing the check can be rejected at no
+    /// cost.  When executed by [`EngineState::deploy`], a deploy failing the check is charged like
+    /// one whose payment code failed.  Returns `Ok(())` if the precheck is disabled.
+    pub fn precheck_deploy(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        prestate_hash: Digest,
+        deploy_item: &DeployItem,
+    ) -> Result<(), Error> {
+        if !self.config.entry_point_precheck() || deploy_item.session.is_transfer() {
+            return Ok(());
+        }
+
+        let tracking_copy = match self.tracking_copy(prestate_hash)? {
+            Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+            None => return Err(Error::RootNotFound(prestate_hash)),
+        };
+        let account = self.get_authorized_account(
+            correlation_id,
+            deploy_item.address,
+            &deploy_item.authorization_keys,
+            Rc::clone(&tracking_copy),
+        )?;
+
+        for (executable_deploy_item, phase) in [
+            (&deploy_item.session, Phase::Session),
+            (&deploy_item.payment, Phase::Payment),
+        ] {
+            if executable_deploy_item.is_standard_payment(phase) {
+                continue;
+            }
+            let execution_kind = ExecutionKind::new(
+                Rc::clone(&tracking_copy),
+                account.named_keys(),
+                executable_deploy_item.clone(),
+                correlation_id,
+                &protocol_version,
+                phase,
+            )?;
+            execution_kind.precheck_entry_point(
+                Rc::clone(&tracking_copy),
+                correlation_id,
+                executable_deploy_item.args(),
+                self.config.strict_argument_checking(),
+            )?;
+        }
+        Ok(())
+    }
+
     /// Executes a deploy.
     ///
     /// A deploy execution consists of running the payment code, which is expected to deposit funds
@@ -1257,6 +1592,20 @@ where
                 return Ok(ExecutionResult::precondition_failure(error));
             }
         };
+        // A failed precheck is charged for like a payment code error once the purses to charge
+        // are known, below.
+        let session_precheck_error = if self.config.entry_point_precheck() {
+            session_execution_kind
+                .precheck_entry_point(
+                    Rc::clone(&tracking_copy),
+                    correlation_id,
+                    &session_args,
+                    self.config.strict_argument_checking(),
+                )
+                .err()
+        } else {
+            None
+        };
 
         // Get account main purse balance key
         // validation_spec_5: account main purse minimum balance
@@ -1360,6 +1709,21 @@ where
             }
         };
 
+        // Function below creates an ExecutionResult charging `max_payment_cost` for a deploy
+        // rejected by the entry point precheck, as for errors in the payment code.
+        let make_charged_precheck_failure =
+            |error, gas_cost| match ExecutionResult::new_payment_code_error(
+                error,
+                max_payment_cost,
+                account_main_purse_balance,
+                gas_cost,
+                account_main_purse_balance_key,
+                rewards_target_purse_balance_key,
+            ) {
+                Ok(execution_result) => execution_result,
+                Err(error) => ExecutionResult::precondition_failure(error),
+            };
+
         // [`ExecutionResultBuilder`] handles merging of multiple execution results
         let mut execution_result_builder = execution_result::ExecutionResultBuilder::new();
 
@@ -1376,6 +1740,10 @@ where
                 }
             };
 
+            if let Some(error) = session_precheck_error {
+                return Ok(make_charged_precheck_failure(error, payment_gas_limit));
+            }
+
             // Create payment code module from bytes
             // validation_spec_1: valid wasm bytes
             let phase = Phase::Payment;
@@ -1424,6 +1792,16 @@ where
                         return Ok(ExecutionResult::precondition_failure(error));
                     }
                 };
+                if self.config.entry_point_precheck() {
+                    if let Err(error) = payment_execution_kind.precheck_entry_point(
+                        Rc::clone(&tracking_copy),
+                        correlation_id,
+                        &payment_args,
+                        self.config.strict_argument_checking(),
+                    ) {
+                        return Ok(make_charged_precheck_failure(error, payment_gas_limit));
+                    }
+                }
                 executor.exec(
                     payment_execution_kind,
                     payment_args,
@@ -1643,6 +2021,24 @@ where
                     }
                 };
 
+                // Work out the fee and refund the same way the handle payment contract does when
+                // finalizing payment, so that they can be reported.  If this fails, so does the
+                // finalization.
+                if let Ok((refund, fee)) = calculate_refund_and_fee(
+                    finalize_cost_motes.value(),
+                    payment_purse_balance.value(),
+                    self.config.refund_handling(),
+                ) {
+                    let refund = match self.config.refund_handling() {
+                        RefundHandling::Refund { .. } => refund,
+                        RefundHandling::Burn { .. } => U512::zero(),
+                    };
+                    execution_result_builder.set_payment_settlement(PaymentSettlement {
+                        fee: Motes::new(fee),
+                        refund: Motes::new(refund),
+                    });
+                }
+
                 let maybe_runtime_args = RuntimeArgs::try_new(|args| {
                     args.insert(handle_payment::ARG_AMOUNT, finalize_cost_motes.value())?;
                     args.insert(handle_payment::ARG_ACCOUNT, account.account_hash())?;
@@ -1907,16 +2303,119 @@ where
             .map_err(|err| Error::Exec(err.into()))?;
 
         let mut bids = BTreeMap::new();
+        let mut bid_metadata = BTreeMap::new();
 
         for key in bid_keys.iter() {
             if let Some(StoredValue::Bid(bid)) =
                 tracking_copy.get(correlation_id, key).map_err(Into::into)?
             {
+                if let Key::Bid(account_hash) = key {
+                    if let Some(StoredValue::CLValue(cl_value)) = tracking_copy
+                        .get(correlation_id, &Key::BidMetadata(*account_hash))
+                        .map_err(Into::into)?
+                    {
+                        let metadata: Bytes = cl_value.into_t().map_err(execution::Error::from)?;
+                        bid_metadata.insert(bid.validator_public_key().clone(), metadata);
+                    }
+                }
                 bids.insert(bid.validator_public_key().clone(), *bid);
             };
         }
 
-        Ok(GetBidsResult::Success { bids })
+        Ok(GetBidsResult::Success { bids, bid_metadata })
+    }
+
+    /// Gets the pending unbonding requests made by a validator or delegator.
+    pub fn get_unbonds(
+        &self,
+        correlation_id: CorrelationId,
+        get_unbonds_request: GetUnbondsRequest,
+    ) -> Result<GetUnbondsResult, Error> {
+        let state_hash = get_unbonds_request.state_hash();
+        let mut tracking_copy = match self.tracking_copy(state_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(GetUnbondsResult::RootNotFound),
+        };
+
+        let auction_hash = self.get_system_auction_hash(correlation_id, state_hash)?;
+        let auction_contract = tracking_copy.get_contract(correlation_id, auction_hash)?;
+        let unbonding_delay_key = auction_contract
+            .named_keys()
+            .get(UNBONDING_DELAY_KEY)
+            .copied()
+            .ok_or_else(|| execution::Error::NamedKeyNotFound(UNBONDING_DELAY_KEY.to_string()))?;
+        let unbonding_delay: u64 = match tracking_copy
+            .get(correlation_id, &unbonding_delay_key)
+            .map_err(Into::into)?
+        {
+            Some(StoredValue::CLValue(cl_value)) => {
+                cl_value.into_t().map_err(execution::Error::from)?
+            }
+            _ => return Err(execution::Error::KeyNotFound(unbonding_delay_key).into()),
+        };
+
+        let unbond_keys = tracking_copy
+            .get_keys(correlation_id, &KeyTag::Unbond)
+            .map_err(|err| Error::Exec(err.into()))?;
+
+        let mut pending_unbonds = Vec::new();
+        for key in unbond_keys.iter() {
+            if let Some(StoredValue::Unbonding(unbonding_purses)) =
+                tracking_copy.get(correlation_id, key).map_err(Into::into)?
+            {
+                for unbonding_purse in unbonding_purses {
+                    if unbonding_purse.unbonder_public_key() == get_unbonds_request.public_key() {
+                        pending_unbonds.push(PendingUnbond::new(unbonding_purse, unbonding_delay));
+                    }
+                }
+            }
+        }
+
+        Ok(GetUnbondsResult::Success { pending_unbonds })
+    }
+
+    /// Gets a contract from global state.
+    pub fn get_contract(
+        &self,
+        correlation_id: CorrelationId,
+        get_contract_request: GetContractRequest,
+    ) -> Result<GetContractResult, Error> {
+        let mut tracking_copy = match self.tracking_copy(get_contract_request.state_hash())? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(GetContractResult::RootNotFound),
+        };
+
+        match tracking_copy.get_contract(correlation_id, get_contract_request.contract_hash()) {
+            Ok(contract) => Ok(GetContractResult::Success {
+                contract: Box::new(contract),
+            }),
+            Err(execution::Error::KeyNotFound(_)) => Ok(GetContractResult::NotFound),
+            Err(error) => Err(Error::Exec(error)),
+        }
+    }
+
+    /// Gets a contract package from global state.
+    pub fn get_contract_package(
+        &self,
+        correlation_id: CorrelationId,
+        get_contract_package_request: GetContractPackageRequest,
+    ) -> Result<GetContractPackageResult, Error> {
+        let mut tracking_copy =
+            match self.tracking_copy(get_contract_package_request.state_hash())? {
+                Some(tracking_copy) => tracking_copy,
+                None => return Ok(GetContractPackageResult::RootNotFound),
+            };
+
+        match tracking_copy.get_contract_package(
+            correlation_id,
+            get_contract_package_request.contract_package_hash(),
+        ) {
+            Ok(contract_package) => Ok(GetContractPackageResult::Success {
+                contract_package: Box::new(contract_package),
+            }),
+            Err(execution::Error::KeyNotFound(_)) => Ok(GetContractPackageResult::NotFound),
+            Err(error) => Err(Error::Exec(error)),
+        }
     }
 
     /// Executes a step request.
@@ -2282,6 +2781,7 @@ fn log_execution_result(preamble: &'static str, result: &ExecutionResult) {
             transfers,
             cost,
             execution_journal,
+            ..
         } => {
             debug!(
                 %cost,
@@ -2296,6 +2796,7 @@ fn log_execution_result(preamble: &'static str, result: &ExecutionResult) {
             transfers,
             cost,
             execution_journal,
+            ..
         } => {
             debug!(
                 %error,
@@ -2311,12 +2812,7 @@ fn log_execution_result(preamble: &'static str, result: &ExecutionResult) {
 
 fn should_charge_for_errors_in_wasm(execution_result: &ExecutionResult) -> bool {
     match execution_result {
-        ExecutionResult::Failure {
-            error,
-            transfers: _,
-            cost: _,
-            execution_journal: _,
-        } => match error {
+        ExecutionResult::Failure { error, .. } => match error {
             Error::Exec(err) => match err {
                 ExecError::WasmPreprocessing(_) | ExecError::UnsupportedWasmStart => true,
                 ExecError::Storage(_)
@@ -2394,7 +2890,8 @@ fn should_charge_for_errors_in_wasm(execution_result: &ExecutionResult) -> bool
             | Error::FailedToRetrieveUnbondingDelay
             | Error::FailedToRetrieveEraId
             | Error::MissingTrieNodeChildren(_)
-            | Error::FailedToRetrieveAccumulationPurse => false,
+            | Error::FailedToRetrieveAccumulationPurse
+            | Error::MissingEntryPoint { .. } => false,
         },
         ExecutionResult::Success { .. } => false,
     }
@@ -1,4 +1,19 @@
 //! Generates unique 32-byte addresses.
+//!
+//! The addresses of all [`URef`]s, contracts and transfers created while executing a deploy are
+//! drawn from a deterministic stream, so every node executing the same deploy creates the same
+//! addresses.  An [`AddressGenerator`] created via [`AddressGenerator::new`] is seeded as follows:
+//!
+//! 1. the seed bytes are the 32-byte deploy hash followed by the single byte of the execution
+//!    [`Phase`] (`Phase as u8`),
+//! 2. the seed bytes are hashed with blake2b256 and the digest seeds a ChaCha20 PRNG,
+//! 3. the `n`th generated address (counting from 0) is the `n`th 32-byte block of the PRNG's
+//!    output.
+//!
+//! Since the phase is part of the seed, the payment, session and finalization phases of a single
+//! deploy draw from independent streams which cannot collide.
+//! [`AddressGenerator::predict_address`] and [`AddressGenerator::predict_hash_address`] compute the
+//! address at a given position of a stream without executing the deploy.
 use rand::{RngCore, SeedableRng};
 use rand_chacha::ChaChaRng;
 
@@ -10,21 +25,50 @@ use crate::core::{Address, ADDRESS_LENGTH};
 const SEED_LENGTH: usize = 32;
 
 /// An `AddressGenerator` generates `URef` addresses.
-pub struct AddressGenerator(ChaChaRng);
+pub struct AddressGenerator {
+    rng: ChaChaRng,
+    phase: Option<Phase>,
+}
 
 impl AddressGenerator {
     /// Creates an [`AddressGenerator`] from a 32-byte hash digest and [`Phase`].
     pub fn new(hash: &[u8], phase: Phase) -> AddressGenerator {
-        AddressGeneratorBuilder::new()
+        let mut generator = AddressGeneratorBuilder::new()
             .seed_with(hash)
             .seed_with(&[phase as u8])
-            .build()
+            .build();
+        generator.phase = Some(phase);
+        generator
+    }
+
+    /// Returns the address which an [`AddressGenerator`] created with the given hash and [`Phase`]
+    /// returns from its `index`th call (counting from 0) to
+    /// [`create_address`](AddressGenerator::create_address).
+    pub fn predict_address(hash: &[u8], phase: Phase, index: usize) -> Address {
+        let mut generator = AddressGenerator::new(hash, phase);
+        for _ in 0..index {
+            generator.create_address();
+        }
+        generator.create_address()
+    }
+
+    /// Returns the address which an [`AddressGenerator`] created with the given hash and [`Phase`]
+    /// returns from [`new_hash_address`](AddressGenerator::new_hash_address) when it is the
+    /// `index`th address drawn from the generator (counting from 0).
+    pub fn predict_hash_address(hash: &[u8], phase: Phase, index: usize) -> Address {
+        Digest::hash(Self::predict_address(hash, phase, index)).value()
+    }
+
+    /// Returns the [`Phase`] this generator was seeded with, or `None` if it was created via an
+    /// [`AddressGeneratorBuilder`].
+    pub fn phase(&self) -> Option<Phase> {
+        self.phase
     }
 
     /// Creates a new [`Address`] by using an internal instance of PRNG.
     pub fn create_address(&mut self) -> Address {
         let mut buff = [0u8; ADDRESS_LENGTH];
-        self.0.fill_bytes(&mut buff);
+        self.rng.fill_bytes(&mut buff);
         buff
     }
 
@@ -64,7 +108,10 @@ impl AddressGeneratorBuilder {
     /// This method hashes the seed bytes, and seeds the PRNG with it.
     pub fn build(self) -> AddressGenerator {
         let seed: [u8; SEED_LENGTH] = Digest::hash(self.data).value();
-        AddressGenerator(ChaChaRng::from_seed(seed))
+        AddressGenerator {
+            rng: ChaChaRng::from_seed(seed),
+            phase: None,
+        }
     }
 }
 
@@ -116,4 +163,25 @@ mod tests {
             "different phase should have different output"
         );
     }
+
+    #[test]
+    fn should_predict_generated_addresses() {
+        let mut generator = AddressGenerator::new(&DEPLOY_HASH_1, Phase::Session);
+        let first = generator.create_address();
+        let second = generator.new_hash_address();
+
+        assert_eq!(
+            AddressGenerator::predict_address(&DEPLOY_HASH_1, Phase::Session, 0),
+            first
+        );
+        assert_eq!(
+            AddressGenerator::predict_hash_address(&DEPLOY_HASH_1, Phase::Session, 1),
+            second
+        );
+        assert_ne!(
+            AddressGenerator::predict_address(&DEPLOY_HASH_1, Phase::Payment, 0),
+            first
+        );
+        assert_eq!(generator.phase(), Some(Phase::Session));
+    }
 }
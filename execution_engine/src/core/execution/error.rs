@@ -5,7 +5,8 @@ use thiserror::Error;
 use casper_types::{
     account::{AddKeyFailure, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure},
     bytesrepr, system, AccessRights, ApiError, CLType, CLValueError, ContractHash,
-    ContractPackageHash, ContractVersionKey, ContractWasmHash, Key, StoredValueTypeMismatch, URef,
+    ContractPackageHash, ContractVersionKey, ContractWasmHash, ErrorCategory, ExecutionErrorCode,
+    Key, StoredValueTypeMismatch, URef,
 };
 
 use crate::{
@@ -198,6 +199,133 @@ impl Error {
             format!("{:?}", found),
         ))
     }
+
+    /// Returns the stable error code identifying this error.
+    ///
+    /// Errors which are visible to the executed code as an [`ApiError`] are reported as reverts
+    /// carrying that [`ApiError`]'s value.
+    pub fn error_code(&self) -> ExecutionErrorCode {
+        let (category, code) = match self {
+            Error::Revert(api_error) => return ExecutionErrorCode::from(*api_error),
+            Error::AddKeyFailure(failure) => return ApiError::from(*failure).into(),
+            Error::RemoveKeyFailure(failure) => return ApiError::from(*failure).into(),
+            Error::UpdateKeyFailure(failure) => return ApiError::from(*failure).into(),
+            Error::SetThresholdFailure(failure) => return ApiError::from(*failure).into(),
+            Error::SystemContract(system::Error::Mint(error)) => {
+                return ApiError::from(*error).into()
+            }
+            Error::SystemContract(system::Error::HandlePayment(error)) => {
+                return ApiError::from(*error).into()
+            }
+            Error::SystemContract(system::Error::Auction(error)) => {
+                return ApiError::from(*error).into()
+            }
+            Error::Interpreter(_) => (ErrorCategory::RuntimeTrap, 100),
+            Error::Storage(_) => (ErrorCategory::Internal, 101),
+            Error::BytesRepr(_) => (ErrorCategory::RuntimeTrap, 102),
+            Error::NamedKeyNotFound(_) => (ErrorCategory::Precondition, 103),
+            Error::KeyNotFound(_) => (ErrorCategory::Precondition, 104),
+            Error::AccountNotFound(_) => (ErrorCategory::Precondition, 105),
+            Error::TypeMismatch(_) => (ErrorCategory::RuntimeTrap, 106),
+            Error::InvalidAccess { .. } => (ErrorCategory::Authorization, 107),
+            Error::ForgedReference(_) => (ErrorCategory::Authorization, 108),
+            Error::URefNotFound(_) => (ErrorCategory::Precondition, 109),
+            Error::FunctionNotFound(_) => (ErrorCategory::Precondition, 110),
+            Error::ParityWasm(_) => (ErrorCategory::Precondition, 111),
+            Error::WasmOptimizer => (ErrorCategory::Precondition, 112),
+            Error::GasLimit => (ErrorCategory::OutOfGas, 113),
+            Error::Ret(_) => (ErrorCategory::RuntimeTrap, 114),
+            Error::Resolver(_) => (ErrorCategory::RuntimeTrap, 115),
+            Error::DeploymentAuthorizationFailure => (ErrorCategory::Authorization, 116),
+            Error::ExpectedReturnValue => (ErrorCategory::RuntimeTrap, 117),
+            Error::UnexpectedReturnValue => (ErrorCategory::RuntimeTrap, 118),
+            Error::InvalidContext => (ErrorCategory::RuntimeTrap, 119),
+            Error::IncompatibleProtocolMajorVersion { .. } => (ErrorCategory::Precondition, 120),
+            Error::CLValue(_) => (ErrorCategory::RuntimeTrap, 121),
+            Error::HostBufferEmpty => (ErrorCategory::RuntimeTrap, 122),
+            Error::UnsupportedWasmStart => (ErrorCategory::Precondition, 123),
+            Error::NoActiveContractVersions(_) => (ErrorCategory::Precondition, 124),
+            Error::InvalidContractVersion(_) => (ErrorCategory::Precondition, 125),
+            Error::NoSuchMethod(_) => (ErrorCategory::Precondition, 126),
+            Error::WasmPreprocessing(_) => (ErrorCategory::Precondition, 127),
+            Error::KeyIsNotAURef(_) => (ErrorCategory::RuntimeTrap, 128),
+            Error::UnexpectedStoredValueVariant => (ErrorCategory::RuntimeTrap, 129),
+            Error::LockedContract(_) => (ErrorCategory::Precondition, 130),
+            Error::InvalidContractPackage(_) => (ErrorCategory::Precondition, 131),
+            Error::InvalidContract(_) => (ErrorCategory::Precondition, 132),
+            Error::InvalidContractWasm(_) => (ErrorCategory::Precondition, 133),
+            Error::MissingArgument { .. } => (ErrorCategory::Precondition, 134),
+            Error::DictionaryItemKeyExceedsLength => (ErrorCategory::RuntimeTrap, 135),
+            Error::MissingSystemContractRegistry => (ErrorCategory::Internal, 136),
+            Error::MissingSystemContractHash(_) => (ErrorCategory::Internal, 137),
+            Error::RuntimeStackOverflow => (ErrorCategory::RuntimeTrap, 138),
+            Error::ValueTooLarge => (ErrorCategory::RuntimeTrap, 139),
+            Error::MissingRuntimeStack => (ErrorCategory::Internal, 140),
+            Error::DisabledContract(_) => (ErrorCategory::Precondition, 141),
+            Error::DisabledUnrestrictedTransfers => (ErrorCategory::Authorization, 142),
+            Error::SystemContract(_) => (ErrorCategory::Internal, 143),
+        };
+        ExecutionErrorCode::new(category, code)
+    }
+
+    /// Returns `true` if the error is determined by the executed code and the global state it
+    /// executes against, so that every node executing it hits the error alike.
+    ///
+    /// New variants must be classified explicitly, and only errors known to be deterministic may
+    /// be listed as such.
+    pub fn is_deterministic(&self) -> bool {
+        match self {
+            Error::Storage(_) => false,
+            Error::Interpreter(_)
+            | Error::BytesRepr(_)
+            | Error::NamedKeyNotFound(_)
+            | Error::KeyNotFound(_)
+            | Error::AccountNotFound(_)
+            | Error::TypeMismatch(_)
+            | Error::InvalidAccess { .. }
+            | Error::ForgedReference(_)
+            | Error::URefNotFound(_)
+            | Error::FunctionNotFound(_)
+            | Error::ParityWasm(_)
+            | Error::WasmOptimizer
+            | Error::GasLimit
+            | Error::Ret(_)
+            | Error::Resolver(_)
+            | Error::Revert(_)
+            | Error::AddKeyFailure(_)
+            | Error::RemoveKeyFailure(_)
+            | Error::UpdateKeyFailure(_)
+            | Error::SetThresholdFailure(_)
+            | Error::SystemContract(_)
+            | Error::DeploymentAuthorizationFailure
+            | Error::ExpectedReturnValue
+            | Error::UnexpectedReturnValue
+            | Error::InvalidContext
+            | Error::IncompatibleProtocolMajorVersion { .. }
+            | Error::CLValue(_)
+            | Error::HostBufferEmpty
+            | Error::UnsupportedWasmStart
+            | Error::NoActiveContractVersions(_)
+            | Error::InvalidContractVersion(_)
+            | Error::NoSuchMethod(_)
+            | Error::WasmPreprocessing(_)
+            | Error::KeyIsNotAURef(_)
+            | Error::UnexpectedStoredValueVariant
+            | Error::LockedContract(_)
+            | Error::InvalidContractPackage(_)
+            | Error::InvalidContract(_)
+            | Error::InvalidContractWasm(_)
+            | Error::MissingArgument { .. }
+            | Error::DictionaryItemKeyExceedsLength
+            | Error::MissingSystemContractRegistry
+            | Error::MissingSystemContractHash(_)
+            | Error::RuntimeStackOverflow
+            | Error::ValueTooLarge
+            | Error::MissingRuntimeStack
+            | Error::DisabledContract(_)
+            | Error::DisabledUnrestrictedTransfers => true,
+        }
+    }
 }
 
 impl casper_wasmi::HostError for Error {}
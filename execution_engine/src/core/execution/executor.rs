@@ -122,12 +122,14 @@ impl Executor {
                 execution_journal: runtime.context().execution_journal(),
                 transfers: runtime.context().transfers().to_owned(),
                 cost: runtime.context().gas_counter(),
+                payment_settlement: None,
             },
             Err(error) => ExecutionResult::Failure {
                 error: error.into(),
                 execution_journal: runtime.context().execution_journal(),
                 transfers: runtime.context().transfers().to_owned(),
                 cost: runtime.context().gas_counter(),
+                payment_settlement: None,
             },
         }
     }
@@ -197,12 +199,14 @@ impl Executor {
                 execution_journal: runtime.context().execution_journal(),
                 transfers: runtime.context().transfers().to_owned(),
                 cost: runtime.context().gas_counter(),
+                payment_settlement: None,
             },
             Err(error) => ExecutionResult::Failure {
                 execution_journal,
                 error: error.into(),
                 transfers: runtime.context().transfers().to_owned(),
                 cost: runtime.context().gas_counter(),
+                payment_settlement: None,
             },
         }
     }
@@ -235,7 +239,50 @@ impl Executor {
             let generator = AddressGenerator::new(deploy_hash.as_bytes(), phase);
             Rc::new(RefCell::new(generator))
         };
+        self.call_system_contract_with_address_generator(
+            direct_system_contract_call,
+            runtime_args,
+            account,
+            authorization_keys,
+            blocktime,
+            deploy_hash,
+            gas_limit,
+            protocol_version,
+            correlation_id,
+            tracking_copy,
+            phase,
+            address_generator,
+            stack,
+            remaining_spending_limit,
+        )
+    }
 
+    /// Like [`Executor::call_system_contract`], but generates new addresses with the given
+    /// `address_generator`, so that several calls made in the same phase don't generate the same
+    /// addresses.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn call_system_contract_with_address_generator<R, T>(
+        &self,
+        direct_system_contract_call: DirectSystemContractCall,
+        runtime_args: RuntimeArgs,
+        account: &Account,
+        authorization_keys: BTreeSet<AccountHash>,
+        blocktime: BlockTime,
+        deploy_hash: DeployHash,
+        gas_limit: Gas,
+        protocol_version: ProtocolVersion,
+        correlation_id: CorrelationId,
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+        phase: Phase,
+        address_generator: Rc<RefCell<AddressGenerator>>,
+        stack: RuntimeStack,
+        remaining_spending_limit: U512,
+    ) -> (Option<T>, ExecutionResult)
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<Error>,
+        T: FromBytes + CLTyped,
+    {
         // Today lack of existence of the system contract registry and lack of entry
         // for the minimum defined system contracts (mint, auction, handle_payment)
         // should cause the EE to panic. Do not remove the panics.
@@ -322,6 +369,7 @@ impl Executor {
                     execution_journal: runtime.context().execution_journal(),
                     transfers: runtime.context().transfers().to_owned(),
                     cost: runtime.context().gas_counter(),
+                    payment_settlement: None,
                 }
                 .take_with_ret(ret),
                 Err(error) => ExecutionResult::Failure {
@@ -329,6 +377,7 @@ impl Executor {
                     error: Error::CLValue(error).into(),
                     transfers: runtime.context().transfers().to_owned(),
                     cost: runtime.context().gas_counter(),
+                    payment_settlement: None,
                 }
                 .take_without_ret(),
             },
@@ -337,6 +386,7 @@ impl Executor {
                 error: error.into(),
                 transfers: runtime.context().transfers().to_owned(),
                 cost: runtime.context().gas_counter(),
+                payment_settlement: None,
             }
             .take_without_ret(),
         }
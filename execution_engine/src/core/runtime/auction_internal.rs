@@ -2,7 +2,7 @@ use std::collections::BTreeSet;
 
 use casper_types::{
     account::AccountHash,
-    bytesrepr::{FromBytes, ToBytes},
+    bytesrepr::{Bytes, FromBytes, ToBytes},
     crypto,
     system::{
         auction::{Bid, EraInfo, Error, UnbondingPurse},
@@ -39,6 +39,13 @@ where
     R: StateReader<Key, StoredValue>,
     R::Error: Into<execution::Error>,
 {
+    fn new_uref<T: CLTyped + ToBytes>(&mut self, init: T) -> Result<URef, Error> {
+        let cl_value = CLValue::from_t(init).map_err(|_| Error::CLValue)?;
+        self.context
+            .new_uref(StoredValue::CLValue(cl_value))
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))
+    }
+
     fn read<T: FromBytes + CLTyped>(&mut self, uref: URef) -> Result<Option<T>, Error> {
         match self.context.read_gs(&uref.into()) {
             Ok(Some(StoredValue::CLValue(cl_value))) => {
@@ -106,6 +113,35 @@ where
             .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))
     }
 
+    fn write_bid_metadata(
+        &mut self,
+        account_hash: AccountHash,
+        metadata: Bytes,
+    ) -> Result<(), Error> {
+        let cl_value = CLValue::from_t(metadata).map_err(|_| Error::CLValue)?;
+        self.context
+            .metered_write_gs_unsafe(
+                Key::BidMetadata(account_hash),
+                StoredValue::CLValue(cl_value),
+            )
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))
+    }
+
+    fn remove_bid_metadata(&mut self, account_hash: AccountHash) -> Result<(), Error> {
+        let key = Key::BidMetadata(account_hash);
+        match self.context.read_gs(&key) {
+            Ok(Some(_)) => {
+                self.context.prune_gs_unsafe(key);
+                Ok(())
+            }
+            Ok(None) => Ok(()),
+            // NOTE: This extra condition is needed to correctly propagate GasLimit to the user. See
+            // also [`Runtime::reverter`] and [`to_auction_error`]
+            Err(execution::Error::GasLimit) => Err(Error::GasLimit),
+            Err(_) => Err(Error::Storage),
+        }
+    }
+
     fn record_era_info(&mut self, _era_id: EraId, era_summary: EraInfo) -> Result<(), Error> {
         Runtime::record_era_summary(self, era_summary)
             .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::RecordEraInfo))
@@ -144,6 +180,16 @@ where
     fn allow_auction_bids(&self) -> bool {
         self.config.allow_auction_bids()
     }
+
+    fn validator_eviction_threshold(&self) -> u64 {
+        self.config.validator_eviction_threshold()
+    }
+
+    fn put_key(&mut self, name: &str, key: Key) -> Result<(), Error> {
+        self.context
+            .put_key(name.to_string(), key)
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))
+    }
 }
 
 impl<'a, R> MintProvider for Runtime<'a, R>
@@ -305,6 +351,18 @@ where
         // exception here.
         Ok(Runtime::context(self).account().main_purse())
     }
+
+    fn get_account_main_purse(&mut self, account_hash: AccountHash) -> Result<Option<URef>, Error> {
+        let maybe_value = self
+            .context
+            .read_gs_direct(&Key::Account(account_hash))
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))?;
+        match maybe_value {
+            Some(StoredValue::Account(account)) => Ok(Some(account.main_purse())),
+            Some(_) => Err(Error::Storage),
+            None => Ok(None),
+        }
+    }
 }
 
 impl<'a, R> Auction for Runtime<'a, R>
@@ -76,6 +76,10 @@ where
         self.config.allow_unrestricted_transfers()
     }
 
+    fn minimum_remaining_balance(&self) -> U512 {
+        U512::from(self.config.minimum_remaining_balance())
+    }
+
     fn get_system_contract_registry(&self) -> Result<SystemContractRegistry, execution::Error> {
         self.context.system_contract_registry()
     }
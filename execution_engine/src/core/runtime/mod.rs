@@ -1028,6 +1028,77 @@ where
                 CLValue::from_t(()).map_err(Self::reverter)
             })(),
 
+            // Type: `fn set_bid_metadata(public_key: PublicKey, metadata: Bytes) -> Result<(),
+            // Error>`
+            auction::METHOD_SET_BID_METADATA => (|| {
+                runtime.charge_system_contract_call(auction_costs.set_bid_metadata)?;
+
+                let public_key: PublicKey =
+                    Self::get_named_argument(runtime_args, auction::ARG_PUBLIC_KEY)?;
+                let metadata: Bytes =
+                    Self::get_named_argument(runtime_args, auction::ARG_METADATA)?;
+
+                runtime
+                    .set_bid_metadata(public_key, metadata)
+                    .map_err(Self::reverter)?;
+
+                CLValue::from_t(()).map_err(Self::reverter)
+            })(),
+
+            auction::METHOD_SET_REWARDS_DESTINATION => (|| {
+                runtime.charge_system_contract_call(auction_costs.set_rewards_destination)?;
+
+                let public_key: PublicKey =
+                    Self::get_named_argument(runtime_args, auction::ARG_PUBLIC_KEY)?;
+                let rewards_destination =
+                    Self::get_named_argument(runtime_args, auction::ARG_REWARDS_DESTINATION)?;
+
+                runtime
+                    .set_rewards_destination(public_key, rewards_destination)
+                    .map_err(Self::reverter)?;
+
+                CLValue::from_t(()).map_err(Self::reverter)
+            })(),
+
+            auction::METHOD_SET_DELEGATOR_REWARDS_DESTINATION => (|| {
+                runtime
+                    .charge_system_contract_call(auction_costs.set_delegator_rewards_destination)?;
+
+                let delegator = Self::get_named_argument(runtime_args, auction::ARG_DELEGATOR)?;
+                let validator = Self::get_named_argument(runtime_args, auction::ARG_VALIDATOR)?;
+                let rewards_destination =
+                    Self::get_named_argument(runtime_args, auction::ARG_REWARDS_DESTINATION)?;
+
+                runtime
+                    .set_delegator_rewards_destination(delegator, validator, rewards_destination)
+                    .map_err(Self::reverter)?;
+
+                CLValue::from_t(()).map_err(Self::reverter)
+            })(),
+
+            auction::METHOD_CANCEL_UNBONDING => (|| {
+                runtime.charge_system_contract_call(auction_costs.cancel_unbonding)?;
+
+                let public_key = Self::get_named_argument(runtime_args, auction::ARG_PUBLIC_KEY)?;
+                let validator = Self::get_named_argument(runtime_args, auction::ARG_VALIDATOR)?;
+                let amount = Self::get_named_argument(runtime_args, auction::ARG_AMOUNT)?;
+
+                let max_delegators_per_validator = self.config.max_delegators_per_validator();
+                let minimum_delegation_amount = self.config.minimum_delegation_amount();
+
+                let result = runtime
+                    .cancel_unbonding(
+                        public_key,
+                        validator,
+                        amount,
+                        max_delegators_per_validator,
+                        minimum_delegation_amount,
+                    )
+                    .map_err(Self::reverter)?;
+
+                CLValue::from_t(result).map_err(Self::reverter)
+            })(),
+
             _ => CLValue::from_t(()).map_err(Self::reverter),
         };
 
@@ -3046,8 +3117,7 @@ where
 #[cfg(feature = "test-support")]
 fn dump_runtime_stack_info(instance: casper_wasmi::ModuleRef, max_stack_height: u32) {
     let globals = instance.globals();
-    let Some(current_runtime_call_stack_height) = globals.last()
-    else {
+    let Some(current_runtime_call_stack_height) = globals.last() else {
         return;
     };
 
@@ -136,6 +136,13 @@ where
         transfers: Vec<TransferAddr>,
         remaining_spending_limit: U512,
     ) -> Self {
+        debug_assert!(
+            address_generator
+                .borrow()
+                .phase()
+                .map_or(true, |generator_phase| generator_phase == phase),
+            "address generator must be seeded with the phase of the runtime context"
+        );
         RuntimeContext {
             tracking_copy,
             entry_point_type,
@@ -338,6 +345,10 @@ where
                 error!("should not remove the checksum registry key");
                 Err(Error::RemoveKeyFailure(RemoveKeyFailure::PermissionDenied))
             }
+            Key::BidMetadata(_) => {
+                self.named_keys.remove(name);
+                Ok(())
+            }
         }
     }
 
@@ -796,6 +807,7 @@ where
             Key::Unbond(_) => true,
             Key::ChainspecRegistry => true,
             Key::ChecksumRegistry => true,
+            Key::BidMetadata(_) => true,
         }
     }
 
@@ -816,6 +828,7 @@ where
             Key::Unbond(_) => false,
             Key::ChainspecRegistry => false,
             Key::ChecksumRegistry => false,
+            Key::BidMetadata(_) => false,
         }
     }
 
@@ -836,6 +849,7 @@ where
             Key::Unbond(_) => false,
             Key::ChainspecRegistry => false,
             Key::ChecksumRegistry => false,
+            Key::BidMetadata(_) => false,
         }
     }
 
@@ -924,6 +938,17 @@ where
         Ok(())
     }
 
+    /// Prunes `key`, so that it is absent from the global state once the effects are committed.
+    ///
+    /// Use with caution - there is no validation done as the key is assumed to be validated
+    /// already.
+    pub(crate) fn prune_gs_unsafe<K>(&mut self, key: K)
+    where
+        K: Into<Key>,
+    {
+        self.tracking_copy.borrow_mut().prune(key.into());
+    }
+
     /// Writes data to a global state and charges for bytes stored.
     ///
     /// This method performs full validation of the key to be written.
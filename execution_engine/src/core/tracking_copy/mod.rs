@@ -137,6 +137,7 @@ pub struct TrackingCopyCache<M> {
     muts_cached: HashMap<Key, StoredValue>,
     key_tag_reads_cached: LinkedHashMap<KeyTag, BTreeSet<Key>>,
     key_tag_muts_cached: HashMap<KeyTag, BTreeSet<Key>>,
+    prunes_cached: BTreeSet<Key>,
     meter: M,
 }
 
@@ -153,6 +154,7 @@ impl<M: Meter<Key, StoredValue>> TrackingCopyCache<M> {
             muts_cached: HashMap::new(),
             key_tag_reads_cached: LinkedHashMap::new(),
             key_tag_muts_cached: HashMap::new(),
+            prunes_cached: BTreeSet::new(),
             meter,
         }
     }
@@ -191,6 +193,7 @@ impl<M: Meter<Key, StoredValue>> TrackingCopyCache<M> {
 
     /// Inserts `key` and `value` pair to Write/Add cache.
     pub fn insert_write(&mut self, key: Key, value: StoredValue) {
+        self.prunes_cached.remove(&key);
         self.muts_cached.insert(key, value);
 
         let key_set = self
@@ -201,6 +204,23 @@ impl<M: Meter<Key, StoredValue>> TrackingCopyCache<M> {
         key_set.insert(key);
     }
 
+    /// Marks `key` as pruned, removing any value cached under it.
+    pub fn insert_prune(&mut self, key: Key) {
+        self.muts_cached.remove(&key);
+        if let Some(value) = self.reads_cached.remove(&key) {
+            self.current_cache_size -= Meter::measure(&self.meter, &key, &value);
+        }
+        if let Some(keys) = self.key_tag_muts_cached.get_mut(&key.tag()) {
+            keys.remove(&key);
+        }
+        self.prunes_cached.insert(key);
+    }
+
+    /// Returns `true` if `key` has been pruned.
+    pub fn is_pruned(&self, key: &Key) -> bool {
+        self.prunes_cached.contains(key)
+    }
+
     /// Gets value from `key` in the cache.
     pub fn get(&mut self, key: &Key) -> Option<&StoredValue> {
         if let Some(value) = self.muts_cached.get(key) {
@@ -228,6 +248,11 @@ pub struct TrackingCopy<R> {
     reader: R,
     cache: TrackingCopyCache<HeapSize>,
     journal: ExecutionJournal,
+    /// If `true`, writes which leave a value unchanged are not journaled, and consecutive writes
+    /// to the same key are coalesced into a single journal entry.
+    deduplicate_writes: bool,
+    /// Indices of the journal entries of keys whose latest journaled transform is a write.
+    latest_writes: HashMap<Key, usize>,
 }
 
 /// Result of executing an "add" operation on a value in the state.
@@ -266,9 +291,22 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
              * be fraction of wasm memory
              * limit? */
             journal: Default::default(),
+            deduplicate_writes: false,
+            latest_writes: HashMap::new(),
         }
     }
 
+    /// Enables or disables deduplication of writes.
+    ///
+    /// When enabled, a write storing the value already held under its key is not journaled, and a
+    /// write to a key whose latest journaled transform is also a write replaces that transform
+    /// rather than adding a new one.  The resulting effects leave global state the same as with
+    /// deduplication disabled, which journals every write for auditing.
+    pub fn with_write_deduplication(mut self, deduplicate_writes: bool) -> Self {
+        self.deduplicate_writes = deduplicate_writes;
+        self
+    }
+
     /// Returns the `reader` used to access the state.
     pub fn reader(&self) -> &R {
         &self.reader
@@ -287,7 +325,7 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
     /// forking, however we recognize this is sub-optimal and will revisit
     /// in the future.
     pub fn fork(&self) -> TrackingCopy<&TrackingCopy<R>> {
-        TrackingCopy::new(self)
+        TrackingCopy::new(self).with_write_deduplication(self.deduplicate_writes)
     }
 
     pub(super) fn get(
@@ -295,6 +333,9 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         correlation_id: CorrelationId,
         key: &Key,
     ) -> Result<Option<StoredValue>, R::Error> {
+        if self.cache.is_pruned(key) {
+            return Ok(None);
+        }
         if let Some(value) = self.cache.get(key) {
             return Ok(Some(value.to_owned()));
         }
@@ -327,6 +368,7 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         if let Some(keys) = self.cache.get_key_tag_muts_cached(key_tag) {
             ret.extend(keys)
         }
+        ret.retain(|key| !self.cache.is_pruned(key));
         Ok(ret)
     }
 
@@ -338,7 +380,7 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
     ) -> Result<Option<StoredValue>, R::Error> {
         let normalized_key = key.normalize();
         if let Some(value) = self.get(correlation_id, &normalized_key)? {
-            self.journal.push((normalized_key, Transform::Identity));
+            self.push_journal_entry(normalized_key, Transform::Identity);
             Ok(Some(value))
         } else {
             Ok(None)
@@ -349,8 +391,36 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
     /// remains unmodified.
     pub fn write(&mut self, key: Key, value: StoredValue) {
         let normalized_key = key.normalize();
+        if !self.deduplicate_writes {
+            self.cache.insert_write(normalized_key, value.clone());
+            self.journal.push((normalized_key, Transform::Write(value)));
+            return;
+        }
+
+        if self.cache.get(&normalized_key) == Some(&value) {
+            return;
+        }
         self.cache.insert_write(normalized_key, value.clone());
-        self.journal.push((normalized_key, Transform::Write(value)));
+        let latest_write = match self.latest_writes.get(&normalized_key) {
+            Some(index) => self.journal.get_mut(*index),
+            None => None,
+        };
+        match latest_write {
+            Some((_, transform)) => *transform = Transform::Write(value),
+            None => {
+                self.latest_writes
+                    .insert(normalized_key, self.journal.len());
+                self.journal.push((normalized_key, Transform::Write(value)));
+            }
+        }
+    }
+
+    /// Prunes `key`, so that it is absent from the global state once the effects are committed.
+    /// Note that the prune is only cached, and the global state itself remains unmodified.
+    pub fn prune(&mut self, key: Key) {
+        let normalized_key = key.normalize();
+        self.cache.insert_prune(normalized_key);
+        self.push_journal_entry(normalized_key, Transform::Prune(normalized_key));
     }
 
     /// Ok(None) represents missing key to which we want to "add" some value.
@@ -419,14 +489,23 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         match transform.clone().apply(current_value) {
             Ok(new_value) => {
                 self.cache.insert_write(normalized_key, new_value);
-                self.journal.push((normalized_key, transform));
+                self.push_journal_entry(normalized_key, transform);
                 Ok(AddResult::Success)
             }
             Err(transform::Error::TypeMismatch(type_mismatch)) => {
                 Ok(AddResult::TypeMismatch(type_mismatch))
             }
             Err(transform::Error::Serialization(error)) => Ok(AddResult::Serialization(error)),
+            Err(transform::Error::Pruned(pruned_key)) => Ok(AddResult::KeyNotFound(pruned_key)),
+        }
+    }
+
+    /// Journals a transform other than a write.
+    fn push_journal_entry(&mut self, key: Key, transform: Transform) {
+        if self.deduplicate_writes {
+            self.latest_writes.remove(&key);
         }
+        self.journal.push((key, transform));
     }
 
     /// Returns the execution effects cached by this instance.
@@ -577,6 +656,9 @@ impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for &Tracki
         correlation_id: CorrelationId,
         key: &Key,
     ) -> Result<Option<StoredValue>, Self::Error> {
+        if self.cache.is_pruned(key) {
+            return Ok(None);
+        }
         if let Some(value) = self.cache.muts_cached.get(key) {
             return Ok(Some(value.to_owned()));
         }
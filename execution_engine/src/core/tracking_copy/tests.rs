@@ -160,6 +160,93 @@ fn tracking_copy_write() {
     );
 }
 
+#[test]
+fn tracking_copy_write_with_deduplication() {
+    let counter = Rc::new(Cell::new(0));
+    let db = CountingDb::new(Rc::clone(&counter));
+    let mut tc = TrackingCopy::new(db).with_write_deduplication(true);
+    let correlation_id = CorrelationId::new();
+    let k = Key::Hash([0u8; 32]);
+    let other_k = Key::Hash([1u8; 32]);
+
+    let zero = StoredValue::CLValue(CLValue::from_t(0_i32).unwrap());
+    let one = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+    let two = StoredValue::CLValue(CLValue::from_t(2_i32).unwrap());
+
+    // Writing the value just read is not journaled.
+    assert_eq!(tc.read(correlation_id, &k), Ok(Some(zero.clone())));
+    tc.write(k, zero);
+    assert_eq!(
+        tc.journal,
+        ExecutionJournal::new(vec![(k, Transform::Identity)])
+    );
+
+    // Consecutive writes to the same key are coalesced, even if other keys are written between.
+    tc.write(k, one.clone());
+    tc.write(other_k, one.clone());
+    tc.write(k, two.clone());
+    assert_eq!(
+        tc.journal,
+        ExecutionJournal::new(vec![
+            (k, Transform::Identity),
+            (k, Transform::Write(two.clone())),
+            (other_k, Transform::Write(one.clone())),
+        ])
+    );
+
+    // Writing the value already written is not journaled.
+    tc.write(k, two.clone());
+    assert_eq!(tc.journal.len(), 3);
+
+    // A write following another transform of the same key is journaled separately.
+    assert_matches!(
+        tc.add(correlation_id, k, one.clone()),
+        Ok(AddResult::Success)
+    );
+    tc.write(k, one.clone());
+    assert_eq!(
+        tc.journal,
+        ExecutionJournal::new(vec![
+            (k, Transform::Identity),
+            (k, Transform::Write(two)),
+            (other_k, Transform::Write(one.clone())),
+            (k, Transform::AddInt32(1)),
+            (k, Transform::Write(one)),
+        ])
+    );
+    assert_eq!(counter.get(), 1);
+}
+
+#[test]
+fn tracking_copy_prune() {
+    let correlation_id = CorrelationId::new();
+    let counter = Rc::new(Cell::new(0));
+    let db = CountingDb::new(Rc::clone(&counter));
+    let mut tc = TrackingCopy::new(db);
+    let k = Key::Hash([0u8; 32]);
+
+    let one = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+
+    tc.write(k, one.clone());
+    tc.prune(k);
+    // a pruned key is absent, without querying the DB
+    assert_eq!(tc.read(correlation_id, &k).unwrap(), None);
+    assert_eq!(counter.get(), 0);
+    // Pruning creates a prune transform.
+    assert_eq!(
+        tc.journal,
+        ExecutionJournal::new(vec![
+            (k, Transform::Write(one.clone())),
+            (k, Transform::Prune(k))
+        ])
+    );
+    assert_eq!(tc.effect().transforms.get(&k), Some(&Transform::Prune(k)));
+
+    // writing after pruning makes the key present again
+    tc.write(k, one.clone());
+    assert_eq!(tc.read(correlation_id, &k).unwrap(), Some(one));
+}
+
 #[test]
 fn tracking_copy_add_i32() {
     let correlation_id = CorrelationId::new();
@@ -60,6 +60,18 @@ impl<K, V, S> AdditiveMap<K, V, S> {
     }
 }
 
+impl<K: Ord, V, S> AdditiveMap<K, V, S> {
+    /// Consumes the map, returning its entries sorted by key.
+    ///
+    /// Unlike iterating the map, which visits entries in an order varying between processes, this
+    /// yields the same order on every node, so should be used wherever that order can be observed.
+    pub fn into_sorted_vec(self) -> Vec<(K, V)> {
+        let mut entries: Vec<(K, V)> = self.0.into_iter().collect();
+        entries.sort_unstable_by(|(key_1, _), (key_2, _)| key_1.cmp(key_2));
+        entries
+    }
+}
+
 impl<K: Eq + Hash, V, S: BuildHasher> AdditiveMap<K, V, S> {
     /// Returns a reference to the value corresponding to a key.
     pub fn get<Q>(&self, key: &Q) -> Option<&V>
@@ -185,4 +197,18 @@ mod tests {
         transform_map.insert_add(key, Transform::AddInt32(2));
         assert_eq!(Transform::AddInt32(3), transform_map[key]);
     }
+
+    #[test]
+    fn into_sorted_vec() {
+        let keys = [9, 3, 7, 1, 5, 8, 2, 6, 4, 0];
+        let expected: Vec<(i32, i32)> = (0..10).map(|key| (key, key * 10)).collect();
+        // Maps built separately use differently seeded hashers, so iterate in differing orders.
+        for _ in 0..10 {
+            let mut map = AdditiveMap::new();
+            for key in keys.iter() {
+                map.insert(*key, key * 10);
+            }
+            assert_eq!(map.into_sorted_vec(), expected);
+        }
+    }
 }
@@ -35,6 +35,11 @@ impl ExecutionJournal {
         self.0.push(entry)
     }
 
+    /// Returns a mutable reference to the entry at `index`, if any.
+    pub(crate) fn get_mut(&mut self, index: usize) -> Option<&mut (Key, Transform)> {
+        self.0.get_mut(index)
+    }
+
     /// Returns an iterator over the journal entries.
     pub fn iter(&self) -> impl Iterator<Item = &(Key, Transform)> {
         self.0.iter()
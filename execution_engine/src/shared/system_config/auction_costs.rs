@@ -32,6 +32,14 @@ pub const DEFAULT_WITHDRAW_VALIDATOR_REWARD_COST: u32 = 10_000;
 pub const DEFAULT_READ_ERA_ID_COST: u32 = 10_000;
 /// Default cost of the `activate_bid` auction entry point.
 pub const DEFAULT_ACTIVATE_BID_COST: u32 = 10_000;
+/// Default cost of the `set_bid_metadata` auction entry point.
+pub const DEFAULT_SET_BID_METADATA_COST: u32 = 10_000;
+/// Default cost of the `set_rewards_destination` auction entry point.
+pub const DEFAULT_SET_REWARDS_DESTINATION_COST: u32 = 10_000;
+/// Default cost of the `set_delegator_rewards_destination` auction entry point.
+pub const DEFAULT_SET_DELEGATOR_REWARDS_DESTINATION_COST: u32 = 10_000;
+/// Default cost of the `cancel_unbonding` auction entry point.
+pub const DEFAULT_CANCEL_UNBONDING_COST: u32 = 2_500_000_000;
 
 /// Description of the costs of calling auction entrypoints.
 #[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug, DataSize)]
@@ -65,6 +73,14 @@ pub struct AuctionCosts {
     pub activate_bid: u32,
     /// Cost of calling the `redelegate` entry point.
     pub redelegate: u32,
+    /// Cost of calling the `set_bid_metadata` entry point.
+    pub set_bid_metadata: u32,
+    /// Cost of calling the `set_rewards_destination` entry point.
+    pub set_rewards_destination: u32,
+    /// Cost of calling the `set_delegator_rewards_destination` entry point.
+    pub set_delegator_rewards_destination: u32,
+    /// Cost of calling the `cancel_unbonding` entry point.
+    pub cancel_unbonding: u32,
 }
 
 impl Default for AuctionCosts {
@@ -84,6 +100,10 @@ impl Default for AuctionCosts {
             read_era_id: DEFAULT_READ_ERA_ID_COST,
             activate_bid: DEFAULT_ACTIVATE_BID_COST,
             redelegate: DEFAULT_REDELEGATE_COST,
+            set_bid_metadata: DEFAULT_SET_BID_METADATA_COST,
+            set_rewards_destination: DEFAULT_SET_REWARDS_DESTINATION_COST,
+            set_delegator_rewards_destination: DEFAULT_SET_DELEGATOR_REWARDS_DESTINATION_COST,
+            cancel_unbonding: DEFAULT_CANCEL_UNBONDING_COST,
         }
     }
 }
@@ -107,6 +127,10 @@ impl ToBytes for AuctionCosts {
             read_era_id,
             activate_bid,
             redelegate,
+            set_bid_metadata,
+            set_rewards_destination,
+            set_delegator_rewards_destination,
+            cancel_unbonding,
         } = self;
 
         ret.append(&mut get_era_validators.to_bytes()?);
@@ -123,6 +147,10 @@ impl ToBytes for AuctionCosts {
         ret.append(&mut read_era_id.to_bytes()?);
         ret.append(&mut activate_bid.to_bytes()?);
         ret.append(&mut redelegate.to_bytes()?);
+        ret.append(&mut set_bid_metadata.to_bytes()?);
+        ret.append(&mut set_rewards_destination.to_bytes()?);
+        ret.append(&mut set_delegator_rewards_destination.to_bytes()?);
+        ret.append(&mut cancel_unbonding.to_bytes()?);
 
         Ok(ret)
     }
@@ -143,6 +171,10 @@ impl ToBytes for AuctionCosts {
             read_era_id,
             activate_bid,
             redelegate,
+            set_bid_metadata,
+            set_rewards_destination,
+            set_delegator_rewards_destination,
+            cancel_unbonding,
         } = self;
 
         get_era_validators.serialized_length()
@@ -159,6 +191,10 @@ impl ToBytes for AuctionCosts {
             + read_era_id.serialized_length()
             + activate_bid.serialized_length()
             + redelegate.serialized_length()
+            + set_bid_metadata.serialized_length()
+            + set_rewards_destination.serialized_length()
+            + set_delegator_rewards_destination.serialized_length()
+            + cancel_unbonding.serialized_length()
     }
 }
 
@@ -178,6 +214,10 @@ impl FromBytes for AuctionCosts {
         let (read_era_id, rem) = FromBytes::from_bytes(rem)?;
         let (activate_bid, rem) = FromBytes::from_bytes(rem)?;
         let (redelegate, rem) = FromBytes::from_bytes(rem)?;
+        let (set_bid_metadata, rem) = FromBytes::from_bytes(rem)?;
+        let (set_rewards_destination, rem) = FromBytes::from_bytes(rem)?;
+        let (set_delegator_rewards_destination, rem) = FromBytes::from_bytes(rem)?;
+        let (cancel_unbonding, rem) = FromBytes::from_bytes(rem)?;
         Ok((
             Self {
                 get_era_validators,
@@ -194,6 +234,10 @@ impl FromBytes for AuctionCosts {
                 read_era_id,
                 activate_bid,
                 redelegate,
+                set_bid_metadata,
+                set_rewards_destination,
+                set_delegator_rewards_destination,
+                cancel_unbonding,
             },
             rem,
         ))
@@ -217,6 +261,10 @@ impl Distribution<AuctionCosts> for Standard {
             read_era_id: rng.gen(),
             activate_bid: rng.gen(),
             redelegate: rng.gen(),
+            set_bid_metadata: rng.gen(),
+            set_rewards_destination: rng.gen(),
+            set_delegator_rewards_destination: rng.gen(),
+            cancel_unbonding: rng.gen(),
         }
     }
 }
@@ -244,6 +292,10 @@ pub mod gens {
             read_era_id in num::u32::ANY,
             activate_bid in num::u32::ANY,
             redelegate in num::u32::ANY,
+            set_bid_metadata in num::u32::ANY,
+            set_rewards_destination in num::u32::ANY,
+            set_delegator_rewards_destination in num::u32::ANY,
+            cancel_unbonding in num::u32::ANY,
         ) -> AuctionCosts {
             AuctionCosts {
                 get_era_validators,
@@ -260,6 +312,10 @@ pub mod gens {
                 read_era_id,
                 activate_bid,
                 redelegate,
+                set_bid_metadata,
+                set_rewards_destination,
+                set_delegator_rewards_destination,
+                cancel_unbonding,
             }
         }
     }
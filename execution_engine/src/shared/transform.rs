@@ -13,7 +13,8 @@ use num::traits::{AsPrimitive, WrappingAdd};
 use casper_types::{
     bytesrepr::{self, FromBytes, ToBytes},
     contracts::NamedKeys,
-    CLType, CLTyped, CLValue, CLValueError, StoredValue, StoredValueTypeMismatch, U128, U256, U512,
+    CLType, CLTyped, CLValue, CLValueError, Key, StoredValue, StoredValueTypeMismatch, U128, U256,
+    U512,
 };
 
 /// Error type for applying and combining transforms. A `TypeMismatch`
@@ -32,6 +33,9 @@ pub enum Error {
     /// Type mismatch error.
     #[error("{0}")]
     TypeMismatch(StoredValueTypeMismatch),
+    /// The key was pruned, so there is no value to apply the transform to.
+    #[error("Key {0} was pruned")]
+    Pruned(Key),
 }
 
 impl From<StoredValueTypeMismatch> for Error {
@@ -89,6 +93,10 @@ pub enum Transform {
     /// Represents the case where applying a transform would cause an error.
     #[data_size(skip)]
     Failure(Error),
+    /// Removes the value stored under the given key from the global state.
+    ///
+    /// Pruning a key which is absent from the global state is a no-op.
+    Prune(Key),
 }
 
 macro_rules! from_try_from_impl {
@@ -168,6 +176,8 @@ impl Transform {
     /// Applies the transformation on a specified stored value instance.
     ///
     /// This method produces a new [`StoredValue`] instance based on the [`Transform`] variant.
+    /// Applying a [`Transform::Prune`] yields an [`Error::Pruned`], since pruning leaves no value
+    /// behind; a prune is instead handled by removing the key when the transform is committed.
     pub fn apply(self, stored_value: StoredValue) -> Result<StoredValue, Error> {
         match self {
             Transform::Identity => Ok(stored_value),
@@ -233,6 +243,7 @@ impl Transform {
                 }
             },
             Transform::Failure(error) => Err(error),
+            Transform::Prune(key) => Err(Error::Pruned(key)),
         }
     }
 }
@@ -275,6 +286,9 @@ impl Add for Transform {
             (a @ Transform::Failure(_), _) => a,
             (_, b @ Transform::Failure(_)) => b,
             (_, b @ Transform::Write(_)) => b,
+            (_, b @ Transform::Prune(_)) => b,
+            // adding to a pruned value is equivalent to adding to a missing key
+            (Transform::Prune(key), _) => Transform::Failure(Error::Pruned(key)),
             (Transform::Write(v), b) => {
                 // second transform changes value being written
                 match b.apply(v) {
@@ -389,6 +403,7 @@ impl From<&Transform> for casper_types::Transform {
                     .collect(),
             ),
             Transform::Failure(error) => casper_types::Transform::Failure(error.to_string()),
+            Transform::Prune(key) => casper_types::Transform::Prune(*key),
         }
     }
 }
@@ -398,7 +413,7 @@ impl From<&Transform> for casper_types::Transform {
 pub mod gens {
     use proptest::{collection::vec, prelude::*};
 
-    use casper_types::gens::stored_value_arb;
+    use casper_types::gens::{key_arb, stored_value_arb};
 
     use super::Transform;
 
@@ -419,6 +434,7 @@ pub mod gens {
                 buf.copy_from_slice(&u);
                 Transform::AddUInt512(buf.into())
             }),
+            key_arb().prop_map(Transform::Prune),
         ]
     }
 }
@@ -868,4 +884,29 @@ mod tests {
         assert_eq!(ZERO_U512, add(MAX_U512, ONE_U512));
         assert_eq!(MAX_U512 - 1, add(MAX_U512, MAX_U512));
     }
+
+    #[test]
+    fn prune_should_supersede_and_be_superseded() {
+        let key = Key::Hash([1; 32]);
+        let write = Transform::Write(StoredValue::CLValue(CLValue::from_t(1_i32).unwrap()));
+
+        assert_eq!(write.clone() + Transform::Prune(key), Transform::Prune(key));
+        assert_eq!(
+            Transform::AddInt32(1) + Transform::Prune(key),
+            Transform::Prune(key)
+        );
+        assert_eq!(
+            Transform::Prune(key) + Transform::Identity,
+            Transform::Prune(key)
+        );
+        assert_eq!(Transform::Prune(key) + write.clone(), write);
+        assert_eq!(
+            Transform::Prune(key) + Transform::AddInt32(1),
+            Transform::Failure(Error::Pruned(key))
+        );
+        assert_eq!(
+            Transform::Prune(key).apply(StoredValue::CLValue(CLValue::from_t(1_i32).unwrap())),
+            Err(Error::Pruned(key))
+        );
+    }
 }
@@ -26,6 +26,10 @@ pub enum Error {
     /// Error committing to execution engine.
     #[error(transparent)]
     CommitError(#[from] CommitError),
+
+    /// Failure to compress or decompress a stored trie.
+    #[error("trie compression error: {0}")]
+    Compression(String),
 }
 
 impl casper_wasmi::HostError for Error {}
@@ -0,0 +1,327 @@
+//! A feed of the changes committed to global state.
+//!
+//! External indexers can register a [`TrieChangeSink`] with the [`TrieChangeFeed`] of an
+//! [`LmdbGlobalState`](super::lmdb::LmdbGlobalState) to be notified of the keys changed by every
+//! commit, along with the hashes of their old and new values, rather than replaying execution or
+//! diffing state roots to maintain a mirror of global state.
+//!
+//! A sink which misses change sets, e.g. because it fell behind and was disconnected, is sent a
+//! [`TrieChangeEvent::Gap`] before the next change set it receives.
+
+use std::{
+    io,
+    sync::{mpsc, RwLock},
+    time::{Duration, Instant},
+};
+#[cfg(unix)]
+use std::{io::Write, os::unix::net::UnixStream, path::Path, thread};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{debug, info, warn};
+
+use casper_hashing::Digest;
+use casper_types::Key;
+
+/// A change to the value stored under a single key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrieChange {
+    /// The changed key.
+    pub key: Key,
+    /// The hash of the serialized value under the key before the commit, or `None` if the key
+    /// didn't exist.
+    pub old_value_hash: Option<Digest>,
+    /// The hash of the serialized value under the key after the commit, or `None` if the key was
+    /// pruned.
+    pub new_value_hash: Option<Digest>,
+}
+
+/// The changes made to global state by a single commit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrieChangeSet {
+    /// The state root hash the commit was applied to.
+    pub pre_state_hash: Digest,
+    /// The state root hash resulting from the commit.
+    pub post_state_hash: Digest,
+    /// The changed keys, ordered by key.
+    pub changes: Vec<TrieChange>,
+}
+
+/// A notification sent to a [`TrieChangeSink`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrieChangeEvent {
+    /// The changes made by a single commit.
+    Changes(TrieChangeSet),
+    /// One or more change sets were not delivered to the sink, so the `pre_state_hash` of the next
+    /// change set may not be the `post_state_hash` of the last one received.
+    Gap,
+}
+
+/// The default minimum time between attempts to reconnect a sink registered via
+/// [`TrieChangeFeed::register_reconnecting`].
+pub const DEFAULT_TRIE_CHANGE_SINK_RECONNECT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// An error sending a [`TrieChangeEvent`] to a [`TrieChangeSink`].
+#[derive(Debug, Error)]
+pub enum TrieChangeSinkError {
+    /// The receiving end of the sink has gone away.
+    #[error("trie change sink disconnected")]
+    Disconnected,
+    /// The receiving end of the sink has fallen too far behind.
+    #[error("trie change sink has {0} change sets waiting to be received")]
+    Full(usize),
+    /// Writing to the sink failed.
+    #[error("failed to write to trie change sink: {0}")]
+    Io(#[from] io::Error),
+    /// Encoding the event failed.
+    #[error("failed to encode trie change event: {0}")]
+    Encoding(#[from] serde_json::Error),
+}
+
+/// A receiver of the changes committed to global state.
+pub trait TrieChangeSink: Send + Sync {
+    /// Sends the changes made by a single commit, or a marker that some were missed.
+    ///
+    /// A sink returning an error is removed from the feed, or reconnected if it was registered
+    /// via [`TrieChangeFeed::register_reconnecting`].
+    fn send(&self, event: &TrieChangeEvent) -> Result<(), TrieChangeSinkError>;
+}
+
+/// A sink forwarding events to a bounded in-process channel.
+///
+/// Once the channel is full, sending fails and so the sink is removed from the feed, rather than
+/// the commit waiting for the receiver to catch up.
+pub struct ChannelSink {
+    sender: mpsc::SyncSender<TrieChangeEvent>,
+    capacity: usize,
+}
+
+impl ChannelSink {
+    /// Creates a new sink holding at most `capacity` events which haven't been received yet,
+    /// returning it along with the receiving end of its channel.
+    pub fn new(capacity: usize) -> (Self, mpsc::Receiver<TrieChangeEvent>) {
+        let (sender, receiver) = mpsc::sync_channel(capacity);
+        let sink = ChannelSink { sender, capacity };
+        (sink, receiver)
+    }
+}
+
+impl TrieChangeSink for ChannelSink {
+    fn send(&self, event: &TrieChangeEvent) -> Result<(), TrieChangeSinkError> {
+        self.sender
+            .try_send(event.clone())
+            .map_err(|error| match error {
+                mpsc::TrySendError::Full(_) => TrieChangeSinkError::Full(self.capacity),
+                mpsc::TrySendError::Disconnected(_) => TrieChangeSinkError::Disconnected,
+            })
+    }
+}
+
+/// The time after which a blocked write to a [`UnixSocketSink`]'s socket fails.
+#[cfg(unix)]
+const UNIX_SOCKET_WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A sink writing events to a Unix domain socket, as one JSON object per line.
+///
+/// The events are written by a dedicated thread, so that commits never wait on the socket.  If the
+/// reader falls `capacity` events behind, or a write fails or times out, the sink is removed from
+/// the feed and the socket closed.
+#[cfg(unix)]
+pub struct UnixSocketSink {
+    sender: mpsc::SyncSender<Vec<u8>>,
+    capacity: usize,
+}
+
+#[cfg(unix)]
+impl UnixSocketSink {
+    /// Connects to the Unix domain socket at the given path, holding at most `capacity` events
+    /// which haven't been written to it yet.
+    pub fn connect<P: AsRef<Path>>(path: P, capacity: usize) -> Result<Self, TrieChangeSinkError> {
+        let mut stream = UnixStream::connect(path)?;
+        stream.set_write_timeout(Some(UNIX_SOCKET_WRITE_TIMEOUT))?;
+        let (sender, receiver) = mpsc::sync_channel::<Vec<u8>>(capacity);
+        thread::Builder::new()
+            .name("trie-change-feed".to_string())
+            .spawn(move || {
+                for line in receiver {
+                    if let Err(error) = stream.write_all(&line) {
+                        // Dropping the receiver makes the next send fail, removing the sink.
+                        warn!(%error, "failed to write to trie change feed socket");
+                        return;
+                    }
+                }
+                debug!("trie change feed socket closed");
+            })?;
+        Ok(UnixSocketSink { sender, capacity })
+    }
+}
+
+#[cfg(unix)]
+impl TrieChangeSink for UnixSocketSink {
+    fn send(&self, event: &TrieChangeEvent) -> Result<(), TrieChangeSinkError> {
+        let mut line = serde_json::to_vec(event)?;
+        line.push(b'\n');
+        self.sender.try_send(line).map_err(|error| match error {
+            mpsc::TrySendError::Full(_) => TrieChangeSinkError::Full(self.capacity),
+            mpsc::TrySendError::Disconnected(_) => TrieChangeSinkError::Disconnected,
+        })
+    }
+}
+
+/// A function connecting a new sink.
+type Connect = Box<dyn Fn() -> Result<Box<dyn TrieChangeSink>, TrieChangeSinkError> + Send + Sync>;
+
+/// A sink registered with a [`TrieChangeFeed`].
+struct RegisteredSink {
+    /// The connected sink, or `None` while waiting to be reconnected.
+    sink: Option<Box<dyn TrieChangeSink>>,
+    /// Connects a replacement for the sink once it fails, if it can be reconnected.
+    connect: Option<Connect>,
+    /// The minimum time between attempts to reconnect.
+    reconnect_interval: Duration,
+    /// The time of the last attempt to connect.
+    last_connect_attempt: Option<Instant>,
+    /// Whether any change sets weren't delivered since the last one which was.
+    missed_changes: bool,
+}
+
+impl RegisteredSink {
+    /// Tries to connect the sink, unless the last attempt was too recent.
+    fn connect(&mut self) -> Result<(), TrieChangeSinkError> {
+        let connect = match &self.connect {
+            Some(connect) => connect,
+            None => return Ok(()),
+        };
+        if let Some(last_connect_attempt) = self.last_connect_attempt {
+            if last_connect_attempt.elapsed() < self.reconnect_interval {
+                return Ok(());
+            }
+        }
+        self.last_connect_attempt = Some(Instant::now());
+        self.sink = Some(connect()?);
+        Ok(())
+    }
+
+    /// Sends `event` to the sink, preceded by a gap marker if it missed any change sets.
+    ///
+    /// Returns `false` if the sink failed and can't be reconnected, so should be removed.
+    fn publish(&mut self, event: &TrieChangeEvent) -> bool {
+        if self.sink.is_none() {
+            match self.connect() {
+                Ok(()) if self.sink.is_some() => info!("reconnected trie change sink"),
+                Ok(()) => (),
+                Err(error) => debug!(%error, "failed to reconnect trie change sink"),
+            }
+        }
+        let sink = match &self.sink {
+            Some(sink) => sink,
+            None => {
+                self.missed_changes = true;
+                return true;
+            }
+        };
+
+        let result = if self.missed_changes {
+            sink.send(&TrieChangeEvent::Gap)
+        } else {
+            Ok(())
+        }
+        .and_then(|()| sink.send(event));
+        match result {
+            Ok(()) => {
+                self.missed_changes = false;
+                true
+            }
+            Err(error) => {
+                self.sink = None;
+                self.missed_changes = true;
+                if self.connect.is_some() {
+                    warn!(%error, "disconnecting trie change sink until it can be reconnected");
+                    true
+                } else {
+                    warn!(%error, "removing trie change sink");
+                    false
+                }
+            }
+        }
+    }
+}
+
+/// The set of sinks notified of the changes committed to global state.
+#[derive(Default)]
+pub struct TrieChangeFeed {
+    sinks: RwLock<Vec<RegisteredSink>>,
+}
+
+impl TrieChangeFeed {
+    /// Registers a sink to be notified of all subsequent commits.
+    ///
+    /// The sink is removed from the feed once sending to it fails.
+    pub fn register(&self, sink: Box<dyn TrieChangeSink>) {
+        self.sinks
+            .write()
+            .expect("should lock sinks")
+            .push(RegisteredSink {
+                sink: Some(sink),
+                connect: None,
+                reconnect_interval: DEFAULT_TRIE_CHANGE_SINK_RECONNECT_INTERVAL,
+                last_connect_attempt: None,
+                missed_changes: false,
+            });
+    }
+
+    /// Registers a sink connected by calling `connect`, to be notified of all subsequent commits.
+    ///
+    /// Whenever the sink fails, or if it can't be connected now, `connect` is called again on a
+    /// later commit, at most once per `reconnect_interval`.  The replacement sink is first sent a
+    /// [`TrieChangeEvent::Gap`].  Returns the error of connecting now, if any, in which case the
+    /// sink is still registered.
+    pub fn register_reconnecting<F>(
+        &self,
+        connect: F,
+        reconnect_interval: Duration,
+    ) -> Result<(), TrieChangeSinkError>
+    where
+        F: Fn() -> Result<Box<dyn TrieChangeSink>, TrieChangeSinkError> + Send + Sync + 'static,
+    {
+        let mut registered_sink = RegisteredSink {
+            sink: None,
+            connect: Some(Box::new(connect)),
+            reconnect_interval,
+            last_connect_attempt: None,
+            missed_changes: false,
+        };
+        let result = registered_sink.connect();
+        self.sinks
+            .write()
+            .expect("should lock sinks")
+            .push(registered_sink);
+        result
+    }
+
+    /// Returns `true` if any sinks are registered.
+    ///
+    /// Computing the changes made by a commit requires extra reads of global state, which can be
+    /// skipped if nobody is listening.
+    pub fn has_sinks(&self) -> bool {
+        !self.sinks.read().expect("should lock sinks").is_empty()
+    }
+
+    /// Sends the given change set to every registered sink, removing any sink which fails and
+    /// can't be reconnected.
+    pub fn publish(&self, change_set: TrieChangeSet) {
+        let event = TrieChangeEvent::Changes(change_set);
+        self.sinks
+            .write()
+            .expect("should lock sinks")
+            .retain_mut(|registered_sink| registered_sink.publish(&event));
+    }
+
+    /// Records that the changes made by a commit couldn't be published, so that every sink is
+    /// sent a [`TrieChangeEvent::Gap`] before the next change set.
+    pub fn record_gap(&self) {
+        for registered_sink in self.sinks.write().expect("should lock sinks").iter_mut() {
+            registered_sink.missed_changes = true;
+        }
+    }
+}
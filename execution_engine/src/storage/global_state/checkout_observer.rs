@@ -0,0 +1,14 @@
+//! Observation of checkouts of global state.
+//!
+//! Every read of global state starts with a checkout of a state root.  A [`CheckoutObserver`]
+//! registered with an [`LmdbGlobalState`](super::lmdb::LmdbGlobalState) is told how long each
+//! checkout took and whether the state root was found, allowing the embedder to export these as
+//! metrics.
+
+use std::time::Duration;
+
+/// A receiver of measurements of checkouts of global state.
+pub trait CheckoutObserver: Send + Sync {
+    /// Records a single checkout which took `duration`, and whether its state root was found.
+    fn observe_checkout(&self, duration: Duration, found: bool);
+}
@@ -16,13 +16,16 @@ use crate::{
             Transaction, TransactionSource,
         },
         trie::{
-            merkle_proof::TrieMerkleProof, operations::create_hashed_empty_trie, Trie, TrieRaw,
+            merkle_proof::{TrieMerkleAbsenceProof, TrieMerkleMultiProof, TrieMerkleProof},
+            operations::create_hashed_empty_trie,
+            Trie, TrieRaw,
         },
         trie_store::{
             in_memory::InMemoryTrieStore,
             operations::{
-                self, delete, keys_with_prefix, missing_children, put_trie, read, read_with_proof,
-                DeleteResult, ReadResult, WriteResult,
+                self, delete, keys_with_prefix, missing_children, put_trie, read,
+                read_with_absence_proof, read_with_multi_proof, read_with_proof, DeleteResult,
+                ReadResult, WriteResult,
             },
         },
     },
@@ -235,6 +238,17 @@ impl StateProvider for InMemoryGlobalState {
         Ok(maybe_state)
     }
 
+    fn has_root(&self, state_hash: Digest) -> Result<bool, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let maybe_root_bytes = Store::<Digest, Trie<Digest, StoredValue>>::get_raw(
+            &*self.trie_store,
+            &txn,
+            &state_hash,
+        )?;
+        txn.commit()?;
+        Ok(maybe_root_bytes.is_some())
+    }
+
     fn empty_root(&self) -> Digest {
         self.empty_root_hash
     }
@@ -284,6 +298,50 @@ impl StateProvider for InMemoryGlobalState {
         Ok(missing_descendants)
     }
 
+    fn multi_proof(
+        &self,
+        correlation_id: CorrelationId,
+        root: Digest,
+        keys: &[Key],
+    ) -> Result<Option<TrieMerkleMultiProof<Key, StoredValue>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read_with_multi_proof::<
+            Key,
+            StoredValue,
+            InMemoryReadTransaction,
+            InMemoryTrieStore,
+            Self::Error,
+        >(correlation_id, &txn, self.trie_store.deref(), &root, keys)?
+        {
+            ReadResult::Found(proof) => Some(proof),
+            ReadResult::NotFound | ReadResult::RootNotFound => None,
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn absence_proof(
+        &self,
+        correlation_id: CorrelationId,
+        root: Digest,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleAbsenceProof<Key, StoredValue>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read_with_absence_proof::<
+            Key,
+            StoredValue,
+            InMemoryReadTransaction,
+            InMemoryTrieStore,
+            Self::Error,
+        >(correlation_id, &txn, self.trie_store.deref(), &root, key)?
+        {
+            ReadResult::Found(proof) => Some(proof),
+            ReadResult::NotFound | ReadResult::RootNotFound => None,
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+
     fn delete_keys(
         &self,
         correlation_id: CorrelationId,
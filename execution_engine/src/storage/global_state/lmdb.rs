@@ -1,26 +1,40 @@
-use std::{collections::HashMap, ops::Deref, sync::Arc};
+use std::{
+    collections::{BTreeSet, HashMap},
+    ops::Deref,
+    sync::Arc,
+    time::Instant,
+};
+
+use tracing::warn;
 
 use casper_hashing::Digest;
-use casper_types::{Key, StoredValue};
+use casper_types::{bytesrepr::ToBytes, Key, StoredValue};
 
 use crate::{
     shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
     storage::{
         error,
         global_state::{
-            commit, put_stored_values, scratch::ScratchGlobalState, CommitProvider, StateProvider,
-            StateReader,
+            change_feed::{TrieChange, TrieChangeFeed, TrieChangeSet},
+            checkout_observer::CheckoutObserver,
+            commit, prune_keys, put_stored_values,
+            recording::RecordingGlobalState,
+            scratch::ScratchGlobalState,
+            CommitError, CommitProvider, StateProvider, StateReader,
         },
         store::Store,
         transaction_source::{lmdb::LmdbEnvironment, Transaction, TransactionSource},
         trie::{
-            merkle_proof::TrieMerkleProof, operations::create_hashed_empty_trie, Trie, TrieRaw,
+            merkle_proof::{TrieMerkleAbsenceProof, TrieMerkleMultiProof, TrieMerkleProof},
+            operations::create_hashed_empty_trie,
+            Trie, TrieRaw,
         },
         trie_store::{
             lmdb::{LmdbTrieStore, ScratchTrieStore},
             operations::{
-                delete, keys_with_prefix, missing_children, put_trie, read, read_with_proof,
-                DeleteResult, ReadResult,
+                delete, keys_with_prefix, missing_children, put_trie, read,
+                read_with_absence_proof, read_with_multi_proof, read_with_proof, DeleteResult,
+                ReadResult,
             },
         },
     },
@@ -35,6 +49,10 @@ pub struct LmdbGlobalState {
     // TODO: make this a lazy-static
     /// Empty root hash used for a new trie.
     pub(crate) empty_root_hash: Digest,
+    /// Sinks notified of the changes made by each commit.
+    pub(crate) change_feed: Arc<TrieChangeFeed>,
+    /// Observer of every checkout, if any.
+    pub(crate) checkout_observer: Option<Arc<dyn CheckoutObserver>>,
 }
 
 /// Represents a "view" of global state at a particular root hash.
@@ -75,16 +93,42 @@ impl LmdbGlobalState {
             environment,
             trie_store,
             empty_root_hash,
+            change_feed: Arc::new(TrieChangeFeed::default()),
+            checkout_observer: None,
         }
     }
 
+    /// Sets the observer notified of every checkout, including those of scratch states created
+    /// from this state.
+    pub fn with_checkout_observer(mut self, checkout_observer: Arc<dyn CheckoutObserver>) -> Self {
+        self.checkout_observer = Some(checkout_observer);
+        self
+    }
+
     /// Creates an in-memory cache for changes written.
     pub fn create_scratch(&self) -> ScratchGlobalState {
-        ScratchGlobalState::new(
+        let scratch = ScratchGlobalState::new(
+            Arc::clone(&self.environment),
+            Arc::clone(&self.trie_store),
+            self.empty_root_hash,
+        );
+        match &self.checkout_observer {
+            Some(checkout_observer) => {
+                scratch.with_checkout_observer(Arc::clone(checkout_observer))
+            }
+            None => scratch,
+        }
+    }
+
+    /// Creates a view of this state recording every value read through it.
+    ///
+    /// Changes committed through the returned state are not published to this state's change feed.
+    pub fn create_recording(&self) -> RecordingGlobalState<LmdbGlobalState> {
+        RecordingGlobalState::new(LmdbGlobalState::new(
             Arc::clone(&self.environment),
             Arc::clone(&self.trie_store),
             self.empty_root_hash,
-        )
+        ))
     }
 
     /// Write stored values to LMDB.
@@ -106,11 +150,128 @@ impl LmdbGlobalState {
         Ok(new_state_root)
     }
 
+    /// Computes the state root hash which writing `stored_values` on top of `prestate_hash` and
+    /// then deleting `pruned_keys` would produce, keeping the new tries in memory rather than
+    /// writing them to LMDB.
+    ///
+    /// Pruned keys which don't exist are skipped.
+    pub fn compute_post_state_hash(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Digest,
+        stored_values: HashMap<Key, StoredValue>,
+        pruned_keys: &BTreeSet<Key>,
+    ) -> Result<Digest, error::Error> {
+        let scratch_trie = self.get_scratch_store();
+        let post_state_hash = put_stored_values::<_, _, error::Error>(
+            &scratch_trie,
+            &scratch_trie,
+            correlation_id,
+            prestate_hash,
+            stored_values,
+        )?;
+        let mut txn = scratch_trie.create_read_write_txn()?;
+        let post_state_hash = prune_keys::<_, _, error::Error>(
+            correlation_id,
+            &mut txn,
+            &scratch_trie,
+            post_state_hash,
+            pruned_keys,
+        )?;
+        txn.commit()?;
+        Ok(post_state_hash)
+    }
+
     /// Gets a scratch trie store.
     fn get_scratch_store(&self) -> ScratchTrieStore {
         ScratchTrieStore::new(Arc::clone(&self.trie_store), Arc::clone(&self.environment))
     }
 
+    /// Returns the feed of the changes committed to this global state, with which sinks can be
+    /// registered.
+    pub fn change_feed(&self) -> &TrieChangeFeed {
+        &self.change_feed
+    }
+
+    /// Publishes the changes to the given keys between `pre_state_hash` and `post_state_hash` to
+    /// the change feed.
+    ///
+    /// Keys whose value is the same under both roots are omitted.  Does nothing if no sinks are
+    /// registered.  The commit has already happened, so failing to compute the changes is only
+    /// logged, and the sinks are sent a gap marker before the next change set instead.
+    pub fn publish_changes<I>(
+        &self,
+        correlation_id: CorrelationId,
+        pre_state_hash: Digest,
+        post_state_hash: Digest,
+        keys: I,
+    ) where
+        I: IntoIterator<Item = Key>,
+    {
+        if !self.change_feed.has_sinks() {
+            return;
+        }
+
+        match self.compute_changes(correlation_id, pre_state_hash, post_state_hash, keys) {
+            Ok(changes) => self.change_feed.publish(TrieChangeSet {
+                pre_state_hash,
+                post_state_hash,
+                changes,
+            }),
+            Err(error) => {
+                warn!(
+                    %error,
+                    %pre_state_hash,
+                    %post_state_hash,
+                    "failed to compute changes for trie change feed"
+                );
+                self.change_feed.record_gap();
+            }
+        }
+    }
+
+    /// Returns the changes to the given keys between `pre_state_hash` and `post_state_hash`,
+    /// ordered by key.
+    fn compute_changes<I>(
+        &self,
+        correlation_id: CorrelationId,
+        pre_state_hash: Digest,
+        post_state_hash: Digest,
+        keys: I,
+    ) -> Result<Vec<TrieChange>, error::Error>
+    where
+        I: IntoIterator<Item = Key>,
+    {
+        let txn = self.environment.create_read_txn()?;
+        let value_hash = |root: &Digest, key: &Key| -> Result<Option<Digest>, error::Error> {
+            match read::<Key, StoredValue, lmdb::RoTransaction, LmdbTrieStore, error::Error>(
+                correlation_id,
+                &txn,
+                self.trie_store.deref(),
+                root,
+                key,
+            )? {
+                ReadResult::Found(value) => Ok(Some(Digest::hash(value.to_bytes()?))),
+                ReadResult::NotFound => Ok(None),
+                ReadResult::RootNotFound => Err(CommitError::ReadRootNotFound(*root).into()),
+            }
+        };
+        let mut changes = vec![];
+        for key in keys.into_iter().collect::<BTreeSet<_>>() {
+            let old_value_hash = value_hash(&pre_state_hash, &key)?;
+            let new_value_hash = value_hash(&post_state_hash, &key)?;
+            if old_value_hash != new_value_hash {
+                changes.push(TrieChange {
+                    key,
+                    old_value_hash,
+                    new_value_hash,
+                });
+            }
+        }
+        txn.commit()?;
+        Ok(changes)
+    }
+
     /// Get a reference to the lmdb global state's environment.
     #[must_use]
     pub fn environment(&self) -> &LmdbEnvironment {
@@ -217,14 +378,20 @@ impl CommitProvider for LmdbGlobalState {
         prestate_hash: Digest,
         effects: AdditiveMap<Key, Transform>,
     ) -> Result<Digest, Self::Error> {
-        commit::<LmdbEnvironment, LmdbTrieStore, _, Self::Error>(
+        let keys: Vec<Key> = if self.change_feed.has_sinks() {
+            effects.keys().copied().collect()
+        } else {
+            vec![]
+        };
+        let post_state_hash = commit::<LmdbEnvironment, LmdbTrieStore, _, Self::Error>(
             &self.environment,
             &self.trie_store,
             correlation_id,
             prestate_hash,
             effects,
-        )
-        .map_err(Into::into)
+        )?;
+        self.publish_changes(correlation_id, prestate_hash, post_state_hash, keys);
+        Ok(post_state_hash)
     }
 }
 
@@ -234,6 +401,7 @@ impl StateProvider for LmdbGlobalState {
     type Reader = LmdbGlobalStateView;
 
     fn checkout(&self, state_hash: Digest) -> Result<Option<Self::Reader>, Self::Error> {
+        let start = Instant::now();
         let txn = self.environment.create_read_txn()?;
         let maybe_root: Option<Trie<Key, StoredValue>> = self.trie_store.get(&txn, &state_hash)?;
         let maybe_state = maybe_root.map(|_| LmdbGlobalStateView {
@@ -242,9 +410,23 @@ impl StateProvider for LmdbGlobalState {
             root_hash: state_hash,
         });
         txn.commit()?;
+        if let Some(checkout_observer) = &self.checkout_observer {
+            checkout_observer.observe_checkout(start.elapsed(), maybe_state.is_some());
+        }
         Ok(maybe_state)
     }
 
+    fn has_root(&self, state_hash: Digest) -> Result<bool, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let maybe_root_bytes = Store::<Digest, Trie<Digest, StoredValue>>::get_raw(
+            &*self.trie_store,
+            &txn,
+            &state_hash,
+        )?;
+        txn.commit()?;
+        Ok(maybe_root_bytes.is_some())
+    }
+
     fn empty_root(&self) -> Digest {
         self.empty_root_hash
     }
@@ -293,6 +475,50 @@ impl StateProvider for LmdbGlobalState {
         Ok(missing_hashes)
     }
 
+    fn multi_proof(
+        &self,
+        correlation_id: CorrelationId,
+        root: Digest,
+        keys: &[Key],
+    ) -> Result<Option<TrieMerkleMultiProof<Key, StoredValue>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read_with_multi_proof::<
+            Key,
+            StoredValue,
+            lmdb::RoTransaction,
+            LmdbTrieStore,
+            Self::Error,
+        >(correlation_id, &txn, self.trie_store.deref(), &root, keys)?
+        {
+            ReadResult::Found(proof) => Some(proof),
+            ReadResult::NotFound | ReadResult::RootNotFound => None,
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn absence_proof(
+        &self,
+        correlation_id: CorrelationId,
+        root: Digest,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleAbsenceProof<Key, StoredValue>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read_with_absence_proof::<
+            Key,
+            StoredValue,
+            lmdb::RoTransaction,
+            LmdbTrieStore,
+            Self::Error,
+        >(correlation_id, &txn, self.trie_store.deref(), &root, key)?
+        {
+            ReadResult::Found(proof) => Some(proof),
+            ReadResult::NotFound | ReadResult::RootNotFound => None,
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+
     /// Delete keys.
     fn delete_keys(
         &self,
@@ -332,12 +558,22 @@ mod tests {
     use lmdb::DatabaseFlags;
     use tempfile::tempdir;
 
+    use std::{iter, sync::Mutex, time::Duration};
+
     use casper_hashing::Digest;
-    use casper_types::{account::AccountHash, CLValue};
+    use casper_types::{
+        account::AccountHash,
+        bytesrepr::{self, ToBytes},
+        CLValue,
+    };
 
     use super::*;
     use crate::storage::{
-        trie_store::operations::{write, WriteResult},
+        global_state::change_feed::{ChannelSink, TrieChangeEvent, TrieChangeSink},
+        trie_store::{
+            node_cache::TrieNodeCache,
+            operations::{write, WriteResult},
+        },
         DEFAULT_TEST_MAX_DB_SIZE, DEFAULT_TEST_MAX_READERS,
     };
 
@@ -431,6 +667,101 @@ mod tests {
         }
     }
 
+    #[test]
+    fn multi_proof_verifies_against_root() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state(create_test_pairs);
+        let absent_key = Key::Account(AccountHash::new([3_u8; 32]));
+        let keys: Vec<Key> = create_test_pairs()
+            .iter()
+            .map(|pair| pair.key)
+            .chain(iter::once(absent_key))
+            .collect();
+
+        let proof = state
+            .multi_proof(correlation_id, root_hash, &keys)
+            .unwrap()
+            .expect("should have root");
+        assert_eq!(proof.compute_state_hash().unwrap(), root_hash);
+        let expected: Vec<(Key, StoredValue)> = create_test_pairs()
+            .iter()
+            .cloned()
+            .map(|TestPair { key, value }| (key, value))
+            .collect();
+        let entries: Vec<(Key, StoredValue)> = proof
+            .entries()
+            .into_iter()
+            .map(|(key, value)| (*key, value.clone()))
+            .collect();
+        assert_eq!(entries, expected);
+
+        // Shared tries are only included once, so the proof is smaller than the single proofs
+        // combined.
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        let single_proofs_length: usize = create_test_pairs()
+            .iter()
+            .map(|TestPair { key, .. }| {
+                checkout
+                    .read_with_proof(correlation_id, key)
+                    .unwrap()
+                    .unwrap()
+                    .serialized_length()
+            })
+            .sum();
+        assert!(proof.serialized_length() < single_proofs_length);
+        bytesrepr::test_serialization_roundtrip(&proof);
+
+        let fake_hash: Digest = Digest::hash([1u8; 32]);
+        assert!(state
+            .multi_proof(correlation_id, fake_hash, &keys)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn absence_proof_verifies_pruned_key_against_root() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs = create_test_pairs();
+        let (state, root_hash) = create_test_state(create_test_pairs);
+        let pruned_key = test_pairs[0].key;
+
+        let mut effects = AdditiveMap::new();
+        effects.insert(pruned_key, Transform::Prune(pruned_key));
+        let pruned_hash = state.commit(correlation_id, root_hash, effects).unwrap();
+
+        let proof = state
+            .absence_proof(correlation_id, pruned_hash, &pruned_key)
+            .unwrap()
+            .expect("should prove absence of pruned key");
+        assert_eq!(*proof.key(), pruned_key);
+        assert!(proof.proves_absence().unwrap());
+        assert_eq!(proof.compute_state_hash().unwrap(), pruned_hash);
+        bytesrepr::test_serialization_roundtrip(&proof);
+
+        // A key which was never written is absent too.
+        let absent_key = Key::Account(AccountHash::new([3_u8; 32]));
+        let proof = state
+            .absence_proof(correlation_id, root_hash, &absent_key)
+            .unwrap()
+            .expect("should prove absence of missing key");
+        assert!(proof.proves_absence().unwrap());
+        assert_eq!(proof.compute_state_hash().unwrap(), root_hash);
+
+        // No absence proof exists for a present key or under an unknown root.
+        assert!(state
+            .absence_proof(correlation_id, root_hash, &pruned_key)
+            .unwrap()
+            .is_none());
+        assert!(state
+            .absence_proof(correlation_id, pruned_hash, &test_pairs[1].key)
+            .unwrap()
+            .is_none());
+        assert!(state
+            .absence_proof(correlation_id, Digest::hash([1u8; 32]), &pruned_key)
+            .unwrap()
+            .is_none());
+    }
+
     #[test]
     fn checkout_fails_if_unknown_hash_is_given() {
         let (state, _) = create_test_state(create_test_pairs);
@@ -439,6 +770,111 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn has_root_reports_presence_of_state_root() {
+        let (state, root_hash) = create_test_state(create_test_pairs);
+        assert!(state.has_root(root_hash).unwrap());
+        assert!(state.has_root(state.empty_root()).unwrap());
+        assert!(!state.has_root(Digest::hash([1u8; 32])).unwrap());
+    }
+
+    #[test]
+    fn checkout_fails_for_root_of_aborted_commit() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state(create_test_pairs);
+        let trie_store = (*state.trie_store)
+            .clone()
+            .with_node_cache(Arc::new(TrieNodeCache::new(100)));
+        let state = LmdbGlobalState::new(
+            Arc::clone(&state.environment),
+            Arc::new(trie_store),
+            state.empty_root_hash,
+        );
+
+        let new_pair = &create_test_pairs_updated()[2];
+        let write_effects = || {
+            let mut effects = AdditiveMap::new();
+            effects.insert(new_pair.key, Transform::Write(new_pair.value.clone()));
+            effects
+        };
+        let aborted_root = state
+            .create_scratch()
+            .commit(correlation_id, root_hash, write_effects())
+            .unwrap();
+
+        // The write is applied and its root read back before adding to a missing key fails,
+        // aborting the commit.
+        let mut effects = write_effects();
+        let missing_key = Key::Account(AccountHash::new([4_u8; 32]));
+        effects.insert(missing_key, Transform::AddInt32(1));
+        assert!(matches!(
+            state.commit(correlation_id, root_hash, effects),
+            Err(error::Error::CommitError(CommitError::KeyNotFound(key))) if key == missing_key
+        ));
+
+        assert!(!state.has_root(aborted_root).unwrap());
+        assert!(state.checkout(aborted_root).unwrap().is_none());
+        assert!(state.checkout(root_hash).unwrap().is_some());
+    }
+
+    #[test]
+    fn commit_fails_alike_under_differently_seeded_hashers() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state(create_test_pairs);
+
+        let missing_keys: Vec<Key> = (4_u8..20)
+            .rev()
+            .map(|byte| Key::Account(AccountHash::new([byte; 32])))
+            .collect();
+        let first_missing_key = *missing_keys.iter().min().unwrap();
+
+        // Every map is built with a freshly seeded hasher, so iterating the effects would visit
+        // the missing keys in a different order each time, and report a different one.
+        for _ in 0..10 {
+            let effects = || {
+                let mut effects = AdditiveMap::new();
+                for key in &missing_keys {
+                    effects.insert(*key, Transform::AddInt32(1));
+                }
+                effects
+            };
+            assert!(matches!(
+                state.commit(correlation_id, root_hash, effects()),
+                Err(error::Error::CommitError(CommitError::KeyNotFound(key)))
+                    if key == first_missing_key
+            ));
+            assert!(matches!(
+                state.create_scratch().commit(correlation_id, root_hash, effects()),
+                Err(error::Error::CommitError(CommitError::KeyNotFound(key)))
+                    if key == first_missing_key
+            ));
+        }
+    }
+
+    #[test]
+    fn checkouts_are_reported_to_checkout_observer() {
+        struct TestObserver(Mutex<Vec<bool>>);
+
+        impl CheckoutObserver for TestObserver {
+            fn observe_checkout(&self, _duration: Duration, found: bool) {
+                self.0.lock().unwrap().push(found);
+            }
+        }
+
+        let (state, root_hash) = create_test_state(create_test_pairs);
+        let observer = Arc::new(TestObserver(Mutex::new(vec![])));
+        let state = state.with_checkout_observer(Arc::clone(&observer) as Arc<_>);
+
+        assert!(state.checkout(root_hash).unwrap().is_some());
+        assert!(state.checkout(Digest::hash([1u8; 32])).unwrap().is_none());
+        assert!(state
+            .create_scratch()
+            .checkout(root_hash)
+            .unwrap()
+            .is_some());
+        assert_eq!(*observer.0.lock().unwrap(), vec![true, false, true]);
+    }
+
     #[test]
     fn commit_updates_state() {
         let correlation_id = CorrelationId::new();
@@ -466,6 +902,194 @@ mod tests {
         }
     }
 
+    #[test]
+    fn commit_prunes_keys() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs = create_test_pairs();
+        let missing_key = Key::Account(AccountHash::new([3_u8; 32]));
+
+        let (state, root_hash) = create_test_state(create_test_pairs);
+
+        let effects: AdditiveMap<Key, Transform> = {
+            let mut tmp = AdditiveMap::new();
+            tmp.insert(test_pairs[0].key, Transform::Prune(test_pairs[0].key));
+            tmp.insert(missing_key, Transform::Prune(missing_key));
+            tmp
+        };
+
+        let updated_hash = state.commit(correlation_id, root_hash, effects).unwrap();
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        assert_eq!(
+            None,
+            updated_checkout
+                .read(correlation_id, &test_pairs[0].key)
+                .unwrap()
+        );
+        assert_eq!(
+            Some(test_pairs[1].value.clone()),
+            updated_checkout
+                .read(correlation_id, &test_pairs[1].key)
+                .unwrap()
+        );
+
+        let original_checkout = state.checkout(root_hash).unwrap().unwrap();
+        assert_eq!(
+            Some(test_pairs[0].value.clone()),
+            original_checkout
+                .read(correlation_id, &test_pairs[0].key)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn commit_publishes_changes_to_change_feed() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs = create_test_pairs();
+        let new_pair = &create_test_pairs_updated()[2];
+        let (state, root_hash) = create_test_state(create_test_pairs);
+        let (sink, receiver) = ChannelSink::new(10);
+        state.change_feed().register(Box::new(sink));
+
+        let effects: AdditiveMap<Key, Transform> = {
+            let mut tmp = AdditiveMap::new();
+            tmp.insert(test_pairs[0].key, Transform::Prune(test_pairs[0].key));
+            tmp.insert(
+                test_pairs[1].key,
+                Transform::Write(test_pairs[1].value.clone()),
+            );
+            tmp.insert(new_pair.key, Transform::Write(new_pair.value.clone()));
+            tmp
+        };
+        let updated_hash = state.commit(correlation_id, root_hash, effects).unwrap();
+
+        let value_hash = |value: &StoredValue| Digest::hash(value.to_bytes().unwrap());
+        let change_set = match receiver.try_recv().expect("should publish change set") {
+            TrieChangeEvent::Changes(change_set) => change_set,
+            TrieChangeEvent::Gap => panic!("should not publish a gap"),
+        };
+        assert_eq!(change_set.pre_state_hash, root_hash);
+        assert_eq!(change_set.post_state_hash, updated_hash);
+        // The unchanged value written under the second key is omitted.
+        assert_eq!(
+            change_set.changes,
+            vec![
+                TrieChange {
+                    key: test_pairs[0].key,
+                    old_value_hash: Some(value_hash(&test_pairs[0].value)),
+                    new_value_hash: None,
+                },
+                TrieChange {
+                    key: new_pair.key,
+                    old_value_hash: None,
+                    new_value_hash: Some(value_hash(&new_pair.value)),
+                },
+            ]
+        );
+
+        drop(receiver);
+        let _ = state
+            .commit(correlation_id, updated_hash, AdditiveMap::new())
+            .unwrap();
+        assert!(!state.change_feed().has_sinks());
+    }
+
+    #[test]
+    fn change_feed_removes_sink_which_falls_behind() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state(create_test_pairs);
+        let (sink, receiver) = ChannelSink::new(1);
+        state.change_feed().register(Box::new(sink));
+
+        let updated_hash = state
+            .commit(correlation_id, root_hash, AdditiveMap::new())
+            .unwrap();
+        assert!(state.change_feed().has_sinks());
+
+        // The first change set hasn't been received, so the second doesn't fit in the channel.
+        let _ = state
+            .commit(correlation_id, updated_hash, AdditiveMap::new())
+            .unwrap();
+        assert!(!state.change_feed().has_sinks());
+        assert_eq!(receiver.try_iter().count(), 1);
+    }
+
+    #[test]
+    fn change_feed_reconnects_sink_after_gap() {
+        let correlation_id = CorrelationId::new();
+        let (state, mut root_hash) = create_test_state(create_test_pairs);
+        let receivers = Arc::new(Mutex::new(vec![]));
+        let connect = {
+            let receivers = Arc::clone(&receivers);
+            move || -> Result<Box<dyn TrieChangeSink>, _> {
+                let (sink, receiver) = ChannelSink::new(2);
+                receivers.lock().unwrap().push(receiver);
+                Ok(Box::new(sink))
+            }
+        };
+        state
+            .change_feed()
+            .register_reconnecting(connect, Duration::ZERO)
+            .unwrap();
+
+        // The third change set doesn't fit in the first sink's channel, so it is disconnected.  The
+        // fourth is sent to a reconnected sink, preceded by a gap marker.
+        let mut post_state_hashes = vec![];
+        for _ in 0..4 {
+            root_hash = state
+                .commit(correlation_id, root_hash, AdditiveMap::new())
+                .unwrap();
+            post_state_hashes.push(root_hash);
+        }
+        assert!(state.change_feed().has_sinks());
+
+        let receivers = receivers.lock().unwrap();
+        assert_eq!(receivers.len(), 2);
+        assert_eq!(receivers[0].try_iter().count(), 2);
+        let events: Vec<_> = receivers[1].try_iter().collect();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0], TrieChangeEvent::Gap);
+        match &events[1] {
+            TrieChangeEvent::Changes(change_set) => {
+                assert_eq!(change_set.post_state_hash, post_state_hashes[3])
+            }
+            TrieChangeEvent::Gap => panic!("should send change set after gap"),
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn change_feed_writes_to_unix_socket() {
+        use std::{
+            io::{BufRead, BufReader},
+            os::unix::net::UnixListener,
+        };
+
+        use crate::storage::global_state::change_feed::UnixSocketSink;
+
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state(create_test_pairs);
+        let socket_dir = tempdir().unwrap();
+        let socket_path = socket_dir.path().join("trie-changes.sock");
+        let listener = UnixListener::bind(&socket_path).unwrap();
+        let sink = UnixSocketSink::connect(&socket_path, 10).unwrap();
+        state.change_feed().register(Box::new(sink));
+        let (stream, _) = listener.accept().unwrap();
+
+        let updated_hash = state
+            .commit(correlation_id, root_hash, AdditiveMap::new())
+            .unwrap();
+
+        let mut line = String::new();
+        BufReader::new(stream).read_line(&mut line).unwrap();
+        let change_set = match serde_json::from_str(&line).unwrap() {
+            TrieChangeEvent::Changes(change_set) => change_set,
+            TrieChangeEvent::Gap => panic!("should not write a gap"),
+        };
+        assert_eq!(change_set.pre_state_hash, root_hash);
+        assert_eq!(change_set.post_state_hash, updated_hash);
+    }
+
     #[test]
     fn commit_updates_state_and_original_state_stays_intact() {
         let correlation_id = CorrelationId::new();
@@ -1,11 +1,20 @@
 //! Global state.
 
+/// A feed of the changes committed to global state.
+pub mod change_feed;
+
+/// Observation of checkouts of global state.
+pub mod checkout_observer;
+
 /// In-memory implementation of global state.
 pub mod in_memory;
 
 /// Lmdb implementation of global state.
 pub mod lmdb;
 
+/// Global state recording the values read through it.
+pub mod recording;
+
 /// Lmdb implementation of global state with cache.
 pub mod scratch;
 
@@ -23,10 +32,13 @@ use crate::{
         transform::{self, Transform},
     },
     storage::{
-        transaction_source::{Transaction, TransactionSource},
-        trie::{merkle_proof::TrieMerkleProof, Trie, TrieRaw},
+        transaction_source::{Readable, Transaction, TransactionSource, Writable},
+        trie::{
+            merkle_proof::{TrieMerkleAbsenceProof, TrieMerkleMultiProof, TrieMerkleProof},
+            Trie, TrieRaw,
+        },
         trie_store::{
-            operations::{read, write, ReadResult, WriteResult},
+            operations::{delete, read, write, ReadResult, WriteResult},
             TrieStore,
         },
     },
@@ -103,6 +115,10 @@ pub trait StateProvider {
     /// Checkouts to the post state of a specific block.
     fn checkout(&self, state_hash: Digest) -> Result<Option<Self::Reader>, Self::Error>;
 
+    /// Returns `true` if the given state root is present, without decoding it or constructing a
+    /// reader.
+    fn has_root(&self, state_hash: Digest) -> Result<bool, Self::Error>;
+
     /// Returns an empty root hash.
     fn empty_root(&self) -> Digest;
 
@@ -123,6 +139,24 @@ pub trait StateProvider {
         trie_raw: &[u8],
     ) -> Result<Vec<Digest>, Self::Error>;
 
+    /// Returns a single merkle proof of the values of all of the given `keys` present under `root`,
+    /// or `None` if `root` is not found.
+    fn multi_proof(
+        &self,
+        correlation_id: CorrelationId,
+        root: Digest,
+        keys: &[Key],
+    ) -> Result<Option<TrieMerkleMultiProof<Key, StoredValue>>, Self::Error>;
+
+    /// Returns a merkle proof that `key` is not present under `root`, or `None` if `root` is not
+    /// found or `key` is present under it.
+    fn absence_proof(
+        &self,
+        correlation_id: CorrelationId,
+        root: Digest,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleAbsenceProof<Key, StoredValue>>, Self::Error>;
+
     /// Delete key from the global state.
     fn delete_keys(
         &self,
@@ -152,7 +186,10 @@ where
     if maybe_root.is_none() {
         return Err(CommitError::RootNotFound(prestate_hash).into());
     };
-    for (key, value) in stored_values.iter() {
+    // Written in key order, like effects in `commit`.
+    let mut stored_values: Vec<_> = stored_values.iter().collect();
+    stored_values.sort_unstable_by_key(|(key, _)| *key);
+    for (key, value) in stored_values {
         let write_result =
             write::<_, _, _, _, E>(correlation_id, &mut txn, store, &state_root, key, value)?;
         match write_result {
@@ -194,7 +231,17 @@ where
         return Err(CommitError::RootNotFound(prestate_hash).into());
     };
 
-    for (key, transform) in effects.into_iter() {
+    // Effects are applied in key order so that every node performs the same sequence of writes and,
+    // should the commit fail, reports the same error.
+    // Pruned keys are deleted together once all other effects are applied.  No key has both a prune
+    // and another transform, so the resulting state is the same as deleting them in key order.
+    let mut pruned_keys = Vec::new();
+    for (key, transform) in effects.into_sorted_vec() {
+        if let Transform::Prune(_) = transform {
+            pruned_keys.push(key);
+            continue;
+        }
+
         let read_result = read::<_, _, _, _, E>(correlation_id, &txn, store, &state_root, &key)?;
 
         let value = match (read_result, transform) {
@@ -246,7 +293,40 @@ where
         }
     }
 
+    state_root = prune_keys::<_, _, E>(correlation_id, &mut txn, store, state_root, &pruned_keys)?;
+
     txn.commit()?;
 
     Ok(state_root)
 }
+
+/// Deletes `keys` from the trie under `state_root` within `txn`, returning the resulting state root.
+///
+/// Keys which don't exist are skipped.
+pub(crate) fn prune_keys<'a, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    mut state_root: Digest,
+    keys: impl IntoIterator<Item = &'a Key>,
+) -> Result<Digest, E>
+where
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<Key, StoredValue>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<bytesrepr::Error> + From<CommitError>,
+{
+    for key in keys {
+        match delete::<_, _, _, _, E>(correlation_id, txn, store, &state_root, key)? {
+            DeleteResult::Deleted(root_hash) => {
+                state_root = root_hash;
+            }
+            DeleteResult::DoesNotExist => (),
+            DeleteResult::RootNotFound => {
+                error!(?state_root, ?key, "Error pruning key");
+                return Err(CommitError::WriteRootNotFound(state_root).into());
+            }
+        }
+    }
+    Ok(state_root)
+}
@@ -0,0 +1,250 @@
+use std::{
+    collections::BTreeMap,
+    mem,
+    sync::{Arc, Mutex},
+};
+
+use casper_hashing::Digest;
+use casper_types::{Key, StoredValue};
+
+use crate::{
+    shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
+    storage::{
+        global_state::{CommitProvider, StateProvider, StateReader},
+        trie::{
+            merkle_proof::{TrieMerkleAbsenceProof, TrieMerkleMultiProof, TrieMerkleProof},
+            TrieRaw,
+        },
+        trie_store::operations::DeleteResult,
+    },
+};
+
+type SharedReads = Arc<Mutex<BTreeMap<Key, StoredValue>>>;
+
+/// Global state which records every value read through it.
+///
+/// The recorded values are sufficient to re-execute the same operations against an
+/// [`InMemoryGlobalState`](super::in_memory::InMemoryGlobalState) built from them, without access
+/// to the original state.
+pub struct RecordingGlobalState<S> {
+    state: S,
+    reads: SharedReads,
+}
+
+/// Represents a "view" of recording global state at a particular root hash.
+pub struct RecordingGlobalStateView<R> {
+    reader: R,
+    reads: SharedReads,
+}
+
+impl<S> RecordingGlobalState<S> {
+    /// Creates a state recording the values read from `state`.
+    pub fn new(state: S) -> Self {
+        RecordingGlobalState {
+            state,
+            reads: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    /// Returns the values read so far, clearing the record.
+    pub fn take_reads(&self) -> BTreeMap<Key, StoredValue> {
+        mem::take(&mut *self.reads.lock().unwrap())
+    }
+}
+
+impl<R> RecordingGlobalStateView<R> {
+    fn record(&self, key: Key, value: StoredValue) {
+        let _ = self.reads.lock().unwrap().insert(key, value);
+    }
+}
+
+impl<R> StateReader<Key, StoredValue> for RecordingGlobalStateView<R>
+where
+    R: StateReader<Key, StoredValue>,
+{
+    type Error = R::Error;
+
+    fn read(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, Self::Error> {
+        let maybe_value = self.reader.read(correlation_id, key)?;
+        if let Some(value) = &maybe_value {
+            self.record(*key, value.clone());
+        }
+        Ok(maybe_value)
+    }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error> {
+        let maybe_proof = self.reader.read_with_proof(correlation_id, key)?;
+        if let Some(proof) = &maybe_proof {
+            self.record(*key, proof.value().clone());
+        }
+        Ok(maybe_proof)
+    }
+
+    /// Returns the keys matching `prefix`, recording the value under each of them.
+    ///
+    /// The values are recorded even if they're never read, so that the same keys are found when
+    /// the recorded values are replayed.
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<Vec<Key>, Self::Error> {
+        let keys = self.reader.keys_with_prefix(correlation_id, prefix)?;
+        for key in &keys {
+            let _ = self.read(correlation_id, key)?;
+        }
+        Ok(keys)
+    }
+}
+
+impl<S> CommitProvider for RecordingGlobalState<S>
+where
+    S: CommitProvider,
+{
+    fn commit(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Digest,
+        effects: AdditiveMap<Key, Transform>,
+    ) -> Result<Digest, Self::Error> {
+        self.state.commit(correlation_id, state_hash, effects)
+    }
+}
+
+impl<S> StateProvider for RecordingGlobalState<S>
+where
+    S: StateProvider,
+{
+    type Error = S::Error;
+
+    type Reader = RecordingGlobalStateView<S::Reader>;
+
+    fn checkout(&self, state_hash: Digest) -> Result<Option<Self::Reader>, Self::Error> {
+        let maybe_reader = self.state.checkout(state_hash)?;
+        Ok(maybe_reader.map(|reader| RecordingGlobalStateView {
+            reader,
+            reads: Arc::clone(&self.reads),
+        }))
+    }
+
+    fn has_root(&self, state_hash: Digest) -> Result<bool, Self::Error> {
+        self.state.has_root(state_hash)
+    }
+
+    fn empty_root(&self) -> Digest {
+        self.state.empty_root()
+    }
+
+    fn get_trie_full(
+        &self,
+        correlation_id: CorrelationId,
+        trie_key: &Digest,
+    ) -> Result<Option<TrieRaw>, Self::Error> {
+        self.state.get_trie_full(correlation_id, trie_key)
+    }
+
+    fn put_trie(&self, correlation_id: CorrelationId, trie: &[u8]) -> Result<Digest, Self::Error> {
+        self.state.put_trie(correlation_id, trie)
+    }
+
+    fn missing_children(
+        &self,
+        correlation_id: CorrelationId,
+        trie_raw: &[u8],
+    ) -> Result<Vec<Digest>, Self::Error> {
+        self.state.missing_children(correlation_id, trie_raw)
+    }
+
+    fn multi_proof(
+        &self,
+        correlation_id: CorrelationId,
+        root: Digest,
+        keys: &[Key],
+    ) -> Result<Option<TrieMerkleMultiProof<Key, StoredValue>>, Self::Error> {
+        self.state.multi_proof(correlation_id, root, keys)
+    }
+
+    fn absence_proof(
+        &self,
+        correlation_id: CorrelationId,
+        root: Digest,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleAbsenceProof<Key, StoredValue>>, Self::Error> {
+        self.state.absence_proof(correlation_id, root, key)
+    }
+
+    fn delete_keys(
+        &self,
+        correlation_id: CorrelationId,
+        root: Digest,
+        keys_to_delete: &[Key],
+    ) -> Result<DeleteResult, Self::Error> {
+        self.state.delete_keys(correlation_id, root, keys_to_delete)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{account::AccountHash, bytesrepr::ToBytes, CLValue};
+
+    use super::*;
+    use crate::storage::global_state::in_memory::InMemoryGlobalState;
+
+    fn test_pairs() -> Vec<(Key, StoredValue)> {
+        (1..=3)
+            .map(|i| {
+                (
+                    Key::Account(AccountHash::new([i; 32])),
+                    StoredValue::CLValue(CLValue::from_t(i32::from(i)).unwrap()),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn records_values_read_and_replays_them() {
+        let correlation_id = CorrelationId::new();
+        let pairs = test_pairs();
+        let (state, root_hash) = InMemoryGlobalState::from_pairs(correlation_id, &pairs).unwrap();
+        let recording = RecordingGlobalState::new(state);
+
+        let view = recording.checkout(root_hash).unwrap().unwrap();
+        let missing_key = Key::Account(AccountHash::new([9; 32]));
+        assert_eq!(
+            view.read(correlation_id, &pairs[0].0).unwrap(),
+            Some(pairs[0].1.clone())
+        );
+        assert_eq!(view.read(correlation_id, &missing_key).unwrap(), None);
+
+        let reads = recording.take_reads();
+        assert_eq!(
+            reads.into_iter().collect::<Vec<_>>(),
+            vec![pairs[0].clone()]
+        );
+        assert!(recording.take_reads().is_empty());
+
+        let prefix = [pairs[0].0.to_bytes().unwrap()[0]];
+        let keys = view.keys_with_prefix(correlation_id, &prefix).unwrap();
+        assert_eq!(keys.len(), pairs.len());
+        let reads: Vec<_> = recording.take_reads().into_iter().collect();
+        assert_eq!(reads, pairs);
+
+        let (replay_state, replay_root_hash) =
+            InMemoryGlobalState::from_pairs(correlation_id, &reads).unwrap();
+        let replay_view = replay_state.checkout(replay_root_hash).unwrap().unwrap();
+        assert_eq!(
+            replay_view
+                .keys_with_prefix(correlation_id, &prefix)
+                .unwrap(),
+            keys
+        );
+    }
+}
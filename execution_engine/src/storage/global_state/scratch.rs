@@ -1,8 +1,9 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap},
     mem,
     ops::Deref,
     sync::{Arc, RwLock},
+    time::Instant,
 };
 
 use tracing::error;
@@ -14,15 +15,22 @@ use crate::{
     shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
     storage::{
         error,
-        global_state::{CommitError, CommitProvider, StateProvider, StateReader},
+        global_state::{
+            checkout_observer::CheckoutObserver, CommitError, CommitProvider, StateProvider,
+            StateReader,
+        },
         store::Store,
         transaction_source::{lmdb::LmdbEnvironment, Transaction, TransactionSource},
-        trie::{merkle_proof::TrieMerkleProof, Trie, TrieRaw},
+        trie::{
+            merkle_proof::{TrieMerkleAbsenceProof, TrieMerkleMultiProof, TrieMerkleProof},
+            Trie, TrieRaw,
+        },
         trie_store::{
             lmdb::LmdbTrieStore,
             operations::{
-                delete, keys_with_prefix, missing_children, put_trie, read, read_with_proof,
-                DeleteResult, ReadResult,
+                delete, keys_with_prefix, missing_children, put_trie, read,
+                read_with_absence_proof, read_with_multi_proof, read_with_proof, DeleteResult,
+                ReadResult,
             },
         },
     },
@@ -32,19 +40,31 @@ type SharedCache = Arc<RwLock<Cache>>;
 
 struct Cache {
     cached_values: HashMap<Key, (bool, StoredValue)>,
+    pruned_keys: BTreeSet<Key>,
 }
 
 impl Cache {
     fn new() -> Self {
         Cache {
             cached_values: HashMap::new(),
+            pruned_keys: BTreeSet::new(),
         }
     }
 
     fn insert_write(&mut self, key: Key, value: StoredValue) {
+        self.pruned_keys.remove(&key);
         self.cached_values.insert(key, (true, value));
     }
 
+    fn insert_prune(&mut self, key: Key) {
+        self.cached_values.remove(&key);
+        self.pruned_keys.insert(key);
+    }
+
+    fn is_pruned(&self, key: &Key) -> bool {
+        self.pruned_keys.contains(key)
+    }
+
     fn insert_read(&mut self, key: Key, value: StoredValue) {
         self.cached_values.entry(key).or_insert((false, value));
     }
@@ -54,12 +74,14 @@ impl Cache {
     }
 
     /// Consumes self and returns only written values as values that were only read must be filtered
-    /// out to prevent unnecessary writes.
-    fn into_dirty_writes(self) -> HashMap<Key, StoredValue> {
-        self.cached_values
+    /// out to prevent unnecessary writes, along with the pruned keys.
+    fn into_dirty_writes(self) -> (HashMap<Key, StoredValue>, BTreeSet<Key>) {
+        let dirty_writes = self
+            .cached_values
             .into_iter()
             .filter_map(|(key, (dirty, value))| if dirty { Some((key, value)) } else { None })
-            .collect()
+            .collect();
+        (dirty_writes, self.pruned_keys)
     }
 }
 
@@ -74,6 +96,8 @@ pub struct ScratchGlobalState {
     // TODO: make this a lazy-static
     /// Empty root hash used for a new trie.
     pub(crate) empty_root_hash: Digest,
+    /// Observer of every checkout, if any.
+    checkout_observer: Option<Arc<dyn CheckoutObserver>>,
 }
 
 /// Represents a "view" of global state at a particular root hash.
@@ -100,11 +124,18 @@ impl ScratchGlobalState {
             environment,
             trie_store,
             empty_root_hash,
+            checkout_observer: None,
         }
     }
 
-    /// Consume self and return inner cache.
-    pub fn into_inner(self) -> HashMap<Key, StoredValue> {
+    /// Sets the observer notified of every checkout.
+    pub fn with_checkout_observer(mut self, checkout_observer: Arc<dyn CheckoutObserver>) -> Self {
+        self.checkout_observer = Some(checkout_observer);
+        self
+    }
+
+    /// Consume self and return inner cache, i.e. the written values and the pruned keys.
+    pub fn into_inner(self) -> (HashMap<Key, StoredValue>, BTreeSet<Key>) {
         let cache = mem::replace(&mut *self.cache.write().unwrap(), Cache::new());
         cache.into_dirty_writes()
     }
@@ -118,8 +149,14 @@ impl StateReader<Key, StoredValue> for ScratchGlobalStateView {
         correlation_id: CorrelationId,
         key: &Key,
     ) -> Result<Option<StoredValue>, Self::Error> {
-        if let Some(value) = self.cache.read().unwrap().get(key) {
-            return Ok(Some(value.clone()));
+        {
+            let cache = self.cache.read().unwrap();
+            if cache.is_pruned(key) {
+                return Ok(None);
+            }
+            if let Some(value) = cache.get(key) {
+                return Ok(Some(value.clone()));
+            }
         }
         let txn = self.environment.create_read_txn()?;
         let ret = match read::<Key, StoredValue, lmdb::RoTransaction, LmdbTrieStore, Self::Error>(
@@ -201,7 +238,11 @@ impl CommitProvider for ScratchGlobalState {
         state_hash: Digest,
         effects: AdditiveMap<Key, Transform>,
     ) -> Result<Digest, Self::Error> {
-        for (key, transform) in effects.into_iter() {
+        for (key, transform) in effects.into_sorted_vec() {
+            if let Transform::Prune(_) = transform {
+                self.cache.write().unwrap().insert_prune(key);
+                continue;
+            }
             let cached_value = self.cache.read().unwrap().get(&key).cloned();
             let value = match (cached_value, transform) {
                 (None, Transform::Write(new_value)) => new_value,
@@ -268,6 +309,7 @@ impl StateProvider for ScratchGlobalState {
     type Reader = ScratchGlobalStateView;
 
     fn checkout(&self, state_hash: Digest) -> Result<Option<Self::Reader>, Self::Error> {
+        let start = Instant::now();
         let txn = self.environment.create_read_txn()?;
         let maybe_root: Option<Trie<Key, StoredValue>> = self.trie_store.get(&txn, &state_hash)?;
         let maybe_state = maybe_root.map(|_| ScratchGlobalStateView {
@@ -277,9 +319,23 @@ impl StateProvider for ScratchGlobalState {
             root_hash: state_hash,
         });
         txn.commit()?;
+        if let Some(checkout_observer) = &self.checkout_observer {
+            checkout_observer.observe_checkout(start.elapsed(), maybe_state.is_some());
+        }
         Ok(maybe_state)
     }
 
+    fn has_root(&self, state_hash: Digest) -> Result<bool, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let maybe_root_bytes = Store::<Digest, Trie<Digest, StoredValue>>::get_raw(
+            &*self.trie_store,
+            &txn,
+            &state_hash,
+        )?;
+        txn.commit()?;
+        Ok(maybe_root_bytes.is_some())
+    }
+
     fn empty_root(&self) -> Digest {
         self.empty_root_hash
     }
@@ -328,6 +384,50 @@ impl StateProvider for ScratchGlobalState {
         Ok(missing_descendants)
     }
 
+    fn multi_proof(
+        &self,
+        correlation_id: CorrelationId,
+        root: Digest,
+        keys: &[Key],
+    ) -> Result<Option<TrieMerkleMultiProof<Key, StoredValue>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read_with_multi_proof::<
+            Key,
+            StoredValue,
+            lmdb::RoTransaction,
+            LmdbTrieStore,
+            Self::Error,
+        >(correlation_id, &txn, self.trie_store.deref(), &root, keys)?
+        {
+            ReadResult::Found(proof) => Some(proof),
+            ReadResult::NotFound | ReadResult::RootNotFound => None,
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn absence_proof(
+        &self,
+        correlation_id: CorrelationId,
+        root: Digest,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleAbsenceProof<Key, StoredValue>>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read_with_absence_proof::<
+            Key,
+            StoredValue,
+            lmdb::RoTransaction,
+            LmdbTrieStore,
+            Self::Error,
+        >(correlation_id, &txn, self.trie_store.deref(), &root, key)?
+        {
+            ReadResult::Found(proof) => Some(proof),
+            ReadResult::NotFound | ReadResult::RootNotFound => None,
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+
     fn delete_keys(
         &self,
         correlation_id: CorrelationId,
@@ -510,7 +610,8 @@ mod tests {
             .keys_with_prefix(correlation_id, &[])
             .unwrap();
 
-        let stored_values = scratch.into_inner();
+        let (stored_values, pruned_keys) = scratch.into_inner();
+        assert!(pruned_keys.is_empty());
         assert_eq!(all_keys.len(), stored_values.len());
 
         for key in all_keys {
@@ -592,6 +693,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn commit_prunes_keys() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs = create_test_pairs();
+
+        let TestState { state, root_hash } = create_test_state();
+        let scratch = state.create_scratch();
+
+        let effects: AdditiveMap<Key, Transform> = {
+            let mut tmp = AdditiveMap::new();
+            tmp.insert(test_pairs[0].key, Transform::Prune(test_pairs[0].key));
+            tmp
+        };
+        scratch.commit(correlation_id, root_hash, effects).unwrap();
+
+        let scratch_checkout = scratch.checkout(root_hash).unwrap().unwrap();
+        assert_eq!(
+            None,
+            scratch_checkout
+                .read(correlation_id, &test_pairs[0].key)
+                .unwrap()
+        );
+        assert_eq!(
+            Some(test_pairs[1].value.clone()),
+            scratch_checkout
+                .read(correlation_id, &test_pairs[1].key)
+                .unwrap()
+        );
+
+        let (stored_values, pruned_keys) = scratch.into_inner();
+        assert!(stored_values.is_empty());
+        assert_eq!(
+            pruned_keys.into_iter().collect::<Vec<_>>(),
+            vec![test_pairs[0].key]
+        );
+    }
+
     #[test]
     fn commit_updates_state_and_original_state_stays_intact() {
         let correlation_id = CorrelationId::new();
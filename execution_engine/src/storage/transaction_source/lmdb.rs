@@ -8,7 +8,7 @@ use lmdb::{
 use crate::storage::{
     error,
     transaction_source::{Readable, Transaction, TransactionSource, Writable},
-    trie_store::lmdb::ScratchTrieStore,
+    trie_store::lmdb::{decode_trie_bytes, ScratchTrieStore},
     MAX_DBS,
 };
 
@@ -28,7 +28,7 @@ impl Readable for ScratchTrieStore {
     fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Bytes>, Self::Error> {
         let txn = self.env.create_read_txn()?;
         match lmdb::Transaction::get(&txn, handle.store.get_db(), &key) {
-            Ok(bytes) => Ok(Some(Bytes::from(bytes))),
+            Ok(bytes) => decode_trie_bytes(Bytes::from(bytes)).map(Some),
             Err(lmdb::Error::NotFound) => Ok(None),
             Err(e) => Err(error::Error::Lmdb(e)),
         }
@@ -76,6 +76,10 @@ impl<'a> Readable for RoTransaction<'a> {
             Err(e) => Err(e),
         }
     }
+
+    fn is_read_only(&self) -> bool {
+        true
+    }
 }
 
 impl<'a> Transaction for RwTransaction<'a> {
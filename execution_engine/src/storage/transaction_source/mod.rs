@@ -29,6 +29,12 @@ pub trait Transaction: Sized {
 pub trait Readable: Transaction {
     /// Returns the value from the corresponding key from a given [`Transaction::Handle`].
     fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Bytes>, Self::Error>;
+
+    /// Returns `true` if the transaction can't write, so that everything it reads has already been
+    /// committed.
+    fn is_read_only(&self) -> bool {
+        false
+    }
 }
 
 /// A transaction with the capability to write to a given [`Handle`](Transaction::Handle).
@@ -3,13 +3,31 @@ use std::collections::VecDeque;
 use serde::{Deserialize, Serialize};
 
 use casper_hashing::Digest;
-use casper_types::bytesrepr::{self, Bytes, FromBytes, ToBytes};
+use casper_types::{
+    bytesrepr::{self, Bytes, FromBytes, ToBytes},
+    UREF_SERIALIZED_LENGTH,
+};
 
 use crate::storage::trie::{Pointer, Trie, RADIX};
 
 const TRIE_MERKLE_PROOF_STEP_NODE_ID: u8 = 0;
 const TRIE_MERKLE_PROOF_STEP_EXTENSION_ID: u8 = 1;
 
+const TRIE_MERKLE_MULTI_PROOF_TRIE_PRUNED_ID: u8 = 0;
+const TRIE_MERKLE_MULTI_PROOF_TRIE_LEAF_ID: u8 = 1;
+const TRIE_MERKLE_MULTI_PROOF_TRIE_NODE_ID: u8 = 2;
+const TRIE_MERKLE_MULTI_PROOF_TRIE_EXTENSION_ID: u8 = 3;
+
+/// The length of the longest serialized global state key, that of a `Key::URef`.
+const MAX_KEY_SERIALIZED_LENGTH: usize = 1 + UREF_SERIALIZED_LENGTH;
+
+/// The maximum number of levels of a [`TrieMerkleMultiProofTrie`] accepted when deserializing.
+///
+/// Each node and each extension on the path to a leaf consumes at least one byte of its key, and
+/// no extension is directly followed by another, so no path holds more than two tries per key byte
+/// plus the leaf.
+const TRIE_MERKLE_MULTI_PROOF_MAX_DEPTH: usize = 2 * MAX_KEY_SERIALIZED_LENGTH + 1;
+
 /// A component of a proof that an entry exists in the Merkle trie.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TrieMerkleProofStep {
@@ -160,6 +178,9 @@ where
     ///
     /// 3. When there are no more steps, we return the final hash we have computed.
     ///
+    /// Fails if the pointers of a node step are not listed once each, in order of their indices, or
+    /// if one of them is at the step's hole index.
+    ///
     /// The steps in this function reflect `operations::rehash`.
     pub fn compute_state_hash(&self) -> Result<Digest, bytesrepr::Error> {
         let mut hash = {
@@ -179,7 +200,13 @@ where
                     indexed_pointers_with_hole,
                 } => {
                     let hole_index = *hole_index;
-                    assert!(hole_index as usize <= RADIX, "hole_index exceeded RADIX");
+                    check_children_order(indexed_pointers_with_hole)?;
+                    if indexed_pointers_with_hole
+                        .iter()
+                        .any(|(index, _)| *index == hole_index)
+                    {
+                        return Err(bytesrepr::Error::Formatting);
+                    }
                     let mut indexed_pointers = indexed_pointers_with_hole.to_owned();
                     indexed_pointers.push((hole_index, pointer));
                     Trie::<K, V>::node(&indexed_pointers).to_bytes()?
@@ -235,6 +262,381 @@ where
     }
 }
 
+/// A trie on the paths covered by a [`TrieMerkleMultiProof`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrieMerkleMultiProofTrie<K, V> {
+    /// A subtrie which is not on any of the proven paths, represented by the pointer to it.
+    Pruned(Pointer),
+    /// Corresponds to [`Trie::Leaf`]
+    Leaf {
+        /// Key.
+        key: K,
+        /// Value.
+        value: V,
+    },
+    /// Corresponds to [`Trie::Node`]
+    Node {
+        /// Indexed children.
+        children: Vec<(u8, TrieMerkleMultiProofTrie<K, V>)>,
+    },
+    /// Corresponds to [`Trie::Extension`]
+    Extension {
+        /// Affix bytes.
+        affix: Bytes,
+        /// Child.
+        child: Box<TrieMerkleMultiProofTrie<K, V>>,
+    },
+}
+
+impl<K, V> TrieMerkleMultiProofTrie<K, V>
+where
+    K: ToBytes + Clone,
+    V: ToBytes + Clone,
+{
+    /// Returns the pointer to this trie, computing its hash from its children if required.
+    fn pointer(&self) -> Result<Pointer, bytesrepr::Error> {
+        let pointer = match self {
+            TrieMerkleMultiProofTrie::Pruned(pointer) => *pointer,
+            TrieMerkleMultiProofTrie::Leaf { key, value } => {
+                Pointer::LeafPointer(Trie::leaf(key.clone(), value.clone()).trie_hash()?)
+            }
+            TrieMerkleMultiProofTrie::Node { children } => {
+                check_children_order(children)?;
+                let indexed_pointers = children
+                    .iter()
+                    .map(|(index, child)| Ok((*index, child.pointer()?)))
+                    .collect::<Result<Vec<_>, bytesrepr::Error>>()?;
+                Pointer::NodePointer(Trie::<K, V>::node(&indexed_pointers).trie_hash()?)
+            }
+            TrieMerkleMultiProofTrie::Extension { affix, child } => Pointer::NodePointer(
+                Trie::<K, V>::extension(affix.clone().into(), child.pointer()?).trie_hash()?,
+            ),
+        };
+        Ok(pointer)
+    }
+}
+
+/// Returns an error unless the indices of `children` are strictly increasing.
+///
+/// A node's hash only covers the last child given for an index, so a child listed twice, or out of
+/// order, could hold leaves which are not in the trie while the proof still hashes to its root.
+fn check_children_order<T>(children: &[(u8, T)]) -> Result<(), bytesrepr::Error> {
+    if children.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+        return Err(bytesrepr::Error::Formatting);
+    }
+    Ok(())
+}
+
+impl<K, V> TrieMerkleMultiProofTrie<K, V> {
+    /// Appends the leaves of this trie to `entries`, in trie order.
+    fn collect_entries<'a>(&'a self, entries: &mut Vec<(&'a K, &'a V)>) {
+        match self {
+            TrieMerkleMultiProofTrie::Pruned(_) => {}
+            TrieMerkleMultiProofTrie::Leaf { key, value } => entries.push((key, value)),
+            TrieMerkleMultiProofTrie::Node { children } => children
+                .iter()
+                .for_each(|(_, child)| child.collect_entries(entries)),
+            TrieMerkleMultiProofTrie::Extension { child, .. } => child.collect_entries(entries),
+        }
+    }
+}
+
+impl<K, V> ToBytes for TrieMerkleMultiProofTrie<K, V>
+where
+    K: ToBytes,
+    V: ToBytes,
+{
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret: Vec<u8> = bytesrepr::allocate_buffer(self)?;
+        match self {
+            TrieMerkleMultiProofTrie::Pruned(pointer) => {
+                ret.push(TRIE_MERKLE_MULTI_PROOF_TRIE_PRUNED_ID);
+                ret.append(&mut pointer.to_bytes()?)
+            }
+            TrieMerkleMultiProofTrie::Leaf { key, value } => {
+                ret.push(TRIE_MERKLE_MULTI_PROOF_TRIE_LEAF_ID);
+                ret.append(&mut key.to_bytes()?);
+                ret.append(&mut value.to_bytes()?)
+            }
+            TrieMerkleMultiProofTrie::Node { children } => {
+                ret.push(TRIE_MERKLE_MULTI_PROOF_TRIE_NODE_ID);
+                ret.append(&mut children.to_bytes()?)
+            }
+            TrieMerkleMultiProofTrie::Extension { affix, child } => {
+                ret.push(TRIE_MERKLE_MULTI_PROOF_TRIE_EXTENSION_ID);
+                ret.append(&mut affix.to_bytes()?);
+                ret.append(&mut child.to_bytes()?)
+            }
+        };
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        std::mem::size_of::<u8>()
+            + match self {
+                TrieMerkleMultiProofTrie::Pruned(pointer) => pointer.serialized_length(),
+                TrieMerkleMultiProofTrie::Leaf { key, value } => {
+                    key.serialized_length() + value.serialized_length()
+                }
+                TrieMerkleMultiProofTrie::Node { children } => children.serialized_length(),
+                TrieMerkleMultiProofTrie::Extension { affix, child } => {
+                    affix.serialized_length() + child.serialized_length()
+                }
+            }
+    }
+}
+
+impl<K, V> FromBytes for TrieMerkleMultiProofTrie<K, V>
+where
+    K: FromBytes,
+    V: FromBytes,
+{
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        depth_limited_from_bytes(0, bytes)
+    }
+}
+
+/// Deserializes a [`TrieMerkleMultiProofTrie`] found `depth` levels below the root, rejecting
+/// tries deeper than [`TRIE_MERKLE_MULTI_PROOF_MAX_DEPTH`] levels.
+fn depth_limited_from_bytes<K, V>(
+    depth: usize,
+    bytes: &[u8],
+) -> Result<(TrieMerkleMultiProofTrie<K, V>, &[u8]), bytesrepr::Error>
+where
+    K: FromBytes,
+    V: FromBytes,
+{
+    if depth >= TRIE_MERKLE_MULTI_PROOF_MAX_DEPTH {
+        return Err(bytesrepr::Error::ExceededRecursionDepth);
+    }
+    let depth = depth + 1;
+    let (tag, rem): (u8, &[u8]) = FromBytes::from_bytes(bytes)?;
+    match tag {
+        TRIE_MERKLE_MULTI_PROOF_TRIE_PRUNED_ID => {
+            let (pointer, rem) = Pointer::from_bytes(rem)?;
+            Ok((TrieMerkleMultiProofTrie::Pruned(pointer), rem))
+        }
+        TRIE_MERKLE_MULTI_PROOF_TRIE_LEAF_ID => {
+            let (key, rem) = K::from_bytes(rem)?;
+            let (value, rem) = V::from_bytes(rem)?;
+            Ok((TrieMerkleMultiProofTrie::Leaf { key, value }, rem))
+        }
+        TRIE_MERKLE_MULTI_PROOF_TRIE_NODE_ID => {
+            let (count, mut rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+            if count as usize > RADIX {
+                return Err(bytesrepr::Error::Formatting);
+            }
+            let mut children = Vec::with_capacity(count as usize);
+            for _ in 0..count {
+                let (index, child_rem): (u8, &[u8]) = FromBytes::from_bytes(rem)?;
+                let (child, child_rem) = depth_limited_from_bytes(depth, child_rem)?;
+                children.push((index, child));
+                rem = child_rem;
+            }
+            check_children_order(&children)?;
+            Ok((TrieMerkleMultiProofTrie::Node { children }, rem))
+        }
+        TRIE_MERKLE_MULTI_PROOF_TRIE_EXTENSION_ID => {
+            let (affix, rem) = FromBytes::from_bytes(rem)?;
+            let (child, rem) = depth_limited_from_bytes(depth, rem)?;
+            Ok((
+                TrieMerkleMultiProofTrie::Extension {
+                    affix,
+                    child: Box::new(child),
+                },
+                rem,
+            ))
+        }
+        _ => Err(bytesrepr::Error::Formatting),
+    }
+}
+
+/// A proof that several nodes with specified keys and values are present in the Merkle trie.
+///
+/// Holds the part of the trie spanned by the paths from the root to the proven leaves, with every
+/// subtrie off those paths pruned to the pointer to it.  Tries shared by several paths are thus
+/// included only once.  Given a state hash `x`, one can validate a proof `p` by checking
+/// `x == p.compute_state_hash()`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrieMerkleMultiProof<K, V> {
+    root: TrieMerkleMultiProofTrie<K, V>,
+}
+
+impl<K, V> TrieMerkleMultiProof<K, V> {
+    /// Constructor for [`TrieMerkleMultiProof`]
+    pub fn new(root: TrieMerkleMultiProofTrie<K, V>) -> Self {
+        TrieMerkleMultiProof { root }
+    }
+
+    /// Getter for the root trie in [`TrieMerkleMultiProof`]
+    pub fn root(&self) -> &TrieMerkleMultiProofTrie<K, V> {
+        &self.root
+    }
+
+    /// Returns the keys and values proven by this [`TrieMerkleMultiProof`], in trie order.
+    ///
+    /// The entries can only be trusted once [`TrieMerkleMultiProof::compute_state_hash`] has
+    /// returned the expected state hash.
+    pub fn entries(&self) -> Vec<(&K, &V)> {
+        let mut entries = vec![];
+        self.root.collect_entries(&mut entries);
+        entries
+    }
+}
+
+impl<K, V> TrieMerkleMultiProof<K, V>
+where
+    K: ToBytes + Clone,
+    V: ToBytes + Clone,
+{
+    /// Recomputes a state root hash from a [`TrieMerkleMultiProof`].
+    ///
+    /// The hash of every trie in the proof is computed from its children, starting with the leaves
+    /// and pruned pointers, up to the root.  Fails if the children of any node are not listed once
+    /// each, in order of their indices.
+    pub fn compute_state_hash(&self) -> Result<Digest, bytesrepr::Error> {
+        self.root.pointer().map(|pointer| *pointer.hash())
+    }
+}
+
+impl<K, V> ToBytes for TrieMerkleMultiProof<K, V>
+where
+    K: ToBytes,
+    V: ToBytes,
+{
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.root.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.root.serialized_length()
+    }
+}
+
+impl<K, V> FromBytes for TrieMerkleMultiProof<K, V>
+where
+    K: FromBytes,
+    V: FromBytes,
+{
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (root, rem) = FromBytes::from_bytes(bytes)?;
+        Ok((TrieMerkleMultiProof { root }, rem))
+    }
+}
+
+/// A proof that no node with a specified `key` is present in the Merkle trie.
+///
+/// Holds the tries on the path from the root towards `key`, with every subtrie off that path pruned
+/// to the pointer to it, down to the trie at which the path ends: a node with no child at the key's
+/// next byte, an extension whose affix differs from the key's next bytes, or a leaf holding another
+/// key.  Given a state hash `x`, one can validate a proof `p` by checking `p.proves_absence()` and
+/// `x == p.compute_state_hash()`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrieMerkleAbsenceProof<K, V> {
+    key: K,
+    root: TrieMerkleMultiProofTrie<K, V>,
+}
+
+impl<K, V> TrieMerkleAbsenceProof<K, V> {
+    /// Constructor for [`TrieMerkleAbsenceProof`]
+    pub fn new(key: K, root: TrieMerkleMultiProofTrie<K, V>) -> Self {
+        TrieMerkleAbsenceProof { key, root }
+    }
+
+    /// Getter for the key in [`TrieMerkleAbsenceProof`]
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Getter for the root trie in [`TrieMerkleAbsenceProof`]
+    pub fn root(&self) -> &TrieMerkleMultiProofTrie<K, V> {
+        &self.root
+    }
+}
+
+impl<K, V> TrieMerkleAbsenceProof<K, V>
+where
+    K: ToBytes + Clone + PartialEq,
+    V: ToBytes + Clone,
+{
+    /// Returns `true` if the path towards [`TrieMerkleAbsenceProof::key`] ends without reaching a
+    /// leaf holding it.
+    ///
+    /// Returns `false` if the path reaches such a leaf, or a pruned subtrie which might hold it.
+    pub fn proves_absence(&self) -> Result<bool, bytesrepr::Error> {
+        let path = self.key.to_bytes()?;
+        let mut depth: usize = 0;
+        let mut current = &self.root;
+        loop {
+            match current {
+                TrieMerkleMultiProofTrie::Pruned(_) => return Ok(false),
+                TrieMerkleMultiProofTrie::Leaf { key, .. } => return Ok(*key != self.key),
+                TrieMerkleMultiProofTrie::Node { children } => {
+                    if check_children_order(children).is_err() {
+                        return Ok(false);
+                    }
+                    let index = match path.get(depth) {
+                        Some(index) => *index,
+                        None => return Ok(false),
+                    };
+                    match children
+                        .iter()
+                        .find(|(child_index, _)| *child_index == index)
+                    {
+                        Some((_, child)) => {
+                            depth += 1;
+                            current = child;
+                        }
+                        None => return Ok(true),
+                    }
+                }
+                TrieMerkleMultiProofTrie::Extension { affix, child } => {
+                    if path.get(depth..depth + affix.len()) != Some(affix.as_slice()) {
+                        return Ok(true);
+                    }
+                    depth += affix.len();
+                    current = child;
+                }
+            }
+        }
+    }
+
+    /// Recomputes a state root hash from a [`TrieMerkleAbsenceProof`], as for a
+    /// [`TrieMerkleMultiProof`].
+    pub fn compute_state_hash(&self) -> Result<Digest, bytesrepr::Error> {
+        self.root.pointer().map(|pointer| *pointer.hash())
+    }
+}
+
+impl<K, V> ToBytes for TrieMerkleAbsenceProof<K, V>
+where
+    K: ToBytes,
+    V: ToBytes,
+{
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret: Vec<u8> = bytesrepr::allocate_buffer(self)?;
+        ret.append(&mut self.key.to_bytes()?);
+        ret.append(&mut self.root.to_bytes()?);
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.key.serialized_length() + self.root.serialized_length()
+    }
+}
+
+impl<K, V> FromBytes for TrieMerkleAbsenceProof<K, V>
+where
+    K: FromBytes,
+    V: FromBytes,
+{
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (key, rem) = K::from_bytes(bytes)?;
+        let (root, rem) = FromBytes::from_bytes(rem)?;
+        Ok((TrieMerkleAbsenceProof { key, root }, rem))
+    }
+}
+
 #[cfg(test)]
 mod gens {
     use proptest::{collection::vec, prelude::*};
@@ -290,9 +692,18 @@ mod gens {
 mod tests {
     use proptest::prelude::*;
 
-    use casper_types::bytesrepr;
+    use casper_hashing::Digest;
+    use casper_types::{
+        account::AccountHash,
+        bytesrepr::{self, FromBytes, ToBytes},
+        CLValue, Key, StoredValue,
+    };
 
-    use super::gens;
+    use super::{
+        gens, TrieMerkleAbsenceProof, TrieMerkleMultiProof, TrieMerkleMultiProofTrie,
+        TRIE_MERKLE_MULTI_PROOF_MAX_DEPTH,
+    };
+    use crate::storage::trie::Pointer;
 
     proptest! {
         #[test]
@@ -309,4 +720,111 @@ mod tests {
             bytesrepr::test_serialization_roundtrip(&proof)
         }
     }
+
+    /// Returns a multi-proof whose root is `levels` levels deep, every trie but the last being an
+    /// extension.
+    fn nested_multi_proof(levels: usize) -> TrieMerkleMultiProof<Key, StoredValue> {
+        let pointer = Pointer::NodePointer(Digest::hash([0u8]));
+        let root = (1..levels).fold(TrieMerkleMultiProofTrie::Pruned(pointer), |child, _| {
+            TrieMerkleMultiProofTrie::Extension {
+                affix: vec![1u8].into(),
+                child: Box::new(child),
+            }
+        });
+        TrieMerkleMultiProof::new(root)
+    }
+
+    #[test]
+    fn trie_merkle_multi_proof_deserialization_should_be_depth_limited() {
+        let deepest = nested_multi_proof(TRIE_MERKLE_MULTI_PROOF_MAX_DEPTH);
+        bytesrepr::test_serialization_roundtrip(&deepest);
+
+        let too_deep = nested_multi_proof(TRIE_MERKLE_MULTI_PROOF_MAX_DEPTH + 1);
+        let bytes = too_deep.to_bytes().expect("should serialize");
+        assert_eq!(
+            TrieMerkleMultiProof::<Key, StoredValue>::from_bytes(&bytes),
+            Err(bytesrepr::Error::ExceededRecursionDepth)
+        );
+    }
+
+    #[test]
+    fn trie_merkle_absence_proof_should_only_prove_absence_where_path_ends() {
+        // The serialized key starts with its tag, 0 for an account, followed by the account hash.
+        let key = Key::Account(AccountHash::new([1u8; 32]));
+        let other_key = Key::Account(AccountHash::new([2u8; 32]));
+        let value = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+        let pruned = || TrieMerkleMultiProofTrie::Pruned(Pointer::NodePointer(Digest::hash([0u8])));
+        let leaf = |key: Key| TrieMerkleMultiProofTrie::Leaf {
+            key,
+            value: value.clone(),
+        };
+        let node = |children: Vec<(u8, TrieMerkleMultiProofTrie<Key, StoredValue>)>| {
+            TrieMerkleMultiProofTrie::Node { children }
+        };
+        let proves_absence = |root: TrieMerkleMultiProofTrie<Key, StoredValue>| {
+            let proof = TrieMerkleAbsenceProof::new(key, root);
+            if proof.root().pointer().is_ok() {
+                bytesrepr::test_serialization_roundtrip(&proof);
+            }
+            proof.proves_absence().unwrap()
+        };
+
+        assert!(proves_absence(node(vec![])));
+        assert!(proves_absence(node(vec![(1, pruned())])));
+        assert!(proves_absence(node(vec![(0, leaf(other_key))])));
+        assert!(proves_absence(TrieMerkleMultiProofTrie::Extension {
+            affix: vec![0u8, 2u8].into(),
+            child: Box::new(pruned()),
+        }));
+
+        assert!(!proves_absence(node(vec![(0, leaf(key))])));
+        assert!(!proves_absence(node(vec![(0, pruned())])));
+        assert!(!proves_absence(TrieMerkleMultiProofTrie::Extension {
+            affix: vec![0u8, 1u8].into(),
+            child: Box::new(pruned()),
+        }));
+        // Only the last child given for an index is covered by the node's hash.
+        assert!(!proves_absence(node(vec![
+            (0, leaf(other_key)),
+            (0, pruned())
+        ])));
+    }
+
+    #[test]
+    fn trie_merkle_multi_proof_should_reject_repeated_child_indices() {
+        let real_key = Key::Account(AccountHash::new([1u8; 32]));
+        let fake_key = Key::Account(AccountHash::new([2u8; 32]));
+        let value = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+        let leaf = |key: Key| TrieMerkleMultiProofTrie::Leaf {
+            key,
+            value: value.clone(),
+        };
+
+        // Only the last child given for an index is covered by the node's hash, so this proof of a
+        // fake leaf would otherwise hash to the same root as the honest one.
+        let honest = TrieMerkleMultiProof::new(TrieMerkleMultiProofTrie::Node {
+            children: vec![(0, leaf(real_key))],
+        });
+        let forged = TrieMerkleMultiProof::new(TrieMerkleMultiProofTrie::Node {
+            children: vec![(0, leaf(fake_key)), (0, leaf(real_key))],
+        });
+        assert!(honest.compute_state_hash().is_ok());
+        assert_eq!(
+            forged.compute_state_hash(),
+            Err(bytesrepr::Error::Formatting)
+        );
+        let bytes = forged.to_bytes().expect("should serialize");
+        assert_eq!(
+            TrieMerkleMultiProof::<Key, StoredValue>::from_bytes(&bytes),
+            Err(bytesrepr::Error::Formatting)
+        );
+
+        let out_of_order = TrieMerkleMultiProof::new(TrieMerkleMultiProofTrie::Node {
+            children: vec![(1, leaf(fake_key)), (0, leaf(real_key))],
+        });
+        assert_eq!(
+            out_of_order.compute_state_hash(),
+            Err(bytesrepr::Error::Formatting)
+        );
+    }
 }
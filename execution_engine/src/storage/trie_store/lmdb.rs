@@ -110,7 +110,7 @@ use std::{
 };
 
 use casper_types::{
-    bytesrepr::{self, Bytes, ToBytes},
+    bytesrepr::{self, Bytes, FromBytes, ToBytes},
     Key, StoredValue,
 };
 use lmdb::{Database, DatabaseFlags, Transaction};
@@ -122,16 +122,30 @@ use crate::storage::{
     global_state::CommitError,
     store::Store,
     transaction_source::{lmdb::LmdbEnvironment, Readable, TransactionSource, Writable},
-    trie::{self, LazyTrieLeaf, Trie},
-    trie_store::{self, TrieStore},
+    trie::{self, LazyTrieLeaf, Trie, TrieTag},
+    trie_store::{self, node_cache::TrieNodeCache, TrieStore},
 };
 
+/// Leading byte of a stored trie whose serialized form is compressed.  Serialized tries always
+/// start with a [`TrieTag`](trie::TrieTag), which never takes this value.
+const COMPRESSED_TRIE_MARKER: u8 = u8::MAX;
+
+/// Version of the encoding of compressed tries, stored after [`COMPRESSED_TRIE_MARKER`].
+///
+/// Version 1: the zstd-compressed serialized trie.
+const COMPRESSED_TRIE_VERSION: u8 = 1;
+
+/// The zstd compression level used for stored tries.
+const ZSTD_COMPRESSION_LEVEL: i32 = 3;
+
 /// An LMDB-backed trie store.
 ///
 /// Wraps [`lmdb::Database`].
 #[derive(Debug, Clone)]
 pub struct LmdbTrieStore {
     db: Database,
+    compression_threshold: Option<usize>,
+    node_cache: Option<Arc<TrieNodeCache>>,
 }
 
 impl LmdbTrieStore {
@@ -143,14 +157,22 @@ impl LmdbTrieStore {
     ) -> Result<Self, error::Error> {
         let name = Self::name(maybe_name);
         let db = env.env().create_db(Some(&name), flags)?;
-        Ok(LmdbTrieStore { db })
+        Ok(LmdbTrieStore {
+            db,
+            compression_threshold: None,
+            node_cache: None,
+        })
     }
 
     /// Constructor for `LmdbTrieStore` which opens an existing lmdb store file.
     pub fn open(env: &LmdbEnvironment, maybe_name: Option<&str>) -> Result<Self, error::Error> {
         let name = Self::name(maybe_name);
         let db = env.env().open_db(Some(&name))?;
-        Ok(LmdbTrieStore { db })
+        Ok(LmdbTrieStore {
+            db,
+            compression_threshold: None,
+            node_cache: None,
+        })
     }
 
     fn name(maybe_name: Option<&str>) -> String {
@@ -159,12 +181,76 @@ impl LmdbTrieStore {
             .unwrap_or_else(|| String::from(trie_store::NAME))
     }
 
+    /// Enables compression of stored leaves whose serialized size exceeds `threshold` bytes.
+    ///
+    /// Compressed and uncompressed tries can be read regardless of this setting, so it can be
+    /// toggled on an existing store.  Trie hashes are always computed over the uncompressed bytes.
+    pub fn with_compression_threshold(mut self, threshold: usize) -> Self {
+        self.compression_threshold = Some(threshold);
+        self
+    }
+
+    /// Enables caching of decoded trie nodes read from this store in the given cache.
+    ///
+    /// The cache is shared by all clones of this store, and so by all readers of global state.  Only
+    /// nodes read in read-only transactions are cached, so that nodes written by a transaction which
+    /// is later aborted are never cached.
+    pub fn with_node_cache(mut self, node_cache: Arc<TrieNodeCache>) -> Self {
+        self.node_cache = Some(node_cache);
+        self
+    }
+
+    /// Returns the cache of decoded trie nodes, if enabled.
+    pub fn node_cache(&self) -> Option<&TrieNodeCache> {
+        self.node_cache.as_deref()
+    }
+
     /// Get a handle to the underlying database.
     pub fn get_db(&self) -> Database {
         self.db
     }
 }
 
+/// Returns the bytes under which the given serialized trie should be stored: compressed if it is a
+/// leaf larger than `compression_threshold` and compression actually saves space, or unchanged.
+pub(crate) fn encode_trie_bytes(
+    trie_bytes: Cow<'_, [u8]>,
+    compression_threshold: Option<usize>,
+) -> Result<Cow<'_, [u8]>, error::Error> {
+    let should_compress = match compression_threshold {
+        Some(threshold) => {
+            trie_bytes.len() > threshold && trie_bytes.first() == Some(&(TrieTag::Leaf as u8))
+        }
+        None => false,
+    };
+    if !should_compress {
+        return Ok(trie_bytes);
+    }
+    let mut encoded = vec![COMPRESSED_TRIE_MARKER, COMPRESSED_TRIE_VERSION];
+    zstd::stream::copy_encode(&*trie_bytes, &mut encoded, ZSTD_COMPRESSION_LEVEL)
+        .map_err(|error| error::Error::Compression(error.to_string()))?;
+    if encoded.len() < trie_bytes.len() {
+        Ok(Cow::Owned(encoded))
+    } else {
+        Ok(trie_bytes)
+    }
+}
+
+/// Returns the serialized trie held in the given stored bytes, decompressing them if required.
+pub(crate) fn decode_trie_bytes(stored_bytes: Bytes) -> Result<Bytes, error::Error> {
+    match stored_bytes.first() {
+        Some(&COMPRESSED_TRIE_MARKER) => match stored_bytes.get(1) {
+            Some(&COMPRESSED_TRIE_VERSION) => zstd::stream::decode_all(&stored_bytes[2..])
+                .map(Bytes::from)
+                .map_err(|error| error::Error::Compression(error.to_string())),
+            _ => Err(error::Error::Compression(
+                "unknown compressed trie version".to_string(),
+            )),
+        },
+        _ => Ok(stored_bytes),
+    }
+}
+
 impl<K, V> Store<Digest, Trie<K, V>> for LmdbTrieStore {
     type Error = error::Error;
 
@@ -173,6 +259,57 @@ impl<K, V> Store<Digest, Trie<K, V>> for LmdbTrieStore {
     fn handle(&self) -> Self::Handle {
         self.db
     }
+
+    fn get<T>(&self, txn: &T, key: &Digest) -> Result<Option<Trie<K, V>>, Self::Error>
+    where
+        T: Readable<Handle = Self::Handle>,
+        Trie<K, V>: FromBytes,
+        Self::Error: From<T::Error>,
+    {
+        if let Some(node_cache) = &self.node_cache {
+            if let Some(trie) = node_cache.get(key)? {
+                return Ok(Some(trie));
+            }
+        }
+        let maybe_trie: Option<Trie<K, V>> =
+            match <Self as Store<Digest, Trie<K, V>>>::get_raw(self, txn, key)? {
+                Some(bytes) => Some(bytesrepr::deserialize_from_slice(bytes)?),
+                None => None,
+            };
+        // A read-write transaction may read tries it wrote itself and then be aborted, so only
+        // tries read in read-only transactions are known to be in the store.
+        if let (Some(node_cache), Some(trie), true) =
+            (&self.node_cache, &maybe_trie, txn.is_read_only())
+        {
+            node_cache.insert(*key, trie)?;
+        }
+        Ok(maybe_trie)
+    }
+
+    fn get_raw<T>(&self, txn: &T, key: &Digest) -> Result<Option<Bytes>, Self::Error>
+    where
+        T: Readable<Handle = Self::Handle>,
+        Self::Error: From<T::Error>,
+    {
+        txn.read(self.db, key.as_ref())?
+            .map(decode_trie_bytes)
+            .transpose()
+    }
+
+    fn put_raw<T>(
+        &self,
+        txn: &mut T,
+        key: &Digest,
+        value_bytes: Cow<'_, [u8]>,
+    ) -> Result<(), Self::Error>
+    where
+        T: Writable<Handle = Self::Handle>,
+        Self::Error: From<T::Error>,
+    {
+        let stored_bytes = encode_trie_bytes(value_bytes, self.compression_threshold)?;
+        txn.write(self.db, key.as_ref(), &stored_bytes)
+            .map_err(Into::into)
+    }
 }
 
 impl<K, V> TrieStore<K, V> for LmdbTrieStore {}
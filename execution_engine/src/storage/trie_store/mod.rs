@@ -4,6 +4,7 @@
 //! [lmdb](lmdb/index.html#usage) modules for usage examples.
 pub mod in_memory;
 pub mod lmdb;
+pub mod node_cache;
 pub(crate) mod operations;
 #[cfg(test)]
 mod tests;
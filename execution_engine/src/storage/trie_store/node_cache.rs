@@ -0,0 +1,220 @@
+//! An in-memory cache of decoded trie nodes, shared across all readers of a trie store.
+//!
+//! Every read of global state walks from the state root down through the same few levels of
+//! nodes, so deserializing those nodes on every read is wasted work.  A [`TrieNodeCache`]
+//! attached to an [`LmdbTrieStore`](super::lmdb::LmdbTrieStore) keeps the most recently used
+//! nodes and extensions keyed by their hash.  Leaves are never cached, as they are rarely read
+//! repeatedly and may be large.
+//!
+//! As tries are content-addressed, a cached node can never become stale.  Nodes are only cached
+//! once read in a read-only transaction, so every cached node has been committed to the store.
+
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::{Arc, Mutex},
+};
+
+use casper_hashing::Digest;
+use casper_types::bytesrepr::Bytes;
+use linked_hash_map::LinkedHashMap;
+
+use crate::storage::{
+    error,
+    trie::{Pointer, PointerBlock, Trie},
+};
+
+/// A receiver of the outcomes of lookups in a [`TrieNodeCache`].
+pub trait TrieNodeCacheObserver: Send + Sync {
+    /// Records a single lookup, and whether it was a hit.
+    fn observe_lookup(&self, hit: bool);
+}
+
+/// A non-leaf trie, which is independent of the key and value types of the trie.
+enum CachedNode {
+    Node { pointer_block: Box<PointerBlock> },
+    Extension { affix: Bytes, pointer: Pointer },
+}
+
+impl CachedNode {
+    fn from_trie<K, V>(trie: &Trie<K, V>) -> Option<Self> {
+        match trie {
+            Trie::Leaf { .. } => None,
+            Trie::Node { pointer_block } => Some(CachedNode::Node {
+                pointer_block: pointer_block.clone(),
+            }),
+            Trie::Extension { affix, pointer } => Some(CachedNode::Extension {
+                affix: affix.clone(),
+                pointer: *pointer,
+            }),
+        }
+    }
+
+    fn to_trie<K, V>(&self) -> Trie<K, V> {
+        match self {
+            CachedNode::Node { pointer_block } => Trie::Node {
+                pointer_block: pointer_block.clone(),
+            },
+            CachedNode::Extension { affix, pointer } => Trie::Extension {
+                affix: affix.clone(),
+                pointer: *pointer,
+            },
+        }
+    }
+}
+
+/// A least-recently-used cache of decoded trie nodes, keyed by their hash.
+pub struct TrieNodeCache {
+    capacity: usize,
+    nodes: Mutex<LinkedHashMap<Digest, Arc<CachedNode>>>,
+    observer: Option<Arc<dyn TrieNodeCacheObserver>>,
+}
+
+impl TrieNodeCache {
+    /// Creates a new cache holding at most `capacity` nodes.
+    pub fn new(capacity: usize) -> Self {
+        TrieNodeCache {
+            capacity,
+            nodes: Mutex::new(LinkedHashMap::new()),
+            observer: None,
+        }
+    }
+
+    /// Sets the observer notified of the outcome of every lookup.
+    pub fn with_observer(mut self, observer: Arc<dyn TrieNodeCacheObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Returns the maximum number of nodes held.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of nodes currently held.
+    pub fn len(&self) -> Result<usize, error::Error> {
+        Ok(self.nodes.lock()?.len())
+    }
+
+    /// Returns `true` if no nodes are currently held.
+    pub fn is_empty(&self) -> Result<bool, error::Error> {
+        Ok(self.nodes.lock()?.is_empty())
+    }
+
+    /// Returns the node with the given hash, if cached.
+    pub(crate) fn get<K, V>(&self, hash: &Digest) -> Result<Option<Trie<K, V>>, error::Error> {
+        let maybe_node = self.nodes.lock()?.get_refresh(hash).map(Arc::clone);
+        if let Some(observer) = &self.observer {
+            observer.observe_lookup(maybe_node.is_some());
+        }
+        Ok(maybe_node.map(|node| node.to_trie()))
+    }
+
+    /// Caches the given trie under its hash, unless it is a leaf, evicting the least recently used
+    /// node if the cache is full.
+    pub(crate) fn insert<K, V>(&self, hash: Digest, trie: &Trie<K, V>) -> Result<(), error::Error> {
+        if self.capacity == 0 {
+            return Ok(());
+        }
+        let node = match CachedNode::from_trie(trie) {
+            Some(node) => Arc::new(node),
+            None => return Ok(()),
+        };
+        let mut nodes = self.nodes.lock()?;
+        nodes.insert(hash, node);
+        while nodes.len() > self.capacity {
+            nodes.pop_front();
+        }
+        Ok(())
+    }
+}
+
+impl Debug for TrieNodeCache {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("TrieNodeCache")
+            .field("capacity", &self.capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use casper_types::bytesrepr::ToBytes;
+
+    use super::*;
+
+    type TestTrie = Trie<Bytes, Bytes>;
+
+    #[derive(Default)]
+    struct TestObserver {
+        hits: AtomicUsize,
+        misses: AtomicUsize,
+    }
+
+    impl TrieNodeCacheObserver for TestObserver {
+        fn observe_lookup(&self, hit: bool) {
+            let counter = if hit { &self.hits } else { &self.misses };
+            counter.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn extension(affix: u8) -> (Digest, TestTrie) {
+        let trie = Trie::Extension {
+            affix: Bytes::from(vec![affix]),
+            pointer: Pointer::NodePointer(Digest::hash([affix])),
+        };
+        (Digest::hash(trie.to_bytes().unwrap()), trie)
+    }
+
+    #[test]
+    fn should_evict_least_recently_used_node() {
+        let cache = TrieNodeCache::new(2);
+        let (hash_1, trie_1) = extension(1);
+        let (hash_2, trie_2) = extension(2);
+        let (hash_3, trie_3) = extension(3);
+
+        cache.insert(hash_1, &trie_1).unwrap();
+        cache.insert(hash_2, &trie_2).unwrap();
+        // Refresh the first node, so the second becomes the least recently used.
+        assert_eq!(
+            cache.get::<Bytes, Bytes>(&hash_1).unwrap(),
+            Some(trie_1.clone())
+        );
+        cache.insert(hash_3, &trie_3).unwrap();
+
+        assert_eq!(cache.len().unwrap(), 2);
+        assert_eq!(cache.get::<Bytes, Bytes>(&hash_1).unwrap(), Some(trie_1));
+        assert_eq!(cache.get::<Bytes, Bytes>(&hash_2).unwrap(), None);
+        assert_eq!(cache.get::<Bytes, Bytes>(&hash_3).unwrap(), Some(trie_3));
+    }
+
+    #[test]
+    fn should_not_cache_leaves() {
+        let cache = TrieNodeCache::new(10);
+        let leaf: TestTrie = Trie::Leaf {
+            key: Bytes::from(vec![1]),
+            value: Bytes::from(vec![2]),
+        };
+        let hash = Digest::hash(leaf.to_bytes().unwrap());
+
+        cache.insert(hash, &leaf).unwrap();
+
+        assert!(cache.is_empty().unwrap());
+    }
+
+    #[test]
+    fn should_report_lookups_to_observer() {
+        let observer = Arc::new(TestObserver::default());
+        let cache = TrieNodeCache::new(10).with_observer(Arc::clone(&observer) as Arc<_>);
+        let (hash, trie) = extension(1);
+
+        assert_eq!(cache.get::<Bytes, Bytes>(&hash).unwrap(), None);
+        cache.insert(hash, &trie).unwrap();
+        assert_eq!(cache.get::<Bytes, Bytes>(&hash).unwrap(), Some(trie));
+
+        assert_eq!(observer.hits.load(Ordering::SeqCst), 1);
+        assert_eq!(observer.misses.load(Ordering::SeqCst), 1);
+    }
+}
@@ -3,7 +3,13 @@ mod tests;
 
 #[cfg(test)]
 use std::collections::HashSet;
-use std::{borrow::Cow, cmp, collections::VecDeque, convert::TryInto, mem};
+use std::{
+    borrow::Cow,
+    cmp,
+    collections::{BTreeMap, VecDeque},
+    convert::TryInto,
+    mem,
+};
 
 use either::Either;
 use num_traits::FromPrimitive;
@@ -18,7 +24,10 @@ use crate::{
         transaction_source::{Readable, Writable},
         trie::{
             self,
-            merkle_proof::{TrieMerkleProof, TrieMerkleProofStep},
+            merkle_proof::{
+                TrieMerkleAbsenceProof, TrieMerkleMultiProof, TrieMerkleMultiProofTrie,
+                TrieMerkleProof, TrieMerkleProofStep,
+            },
             Parents, Pointer, PointerBlock, Trie, TrieTag, RADIX, USIZE_EXCEEDS_U8,
         },
         trie_store::TrieStore,
@@ -234,6 +243,186 @@ where
     }
 }
 
+/// Same as [`read_with_proof`], except that a single [`TrieMerkleMultiProof`] covering all of the
+/// given keys is generated.  Tries on the paths to several of the keys are included only once.
+///
+/// Keys which are not present under the given root are omitted from the proof.
+pub fn read_with_multi_proof<K, V, T, S, E>(
+    _correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    root: &Digest,
+    keys: &[K],
+) -> Result<ReadResult<TrieMerkleMultiProof<K, V>>, E>
+where
+    K: ToBytes + FromBytes + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<bytesrepr::Error>,
+{
+    if store.get(txn, root)?.is_none() {
+        return Ok(ReadResult::RootNotFound);
+    }
+
+    let mut proof_tries: BTreeMap<Digest, Trie<K, V>> = BTreeMap::new();
+    for key in keys {
+        let path: Vec<u8> = key.to_bytes()?;
+        let mut depth: usize = 0;
+        let mut path_tries: Vec<(Digest, Trie<K, V>)> = vec![];
+        let mut next_hash = *root;
+        loop {
+            let current: Trie<K, V> = match store.get(txn, &next_hash)? {
+                Some(current) => current,
+                None => {
+                    warn!(
+                        "No trie value at key: {:?} (reading from path: {:?})",
+                        next_hash, path
+                    );
+                    break;
+                }
+            };
+            let maybe_next = match &current {
+                Trie::Leaf { key: leaf_key, .. } => {
+                    if key == leaf_key {
+                        path_tries.push((next_hash, current));
+                        proof_tries.extend(path_tries.drain(..));
+                    }
+                    break;
+                }
+                Trie::Node { pointer_block } => {
+                    let index: usize = {
+                        assert!(depth < path.len(), "depth must be < {}", path.len());
+                        path[depth].into()
+                    };
+                    depth += 1;
+                    pointer_block[index].map(|pointer| *pointer.hash())
+                }
+                Trie::Extension { affix, pointer } => {
+                    let sub_path = path.get(depth..depth + affix.len());
+                    depth += affix.len();
+                    if sub_path == Some(affix.as_slice()) {
+                        Some(*pointer.hash())
+                    } else {
+                        None
+                    }
+                }
+            };
+            match maybe_next {
+                Some(hash) => {
+                    path_tries.push((next_hash, current));
+                    next_hash = hash;
+                }
+                None => break,
+            }
+        }
+    }
+
+    let root = multi_proof_trie(&mut proof_tries, Pointer::NodePointer(*root));
+    Ok(ReadResult::Found(TrieMerkleMultiProof::new(root)))
+}
+
+/// Returns a [`TrieMerkleAbsenceProof`] that the given key is not present under the given root in
+/// the given store.
+///
+/// As no such proof exists for a key which is present, [`ReadResult::NotFound`] is returned for
+/// one.
+pub fn read_with_absence_proof<K, V, T, S, E>(
+    _correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    root: &Digest,
+    key: &K,
+) -> Result<ReadResult<TrieMerkleAbsenceProof<K, V>>, E>
+where
+    K: ToBytes + FromBytes + Eq + Clone + std::fmt::Debug,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<bytesrepr::Error>,
+{
+    if store.get(txn, root)?.is_none() {
+        return Ok(ReadResult::RootNotFound);
+    }
+
+    let path: Vec<u8> = key.to_bytes()?;
+    let mut depth: usize = 0;
+    let mut proof_tries: BTreeMap<Digest, Trie<K, V>> = BTreeMap::new();
+    let mut next_hash = *root;
+    loop {
+        let current: Trie<K, V> = match store.get(txn, &next_hash)? {
+            Some(current) => current,
+            None => {
+                warn!(
+                    "No trie value at key: {:?} (reading from path: {:?})",
+                    next_hash, path
+                );
+                return Ok(ReadResult::NotFound);
+            }
+        };
+        let maybe_next = match &current {
+            Trie::Leaf { key: leaf_key, .. } => {
+                if key == leaf_key {
+                    return Ok(ReadResult::NotFound);
+                }
+                None
+            }
+            Trie::Node { pointer_block } => {
+                let index: usize = {
+                    assert!(depth < path.len(), "depth must be < {}", path.len());
+                    path[depth].into()
+                };
+                depth += 1;
+                pointer_block[index].map(|pointer| *pointer.hash())
+            }
+            Trie::Extension { affix, pointer } => {
+                let sub_path = path.get(depth..depth + affix.len());
+                depth += affix.len();
+                if sub_path == Some(affix.as_slice()) {
+                    Some(*pointer.hash())
+                } else {
+                    None
+                }
+            }
+        };
+        proof_tries.insert(next_hash, current);
+        match maybe_next {
+            Some(hash) => next_hash = hash,
+            None => break,
+        }
+    }
+
+    let proof_root = multi_proof_trie(&mut proof_tries, Pointer::NodePointer(*root));
+    Ok(ReadResult::Found(TrieMerkleAbsenceProof::new(
+        key.clone(),
+        proof_root,
+    )))
+}
+
+/// Builds the part of a [`TrieMerkleMultiProof`] under `pointer`, pruning any subtrie which is not
+/// in `proof_tries`.
+fn multi_proof_trie<K, V>(
+    proof_tries: &mut BTreeMap<Digest, Trie<K, V>>,
+    pointer: Pointer,
+) -> TrieMerkleMultiProofTrie<K, V> {
+    match proof_tries.remove(pointer.hash()) {
+        None => TrieMerkleMultiProofTrie::Pruned(pointer),
+        Some(Trie::Leaf { key, value }) => TrieMerkleMultiProofTrie::Leaf { key, value },
+        Some(Trie::Node { pointer_block }) => TrieMerkleMultiProofTrie::Node {
+            children: pointer_block
+                .as_indexed_pointers()
+                .map(|(index, pointer)| (index, multi_proof_trie(proof_tries, pointer)))
+                .collect(),
+        },
+        Some(Trie::Extension { affix, pointer }) => TrieMerkleMultiProofTrie::Extension {
+            affix,
+            child: Box::new(multi_proof_trie(proof_tries, pointer)),
+        },
+    }
+}
+
 /// Given a serialized trie, find any children that are referenced but not present in the database.
 pub fn missing_children<K, V, T, S, E>(
     _correlation_id: CorrelationId,
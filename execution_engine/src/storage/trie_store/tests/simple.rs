@@ -1,12 +1,13 @@
 use lmdb::DatabaseFlags;
 use tempfile::tempdir;
 
+use casper_hashing::Digest;
 use casper_types::bytesrepr::{self, Bytes, FromBytes, ToBytes};
 
 use super::TestData;
 use crate::storage::{
     error::{self, in_memory},
-    store::StoreExt,
+    store::{Store, StoreExt},
     transaction_source::{
         in_memory::InMemoryEnvironment, lmdb::LmdbEnvironment, Transaction, TransactionSource,
     },
@@ -129,6 +130,48 @@ fn lmdb_put_get_succeeds() {
     tmp_dir.close().unwrap();
 }
 
+#[test]
+fn lmdb_put_get_compressed_leaf_succeeds() {
+    let tmp_dir = tempdir().unwrap();
+    let env = LmdbEnvironment::new(
+        tmp_dir.path(),
+        DEFAULT_TEST_MAX_DB_SIZE,
+        DEFAULT_TEST_MAX_READERS,
+        true,
+    )
+    .unwrap();
+    let store = LmdbTrieStore::new(&env, None, DatabaseFlags::empty())
+        .unwrap()
+        .with_compression_threshold(64);
+    let leaf: Trie<Bytes, Bytes> = Trie::Leaf {
+        key: Bytes::from(vec![0u8, 0, 0]),
+        value: Bytes::from(vec![7u8; 4096]),
+    };
+    let leaf_bytes = leaf.to_bytes().unwrap();
+    let leaf_hash = Digest::hash(&leaf_bytes);
+
+    let mut txn = env.create_read_write_txn().unwrap();
+    Store::<Digest, Trie<Bytes, Bytes>>::put(&store, &mut txn, &leaf_hash, &leaf).unwrap();
+    txn.commit().unwrap();
+
+    // The leaf is stored compressed, but read back as its canonical serialized form.
+    let uncompressed_store = LmdbTrieStore::open(&env, None).unwrap();
+    let txn = env.create_read_txn().unwrap();
+    let stored_bytes = lmdb::Transaction::get(&txn, store.get_db(), &leaf_hash).unwrap();
+    assert!(stored_bytes.len() < leaf_bytes.len());
+    let raw = Store::<Digest, Trie<Bytes, Bytes>>::get_raw(&store, &txn, &leaf_hash).unwrap();
+    assert_eq!(raw, Some(Bytes::from(leaf_bytes)));
+
+    // Stores without compression enabled can still read compressed leaves.
+    assert_eq!(
+        Store::<Digest, Trie<Bytes, Bytes>>::get(&uncompressed_store, &txn, &leaf_hash).unwrap(),
+        Some(leaf)
+    );
+    txn.commit().unwrap();
+
+    tmp_dir.close().unwrap();
+}
+
 #[test]
 fn in_memory_put_get_many_succeeds() {
     let env = InMemoryEnvironment::new();
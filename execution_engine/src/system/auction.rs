@@ -8,9 +8,11 @@ use num_traits::{CheckedMul, CheckedSub};
 
 use casper_types::{
     account::AccountHash,
+    bytesrepr::Bytes,
     system::auction::{
-        Bid, DelegationRate, EraInfo, EraValidators, Error, SeigniorageAllocation,
-        SeigniorageRecipients, ValidatorWeights, BLOCK_REWARD, DELEGATION_RATE_DENOMINATOR,
+        Bid, DelegationRate, Delegator, EraInfo, EraValidators, Error, RewardsDestination,
+        SeigniorageAllocation, SeigniorageRecipients, ValidatorWeights, BLOCK_REWARD,
+        DELEGATION_RATE_DENOMINATOR, MAX_BID_METADATA_LENGTH,
     },
     ApiError, EraId, PublicKey, U512,
 };
@@ -195,6 +197,10 @@ pub trait Auction:
             *bid.delegators_mut() = BTreeMap::new();
 
             bid.deactivate();
+
+            // The validator has left, so it no longer publishes any metadata.
+            self.remove_bid_metadata(provided_account_hash)?;
+            detail::remove_rewards_destinations(self, &public_key, None)?;
         }
 
         self.write_bid(provided_account_hash, bid)?;
@@ -295,7 +301,7 @@ pub trait Auction:
             Some(delegator) => {
                 detail::create_unbonding_purse(
                     self,
-                    validator_public_key,
+                    validator_public_key.clone(),
                     delegator_public_key.clone(),
                     *delegator.bonding_purse(),
                     amount,
@@ -314,6 +320,14 @@ pub trait Auction:
 
         self.write_bid(validator_account_hash, bid)?;
 
+        if new_amount.is_zero() {
+            detail::remove_rewards_destinations(
+                self,
+                &validator_public_key,
+                Some(&delegator_public_key),
+            )?;
+        }
+
         Ok(new_amount)
     }
 
@@ -358,7 +372,7 @@ pub trait Auction:
             Some(delegator) => {
                 detail::create_unbonding_purse(
                     self,
-                    validator_public_key,
+                    validator_public_key.clone(),
                     delegator_public_key.clone(),
                     *delegator.bonding_purse(),
                     amount,
@@ -377,6 +391,14 @@ pub trait Auction:
 
         self.write_bid(validator_account_hash, bid)?;
 
+        if new_amount.is_zero() {
+            detail::remove_rewards_destinations(
+                self,
+                &validator_public_key,
+                Some(&delegator_public_key),
+            )?;
+        }
+
         Ok(new_amount)
     }
 
@@ -425,6 +447,9 @@ pub trait Auction:
     /// added to their delegators') ordered by size from largest to smallest, then takes the top N
     /// (number of auction slots) bidders and replaces era_validators with these.
     ///
+    /// Bids of `evicted_validators` are deactivated once they have been reported inactive for the
+    /// configured number of consecutive eras.
+    ///
     /// Accessed by: node
     fn run_auction(
         &mut self,
@@ -445,6 +470,9 @@ pub trait Auction:
         // Process unbond requests
         detail::process_unbond_requests(self, max_delegators_per_validator)?;
 
+        // Only evict validators which have been inactive for enough consecutive eras
+        let evicted_validators = detail::process_inactive_validators(self, evicted_validators)?;
+
         let mut bids = detail::get_bids(self)?;
 
         // Process bids
@@ -559,6 +587,7 @@ pub trait Auction:
 
         let seigniorage_recipients = self.read_seigniorage_recipients()?;
         let base_round_reward = self.read_base_round_reward()?;
+        let rewards_destinations = detail::get_rewards_destinations(self)?;
 
         let mut era_info = EraInfo::new();
         let seigniorage_allocations = era_info.seigniorage_allocations_mut();
@@ -621,6 +650,7 @@ pub trait Auction:
                 seigniorage_allocations,
                 public_key.clone(),
                 delegator_rewards,
+                &rewards_destinations,
             )?;
             let total_delegator_payout: U512 = delegator_payouts
                 .iter()
@@ -629,18 +659,19 @@ pub trait Auction:
 
             let validators_part: Ratio<U512> = total_reward - Ratio::from(total_delegator_payout);
             let validator_reward = validators_part.to_integer();
-            let validator_bonding_purse = detail::reinvest_validator_reward(
+            let validator_payout_purse = detail::reinvest_validator_reward(
                 self,
                 seigniorage_allocations,
                 public_key.clone(),
                 validator_reward,
+                &rewards_destinations,
             )?;
 
-            self.mint_into_existing_purse(validator_reward, validator_bonding_purse)
+            self.mint_into_existing_purse(validator_reward, validator_payout_purse)
                 .map_err(Error::from)?;
 
-            for (_delegator_account_hash, delegator_payout, bonding_purse) in delegator_payouts {
-                self.mint_into_existing_purse(delegator_payout, bonding_purse)
+            for (_delegator_account_hash, delegator_payout, payout_purse) in delegator_payouts {
+                self.mint_into_existing_purse(delegator_payout, payout_purse)
                     .map_err(Error::from)?;
             }
         }
@@ -674,6 +705,158 @@ pub trait Auction:
 
         self.write_bid(provided_account_hash, bid)?;
 
+        let mut inactive_eras = detail::get_inactive_eras(self)?;
+        if inactive_eras.remove(&validator_public_key).is_some() {
+            detail::set_inactive_eras(self, inactive_eras)?;
+        }
+
         Ok(())
     }
+
+    /// Sets the metadata (e.g. name, website or logo hash) a validator publishes alongside its
+    /// bid, replacing any previously set metadata.
+    fn set_bid_metadata(&mut self, public_key: PublicKey, metadata: Bytes) -> Result<(), Error> {
+        let provided_account_hash = AccountHash::from_public_key(&public_key, |x| self.blake2b(x));
+
+        if !self.is_allowed_session_caller(&provided_account_hash) {
+            return Err(Error::InvalidContext);
+        }
+
+        if metadata.len() > MAX_BID_METADATA_LENGTH {
+            return Err(Error::BidMetadataTooLarge);
+        }
+
+        if self.read_bid(&provided_account_hash)?.is_none() {
+            return Err(Error::ValidatorNotFound);
+        }
+
+        self.write_bid_metadata(provided_account_hash, metadata)
+    }
+
+    /// Sets whether the era rewards earned by a validator's own stake are added to its stake or
+    /// paid into its main purse.
+    fn set_rewards_destination(
+        &mut self,
+        public_key: PublicKey,
+        rewards_destination: RewardsDestination,
+    ) -> Result<(), Error> {
+        let provided_account_hash = AccountHash::from_public_key(&public_key, |x| self.blake2b(x));
+
+        if !self.is_allowed_session_caller(&provided_account_hash) {
+            return Err(Error::InvalidContext);
+        }
+
+        if self.read_bid(&provided_account_hash)?.is_none() {
+            return Err(Error::ValidatorNotFound);
+        }
+
+        detail::set_rewards_destination(self, public_key, None, rewards_destination)
+    }
+
+    /// Sets whether the era rewards earned by a delegator's stake with a given validator are added
+    /// to the delegated stake or paid into the delegator's main purse.
+    fn set_delegator_rewards_destination(
+        &mut self,
+        delegator_public_key: PublicKey,
+        validator_public_key: PublicKey,
+        rewards_destination: RewardsDestination,
+    ) -> Result<(), Error> {
+        let provided_account_hash =
+            AccountHash::from_public_key(&delegator_public_key, |x| self.blake2b(x));
+
+        if !self.is_allowed_session_caller(&provided_account_hash) {
+            return Err(Error::InvalidContext);
+        }
+
+        let validator_account_hash = AccountHash::from(&validator_public_key);
+        let bid = match self.read_bid(&validator_account_hash)? {
+            Some(bid) => bid,
+            None => return Err(Error::ValidatorNotFound),
+        };
+        if !bid.delegators().contains_key(&delegator_public_key) {
+            return Err(Error::DelegatorNotFound);
+        }
+
+        detail::set_rewards_destination(
+            self,
+            validator_public_key,
+            Some(delegator_public_key),
+            rewards_destination,
+        )
+    }
+
+    /// Cancels `amount` of the unbonder's pending unbonding requests from the given validator, the
+    /// most recent first, adding it back to the unbonder's stake with that validator.
+    ///
+    /// The unbonder is either the validator itself or one of its delegators, including one which
+    /// has since undelegated its whole stake and so becomes a delegator again.  An inactive bid
+    /// stays inactive.
+    ///
+    /// Returns the unbonder's updated stake.
+    fn cancel_unbonding(
+        &mut self,
+        unbonder_public_key: PublicKey,
+        validator_public_key: PublicKey,
+        amount: U512,
+        max_delegators_per_validator: Option<u32>,
+        minimum_delegation_amount: u64,
+    ) -> Result<U512, Error> {
+        let provided_account_hash =
+            AccountHash::from_public_key(&unbonder_public_key, |x| self.blake2b(x));
+
+        if !self.is_allowed_session_caller(&provided_account_hash) {
+            return Err(Error::InvalidContext);
+        }
+
+        if amount.is_zero() {
+            return Err(Error::BondTooSmall);
+        }
+
+        let validator_account_hash = AccountHash::from(&validator_public_key);
+        let mut bid = self
+            .read_bid(&validator_account_hash)?
+            .ok_or(Error::ValidatorNotFound)?;
+
+        let mut unbonding_purses = self.read_unbond(&validator_account_hash)?;
+        let cancelled =
+            detail::cancel_unbonding_purses(&mut unbonding_purses, &unbonder_public_key, amount)?;
+
+        let updated_stake = if unbonder_public_key == validator_public_key {
+            detail::move_to_bonding_purse(self, &cancelled, *bid.bonding_purse())?;
+            bid.increase_stake(amount)?
+        } else {
+            if !bid.delegators().contains_key(&unbonder_public_key) {
+                if !detail::is_under_max_delegator_cap(
+                    max_delegators_per_validator,
+                    bid.delegators().len(),
+                ) {
+                    return Err(Error::ExceededDelegatorSizeLimit);
+                }
+                if amount < U512::from(minimum_delegation_amount) {
+                    return Err(Error::DelegationAmountTooSmall);
+                }
+                // The stake resumes in the purse of the most recent request.
+                let (bonding_purse, _) = cancelled[0];
+                let delegator = Delegator::unlocked(
+                    unbonder_public_key.clone(),
+                    U512::zero(),
+                    bonding_purse,
+                    validator_public_key,
+                );
+                bid.delegators_mut()
+                    .insert(unbonder_public_key.clone(), delegator);
+            }
+            let delegator = bid
+                .delegators_mut()
+                .get_mut(&unbonder_public_key)
+                .ok_or(Error::DelegatorNotFound)?;
+            detail::move_to_bonding_purse(self, &cancelled, *delegator.bonding_purse())?;
+            delegator.increase_stake(amount)?
+        };
+
+        self.write_unbond(validator_account_hash, unbonding_purses)?;
+        self.write_bid(validator_account_hash, bid)?;
+
+        Ok(updated_stake)
+    }
 }
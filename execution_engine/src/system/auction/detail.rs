@@ -1,4 +1,7 @@
-use std::{collections::BTreeMap, convert::TryInto};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    convert::TryInto,
+};
 
 use num_rational::Ratio;
 
@@ -6,16 +9,18 @@ use casper_types::{
     account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
     system::auction::{
-        Bids, Delegator, Error, SeigniorageAllocation, SeigniorageRecipientsSnapshot,
-        UnbondingPurse, UnbondingPurses, AUCTION_DELAY_KEY, ERA_END_TIMESTAMP_MILLIS_KEY,
-        ERA_ID_KEY, SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY, UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
+        Bids, Delegator, Error, RewardsDestination, RewardsDestinations, SeigniorageAllocation,
+        SeigniorageRecipientsSnapshot, UnbondingPurse, UnbondingPurses, AUCTION_DELAY_KEY,
+        ERA_END_TIMESTAMP_MILLIS_KEY, ERA_ID_KEY, INACTIVE_ERAS_KEY, REWARDS_DESTINATIONS_KEY,
+        SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY, UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
     },
     ApiError, CLTyped, EraId, Key, KeyTag, PublicKey, URef, U512,
 };
 use tracing::error;
 
 use super::{
-    Auction, Bid, EraValidators, MintProvider, RuntimeProvider, StorageProvider, ValidatorWeights,
+    AccountProvider, Auction, Bid, EraValidators, MintProvider, RuntimeProvider, StorageProvider,
+    ValidatorWeights,
 };
 
 fn read_from<P, T>(provider: &mut P, name: &str) -> Result<T, Error>
@@ -39,6 +44,19 @@ where
     provider.write(uref, value)
 }
 
+/// Writes `value` under the named key `name`, creating the named key if it doesn't exist yet.
+fn write_or_create<P, T>(provider: &mut P, name: &str, value: T) -> Result<(), Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+    T: ToBytes + CLTyped,
+{
+    if provider.named_keys_get(name).is_some() {
+        return write_to(provider, name, value);
+    }
+    let uref = provider.new_uref(value)?;
+    provider.put_key(name, uref.into())
+}
+
 pub fn get_bids<P>(provider: &mut P) -> Result<Bids, Error>
 where
     P: StorageProvider + RuntimeProvider + ?Sized,
@@ -186,6 +204,161 @@ where
     read_from(provider, UNBONDING_DELAY_KEY)
 }
 
+/// Returns the number of consecutive eras each validator has been reported inactive for, or an
+/// empty map if nothing has been recorded yet.
+pub(crate) fn get_inactive_eras<P>(provider: &mut P) -> Result<BTreeMap<PublicKey, u64>, Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    if provider.named_keys_get(INACTIVE_ERAS_KEY).is_none() {
+        return Ok(BTreeMap::new());
+    }
+    read_from(provider, INACTIVE_ERAS_KEY)
+}
+
+/// Stores the number of consecutive eras each validator has been reported inactive for.
+///
+/// The named key is only created once there is something to record.
+pub(crate) fn set_inactive_eras<P>(
+    provider: &mut P,
+    inactive_eras: BTreeMap<PublicKey, u64>,
+) -> Result<(), Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    if inactive_eras.is_empty() && provider.named_keys_get(INACTIVE_ERAS_KEY).is_none() {
+        return Ok(());
+    }
+    write_or_create(provider, INACTIVE_ERAS_KEY, inactive_eras)
+}
+
+/// Returns which of the validators reported inactive in the era just ended should be evicted.
+///
+/// A validator is evicted once it has been reported inactive for `validator_eviction_threshold`
+/// consecutive eras, at which point its count is cleared.  A validator not reported inactive has
+/// its count reset.  A threshold of 0 disables eviction.
+pub(crate) fn process_inactive_validators<P>(
+    provider: &mut P,
+    inactive_validators: Vec<PublicKey>,
+) -> Result<Vec<PublicKey>, Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    let validator_eviction_threshold = provider.validator_eviction_threshold();
+    let previous_inactive_eras = get_inactive_eras(provider)?;
+
+    let mut evicted_validators = Vec::new();
+    let mut inactive_eras = BTreeMap::new();
+    match validator_eviction_threshold {
+        0 => {}
+        1 => evicted_validators = inactive_validators,
+        _ => {
+            let inactive_validators: BTreeSet<PublicKey> =
+                inactive_validators.into_iter().collect();
+            for public_key in inactive_validators {
+                let count = previous_inactive_eras
+                    .get(&public_key)
+                    .copied()
+                    .unwrap_or_default()
+                    .saturating_add(1);
+                if count >= validator_eviction_threshold {
+                    evicted_validators.push(public_key);
+                } else {
+                    inactive_eras.insert(public_key, count);
+                }
+            }
+        }
+    }
+
+    if inactive_eras != previous_inactive_eras {
+        set_inactive_eras(provider, inactive_eras)?;
+    }
+
+    Ok(evicted_validators)
+}
+
+/// Returns the destinations chosen for era rewards, or an empty map if none has been chosen yet.
+pub(crate) fn get_rewards_destinations<P>(provider: &mut P) -> Result<RewardsDestinations, Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    if provider.named_keys_get(REWARDS_DESTINATIONS_KEY).is_none() {
+        return Ok(BTreeMap::new());
+    }
+    read_from(provider, REWARDS_DESTINATIONS_KEY)
+}
+
+/// Sets the destination of the era rewards of the stake delegated by `delegator_public_key` to
+/// `validator_public_key`, or of the validator's own stake if `delegator_public_key` is `None`.
+///
+/// Only destinations other than the default are recorded.
+pub(crate) fn set_rewards_destination<P>(
+    provider: &mut P,
+    validator_public_key: PublicKey,
+    delegator_public_key: Option<PublicKey>,
+    rewards_destination: RewardsDestination,
+) -> Result<(), Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    let mut rewards_destinations = get_rewards_destinations(provider)?;
+    let stake = (validator_public_key, delegator_public_key);
+    let previous = if rewards_destination == RewardsDestination::default() {
+        rewards_destinations.remove(&stake)
+    } else {
+        rewards_destinations.insert(stake, rewards_destination)
+    };
+    if previous.unwrap_or_default() == rewards_destination {
+        return Ok(());
+    }
+    write_or_create(provider, REWARDS_DESTINATIONS_KEY, rewards_destinations)
+}
+
+/// Removes the destinations chosen for the era rewards of stakes which no longer exist: of the
+/// stake delegated by `delegator_public_key` to `validator_public_key`, or, if
+/// `delegator_public_key` is `None`, of the validator's own stake and of every stake delegated to
+/// it.
+pub(crate) fn remove_rewards_destinations<P>(
+    provider: &mut P,
+    validator_public_key: &PublicKey,
+    delegator_public_key: Option<&PublicKey>,
+) -> Result<(), Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    let mut rewards_destinations = get_rewards_destinations(provider)?;
+    let previous_count = rewards_destinations.len();
+    rewards_destinations.retain(|(validator, delegator), _| {
+        validator != validator_public_key
+            || delegator_public_key.map_or(false, |delegator_public_key| {
+                delegator.as_ref() != Some(delegator_public_key)
+            })
+    });
+    if rewards_destinations.len() == previous_count {
+        return Ok(());
+    }
+    write_to(provider, REWARDS_DESTINATIONS_KEY, rewards_destinations)
+}
+
+/// Returns the purse era rewards should be paid into for a stake whose rewards go to
+/// `rewards_destination`, or `None` if they should be added to the stake.
+fn rewards_payout_purse<P>(
+    provider: &mut P,
+    staker_public_key: &PublicKey,
+    rewards_destination: Option<&RewardsDestination>,
+) -> Result<Option<URef>, Error>
+where
+    P: AccountProvider + ?Sized,
+{
+    match rewards_destination.copied().unwrap_or_default() {
+        RewardsDestination::Stake => Ok(None),
+        // Should the staker's account be missing, the rewards are added to the stake instead.
+        RewardsDestination::MainPurse => {
+            provider.get_account_main_purse(staker_public_key.to_account_hash())
+        }
+    }
+}
+
 /// Iterates over unbonding entries and checks if a locked amount can be paid already if
 /// a specific era is reached.
 ///
@@ -308,15 +481,87 @@ pub(crate) fn create_unbonding_purse<P: Auction + ?Sized>(
     Ok(())
 }
 
+/// Removes `amount` from the pending unbonding requests of `unbonder_public_key` among
+/// `unbonding_purses`, the most recent first, shrinking the last request drawn on if only part of it
+/// is needed.
+///
+/// Returns the amounts drawn from each request, along with the bonding purses holding them.
+pub(crate) fn cancel_unbonding_purses(
+    unbonding_purses: &mut Vec<UnbondingPurse>,
+    unbonder_public_key: &PublicKey,
+    amount: U512,
+) -> Result<Vec<(URef, U512)>, Error> {
+    let mut remaining = amount;
+    let mut cancelled = Vec::new();
+    for index in (0..unbonding_purses.len()).rev() {
+        if remaining.is_zero() {
+            break;
+        }
+        let unbonding_purse = &unbonding_purses[index];
+        if unbonding_purse.unbonder_public_key() != unbonder_public_key {
+            continue;
+        }
+        let drawn = remaining.min(*unbonding_purse.amount());
+        cancelled.push((*unbonding_purse.bonding_purse(), drawn));
+        remaining -= drawn;
+        if drawn == *unbonding_purse.amount() {
+            unbonding_purses.remove(index);
+        } else {
+            let shrunk_unbonding_purse = UnbondingPurse::new(
+                *unbonding_purse.bonding_purse(),
+                unbonding_purse.validator_public_key().clone(),
+                unbonder_public_key.clone(),
+                unbonding_purse.era_of_creation(),
+                *unbonding_purse.amount() - drawn,
+                unbonding_purse.new_validator().clone(),
+            );
+            unbonding_purses[index] = shrunk_unbonding_purse;
+        }
+    }
+    if !remaining.is_zero() {
+        return Err(Error::CancelUnbondingTooLarge);
+    }
+    Ok(cancelled)
+}
+
+/// Moves amounts drawn from cancelled unbonding requests into `bonding_purse`, where they are held
+/// in a different purse.
+pub(crate) fn move_to_bonding_purse<P>(
+    provider: &mut P,
+    cancelled: &[(URef, U512)],
+    bonding_purse: URef,
+) -> Result<(), Error>
+where
+    P: MintProvider + ?Sized,
+{
+    for (source, amount) in cancelled {
+        if source.addr() == bonding_purse.addr() {
+            continue;
+        }
+        provider
+            .mint_transfer_direct(
+                Some(PublicKey::System.to_account_hash()),
+                *source,
+                bonding_purse,
+                *amount,
+                None,
+            )
+            .map_err(|_| Error::TransferToBidPurse)?
+            .map_err(|_| Error::TransferToBidPurse)?;
+    }
+    Ok(())
+}
+
 /// Reinvests delegator reward by increasing its stake.
 pub fn reinvest_delegator_rewards<P>(
     provider: &mut P,
     seigniorage_allocations: &mut Vec<SeigniorageAllocation>,
     validator_public_key: PublicKey,
     rewards: impl Iterator<Item = (PublicKey, Ratio<U512>)>,
+    rewards_destinations: &RewardsDestinations,
 ) -> Result<Vec<(AccountHash, U512, URef)>, Error>
 where
-    P: StorageProvider,
+    P: StorageProvider + AccountProvider,
 {
     let mut delegator_payouts = Vec::new();
 
@@ -337,12 +582,21 @@ where
 
         let delegator_reward_trunc = delegator_reward.to_integer();
 
-        delegator.increase_stake(delegator_reward_trunc)?;
+        let rewards_destination =
+            rewards_destinations.get(&(validator_public_key.clone(), Some(delegator_key.clone())));
+        let payout_purse =
+            match rewards_payout_purse(provider, &delegator_key, rewards_destination)? {
+                Some(main_purse) => main_purse,
+                None => {
+                    delegator.increase_stake(delegator_reward_trunc)?;
+                    *delegator.bonding_purse()
+                }
+            };
 
         delegator_payouts.push((
             delegator_key.to_account_hash(),
             delegator_reward_trunc,
-            *delegator.bonding_purse(),
+            payout_purse,
         ));
 
         let allocation = SeigniorageAllocation::delegator(
@@ -359,15 +613,17 @@ where
     Ok(delegator_payouts)
 }
 
-/// Reinvests validator reward by increasing its stake and returns its bonding purse.
+/// Reinvests validator reward by increasing its stake and returns its bonding purse, unless the
+/// validator has chosen to be paid its rewards, in which case its main purse is returned instead.
 pub fn reinvest_validator_reward<P>(
     provider: &mut P,
     seigniorage_allocations: &mut Vec<SeigniorageAllocation>,
     validator_public_key: PublicKey,
     amount: U512,
+    rewards_destinations: &RewardsDestinations,
 ) -> Result<URef, Error>
 where
-    P: StorageProvider,
+    P: StorageProvider + AccountProvider,
 {
     let validator_account_hash = AccountHash::from(&validator_public_key);
 
@@ -378,17 +634,23 @@ where
         }
     };
 
-    bid.increase_stake(amount)?;
+    let rewards_destination = rewards_destinations.get(&(validator_public_key.clone(), None));
+    let payout_purse =
+        match rewards_payout_purse(provider, &validator_public_key, rewards_destination)? {
+            Some(main_purse) => main_purse,
+            None => {
+                bid.increase_stake(amount)?;
+                *bid.bonding_purse()
+            }
+        };
 
     let allocation = SeigniorageAllocation::validator(validator_public_key, amount);
 
     seigniorage_allocations.push(allocation);
 
-    let bonding_purse = *bid.bonding_purse();
-
     provider.write_bid(validator_account_hash, bid)?;
 
-    Ok(bonding_purse)
+    Ok(payout_purse)
 }
 
 pub(crate) fn handle_delegation<P>(
@@ -494,7 +756,7 @@ pub(crate) fn era_validators_from_snapshot(
         .collect()
 }
 
-fn is_under_max_delegator_cap(
+pub(crate) fn is_under_max_delegator_cap(
     max_delegators_per_validator: Option<u32>,
     new_validator_delegator_len: usize,
 ) -> bool {
@@ -2,7 +2,7 @@ use std::collections::BTreeSet;
 
 use casper_types::{
     account::AccountHash,
-    bytesrepr::{FromBytes, ToBytes},
+    bytesrepr::{Bytes, FromBytes, ToBytes},
     system::{
         auction::{Bid, EraInfo, Error, UnbondingPurse},
         mint,
@@ -32,10 +32,19 @@ pub trait RuntimeProvider {
 
     /// Check if auction bids are allowed.
     fn allow_auction_bids(&self) -> bool;
+
+    /// Returns the number of consecutive eras a validator must be inactive for to be evicted.
+    fn validator_eviction_threshold(&self) -> u64;
+
+    /// Adds a named key to the auction contract.
+    fn put_key(&mut self, name: &str, key: Key) -> Result<(), Error>;
 }
 
 /// Provides functionality of a contract storage.
 pub trait StorageProvider {
+    /// Writes data to a new [`URef`].
+    fn new_uref<T: CLTyped + ToBytes>(&mut self, init: T) -> Result<URef, Error>;
+
     /// Reads data from [`URef`].
     fn read<T: FromBytes + CLTyped>(&mut self, uref: URef) -> Result<Option<T>, Error>;
 
@@ -58,6 +67,16 @@ pub trait StorageProvider {
         unbonding_purses: Vec<UnbondingPurse>,
     ) -> Result<(), Error>;
 
+    /// Writes given bid metadata at account hash derived from given public key
+    fn write_bid_metadata(
+        &mut self,
+        account_hash: AccountHash,
+        metadata: Bytes,
+    ) -> Result<(), Error>;
+
+    /// Removes the bid metadata stored at account hash derived from given public key, if any.
+    fn remove_bid_metadata(&mut self, account_hash: AccountHash) -> Result<(), Error>;
+
     /// Records era summary.
     fn record_era_info(&mut self, _era_id: EraId, era_summary: EraInfo) -> Result<(), Error>;
 }
@@ -105,4 +124,7 @@ pub trait MintProvider {
 pub trait AccountProvider {
     /// Get currently executing account's purse.
     fn get_main_purse(&self) -> Result<URef, Error>;
+
+    /// Gets the main purse of the account with the given hash, if such an account exists.
+    fn get_account_main_purse(&mut self, account_hash: AccountHash) -> Result<Option<URef>, Error>;
 }
@@ -53,7 +53,7 @@ pub(crate) fn get_refund_purse<R: RuntimeProvider>(
 /// # Note
 ///
 /// Any dust amounts are added to the fee.
-fn calculate_refund_and_fee(
+pub(crate) fn calculate_refund_and_fee(
     gas_spent: U512,
     payment_purse_balance: U512,
     refund_handling: &RefundHandling,
@@ -249,6 +249,12 @@ pub trait Mint: RuntimeProvider + StorageProvider + SystemProvider {
             if amount > self.get_approved_spending_limit() {
                 return Err(Error::UnapprovedSpendingAmount);
             }
+            // Payment is exempt so that an account can always pay for its own deploys.
+            if self.get_phase() == Phase::Session
+                && source_balance - amount < self.minimum_remaining_balance()
+            {
+                return Err(Error::InsufficientRemainingBalance);
+            }
             self.sub_approved_spending_limit(amount);
         }
         self.write_balance(source, source_balance - amount)?;
@@ -48,4 +48,8 @@ pub trait RuntimeProvider {
     /// Checks if users can perform unrestricted transfers. This option is valid only for private
     /// chains.
     fn allow_unrestricted_transfers(&self) -> bool;
+
+    /// Returns the minimum balance an account's main purse must retain after a transfer made from
+    /// it during session execution.
+    fn minimum_remaining_balance(&self) -> U512;
 }
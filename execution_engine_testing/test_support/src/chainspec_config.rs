@@ -11,7 +11,7 @@ use serde::{Deserialize, Serialize};
 
 use casper_execution_engine::{
     core::engine_state::{
-        engine_config::{FeeHandling, RefundHandling},
+        engine_config::{FeeHandling, RefundHandling, DEFAULT_VALIDATOR_EVICTION_THRESHOLD},
         genesis::ExecConfigBuilder,
         run_genesis_request::RunGenesisRequest,
         ExecConfig, GenesisAccount,
@@ -72,6 +72,25 @@ pub struct CoreConfig {
     pub(crate) minimum_delegation_amount: u64,
     /// Enables strict arguments checking when calling a contract.
     pub(crate) strict_argument_checking: bool,
+    /// Enables checking that the entry point targeted by a stored contract call exists before
+    /// executing the deploy.
+    #[serde(default)]
+    pub(crate) entry_point_precheck: bool,
+    /// The minimum balance an account's main purse must retain after a transfer made from it.
+    #[serde(default)]
+    pub(crate) minimum_remaining_balance: u64,
+    /// The number of consecutive eras a validator must be inactive for before it is evicted.
+    #[serde(default = "default_validator_eviction_threshold")]
+    pub(crate) validator_eviction_threshold: u64,
+    /// Omits writes which leave a value unchanged from execution effects.
+    #[serde(default)]
+    pub(crate) deduplicate_writes: bool,
+    /// Records a deploy which hits an internal error as failed rather than aborting the block.
+    #[serde(default)]
+    pub(crate) isolate_deploy_failures: bool,
+    /// Records the error codes of failed deploys in the execution results of a block.
+    #[serde(default)]
+    pub(crate) record_execution_error_codes: bool,
     /// The maximum amount of delegators per validator.
     pub(crate) max_delegators_per_validator: Option<u32>,
     /// Refund handling.
@@ -80,6 +99,10 @@ pub struct CoreConfig {
     pub(crate) fee_handling: FeeHandling,
 }
 
+fn default_validator_eviction_threshold() -> u64 {
+    DEFAULT_VALIDATOR_EVICTION_THRESHOLD
+}
+
 /// This struct can be parsed from a TOML-encoded chainspec file.  It means that as the
 /// chainspec format changes over versions, as long as we maintain the core config in this form
 /// in the chainspec file, it can continue to be parsed as an `ChainspecConfig`.
@@ -157,6 +180,12 @@ impl ChainspecConfig {
             max_runtime_call_stack_height: _,
             minimum_delegation_amount: _,
             strict_argument_checking: _,
+            entry_point_precheck: _,
+            minimum_remaining_balance: _,
+            validator_eviction_threshold: _,
+            deduplicate_writes: _,
+            isolate_deploy_failures: _,
+            record_execution_error_codes: _,
             max_delegators_per_validator: _,
             refund_handling: _,
             fee_handling: _,
@@ -1,9 +1,14 @@
-use std::{collections::BTreeSet, path::Path};
+use std::{
+    collections::BTreeSet,
+    error,
+    fmt::{self, Display, Formatter},
+    path::Path,
+};
 
 use rand::Rng;
 
 use casper_execution_engine::core::engine_state::{
-    deploy_item::DeployItem, executable_deploy_item::ExecutableDeployItem,
+    deploy_item::DeployItem, executable_deploy_item::ExecutableDeployItem, NativeAuctionEntryPoint,
 };
 use casper_hashing::Digest;
 use casper_types::{
@@ -23,6 +28,31 @@ struct DeployItemData {
     pub deploy_hash: Option<DeployHash>,
 }
 
+/// An error returned by [`DeployItemBuilder::try_build`] if the builder can't produce a valid
+/// [`DeployItem`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeployItemBuilderError {
+    /// No session code was set.
+    MissingSessionCode,
+    /// No payment code was set, and the session code isn't a native transfer.
+    MissingPaymentCode,
+}
+
+impl Display for DeployItemBuilderError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            DeployItemBuilderError::MissingSessionCode => {
+                write!(formatter, "deploy item has no session code")
+            }
+            DeployItemBuilderError::MissingPaymentCode => {
+                write!(formatter, "deploy item has no payment code")
+            }
+        }
+    }
+}
+
+impl error::Error for DeployItemBuilderError {}
+
 /// Builds a [`DeployItem`].
 pub struct DeployItemBuilder {
     deploy_item: DeployItemData,
@@ -143,6 +173,23 @@ impl DeployItemBuilder {
         self
     }
 
+    /// Sets the session code of the deploy as a batch of native transfers.
+    pub fn with_multi_transfer_args(mut self, transfers: Vec<RuntimeArgs>) -> Self {
+        self.deploy_item.session_code = Some(ExecutableDeployItem::MultiTransfer { transfers });
+        self
+    }
+
+    /// Sets the session code of the deploy as a native call to an auction contract entry point.
+    pub fn with_native_auction(
+        mut self,
+        entry_point: NativeAuctionEntryPoint,
+        args: RuntimeArgs,
+    ) -> Self {
+        self.deploy_item.session_code =
+            Some(ExecutableDeployItem::NativeAuction { entry_point, args });
+        self
+    }
+
     /// Sets the session code for the deploy with a stored contract hash, entrypoint and runtime
     /// arguments.
     pub fn with_stored_session_hash(
@@ -262,27 +309,52 @@ impl DeployItemBuilder {
     }
 
     /// Consumes self and returns a [`DeployItem`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`try_build`](DeployItemBuilder::try_build) would return an error.
     pub fn build(self) -> DeployItem {
-        DeployItem {
-            address: self
-                .deploy_item
-                .address
-                .unwrap_or_else(|| AccountHash::new([0u8; 32])),
-            session: self
-                .deploy_item
-                .session_code
-                .expect("should have session code"),
-            payment: self
-                .deploy_item
-                .payment_code
-                .expect("should have payment code"),
-            gas_price: self.deploy_item.gas_price,
-            authorization_keys: self.deploy_item.authorization_keys,
-            deploy_hash: self
-                .deploy_item
-                .deploy_hash
-                .unwrap_or_else(|| rand::thread_rng().gen()),
-        }
+        self.try_build()
+            .unwrap_or_else(|error| panic!("failed to build deploy item: {}", error))
+    }
+
+    /// Consumes self and returns a [`DeployItem`], or an error if a required field wasn't set.
+    ///
+    /// Fields which weren't set default as follows:
+    /// * the address to the all-zeros account hash,
+    /// * the payment code of a native transfer, which is ignored, to empty module bytes,
+    /// * the deploy hash to a random one.
+    pub fn try_build(self) -> Result<DeployItem, DeployItemBuilderError> {
+        let DeployItemData {
+            address,
+            payment_code,
+            session_code,
+            gas_price,
+            authorization_keys,
+            deploy_hash,
+        } = self.deploy_item;
+
+        let session = session_code.ok_or(DeployItemBuilderError::MissingSessionCode)?;
+        let payment = match (payment_code, &session) {
+            (Some(payment), _) => payment,
+            (
+                None,
+                ExecutableDeployItem::Transfer { .. } | ExecutableDeployItem::MultiTransfer { .. },
+            ) => ExecutableDeployItem::ModuleBytes {
+                module_bytes: Vec::new().into(),
+                args: RuntimeArgs::new(),
+            },
+            (None, _) => return Err(DeployItemBuilderError::MissingPaymentCode),
+        };
+
+        Ok(DeployItem {
+            address: address.unwrap_or_else(|| AccountHash::new([0u8; 32])),
+            session,
+            payment,
+            gas_price,
+            authorization_keys,
+            deploy_hash: deploy_hash.unwrap_or_else(|| rand::thread_rng().gen()),
+        })
     }
 }
 
@@ -311,4 +383,32 @@ mod tests {
             .build();
         assert_ne!(deploy.deploy_hash, DeployHash::default());
     }
+
+    #[test]
+    fn should_not_build_without_session_code() {
+        let result = DeployItemBuilder::new()
+            .with_payment_bytes(Vec::new(), RuntimeArgs::new())
+            .try_build();
+        assert_eq!(result, Err(DeployItemBuilderError::MissingSessionCode));
+    }
+
+    #[test]
+    fn should_require_payment_code_only_if_not_native_transfer() {
+        let result = DeployItemBuilder::new()
+            .with_session_bytes(Vec::new(), RuntimeArgs::new())
+            .try_build();
+        assert_eq!(result, Err(DeployItemBuilderError::MissingPaymentCode));
+
+        let deploy = DeployItemBuilder::new()
+            .with_transfer_args(RuntimeArgs::new())
+            .try_build()
+            .expect("should build native transfer without payment code");
+        assert_eq!(
+            deploy.payment,
+            ExecutableDeployItem::ModuleBytes {
+                module_bytes: Vec::new().into(),
+                args: RuntimeArgs::new(),
+            }
+        );
+    }
 }
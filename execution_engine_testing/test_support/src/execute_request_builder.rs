@@ -3,7 +3,7 @@ use std::convert::TryInto;
 use rand::Rng;
 
 use casper_execution_engine::core::engine_state::{
-    deploy_item::DeployItem, execute_request::ExecuteRequest,
+    deploy_item::DeployItem, execute_request::ExecuteRequest, NativeAuctionEntryPoint,
 };
 use casper_types::{
     account::AccountHash, runtime_args, ContractHash, ContractPackageHash, ContractVersion,
@@ -216,6 +216,26 @@ impl ExecuteRequestBuilder {
 
         ExecuteRequestBuilder::from_deploy_item(deploy_item)
     }
+
+    /// Returns an [`ExecuteRequest`] that will natively call an auction contract entry point.
+    pub fn native_auction(
+        sender: AccountHash,
+        entry_point: NativeAuctionEntryPoint,
+        args: RuntimeArgs,
+    ) -> Self {
+        let mut rng = rand::thread_rng();
+        let deploy_hash = rng.gen();
+
+        let deploy_item = DeployItemBuilder::new()
+            .with_address(sender)
+            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT, })
+            .with_native_auction(entry_point, args)
+            .with_authorization_keys(&[sender])
+            .with_deploy_hash(deploy_hash)
+            .build();
+
+        ExecuteRequestBuilder::from_deploy_item(deploy_item)
+    }
 }
 
 impl Default for ExecuteRequestBuilder {
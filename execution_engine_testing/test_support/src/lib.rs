@@ -43,7 +43,7 @@ use casper_types::{account::AccountHash, Motes, ProtocolVersion, PublicKey, Secr
 use crate::chainspec_config::PRODUCTION_PATH;
 pub use additive_map_diff::AdditiveMapDiff;
 pub use chainspec_config::ChainspecConfig;
-pub use deploy_item_builder::DeployItemBuilder;
+pub use deploy_item_builder::{DeployItemBuilder, DeployItemBuilderError};
 pub use execute_request_builder::ExecuteRequestBuilder;
 pub use step_request_builder::StepRequestBuilder;
 pub use upgrade_request_builder::UpgradeRequestBuilder;
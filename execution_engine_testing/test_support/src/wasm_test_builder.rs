@@ -26,8 +26,9 @@ use casper_execution_engine::{
             run_genesis_request::RunGenesisRequest,
             step::{EvictItem, StepRequest, StepSuccess},
             BalanceResult, EngineConfig, EngineConfigBuilder, EngineState, Error, GenesisSuccess,
-            GetBidsRequest, PruneConfig, PruneResult, QueryRequest, QueryResult, RewardItem,
-            StepError, SystemContractRegistry, UpgradeConfig, UpgradeSuccess,
+            GetBidsRequest, GetBidsResult, GetContractPackageRequest, GetContractRequest,
+            GetUnbondsRequest, PendingUnbond, PruneConfig, PruneResult, QueryRequest, QueryResult,
+            RewardItem, StepError, SystemContractRegistry, UpgradeConfig, UpgradeSuccess,
             DEFAULT_MAX_QUERY_DEPTH,
         },
         execution,
@@ -57,7 +58,7 @@ use casper_execution_engine::{
 use casper_hashing::Digest;
 use casper_types::{
     account::{Account, AccountHash},
-    bytesrepr::{self, FromBytes},
+    bytesrepr::{self, Bytes, FromBytes},
     runtime_args,
     system::{
         auction::{
@@ -231,6 +232,12 @@ impl InMemoryWasmTestBuilder {
             max_runtime_call_stack_height,
             minimum_delegation_amount,
             strict_argument_checking,
+            entry_point_precheck,
+            minimum_remaining_balance,
+            validator_eviction_threshold,
+            deduplicate_writes,
+            isolate_deploy_failures,
+            record_execution_error_codes,
             max_delegators_per_validator,
             refund_handling,
             fee_handling,
@@ -242,6 +249,12 @@ impl InMemoryWasmTestBuilder {
             .with_max_runtime_call_stack_height(max_runtime_call_stack_height)
             .with_minimum_delegation_amount(minimum_delegation_amount)
             .with_strict_argument_checking(strict_argument_checking)
+            .with_entry_point_precheck(entry_point_precheck)
+            .with_minimum_remaining_balance(minimum_remaining_balance)
+            .with_validator_eviction_threshold(validator_eviction_threshold)
+            .with_deduplicate_writes(deduplicate_writes)
+            .with_isolate_deploy_failures(isolate_deploy_failures)
+            .with_record_execution_error_codes(record_execution_error_codes)
             .with_vesting_schedule_period_millis(vesting_schedule_period.millis())
             .with_max_delegators_per_validator(max_delegators_per_validator)
             .with_wasm_config(wasm_config)
@@ -361,6 +374,16 @@ impl LmdbWasmTestBuilder {
             )
             .with_minimum_delegation_amount(chainspec_config.core_config.minimum_delegation_amount)
             .with_strict_argument_checking(chainspec_config.core_config.strict_argument_checking)
+            .with_entry_point_precheck(chainspec_config.core_config.entry_point_precheck)
+            .with_minimum_remaining_balance(chainspec_config.core_config.minimum_remaining_balance)
+            .with_validator_eviction_threshold(
+                chainspec_config.core_config.validator_eviction_threshold,
+            )
+            .with_deduplicate_writes(chainspec_config.core_config.deduplicate_writes)
+            .with_isolate_deploy_failures(chainspec_config.core_config.isolate_deploy_failures)
+            .with_record_execution_error_codes(
+                chainspec_config.core_config.record_execution_error_codes,
+            )
             .with_vesting_schedule_period_millis(
                 chainspec_config
                     .core_config
@@ -1100,18 +1123,34 @@ where
     }
 
     /// Queries for a contract by `ContractHash`.
+    ///
+    /// Returns `None` if nothing, or a value other than a contract, is stored under the hash.
     pub fn get_contract(&self, contract_hash: ContractHash) -> Option<Contract> {
-        let contract_value: StoredValue = self
-            .query(None, contract_hash.into(), &[])
-            .expect("should have contract value");
+        let get_contract_request =
+            GetContractRequest::new(self.get_post_state_hash(), contract_hash);
 
-        if let StoredValue::Contract(contract) = contract_value {
-            Some(contract)
-        } else {
-            None
+        match self
+            .engine_state
+            .get_contract(CorrelationId::new(), get_contract_request)
+        {
+            Ok(get_contract_result) => get_contract_result.into_success(),
+            Err(Error::Exec(execution::Error::TypeMismatch(_))) => None,
+            Err(error) => panic!("should get contract: {:?}", error),
         }
     }
 
+    /// Queries for a contract by `ContractHash` and panics if it can't be found.
+    pub fn get_expected_contract(&self, contract_hash: ContractHash) -> Contract {
+        let get_contract_request =
+            GetContractRequest::new(self.get_post_state_hash(), contract_hash);
+
+        self.engine_state
+            .get_contract(CorrelationId::new(), get_contract_request)
+            .expect("should get contract")
+            .into_success()
+            .expect("contract to exist")
+    }
+
     /// Queries for a contract by `ContractHash` and returns an `Option<ContractWasm>`.
     pub fn get_contract_wasm(&self, contract_hash: ContractHash) -> Option<ContractWasm> {
         let contract_value: StoredValue = self
@@ -1126,21 +1165,41 @@ where
     }
 
     /// Queries for a contract package by `ContractPackageHash`.
+    ///
+    /// Returns `None` if nothing, or a value other than a contract package, is stored under the
+    /// hash.
     pub fn get_contract_package(
         &self,
         contract_package_hash: ContractPackageHash,
     ) -> Option<ContractPackage> {
-        let contract_value: StoredValue = self
-            .query(None, contract_package_hash.into(), &[])
-            .expect("should have package value");
+        let get_contract_package_request =
+            GetContractPackageRequest::new(self.get_post_state_hash(), contract_package_hash);
 
-        if let StoredValue::ContractPackage(package) = contract_value {
-            Some(package)
-        } else {
-            None
+        match self
+            .engine_state
+            .get_contract_package(CorrelationId::new(), get_contract_package_request)
+        {
+            Ok(get_contract_package_result) => get_contract_package_result.into_success(),
+            Err(Error::Exec(execution::Error::TypeMismatch(_))) => None,
+            Err(error) => panic!("should get contract package: {:?}", error),
         }
     }
 
+    /// Queries for a contract package by `ContractPackageHash` and panics if it can't be found.
+    pub fn get_expected_contract_package(
+        &self,
+        contract_package_hash: ContractPackageHash,
+    ) -> ContractPackage {
+        let get_contract_package_request =
+            GetContractPackageRequest::new(self.get_post_state_hash(), contract_package_hash);
+
+        self.engine_state
+            .get_contract_package(CorrelationId::new(), get_contract_package_request)
+            .expect("should get contract package")
+            .into_success()
+            .expect("contract package to exist")
+    }
+
     /// Queries for a transfer by `TransferAddr`.
     pub fn get_transfer(&self, transfer: TransferAddr) -> Option<Transfer> {
         let transfer_value: StoredValue = self
@@ -1242,6 +1301,32 @@ where
         get_bids_result.into_success().unwrap()
     }
 
+    /// Gets the metadata validators have set for their bids.
+    pub fn get_bid_metadata(&mut self) -> BTreeMap<PublicKey, Bytes> {
+        let get_bids_request = GetBidsRequest::new(self.get_post_state_hash());
+
+        match self
+            .engine_state
+            .get_bids(CorrelationId::new(), get_bids_request)
+            .unwrap()
+        {
+            GetBidsResult::Success { bid_metadata, .. } => bid_metadata,
+            GetBidsResult::RootNotFound => panic!("should have post state hash"),
+        }
+    }
+
+    /// Gets the pending unbonding requests made by `public_key`.
+    pub fn get_pending_unbonds(&mut self, public_key: PublicKey) -> Vec<PendingUnbond> {
+        let get_unbonds_request = GetUnbondsRequest::new(self.get_post_state_hash(), public_key);
+
+        let get_unbonds_result = self
+            .engine_state
+            .get_unbonds(CorrelationId::new(), get_unbonds_request)
+            .unwrap();
+
+        get_unbonds_result.into_success().unwrap()
+    }
+
     /// Gets [`UnbondingPurses`].
     pub fn get_unbonds(&mut self) -> UnbondingPurses {
         let correlation_id = CorrelationId::new();
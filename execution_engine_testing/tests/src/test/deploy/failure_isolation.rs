@@ -0,0 +1,135 @@
+use assert_matches::assert_matches;
+
+use casper_engine_test_support::{
+    DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_GAS_PRICE, DEFAULT_PAYMENT, PRODUCTION_RUN_GENESIS_REQUEST,
+};
+use casper_execution_engine::{
+    core::engine_state::{EngineConfigBuilder, Error, ExecuteRequest, MAX_PAYMENT},
+    shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
+};
+use casper_types::{
+    contracts::NamedKeys, runtime_args, system::handle_payment, Gas, Key, Motes, RuntimeArgs,
+    StoredValue,
+};
+
+const DO_NOTHING_WASM: &str = "do_nothing.wasm";
+const ARG_AMOUNT: &str = "amount";
+
+fn setup(isolate_deploy_failures: bool) -> InMemoryWasmTestBuilder {
+    let engine_config = EngineConfigBuilder::new()
+        .with_isolate_deploy_failures(isolate_deploy_failures)
+        .build();
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    // Replace the handle payment contract's payment purse with a key which isn't a `URef`, so that
+    // executing any deploy hits an internal error.
+    let handle_payment_hash = builder.get_handle_payment_contract_hash();
+    let mut handle_payment_contract = builder.get_handle_payment_contract();
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(
+        handle_payment::PAYMENT_PURSE_KEY.to_string(),
+        Key::Account(*DEFAULT_ACCOUNT_ADDR),
+    );
+    handle_payment_contract.named_keys_append(&mut named_keys);
+
+    let mut effects = AdditiveMap::new();
+    effects.insert(
+        Key::from(handle_payment_hash),
+        Transform::Write(StoredValue::Contract(handle_payment_contract)),
+    );
+    let pre_state_hash = builder.get_post_state_hash();
+    builder.commit_transforms(pre_state_hash, effects);
+    builder
+}
+
+fn exec_request() -> ExecuteRequest {
+    let deploys = (1..=2).map(|index| {
+        DeployItemBuilder::new()
+            .with_address(*DEFAULT_ACCOUNT_ADDR)
+            .with_session_code(DO_NOTHING_WASM, RuntimeArgs::default())
+            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+            .with_authorization_keys(&[*DEFAULT_ACCOUNT_ADDR])
+            .with_deploy_hash([index; 32])
+            .build()
+    });
+    deploys
+        .fold(ExecuteRequestBuilder::new(), |request_builder, deploy| {
+            request_builder.push_deploy(deploy)
+        })
+        .build()
+}
+
+#[ignore]
+#[test]
+fn should_abort_execution_on_internal_error_by_default() {
+    let builder = setup(false);
+
+    let mut exec_request = exec_request();
+    exec_request.parent_state_hash = builder.get_post_state_hash();
+    let result = builder
+        .get_engine_state()
+        .run_execute(CorrelationId::new(), exec_request);
+
+    assert_matches!(result, Err(Error::InvalidKeyVariant));
+}
+
+#[ignore]
+#[test]
+fn should_charge_deploy_hitting_internal_error_as_failed() {
+    let mut builder = setup(true);
+    let main_purse = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .main_purse();
+    let initial_balance = builder.get_purse_balance(main_purse);
+
+    builder.exec(exec_request()).commit();
+
+    let exec_results = builder
+        .get_last_exec_results()
+        .expect("should have exec results");
+    // The failure of the first deploy doesn't prevent the second from being executed.
+    assert_eq!(exec_results.len(), 2);
+    let payment_gas_limit = Gas::from_motes(Motes::new(*MAX_PAYMENT), DEFAULT_GAS_PRICE)
+        .expect("should convert to gas");
+    for exec_result in exec_results {
+        assert_matches!(exec_result.as_error(), Some(Error::InvalidKeyVariant));
+        assert!(!exec_result.has_precondition_failure());
+        assert_eq!(exec_result.cost(), payment_gas_limit);
+    }
+    // Each deploy is charged as for an error in its payment code.
+    assert_eq!(
+        builder.get_purse_balance(main_purse),
+        initial_balance - *MAX_PAYMENT * 2
+    );
+}
+
+#[ignore]
+#[test]
+fn should_isolate_internal_error_deterministically() {
+    let mut builder_1 = setup(true);
+    let mut builder_2 = setup(true);
+    assert_eq!(
+        builder_1.get_post_state_hash(),
+        builder_2.get_post_state_hash()
+    );
+
+    builder_1.exec(exec_request()).commit();
+    builder_2.exec(exec_request()).commit();
+
+    let errors = |builder: &InMemoryWasmTestBuilder| {
+        builder
+            .get_last_exec_results()
+            .expect("should have exec results")
+            .iter()
+            .map(|exec_result| exec_result.as_error().map(ToString::to_string))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(errors(&builder_1), errors(&builder_2));
+    assert_eq!(
+        builder_1.get_post_state_hash(),
+        builder_2.get_post_state_hash()
+    );
+}
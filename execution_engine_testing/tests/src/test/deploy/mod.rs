@@ -1,4 +1,6 @@
+mod address_prediction;
 mod context_association;
+mod failure_isolation;
 mod non_standard_payment;
 mod preconditions;
 mod receipts;
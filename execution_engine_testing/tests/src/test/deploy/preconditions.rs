@@ -2,13 +2,21 @@ use assert_matches::assert_matches;
 
 use casper_engine_test_support::{
     utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_ACCOUNT_INITIAL_BALANCE, DEFAULT_GAS_PRICE, DEFAULT_PAYMENT, DEFAULT_PROTOCOL_VERSION,
     PRODUCTION_RUN_GENESIS_REQUEST,
 };
-use casper_execution_engine::core::engine_state::Error;
-use casper_types::{account::AccountHash, runtime_args, RuntimeArgs, U512};
+use casper_execution_engine::{
+    core::{
+        engine_state::{DeployItem, EngineConfigBuilder, Error, MAX_PAYMENT},
+        execution,
+    },
+    shared::newtypes::CorrelationId,
+};
+use casper_types::{account::AccountHash, runtime_args, Gas, Motes, RuntimeArgs, U512};
 
 const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([42u8; 32]);
 const ARG_AMOUNT: &str = "amount";
+const MISSING_ENTRY_POINT_NAME: &str = "missing_entry_point";
 
 #[ignore]
 #[test]
@@ -116,3 +124,113 @@ fn should_raise_precondition_authorization_failure_invalid_authorized_keys() {
     let precondition_failure = utils::get_precondition_failure(&response);
     assert_matches!(precondition_failure, Error::Authorization);
 }
+
+fn missing_mint_entry_point_deploy(builder: &InMemoryWasmTestBuilder) -> DeployItem {
+    DeployItemBuilder::new()
+        .with_address(*DEFAULT_ACCOUNT_ADDR)
+        .with_deploy_hash([1; 32])
+        .with_stored_session_hash(
+            builder.get_mint_contract_hash(),
+            MISSING_ENTRY_POINT_NAME,
+            RuntimeArgs::default(),
+        )
+        .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+        .with_authorization_keys(&[*DEFAULT_ACCOUNT_ADDR])
+        .build()
+}
+
+fn builder_with_entry_point_precheck(entry_point_precheck: bool) -> InMemoryWasmTestBuilder {
+    let engine_config = EngineConfigBuilder::new()
+        .with_entry_point_precheck(entry_point_precheck)
+        .build();
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+    builder
+}
+
+fn call_missing_mint_entry_point(entry_point_precheck: bool) -> InMemoryWasmTestBuilder {
+    let mut builder = builder_with_entry_point_precheck(entry_point_precheck);
+    let deploy = missing_mint_entry_point_deploy(&builder);
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+    builder.exec(exec_request).commit();
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_charge_for_missing_entry_point_with_precheck() {
+    let builder = call_missing_mint_entry_point(true);
+
+    let response = builder
+        .get_exec_result_owned(0)
+        .expect("there should be a response");
+    let result = response.get(0).expect("should have a result");
+
+    assert!(!result.has_precondition_failure());
+    assert_matches!(
+        result.as_error(),
+        Some(Error::MissingEntryPoint { contract_hash, entry_point_name })
+        if *contract_hash == builder.get_mint_contract_hash()
+            && entry_point_name == MISSING_ENTRY_POINT_NAME
+    );
+
+    let payment_gas_limit = Gas::from_motes(Motes::new(*MAX_PAYMENT), DEFAULT_GAS_PRICE)
+        .expect("should convert to gas");
+    assert_eq!(result.cost(), payment_gas_limit);
+
+    let account_balance = builder.get_purse_balance(
+        builder
+            .get_account(*DEFAULT_ACCOUNT_ADDR)
+            .expect("should have account")
+            .main_purse(),
+    );
+    assert_eq!(
+        account_balance,
+        U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE) - *MAX_PAYMENT
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_missing_entry_point_at_no_cost_in_precheck() {
+    let builder = builder_with_entry_point_precheck(true);
+    let deploy = missing_mint_entry_point_deploy(&builder);
+
+    let result = builder.get_engine_state().precheck_deploy(
+        CorrelationId::new(),
+        *DEFAULT_PROTOCOL_VERSION,
+        builder.get_post_state_hash(),
+        &deploy,
+    );
+    assert_matches!(
+        result,
+        Err(Error::MissingEntryPoint { contract_hash, entry_point_name })
+        if contract_hash == builder.get_mint_contract_hash()
+            && entry_point_name == MISSING_ENTRY_POINT_NAME
+    );
+
+    let account_balance = builder.get_purse_balance(
+        builder
+            .get_account(*DEFAULT_ACCOUNT_ADDR)
+            .expect("should have account")
+            .main_purse(),
+    );
+    assert_eq!(account_balance, U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE));
+}
+
+#[ignore]
+#[test]
+fn should_execute_call_to_missing_entry_point_without_precheck() {
+    let builder = call_missing_mint_entry_point(false);
+
+    let response = builder
+        .get_exec_result_owned(0)
+        .expect("there should be a response");
+    let result = response.get(0).expect("should have a result");
+
+    assert!(!result.has_precondition_failure());
+    assert_matches!(
+        result.as_error(),
+        Some(Error::Exec(execution::Error::NoSuchMethod(_)))
+    );
+}
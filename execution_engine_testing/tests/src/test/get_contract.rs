@@ -0,0 +1,120 @@
+use casper_engine_test_support::{InMemoryWasmTestBuilder, PRODUCTION_RUN_GENESIS_REQUEST};
+use casper_execution_engine::{
+    core::engine_state::{
+        GetContractPackageRequest, GetContractPackageResult, GetContractRequest, GetContractResult,
+    },
+    shared::newtypes::CorrelationId,
+};
+use casper_hashing::Digest;
+use casper_types::{system::mint::METHOD_MINT, ContractHash, ContractPackageHash};
+
+#[ignore]
+#[test]
+fn get_contract_should_return_parsed_contract_and_package() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+    let state_root_hash = builder.get_post_state_hash();
+    let mint_hash = builder.get_mint_contract_hash();
+
+    let get_contract_result = builder
+        .get_engine_state()
+        .get_contract(
+            CorrelationId::new(),
+            GetContractRequest::new(state_root_hash, mint_hash),
+        )
+        .expect("should get contract");
+    assert!(get_contract_result
+        .entry_points()
+        .expect("should have entry points")
+        .has_entry_point(METHOD_MINT));
+    let mint = get_contract_result
+        .into_success()
+        .expect("should have mint contract");
+
+    let contract_package = builder
+        .get_engine_state()
+        .get_contract_package(
+            CorrelationId::new(),
+            GetContractPackageRequest::new(state_root_hash, mint.contract_package_hash()),
+        )
+        .expect("should get contract package")
+        .into_success()
+        .expect("should have mint contract package");
+    assert_eq!(contract_package.current_contract_hash(), Some(mint_hash));
+}
+
+#[ignore]
+#[test]
+fn get_contract_should_report_missing_contract_and_root() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+    let state_root_hash = builder.get_post_state_hash();
+    let missing_hash = ContractHash::new([42; 32]);
+
+    let get_contract_result = builder
+        .get_engine_state()
+        .get_contract(
+            CorrelationId::new(),
+            GetContractRequest::new(state_root_hash, missing_hash),
+        )
+        .expect("should get contract");
+    assert!(matches!(get_contract_result, GetContractResult::NotFound));
+
+    let get_contract_package_result = builder
+        .get_engine_state()
+        .get_contract_package(
+            CorrelationId::new(),
+            GetContractPackageRequest::new(state_root_hash, ContractPackageHash::new([42; 32])),
+        )
+        .expect("should get contract package");
+    assert!(matches!(
+        get_contract_package_result,
+        GetContractPackageResult::NotFound
+    ));
+
+    let get_contract_result = builder
+        .get_engine_state()
+        .get_contract(
+            CorrelationId::new(),
+            GetContractRequest::new(Digest::hash([42; 32]), missing_hash),
+        )
+        .expect("should get contract");
+    assert!(matches!(
+        get_contract_result,
+        GetContractResult::RootNotFound
+    ));
+}
+
+#[ignore]
+#[test]
+fn builder_should_return_none_for_value_of_other_type() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+    let mint_hash = builder.get_mint_contract_hash();
+    let mint_package_hash = builder
+        .get_expected_contract(mint_hash)
+        .contract_package_hash();
+
+    // The hashes are swapped, so each query finds a value of the other type.
+    assert!(builder
+        .get_contract(ContractHash::new(mint_package_hash.value()))
+        .is_none());
+    assert!(builder
+        .get_contract_package(ContractPackageHash::new(mint_hash.value()))
+        .is_none());
+    assert_eq!(
+        builder
+            .get_expected_contract_package(mint_package_hash)
+            .current_contract_hash(),
+        Some(mint_hash)
+    );
+}
+
+#[ignore]
+#[test]
+#[should_panic(expected = "contract to exist")]
+fn builder_should_panic_on_missing_expected_contract() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+    builder.get_expected_contract(ContractHash::new([42; 32]));
+}
@@ -7,6 +7,7 @@ mod deploy;
 mod explorer;
 mod gas_counter;
 mod get_balance;
+mod get_contract;
 mod groups;
 mod host_function_costs;
 mod manage_groups;
@@ -0,0 +1,209 @@
+use once_cell::sync::Lazy;
+
+use casper_engine_test_support::{
+    ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    MINIMUM_ACCOUNT_CREATION_BALANCE, PRODUCTION_RUN_GENESIS_REQUEST,
+};
+use casper_execution_engine::core::{engine_state, execution};
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::Bytes,
+    runtime_args,
+    system::{
+        auction::{self, DelegationRate, MAX_BID_METADATA_LENGTH},
+        mint,
+    },
+    ApiError, PublicKey, RuntimeArgs, SecretKey, U512,
+};
+
+static VALIDATOR_SECRET_KEY: Lazy<SecretKey> =
+    Lazy::new(|| SecretKey::ed25519_from_bytes([71; 32]).unwrap());
+static VALIDATOR_PUBLIC_KEY: Lazy<PublicKey> =
+    Lazy::new(|| PublicKey::from(&*VALIDATOR_SECRET_KEY));
+static VALIDATOR_ADDR: Lazy<AccountHash> = Lazy::new(|| AccountHash::from(&*VALIDATOR_PUBLIC_KEY));
+
+static OTHER_SECRET_KEY: Lazy<SecretKey> =
+    Lazy::new(|| SecretKey::ed25519_from_bytes([72; 32]).unwrap());
+static OTHER_PUBLIC_KEY: Lazy<PublicKey> = Lazy::new(|| PublicKey::from(&*OTHER_SECRET_KEY));
+static OTHER_ADDR: Lazy<AccountHash> = Lazy::new(|| AccountHash::from(&*OTHER_PUBLIC_KEY));
+
+const BID_DELEGATION_RATE: DelegationRate = 10;
+static BID_AMOUNT: Lazy<U512> = Lazy::new(|| U512::from(1_000_000_000_000u64));
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    for account_hash in [*VALIDATOR_ADDR, *OTHER_ADDR] {
+        let transfer_request = ExecuteRequestBuilder::transfer(
+            *DEFAULT_ACCOUNT_ADDR,
+            runtime_args! {
+                mint::ARG_TARGET => account_hash,
+                mint::ARG_AMOUNT => U512::from(MINIMUM_ACCOUNT_CREATION_BALANCE),
+                mint::ARG_ID => <Option<u64>>::None,
+            },
+        )
+        .build();
+        builder.exec(transfer_request).expect_success().commit();
+    }
+
+    let add_bid_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *VALIDATOR_ADDR,
+        builder.get_auction_contract_hash(),
+        auction::METHOD_ADD_BID,
+        runtime_args! {
+            auction::ARG_PUBLIC_KEY => VALIDATOR_PUBLIC_KEY.clone(),
+            auction::ARG_AMOUNT => *BID_AMOUNT,
+            auction::ARG_DELEGATION_RATE => BID_DELEGATION_RATE,
+        },
+    )
+    .build();
+    builder.exec(add_bid_request).expect_success().commit();
+
+    builder
+}
+
+fn set_bid_metadata(
+    builder: &mut InMemoryWasmTestBuilder,
+    sender: AccountHash,
+    public_key: PublicKey,
+    metadata: Bytes,
+) {
+    let set_bid_metadata_request = ExecuteRequestBuilder::contract_call_by_hash(
+        sender,
+        builder.get_auction_contract_hash(),
+        auction::METHOD_SET_BID_METADATA,
+        runtime_args! {
+            auction::ARG_PUBLIC_KEY => public_key,
+            auction::ARG_METADATA => metadata,
+        },
+    )
+    .build();
+    builder.exec(set_bid_metadata_request).commit();
+}
+
+fn assert_auction_error(builder: &InMemoryWasmTestBuilder, expected: auction::Error) {
+    let error = builder.get_error().expect("should have error");
+    assert!(matches!(
+        error,
+        engine_state::Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == expected as u8
+    ));
+}
+
+#[ignore]
+#[test]
+fn should_set_and_replace_bid_metadata() {
+    let mut builder = setup();
+    assert!(builder.get_bid_metadata().is_empty());
+
+    let metadata = Bytes::from(b"name=Validator One;url=https://example.com".to_vec());
+    set_bid_metadata(
+        &mut builder,
+        *VALIDATOR_ADDR,
+        VALIDATOR_PUBLIC_KEY.clone(),
+        metadata.clone(),
+    );
+    builder.expect_success();
+    assert_eq!(
+        builder.get_bid_metadata().get(&VALIDATOR_PUBLIC_KEY),
+        Some(&metadata)
+    );
+
+    let max_metadata = Bytes::from(vec![1u8; MAX_BID_METADATA_LENGTH]);
+    set_bid_metadata(
+        &mut builder,
+        *VALIDATOR_ADDR,
+        VALIDATOR_PUBLIC_KEY.clone(),
+        max_metadata.clone(),
+    );
+    builder.expect_success();
+    assert_eq!(
+        builder.get_bid_metadata().get(&VALIDATOR_PUBLIC_KEY),
+        Some(&max_metadata)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_set_bid_metadata_over_size_limit() {
+    let mut builder = setup();
+
+    set_bid_metadata(
+        &mut builder,
+        *VALIDATOR_ADDR,
+        VALIDATOR_PUBLIC_KEY.clone(),
+        Bytes::from(vec![1u8; MAX_BID_METADATA_LENGTH + 1]),
+    );
+    assert_auction_error(&builder, auction::Error::BidMetadataTooLarge);
+    assert!(builder.get_bid_metadata().is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_not_set_bid_metadata_for_another_validator() {
+    let mut builder = setup();
+
+    set_bid_metadata(
+        &mut builder,
+        *OTHER_ADDR,
+        VALIDATOR_PUBLIC_KEY.clone(),
+        Bytes::from(b"name=Impostor".to_vec()),
+    );
+    assert_auction_error(&builder, auction::Error::InvalidContext);
+    assert!(builder.get_bid_metadata().is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_not_set_bid_metadata_without_bid() {
+    let mut builder = setup();
+
+    set_bid_metadata(
+        &mut builder,
+        *OTHER_ADDR,
+        OTHER_PUBLIC_KEY.clone(),
+        Bytes::from(b"name=No Bid".to_vec()),
+    );
+    assert_auction_error(&builder, auction::Error::ValidatorNotFound);
+    assert!(builder.get_bid_metadata().is_empty());
+}
+
+fn withdraw_bid(builder: &mut InMemoryWasmTestBuilder, amount: U512) {
+    let withdraw_bid_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *VALIDATOR_ADDR,
+        builder.get_auction_contract_hash(),
+        auction::METHOD_WITHDRAW_BID,
+        runtime_args! {
+            auction::ARG_PUBLIC_KEY => VALIDATOR_PUBLIC_KEY.clone(),
+            auction::ARG_AMOUNT => amount,
+        },
+    )
+    .build();
+    builder.exec(withdraw_bid_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_remove_bid_metadata_once_whole_stake_is_withdrawn() {
+    let mut builder = setup();
+
+    let metadata = Bytes::from(b"name=Validator One".to_vec());
+    set_bid_metadata(
+        &mut builder,
+        *VALIDATOR_ADDR,
+        VALIDATOR_PUBLIC_KEY.clone(),
+        metadata.clone(),
+    );
+    builder.expect_success();
+
+    let partial_amount = *BID_AMOUNT / 2;
+    withdraw_bid(&mut builder, partial_amount);
+    assert_eq!(
+        builder.get_bid_metadata().get(&VALIDATOR_PUBLIC_KEY),
+        Some(&metadata)
+    );
+
+    withdraw_bid(&mut builder, *BID_AMOUNT - partial_amount);
+    assert!(builder.get_bid_metadata().is_empty());
+}
@@ -0,0 +1,283 @@
+use once_cell::sync::Lazy;
+
+use casper_engine_test_support::{
+    ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    MINIMUM_ACCOUNT_CREATION_BALANCE, PRODUCTION_RUN_GENESIS_REQUEST,
+};
+use casper_execution_engine::core::{
+    engine_state::{
+        self, engine_config::DEFAULT_MINIMUM_DELEGATION_AMOUNT, NativeAuctionEntryPoint,
+    },
+    execution,
+};
+use casper_types::{
+    account::AccountHash,
+    runtime_args,
+    system::{
+        auction::{self, DelegationRate},
+        mint,
+    },
+    ApiError, PublicKey, RuntimeArgs, SecretKey, U512,
+};
+
+static VALIDATOR_SECRET_KEY: Lazy<SecretKey> =
+    Lazy::new(|| SecretKey::ed25519_from_bytes([101; 32]).unwrap());
+static VALIDATOR_PUBLIC_KEY: Lazy<PublicKey> =
+    Lazy::new(|| PublicKey::from(&*VALIDATOR_SECRET_KEY));
+static VALIDATOR_ADDR: Lazy<AccountHash> = Lazy::new(|| AccountHash::from(&*VALIDATOR_PUBLIC_KEY));
+
+static DELEGATOR_SECRET_KEY: Lazy<SecretKey> =
+    Lazy::new(|| SecretKey::ed25519_from_bytes([102; 32]).unwrap());
+static DELEGATOR_PUBLIC_KEY: Lazy<PublicKey> =
+    Lazy::new(|| PublicKey::from(&*DELEGATOR_SECRET_KEY));
+static DELEGATOR_ADDR: Lazy<AccountHash> = Lazy::new(|| AccountHash::from(&*DELEGATOR_PUBLIC_KEY));
+
+const BID_DELEGATION_RATE: DelegationRate = 10;
+static BID_AMOUNT: Lazy<U512> = Lazy::new(|| U512::from(1_000_000_000_000u64));
+static DELEGATE_AMOUNT: Lazy<U512> =
+    Lazy::new(|| U512::from(2 * DEFAULT_MINIMUM_DELEGATION_AMOUNT));
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    for account_hash in [*VALIDATOR_ADDR, *DELEGATOR_ADDR] {
+        let transfer_request = ExecuteRequestBuilder::transfer(
+            *DEFAULT_ACCOUNT_ADDR,
+            runtime_args! {
+                mint::ARG_TARGET => account_hash,
+                mint::ARG_AMOUNT => U512::from(MINIMUM_ACCOUNT_CREATION_BALANCE),
+                mint::ARG_ID => <Option<u64>>::None,
+            },
+        )
+        .build();
+        builder.exec(transfer_request).expect_success().commit();
+    }
+
+    let add_bid_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *VALIDATOR_ADDR,
+        builder.get_auction_contract_hash(),
+        auction::METHOD_ADD_BID,
+        runtime_args! {
+            auction::ARG_PUBLIC_KEY => VALIDATOR_PUBLIC_KEY.clone(),
+            auction::ARG_AMOUNT => *BID_AMOUNT,
+            auction::ARG_DELEGATION_RATE => BID_DELEGATION_RATE,
+        },
+    )
+    .build();
+    builder.exec(add_bid_request).expect_success().commit();
+
+    let delegate_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *DELEGATOR_ADDR,
+        builder.get_auction_contract_hash(),
+        auction::METHOD_DELEGATE,
+        runtime_args! {
+            auction::ARG_DELEGATOR => DELEGATOR_PUBLIC_KEY.clone(),
+            auction::ARG_VALIDATOR => VALIDATOR_PUBLIC_KEY.clone(),
+            auction::ARG_AMOUNT => *DELEGATE_AMOUNT,
+        },
+    )
+    .build();
+    builder.exec(delegate_request).expect_success().commit();
+
+    builder
+}
+
+fn undelegate(builder: &mut InMemoryWasmTestBuilder, amount: U512) {
+    let undelegate_request = ExecuteRequestBuilder::native_auction(
+        *DELEGATOR_ADDR,
+        NativeAuctionEntryPoint::Undelegate,
+        runtime_args! {
+            auction::ARG_DELEGATOR => DELEGATOR_PUBLIC_KEY.clone(),
+            auction::ARG_VALIDATOR => VALIDATOR_PUBLIC_KEY.clone(),
+            auction::ARG_AMOUNT => amount,
+        },
+    )
+    .build();
+    builder.exec(undelegate_request).expect_success().commit();
+}
+
+fn cancel_unbonding(
+    builder: &mut InMemoryWasmTestBuilder,
+    sender: AccountHash,
+    public_key: PublicKey,
+    amount: U512,
+) {
+    let cancel_unbonding_request = ExecuteRequestBuilder::native_auction(
+        sender,
+        NativeAuctionEntryPoint::CancelUnbonding,
+        runtime_args! {
+            auction::ARG_PUBLIC_KEY => public_key,
+            auction::ARG_VALIDATOR => VALIDATOR_PUBLIC_KEY.clone(),
+            auction::ARG_AMOUNT => amount,
+        },
+    )
+    .build();
+    builder.exec(cancel_unbonding_request).commit();
+}
+
+fn pending_amounts(builder: &mut InMemoryWasmTestBuilder, public_key: &PublicKey) -> Vec<U512> {
+    builder
+        .get_pending_unbonds(public_key.clone())
+        .iter()
+        .map(|pending_unbond| *pending_unbond.amount())
+        .collect()
+}
+
+fn delegator_stake(builder: &mut InMemoryWasmTestBuilder) -> Option<U512> {
+    builder
+        .get_bids()
+        .get(&VALIDATOR_PUBLIC_KEY)
+        .expect("should have bid")
+        .delegators()
+        .get(&DELEGATOR_PUBLIC_KEY)
+        .map(|delegator| *delegator.staked_amount())
+}
+
+fn assert_auction_error(builder: &InMemoryWasmTestBuilder, expected: auction::Error) {
+    let error = builder.get_error().expect("should have error");
+    assert!(matches!(
+        error,
+        engine_state::Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == expected as u8
+    ));
+}
+
+#[ignore]
+#[test]
+fn should_list_pending_unbonds() {
+    let mut builder = setup();
+    assert!(builder
+        .get_pending_unbonds(DELEGATOR_PUBLIC_KEY.clone())
+        .is_empty());
+
+    let undelegate_amount = U512::from(DEFAULT_MINIMUM_DELEGATION_AMOUNT);
+    undelegate(&mut builder, undelegate_amount);
+
+    let pending_unbonds = builder.get_pending_unbonds(DELEGATOR_PUBLIC_KEY.clone());
+    assert_eq!(pending_unbonds.len(), 1);
+    assert_eq!(*pending_unbonds[0].amount(), undelegate_amount);
+    assert_eq!(
+        pending_unbonds[0].era_available(),
+        builder.get_era() + builder.get_unbonding_delay()
+    );
+    assert!(builder
+        .get_pending_unbonds(VALIDATOR_PUBLIC_KEY.clone())
+        .is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_cancel_part_of_unbonding_most_recent_first() {
+    let mut builder = setup();
+    let first_amount = U512::from(DEFAULT_MINIMUM_DELEGATION_AMOUNT / 2);
+    let second_amount = U512::from(DEFAULT_MINIMUM_DELEGATION_AMOUNT / 4);
+    undelegate(&mut builder, first_amount);
+    undelegate(&mut builder, second_amount);
+
+    let cancelled_amount = second_amount + U512::from(1);
+    cancel_unbonding(
+        &mut builder,
+        *DELEGATOR_ADDR,
+        DELEGATOR_PUBLIC_KEY.clone(),
+        cancelled_amount,
+    );
+    builder.expect_success();
+
+    // The most recent request is cancelled in full, and the older one shrinks by the remainder.
+    assert_eq!(
+        pending_amounts(&mut builder, &DELEGATOR_PUBLIC_KEY),
+        vec![first_amount - U512::from(1)]
+    );
+    assert_eq!(
+        delegator_stake(&mut builder),
+        Some(*DELEGATE_AMOUNT - first_amount - second_amount + cancelled_amount)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_restore_delegator_after_cancelling_full_undelegation() {
+    let mut builder = setup();
+    undelegate(&mut builder, *DELEGATE_AMOUNT);
+    assert_eq!(delegator_stake(&mut builder), None);
+
+    cancel_unbonding(
+        &mut builder,
+        *DELEGATOR_ADDR,
+        DELEGATOR_PUBLIC_KEY.clone(),
+        *DELEGATE_AMOUNT,
+    );
+    builder.expect_success();
+
+    assert_eq!(delegator_stake(&mut builder), Some(*DELEGATE_AMOUNT));
+    assert!(pending_amounts(&mut builder, &DELEGATOR_PUBLIC_KEY).is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_cancel_validator_unbonding() {
+    let mut builder = setup();
+    let withdraw_amount = U512::from(1_000_000_000u64);
+    let withdraw_bid_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *VALIDATOR_ADDR,
+        builder.get_auction_contract_hash(),
+        auction::METHOD_WITHDRAW_BID,
+        runtime_args! {
+            auction::ARG_PUBLIC_KEY => VALIDATOR_PUBLIC_KEY.clone(),
+            auction::ARG_AMOUNT => withdraw_amount,
+        },
+    )
+    .build();
+    builder.exec(withdraw_bid_request).expect_success().commit();
+
+    cancel_unbonding(
+        &mut builder,
+        *VALIDATOR_ADDR,
+        VALIDATOR_PUBLIC_KEY.clone(),
+        withdraw_amount,
+    );
+    builder.expect_success();
+
+    let bids = builder.get_bids();
+    let bid = bids.get(&VALIDATOR_PUBLIC_KEY).expect("should have bid");
+    assert_eq!(*bid.staked_amount(), *BID_AMOUNT);
+    assert!(pending_amounts(&mut builder, &VALIDATOR_PUBLIC_KEY).is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_not_cancel_more_than_pending() {
+    let mut builder = setup();
+    let undelegate_amount = U512::from(DEFAULT_MINIMUM_DELEGATION_AMOUNT);
+    undelegate(&mut builder, undelegate_amount);
+
+    cancel_unbonding(
+        &mut builder,
+        *DELEGATOR_ADDR,
+        DELEGATOR_PUBLIC_KEY.clone(),
+        undelegate_amount + U512::from(1),
+    );
+    assert_auction_error(&builder, auction::Error::CancelUnbondingTooLarge);
+
+    // The validator has nothing pending, and can't cancel the delegator's request.
+    cancel_unbonding(
+        &mut builder,
+        *VALIDATOR_ADDR,
+        VALIDATOR_PUBLIC_KEY.clone(),
+        U512::from(1),
+    );
+    assert_auction_error(&builder, auction::Error::CancelUnbondingTooLarge);
+    cancel_unbonding(
+        &mut builder,
+        *VALIDATOR_ADDR,
+        DELEGATOR_PUBLIC_KEY.clone(),
+        U512::from(1),
+    );
+    assert_auction_error(&builder, auction::Error::InvalidContext);
+
+    assert_eq!(
+        pending_amounts(&mut builder, &DELEGATOR_PUBLIC_KEY),
+        vec![undelegate_amount]
+    );
+}
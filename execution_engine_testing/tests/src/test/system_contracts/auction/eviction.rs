@@ -0,0 +1,178 @@
+use once_cell::sync::Lazy;
+
+use casper_engine_test_support::{
+    ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_GENESIS_TIMESTAMP_MILLIS, MINIMUM_ACCOUNT_CREATION_BALANCE,
+    PRODUCTION_RUN_GENESIS_REQUEST, SYSTEM_ADDR,
+};
+use casper_execution_engine::core::engine_state::{EngineConfigBuilder, NativeAuctionEntryPoint};
+use casper_types::{
+    account::AccountHash,
+    runtime_args,
+    system::{
+        auction::{self, DelegationRate},
+        mint,
+    },
+    PublicKey, RuntimeArgs, SecretKey, U512,
+};
+
+static VALIDATOR_SECRET_KEY: Lazy<SecretKey> =
+    Lazy::new(|| SecretKey::ed25519_from_bytes([81; 32]).unwrap());
+static VALIDATOR_PUBLIC_KEY: Lazy<PublicKey> =
+    Lazy::new(|| PublicKey::from(&*VALIDATOR_SECRET_KEY));
+static VALIDATOR_ADDR: Lazy<AccountHash> = Lazy::new(|| AccountHash::from(&*VALIDATOR_PUBLIC_KEY));
+
+const BID_DELEGATION_RATE: DelegationRate = 10;
+static BID_AMOUNT: Lazy<U512> = Lazy::new(|| U512::from(1_000_000_000_000u64));
+
+const ERA_DURATION_MILLIS: u64 = 2 * 60 * 60 * 1000;
+
+struct EvictionFixture {
+    builder: InMemoryWasmTestBuilder,
+    era_end_timestamp_millis: u64,
+}
+
+impl EvictionFixture {
+    fn new(validator_eviction_threshold: u64) -> Self {
+        let engine_config = EngineConfigBuilder::new()
+            .with_validator_eviction_threshold(validator_eviction_threshold)
+            .build();
+        let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+        builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+        for account_hash in [*SYSTEM_ADDR, *VALIDATOR_ADDR] {
+            let transfer_request = ExecuteRequestBuilder::transfer(
+                *DEFAULT_ACCOUNT_ADDR,
+                runtime_args! {
+                    mint::ARG_TARGET => account_hash,
+                    mint::ARG_AMOUNT => U512::from(MINIMUM_ACCOUNT_CREATION_BALANCE),
+                    mint::ARG_ID => <Option<u64>>::None,
+                },
+            )
+            .build();
+            builder.exec(transfer_request).expect_success().commit();
+        }
+
+        let add_bid_request = ExecuteRequestBuilder::contract_call_by_hash(
+            *VALIDATOR_ADDR,
+            builder.get_auction_contract_hash(),
+            auction::METHOD_ADD_BID,
+            runtime_args! {
+                auction::ARG_PUBLIC_KEY => VALIDATOR_PUBLIC_KEY.clone(),
+                auction::ARG_AMOUNT => *BID_AMOUNT,
+                auction::ARG_DELEGATION_RATE => BID_DELEGATION_RATE,
+            },
+        )
+        .build();
+        builder.exec(add_bid_request).expect_success().commit();
+
+        EvictionFixture {
+            builder,
+            era_end_timestamp_millis: DEFAULT_GENESIS_TIMESTAMP_MILLIS,
+        }
+    }
+
+    fn end_era(&mut self, validator_inactive: bool) {
+        let evicted_validators = if validator_inactive {
+            vec![VALIDATOR_PUBLIC_KEY.clone()]
+        } else {
+            Vec::new()
+        };
+        self.era_end_timestamp_millis += ERA_DURATION_MILLIS;
+        self.builder
+            .run_auction(self.era_end_timestamp_millis, evicted_validators);
+    }
+
+    fn activate_bid_natively(&mut self) {
+        let activate_bid_request = ExecuteRequestBuilder::native_auction(
+            *VALIDATOR_ADDR,
+            NativeAuctionEntryPoint::ActivateBid,
+            runtime_args! {
+                auction::ARG_VALIDATOR_PUBLIC_KEY => VALIDATOR_PUBLIC_KEY.clone(),
+            },
+        )
+        .build();
+        self.builder
+            .exec(activate_bid_request)
+            .expect_success()
+            .commit();
+    }
+
+    fn is_bid_inactive(&mut self) -> bool {
+        self.builder
+            .get_bids()
+            .get(&VALIDATOR_PUBLIC_KEY)
+            .expect("should have bid")
+            .inactive()
+    }
+}
+
+#[ignore]
+#[test]
+fn should_evict_after_consecutive_inactive_eras() {
+    let mut fixture = EvictionFixture::new(3);
+
+    fixture.end_era(true);
+    fixture.end_era(true);
+    assert!(!fixture.is_bid_inactive());
+
+    // An active era resets the count.
+    fixture.end_era(false);
+    fixture.end_era(true);
+    fixture.end_era(true);
+    assert!(!fixture.is_bid_inactive());
+
+    fixture.end_era(true);
+    assert!(fixture.is_bid_inactive());
+}
+
+#[ignore]
+#[test]
+fn should_evict_after_single_inactive_era_by_default() {
+    let mut fixture = EvictionFixture::new(1);
+
+    fixture.end_era(true);
+    assert!(fixture.is_bid_inactive());
+}
+
+#[ignore]
+#[test]
+fn should_not_evict_with_zero_threshold() {
+    let mut fixture = EvictionFixture::new(0);
+
+    for _ in 0..3 {
+        fixture.end_era(true);
+    }
+    assert!(!fixture.is_bid_inactive());
+}
+
+#[ignore]
+#[test]
+fn should_reactivate_evicted_bid_natively() {
+    let mut fixture = EvictionFixture::new(2);
+
+    fixture.end_era(true);
+    fixture.end_era(true);
+    assert!(fixture.is_bid_inactive());
+
+    fixture.activate_bid_natively();
+    assert!(!fixture.is_bid_inactive());
+
+    // The count starts over after reactivation.
+    fixture.end_era(true);
+    assert!(!fixture.is_bid_inactive());
+}
+
+#[ignore]
+#[test]
+fn should_clear_inactive_eras_on_activate_bid() {
+    let mut fixture = EvictionFixture::new(2);
+
+    fixture.end_era(true);
+    fixture.activate_bid_natively();
+    fixture.end_era(true);
+    assert!(!fixture.is_bid_inactive());
+
+    fixture.end_era(true);
+    assert!(fixture.is_bid_inactive());
+}
@@ -1,2 +1,7 @@
+mod bid_metadata;
 mod bids;
+mod cancel_unbonding;
 mod distribute;
+mod eviction;
+mod native;
+mod rewards_destination;
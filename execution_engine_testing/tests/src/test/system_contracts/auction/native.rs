@@ -0,0 +1,200 @@
+use once_cell::sync::Lazy;
+
+use casper_engine_test_support::{
+    ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    MINIMUM_ACCOUNT_CREATION_BALANCE, PRODUCTION_RUN_GENESIS_REQUEST,
+};
+use casper_execution_engine::core::engine_state::{
+    engine_config::DEFAULT_MINIMUM_DELEGATION_AMOUNT, NativeAuctionEntryPoint,
+};
+use casper_types::{
+    account::AccountHash,
+    runtime_args,
+    system::{
+        auction::{self, DelegationRate},
+        mint,
+    },
+    PublicKey, RuntimeArgs, SecretKey, U512,
+};
+
+static VALIDATOR_1_SECRET_KEY: Lazy<SecretKey> =
+    Lazy::new(|| SecretKey::ed25519_from_bytes([61; 32]).unwrap());
+static VALIDATOR_1_PUBLIC_KEY: Lazy<PublicKey> =
+    Lazy::new(|| PublicKey::from(&*VALIDATOR_1_SECRET_KEY));
+static VALIDATOR_1_ADDR: Lazy<AccountHash> =
+    Lazy::new(|| AccountHash::from(&*VALIDATOR_1_PUBLIC_KEY));
+
+static VALIDATOR_2_SECRET_KEY: Lazy<SecretKey> =
+    Lazy::new(|| SecretKey::ed25519_from_bytes([62; 32]).unwrap());
+static VALIDATOR_2_PUBLIC_KEY: Lazy<PublicKey> =
+    Lazy::new(|| PublicKey::from(&*VALIDATOR_2_SECRET_KEY));
+static VALIDATOR_2_ADDR: Lazy<AccountHash> =
+    Lazy::new(|| AccountHash::from(&*VALIDATOR_2_PUBLIC_KEY));
+
+static DELEGATOR_SECRET_KEY: Lazy<SecretKey> =
+    Lazy::new(|| SecretKey::ed25519_from_bytes([63; 32]).unwrap());
+static DELEGATOR_PUBLIC_KEY: Lazy<PublicKey> =
+    Lazy::new(|| PublicKey::from(&*DELEGATOR_SECRET_KEY));
+static DELEGATOR_ADDR: Lazy<AccountHash> = Lazy::new(|| AccountHash::from(&*DELEGATOR_PUBLIC_KEY));
+
+const BID_DELEGATION_RATE: DelegationRate = 10;
+static BID_AMOUNT: Lazy<U512> = Lazy::new(|| U512::from(1_000_000_000_000u64));
+static DELEGATE_AMOUNT: Lazy<U512> = Lazy::new(|| U512::from(DEFAULT_MINIMUM_DELEGATION_AMOUNT));
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    for account_hash in [*VALIDATOR_1_ADDR, *VALIDATOR_2_ADDR, *DELEGATOR_ADDR] {
+        let transfer_request = ExecuteRequestBuilder::transfer(
+            *DEFAULT_ACCOUNT_ADDR,
+            runtime_args! {
+                mint::ARG_TARGET => account_hash,
+                mint::ARG_AMOUNT => U512::from(MINIMUM_ACCOUNT_CREATION_BALANCE),
+                mint::ARG_ID => <Option<u64>>::None,
+            },
+        )
+        .build();
+        builder.exec(transfer_request).expect_success().commit();
+    }
+
+    for (validator_addr, validator_public_key) in [
+        (*VALIDATOR_1_ADDR, VALIDATOR_1_PUBLIC_KEY.clone()),
+        (*VALIDATOR_2_ADDR, VALIDATOR_2_PUBLIC_KEY.clone()),
+    ] {
+        let add_bid_request = ExecuteRequestBuilder::contract_call_by_hash(
+            validator_addr,
+            builder.get_auction_contract_hash(),
+            auction::METHOD_ADD_BID,
+            runtime_args! {
+                auction::ARG_PUBLIC_KEY => validator_public_key,
+                auction::ARG_AMOUNT => *BID_AMOUNT,
+                auction::ARG_DELEGATION_RATE => BID_DELEGATION_RATE,
+            },
+        )
+        .build();
+        builder.exec(add_bid_request).expect_success().commit();
+    }
+
+    builder
+}
+
+fn native_delegate(builder: &mut InMemoryWasmTestBuilder) {
+    let delegate_request = ExecuteRequestBuilder::native_auction(
+        *DELEGATOR_ADDR,
+        NativeAuctionEntryPoint::Delegate,
+        runtime_args! {
+            auction::ARG_DELEGATOR => DELEGATOR_PUBLIC_KEY.clone(),
+            auction::ARG_VALIDATOR => VALIDATOR_1_PUBLIC_KEY.clone(),
+            auction::ARG_AMOUNT => *DELEGATE_AMOUNT,
+        },
+    )
+    .build();
+    builder.exec(delegate_request).expect_success().commit();
+}
+
+fn delegated_amount(
+    builder: &mut InMemoryWasmTestBuilder,
+    validator_public_key: &PublicKey,
+) -> Option<U512> {
+    let bids = builder.get_bids();
+    let bid = bids.get(validator_public_key).expect("should have bid");
+    bid.delegators()
+        .get(&DELEGATOR_PUBLIC_KEY)
+        .map(|delegator| *delegator.staked_amount())
+}
+
+#[ignore]
+#[test]
+fn should_delegate_and_undelegate_natively() {
+    let mut builder = setup();
+
+    native_delegate(&mut builder);
+    assert_eq!(
+        delegated_amount(&mut builder, &VALIDATOR_1_PUBLIC_KEY),
+        Some(*DELEGATE_AMOUNT)
+    );
+
+    let undelegate_request = ExecuteRequestBuilder::native_auction(
+        *DELEGATOR_ADDR,
+        NativeAuctionEntryPoint::Undelegate,
+        runtime_args! {
+            auction::ARG_DELEGATOR => DELEGATOR_PUBLIC_KEY.clone(),
+            auction::ARG_VALIDATOR => VALIDATOR_1_PUBLIC_KEY.clone(),
+            auction::ARG_AMOUNT => *DELEGATE_AMOUNT,
+        },
+    )
+    .build();
+    builder.exec(undelegate_request).expect_success().commit();
+
+    assert_eq!(
+        delegated_amount(&mut builder, &VALIDATOR_1_PUBLIC_KEY),
+        None
+    );
+    let unbonds = builder.get_unbonds();
+    let unbonding_purses = unbonds
+        .get(&VALIDATOR_1_ADDR)
+        .expect("should have unbonding purses");
+    assert_eq!(unbonding_purses.len(), 1);
+    assert_eq!(
+        unbonding_purses[0].unbonder_public_key(),
+        &*DELEGATOR_PUBLIC_KEY
+    );
+    assert_eq!(*unbonding_purses[0].amount(), *DELEGATE_AMOUNT);
+    assert_eq!(unbonding_purses[0].new_validator(), &None);
+}
+
+#[ignore]
+#[test]
+fn should_redelegate_natively() {
+    let mut builder = setup();
+
+    native_delegate(&mut builder);
+
+    let redelegate_request = ExecuteRequestBuilder::native_auction(
+        *DELEGATOR_ADDR,
+        NativeAuctionEntryPoint::Redelegate,
+        runtime_args! {
+            auction::ARG_DELEGATOR => DELEGATOR_PUBLIC_KEY.clone(),
+            auction::ARG_VALIDATOR => VALIDATOR_1_PUBLIC_KEY.clone(),
+            auction::ARG_AMOUNT => *DELEGATE_AMOUNT,
+            auction::ARG_NEW_VALIDATOR => VALIDATOR_2_PUBLIC_KEY.clone(),
+        },
+    )
+    .build();
+    builder.exec(redelegate_request).expect_success().commit();
+
+    let unbonds = builder.get_unbonds();
+    let unbonding_purses = unbonds
+        .get(&VALIDATOR_1_ADDR)
+        .expect("should have unbonding purses");
+    assert_eq!(unbonding_purses.len(), 1);
+    assert_eq!(
+        unbonding_purses[0].new_validator(),
+        &Some(VALIDATOR_2_PUBLIC_KEY.clone())
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_delegate_natively_for_another_account() {
+    let mut builder = setup();
+
+    let delegate_request = ExecuteRequestBuilder::native_auction(
+        *VALIDATOR_2_ADDR,
+        NativeAuctionEntryPoint::Delegate,
+        runtime_args! {
+            auction::ARG_DELEGATOR => DELEGATOR_PUBLIC_KEY.clone(),
+            auction::ARG_VALIDATOR => VALIDATOR_1_PUBLIC_KEY.clone(),
+            auction::ARG_AMOUNT => *DELEGATE_AMOUNT,
+        },
+    )
+    .build();
+    builder.exec(delegate_request).commit();
+
+    assert!(builder.get_error().is_some());
+    assert_eq!(
+        delegated_amount(&mut builder, &VALIDATOR_1_PUBLIC_KEY),
+        None
+    );
+}
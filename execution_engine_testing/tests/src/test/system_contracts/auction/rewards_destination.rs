@@ -0,0 +1,384 @@
+use std::collections::BTreeSet;
+
+use once_cell::sync::Lazy;
+
+use casper_engine_test_support::{
+    ExecuteRequestBuilder, InMemoryWasmTestBuilder, StepRequestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_PROTOCOL_VERSION, MINIMUM_ACCOUNT_CREATION_BALANCE, PRODUCTION_RUN_GENESIS_REQUEST,
+};
+use casper_execution_engine::core::{
+    engine_state::{self, engine_config::DEFAULT_MINIMUM_DELEGATION_AMOUNT, step::RewardItem},
+    execution,
+};
+use casper_types::{
+    account::AccountHash,
+    runtime_args,
+    system::{
+        auction::{self, DelegationRate, RewardsDestination, RewardsDestinations, BLOCK_REWARD},
+        mint,
+    },
+    ApiError, PublicKey, RuntimeArgs, SecretKey, U512,
+};
+
+static VALIDATOR_SECRET_KEY: Lazy<SecretKey> =
+    Lazy::new(|| SecretKey::ed25519_from_bytes([91; 32]).unwrap());
+static VALIDATOR_PUBLIC_KEY: Lazy<PublicKey> =
+    Lazy::new(|| PublicKey::from(&*VALIDATOR_SECRET_KEY));
+static VALIDATOR_ADDR: Lazy<AccountHash> = Lazy::new(|| AccountHash::from(&*VALIDATOR_PUBLIC_KEY));
+
+static DELEGATOR_1_SECRET_KEY: Lazy<SecretKey> =
+    Lazy::new(|| SecretKey::ed25519_from_bytes([92; 32]).unwrap());
+static DELEGATOR_1_PUBLIC_KEY: Lazy<PublicKey> =
+    Lazy::new(|| PublicKey::from(&*DELEGATOR_1_SECRET_KEY));
+static DELEGATOR_1_ADDR: Lazy<AccountHash> =
+    Lazy::new(|| AccountHash::from(&*DELEGATOR_1_PUBLIC_KEY));
+
+static DELEGATOR_2_SECRET_KEY: Lazy<SecretKey> =
+    Lazy::new(|| SecretKey::ed25519_from_bytes([93; 32]).unwrap());
+static DELEGATOR_2_PUBLIC_KEY: Lazy<PublicKey> =
+    Lazy::new(|| PublicKey::from(&*DELEGATOR_2_SECRET_KEY));
+static DELEGATOR_2_ADDR: Lazy<AccountHash> =
+    Lazy::new(|| AccountHash::from(&*DELEGATOR_2_PUBLIC_KEY));
+
+const BID_DELEGATION_RATE: DelegationRate = 10;
+static BID_AMOUNT: Lazy<U512> = Lazy::new(|| U512::from(1_000_000_000_000u64));
+static DELEGATE_AMOUNT: Lazy<U512> = Lazy::new(|| U512::from(DEFAULT_MINIMUM_DELEGATION_AMOUNT));
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    for account_hash in [*VALIDATOR_ADDR, *DELEGATOR_1_ADDR, *DELEGATOR_2_ADDR] {
+        let transfer_request = ExecuteRequestBuilder::transfer(
+            *DEFAULT_ACCOUNT_ADDR,
+            runtime_args! {
+                mint::ARG_TARGET => account_hash,
+                mint::ARG_AMOUNT => U512::from(MINIMUM_ACCOUNT_CREATION_BALANCE),
+                mint::ARG_ID => <Option<u64>>::None,
+            },
+        )
+        .build();
+        builder.exec(transfer_request).expect_success().commit();
+    }
+
+    let add_bid_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *VALIDATOR_ADDR,
+        builder.get_auction_contract_hash(),
+        auction::METHOD_ADD_BID,
+        runtime_args! {
+            auction::ARG_PUBLIC_KEY => VALIDATOR_PUBLIC_KEY.clone(),
+            auction::ARG_AMOUNT => *BID_AMOUNT,
+            auction::ARG_DELEGATION_RATE => BID_DELEGATION_RATE,
+        },
+    )
+    .build();
+    builder.exec(add_bid_request).expect_success().commit();
+
+    for (delegator_addr, delegator_public_key) in [
+        (*DELEGATOR_1_ADDR, DELEGATOR_1_PUBLIC_KEY.clone()),
+        (*DELEGATOR_2_ADDR, DELEGATOR_2_PUBLIC_KEY.clone()),
+    ] {
+        let delegate_request = ExecuteRequestBuilder::contract_call_by_hash(
+            delegator_addr,
+            builder.get_auction_contract_hash(),
+            auction::METHOD_DELEGATE,
+            runtime_args! {
+                auction::ARG_DELEGATOR => delegator_public_key,
+                auction::ARG_VALIDATOR => VALIDATOR_PUBLIC_KEY.clone(),
+                auction::ARG_AMOUNT => *DELEGATE_AMOUNT,
+            },
+        )
+        .build();
+        builder.exec(delegate_request).expect_success().commit();
+    }
+
+    builder
+}
+
+fn set_rewards_destination(
+    builder: &mut InMemoryWasmTestBuilder,
+    sender: AccountHash,
+    public_key: PublicKey,
+    rewards_destination: RewardsDestination,
+) {
+    let request = ExecuteRequestBuilder::contract_call_by_hash(
+        sender,
+        builder.get_auction_contract_hash(),
+        auction::METHOD_SET_REWARDS_DESTINATION,
+        runtime_args! {
+            auction::ARG_PUBLIC_KEY => public_key,
+            auction::ARG_REWARDS_DESTINATION => rewards_destination,
+        },
+    )
+    .build();
+    builder.exec(request).commit();
+}
+
+fn set_delegator_rewards_destination(
+    builder: &mut InMemoryWasmTestBuilder,
+    sender: AccountHash,
+    delegator_public_key: PublicKey,
+    rewards_destination: RewardsDestination,
+) {
+    let request = ExecuteRequestBuilder::contract_call_by_hash(
+        sender,
+        builder.get_auction_contract_hash(),
+        auction::METHOD_SET_DELEGATOR_REWARDS_DESTINATION,
+        runtime_args! {
+            auction::ARG_DELEGATOR => delegator_public_key,
+            auction::ARG_VALIDATOR => VALIDATOR_PUBLIC_KEY.clone(),
+            auction::ARG_REWARDS_DESTINATION => rewards_destination,
+        },
+    )
+    .build();
+    builder.exec(request).commit();
+}
+
+/// Advances eras until the validator is in the validator set, then ends an era rewarding it.
+fn distribute_rewards(builder: &mut InMemoryWasmTestBuilder) {
+    for _ in 0..=builder.get_auction_delay() {
+        let step_request = StepRequestBuilder::new()
+            .with_parent_state_hash(builder.get_post_state_hash())
+            .with_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+            .with_next_era_id(builder.get_era().successor())
+            .with_run_auction(true)
+            .build();
+        builder
+            .step(step_request)
+            .expect("must execute step successfully");
+    }
+
+    let step_request = StepRequestBuilder::new()
+        .with_parent_state_hash(builder.get_post_state_hash())
+        .with_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+        .with_reward_item(RewardItem::new(VALIDATOR_PUBLIC_KEY.clone(), BLOCK_REWARD))
+        .with_next_era_id(builder.get_era().successor())
+        .with_run_auction(true)
+        .build();
+    builder
+        .step(step_request)
+        .expect("must execute step successfully");
+}
+
+fn main_purse_balance(builder: &InMemoryWasmTestBuilder, account_hash: AccountHash) -> U512 {
+    let main_purse = builder
+        .get_account(account_hash)
+        .expect("should have account")
+        .main_purse();
+    builder.get_purse_balance(main_purse)
+}
+
+fn validator_stake(builder: &mut InMemoryWasmTestBuilder) -> U512 {
+    *builder
+        .get_bids()
+        .get(&VALIDATOR_PUBLIC_KEY)
+        .expect("should have bid")
+        .staked_amount()
+}
+
+fn delegator_stake(
+    builder: &mut InMemoryWasmTestBuilder,
+    delegator_public_key: &PublicKey,
+) -> U512 {
+    *builder
+        .get_bids()
+        .get(&VALIDATOR_PUBLIC_KEY)
+        .expect("should have bid")
+        .delegators()
+        .get(delegator_public_key)
+        .expect("should have delegator")
+        .staked_amount()
+}
+
+fn assert_auction_error(builder: &InMemoryWasmTestBuilder, expected: auction::Error) {
+    let error = builder.get_error().expect("should have error");
+    assert!(matches!(
+        error,
+        engine_state::Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == expected as u8
+    ));
+}
+
+#[ignore]
+#[test]
+fn should_pay_rewards_to_main_purse_when_chosen() {
+    let mut builder = setup();
+
+    set_rewards_destination(
+        &mut builder,
+        *VALIDATOR_ADDR,
+        VALIDATOR_PUBLIC_KEY.clone(),
+        RewardsDestination::MainPurse,
+    );
+    builder.expect_success();
+    set_delegator_rewards_destination(
+        &mut builder,
+        *DELEGATOR_1_ADDR,
+        DELEGATOR_1_PUBLIC_KEY.clone(),
+        RewardsDestination::MainPurse,
+    );
+    builder.expect_success();
+
+    let validator_balance_before = main_purse_balance(&builder, *VALIDATOR_ADDR);
+    let delegator_1_balance_before = main_purse_balance(&builder, *DELEGATOR_1_ADDR);
+    let delegator_2_balance_before = main_purse_balance(&builder, *DELEGATOR_2_ADDR);
+
+    distribute_rewards(&mut builder);
+
+    // The validator and delegator 1 are paid liquid rewards, leaving their stakes unchanged.
+    assert_eq!(validator_stake(&mut builder), *BID_AMOUNT);
+    assert!(main_purse_balance(&builder, *VALIDATOR_ADDR) > validator_balance_before);
+    assert_eq!(
+        delegator_stake(&mut builder, &DELEGATOR_1_PUBLIC_KEY),
+        *DELEGATE_AMOUNT
+    );
+    assert!(main_purse_balance(&builder, *DELEGATOR_1_ADDR) > delegator_1_balance_before);
+
+    // Delegator 2 keeps compounding its rewards.
+    assert!(delegator_stake(&mut builder, &DELEGATOR_2_PUBLIC_KEY) > *DELEGATE_AMOUNT);
+    assert_eq!(
+        main_purse_balance(&builder, *DELEGATOR_2_ADDR),
+        delegator_2_balance_before
+    );
+}
+
+#[ignore]
+#[test]
+fn should_compound_rewards_after_switching_back_to_stake() {
+    let mut builder = setup();
+
+    for rewards_destination in [RewardsDestination::MainPurse, RewardsDestination::Stake] {
+        set_rewards_destination(
+            &mut builder,
+            *VALIDATOR_ADDR,
+            VALIDATOR_PUBLIC_KEY.clone(),
+            rewards_destination,
+        );
+        builder.expect_success();
+    }
+
+    let validator_balance_before = main_purse_balance(&builder, *VALIDATOR_ADDR);
+
+    distribute_rewards(&mut builder);
+
+    assert!(validator_stake(&mut builder) > *BID_AMOUNT);
+    assert_eq!(
+        main_purse_balance(&builder, *VALIDATOR_ADDR),
+        validator_balance_before
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_set_rewards_destination_for_another_staker() {
+    let mut builder = setup();
+
+    set_rewards_destination(
+        &mut builder,
+        *DELEGATOR_1_ADDR,
+        VALIDATOR_PUBLIC_KEY.clone(),
+        RewardsDestination::MainPurse,
+    );
+    assert_auction_error(&builder, auction::Error::InvalidContext);
+
+    set_delegator_rewards_destination(
+        &mut builder,
+        *DELEGATOR_1_ADDR,
+        DELEGATOR_2_PUBLIC_KEY.clone(),
+        RewardsDestination::MainPurse,
+    );
+    assert_auction_error(&builder, auction::Error::InvalidContext);
+}
+
+#[ignore]
+#[test]
+fn should_not_set_rewards_destination_without_stake() {
+    let mut builder = setup();
+
+    set_rewards_destination(
+        &mut builder,
+        *DELEGATOR_1_ADDR,
+        DELEGATOR_1_PUBLIC_KEY.clone(),
+        RewardsDestination::MainPurse,
+    );
+    assert_auction_error(&builder, auction::Error::ValidatorNotFound);
+
+    set_delegator_rewards_destination(
+        &mut builder,
+        *VALIDATOR_ADDR,
+        VALIDATOR_PUBLIC_KEY.clone(),
+        RewardsDestination::MainPurse,
+    );
+    assert_auction_error(&builder, auction::Error::DelegatorNotFound);
+}
+
+fn rewards_destinations(builder: &mut InMemoryWasmTestBuilder) -> RewardsDestinations {
+    let auction_hash = builder.get_auction_contract_hash();
+    builder.get_value(auction_hash, auction::REWARDS_DESTINATIONS_KEY)
+}
+
+#[ignore]
+#[test]
+fn should_remove_rewards_destinations_of_exited_stakers() {
+    let mut builder = setup();
+
+    set_rewards_destination(
+        &mut builder,
+        *VALIDATOR_ADDR,
+        VALIDATOR_PUBLIC_KEY.clone(),
+        RewardsDestination::MainPurse,
+    );
+    builder.expect_success();
+    for (delegator_addr, delegator_public_key) in [
+        (*DELEGATOR_1_ADDR, DELEGATOR_1_PUBLIC_KEY.clone()),
+        (*DELEGATOR_2_ADDR, DELEGATOR_2_PUBLIC_KEY.clone()),
+    ] {
+        set_delegator_rewards_destination(
+            &mut builder,
+            delegator_addr,
+            delegator_public_key,
+            RewardsDestination::MainPurse,
+        );
+        builder.expect_success();
+    }
+    assert_eq!(rewards_destinations(&mut builder).len(), 3);
+
+    // Delegator 1 undelegates its whole stake, dropping only its own choice.
+    let undelegate_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *DELEGATOR_1_ADDR,
+        builder.get_auction_contract_hash(),
+        auction::METHOD_UNDELEGATE,
+        runtime_args! {
+            auction::ARG_DELEGATOR => DELEGATOR_1_PUBLIC_KEY.clone(),
+            auction::ARG_VALIDATOR => VALIDATOR_PUBLIC_KEY.clone(),
+            auction::ARG_AMOUNT => *DELEGATE_AMOUNT,
+        },
+    )
+    .build();
+    builder.exec(undelegate_request).expect_success().commit();
+    let remaining: BTreeSet<_> = rewards_destinations(&mut builder).into_keys().collect();
+    assert_eq!(
+        remaining,
+        BTreeSet::from([
+            (VALIDATOR_PUBLIC_KEY.clone(), None),
+            (
+                VALIDATOR_PUBLIC_KEY.clone(),
+                Some(DELEGATOR_2_PUBLIC_KEY.clone())
+            ),
+        ])
+    );
+
+    // The validator withdraws its whole stake, which also unbonds delegator 2.
+    let withdraw_bid_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *VALIDATOR_ADDR,
+        builder.get_auction_contract_hash(),
+        auction::METHOD_WITHDRAW_BID,
+        runtime_args! {
+            auction::ARG_PUBLIC_KEY => VALIDATOR_PUBLIC_KEY.clone(),
+            auction::ARG_AMOUNT => *BID_AMOUNT,
+        },
+    )
+    .build();
+    builder.exec(withdraw_bid_request).expect_success().commit();
+    assert!(rewards_destinations(&mut builder).is_empty());
+}
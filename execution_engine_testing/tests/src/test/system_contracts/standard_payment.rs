@@ -9,7 +9,7 @@ use casper_engine_test_support::{
 };
 use casper_execution_engine::{
     core::{
-        engine_state::{Error, MAX_PAYMENT},
+        engine_state::{execution_result::PaymentSettlement, Error, MAX_PAYMENT},
         execution,
     },
     shared::transform::Transform,
@@ -145,6 +145,15 @@ fn should_forward_payment_execution_runtime_error() {
         error,
         Error::Exec(execution::Error::Revert(ApiError::User(100)))
     );
+
+    // The engine reports the whole of the maximum payment as the fee, with nothing refunded.
+    assert_eq!(
+        execution_result.payment_settlement(),
+        Some(PaymentSettlement {
+            fee: Motes::new(transaction_fee),
+            refund: Motes::new(U512::zero()),
+        })
+    );
 }
 
 #[ignore]
@@ -424,6 +433,20 @@ fn should_correctly_charge_when_session_code_succeeds() {
     let total = transaction_fee_1 + U512::from(transferred_amount);
     let tally = total + modified_balance;
 
+    // The engine reports the fee actually paid to the proposer, and the rest of the payment as
+    // refunded.
+    let response = builder
+        .get_exec_result_owned(0)
+        .expect("there should be a response");
+    let payment_settlement = utils::get_success_result(&response)
+        .payment_settlement()
+        .expect("should have payment settlement");
+    assert_eq!(payment_settlement.fee.value(), transaction_fee_1);
+    assert_eq!(
+        payment_settlement.fee.value() + payment_settlement.refund.value(),
+        payment_purse_amount
+    );
+
     assert_eq!(
         initial_balance, tally,
         "no net resources should be gained or lost post-distribution"
@@ -0,0 +1,241 @@
+//! Property tests applying generated `UpgradeConfig`s to the genesis state.
+
+use assert_matches::assert_matches;
+use num_rational::Ratio;
+use proptest::{
+    option,
+    prelude::*,
+    test_runner::{Config, TestRunner},
+};
+
+use casper_engine_test_support::{
+    InMemoryWasmTestBuilder, UpgradeRequestBuilder, DEFAULT_PROTOCOL_VERSION,
+    PRODUCTION_RUN_GENESIS_REQUEST,
+};
+use casper_execution_engine::{
+    core::engine_state::{upgrade::ProtocolUpgradeError, Error},
+    shared::newtypes::CorrelationId,
+};
+use casper_hashing::Digest;
+use casper_types::{
+    bytesrepr::FromBytes,
+    system::{
+        auction::{
+            AUCTION_DELAY_KEY, LOCKED_FUNDS_PERIOD_KEY, UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
+        },
+        mint::ROUND_SEIGNIORAGE_RATE_KEY,
+    },
+    CLTyped, ContractHash, EraId, Key, ProtocolVersion, StoredValue, U512,
+};
+
+const TEST_CASES: u32 = 256;
+
+/// The parameters of a generated upgrade.
+#[derive(Debug, Clone)]
+struct UpgradeParams {
+    unknown_pre_state_hash: Option<Digest>,
+    new_protocol_version: ProtocolVersion,
+    new_validator_slots: Option<u32>,
+    new_auction_delay: Option<u64>,
+    new_locked_funds_period_millis: Option<u64>,
+    new_round_seigniorage_rate: Option<Ratio<u64>>,
+    new_unbonding_delay: Option<u64>,
+}
+
+/// The values stored under the system contracts' named keys which an upgrade can change.
+#[derive(Debug, PartialEq)]
+struct UpgradableValues {
+    validator_slots: u32,
+    auction_delay: u64,
+    locked_funds_period_millis: u64,
+    round_seigniorage_rate: Ratio<U512>,
+    unbonding_delay: u64,
+}
+
+fn protocol_version_arb() -> impl Strategy<Value = ProtocolVersion> {
+    let current = DEFAULT_PROTOCOL_VERSION.value();
+    (
+        current.major..=current.major + 2,
+        0..=current.minor + 2,
+        0..=current.patch + 2,
+    )
+        .prop_map(|(major, minor, patch)| ProtocolVersion::from_parts(major, minor, patch))
+}
+
+fn round_seigniorage_rate_arb() -> impl Strategy<Value = Ratio<u64>> {
+    (any::<u64>(), prop_oneof![Just(0), 1..=u64::MAX])
+        .prop_map(|(numer, denom)| Ratio::new_raw(numer, denom))
+}
+
+fn upgrade_params_arb() -> impl Strategy<Value = UpgradeParams> {
+    (
+        option::weighted(0.1, any::<[u8; Digest::LENGTH]>().prop_map(Digest::from)),
+        protocol_version_arb(),
+        option::of(any::<u32>()),
+        option::of(any::<u64>()),
+        option::of(any::<u64>()),
+        option::of(round_seigniorage_rate_arb()),
+        option::of(any::<u64>()),
+    )
+        .prop_map(
+            |(
+                unknown_pre_state_hash,
+                new_protocol_version,
+                new_validator_slots,
+                new_auction_delay,
+                new_locked_funds_period_millis,
+                new_round_seigniorage_rate,
+                new_unbonding_delay,
+            )| UpgradeParams {
+                unknown_pre_state_hash,
+                new_protocol_version,
+                new_validator_slots,
+                new_auction_delay,
+                new_locked_funds_period_millis,
+                new_round_seigniorage_rate,
+                new_unbonding_delay,
+            },
+        )
+}
+
+fn query_named_key<T: CLTyped + FromBytes>(
+    builder: &InMemoryWasmTestBuilder,
+    state_hash: Digest,
+    contract_hash: ContractHash,
+    name: &str,
+) -> T {
+    match builder
+        .query(
+            Some(state_hash),
+            Key::from(contract_hash),
+            &[name.to_string()],
+        )
+        .expect("should query named key")
+    {
+        StoredValue::CLValue(cl_value) => cl_value.into_t().expect("should convert value"),
+        other => panic!("unexpected stored value {:?} under {}", other, name),
+    }
+}
+
+fn upgradable_values(builder: &InMemoryWasmTestBuilder, state_hash: Digest) -> UpgradableValues {
+    let auction_hash = builder.get_auction_contract_hash();
+    let mint_hash = builder.get_mint_contract_hash();
+    UpgradableValues {
+        validator_slots: query_named_key(builder, state_hash, auction_hash, VALIDATOR_SLOTS_KEY),
+        auction_delay: query_named_key(builder, state_hash, auction_hash, AUCTION_DELAY_KEY),
+        locked_funds_period_millis: query_named_key(
+            builder,
+            state_hash,
+            auction_hash,
+            LOCKED_FUNDS_PERIOD_KEY,
+        ),
+        round_seigniorage_rate: query_named_key(
+            builder,
+            state_hash,
+            mint_hash,
+            ROUND_SEIGNIORAGE_RATE_KEY,
+        ),
+        unbonding_delay: query_named_key(builder, state_hash, auction_hash, UNBONDING_DELAY_KEY),
+    }
+}
+
+#[ignore]
+#[test]
+fn generated_upgrades_should_apply_cleanly_or_fail_with_typed_error() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+    let genesis_hash = builder.get_post_state_hash();
+    let genesis_values = upgradable_values(&builder, genesis_hash);
+
+    let mut runner = TestRunner::new(Config {
+        cases: TEST_CASES,
+        ..Config::default()
+    });
+    runner
+        .run(&upgrade_params_arb(), |params| {
+            let pre_state_hash = params.unknown_pre_state_hash.unwrap_or(genesis_hash);
+            let mut upgrade_request_builder = UpgradeRequestBuilder::new()
+                .with_pre_state_hash(pre_state_hash)
+                .with_current_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+                .with_new_protocol_version(params.new_protocol_version)
+                .with_activation_point(EraId::new(1));
+            if let Some(validator_slots) = params.new_validator_slots {
+                upgrade_request_builder =
+                    upgrade_request_builder.with_new_validator_slots(validator_slots);
+            }
+            if let Some(auction_delay) = params.new_auction_delay {
+                upgrade_request_builder =
+                    upgrade_request_builder.with_new_auction_delay(auction_delay);
+            }
+            if let Some(locked_funds_period) = params.new_locked_funds_period_millis {
+                upgrade_request_builder = upgrade_request_builder
+                    .with_new_locked_funds_period_millis(locked_funds_period);
+            }
+            if let Some(rate) = params.new_round_seigniorage_rate {
+                upgrade_request_builder =
+                    upgrade_request_builder.with_new_round_seigniorage_rate(rate);
+            }
+            if let Some(unbonding_delay) = params.new_unbonding_delay {
+                upgrade_request_builder =
+                    upgrade_request_builder.with_new_unbonding_delay(unbonding_delay);
+            }
+
+            let result = builder
+                .get_engine_state()
+                .commit_upgrade(CorrelationId::new(), upgrade_request_builder.build());
+
+            let version_is_invalid = DEFAULT_PROTOCOL_VERSION
+                .check_next_version(&params.new_protocol_version)
+                .is_invalid();
+            let rate_is_invalid = params
+                .new_round_seigniorage_rate
+                .map_or(false, |rate| *rate.denom() == 0);
+
+            if params.unknown_pre_state_hash.is_some() {
+                assert_matches!(result, Err(Error::RootNotFound(hash)) if hash == pre_state_hash);
+            } else if version_is_invalid {
+                assert_matches!(
+                    result,
+                    Err(Error::InvalidProtocolVersion(version))
+                    if version == params.new_protocol_version
+                );
+            } else if rate_is_invalid {
+                assert_matches!(
+                    result,
+                    Err(Error::ProtocolUpgrade(
+                        ProtocolUpgradeError::InvalidUpgradeConfig
+                    ))
+                );
+            } else {
+                let post_state_hash = result.expect("upgrade should succeed").post_state_hash;
+                let expected_values = UpgradableValues {
+                    validator_slots: params
+                        .new_validator_slots
+                        .unwrap_or(genesis_values.validator_slots),
+                    auction_delay: params
+                        .new_auction_delay
+                        .unwrap_or(genesis_values.auction_delay),
+                    locked_funds_period_millis: params
+                        .new_locked_funds_period_millis
+                        .unwrap_or(genesis_values.locked_funds_period_millis),
+                    round_seigniorage_rate: params
+                        .new_round_seigniorage_rate
+                        .map_or(genesis_values.round_seigniorage_rate.clone(), |rate| {
+                            Ratio::new(U512::from(*rate.numer()), U512::from(*rate.denom()))
+                        }),
+                    unbonding_delay: params
+                        .new_unbonding_delay
+                        .unwrap_or(genesis_values.unbonding_delay),
+                };
+                assert_eq!(
+                    upgradable_values(&builder, post_state_hash),
+                    expected_values
+                );
+            }
+
+            // Whatever the outcome, the pre-state must be left untouched.
+            assert_eq!(upgradable_values(&builder, genesis_hash), genesis_values);
+            Ok(())
+        })
+        .unwrap();
+}
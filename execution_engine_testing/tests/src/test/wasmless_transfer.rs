@@ -9,7 +9,7 @@ use casper_execution_engine::{
     core::{
         engine_state::{
             engine_config::DEFAULT_MAX_ASSOCIATED_KEYS, EngineConfigBuilder, Error as CoreError,
-            WASMLESS_TRANSFER_FIXED_GAS_PRICE,
+            ExecuteRequest, WASMLESS_TRANSFER_FIXED_GAS_PRICE,
         },
         execution::Error as ExecError,
     },
@@ -1054,3 +1054,248 @@ fn transfer_wasmless_should_observe_upgraded_cost() {
         default_account_balance_before - default_account_balance_after - transfer_amount
     );
 }
+
+const MINIMUM_REMAINING_BALANCE: u64 = 2_500_000_000;
+
+fn init_minimum_remaining_balance_builder() -> InMemoryWasmTestBuilder {
+    let engine_config = EngineConfigBuilder::new()
+        .with_minimum_remaining_balance(MINIMUM_REMAINING_BALANCE)
+        .build();
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+    builder
+}
+
+fn wasmless_transfer_request_from_default_account(amount: U512) -> ExecuteRequest {
+    let deploy_item = DeployItemBuilder::new()
+        .with_address(*DEFAULT_ACCOUNT_ADDR)
+        .with_empty_payment_bytes(runtime_args! {})
+        .with_transfer_args(runtime_args! {
+            mint::ARG_TARGET => *ACCOUNT_1_ADDR,
+            mint::ARG_AMOUNT => amount,
+            mint::ARG_ID => <Option<u64>>::None
+        })
+        .with_authorization_keys(&[*DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([42; 32])
+        .build();
+    ExecuteRequestBuilder::from_deploy_item(deploy_item).build()
+}
+
+#[ignore]
+#[test]
+fn transfer_wasmless_should_fail_below_minimum_remaining_balance() {
+    let wasmless_transfer_cost = Motes::from_gas(
+        Gas::from(DEFAULT_WASMLESS_TRANSFER_COST),
+        WASMLESS_TRANSFER_FIXED_GAS_PRICE,
+    )
+    .expect("gas overflow");
+
+    let mut builder = init_minimum_remaining_balance_builder();
+    let default_account_purse = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account")
+        .main_purse();
+    let starting_balance = builder.get_purse_balance(default_account_purse);
+    let transfer_amount =
+        starting_balance - wasmless_transfer_cost.value() - U512::from(MINIMUM_REMAINING_BALANCE)
+            + U512::one();
+
+    builder
+        .exec(wasmless_transfer_request_from_default_account(
+            transfer_amount,
+        ))
+        .commit();
+
+    let exec_result = &builder.get_last_exec_results().unwrap()[0];
+    let error = exec_result.as_error().expect("should have error");
+    assert!(
+        matches!(
+            error,
+            CoreError::Exec(ExecError::Revert(api_error))
+            if *api_error == mint::Error::InsufficientRemainingBalance.into()
+        ),
+        "{:?}",
+        error
+    );
+    assert_eq!(
+        builder.get_purse_balance(default_account_purse),
+        starting_balance - wasmless_transfer_cost.value(),
+        "only the cost of the transfer should be charged"
+    );
+    assert_eq!(builder.get_account(*ACCOUNT_1_ADDR), None);
+}
+
+#[ignore]
+#[test]
+fn transfer_wasmless_should_leave_minimum_remaining_balance() {
+    let wasmless_transfer_cost = Motes::from_gas(
+        Gas::from(DEFAULT_WASMLESS_TRANSFER_COST),
+        WASMLESS_TRANSFER_FIXED_GAS_PRICE,
+    )
+    .expect("gas overflow");
+
+    let mut builder = init_minimum_remaining_balance_builder();
+    let default_account_purse = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account")
+        .main_purse();
+    let starting_balance = builder.get_purse_balance(default_account_purse);
+    let transfer_amount =
+        starting_balance - wasmless_transfer_cost.value() - U512::from(MINIMUM_REMAINING_BALANCE);
+
+    builder
+        .exec(wasmless_transfer_request_from_default_account(
+            transfer_amount,
+        ))
+        .expect_success()
+        .commit();
+
+    assert_eq!(
+        builder.get_purse_balance(default_account_purse),
+        U512::from(MINIMUM_REMAINING_BALANCE)
+    );
+}
+
+#[ignore]
+#[test]
+fn transfer_from_session_should_fail_below_minimum_remaining_balance() {
+    let mut builder = init_minimum_remaining_balance_builder();
+    let default_account_purse = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account")
+        .main_purse();
+    let transfer_amount = builder.get_purse_balance(default_account_purse)
+        - *DEFAULT_PAYMENT
+        - U512::from(MINIMUM_REMAINING_BALANCE)
+        + U512::one();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+        runtime_args! {
+            mint::ARG_TARGET => *ACCOUNT_1_ADDR,
+            mint::ARG_AMOUNT => transfer_amount,
+        },
+    )
+    .build();
+    builder.exec(exec_request).commit();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(
+            error,
+            CoreError::Exec(ExecError::Revert(api_error))
+            if api_error == mint::Error::InsufficientRemainingBalance.into()
+        ),
+        "{:?}",
+        error
+    );
+}
+
+fn multi_transfer_request_from_default_account(transfers: Vec<RuntimeArgs>) -> ExecuteRequest {
+    let deploy_item = DeployItemBuilder::new()
+        .with_address(*DEFAULT_ACCOUNT_ADDR)
+        .with_empty_payment_bytes(runtime_args! {})
+        .with_multi_transfer_args(transfers)
+        .with_authorization_keys(&[*DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([42; 32])
+        .build();
+    ExecuteRequestBuilder::from_deploy_item(deploy_item).build()
+}
+
+#[ignore]
+#[test]
+fn multi_transfer_should_make_every_transfer_for_a_fee_each() {
+    let wasmless_transfer_cost = Motes::from_gas(
+        Gas::from(DEFAULT_WASMLESS_TRANSFER_COST),
+        WASMLESS_TRANSFER_FIXED_GAS_PRICE,
+    )
+    .expect("gas overflow");
+    let amount_1 = U512::from(1_000_000_000u64);
+    let amount_2 = U512::from(2_000_000_000u64);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+    let default_account_purse = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account")
+        .main_purse();
+    let starting_balance = builder.get_purse_balance(default_account_purse);
+
+    builder
+        .exec(multi_transfer_request_from_default_account(vec![
+            runtime_args! {
+                mint::ARG_TARGET => *ACCOUNT_1_ADDR,
+                mint::ARG_AMOUNT => amount_1,
+                mint::ARG_ID => Some(1u64)
+            },
+            runtime_args! {
+                mint::ARG_TARGET => *ACCOUNT_2_ADDR,
+                mint::ARG_AMOUNT => amount_2,
+                mint::ARG_ID => Some(2u64)
+            },
+        ]))
+        .expect_success()
+        .commit();
+
+    let exec_result = &builder.get_last_exec_results().unwrap()[0];
+    assert_eq!(exec_result.transfers().len(), 2);
+
+    let account_1_purse = builder
+        .get_account(*ACCOUNT_1_ADDR)
+        .expect("should create account 1")
+        .main_purse();
+    let account_2_purse = builder
+        .get_account(*ACCOUNT_2_ADDR)
+        .expect("should create account 2")
+        .main_purse();
+    assert_ne!(account_1_purse, account_2_purse);
+    assert_eq!(builder.get_purse_balance(account_1_purse), amount_1);
+    assert_eq!(builder.get_purse_balance(account_2_purse), amount_2);
+    assert_eq!(
+        builder.get_purse_balance(default_account_purse),
+        starting_balance - amount_1 - amount_2 - wasmless_transfer_cost.value() * 2
+    );
+}
+
+#[ignore]
+#[test]
+fn multi_transfer_should_make_no_transfer_if_one_fails() {
+    let wasmless_transfer_cost = Motes::from_gas(
+        Gas::from(DEFAULT_WASMLESS_TRANSFER_COST),
+        WASMLESS_TRANSFER_FIXED_GAS_PRICE,
+    )
+    .expect("gas overflow");
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+    let default_account_purse = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account")
+        .main_purse();
+    let starting_balance = builder.get_purse_balance(default_account_purse);
+
+    builder
+        .exec(multi_transfer_request_from_default_account(vec![
+            runtime_args! {
+                mint::ARG_TARGET => *ACCOUNT_1_ADDR,
+                mint::ARG_AMOUNT => U512::from(1_000_000_000u64),
+                mint::ARG_ID => <Option<u64>>::None
+            },
+            runtime_args! {
+                mint::ARG_TARGET => *DEFAULT_ACCOUNT_ADDR,
+                mint::ARG_AMOUNT => U512::from(1_000_000_000u64),
+                mint::ARG_ID => <Option<u64>>::None
+            },
+        ]))
+        .commit();
+
+    let exec_result = &builder.get_last_exec_results().unwrap()[0];
+    assert!(exec_result.as_error().is_some());
+    assert_eq!(builder.get_account(*ACCOUNT_1_ADDR), None);
+    assert_eq!(
+        builder.get_purse_balance(default_account_purse),
+        starting_balance - wasmless_transfer_cost.value() * 2,
+        "only the cost of the transfers should be charged"
+    );
+}
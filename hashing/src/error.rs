@@ -13,6 +13,12 @@ pub enum Error {
     /// There was a decoding error.
     #[error("Base16 decode error {0}.")]
     Base16DecodeError(base16::DecodeError),
+    /// The mixed-case hex input did not match its checksum.
+    #[error("Invalid checksum at character {index}.")]
+    InvalidChecksum {
+        /// The index of the first character whose case does not match the checksum.
+        index: usize,
+    },
 }
 
 /// Error validating a Merkle proof of a chunk.
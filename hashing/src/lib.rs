@@ -211,9 +211,18 @@ impl Digest {
             .rfold(proof, |prev, next| Digest::hash_pair(next, prev))
     }
 
-    /// Returns a `Digest` parsed from a hex-encoded `Digest`.
+    /// Returns the `Digest` encoded as mixed-case checksummed hex.
+    pub fn to_checksummed_hex(&self) -> String {
+        checksummed_hex::encode(&self.0)
+    }
+
+    /// Returns a `Digest` parsed from a hex-encoded `Digest`, which may be all lowercase, all
+    /// uppercase or mixed-case checksummed hex.
     pub fn from_hex<T: AsRef<[u8]>>(hex_input: T) -> Result<Self, Error> {
-        let bytes = checksummed_hex::decode(&hex_input).map_err(Error::Base16DecodeError)?;
+        let bytes = checksummed_hex::decode_checked(&hex_input).map_err(|error| match error {
+            checksummed_hex::Error::InvalidChecksum { index } => Error::InvalidChecksum { index },
+            checksummed_hex::Error::InvalidHex(error) => Error::Base16DecodeError(error),
+        })?;
         let slice: [u8; Self::LENGTH] = bytes
             .try_into()
             .map_err(|_| Error::IncorrectDigestLength(hex_input.as_ref().len()))?;
@@ -374,7 +383,7 @@ mod tests {
 
     use casper_types::bytesrepr::{self, ToBytes};
 
-    use crate::{ChunkWithProof, Digest};
+    use crate::{ChunkWithProof, Digest, Error};
 
     #[proptest]
     fn bytesrepr_roundtrip(data: [u8; Digest::LENGTH]) {
@@ -460,6 +469,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn checksummed_hex_roundtrip() {
+        let digest = Digest::hash(b"abc");
+        let checksummed = digest.to_checksummed_hex();
+        assert_eq!(Digest::from_hex(&checksummed).unwrap(), digest);
+
+        // Swap the case of the first alphabetic character to break the checksum.
+        let index = checksummed.find(|c: char| c.is_ascii_alphabetic()).unwrap();
+        let mut mistyped = checksummed.into_bytes();
+        mistyped[index] ^= 0x20;
+        assert!(matches!(
+            Digest::from_hex(&mistyped),
+            Err(Error::InvalidChecksum { index: error_index }) if error_index == index
+        ));
+    }
+
     #[test]
     fn should_display_digest_in_hex() {
         let hash = Digest([0u8; 32]);
@@ -104,6 +104,12 @@ pub struct Error {
     /// Additional information about the error.
     #[serde(skip_serializing_if = "Option::is_none")]
     data: Option<Value>,
+    /// Structured, machine-readable information about the error, e.g. a stable error code.
+    ///
+    /// This is an extension to the JSON-RPC error object, allowing "data" to remain a
+    /// human-readable description.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    details: Option<Value>,
 }
 
 impl Error {
@@ -135,6 +141,7 @@ impl Error {
                     "attempted to return reserved error code {}",
                     code
                 ))),
+                details: None,
             };
         }
 
@@ -151,6 +158,7 @@ impl Error {
                         "failed to json-encode additional info in json-rpc error: {}",
                         error
                     ))),
+                    details: None,
                 };
             }
         };
@@ -159,7 +167,22 @@ impl Error {
             code,
             message: Cow::Borrowed(message),
             data,
+            details: None,
+        }
+    }
+
+    /// Returns `self` with `details` JSON-encoded as the "details" field.
+    ///
+    /// If JSON-encoding `details` fails, the failure is logged and `self` is returned unchanged.
+    pub fn with_details<T: Serialize>(mut self, details: T) -> Self {
+        match serde_json::to_value(details) {
+            Ok(Value::Null) => self.details = None,
+            Ok(value) => self.details = Some(value),
+            Err(error) => {
+                error!(%error, "failed to json-encode details in json-rpc error");
+            }
         }
+        self
     }
 }
 
@@ -274,4 +297,27 @@ mod tests {
         let encoded = serde_json::to_string(&error_with_bad_data).unwrap();
         assert_eq!(encoded, EXPECTED);
     }
+
+    #[test]
+    fn should_construct_error_with_details() {
+        const EXPECTED_WITH_DETAILS: &str = r#"{"code":-123,"message":"Valid test error","data":"description","details":{"id":1314,"context":"TEST"}}"#;
+        const EXPECTED_WITH_BAD_DETAILS: &str =
+            r#"{"code":-123,"message":"Valid test error","data":"description"}"#;
+
+        let good_error_code = TestErrorCode {
+            in_reserved_range: false,
+        };
+
+        let error_with_details =
+            Error::new(good_error_code, "description").with_details(AdditionalInfo::default());
+        let encoded = serde_json::to_string(&error_with_details).unwrap();
+        assert_eq!(encoded, EXPECTED_WITH_DETAILS);
+        let decoded: Error = serde_json::from_str(&encoded).unwrap();
+        assert_eq!(decoded, error_with_details);
+
+        let error_with_bad_details =
+            Error::new(good_error_code, "description").with_details(FailToEncode);
+        let encoded = serde_json::to_string(&error_with_bad_details).unwrap();
+        assert_eq!(encoded, EXPECTED_WITH_BAD_DETAILS);
+    }
 }
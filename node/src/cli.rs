@@ -19,13 +19,19 @@ use stats_alloc::{StatsAlloc, INSTRUMENTED_SYSTEM};
 use structopt::StructOpt;
 use toml::{value::Table, Value};
 use tracing::info;
+use tracing_subscriber::EnvFilter;
+
+use casper_hashing::Digest;
 
 use crate::{
     components::network::Identity as NetworkIdentity,
+    deploy_replay::DeployFixture,
     logging,
     reactor::{main_reactor, Runner},
     setup_signal_hooks,
-    types::{Chainspec, ChainspecRawBytes, ExitCode},
+    types::{
+        chainspec::ValidationError, BlockHash, Chainspec, ChainspecRawBytes, DeployHash, ExitCode,
+    },
     utils::{Loadable, WithDir},
 };
 
@@ -78,6 +84,96 @@ pub enum Cli {
         #[structopt(long)]
         new_config: PathBuf,
     },
+    /// Replay execution of a range of stored blocks and verify the outcome matches what was
+    /// stored.
+    ///
+    /// Each block is executed on top of its parent's state root, and the resulting state root
+    /// hash, execution results and block hash are compared with the stored ones.  The first
+    /// divergence found is reported and causes a non-zero exit code.
+    AuditChain {
+        /// Path to configuration file.
+        config: PathBuf,
+        /// Height of the first block to replay.
+        #[structopt(long)]
+        from_height: u64,
+        /// Height of the last block to replay.
+        #[structopt(long)]
+        to_height: u64,
+    },
+    /// Execute a single stored block for debugging, printing the result of each of its deploys.
+    ///
+    /// The block is executed on top of its parent's state root, without starting the node, and with
+    /// the execution traced to the log.  A JSON report of the replayed block and of every deploy's
+    /// result is written to the output file, including the transforms by which each deploy's
+    /// effect differs from the stored one.  A replay differing from the stored block causes a
+    /// non-zero exit code.
+    DebugExecuteBlock {
+        /// Path to configuration file.
+        config: PathBuf,
+        /// Hex-encoded hash of the block to execute.
+        #[structopt(long)]
+        hash: String,
+        /// Path of the report file to write.
+        #[structopt(long)]
+        output: PathBuf,
+        /// Log filter applied while executing the block, in the same format as `RUST_LOG`.
+        #[structopt(
+            long,
+            default_value = "casper_node=trace,casper_execution_engine=trace,info"
+        )]
+        log_filter: String,
+    },
+    /// Export the stored execution effects of the deploys of a block to a JSON file.
+    ///
+    /// Each effect is written in the versioned JSON format of execution effects, which is
+    /// documented by a JSON schema and kept stable across node releases.  Nothing is executed.
+    ExportExecutionEffects {
+        /// Path to configuration file.
+        config: PathBuf,
+        /// Hex-encoded hash of the block whose effects to export.
+        #[structopt(long)]
+        hash: String,
+        /// Path of the export file to write.
+        #[structopt(long)]
+        output: PathBuf,
+    },
+    /// Validate a chainspec, printing each problem found along with how to fix it.
+    ///
+    /// Intended to be run by operators before staging an upgrade; the node performs the same
+    /// checks at startup.  A problem preventing the node from using the chainspec causes a
+    /// non-zero exit code.
+    ValidateChainspec {
+        /// Path to the chainspec file, or to the directory containing it.
+        chainspec: PathBuf,
+    },
+    /// Record the global state read while executing a stored deploy into a fixture file.
+    ///
+    /// The deploy is executed on top of the given state root hash, with the timestamp and proposer
+    /// of the block in which it was executed.  The resulting fixture holds everything needed to
+    /// re-execute the deploy via `replay-deploy` without access to the node's databases.
+    RecordDeploy {
+        /// Path to configuration file.
+        config: PathBuf,
+        /// Hex-encoded state root hash to execute the deploy on top of.
+        #[structopt(long)]
+        state_root_hash: String,
+        /// Hex-encoded hash of the deploy to execute.
+        #[structopt(long)]
+        deploy_hash: String,
+        /// Path of the fixture file to write.
+        #[structopt(long)]
+        output: PathBuf,
+    },
+    /// Re-execute a deploy from a fixture file written by `record-deploy`.
+    ///
+    /// A result differing from the recorded one is reported and causes a non-zero exit code.
+    ReplayDeploy {
+        /// Path to configuration file.
+        config: PathBuf,
+        /// Path of the fixture file to read.
+        #[structopt(long)]
+        fixture: PathBuf,
+    },
 }
 
 #[derive(Debug)]
@@ -237,6 +333,156 @@ impl Cli {
                 )?;
                 Ok(ExitCode::Success as i32)
             }
+            Cli::AuditChain {
+                config,
+                from_height,
+                to_height,
+            } => {
+                let config = Self::init(&config, vec![])?;
+                let (chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_path(config.dir())?;
+
+                info!(
+                    build_version = %crate::VERSION_STRING.as_str(),
+                    from_height,
+                    to_height,
+                    "auditing chain"
+                );
+                match crate::chain_audit::audit_chain(config, &chainspec, from_height, to_height)? {
+                    None => {
+                        info!(from_height, to_height, "chain audit: no divergence found");
+                        Ok(ExitCode::Success as i32)
+                    }
+                    Some(divergence) => bail!("chain audit: {}", divergence),
+                }
+            }
+            Cli::DebugExecuteBlock {
+                config,
+                hash,
+                output,
+                log_filter,
+            } => {
+                let config = Self::init(&config, vec![])?;
+                logging::reload_global_env_filter(
+                    EnvFilter::try_new(&log_filter).context("invalid log filter")?,
+                )?;
+                let (chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_path(config.dir())?;
+                let block_hash =
+                    BlockHash::new(Digest::from_hex(hash).context("invalid block hash")?);
+
+                info!(
+                    build_version = %crate::VERSION_STRING.as_str(),
+                    %block_hash,
+                    "executing stored block"
+                );
+                let report =
+                    crate::chain_audit::debug_execute_block(config, &chainspec, block_hash)?;
+                fs::write(&output, serde_json::to_vec_pretty(&report)?)
+                    .context("could not write report file")
+                    .with_context(|| output.display().to_string())?;
+                info!(output = %output.display(), "block execution report written");
+                if !report.is_identical() {
+                    bail!("block execution: replay differs from the stored block");
+                }
+                Ok(ExitCode::Success as i32)
+            }
+            Cli::ExportExecutionEffects {
+                config,
+                hash,
+                output,
+            } => {
+                let config = Self::init(&config, vec![])?;
+                let (chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_path(config.dir())?;
+                let block_hash =
+                    BlockHash::new(Digest::from_hex(hash).context("invalid block hash")?);
+
+                info!(
+                    build_version = %crate::VERSION_STRING.as_str(),
+                    %block_hash,
+                    "exporting execution effects"
+                );
+                let export =
+                    crate::chain_audit::export_execution_effects(config, &chainspec, block_hash)?;
+                fs::write(&output, serde_json::to_vec_pretty(&export)?)
+                    .context("could not write export file")
+                    .with_context(|| output.display().to_string())?;
+                info!(output = %output.display(), "execution effects exported");
+                Ok(ExitCode::Success as i32)
+            }
+            Cli::ValidateChainspec { chainspec } => {
+                let chainspec_dir = if chainspec.is_dir() {
+                    chainspec.as_path()
+                } else {
+                    chainspec.parent().unwrap_or_else(|| Path::new("."))
+                };
+                let (chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_path(chainspec_dir)
+                    .with_context(|| chainspec_dir.display().to_string())?;
+
+                let problems = chainspec.validate();
+                for problem in &problems {
+                    let severity = if problem.is_fatal() {
+                        "error"
+                    } else {
+                        "warning"
+                    };
+                    println!("{}: {}", severity, problem);
+                }
+                if problems.iter().any(ValidationError::is_fatal) {
+                    bail!("invalid chainspec");
+                }
+                println!("chainspec is valid");
+                Ok(ExitCode::Success as i32)
+            }
+            Cli::RecordDeploy {
+                config,
+                state_root_hash,
+                deploy_hash,
+                output,
+            } => {
+                let config = Self::init(&config, vec![])?;
+                let (chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_path(config.dir())?;
+                let state_root_hash =
+                    Digest::from_hex(state_root_hash).context("invalid state root hash")?;
+                let deploy_hash =
+                    DeployHash::new(Digest::from_hex(deploy_hash).context("invalid deploy hash")?);
+
+                info!(
+                    build_version = %crate::VERSION_STRING.as_str(),
+                    %state_root_hash,
+                    %deploy_hash,
+                    "recording deploy"
+                );
+                let fixture = crate::deploy_replay::record_deploy(
+                    config,
+                    &chainspec,
+                    state_root_hash,
+                    deploy_hash,
+                )?;
+                fs::write(&output, serde_json::to_vec_pretty(&fixture)?)
+                    .context("could not write fixture file")
+                    .with_context(|| output.display().to_string())?;
+                info!(output = %output.display(), "deploy recorded");
+                Ok(ExitCode::Success as i32)
+            }
+            Cli::ReplayDeploy { config, fixture } => {
+                let config = Self::init(&config, vec![])?;
+                let (chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_path(config.dir())?;
+                let encoded_fixture = fs::read(&fixture)
+                    .context("could not read fixture file")
+                    .with_context(|| fixture.display().to_string())?;
+                let fixture: DeployFixture = serde_json::from_slice(&encoded_fixture)?;
+
+                info!(build_version = %crate::VERSION_STRING.as_str(), "replaying deploy");
+                let execution_result =
+                    crate::deploy_replay::replay_deploy(config.value(), &chainspec, &fixture)?;
+                if &execution_result != fixture.execution_result() {
+                    bail!(
+                        "deploy replay: execution result differs from the recorded one: {}",
+                        serde_json::to_string(&execution_result)?
+                    );
+                }
+                info!("deploy replay: execution result matches the recorded one");
+                Ok(ExitCode::Success as i32)
+            }
         }
     }
 
@@ -2,15 +2,17 @@
 
 mod config;
 mod error;
+mod execution_cache;
 mod metrics;
 mod operations;
+mod query_pool;
 #[cfg(test)]
 mod tests;
 mod types;
 
 use std::{
     cmp::Ordering,
-    collections::{BTreeMap, BTreeSet, HashMap},
+    collections::{BTreeMap, HashMap},
     convert::TryInto,
     fmt::{self, Debug, Display, Formatter},
     path::Path,
@@ -25,27 +27,32 @@ use once_cell::sync::Lazy;
 use prometheus::Registry;
 use serde::Serialize;
 use thiserror::Error;
-use tracing::{debug, error, info, trace};
+use tracing::{debug, error, info, trace, warn};
 
 use casper_execution_engine::{
     core::engine_state::{
-        self,
-        engine_config::{FeeHandling, RefundHandling},
-        genesis::GenesisError,
-        ChainspecRegistry, DeployItem, EngineConfigBuilder, EngineState, GenesisSuccess,
-        SystemContractRegistry, UpgradeConfig, UpgradeSuccess,
+        self, genesis::GenesisError, ChainspecRegistry, DeployItem, EngineConfig,
+        EngineConfigBuilder, EngineState, GenesisSuccess, SystemContractRegistry, UpgradeConfig,
+        UpgradeSuccess,
     },
-    shared::{newtypes::CorrelationId, system_config::SystemConfig, wasm_config::WasmConfig},
+    shared::newtypes::CorrelationId,
     storage::{
-        global_state::lmdb::LmdbGlobalState, transaction_source::lmdb::LmdbEnvironment,
-        trie_store::lmdb::LmdbTrieStore,
+        global_state::{
+            change_feed::{
+                TrieChangeSink, TrieChangeSinkError, UnixSocketSink,
+                DEFAULT_TRIE_CHANGE_SINK_RECONNECT_INTERVAL,
+            },
+            lmdb::LmdbGlobalState,
+        },
+        transaction_source::lmdb::LmdbEnvironment,
+        trie_store::{lmdb::LmdbTrieStore, node_cache::TrieNodeCache},
     },
 };
 use casper_hashing::Digest;
-use casper_types::{bytesrepr::Bytes, EraId, ProtocolVersion, PublicKey, Timestamp};
+use casper_types::{bytesrepr::Bytes, EraId, ProtocolVersion, Timestamp};
 
 use crate::{
-    components::{fetcher::FetchResponse, Component, ComponentState},
+    components::{fetcher::FetchResponse, storage::Storage, Component, ComponentState},
     effect::{
         announcements::{
             ContractRuntimeAnnouncement, FatalAnnouncement, MetaBlockAnnouncement,
@@ -59,17 +66,22 @@ use crate::{
     protocol::Message,
     types::{
         ActivationPoint, BlockHash, BlockHeader, Chainspec, ChainspecRawBytes, ChunkingError,
-        Deploy, FinalizedBlock, MetaBlock, MetaBlockState, TrieOrChunk, TrieOrChunkId,
+        Deploy, EraExecutionStats, FinalizedBlock, MetaBlock, MetaBlockState, TrieOrChunk,
+        TrieOrChunkId,
     },
     NodeRng,
 };
 pub(crate) use config::Config;
-pub(crate) use error::{BlockExecutionError, ConfigError};
+pub(crate) use error::{BlockExecutionError, ConfigError, StateQueryError};
+use execution_cache::ExecutionCache;
 use metrics::Metrics;
+pub(crate) use operations::execute_deploy;
 pub use operations::execute_finalized_block;
-use operations::execute_only;
+use operations::{execute_bundle_only, execute_only, replay_finalized_block};
+use query_pool::QueryPool;
 pub(crate) use types::{
-    BlockAndExecutionResults, EraValidatorsRequest, StepEffectAndUpcomingEraValidators,
+    BlockAndExecutionResults, EraValidatorsRequest, ExecutionArtifacts, ReplayedBlock,
+    StepEffectAndUpcomingEraValidators,
 };
 
 const COMPONENT_NAME: &str = "contract_runtime";
@@ -122,12 +134,47 @@ where
 pub struct SpeculativeExecutionState {
     /// State root on top of which to execute deploy.
     pub state_root_hash: Digest,
+    /// Height of the block whose state root is used.
+    pub block_height: u64,
     /// Block time.
     pub block_time: Timestamp,
     /// Protocol version used when creating the original block.
     pub protocol_version: ProtocolVersion,
 }
 
+/// How the age of the state root a query runs against is checked against the configured limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub(crate) enum StateRootAgeCheck {
+    /// The age isn't checked, as the query is made by the node itself.
+    Unchecked,
+    /// The state root is that of the block at the given height.
+    AtHeight(u64),
+    /// The state root isn't known to be that of any stored block.
+    UnknownHeight,
+}
+
+impl StateRootAgeCheck {
+    /// Returns who a query with this check is made for.
+    fn origin(self) -> QueryOrigin {
+        match self {
+            StateRootAgeCheck::Unchecked => QueryOrigin::Node,
+            StateRootAgeCheck::AtHeight(_) | StateRootAgeCheck::UnknownHeight => {
+                QueryOrigin::Client
+            }
+        }
+    }
+}
+
+/// Who a read-only query of global state is made for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub(crate) enum QueryOrigin {
+    /// The node itself, e.g. to validate deploys or look up validators.  Such queries are never
+    /// rejected for want of a free slot in the query pool.
+    Node,
+    /// A client, e.g. via the JSON-RPC server.
+    Client,
+}
+
 /// State to use to construct the next block in the blockchain. Includes the state root hash for the
 /// execution engine as well as certain values the next header will be based on.
 #[derive(DataSize, Debug, Clone, Serialize)]
@@ -208,10 +255,17 @@ pub(crate) struct ContractRuntime {
 
     /// Finalized blocks waiting for their pre-state hash to start executing.
     exec_queue: ExecQueue,
+    /// Results of recently executed blocks, reused if the same block is executed again.
+    execution_cache: Arc<Mutex<ExecutionCache>>,
     /// Cached instance of a [`SystemContractRegistry`].
     system_contract_registry: Option<SystemContractRegistry>,
     activation_point: ActivationPoint,
     prune_batch_size: u64,
+    /// The maximum age in blocks of a state root against which RPC queries and speculative
+    /// execution may run, if limited.
+    max_state_root_age: Option<u64>,
+    /// Pool running read-only queries of global state separately from block execution.
+    query_pool: Arc<QueryPool>,
 }
 
 impl Debug for ContractRuntime {
@@ -341,16 +395,30 @@ impl ContractRuntime {
         match request {
             ContractRuntimeRequest::Query {
                 query_request,
+                state_root_age_check,
                 responder,
             } => {
                 trace!(?query_request, "query");
+                if let Err(error) =
+                    self.check_state_root_age(query_request.state_hash(), state_root_age_check)
+                {
+                    return responder.respond(Err(error)).ignore();
+                }
                 let engine_state = Arc::clone(&self.engine_state);
                 let metrics = Arc::clone(&self.metrics);
+                let query_pool = Arc::clone(&self.query_pool);
                 async move {
-                    let correlation_id = CorrelationId::new();
-                    let start = Instant::now();
-                    let result = engine_state.run_query(correlation_id, query_request);
-                    metrics.run_query.observe(start.elapsed().as_secs_f64());
+                    let result = query_pool
+                        .run(state_root_age_check.origin(), move || {
+                            let correlation_id = CorrelationId::new();
+                            let start = Instant::now();
+                            let result = engine_state.run_query(correlation_id, query_request);
+                            metrics.run_query.observe(start.elapsed().as_secs_f64());
+                            result
+                        })
+                        .await
+                        .unwrap_or_else(Err)
+                        .map_err(StateQueryError::from);
                     trace!(?result, "query result");
                     responder.respond(result).await
                 }
@@ -358,26 +426,44 @@ impl ContractRuntime {
             }
             ContractRuntimeRequest::GetBalance {
                 balance_request,
+                state_root_age_check,
                 responder,
             } => {
                 trace!(?balance_request, "balance");
+                if let Err(error) =
+                    self.check_state_root_age(balance_request.state_hash(), state_root_age_check)
+                {
+                    return responder.respond(Err(error)).ignore();
+                }
                 let engine_state = Arc::clone(&self.engine_state);
                 let metrics = Arc::clone(&self.metrics);
+                let query_pool = Arc::clone(&self.query_pool);
                 async move {
-                    let correlation_id = CorrelationId::new();
-                    let start = Instant::now();
-                    let result = engine_state.get_purse_balance(
-                        correlation_id,
-                        balance_request.state_hash(),
-                        balance_request.purse_uref(),
-                    );
-                    metrics.get_balance.observe(start.elapsed().as_secs_f64());
+                    let result = query_pool
+                        .run(state_root_age_check.origin(), move || {
+                            let correlation_id = CorrelationId::new();
+                            let start = Instant::now();
+                            let result = engine_state.get_purse_balance(
+                                correlation_id,
+                                balance_request.state_hash(),
+                                balance_request.purse_uref(),
+                            );
+                            metrics.get_balance.observe(start.elapsed().as_secs_f64());
+                            result
+                        })
+                        .await
+                        .unwrap_or_else(Err)
+                        .map_err(StateQueryError::from);
                     trace!(?result, "balance result");
                     responder.respond(result).await
                 }
                 .ignore()
             }
-            ContractRuntimeRequest::GetEraValidators { request, responder } => {
+            ContractRuntimeRequest::GetEraValidators {
+                request,
+                origin,
+                responder,
+            } => {
                 trace!(?request, "get era validators request");
                 let engine_state = Arc::clone(&self.engine_state);
                 let metrics = Arc::clone(&self.metrics);
@@ -385,19 +471,26 @@ impl ContractRuntime {
                 self.try_init_system_contract_registry_cache();
 
                 let system_contract_registry = self.system_contract_registry.clone();
+                let query_pool = Arc::clone(&self.query_pool);
                 // Increment the counter to track the amount of times GetEraValidators was
                 // requested.
                 async move {
-                    let correlation_id = CorrelationId::new();
-                    let start = Instant::now();
-                    let era_validators = engine_state.get_era_validators(
-                        correlation_id,
-                        system_contract_registry,
-                        request.into(),
-                    );
-                    metrics
-                        .get_era_validators
-                        .observe(start.elapsed().as_secs_f64());
+                    let era_validators = query_pool
+                        .run(origin, move || {
+                            let correlation_id = CorrelationId::new();
+                            let start = Instant::now();
+                            let era_validators = engine_state.get_era_validators(
+                                correlation_id,
+                                system_contract_registry,
+                                request.into(),
+                            );
+                            metrics
+                                .get_era_validators
+                                .observe(start.elapsed().as_secs_f64());
+                            era_validators
+                        })
+                        .await
+                        .unwrap_or_else(|error| Err(error.into()));
                     trace!(?era_validators, "get era validators response");
                     responder.respond(era_validators).await
                 }
@@ -492,6 +585,7 @@ impl ContractRuntime {
                         let engine_state = Arc::clone(&self.engine_state);
                         let metrics = Arc::clone(&self.metrics);
                         let shared_pre_state = Arc::clone(&self.execution_pre_state);
+                        let execution_cache = Arc::clone(&self.execution_cache);
                         let activation_point = self.activation_point;
                         let prune_batch_size = self.prune_batch_size;
                         effects.extend(
@@ -499,6 +593,7 @@ impl ContractRuntime {
                                 engine_state,
                                 metrics,
                                 exec_queue,
+                                execution_cache,
                                 shared_pre_state,
                                 current_pre_state.clone(),
                                 effect_builder,
@@ -541,16 +636,48 @@ impl ContractRuntime {
                 trace!(?get_bids_request, "get bids request");
                 let engine_state = Arc::clone(&self.engine_state);
                 let metrics = Arc::clone(&self.metrics);
+                let query_pool = Arc::clone(&self.query_pool);
                 async move {
-                    let correlation_id = CorrelationId::new();
-                    let start = Instant::now();
-                    let result = engine_state.get_bids(correlation_id, get_bids_request);
-                    metrics.get_bids.observe(start.elapsed().as_secs_f64());
+                    let result = query_pool
+                        .run(QueryOrigin::Client, move || {
+                            let correlation_id = CorrelationId::new();
+                            let start = Instant::now();
+                            let result = engine_state.get_bids(correlation_id, get_bids_request);
+                            metrics.get_bids.observe(start.elapsed().as_secs_f64());
+                            result
+                        })
+                        .await
+                        .unwrap_or_else(Err);
                     trace!(?result, "get bids result");
                     responder.respond(result).await
                 }
                 .ignore()
             }
+            ContractRuntimeRequest::GetUnbonds {
+                get_unbonds_request,
+                responder,
+            } => {
+                trace!(?get_unbonds_request, "get unbonds request");
+                let engine_state = Arc::clone(&self.engine_state);
+                let metrics = Arc::clone(&self.metrics);
+                let query_pool = Arc::clone(&self.query_pool);
+                async move {
+                    let result = query_pool
+                        .run(QueryOrigin::Client, move || {
+                            let correlation_id = CorrelationId::new();
+                            let start = Instant::now();
+                            let result =
+                                engine_state.get_unbonds(correlation_id, get_unbonds_request);
+                            metrics.get_unbonds.observe(start.elapsed().as_secs_f64());
+                            result
+                        })
+                        .await
+                        .unwrap_or_else(Err);
+                    trace!(?result, "get unbonds result");
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
             ContractRuntimeRequest::GetExecutionResultsChecksum {
                 state_root_hash,
                 responder,
@@ -566,11 +693,20 @@ impl ContractRuntime {
                     });
                 responder.respond(result).ignore()
             }
+            ContractRuntimeRequest::GetEngineConfig { responder } => responder
+                .respond(self.engine_state.config().clone())
+                .ignore(),
             ContractRuntimeRequest::SpeculativeDeployExecution {
                 execution_prestate,
                 deploy,
                 responder,
             } => {
+                if let Err(error) = self.check_state_root_age(
+                    execution_prestate.state_root_hash,
+                    StateRootAgeCheck::AtHeight(execution_prestate.block_height),
+                ) {
+                    return responder.respond(Err(error)).ignore();
+                }
                 let engine_state = Arc::clone(&self.engine_state);
                 async move {
                     let result = run_intensive_task(move || {
@@ -580,7 +716,37 @@ impl ContractRuntime {
                             DeployItem::from((*deploy).clone()),
                         )
                     })
-                    .await;
+                    .await
+                    .map_err(StateQueryError::from);
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
+            ContractRuntimeRequest::SpeculativeDeployBundleExecution {
+                execution_prestate,
+                deploys,
+                responder,
+            } => {
+                if let Err(error) = self.check_state_root_age(
+                    execution_prestate.state_root_hash,
+                    StateRootAgeCheck::AtHeight(execution_prestate.block_height),
+                ) {
+                    return responder.respond(Err(error)).ignore();
+                }
+                let engine_state = Arc::clone(&self.engine_state);
+                async move {
+                    let result = run_intensive_task(move || {
+                        execute_bundle_only(
+                            engine_state.as_ref(),
+                            execution_prestate,
+                            deploys
+                                .iter()
+                                .map(|deploy| DeployItem::from((**deploy).clone()))
+                                .collect(),
+                        )
+                    })
+                    .await
+                    .map_err(StateQueryError::from);
                     responder.respond(result).await
                 }
                 .ignore()
@@ -590,27 +756,14 @@ impl ContractRuntime {
 }
 
 impl ContractRuntime {
-    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         protocol_version: ProtocolVersion,
         storage_dir: &Path,
         contract_runtime_config: &Config,
-        wasm_config: WasmConfig,
-        system_config: SystemConfig,
-        max_associated_keys: u32,
-        max_runtime_call_stack_height: u32,
-        minimum_delegation_amount: u64,
+        engine_config: EngineConfig,
         activation_point: ActivationPoint,
         prune_batch_size: u64,
-        strict_argument_checking: bool,
-        vesting_schedule_period_millis: u64,
-        max_delegators_per_validator: Option<u32>,
         registry: &Registry,
-        administrative_accounts: BTreeSet<PublicKey>,
-        allow_auction_bids: bool,
-        allow_unrestricted_transfers: bool,
-        refund_handling: RefundHandling,
-        fee_handling: FeeHandling,
     ) -> Result<Self, ConfigError> {
         // TODO: This is bogus, get rid of this
         let execution_pre_state = Arc::new(Mutex::new(ExecutionPreState {
@@ -627,34 +780,44 @@ impl ContractRuntime {
             contract_runtime_config.manual_sync_enabled_or_default(),
         )?);
 
-        let trie_store = Arc::new(LmdbTrieStore::new(
-            &environment,
-            None,
-            DatabaseFlags::empty(),
-        )?);
-
-        let global_state = LmdbGlobalState::empty(environment, trie_store)?;
-        let engine_config = EngineConfigBuilder::new()
-            .with_max_query_depth(contract_runtime_config.max_query_depth_or_default())
-            .with_max_associated_keys(max_associated_keys)
-            .with_max_runtime_call_stack_height(max_runtime_call_stack_height)
-            .with_minimum_delegation_amount(minimum_delegation_amount)
-            .with_strict_argument_checking(strict_argument_checking)
-            .with_vesting_schedule_period_millis(vesting_schedule_period_millis)
-            .with_max_delegators_per_validator(max_delegators_per_validator)
-            .with_wasm_config(wasm_config)
-            .with_system_config(system_config)
-            .with_administrative_accounts(administrative_accounts)
-            .with_allow_auction_bids(allow_auction_bids)
-            .with_allow_unrestricted_transfers(allow_unrestricted_transfers)
-            .with_refund_handling(refund_handling)
-            .with_fee_handling(fee_handling)
-            .build();
+        let metrics = Arc::new(Metrics::new(registry)?);
 
+        let mut trie_store = LmdbTrieStore::new(&environment, None, DatabaseFlags::empty())?;
+        if contract_runtime_config.trie_node_compression_enabled_or_default() {
+            trie_store = trie_store.with_compression_threshold(
+                contract_runtime_config.trie_node_compression_threshold_or_default(),
+            );
+        }
+        let trie_node_cache_capacity =
+            contract_runtime_config.trie_node_cache_capacity_or_default();
+        if trie_node_cache_capacity > 0 {
+            let node_cache = TrieNodeCache::new(trie_node_cache_capacity)
+                .with_observer(Arc::new(metrics.trie_node_cache_observer()));
+            trie_store = trie_store.with_node_cache(Arc::new(node_cache));
+        }
+        let trie_store = Arc::new(trie_store);
+
+        let global_state = LmdbGlobalState::empty(environment, trie_store)?
+            .with_checkout_observer(Arc::new(metrics.checkout_observer()));
+        if let Some(path) = &contract_runtime_config.trie_change_feed_socket {
+            let socket_path = path.clone();
+            let capacity = contract_runtime_config.trie_change_feed_capacity_or_default();
+            let connect = move || -> Result<Box<dyn TrieChangeSink>, TrieChangeSinkError> {
+                Ok(Box::new(UnixSocketSink::connect(&socket_path, capacity)?))
+            };
+            if let Err(error) = global_state
+                .change_feed()
+                .register_reconnecting(connect, DEFAULT_TRIE_CHANGE_SINK_RECONNECT_INTERVAL)
+            {
+                warn!(
+                    path = %path.display(),
+                    %error,
+                    "failed to connect to trie change feed socket, will retry"
+                );
+            }
+        }
         let engine_state = Arc::new(EngineState::new(global_state, engine_config));
 
-        let metrics = Arc::new(Metrics::new(registry)?);
-
         Ok(ContractRuntime {
             state: ComponentState::Initialized,
             execution_pre_state,
@@ -662,12 +825,60 @@ impl ContractRuntime {
             metrics,
             protocol_version,
             exec_queue: Arc::new(Mutex::new(BTreeMap::new())),
+            execution_cache: Arc::new(Mutex::new(ExecutionCache::new(
+                contract_runtime_config.max_cached_executed_blocks_or_default(),
+                contract_runtime_config.max_execution_cache_size_or_default(),
+            ))),
             system_contract_registry: None,
             activation_point,
             prune_batch_size,
+            max_state_root_age: contract_runtime_config.max_state_root_age,
+            query_pool: Arc::new(QueryPool::new(
+                contract_runtime_config.max_parallel_queries_or_default(),
+                contract_runtime_config.max_queued_queries_or_default(),
+            )),
         })
     }
 
+    /// Creates a new contract runtime using the global state stored alongside `storage`, configured
+    /// as specified by `chainspec`.
+    pub(crate) fn from_chainspec(
+        storage: &Storage,
+        contract_runtime_config: &Config,
+        chainspec: &Chainspec,
+        registry: &Registry,
+    ) -> Result<Self, ConfigError> {
+        ContractRuntime::new(
+            chainspec.protocol_config.version,
+            storage.root_path(),
+            contract_runtime_config,
+            engine_config_from_chainspec(chainspec, contract_runtime_config),
+            chainspec.protocol_config.activation_point,
+            chainspec.core_config.prune_batch_size,
+            registry,
+        )
+    }
+
+    /// Returns an error if the state root is further behind the most recently executed block than
+    /// permitted by the configured limit.
+    fn check_state_root_age(
+        &self,
+        state_root_hash: Digest,
+        state_root_age_check: StateRootAgeCheck,
+    ) -> Result<(), StateQueryError> {
+        let max_age = match self.max_state_root_age {
+            Some(max_age) => max_age,
+            None => return Ok(()),
+        };
+        let tip_height = self
+            .execution_pre_state
+            .lock()
+            .unwrap()
+            .next_block_height
+            .saturating_sub(1);
+        check_state_root_age(state_root_hash, state_root_age_check, tip_height, max_age)
+    }
+
     /// Commits a genesis request.
     pub(crate) fn commit_genesis(
         &self,
@@ -747,6 +958,7 @@ impl ContractRuntime {
         engine_state: Arc<EngineState<LmdbGlobalState>>,
         metrics: Arc<Metrics>,
         exec_queue: ExecQueue,
+        execution_cache: Arc<Mutex<ExecutionCache>>,
         shared_pre_state: Arc<Mutex<ExecutionPreState>>,
         current_pre_state: ExecutionPreState,
         effect_builder: EffectBuilder<REv>,
@@ -766,34 +978,77 @@ impl ContractRuntime {
             + Send,
     {
         debug!("ContractRuntime: execute_finalized_block_or_requeue");
-        let contract_runtime_metrics = metrics.clone();
+        let maybe_cached = Self::get_previous_execution(
+            &engine_state,
+            &execution_cache,
+            effect_builder,
+            &current_pre_state,
+            &finalized_block,
+        )
+        .await;
+        let block_and_execution_results = match maybe_cached {
+            Some(block_and_execution_results) => {
+                metrics.exec_cache_hits.inc();
+                block_and_execution_results
+            }
+            None => {
+                let contract_runtime_metrics = metrics.clone();
+                let pre_state = current_pre_state.clone();
+                let block_to_cache = finalized_block.clone();
+                match run_intensive_task(move || {
+                    debug!("ContractRuntime: execute_finalized_block");
+                    execute_finalized_block(
+                        engine_state.as_ref(),
+                        Some(contract_runtime_metrics),
+                        protocol_version,
+                        pre_state,
+                        finalized_block,
+                        deploys,
+                        activation_point.era_id(),
+                        key_block_height_for_activation_point,
+                        prune_batch_size,
+                    )
+                })
+                .await
+                {
+                    Ok(block_and_execution_results) => {
+                        let execution_artifacts = ExecutionArtifacts::new(
+                            &current_pre_state,
+                            block_to_cache,
+                            block_and_execution_results.clone(),
+                        );
+                        let max_stored_heights = {
+                            let mut execution_cache = execution_cache
+                                .lock()
+                                .expect("ContractRuntime: execution_cache poisoned mutex");
+                            execution_cache.insert(execution_artifacts.clone());
+                            execution_cache.capacity() as u64
+                        };
+                        if max_stored_heights > 0 {
+                            effect_builder
+                                .put_execution_artifacts_to_storage(
+                                    execution_artifacts,
+                                    max_stored_heights,
+                                )
+                                .await;
+                        }
+                        block_and_execution_results
+                    }
+                    Err(error) => {
+                        error!(%error, "failed to execute block");
+                        return fatal!(effect_builder, "{}", error).await;
+                    }
+                }
+            }
+        };
         let BlockAndExecutionResults {
             block,
             approvals_hashes,
             execution_results,
+            execution_time,
+            transaction_receipts,
             maybe_step_effect_and_upcoming_era_validators,
-        } = match run_intensive_task(move || {
-            debug!("ContractRuntime: execute_finalized_block");
-            execute_finalized_block(
-                engine_state.as_ref(),
-                Some(contract_runtime_metrics),
-                protocol_version,
-                current_pre_state,
-                finalized_block,
-                deploys,
-                activation_point.era_id(),
-                key_block_height_for_activation_point,
-                prune_batch_size,
-            )
-        })
-        .await
-        {
-            Ok(block_and_execution_results) => block_and_execution_results,
-            Err(error) => {
-                error!(%error, "failed to execute block");
-                return fatal!(effect_builder, "{}", error).await;
-            }
-        };
+        } = block_and_execution_results;
 
         let new_execution_pre_state = ExecutionPreState::from_block_header(block.header());
         {
@@ -820,12 +1075,12 @@ impl ContractRuntime {
         let current_era_id = block.header().era_id();
 
         if let Some(StepEffectAndUpcomingEraValidators {
-            step_execution_journal,
+            step_effect,
             mut upcoming_era_validators,
         }) = maybe_step_effect_and_upcoming_era_validators
         {
             effect_builder
-                .announce_commit_step_success(current_era_id, step_execution_journal)
+                .announce_commit_step_success(current_era_id, step_effect)
                 .await;
 
             if current_era_id.is_genesis() {
@@ -877,6 +1132,16 @@ impl ContractRuntime {
                 .put_execution_results_to_storage(*block.hash(), execution_results_map)
                 .await;
         }
+        effect_builder
+            .put_transaction_receipts_to_storage(transaction_receipts)
+            .await;
+        effect_builder
+            .put_block_execution_stats_to_storage(EraExecutionStats::from_executed_block(
+                &block,
+                &execution_results,
+                execution_time,
+            ))
+            .await;
         if meta_block_state
             .register_as_executed()
             .was_already_registered()
@@ -909,6 +1174,90 @@ impl ContractRuntime {
         }
     }
 
+    /// Replays a finalized block which has already been executed and stored, outside of the
+    /// reactor and without writing anything to global state.
+    ///
+    /// This is used to replay blocks, e.g. when auditing the integrity of the stored chain.
+    pub(crate) fn replay_stored_block(
+        &self,
+        execution_pre_state: ExecutionPreState,
+        finalized_block: FinalizedBlock,
+        deploys: Vec<Deploy>,
+        key_block_height_for_activation_point: u64,
+    ) -> Result<ReplayedBlock, BlockExecutionError> {
+        replay_finalized_block(
+            self.engine_state.as_ref(),
+            Some(Arc::clone(&self.metrics)),
+            self.protocol_version,
+            execution_pre_state,
+            finalized_block,
+            deploys,
+            self.activation_point.era_id(),
+            key_block_height_for_activation_point,
+            self.prune_batch_size,
+        )
+    }
+
+    /// Returns the previously computed results of executing `finalized_block` on top of
+    /// `execution_pre_state`, provided the post-state root they refer to is still present in global
+    /// state.
+    ///
+    /// The in-memory cache is consulted first, then the artifacts persisted in storage, which
+    /// survive a restart.  The cache is only locked while looking up and removing the entry, not
+    /// while storage or global state is read.
+    async fn get_previous_execution<REv>(
+        engine_state: &EngineState<LmdbGlobalState>,
+        execution_cache: &Mutex<ExecutionCache>,
+        effect_builder: EffectBuilder<REv>,
+        execution_pre_state: &ExecutionPreState,
+        finalized_block: &FinalizedBlock,
+    ) -> Option<BlockAndExecutionResults>
+    where
+        REv: From<StorageRequest>,
+    {
+        let (maybe_cached, capacity) = {
+            let execution_cache = execution_cache
+                .lock()
+                .expect("ContractRuntime: execution_cache poisoned mutex");
+            (
+                execution_cache.get(execution_pre_state, finalized_block),
+                execution_cache.capacity(),
+            )
+        };
+        let previous = match maybe_cached {
+            Some(cached) => cached,
+            None if capacity > 0 => effect_builder
+                .get_execution_artifacts_from_storage(finalized_block.height())
+                .await
+                .filter(|stored| stored.is_execution_of(execution_pre_state, finalized_block))
+                .map(ExecutionArtifacts::into_block_and_execution_results)?,
+            None => return None,
+        };
+        let state_root_hash = *previous.block.state_root_hash();
+        match engine_state.has_root(state_root_hash) {
+            Ok(true) => {
+                debug!(
+                    block_hash = %previous.block.hash(),
+                    height = previous.block.height(),
+                    "ContractRuntime: reusing previous execution results"
+                );
+                Some(previous)
+            }
+            Ok(false) | Err(_) => {
+                debug!(
+                    block_hash = %previous.block.hash(),
+                    %state_root_hash,
+                    "ContractRuntime: previous post-state root missing, re-executing block"
+                );
+                execution_cache
+                    .lock()
+                    .expect("ContractRuntime: execution_cache poisoned mutex")
+                    .remove(previous.block.hash());
+                None
+            }
+        }
+    }
+
     /// Reads the trie (or chunk of a trie) under the given key and index.
     pub(crate) fn get_trie(
         &self,
@@ -951,8 +1300,7 @@ impl ContractRuntime {
         result.map(|option| option.map(|trie_raw| trie_raw.into_inner()))
     }
 
-    /// Returns the engine state, for testing only.
-    #[cfg(test)]
+    /// Returns the engine state.
     pub(crate) fn engine_state(&self) -> &Arc<EngineState<LmdbGlobalState>> {
         &self.engine_state
     }
@@ -977,14 +1325,145 @@ impl ContractRuntime {
     }
 }
 
+/// Returns the engine config with which the contract runtime executes deploys under `chainspec`.
+pub(crate) fn engine_config_from_chainspec(
+    chainspec: &Chainspec,
+    contract_runtime_config: &Config,
+) -> EngineConfig {
+    let core_config = &chainspec.core_config;
+    let max_delegators_per_validator = if core_config.max_delegators_per_validator == 0 {
+        None
+    } else {
+        Some(core_config.max_delegators_per_validator)
+    };
+    EngineConfigBuilder::new()
+        .with_max_query_depth(contract_runtime_config.max_query_depth_or_default())
+        .with_max_associated_keys(core_config.max_associated_keys)
+        .with_max_runtime_call_stack_height(core_config.max_runtime_call_stack_height)
+        .with_minimum_delegation_amount(core_config.minimum_delegation_amount)
+        .with_strict_argument_checking(core_config.strict_argument_checking)
+        .with_entry_point_precheck(core_config.entry_point_precheck)
+        .with_vesting_schedule_period_millis(core_config.vesting_schedule_period.millis())
+        .with_max_delegators_per_validator(max_delegators_per_validator)
+        .with_wasm_config(chainspec.wasm_config)
+        .with_system_config(chainspec.system_costs_config)
+        .with_administrative_accounts(core_config.administrators.clone())
+        .with_allow_auction_bids(core_config.allow_auction_bids)
+        .with_allow_unrestricted_transfers(core_config.allow_unrestricted_transfers)
+        .with_minimum_remaining_balance(core_config.minimum_remaining_balance)
+        .with_validator_eviction_threshold(core_config.validator_eviction_threshold)
+        .with_deduplicate_writes(core_config.deduplicate_writes)
+        .with_isolate_deploy_failures(core_config.isolate_deploy_failures)
+        .with_record_execution_error_codes(core_config.record_execution_error_codes)
+        .with_refund_handling(core_config.refund_handling)
+        .with_fee_handling(core_config.fee_handling)
+        .build()
+}
+
+/// Returns an error if the state root is more than `max_age` blocks behind the block at
+/// `tip_height`.
+///
+/// State roots which aren't known to be that of any block are rejected too, as their age can't be
+/// determined.
+fn check_state_root_age(
+    state_root_hash: Digest,
+    state_root_age_check: StateRootAgeCheck,
+    tip_height: u64,
+    max_age: u64,
+) -> Result<(), StateQueryError> {
+    let block_height = match state_root_age_check {
+        StateRootAgeCheck::Unchecked => return Ok(()),
+        StateRootAgeCheck::AtHeight(block_height) => block_height,
+        StateRootAgeCheck::UnknownHeight => {
+            debug!(
+                %state_root_hash,
+                max_age, "rejecting request for state root of unknown height"
+            );
+            return Err(StateQueryError::StateRootHeightUnknown {
+                state_root_hash,
+                max_age,
+            });
+        }
+    };
+    if tip_height.saturating_sub(block_height) > max_age {
+        debug!(
+            %state_root_hash,
+            block_height, tip_height, max_age, "rejecting request for old state root"
+        );
+        return Err(StateQueryError::StateRootTooOld {
+            state_root_hash,
+            block_height,
+            tip_height,
+            max_age,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod state_root_age_tests {
+    use assert_matches::assert_matches;
+
+    use casper_hashing::Digest;
+
+    use super::{check_state_root_age, StateQueryError, StateRootAgeCheck};
+
+    const TIP_HEIGHT: u64 = 100;
+    const MAX_AGE: u64 = 10;
+
+    fn check(state_root_age_check: StateRootAgeCheck) -> Result<(), StateQueryError> {
+        check_state_root_age(
+            Digest::hash([1; 32]),
+            state_root_age_check,
+            TIP_HEIGHT,
+            MAX_AGE,
+        )
+    }
+
+    #[test]
+    fn should_accept_state_root_within_max_age() {
+        assert!(check(StateRootAgeCheck::AtHeight(TIP_HEIGHT)).is_ok());
+        assert!(check(StateRootAgeCheck::AtHeight(TIP_HEIGHT - MAX_AGE)).is_ok());
+        // A block executed after the tip was read is not rejected.
+        assert!(check(StateRootAgeCheck::AtHeight(TIP_HEIGHT + 1)).is_ok());
+    }
+
+    #[test]
+    fn should_reject_state_root_older_than_max_age() {
+        let block_height = TIP_HEIGHT - MAX_AGE - 1;
+        assert_matches!(
+            check(StateRootAgeCheck::AtHeight(block_height)),
+            Err(StateQueryError::StateRootTooOld {
+                block_height: rejected_height,
+                tip_height: TIP_HEIGHT,
+                max_age: MAX_AGE,
+                ..
+            }) if rejected_height == block_height
+        );
+    }
+
+    #[test]
+    fn should_reject_state_root_of_unknown_height() {
+        assert_matches!(
+            check(StateRootAgeCheck::UnknownHeight),
+            Err(StateQueryError::StateRootHeightUnknown {
+                max_age: MAX_AGE,
+                ..
+            })
+        );
+    }
+
+    #[test]
+    fn should_not_check_state_root_queried_by_node() {
+        assert!(check(StateRootAgeCheck::Unchecked).is_ok());
+    }
+}
+
 #[cfg(test)]
 mod trie_chunking_tests {
     use casper_execution_engine::{
-        core::engine_state::engine_config::{DEFAULT_FEE_HANDLING, DEFAULT_REFUND_HANDLING},
-        shared::{
-            additive_map::AdditiveMap, newtypes::CorrelationId, system_config::SystemConfig,
-            transform::Transform, wasm_config::WasmConfig,
-        },
+        core::engine_state::EngineConfig,
+        shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
         storage::trie::{Pointer, Trie},
     };
     use casper_hashing::{ChunkWithProof, Digest};
@@ -1055,22 +1534,10 @@ mod trie_chunking_tests {
             ProtocolVersion::default(),
             temp_dir.path(),
             &ContractRuntimeConfig::default(),
-            WasmConfig::default(),
-            SystemConfig::default(),
-            10,
-            10,
-            10,
+            EngineConfig::default(),
             ActivationPoint::EraId(EraId::from(2)),
             5,
-            true,
-            1,
-            None,
             &Registry::default(),
-            Default::default(),
-            true,
-            true,
-            DEFAULT_REFUND_HANDLING,
-            DEFAULT_FEE_HANDLING,
         )
         .unwrap();
         let empty_state_root = contract_runtime
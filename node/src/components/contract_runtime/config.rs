@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
@@ -7,9 +9,17 @@ const DEFAULT_MAX_GLOBAL_STATE_SIZE: usize = 805_306_368_000; // 750 GiB
 const DEFAULT_MAX_READERS: u32 = 512;
 const DEFAULT_MAX_QUERY_DEPTH: u64 = 5;
 const DEFAULT_MANUAL_SYNC_ENABLED: bool = true;
+const DEFAULT_MAX_CACHED_EXECUTED_BLOCKS: usize = 10;
+const DEFAULT_MAX_EXECUTION_CACHE_SIZE: usize = 268_435_456; // 256 MiB
+const DEFAULT_TRIE_NODE_COMPRESSION_ENABLED: bool = false;
+const DEFAULT_TRIE_NODE_COMPRESSION_THRESHOLD: usize = 1024;
+const DEFAULT_TRIE_NODE_CACHE_CAPACITY: usize = 1000;
+const DEFAULT_MAX_PARALLEL_QUERIES: usize = 4;
+const DEFAULT_MAX_QUEUED_QUERIES: usize = 1024;
+const DEFAULT_TRIE_CHANGE_FEED_CAPACITY: usize = 1024;
 
 /// Contract runtime configuration.
-#[derive(Clone, Copy, DataSize, Debug, Deserialize, Serialize)]
+#[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
 #[serde(deny_unknown_fields)]
 pub struct Config {
@@ -31,6 +41,63 @@ pub struct Config {
     ///
     /// Defaults to `true`.
     pub enable_manual_sync: Option<bool>,
+    /// The maximum number of recently executed blocks whose execution results are cached in
+    /// memory, allowing them to be reused if the same block is enqueued for execution again.
+    ///
+    /// Defaults to 10.  Setting to 0 disables the cache.
+    ///
+    /// The execution results of the same number of most recent block heights are also persisted in
+    /// storage, so they can be reused after a restart.
+    pub max_cached_executed_blocks: Option<usize>,
+    /// The maximum estimated size in bytes of the execution results held in the cache of recently
+    /// executed blocks.  The oldest entries are evicted to stay within it, and the results of a
+    /// block larger than it are not cached.
+    ///
+    /// Defaults to 268,435,456 == 256 MiB.
+    pub max_execution_cache_size: Option<usize>,
+    /// Enable zstd compression of large trie leaves in the global state store.
+    ///
+    /// This saves disk space at the cost of slower commits, so is disabled unless opted into.
+    ///
+    /// Defaults to `false`.
+    pub enable_trie_node_compression: Option<bool>,
+    /// The serialized size in bytes above which trie leaves are compressed, if compression is
+    /// enabled.
+    ///
+    /// Defaults to 1024.
+    pub trie_node_compression_threshold: Option<usize>,
+    /// Path of a Unix domain socket to which the changes made to global state by each commit are
+    /// written, as one JSON object per line, for consumption by external indexers.
+    ///
+    /// The node connects to the socket on startup, and whenever it can't connect or gets
+    /// disconnected it retries at most every 10 seconds.  After reconnecting, it first writes a
+    /// `Gap` marker, as some change sets were missed.  Defaults to no socket.
+    pub trie_change_feed_socket: Option<PathBuf>,
+    /// The maximum number of change sets waiting to be written to the trie change feed socket.  If
+    /// the indexer falls further behind, the node disconnects from it until it can reconnect.
+    ///
+    /// Defaults to 1024.
+    pub trie_change_feed_capacity: Option<usize>,
+    /// The maximum age, in blocks behind the most recently executed block, of a state root against
+    /// which RPC queries and speculative execution may run.  Older state roots are rejected.
+    ///
+    /// Defaults to no limit, as is appropriate for archive nodes.
+    pub max_state_root_age: Option<u64>,
+    /// The maximum number of decoded trie nodes kept in memory, shared by all readers of global
+    /// state.
+    ///
+    /// Defaults to 1000.  Setting to 0 disables the cache.
+    pub trie_node_cache_capacity: Option<usize>,
+    /// The maximum number of read-only queries of global state (e.g. queries, balances and era
+    /// validators) run in parallel, separately from block execution.
+    ///
+    /// Defaults to 4.
+    pub max_parallel_queries: Option<usize>,
+    /// The maximum number of read-only queries waiting to run.  Further queries are rejected until
+    /// the backlog clears.
+    ///
+    /// Defaults to 1024.
+    pub max_queued_queries: Option<usize>,
 }
 
 impl Config {
@@ -58,6 +125,54 @@ impl Config {
         self.enable_manual_sync
             .unwrap_or(DEFAULT_MANUAL_SYNC_ENABLED)
     }
+
+    /// Max number of executed blocks to cache.
+    pub fn max_cached_executed_blocks_or_default(&self) -> usize {
+        self.max_cached_executed_blocks
+            .unwrap_or(DEFAULT_MAX_CACHED_EXECUTED_BLOCKS)
+    }
+
+    /// Max size in bytes of the cache of executed blocks.
+    pub fn max_execution_cache_size_or_default(&self) -> usize {
+        self.max_execution_cache_size
+            .unwrap_or(DEFAULT_MAX_EXECUTION_CACHE_SIZE)
+    }
+
+    /// Is trie node compression enabled.
+    pub fn trie_node_compression_enabled_or_default(&self) -> bool {
+        self.enable_trie_node_compression
+            .unwrap_or(DEFAULT_TRIE_NODE_COMPRESSION_ENABLED)
+    }
+
+    /// Size above which trie leaves are compressed.
+    pub fn trie_node_compression_threshold_or_default(&self) -> usize {
+        self.trie_node_compression_threshold
+            .unwrap_or(DEFAULT_TRIE_NODE_COMPRESSION_THRESHOLD)
+    }
+
+    /// Max number of change sets waiting to be written to the trie change feed socket.
+    pub fn trie_change_feed_capacity_or_default(&self) -> usize {
+        self.trie_change_feed_capacity
+            .unwrap_or(DEFAULT_TRIE_CHANGE_FEED_CAPACITY)
+    }
+
+    /// Max number of decoded trie nodes to cache.
+    pub fn trie_node_cache_capacity_or_default(&self) -> usize {
+        self.trie_node_cache_capacity
+            .unwrap_or(DEFAULT_TRIE_NODE_CACHE_CAPACITY)
+    }
+
+    /// Max number of queries to run in parallel.
+    pub fn max_parallel_queries_or_default(&self) -> usize {
+        self.max_parallel_queries
+            .unwrap_or(DEFAULT_MAX_PARALLEL_QUERIES)
+    }
+
+    /// Max number of queries waiting to run.
+    pub fn max_queued_queries_or_default(&self) -> usize {
+        self.max_queued_queries
+            .unwrap_or(DEFAULT_MAX_QUEUED_QUERIES)
+    }
 }
 
 impl Default for Config {
@@ -67,6 +182,16 @@ impl Default for Config {
             max_readers: Some(DEFAULT_MAX_READERS),
             max_query_depth: Some(DEFAULT_MAX_QUERY_DEPTH),
             enable_manual_sync: Some(DEFAULT_MANUAL_SYNC_ENABLED),
+            max_cached_executed_blocks: Some(DEFAULT_MAX_CACHED_EXECUTED_BLOCKS),
+            max_execution_cache_size: Some(DEFAULT_MAX_EXECUTION_CACHE_SIZE),
+            enable_trie_node_compression: Some(DEFAULT_TRIE_NODE_COMPRESSION_ENABLED),
+            trie_node_compression_threshold: Some(DEFAULT_TRIE_NODE_COMPRESSION_THRESHOLD),
+            trie_change_feed_socket: None,
+            trie_change_feed_capacity: Some(DEFAULT_TRIE_CHANGE_FEED_CAPACITY),
+            max_state_root_age: None,
+            trie_node_cache_capacity: Some(DEFAULT_TRIE_NODE_CACHE_CAPACITY),
+            max_parallel_queries: Some(DEFAULT_MAX_PARALLEL_QUERIES),
+            max_queued_queries: Some(DEFAULT_MAX_QUEUED_QUERIES),
         }
     }
 }
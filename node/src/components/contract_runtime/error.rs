@@ -7,6 +7,7 @@ use casper_execution_engine::{
     core::engine_state::{Error as EngineStateError, StepError},
     storage::error::lmdb::Error as StorageLmdbError,
 };
+use casper_hashing::Digest;
 
 use crate::{
     components::contract_runtime::ExecutionPreState,
@@ -25,6 +26,42 @@ pub(crate) enum ConfigError {
     Prometheus(#[from] prometheus::Error),
 }
 
+/// An error returned by the contract runtime when serving a query, a balance request or a
+/// speculative execution against a given state root.
+#[derive(Debug, Error)]
+pub(crate) enum StateQueryError {
+    /// The state root hash is older than the configured limit for historical queries.
+    #[error(
+        "State root {state_root_hash} at block height {block_height} is older than the limit of \
+        {max_age} blocks behind the tip at height {tip_height}"
+    )]
+    StateRootTooOld {
+        /// The requested state root hash.
+        state_root_hash: Digest,
+        /// The height of the block whose state root hash was requested.
+        block_height: u64,
+        /// The height of the most recently executed block.
+        tip_height: u64,
+        /// The maximum permitted age in blocks.
+        max_age: u64,
+    },
+    /// The state root hash isn't known to be that of any block, so its age can't be checked against
+    /// the configured limit for historical queries.
+    #[error(
+        "State root {state_root_hash} is not that of any known block, so can't be checked against \
+        the limit of {max_age} blocks behind the tip"
+    )]
+    StateRootHeightUnknown {
+        /// The requested state root hash.
+        state_root_hash: Digest,
+        /// The maximum permitted age in blocks.
+        max_age: u64,
+    },
+    /// An error returned by the execution engine.
+    #[error(transparent)]
+    EngineState(#[from] EngineStateError),
+}
+
 /// An error during block execution.
 #[derive(Debug, Error, Serialize)]
 pub enum BlockExecutionError {
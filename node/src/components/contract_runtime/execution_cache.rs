@@ -0,0 +1,243 @@
+//! A bounded cache of block execution artifacts, used to avoid redundant re-execution of a block.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use datasize::DataSize;
+use tracing::debug;
+
+use crate::{
+    components::contract_runtime::{
+        BlockAndExecutionResults, ExecutionArtifacts, ExecutionPreState,
+    },
+    types::{BlockHash, FinalizedBlock},
+};
+
+/// A single cached execution of a finalized block.
+#[derive(Clone, Debug, DataSize)]
+struct CachedExecution {
+    /// The artifacts of the execution.
+    execution_artifacts: ExecutionArtifacts,
+    /// The estimated heap size of this entry, counted against the cache's size limit.
+    size: usize,
+}
+
+/// Cache of the outcomes of recently executed blocks, keyed by the hash of the resulting block.
+///
+/// Execution of a finalized block on top of a given pre-state is deterministic, so if the same
+/// block is handed to the contract runtime again (e.g. after a sync leap reset the execution
+/// pre-state, or after a transient failure further down the pipeline) the previously computed
+/// outcome can be reused, provided the post-state root it refers to is still in global state.
+///
+/// The cache holds at most `capacity` entries with an estimated heap size of at most `max_size`
+/// bytes in total, evicting the oldest first.  It is held in memory only; the same artifacts are
+/// persisted in storage so that they can be reused after a restart.
+#[derive(Debug, DataSize)]
+pub(super) struct ExecutionCache {
+    capacity: usize,
+    max_size: usize,
+    size: usize,
+    entries: BTreeMap<BlockHash, CachedExecution>,
+    insertion_order: VecDeque<BlockHash>,
+}
+
+impl ExecutionCache {
+    /// Creates a new, empty cache able to hold up to `capacity` entries totalling up to `max_size`
+    /// bytes.
+    pub(super) fn new(capacity: usize, max_size: usize) -> Self {
+        ExecutionCache {
+            capacity,
+            max_size,
+            size: 0,
+            entries: BTreeMap::new(),
+            insertion_order: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the maximum number of cached entries.
+    pub(super) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of cached entries.
+    #[cfg(test)]
+    pub(super) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the cached outcome of executing `finalized_block` on top of `execution_pre_state`,
+    /// if any.
+    pub(super) fn get(
+        &self,
+        execution_pre_state: &ExecutionPreState,
+        finalized_block: &FinalizedBlock,
+    ) -> Option<BlockAndExecutionResults> {
+        self.entries
+            .values()
+            .find(|cached| {
+                cached
+                    .execution_artifacts
+                    .is_execution_of(execution_pre_state, finalized_block)
+            })
+            .map(|cached| {
+                cached
+                    .execution_artifacts
+                    .block_and_execution_results()
+                    .clone()
+            })
+    }
+
+    /// Caches the artifacts of executing a block.
+    pub(super) fn insert(&mut self, execution_artifacts: ExecutionArtifacts) {
+        if self.capacity == 0 {
+            return;
+        }
+        let block_hash = *execution_artifacts
+            .block_and_execution_results()
+            .block
+            .hash();
+        let mut cached = CachedExecution {
+            execution_artifacts,
+            size: 0,
+        };
+        cached.size = cached.estimate_heap_size();
+        if cached.size > self.max_size {
+            debug!(
+                %block_hash,
+                size = cached.size,
+                max_size = self.max_size,
+                "ContractRuntime: execution results too large to cache"
+            );
+            self.remove(&block_hash);
+            return;
+        }
+        self.size += cached.size;
+        match self.entries.insert(block_hash, cached) {
+            Some(replaced) => self.size -= replaced.size,
+            None => self.insertion_order.push_back(block_hash),
+        }
+        while self.insertion_order.len() > self.capacity || self.size > self.max_size {
+            match self.insertion_order.pop_front() {
+                Some(evicted) => {
+                    if let Some(evicted) = self.entries.remove(&evicted) {
+                        self.size -= evicted.size;
+                    }
+                }
+                None => break,
+            }
+        }
+    }
+
+    /// Removes the entry for the given block hash, if present.
+    pub(super) fn remove(&mut self, block_hash: &BlockHash) {
+        if let Some(removed) = self.entries.remove(block_hash) {
+            self.size -= removed.size;
+            self.insertion_order.retain(|hash| hash != block_hash);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use casper_hashing::Digest;
+    use casper_types::testing::TestRng;
+
+    use super::*;
+
+    fn executed_block(
+        rng: &mut TestRng,
+    ) -> (ExecutionPreState, FinalizedBlock, ExecutionArtifacts) {
+        let height = rng.gen_range(0..1000);
+        let (execution_pre_state, execution_artifacts) = ExecutionArtifacts::random(rng, height);
+        let finalized_block = FinalizedBlock::from(
+            (*execution_artifacts.block_and_execution_results().block).clone(),
+        );
+        (execution_pre_state, finalized_block, execution_artifacts)
+    }
+
+    #[test]
+    fn should_return_cached_execution_for_same_pre_state_and_block() {
+        let mut rng = TestRng::new();
+        let mut cache = ExecutionCache::new(2, usize::MAX);
+        let (pre_state, finalized_block, artifacts) = executed_block(&mut rng);
+        cache.insert(artifacts.clone());
+
+        let cached = cache
+            .get(&pre_state, &finalized_block)
+            .expect("should be cached");
+        assert_eq!(cached.block, artifacts.block_and_execution_results().block);
+
+        let other_pre_state = ExecutionPreState::new(
+            pre_state.next_block_height,
+            Digest::hash([1; 32]),
+            pre_state.parent_hash,
+            pre_state.parent_seed,
+        );
+        assert!(cache.get(&other_pre_state, &finalized_block).is_none());
+    }
+
+    #[test]
+    fn should_evict_oldest_entry_when_full() {
+        let mut rng = TestRng::new();
+        let mut cache = ExecutionCache::new(2, usize::MAX);
+        let first = executed_block(&mut rng);
+        let second = executed_block(&mut rng);
+        let third = executed_block(&mut rng);
+        for (_, _, artifacts) in [&first, &second, &third] {
+            cache.insert(artifacts.clone());
+        }
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&first.0, &first.1).is_none());
+        assert!(cache.get(&second.0, &second.1).is_some());
+        assert!(cache.get(&third.0, &third.1).is_some());
+    }
+
+    #[test]
+    fn should_evict_oldest_entries_to_stay_within_max_size() {
+        let mut rng = TestRng::new();
+        let first = executed_block(&mut rng);
+        let second = executed_block(&mut rng);
+
+        let mut unbounded_cache = ExecutionCache::new(10, usize::MAX);
+        for (_, _, artifacts) in [&first, &second] {
+            unbounded_cache.insert(artifacts.clone());
+        }
+        let total_size = unbounded_cache.size;
+
+        let mut cache = ExecutionCache::new(10, total_size - 1);
+        for (_, _, artifacts) in [&first, &second] {
+            cache.insert(artifacts.clone());
+        }
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get(&first.0, &first.1).is_none());
+        assert!(cache.get(&second.0, &second.1).is_some());
+        assert!(cache.size < total_size);
+
+        cache.remove(second.2.block_and_execution_results().block.hash());
+        assert_eq!(cache.len(), 0);
+        assert_eq!(cache.size, 0);
+    }
+
+    #[test]
+    fn should_not_cache_execution_larger_than_max_size() {
+        let mut rng = TestRng::new();
+        let mut cache = ExecutionCache::new(2, 1);
+        let (pre_state, finalized_block, artifacts) = executed_block(&mut rng);
+        cache.insert(artifacts);
+        assert_eq!(cache.len(), 0);
+        assert_eq!(cache.size, 0);
+        assert!(cache.get(&pre_state, &finalized_block).is_none());
+    }
+
+    #[test]
+    fn should_not_cache_when_capacity_is_zero() {
+        let mut rng = TestRng::new();
+        let mut cache = ExecutionCache::new(0, usize::MAX);
+        let (pre_state, finalized_block, artifacts) = executed_block(&mut rng);
+        cache.insert(artifacts);
+        assert_eq!(cache.len(), 0);
+        assert!(cache.get(&pre_state, &finalized_block).is_none());
+    }
+}
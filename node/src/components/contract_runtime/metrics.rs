@@ -1,4 +1,10 @@
-use prometheus::{self, Gauge, Histogram, IntGauge, Registry};
+use std::time::Duration;
+
+use casper_execution_engine::storage::{
+    global_state::checkout_observer::CheckoutObserver,
+    trie_store::node_cache::TrieNodeCacheObserver,
+};
+use prometheus::{self, Gauge, Histogram, IntCounter, IntGauge, Registry};
 
 use crate::{unregister_metric, utils};
 
@@ -39,6 +45,9 @@ const GET_ERA_VALIDATORS_HELP: &str =
 const GET_BIDS_NAME: &str = "contract_runtime_get_bids";
 const GET_BIDS_HELP: &str = "time in seconds to get bids from global state";
 
+const GET_UNBONDS_NAME: &str = "contract_runtime_get_unbonds";
+const GET_UNBONDS_HELP: &str = "time in seconds to get pending unbonds from global state";
+
 const PUT_TRIE_NAME: &str = "contract_runtime_put_trie";
 const PUT_TRIE_HELP: &str = "time in seconds to put a trie";
 
@@ -55,6 +64,25 @@ const EXEC_QUEUE_SIZE_NAME: &str = "execution_queue_size";
 const EXEC_QUEUE_SIZE_HELP: &str =
     "number of blocks that are currently enqueued and waiting for execution";
 
+const EXEC_CACHE_HITS_NAME: &str = "contract_runtime_execution_cache_hits";
+const EXEC_CACHE_HITS_HELP: &str =
+    "number of times a block's execution results were reused from the execution cache";
+
+const CHECKOUT_NAME: &str = "contract_runtime_checkout";
+const CHECKOUT_HELP: &str = "time in seconds to check out a state root of global state";
+
+const CHECKOUT_ROOT_NOT_FOUND_NAME: &str = "contract_runtime_checkout_root_not_found";
+const CHECKOUT_ROOT_NOT_FOUND_HELP: &str =
+    "number of checkouts of global state for which the state root was not found";
+
+const TRIE_NODE_CACHE_HITS_NAME: &str = "contract_runtime_trie_node_cache_hits";
+const TRIE_NODE_CACHE_HITS_HELP: &str =
+    "number of trie nodes read from global state which were found in the trie node cache";
+
+const TRIE_NODE_CACHE_MISSES_NAME: &str = "contract_runtime_trie_node_cache_misses";
+const TRIE_NODE_CACHE_MISSES_HELP: &str =
+    "number of trie nodes read from global state which were not found in the trie node cache";
+
 /// Metrics for the contract runtime component.
 #[derive(Debug)]
 pub struct Metrics {
@@ -66,14 +94,51 @@ pub struct Metrics {
     pub(super) get_balance: Histogram,
     pub(super) get_era_validators: Histogram,
     pub(super) get_bids: Histogram,
+    pub(super) get_unbonds: Histogram,
     pub(super) put_trie: Histogram,
     pub(super) get_trie: Histogram,
     pub(super) exec_block: Histogram,
     pub(super) latest_commit_step: Gauge,
     pub(super) exec_queue_size: IntGauge,
+    pub(super) exec_cache_hits: IntCounter,
+    pub(super) checkout: Histogram,
+    pub(super) checkout_root_not_found: IntCounter,
+    pub(super) trie_node_cache_hits: IntCounter,
+    pub(super) trie_node_cache_misses: IntCounter,
     registry: Registry,
 }
 
+/// Records checkouts of global state into the contract runtime's metrics.
+pub(super) struct CheckoutMetrics {
+    checkout: Histogram,
+    checkout_root_not_found: IntCounter,
+}
+
+impl CheckoutObserver for CheckoutMetrics {
+    fn observe_checkout(&self, duration: Duration, found: bool) {
+        self.checkout.observe(duration.as_secs_f64());
+        if !found {
+            self.checkout_root_not_found.inc();
+        }
+    }
+}
+
+/// Records lookups in the trie node cache into the contract runtime's metrics.
+pub(super) struct TrieNodeCacheMetrics {
+    hits: IntCounter,
+    misses: IntCounter,
+}
+
+impl TrieNodeCacheObserver for TrieNodeCacheMetrics {
+    fn observe_lookup(&self, hit: bool) {
+        if hit {
+            self.hits.inc();
+        } else {
+            self.misses.inc();
+        }
+    }
+}
+
 impl Metrics {
     /// Constructor of metrics which creates and registers metrics objects for use.
     pub(super) fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
@@ -95,6 +160,21 @@ impl Metrics {
         let exec_queue_size = IntGauge::new(EXEC_QUEUE_SIZE_NAME, EXEC_QUEUE_SIZE_HELP)?;
         registry.register(Box::new(exec_queue_size.clone()))?;
 
+        let exec_cache_hits = IntCounter::new(EXEC_CACHE_HITS_NAME, EXEC_CACHE_HITS_HELP)?;
+        registry.register(Box::new(exec_cache_hits.clone()))?;
+
+        let checkout_root_not_found =
+            IntCounter::new(CHECKOUT_ROOT_NOT_FOUND_NAME, CHECKOUT_ROOT_NOT_FOUND_HELP)?;
+        registry.register(Box::new(checkout_root_not_found.clone()))?;
+
+        let trie_node_cache_hits =
+            IntCounter::new(TRIE_NODE_CACHE_HITS_NAME, TRIE_NODE_CACHE_HITS_HELP)?;
+        registry.register(Box::new(trie_node_cache_hits.clone()))?;
+
+        let trie_node_cache_misses =
+            IntCounter::new(TRIE_NODE_CACHE_MISSES_NAME, TRIE_NODE_CACHE_MISSES_HELP)?;
+        registry.register(Box::new(trie_node_cache_misses.clone()))?;
+
         Ok(Metrics {
             run_execute: utils::register_histogram_metric(
                 registry,
@@ -144,6 +224,12 @@ impl Metrics {
                 GET_BIDS_HELP,
                 common_buckets.clone(),
             )?,
+            get_unbonds: utils::register_histogram_metric(
+                registry,
+                GET_UNBONDS_NAME,
+                GET_UNBONDS_HELP,
+                common_buckets.clone(),
+            )?,
             get_trie: utils::register_histogram_metric(
                 registry,
                 GET_TRIE_NAME,
@@ -154,6 +240,12 @@ impl Metrics {
                 registry,
                 PUT_TRIE_NAME,
                 PUT_TRIE_HELP,
+                tiny_buckets.clone(),
+            )?,
+            checkout: utils::register_histogram_metric(
+                registry,
+                CHECKOUT_NAME,
+                CHECKOUT_HELP,
                 tiny_buckets,
             )?,
             exec_block: utils::register_histogram_metric(
@@ -164,9 +256,29 @@ impl Metrics {
             )?,
             latest_commit_step,
             exec_queue_size,
+            exec_cache_hits,
+            checkout_root_not_found,
+            trie_node_cache_hits,
+            trie_node_cache_misses,
             registry: registry.clone(),
         })
     }
+
+    /// Returns an observer recording checkouts of global state into these metrics.
+    pub(super) fn checkout_observer(&self) -> CheckoutMetrics {
+        CheckoutMetrics {
+            checkout: self.checkout.clone(),
+            checkout_root_not_found: self.checkout_root_not_found.clone(),
+        }
+    }
+
+    /// Returns an observer recording lookups in the trie node cache into these metrics.
+    pub(super) fn trie_node_cache_observer(&self) -> TrieNodeCacheMetrics {
+        TrieNodeCacheMetrics {
+            hits: self.trie_node_cache_hits.clone(),
+            misses: self.trie_node_cache_misses.clone(),
+        }
+    }
 }
 
 impl Drop for Metrics {
@@ -179,10 +291,16 @@ impl Drop for Metrics {
         unregister_metric!(self.registry, self.get_balance);
         unregister_metric!(self.registry, self.get_era_validators);
         unregister_metric!(self.registry, self.get_bids);
+        unregister_metric!(self.registry, self.get_unbonds);
         unregister_metric!(self.registry, self.put_trie);
         unregister_metric!(self.registry, self.get_trie);
         unregister_metric!(self.registry, self.exec_block);
         unregister_metric!(self.registry, self.latest_commit_step);
         unregister_metric!(self.registry, self.exec_queue_size);
+        unregister_metric!(self.registry, self.exec_cache_hits);
+        unregister_metric!(self.registry, self.checkout);
+        unregister_metric!(self.registry, self.checkout_root_not_found);
+        unregister_metric!(self.registry, self.trie_node_cache_hits);
+        unregister_metric!(self.registry, self.trie_node_cache_misses);
     }
 }
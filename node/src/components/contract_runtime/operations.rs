@@ -1,4 +1,10 @@
-use std::{cmp, collections::BTreeMap, ops::Range, sync::Arc, time::Instant};
+use std::{
+    cmp,
+    collections::BTreeMap,
+    ops::Range,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use itertools::Itertools;
 use tracing::{debug, error, info, trace, warn};
@@ -6,26 +12,33 @@ use tracing::{debug, error, info, trace, warn};
 use casper_execution_engine::{
     core::{
         engine_state::{
-            self, execution_result::ExecutionResults, step::EvictItem, ChecksumRegistry,
-            DeployItem, EngineState, ExecuteRequest, ExecutionResult as EngineExecutionResult,
-            GetEraValidatorsRequest, PruneConfig, PruneResult, RewardItem, StepError, StepRequest,
-            StepSuccess,
+            self,
+            execution_result::{ExecutionResults, PaymentSettlement},
+            step::EvictItem,
+            ChecksumRegistry, DeployItem, EngineState, ExecuteRequest,
+            ExecutionResult as EngineExecutionResult, GetEraValidatorsRequest, PruneConfig,
+            PruneResult, RewardItem, StepError, StepRequest, StepSuccess,
+            WASMLESS_TRANSFER_FIXED_GAS_PRICE,
         },
         execution,
     },
     shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
-    storage::global_state::{lmdb::LmdbGlobalState, CommitProvider, StateProvider},
+    storage::global_state::{
+        lmdb::LmdbGlobalState, scratch::ScratchGlobalState, CommitProvider, StateProvider,
+    },
 };
 use casper_hashing::Digest;
 use casper_types::{
-    CLValue, DeployHash, EraId, ExecutionResult, Key, ProtocolVersion, PublicKey, U512,
+    system::mint::ARG_AMOUNT, CLValue, DeployHash, EraId, ExecutionEffect, ExecutionResult, Key,
+    Phase, ProtocolVersion, PublicKey, TimeDiff, Timestamp, TransactionReceipt, U512,
 };
 
 use crate::{
     components::{
         consensus::EraReport,
         contract_runtime::{
-            error::BlockExecutionError, types::StepEffectAndUpcomingEraValidators,
+            error::BlockExecutionError,
+            types::{ReplayedBlock, StepEffectAndUpcomingEraValidators},
             BlockAndExecutionResults, ExecutionPreState, Metrics, SpeculativeExecutionState,
             APPROVALS_CHECKSUM_NAME, EXECUTION_RESULTS_CHECKSUM_NAME,
         },
@@ -33,7 +46,7 @@ use crate::{
     },
     types::{
         self, error::BlockCreationError, ApprovalsHashes, Block, Chunkable, Deploy, DeployHeader,
-        FinalizedBlock,
+        DeployId, FinalizedBlock,
     },
 };
 
@@ -87,34 +100,38 @@ fn calculate_prune_eras(
     Some(range.map(EraId::new).map(Key::EraInfo).collect())
 }
 
-/// Executes a finalized block.
-#[allow(clippy::too_many_arguments)]
-pub fn execute_finalized_block(
+/// The effects of executing the deploys of a finalized block, cached in scratch global state on top
+/// of the block's pre-state root.
+struct ScratchBlockExecution {
+    /// The engine state holding the cached effects.
+    scratch_state: EngineState<ScratchGlobalState>,
+    /// The state root hash the cached effects apply to.
+    state_root_hash: Digest,
+    /// The results from executing the deploys in the block.
+    execution_results: Vec<(types::DeployHash, DeployHeader, ExecutionResult)>,
+    /// How each deploy in the block pays for its execution, and how the engine settled that
+    /// payment.
+    deploy_payments: Vec<(DeployPayment, Option<PaymentSettlement>)>,
+    /// The IDs of the deploys in the block.
+    deploy_ids: Vec<DeployId>,
+    /// The time spent executing the deploys in the block.
+    execution_time: Duration,
+}
+
+/// Executes the deploys of a finalized block on top of `pre_state_root_hash` and writes the block's
+/// checksum registry, caching all effects in scratch global state rather than writing them to LMDB.
+fn execute_deploys_on_scratch(
     engine_state: &EngineState<LmdbGlobalState>,
     metrics: Option<Arc<Metrics>>,
     protocol_version: ProtocolVersion,
-    execution_pre_state: ExecutionPreState,
-    finalized_block: FinalizedBlock,
+    pre_state_root_hash: Digest,
+    finalized_block: &FinalizedBlock,
     deploys: Vec<Deploy>,
-    activation_point_era_id: EraId,
-    key_block_height_for_activation_point: u64,
-    prune_batch_size: u64,
-) -> Result<BlockAndExecutionResults, BlockExecutionError> {
-    if finalized_block.height() != execution_pre_state.next_block_height {
-        return Err(BlockExecutionError::WrongBlockHeight {
-            finalized_block: Box::new(finalized_block),
-            execution_pre_state: Box::new(execution_pre_state),
-        });
-    }
-    let ExecutionPreState {
-        pre_state_root_hash,
-        parent_hash,
-        parent_seed,
-        next_block_height: _,
-    } = execution_pre_state;
+) -> Result<ScratchBlockExecution, BlockExecutionError> {
     let mut state_root_hash = pre_state_root_hash;
     let mut execution_results: Vec<(_, DeployHeader, ExecutionResult)> =
         Vec::with_capacity(deploys.len());
+    let mut deploy_payments = Vec::with_capacity(deploys.len());
     // Run any deploys that must be executed
     let block_time = finalized_block.timestamp().millis();
     let start = Instant::now();
@@ -129,6 +146,7 @@ pub fn execute_finalized_block(
     for deploy in deploys {
         let deploy_hash = *deploy.hash();
         let deploy_header = deploy.header().clone();
+        let deploy_payment = DeployPayment::new(&deploy);
         let execute_request = ExecuteRequest::new(
             state_root_hash,
             block_time,
@@ -146,7 +164,7 @@ pub fn execute_finalized_block(
 
         trace!(?deploy_hash, ?result, "deploy execution result");
         // As for now a given state is expected to exist.
-        let (state_hash, execution_result) = commit_execution_results(
+        let (state_hash, execution_result, payment_settlement) = commit_execution_results(
             &scratch_state,
             metrics.clone(),
             state_root_hash,
@@ -154,6 +172,7 @@ pub fn execute_finalized_block(
             result,
         )?;
         execution_results.push((deploy_hash, deploy_header, execution_result));
+        deploy_payments.push((deploy_payment, payment_settlement));
         state_root_hash = state_hash;
     }
 
@@ -181,10 +200,62 @@ pub fn execute_finalized_block(
     );
     scratch_state.apply_effect(CorrelationId::new(), state_root_hash, effects)?;
 
+    let execution_time = start.elapsed();
     if let Some(metrics) = metrics.as_ref() {
-        metrics.exec_block.observe(start.elapsed().as_secs_f64());
+        metrics.exec_block.observe(execution_time.as_secs_f64());
     }
 
+    Ok(ScratchBlockExecution {
+        scratch_state,
+        state_root_hash,
+        execution_results,
+        deploy_payments,
+        deploy_ids,
+        execution_time,
+    })
+}
+
+/// Executes a finalized block.
+#[allow(clippy::too_many_arguments)]
+pub fn execute_finalized_block(
+    engine_state: &EngineState<LmdbGlobalState>,
+    metrics: Option<Arc<Metrics>>,
+    protocol_version: ProtocolVersion,
+    execution_pre_state: ExecutionPreState,
+    finalized_block: FinalizedBlock,
+    deploys: Vec<Deploy>,
+    activation_point_era_id: EraId,
+    key_block_height_for_activation_point: u64,
+    prune_batch_size: u64,
+) -> Result<BlockAndExecutionResults, BlockExecutionError> {
+    if finalized_block.height() != execution_pre_state.next_block_height {
+        return Err(BlockExecutionError::WrongBlockHeight {
+            finalized_block: Box::new(finalized_block),
+            execution_pre_state: Box::new(execution_pre_state),
+        });
+    }
+    let ExecutionPreState {
+        pre_state_root_hash,
+        parent_hash,
+        parent_seed,
+        next_block_height: _,
+    } = execution_pre_state;
+    let ScratchBlockExecution {
+        scratch_state,
+        mut state_root_hash,
+        execution_results,
+        deploy_payments,
+        deploy_ids,
+        execution_time,
+    } = execute_deploys_on_scratch(
+        engine_state,
+        metrics.clone(),
+        protocol_version,
+        pre_state_root_hash,
+        &finalized_block,
+        deploys,
+    )?;
+
     // If the finalized block has an era report, run the auction contract and get the upcoming era
     // validators.
     let maybe_step_effect_and_upcoming_era_validators =
@@ -215,7 +286,7 @@ pub fn execute_finalized_block(
                 GetEraValidatorsRequest::new(state_root_hash, protocol_version),
             )?;
             Some(StepEffectAndUpcomingEraValidators {
-                step_execution_journal,
+                step_effect: ExecutionEffect::from(&step_execution_journal),
                 upcoming_era_validators,
             })
         } else {
@@ -315,6 +386,21 @@ pub fn execute_finalized_block(
         protocol_version,
     )?);
 
+    let transaction_receipts = execution_results
+        .iter()
+        .zip(deploy_payments)
+        .map(
+            |((deploy_hash, _, execution_result), (deploy_payment, payment_settlement))| {
+                deploy_payment.into_receipt(
+                    &block,
+                    (*deploy_hash).into(),
+                    execution_result,
+                    payment_settlement,
+                )
+            },
+        )
+        .collect();
+
     let approvals_hashes = deploy_ids
         .into_iter()
         .map(|id| id.destructure().1)
@@ -331,10 +417,196 @@ pub fn execute_finalized_block(
         block,
         approvals_hashes,
         execution_results,
+        execution_time: TimeDiff::from(execution_time),
+        transaction_receipts,
         maybe_step_effect_and_upcoming_era_validators,
     })
 }
 
+/// Replays a finalized block which has already been executed and stored, without writing anything
+/// to LMDB.
+///
+/// The block is executed as by [`execute_finalized_block`], but its effects, including the step and
+/// the pruning of old era info, are only cached in scratch global state, and its post-state root
+/// hash is computed in memory.  Global state is thus left exactly as it was.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn replay_finalized_block(
+    engine_state: &EngineState<LmdbGlobalState>,
+    metrics: Option<Arc<Metrics>>,
+    protocol_version: ProtocolVersion,
+    execution_pre_state: ExecutionPreState,
+    finalized_block: FinalizedBlock,
+    deploys: Vec<Deploy>,
+    activation_point_era_id: EraId,
+    key_block_height_for_activation_point: u64,
+    prune_batch_size: u64,
+) -> Result<ReplayedBlock, BlockExecutionError> {
+    if finalized_block.height() != execution_pre_state.next_block_height {
+        return Err(BlockExecutionError::WrongBlockHeight {
+            finalized_block: Box::new(finalized_block),
+            execution_pre_state: Box::new(execution_pre_state),
+        });
+    }
+    let ExecutionPreState {
+        pre_state_root_hash,
+        parent_hash,
+        parent_seed,
+        next_block_height: _,
+    } = execution_pre_state;
+    let ScratchBlockExecution {
+        scratch_state,
+        state_root_hash,
+        execution_results,
+        ..
+    } = execute_deploys_on_scratch(
+        engine_state,
+        metrics.clone(),
+        protocol_version,
+        pre_state_root_hash,
+        &finalized_block,
+        deploys,
+    )?;
+
+    let next_era_validator_weights = match finalized_block.era_report() {
+        Some(era_report) => {
+            commit_step(
+                &scratch_state,
+                metrics,
+                protocol_version,
+                state_root_hash,
+                era_report,
+                finalized_block.timestamp().millis(),
+                finalized_block.era_id().successor(),
+            )?;
+            // Reads of scratch global state see the effects cached on top of the pre-state root.
+            let upcoming_era_validators = scratch_state.get_era_validators(
+                CorrelationId::new(),
+                None,
+                GetEraValidatorsRequest::new(state_root_hash, protocol_version),
+            )?;
+            upcoming_era_validators
+                .get(&finalized_block.era_id().successor())
+                .cloned()
+        }
+        None => None,
+    };
+
+    if let Some(previous_block_height) = finalized_block.height().checked_sub(1) {
+        if let Some(keys_to_prune) = calculate_prune_eras(
+            activation_point_era_id,
+            key_block_height_for_activation_point,
+            previous_block_height,
+            prune_batch_size,
+        ) {
+            let prune_config = PruneConfig::new(state_root_hash, keys_to_prune);
+            match scratch_state.prune_cached(CorrelationId::new(), prune_config)? {
+                PruneResult::RootNotFound => {
+                    return Err(engine_state::Error::RootNotFound(state_root_hash).into());
+                }
+                PruneResult::DoesNotExist => {
+                    warn!(
+                        previous_block_height,
+                        %state_root_hash,
+                        "replay prune: key does not exist"
+                    );
+                }
+                PruneResult::Success { .. } => {}
+            }
+        }
+    }
+
+    let state_root_hash = engine_state
+        .compute_scratch_post_state_hash(state_root_hash, scratch_state.into_inner())?;
+    let block = Block::new(
+        parent_hash,
+        parent_seed,
+        state_root_hash,
+        finalized_block,
+        next_era_validator_weights,
+        protocol_version,
+    )?;
+    Ok(ReplayedBlock {
+        block,
+        execution_results,
+    })
+}
+
+/// How a deploy pays for its execution.
+#[derive(Clone, Copy)]
+enum DeployPayment {
+    /// A native transfer, paying exactly its cost at a fixed gas price.
+    NativeTransfer,
+    /// Standard payment of the given amount at the given gas price.
+    Standard { amount: U512, gas_price: u64 },
+    /// Custom payment code, making an unknown amount available at the given gas price.
+    Custom { gas_price: u64 },
+}
+
+impl DeployPayment {
+    fn new(deploy: &Deploy) -> Self {
+        let gas_price = deploy.header().gas_price();
+        if deploy.session().is_transfer() {
+            return DeployPayment::NativeTransfer;
+        }
+        if !deploy.payment().is_standard_payment(Phase::Payment) {
+            return DeployPayment::Custom { gas_price };
+        }
+        match deploy
+            .payment()
+            .args()
+            .get(ARG_AMOUNT)
+            .and_then(|amount| amount.clone().into_t::<U512>().ok())
+        {
+            Some(amount) => DeployPayment::Standard { amount, gas_price },
+            None => DeployPayment::Custom { gas_price },
+        }
+    }
+
+    /// Creates the receipt of the deploy's execution, with the fee and refund reported by the
+    /// engine.
+    ///
+    /// The engine settles the payment of every deploy it charges for, including those whose
+    /// payment code fails.  A deploy it doesn't charge for, such as one failing a precondition, is
+    /// rejected before payment is taken: it costs nothing and leaves no effect behind.
+    fn into_receipt(
+        self,
+        block: &Block,
+        deploy_hash: DeployHash,
+        execution_result: &ExecutionResult,
+        payment_settlement: Option<PaymentSettlement>,
+    ) -> TransactionReceipt {
+        let (gas_price, payment_amount) = match self {
+            DeployPayment::NativeTransfer => {
+                let gas_consumed = match execution_result {
+                    ExecutionResult::Failure { cost, .. }
+                    | ExecutionResult::Success { cost, .. } => *cost,
+                };
+                (
+                    WASMLESS_TRANSFER_FIXED_GAS_PRICE,
+                    Some(gas_consumed * WASMLESS_TRANSFER_FIXED_GAS_PRICE),
+                )
+            }
+            DeployPayment::Standard { amount, gas_price } => (gas_price, Some(amount)),
+            DeployPayment::Custom { gas_price } => (gas_price, None),
+        };
+        let (fee, refund) = match payment_settlement {
+            Some(PaymentSettlement { fee, refund }) => (fee.value(), refund.value()),
+            None => (U512::zero(), U512::zero()),
+        };
+        TransactionReceipt {
+            deploy_hash,
+            block_hash: block.hash().inner().value(),
+            block_height: block.height(),
+            era_id: block.header().era_id(),
+            execution_result: execution_result.clone(),
+            gas_price,
+            payment_amount,
+            fee: Some(fee),
+            refund: Some(refund),
+        }
+    }
+}
+
 /// Commits the execution results.
 fn commit_execution_results<S>(
     engine_state: &EngineState<S>,
@@ -342,7 +614,7 @@ fn commit_execution_results<S>(
     state_root_hash: Digest,
     deploy_hash: DeployHash,
     execution_results: ExecutionResults,
-) -> Result<(Digest, ExecutionResult), BlockExecutionError>
+) -> Result<(Digest, ExecutionResult, Option<PaymentSettlement>), BlockExecutionError>
 where
     S: StateProvider + CommitProvider,
     S::Error: Into<execution::Error>,
@@ -351,7 +623,15 @@ where
         .into_iter()
         .exactly_one()
         .map_err(|_| BlockExecutionError::MoreThanOneExecutionResult)?;
-    let json_execution_result = ExecutionResult::from(&ee_execution_result);
+    let mut json_execution_result = ExecutionResult::from(&ee_execution_result);
+    // Recording error codes changes the execution results checksum, so it must be enabled by the
+    // chainspec rather than depend on the node version.
+    if let ExecutionResult::Failure { error_code, .. } = &mut json_execution_result {
+        if !engine_state.config().record_execution_error_codes() {
+            *error_code = None;
+        }
+    }
+    let payment_settlement = ee_execution_result.payment_settlement();
 
     let execution_effect: AdditiveMap<Key, Transform> = match ee_execution_result {
         EngineExecutionResult::Success {
@@ -380,7 +660,7 @@ where
     .into();
     let new_state_root =
         commit_transforms(engine_state, metrics, state_root_hash, execution_effect)?;
-    Ok((new_state_root, json_execution_result))
+    Ok((new_state_root, json_execution_result, payment_settlement))
 }
 
 fn commit_transforms<S>(
@@ -419,9 +699,15 @@ where
 {
     let SpeculativeExecutionState {
         state_root_hash,
+        block_height: _,
         block_time,
         protocol_version,
     } = execution_state;
+    if let Some(precheck_failure) =
+        precheck_speculative_deploy(engine_state, protocol_version, state_root_hash, &deploy)?
+    {
+        return Ok(Some(precheck_failure));
+    }
     let deploy_hash = deploy.deploy_hash;
     let execute_request = ExecuteRequest::new(
         state_root_hash,
@@ -449,6 +735,125 @@ where
     })
 }
 
+/// Runs the entry point precheck on a speculatively executed deploy.
+///
+/// Unlike a deploy executed in a block, which is charged if it fails the precheck, a speculatively
+/// executed one is rejected at no cost.  Returns the resulting failure if the deploy is rejected.
+fn precheck_speculative_deploy<S>(
+    engine_state: &EngineState<S>,
+    protocol_version: ProtocolVersion,
+    state_root_hash: Digest,
+    deploy: &DeployItem,
+) -> Result<Option<ExecutionResult>, engine_state::Error>
+where
+    S: StateProvider + CommitProvider,
+    S::Error: Into<execution::Error>,
+{
+    match engine_state.precheck_deploy(
+        CorrelationId::new(),
+        protocol_version,
+        state_root_hash,
+        deploy,
+    ) {
+        Ok(()) => Ok(None),
+        Err(engine_state::Error::RootNotFound(root_hash)) => {
+            Err(engine_state::Error::RootNotFound(root_hash))
+        }
+        Err(error) => Ok(Some(ExecutionResult::from(
+            &EngineExecutionResult::precondition_failure(error),
+        ))),
+    }
+}
+
+/// Execute the sequence of transactions without commiting the effects, each seeing the effects of
+/// those before it.
+/// Intended to be used for simulating multi-step flows on read-only nodes.
+///
+/// Returns the execution results in the order of the given transactions.
+pub fn execute_bundle_only(
+    engine_state: &EngineState<LmdbGlobalState>,
+    execution_state: SpeculativeExecutionState,
+    deploys: Vec<DeployItem>,
+) -> Result<Option<Vec<ExecutionResult>>, engine_state::Error> {
+    let SpeculativeExecutionState {
+        mut state_root_hash,
+        block_height: _,
+        block_time,
+        protocol_version,
+    } = execution_state;
+
+    // Create a new EngineState that reads from LMDB but only caches changes in memory.  It is
+    // dropped once the bundle has been executed, discarding all the cached changes.
+    let scratch_state = engine_state.get_scratch_engine_state();
+
+    let mut execution_results = Vec::with_capacity(deploys.len());
+    for deploy in deploys {
+        if let Some(precheck_failure) =
+            precheck_speculative_deploy(&scratch_state, protocol_version, state_root_hash, &deploy)?
+        {
+            execution_results.push(precheck_failure);
+            continue;
+        }
+        let deploy_hash = deploy.deploy_hash;
+        let execute_request = ExecuteRequest::new(
+            state_root_hash,
+            block_time.millis(),
+            vec![deploy],
+            protocol_version,
+            PublicKey::System,
+        );
+        let ee_execution_result = match execute(&scratch_state, None, execute_request)?
+            .into_iter()
+            .exactly_one()
+        {
+            Ok(ee_execution_result) => ee_execution_result,
+            Err(results) => {
+                warn!(
+                    ?deploy_hash,
+                    "got {} execution results from a single transaction",
+                    results.count()
+                );
+                return Ok(None);
+            }
+        };
+        execution_results.push(ExecutionResult::from(&ee_execution_result));
+
+        let execution_effect: AdditiveMap<Key, Transform> =
+            ee_execution_result.execution_journal().clone().into();
+        state_root_hash =
+            commit_transforms(&scratch_state, None, state_root_hash, execution_effect)?;
+    }
+    Ok(Some(execution_results))
+}
+
+/// Executes a single deploy on top of the given state root as it would be executed in a block with
+/// the given timestamp and proposer, without committing the effects.
+pub(crate) fn execute_deploy<S>(
+    engine_state: &EngineState<S>,
+    protocol_version: ProtocolVersion,
+    state_root_hash: Digest,
+    block_time: Timestamp,
+    proposer: PublicKey,
+    deploy: Deploy,
+) -> Result<ExecutionResult, BlockExecutionError>
+where
+    S: StateProvider + CommitProvider,
+    S::Error: Into<execution::Error>,
+{
+    let execute_request = ExecuteRequest::new(
+        state_root_hash,
+        block_time.millis(),
+        vec![DeployItem::from(deploy)],
+        protocol_version,
+        proposer,
+    );
+    let ee_execution_result = execute(engine_state, None, execute_request)?
+        .into_iter()
+        .exactly_one()
+        .map_err(|_| BlockExecutionError::MoreThanOneExecutionResult)?;
+    Ok(ExecutionResult::from(&ee_execution_result))
+}
+
 fn execute<S>(
     engine_state: &EngineState<S>,
     metrics: Option<Arc<Metrics>>,
@@ -540,8 +945,117 @@ fn compute_execution_results_checksum(
 
 #[cfg(test)]
 mod tests {
+    use casper_types::{testing::TestRng, Gas, Motes};
+
     use super::*;
 
+    #[test]
+    fn should_take_fee_and_refund_for_receipt_from_engine() {
+        let mut rng = TestRng::new();
+        let block = Block::random(&mut rng);
+        let deploy_hash = DeployHash::new([1; 32]);
+        let execution_result = ExecutionResult::Success {
+            effect: Default::default(),
+            transfers: vec![],
+            cost: U512::from(100),
+        };
+        let payment_settlement = PaymentSettlement {
+            fee: Motes::new(U512::from(734)),
+            refund: Motes::new(U512::from(266)),
+        };
+        let payment = DeployPayment::Standard {
+            amount: U512::from(1_000),
+            gas_price: 2,
+        };
+
+        let receipt = payment.into_receipt(
+            &block,
+            deploy_hash,
+            &execution_result,
+            Some(payment_settlement),
+        );
+        assert_eq!(receipt.block_hash, block.hash().inner().value());
+        assert_eq!(receipt.block_height, block.height());
+        assert_eq!(receipt.gas_consumed(), U512::from(100));
+        assert_eq!(receipt.payment_amount, Some(U512::from(1_000)));
+        assert_eq!(receipt.fee, Some(U512::from(734)));
+        assert_eq!(receipt.refund, Some(U512::from(266)));
+
+        // The fee is known even if the payment amount was set by custom payment code.
+        let receipt = DeployPayment::Custom { gas_price: 2 }.into_receipt(
+            &block,
+            deploy_hash,
+            &execution_result,
+            Some(payment_settlement),
+        );
+        assert_eq!(receipt.payment_amount, None);
+        assert_eq!(receipt.fee, Some(U512::from(734)));
+        assert_eq!(receipt.refund, Some(U512::from(266)));
+    }
+
+    #[test]
+    fn should_charge_max_payment_for_payment_code_failure() {
+        let mut rng = TestRng::new();
+        let block = Block::random(&mut rng);
+        let deploy_hash = DeployHash::new([1; 32]);
+        // The engine charges the whole of the maximum payment cost for a payment code error and
+        // refunds nothing, whatever the payment amount.
+        let max_payment_cost = Motes::new(U512::from(2_500_000_000u64));
+        let ee_execution_result = EngineExecutionResult::new_payment_code_error(
+            engine_state::Error::InsufficientPayment,
+            max_payment_cost,
+            Motes::new(U512::from(10_000_000_000u64)),
+            Gas::new(U512::from(2_500_000_000u64)),
+            Key::Balance([1; 32]),
+            Key::Balance([2; 32]),
+        )
+        .expect("should create payment code error");
+        let execution_result = ExecutionResult::from(&ee_execution_result);
+        let payment = DeployPayment::Standard {
+            amount: U512::from(1_000),
+            gas_price: 1,
+        };
+
+        let receipt = payment.into_receipt(
+            &block,
+            deploy_hash,
+            &execution_result,
+            ee_execution_result.payment_settlement(),
+        );
+        assert!(!receipt.is_success());
+        assert_eq!(receipt.fee, Some(max_payment_cost.value()));
+        assert_eq!(receipt.refund, Some(U512::zero()));
+    }
+
+    #[test]
+    fn should_not_charge_fee_for_deploy_failing_precondition() {
+        let mut rng = TestRng::new();
+        let block = Block::random(&mut rng);
+        let deploy_hash = DeployHash::new([1; 32]);
+        let ee_execution_result =
+            EngineExecutionResult::precondition_failure(engine_state::Error::InsufficientPayment);
+        let execution_result = ExecutionResult::from(&ee_execution_result);
+        assert_eq!(ee_execution_result.payment_settlement(), None);
+
+        let payment = DeployPayment::Standard {
+            amount: U512::from(1_000),
+            gas_price: 2,
+        };
+        let receipt = payment.into_receipt(&block, deploy_hash, &execution_result, None);
+        assert_eq!(receipt.payment_amount, Some(U512::from(1_000)));
+        assert_eq!(receipt.fee, Some(U512::zero()));
+        assert_eq!(receipt.refund, Some(U512::zero()));
+
+        let receipt = DeployPayment::Custom { gas_price: 2 }.into_receipt(
+            &block,
+            deploy_hash,
+            &execution_result,
+            None,
+        );
+        assert_eq!(receipt.fee, Some(U512::zero()));
+        assert_eq!(receipt.refund, Some(U512::zero()));
+    }
+
     #[test]
     fn calculation_is_safe_with_invalid_input() {
         assert_eq!(calculate_prune_eras(EraId::new(0), 0, 0, 0,), None);
@@ -0,0 +1,127 @@
+//! A bounded pool of blocking threads serving read-only queries of global state.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use datasize::DataSize;
+use tokio::sync::Semaphore;
+
+use casper_execution_engine::core::engine_state;
+
+use super::QueryOrigin;
+
+/// Runs read-only queries of global state (e.g. queries, balances and era validators) on blocking
+/// threads, separately from block execution.
+///
+/// Queries are served from committed state roots, so they can safely run concurrently with block
+/// execution.  At most `max_parallel_queries` run at once, and once `max_queued_queries` further
+/// queries are waiting for a free slot, new queries made for clients are rejected rather than
+/// queued, so that a burst of heavy RPC reads can neither starve block execution nor grow without
+/// bound.  Queries made by the node itself are never rejected, so that client load can't make the
+/// node misreport e.g. an account as missing while validating deploys.
+#[derive(Debug, DataSize)]
+pub(super) struct QueryPool {
+    #[data_size(skip)]
+    semaphore: Semaphore,
+    max_queued_queries: usize,
+    #[data_size(skip)]
+    queued: AtomicUsize,
+}
+
+/// Decrements the count of queued queries when dropped, including if the waiting future is.
+struct QueuedGuard<'a>(&'a AtomicUsize);
+
+impl Drop for QueuedGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl QueryPool {
+    pub(super) fn new(max_parallel_queries: usize, max_queued_queries: usize) -> Self {
+        QueryPool {
+            semaphore: Semaphore::new(max_parallel_queries),
+            max_queued_queries,
+            queued: AtomicUsize::new(0),
+        }
+    }
+
+    /// Runs `query` once a slot in the pool is free, or returns
+    /// [`engine_state::Error::QueryQueueFull`] if it is made for a client and too many queries are
+    /// already waiting.
+    pub(super) async fn run<T, V>(
+        &self,
+        origin: QueryOrigin,
+        query: T,
+    ) -> Result<V, engine_state::Error>
+    where
+        T: 'static + Send + FnOnce() -> V,
+        V: 'static + Send,
+    {
+        let queued_guard = QueuedGuard(&self.queued);
+        if self.queued.fetch_add(1, Ordering::SeqCst) >= self.max_queued_queries
+            && origin == QueryOrigin::Client
+        {
+            return Err(engine_state::Error::QueryQueueFull {
+                max_queued_queries: self.max_queued_queries,
+            });
+        }
+        // This will never panic since the semaphore is never closed.
+        let _permit = self.semaphore.acquire().await.unwrap();
+        drop(queued_guard);
+        Ok(tokio::task::spawn_blocking(query)
+            .await
+            .expect("query panicked"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{mpsc, Arc};
+
+    use futures::FutureExt;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_reject_client_queries_once_queue_is_full() {
+        let pool = Arc::new(QueryPool::new(1, 1));
+        let (release_sender, release_receiver) = mpsc::channel::<()>();
+        let (started_sender, started_receiver) = mpsc::channel::<()>();
+
+        // Occupy the only slot until released.
+        let running = tokio::spawn({
+            let pool = Arc::clone(&pool);
+            async move {
+                pool.run(QueryOrigin::Client, move || {
+                    started_sender.send(()).unwrap();
+                    release_receiver.recv().unwrap();
+                    1
+                })
+                .await
+            }
+        });
+        tokio::task::spawn_blocking(move || started_receiver.recv().unwrap())
+            .await
+            .unwrap();
+
+        // The second query waits for the slot, and the third is rejected.
+        let mut queued = Box::pin(pool.run(QueryOrigin::Client, || 2));
+        assert!((&mut queued).now_or_never().is_none());
+        assert!(matches!(
+            pool.run(QueryOrigin::Client, || 3).await,
+            Err(engine_state::Error::QueryQueueFull {
+                max_queued_queries: 1
+            })
+        ));
+
+        // Queries made by the node wait for the slot however many queries are queued.
+        let mut queued_for_node = Box::pin(pool.run(QueryOrigin::Node, || 4));
+        assert!((&mut queued_for_node).now_or_never().is_none());
+
+        release_sender.send(()).unwrap();
+        assert_eq!(running.await.unwrap().unwrap(), 1);
+        assert_eq!(queued.await.unwrap(), 2);
+        assert_eq!(queued_for_node.await.unwrap(), 4);
+        assert_eq!(pool.run(QueryOrigin::Client, || 5).await.unwrap(), 5);
+    }
+}
@@ -7,7 +7,9 @@ use serde::Serialize;
 use tempfile::TempDir;
 
 use casper_execution_engine::core::engine_state::ExecutableDeployItem;
-use casper_types::{runtime_args, EraId, PublicKey, RuntimeArgs, SecretKey, TimeDiff, U512};
+use casper_types::{
+    runtime_args, EraId, ExecutionResult, PublicKey, RuntimeArgs, SecretKey, TimeDiff, U512,
+};
 
 use super::*;
 use crate::{
@@ -105,27 +107,8 @@ impl reactor::Reactor for Reactor {
         )
         .unwrap();
 
-        let contract_runtime = ContractRuntime::new(
-            chainspec.protocol_version(),
-            storage.root_path(),
-            &config,
-            chainspec.wasm_config,
-            chainspec.system_costs_config,
-            chainspec.core_config.max_associated_keys,
-            chainspec.core_config.max_runtime_call_stack_height,
-            chainspec.core_config.minimum_delegation_amount,
-            chainspec.protocol_config.activation_point,
-            chainspec.core_config.prune_batch_size,
-            chainspec.core_config.strict_argument_checking,
-            chainspec.core_config.vesting_schedule_period.millis(),
-            Some(chainspec.core_config.max_delegators_per_validator),
-            registry,
-            chainspec.core_config.administrators.clone(),
-            chainspec.core_config.allow_auction_bids,
-            chainspec.core_config.allow_unrestricted_transfers,
-            chainspec.core_config.refund_handling,
-            chainspec.core_config.fee_handling,
-        )?;
+        let contract_runtime =
+            ContractRuntime::from_chainspec(&storage, &config, &chainspec, registry)?;
 
         let reactor = Reactor {
             storage,
@@ -392,3 +375,125 @@ async fn should_not_set_shared_pre_state_to_lower_block_height() {
         next_block_height
     );
 }
+
+#[tokio::test]
+async fn should_execute_bundle_on_top_of_effects_of_earlier_deploys() {
+    testing::init_logging();
+
+    let config = Config {
+        max_global_state_size: Some(100 * 1024 * 1024),
+        ..Config::default()
+    };
+    let (chainspec, chainspec_raw_bytes) =
+        <(Chainspec, ChainspecRawBytes)>::from_resources("local");
+    let chainspec = Arc::new(chainspec);
+    let chainspec_raw_bytes = Arc::new(chainspec_raw_bytes);
+
+    let mut rng = crate::new_rng();
+    let rng = &mut rng;
+
+    let runner: Runner<ConditionCheckReactor<Reactor>> = Runner::new(
+        config,
+        Arc::clone(&chainspec),
+        Arc::clone(&chainspec_raw_bytes),
+        rng,
+    )
+    .await
+    .unwrap();
+    let contract_runtime = &runner.reactor().inner().contract_runtime;
+    let post_commit_genesis_state_hash = contract_runtime
+        .commit_genesis(chainspec.as_ref(), chainspec_raw_bytes.as_ref())
+        .unwrap()
+        .post_state_hash;
+
+    let node_1_secret_key = SecretKey::from_file(
+        RESOURCES_PATH
+            .join("local")
+            .join("secret_keys")
+            .join("node-1.pem"),
+    )
+    .unwrap();
+    let new_account_secret_key = SecretKey::random(rng);
+    let timestamp = Timestamp::now();
+    let transfer_cost = U512::from(chainspec.system_costs_config.wasmless_transfer_cost());
+    let transfer_amount = U512::from(chainspec.deploy_config.native_transfer_minimum_motes);
+    let transfer = |secret_key: &SecretKey, target: PublicKey, amount: U512| {
+        let payment = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::new(),
+            args: runtime_args! {
+              "amount" => transfer_cost,
+            },
+        };
+        let session = ExecutableDeployItem::Transfer {
+            args: runtime_args! {
+              "amount" => amount,
+              "target" => target,
+              "id" => Some(9_u64),
+            },
+        };
+        Deploy::new(
+            timestamp,
+            TimeDiff::from_seconds(100),
+            1,
+            vec![],
+            chainspec.network_config.name.clone(),
+            payment,
+            session,
+            secret_key,
+            None,
+        )
+    };
+
+    // The first deploy creates and funds a new account, which the second deploy spends from.
+    let fund_new_account = transfer(
+        &node_1_secret_key,
+        PublicKey::from(&new_account_secret_key),
+        transfer_amount * 2 + transfer_cost,
+    );
+    let spend_from_new_account = transfer(
+        &new_account_secret_key,
+        PublicKey::random(rng),
+        transfer_amount,
+    );
+
+    let execute_bundle = |deploys: &[&Deploy]| {
+        let execution_prestate = SpeculativeExecutionState {
+            state_root_hash: post_commit_genesis_state_hash,
+            block_height: 0,
+            block_time: timestamp,
+            protocol_version: chainspec.protocol_version(),
+        };
+        let deploy_items = deploys
+            .iter()
+            .map(|deploy| DeployItem::from((*deploy).clone()))
+            .collect();
+        execute_bundle_only(
+            contract_runtime.engine_state(),
+            execution_prestate,
+            deploy_items,
+        )
+        .unwrap()
+        .expect("should have a single execution result per deploy")
+    };
+
+    // On its own, the second deploy fails, as the new account doesn't exist.
+    let execution_results = execute_bundle(&[&spend_from_new_account]);
+    assert!(matches!(
+        execution_results[..],
+        [ExecutionResult::Failure { .. }]
+    ));
+
+    // After the first deploy, the second deploy succeeds.
+    let execution_results = execute_bundle(&[&fund_new_account, &spend_from_new_account]);
+    assert!(
+        matches!(
+            execution_results[..],
+            [
+                ExecutionResult::Success { .. },
+                ExecutionResult::Success { .. }
+            ]
+        ),
+        "{:?}",
+        execution_results
+    );
+}
@@ -1,14 +1,19 @@
 use std::{collections::BTreeMap, sync::Arc};
 
 use datasize::DataSize;
+use serde::{Deserialize, Serialize};
 
-use casper_execution_engine::{
-    core::engine_state::GetEraValidatorsRequest, shared::execution_journal::ExecutionJournal,
-};
+use casper_execution_engine::core::engine_state::GetEraValidatorsRequest;
 use casper_hashing::Digest;
-use casper_types::{EraId, ExecutionResult, ProtocolVersion, PublicKey, U512};
+#[cfg(test)]
+use casper_types::testing::TestRng;
+use casper_types::{
+    EraId, ExecutionEffect, ExecutionResult, ProtocolVersion, PublicKey, TimeDiff,
+    TransactionReceipt, U512,
+};
 
-use crate::types::{ApprovalsHashes, Block, DeployHash, DeployHeader};
+use super::ExecutionPreState;
+use crate::types::{ApprovalsHashes, Block, BlockHash, DeployHash, DeployHeader, FinalizedBlock};
 
 /// Request for validator weights for a specific era.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -84,18 +89,28 @@ impl From<EraValidatorsRequest> for GetEraValidatorsRequest {
 }
 
 /// Effects from running step and the next era validators that are gathered when an era ends.
-#[derive(Clone, Debug, DataSize)]
+#[derive(Clone, Debug, DataSize, Serialize, Deserialize)]
 pub(crate) struct StepEffectAndUpcomingEraValidators {
     /// Validator sets for all upcoming eras that have already been determined.
     pub(crate) upcoming_era_validators: BTreeMap<EraId, BTreeMap<PublicKey, U512>>,
-    /// An [`ExecutionJournal`] created by an era ending.
-    pub(crate) step_execution_journal: ExecutionJournal,
+    /// The [`ExecutionEffect`] of the step run by an era ending.
+    pub(crate) step_effect: ExecutionEffect,
+}
+
+/// A stored [`Block`] replayed by the `ContractRuntime` without committing its effects, along with
+/// the results of executing its deploys.
+#[derive(Clone, Debug)]
+pub(crate) struct ReplayedBlock {
+    /// The [`Block`] produced by the replay.
+    pub(crate) block: Block,
+    /// The results from executing the deploys in the block.
+    pub(crate) execution_results: Vec<(DeployHash, DeployHeader, ExecutionResult)>,
 }
 
 #[doc(hidden)]
 /// A [`Block`] that was the result of execution in the `ContractRuntime` along with any execution
 /// effects it may have.
-#[derive(Clone, Debug, DataSize)]
+#[derive(Clone, Debug, DataSize, Serialize, Deserialize)]
 pub struct BlockAndExecutionResults {
     /// The [`Block`] the contract runtime executed.
     pub(crate) block: Arc<Block>,
@@ -103,7 +118,115 @@ pub struct BlockAndExecutionResults {
     pub(crate) approvals_hashes: Box<ApprovalsHashes>,
     /// The results from executing the deploys in the block.
     pub(crate) execution_results: Vec<(DeployHash, DeployHeader, ExecutionResult)>,
-    /// The [`ExecutionJournal`] and the upcoming validator sets determined by the `step`
+    /// The time spent executing the deploys in the block.
+    pub(crate) execution_time: TimeDiff,
+    /// The receipts of the deploys in the block.
+    pub(crate) transaction_receipts: Vec<TransactionReceipt>,
+    /// The [`ExecutionEffect`] and the upcoming validator sets determined by the `step`
     pub(crate) maybe_step_effect_and_upcoming_era_validators:
         Option<StepEffectAndUpcomingEraValidators>,
 }
+
+/// The outcome of executing a finalized block on top of a given pre-state, kept in memory and
+/// persisted in storage so that the block needn't be executed again if it is enqueued again, even
+/// after a restart.
+#[derive(Clone, Debug, DataSize, Serialize, Deserialize)]
+pub(crate) struct ExecutionArtifacts {
+    /// The pre-state root hash the block was executed on top of.
+    pre_state_root_hash: Digest,
+    /// The hash of the parent of the executed block.
+    parent_hash: BlockHash,
+    /// The finalized block which was executed.
+    finalized_block: FinalizedBlock,
+    /// The outcome of executing the block.
+    block_and_execution_results: BlockAndExecutionResults,
+}
+
+impl ExecutionArtifacts {
+    pub(crate) fn new(
+        execution_pre_state: &ExecutionPreState,
+        finalized_block: FinalizedBlock,
+        block_and_execution_results: BlockAndExecutionResults,
+    ) -> Self {
+        ExecutionArtifacts {
+            pre_state_root_hash: execution_pre_state.pre_state_root_hash,
+            parent_hash: execution_pre_state.parent_hash,
+            finalized_block,
+            block_and_execution_results,
+        }
+    }
+
+    /// Returns `true` if these are the artifacts of executing `finalized_block` on top of
+    /// `execution_pre_state`.
+    pub(crate) fn is_execution_of(
+        &self,
+        execution_pre_state: &ExecutionPreState,
+        finalized_block: &FinalizedBlock,
+    ) -> bool {
+        self.pre_state_root_hash == execution_pre_state.pre_state_root_hash
+            && self.parent_hash == execution_pre_state.parent_hash
+            && self.finalized_block == *finalized_block
+    }
+
+    /// Returns the height of the executed block.
+    pub(crate) fn block_height(&self) -> u64 {
+        self.finalized_block.height()
+    }
+
+    /// Returns the outcome of executing the block.
+    pub(crate) fn block_and_execution_results(&self) -> &BlockAndExecutionResults {
+        &self.block_and_execution_results
+    }
+
+    /// Returns the outcome of executing the block.
+    pub(crate) fn into_block_and_execution_results(self) -> BlockAndExecutionResults {
+        self.block_and_execution_results
+    }
+
+    /// Returns the artifacts of executing a random block at the given height, along with the
+    /// pre-state it was executed on top of.
+    #[cfg(test)]
+    pub(crate) fn random(rng: &mut TestRng, height: u64) -> (ExecutionPreState, Self) {
+        use casper_execution_engine::storage::trie::merkle_proof::TrieMerkleProof;
+        use casper_types::{CLValue, Key, StoredValue};
+
+        let block = Block::random_with_specifics(
+            rng,
+            EraId::new(1),
+            height,
+            ProtocolVersion::V1_0_0,
+            false,
+            None,
+        );
+        let execution_pre_state = ExecutionPreState::new(
+            block.height(),
+            Digest::hash(block.hash().inner()),
+            *block.header().parent_hash(),
+            block.header().accumulated_seed(),
+        );
+        let finalized_block = FinalizedBlock::from(block.clone());
+        let approvals_hashes = ApprovalsHashes::new(
+            block.hash(),
+            vec![],
+            TrieMerkleProof::new(
+                Key::ChecksumRegistry,
+                StoredValue::CLValue(CLValue::from_t(()).unwrap()),
+                Default::default(),
+            ),
+        );
+        let block_and_execution_results = BlockAndExecutionResults {
+            block: Arc::new(block),
+            approvals_hashes: Box::new(approvals_hashes),
+            execution_results: vec![],
+            execution_time: TimeDiff::from_millis(0),
+            transaction_receipts: vec![],
+            maybe_step_effect_and_upcoming_era_validators: None,
+        };
+        let execution_artifacts = ExecutionArtifacts::new(
+            &execution_pre_state,
+            finalized_block,
+            block_and_execution_results,
+        );
+        (execution_pre_state, execution_artifacts)
+    }
+}
@@ -514,7 +514,9 @@ impl DeployAcceptor {
     ) -> Effects<Event> {
         let payment = event_metadata.deploy.payment();
         match payment {
-            ExecutableDeployItem::Transfer { .. } => {
+            ExecutableDeployItem::Transfer { .. }
+            | ExecutableDeployItem::MultiTransfer { .. }
+            | ExecutableDeployItem::NativeAuction { .. } => {
                 let error = Error::parameter_failure(
                     &block_header,
                     DeployParameterFailure::InvalidPaymentVariant,
@@ -572,6 +574,8 @@ impl DeployAcceptor {
             // validation).
             ExecutableDeployItemIdentifier::Module
             | ExecutableDeployItemIdentifier::Transfer
+            | ExecutableDeployItemIdentifier::MultiTransfer
+            | ExecutableDeployItemIdentifier::NativeAuction
             | ExecutableDeployItemIdentifier::Contract(ContractIdentifier::Name(_))
             | ExecutableDeployItemIdentifier::Package(ContractPackageIdentifier::Name { .. }) => {
                 self.verify_session_logic(
@@ -635,11 +639,15 @@ impl DeployAcceptor {
         let session = event_metadata.deploy.session();
 
         match session {
-            ExecutableDeployItem::Transfer { args } => {
+            ExecutableDeployItem::Transfer { .. } | ExecutableDeployItem::MultiTransfer { .. } => {
                 // We rely on the `Deploy::is_config_compliant` to check
                 // that the transfer amount arg is present and is a valid U512.
 
-                if args.get(ARG_TARGET).is_none() {
+                if session
+                    .transfer_args()
+                    .iter()
+                    .any(|args| args.get(ARG_TARGET).is_none())
+                {
                     let error = Error::parameter_failure(
                         &block_header,
                         DeployParameterFailure::MissingTransferTarget,
@@ -671,7 +679,8 @@ impl DeployAcceptor {
             ExecutableDeployItem::StoredContractByHash { .. }
             | ExecutableDeployItem::StoredContractByName { .. }
             | ExecutableDeployItem::StoredVersionedContractByHash { .. }
-            | ExecutableDeployItem::StoredVersionedContractByName { .. } => (),
+            | ExecutableDeployItem::StoredVersionedContractByName { .. }
+            | ExecutableDeployItem::NativeAuction { .. } => (),
         }
 
         match session.identifier() {
@@ -681,6 +690,8 @@ impl DeployAcceptor {
             // validation).
             ExecutableDeployItemIdentifier::Module
             | ExecutableDeployItemIdentifier::Transfer
+            | ExecutableDeployItemIdentifier::MultiTransfer
+            | ExecutableDeployItemIdentifier::NativeAuction
             | ExecutableDeployItemIdentifier::Contract(ContractIdentifier::Name(_))
             | ExecutableDeployItemIdentifier::Package(ContractPackageIdentifier::Name { .. }) => {
                 self.validate_deploy_cryptography(
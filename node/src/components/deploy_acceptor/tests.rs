@@ -576,6 +576,7 @@ impl reactor::Reactor for Reactor {
                 ContractRuntimeRequest::Query {
                     query_request,
                     responder,
+                    ..
                 } => {
                     let query_result = if self.test_scenario
                         == TestScenario::FromClientMissingAccount
@@ -684,6 +685,7 @@ impl reactor::Reactor for Reactor {
                 ContractRuntimeRequest::GetBalance {
                     balance_request,
                     responder,
+                    ..
                 } => {
                     let proof = TrieMerkleProof::new(
                         balance_request.purse_uref().into(),
@@ -376,6 +376,10 @@ impl DeployBuffer {
                             );
                             self.dead.insert(deploy_hash);
                         }
+                        AddError::TransferCount if footprint.transfer_count > 1 => {
+                            // keep iterating, we can maybe fit in a smaller batch or a single
+                            // transfer
+                        }
                         AddError::TransferCount => {
                             if have_hit_deploy_limit {
                                 info!(
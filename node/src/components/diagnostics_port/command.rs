@@ -96,6 +96,9 @@ pub(super) enum Action {
     DumpQueues,
     /// Get detailed networking insights.
     NetInfo,
+    /// Show the configuration the execution engine is running with, including wasm and system
+    /// costs.
+    EngineConfig,
     /// Stop the node at a certain condition.
     Stop {
         /// When to stop the node.
@@ -142,5 +145,8 @@ mod tests {
 
         let cmd = Command::from_line("dump-queues").expect("command parsing failed");
         assert!(matches!(cmd.action, Action::DumpQueues));
+
+        let cmd = Command::from_line("engine-config").expect("command parsing failed");
+        assert!(matches!(cmd.action, Action::EngineConfig));
     }
 }
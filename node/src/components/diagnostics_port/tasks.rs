@@ -26,14 +26,14 @@ use tracing_subscriber::{filter::ParseError, EnvFilter};
 
 use super::{
     command::{Action, Command, OutputFormat},
-    util::ShowUnixAddr,
+    util::{ShowJson, ShowUnixAddr},
 };
 use crate::{
     components::consensus::EraDump,
     effect::{
         announcements::{ControlAnnouncement, QueueDumpFormat},
         diagnostics_port::DumpConsensusStateRequest,
-        requests::{NetworkInfoRequest, SetNodeStopRequest},
+        requests::{ContractRuntimeRequest, NetworkInfoRequest, SetNodeStopRequest},
         EffectBuilder,
     },
     logging,
@@ -201,6 +201,7 @@ impl Session {
         REv: From<DumpConsensusStateRequest>
             + From<ControlAnnouncement>
             + From<NetworkInfoRequest>
+            + From<ContractRuntimeRequest>
             + From<SetNodeStopRequest>
             + Send,
     {
@@ -322,6 +323,13 @@ impl Session {
                         let insights = effect_builder.get_network_insights().await;
                         self.send_to_client(writer, &insights).await?;
                     }
+                    Action::EngineConfig => {
+                        self.send_outcome(writer, &Outcome::success("showing engine config"))
+                            .await?;
+                        let engine_config = effect_builder.get_engine_config().await;
+                        self.send_to_client(writer, &ShowJson(&engine_config))
+                            .await?;
+                    }
                     Action::Stop { at, clear } => {
                         let (msg, stop_at) = if clear {
                             ("clearing stopping point", None)
@@ -477,6 +485,7 @@ where
     REv: From<DumpConsensusStateRequest>
         + From<ControlAnnouncement>
         + From<NetworkInfoRequest>
+        + From<ContractRuntimeRequest>
         + From<SetNodeStopRequest>
         + Send,
 {
@@ -521,6 +530,7 @@ pub(super) async fn server<REv>(
     REv: From<DumpConsensusStateRequest>
         + From<ControlAnnouncement>
         + From<NetworkInfoRequest>
+        + From<ContractRuntimeRequest>
         + From<SetNodeStopRequest>
         + Send,
 {
@@ -601,7 +611,7 @@ mod tests {
         effect::{
             announcements::ControlAnnouncement,
             diagnostics_port::DumpConsensusStateRequest,
-            requests::{NetworkInfoRequest, SetNodeStopRequest},
+            requests::{ContractRuntimeRequest, NetworkInfoRequest, SetNodeStopRequest},
             EffectBuilder, EffectExt, Effects,
         },
         reactor::{
@@ -661,6 +671,8 @@ mod tests {
         NetworkInfoRequest(NetworkInfoRequest),
         #[from]
         SetNodeStopRequest(SetNodeStopRequest),
+        #[from]
+        ContractRuntimeRequest(ContractRuntimeRequest),
     }
 
     impl Display for Event {
@@ -707,7 +719,8 @@ mod tests {
                 Event::DumpConsensusStateRequest(_)
                 | Event::SetNodeStopRequest(_)
                 | Event::ControlAnnouncement(_)
-                | Event::NetworkInfoRequest(_) => {
+                | Event::NetworkInfoRequest(_)
+                | Event::ContractRuntimeRequest(_) => {
                     panic!("unexpected: {}", event)
                 }
             }
@@ -1,7 +1,8 @@
-//! Renderer for unix socket addresses.
+//! Renderers for values shown to diagnostics port clients.
 
 use std::fmt::{self, Display, Formatter};
 
+use serde::{Serialize, Serializer};
 use tokio::net::unix::SocketAddr;
 
 /// Unix socket address `Display` wrapper.
@@ -19,3 +20,22 @@ impl<'a> Display for ShowUnixAddr<'a> {
         }
     }
 }
+
+/// `Display` wrapper rendering a value as pretty-printed JSON.
+///
+/// Serializes transparently, so the other output formats are unaffected.
+#[derive(Debug)]
+pub(super) struct ShowJson<'a, T>(pub &'a T);
+
+impl<'a, T: Serialize> Display for ShowJson<'a, T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let json = serde_json::to_string_pretty(self.0).map_err(|_| fmt::Error)?;
+        f.write_str(&json)
+    }
+}
+
+impl<'a, T: Serialize> Serialize for ShowJson<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
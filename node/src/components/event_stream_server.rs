@@ -16,16 +16,25 @@
 //! This component uses a ring buffer for outbound events providing some robustness against
 //! unintended subscriber disconnects, if a disconnected subscriber re-subscribes before the buffer
 //! has advanced past their last received event.
+//!
+//! If enabled, the most recent events are also persisted in an event log indexed by block height
+//! and era, allowing subscribers to request replay from a given block height or era, including
+//! across node restarts.
+//!
+//! The same events are also available via a WebSocket endpoint, over which a client can multiplex
+//! subscriptions to several topics, each with its own acknowledgement-based flow control.
 
 mod config;
 mod event;
 mod event_indexer;
+mod event_log;
 mod http_server;
 mod sse_server;
 #[cfg(test)]
 mod tests;
+mod ws_server;
 
-use std::{fmt::Debug, net::SocketAddr, path::PathBuf};
+use std::{fmt::Debug, net::SocketAddr, path::PathBuf, sync::Arc};
 
 use datasize::DataSize;
 use tokio::sync::{
@@ -49,8 +58,10 @@ use crate::{
 pub use config::Config;
 pub(crate) use event::Event;
 use event_indexer::{EventIndex, EventIndexer};
+use event_log::EventLog;
 use sse_server::ChannelsAndFilter;
 pub(crate) use sse_server::SseData;
+use ws_server::FlowControl;
 
 const COMPONENT_NAME: &str = "event_stream_server";
 
@@ -115,6 +126,20 @@ impl EventStreamServer {
             * (100 + ADDITIONAL_PERCENT_FOR_BROADCAST_CHANNEL_SIZE)
             / 100;
 
+        let event_log = self.config.event_log_length.and_then(|event_log_length| {
+            match EventLog::open(&self.storage_path, event_log_length) {
+                Ok(event_log) => Some(Arc::new(event_log)),
+                Err(error) => {
+                    warn!(
+                        %error,
+                        "failed to open sse event log, replay from a block height or era will be \
+                        unavailable"
+                    );
+                    None
+                }
+            }
+        });
+
         let ChannelsAndFilter {
             event_broadcaster,
             new_subscriber_info_receiver,
@@ -122,6 +147,13 @@ impl EventStreamServer {
         } = ChannelsAndFilter::new(
             broadcast_channel_size as usize,
             self.config.max_concurrent_subscribers,
+            event_log.clone(),
+            self.api_version,
+            FlowControl {
+                max_unacked_events: self.config.websocket_max_unacked_events,
+                max_pending_events: self.config.websocket_max_pending_events,
+                max_subscriptions: self.config.websocket_max_subscriptions_per_client,
+            },
         );
 
         let (server_shutdown_sender, shutdown_receiver) = oneshot::channel::<()>();
@@ -147,6 +179,7 @@ impl EventStreamServer {
                     sse_data_receiver,
                     event_broadcaster,
                     new_subscriber_info_receiver,
+                    event_log,
                 ));
                 listening_address
             }
@@ -169,6 +202,7 @@ impl EventStreamServer {
                     sse_data_receiver,
                     event_broadcaster,
                     new_subscriber_info_receiver,
+                    event_log,
                 ));
                 listening_address
             }
@@ -192,6 +226,7 @@ impl EventStreamServer {
                     sse_data_receiver,
                     event_broadcaster,
                     new_subscriber_info_receiver,
+                    event_log,
                 ));
                 listening_address
             }
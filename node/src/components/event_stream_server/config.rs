@@ -15,6 +15,15 @@ const DEFAULT_MAX_CONCURRENT_SUBSCRIBERS: u32 = 100;
 /// Default CORS origin.
 const DEFAULT_CORS_ORIGIN: &str = "";
 
+/// Default maximum number of unacknowledged events per WebSocket subscription.
+const DEFAULT_WEBSOCKET_MAX_UNACKED_EVENTS: u32 = 100;
+
+/// Default maximum number of queued events per WebSocket subscription.
+const DEFAULT_WEBSOCKET_MAX_PENDING_EVENTS: u32 = 1000;
+
+/// Default maximum number of subscriptions per WebSocket client.
+const DEFAULT_WEBSOCKET_MAX_SUBSCRIPTIONS_PER_CLIENT: u32 = 32;
+
 /// SSE HTTP server configuration.
 #[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
@@ -34,6 +43,22 @@ pub struct Config {
 
     /// CORS origin.
     pub cors_origin: String,
+
+    /// Number of SSEs to persist for replay from a given block height or era.
+    ///
+    /// If `None`, SSEs are not persisted and clients can only replay buffered SSEs.
+    pub event_log_length: Option<u32>,
+
+    /// Maximum number of events sent per WebSocket subscription before the client must acknowledge
+    /// them.
+    pub websocket_max_unacked_events: u32,
+
+    /// Maximum number of events queued per WebSocket subscription awaiting acknowledgement of
+    /// earlier ones, before the client is disconnected.
+    pub websocket_max_pending_events: u32,
+
+    /// Maximum number of subscriptions a single WebSocket client may hold at once.
+    pub websocket_max_subscriptions_per_client: u32,
 }
 
 impl Config {
@@ -45,6 +70,10 @@ impl Config {
             event_stream_buffer_length: DEFAULT_EVENT_STREAM_BUFFER_LENGTH,
             max_concurrent_subscribers: DEFAULT_MAX_CONCURRENT_SUBSCRIBERS,
             cors_origin: DEFAULT_CORS_ORIGIN.to_string(),
+            event_log_length: None,
+            websocket_max_unacked_events: DEFAULT_WEBSOCKET_MAX_UNACKED_EVENTS,
+            websocket_max_pending_events: DEFAULT_WEBSOCKET_MAX_PENDING_EVENTS,
+            websocket_max_subscriptions_per_client: DEFAULT_WEBSOCKET_MAX_SUBSCRIPTIONS_PER_CLIENT,
         }
     }
 }
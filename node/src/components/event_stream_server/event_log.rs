@@ -0,0 +1,461 @@
+//! A persisted log of the most recent events, indexed by block height and era.
+//!
+//! The log allows clients to request replay of events starting from a given block height or era,
+//! including events emitted before the node last restarted, rather than only from the limited
+//! in-memory buffer.
+
+use std::{
+    ops::Range,
+    path::Path,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use lmdb::{
+    Cursor, Database, DatabaseFlags, Environment, EnvironmentFlags, Error as LmdbError,
+    RwTransaction, Transaction, WriteFlags,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use casper_types::EraId;
+
+use super::{
+    sse_server::{Id, ServerSentEvent},
+    SseData,
+};
+
+const EVENT_LOG_FILENAME: &str = "sse_event_log.lmdb";
+
+/// The maximum size of the event log database.
+///
+/// This only reserves address space; the file grows as events are added.
+const MAX_EVENT_LOG_SIZE: usize = 16 * 1024 * 1024 * 1024;
+
+/// The number of databases held in the event log environment.
+const MAX_DB_COUNT: u32 = 3;
+
+/// An event as held in the event log.
+#[derive(Serialize, Deserialize)]
+struct LoggedEvent {
+    id: Id,
+    data: SseData,
+}
+
+/// Where in the event log a replay requested by a client starts.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub(super) enum ReplayStart {
+    /// The replay starts at the event with the given sequence number.
+    Sequence(u64),
+    /// The requested point has not been reached yet, so there are no events to replay.
+    Live,
+    /// The events from the requested point are no longer held in the log.
+    Unavailable,
+}
+
+/// The persisted log of the most recent events.
+#[derive(Debug)]
+pub(super) struct EventLog {
+    env: Environment,
+    /// The events, keyed by their sequence number.
+    events_db: Database,
+    /// The sequence number of the `BlockAdded` event of each block, keyed by block height.
+    block_heights_db: Database,
+    /// The sequence number of the first `BlockAdded` event of each era, keyed by era ID.
+    eras_db: Database,
+    /// The maximum number of events retained.
+    max_length: u64,
+    /// The sequence number of the next event to be added.
+    next_sequence: AtomicU64,
+}
+
+impl EventLog {
+    /// Opens the event log in the given folder, creating it if it doesn't exist.
+    pub(super) fn open(storage_path: &Path, max_length: u32) -> Result<Self, LmdbError> {
+        let env = Environment::new()
+            .set_flags(EnvironmentFlags::NO_SUB_DIR | EnvironmentFlags::NO_TLS)
+            .set_max_dbs(MAX_DB_COUNT)
+            .set_map_size(MAX_EVENT_LOG_SIZE)
+            .open(&storage_path.join(EVENT_LOG_FILENAME))?;
+        let events_db = env.create_db(Some("events"), DatabaseFlags::empty())?;
+        let block_heights_db = env.create_db(Some("block_heights"), DatabaseFlags::empty())?;
+        let eras_db = env.create_db(Some("eras"), DatabaseFlags::empty())?;
+
+        let next_sequence = {
+            let txn = env.begin_ro_txn()?;
+            let mut cursor = txn.open_ro_cursor(events_db)?;
+            let maybe_last_key = cursor
+                .iter_start()
+                .last()
+                .transpose()?
+                .map(|(key, _)| decode_key(key));
+            maybe_last_key.map_or(0, |last_sequence| last_sequence + 1)
+        };
+        debug!(%next_sequence, "opened sse event log");
+
+        Ok(EventLog {
+            env,
+            events_db,
+            block_heights_db,
+            eras_db,
+            max_length: u64::from(max_length),
+            next_sequence: AtomicU64::new(next_sequence),
+        })
+    }
+
+    /// Adds the events to the log in a single transaction, dropping the oldest events if the log
+    /// is full.
+    ///
+    /// Must not be called concurrently.
+    pub(super) fn append(&self, events: &[(Id, SseData)]) {
+        if let Err(error) = self.try_append(events) {
+            warn!(%error, event_count = %events.len(), "failed to add events to sse event log");
+        }
+    }
+
+    fn try_append(&self, events: &[(Id, SseData)]) -> Result<(), LmdbError> {
+        let mut sequence = self.next_sequence.load(Ordering::SeqCst);
+        let mut txn = self.env.begin_rw_txn()?;
+        for (id, data) in events {
+            let logged_event = LoggedEvent {
+                id: *id,
+                data: data.clone(),
+            };
+            let serialized = match serde_json::to_vec(&logged_event) {
+                Ok(serialized) => serialized,
+                Err(error) => {
+                    warn!(%error, event_id = %id, "failed to serialize event for sse event log");
+                    continue;
+                }
+            };
+            txn.put(
+                self.events_db,
+                &encode_key(sequence),
+                &serialized,
+                WriteFlags::empty(),
+            )?;
+            if let SseData::BlockAdded { block, .. } = data {
+                put_if_absent(
+                    &mut txn,
+                    self.block_heights_db,
+                    block.header.height,
+                    sequence,
+                )?;
+                put_if_absent(
+                    &mut txn,
+                    self.eras_db,
+                    block.header.era_id.value(),
+                    sequence,
+                )?;
+            }
+            sequence += 1;
+        }
+        if sequence > self.max_length {
+            self.prune(&mut txn, sequence - self.max_length)?;
+        }
+        txn.commit()?;
+        self.next_sequence.store(sequence, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Returns the sequence number the next event added to the log will get.
+    pub(super) fn next_sequence(&self) -> u64 {
+        self.next_sequence.load(Ordering::SeqCst)
+    }
+
+    /// Removes all events with a sequence number lower than `first_retained`.
+    ///
+    /// Index entries pointing to removed events are removed too, other than the highest one in each
+    /// index, which is kept to record that replay from that point is no longer possible.
+    fn prune(&self, txn: &mut RwTransaction, first_retained: u64) -> Result<(), LmdbError> {
+        {
+            let mut cursor = txn.open_rw_cursor(self.events_db)?;
+            for entry in cursor.iter_start() {
+                let (key, _) = entry?;
+                if decode_key(key) >= first_retained {
+                    break;
+                }
+                cursor.del(WriteFlags::empty())?;
+            }
+        }
+
+        for index_db in [self.block_heights_db, self.eras_db] {
+            let mut pruned_keys = vec![];
+            {
+                let mut cursor = txn.open_ro_cursor(index_db)?;
+                for entry in cursor.iter_start() {
+                    let (key, value) = entry?;
+                    if decode_key(value) >= first_retained {
+                        break;
+                    }
+                    pruned_keys.push(key.to_vec());
+                }
+            }
+            let _ = pruned_keys.pop();
+            for key in pruned_keys {
+                txn.del(index_db, &key, None)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns where a replay starting from the block at the given height starts.
+    pub(super) fn replay_start_for_block_height(&self, block_height: u64) -> ReplayStart {
+        self.replay_start(self.block_heights_db, block_height)
+    }
+
+    /// Returns where a replay starting from the first block of the given era starts.
+    pub(super) fn replay_start_for_era(&self, era_id: EraId) -> ReplayStart {
+        self.replay_start(self.eras_db, era_id.value())
+    }
+
+    fn replay_start(&self, index_db: Database, requested: u64) -> ReplayStart {
+        let result = (|| {
+            let txn = self.env.begin_ro_txn()?;
+            let first_retained = txn
+                .open_ro_cursor(self.events_db)?
+                .iter_start()
+                .next()
+                .transpose()?
+                .map_or(0, |(key, _)| decode_key(key));
+            let mut cursor = txn.open_ro_cursor(index_db)?;
+            let maybe_first_key = cursor
+                .iter_start()
+                .next()
+                .transpose()?
+                .map(|(key, _)| decode_key(key));
+            let maybe_sequence = cursor
+                .iter_from(encode_key(requested))
+                .next()
+                .transpose()?
+                .map(|(_, value)| decode_key(value));
+            Ok::<_, LmdbError>((first_retained, maybe_first_key, maybe_sequence))
+        })();
+        match result {
+            Ok((_, _, None)) => ReplayStart::Live,
+            Ok((first_retained, Some(first_key), Some(sequence)))
+                if requested < first_key || sequence < first_retained =>
+            {
+                ReplayStart::Unavailable
+            }
+            Ok((_, _, Some(sequence))) => ReplayStart::Sequence(sequence),
+            Err(error) => {
+                warn!(%error, "failed to read sse event log index");
+                ReplayStart::Unavailable
+            }
+        }
+    }
+
+    /// Returns up to `max_count` of the events held in the log with sequence numbers in the given
+    /// range, along with their sequence numbers.
+    pub(super) fn read_chunk(
+        &self,
+        sequences: Range<u64>,
+        max_count: usize,
+    ) -> Vec<(u64, ServerSentEvent)> {
+        let mut events = vec![];
+        let result = (|| {
+            let txn = self.env.begin_ro_txn()?;
+            let mut cursor = txn.open_ro_cursor(self.events_db)?;
+            for entry in cursor.iter_from(encode_key(sequences.start)) {
+                let (key, value) = entry?;
+                let sequence = decode_key(key);
+                if sequence >= sequences.end || events.len() >= max_count {
+                    break;
+                }
+                match serde_json::from_slice::<LoggedEvent>(value) {
+                    Ok(LoggedEvent { id, data }) => {
+                        events.push((sequence, ServerSentEvent { id: Some(id), data }))
+                    }
+                    Err(error) => {
+                        warn!(%error, "failed to parse event from sse event log");
+                        break;
+                    }
+                }
+            }
+            Ok::<_, LmdbError>(())
+        })();
+        if let Err(error) = result {
+            warn!(%error, "failed to read sse event log");
+        }
+        events
+    }
+}
+
+/// Puts `sequence` under `key` unless an entry for `key` already exists.
+fn put_if_absent(
+    txn: &mut RwTransaction,
+    db: Database,
+    key: u64,
+    sequence: u64,
+) -> Result<(), LmdbError> {
+    match txn.put(
+        db,
+        &encode_key(key),
+        &encode_key(sequence),
+        WriteFlags::NO_OVERWRITE,
+    ) {
+        Ok(()) | Err(LmdbError::KeyExist) => Ok(()),
+        Err(error) => Err(error),
+    }
+}
+
+/// Encodes as big-endian so that LMDB orders keys numerically.
+fn encode_key(value: u64) -> [u8; 8] {
+    value.to_be_bytes()
+}
+
+fn decode_key(bytes: &[u8]) -> u64 {
+    let mut buffer = [0; 8];
+    buffer.copy_from_slice(bytes);
+    u64::from_be_bytes(buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{testing::TestRng, ProtocolVersion};
+
+    use super::*;
+    use crate::{
+        logging,
+        types::{Block, JsonBlock},
+    };
+
+    fn block_added(rng: &mut TestRng, era_id: u64, height: u64) -> SseData {
+        let block = Block::random_with_specifics(
+            rng,
+            EraId::new(era_id),
+            height,
+            ProtocolVersion::V1_0_0,
+            false,
+            None,
+        );
+        SseData::BlockAdded {
+            block_hash: *block.hash(),
+            block: Box::new(JsonBlock::new(&block, None)),
+        }
+    }
+
+    #[test]
+    fn should_replay_from_block_height_and_era() {
+        let _ = logging::init();
+        let mut rng = TestRng::new();
+        let tempdir = tempfile::tempdir().unwrap();
+
+        {
+            let event_log = EventLog::open(tempdir.path(), 100).unwrap();
+            // Two eras of three blocks each, with two other events after each block.
+            let mut id = 0;
+            for height in 0..6 {
+                event_log.append(&[
+                    (id, block_added(&mut rng, height / 3, height)),
+                    (id + 1, SseData::random_deploy_processed(&mut rng)),
+                    (id + 2, SseData::random_finality_signature(&mut rng)),
+                ]);
+                id += 3;
+            }
+        }
+
+        // Reopen to check the log persists across restarts.
+        let event_log = EventLog::open(tempdir.path(), 100).unwrap();
+        assert_eq!(
+            event_log.replay_start_for_block_height(4),
+            ReplayStart::Sequence(12)
+        );
+        assert_eq!(
+            event_log.replay_start_for_era(EraId::new(1)),
+            ReplayStart::Sequence(9)
+        );
+        assert_eq!(
+            event_log.replay_start_for_block_height(6),
+            ReplayStart::Live
+        );
+        assert_eq!(
+            event_log.replay_start_for_era(EraId::new(2)),
+            ReplayStart::Live
+        );
+
+        assert_eq!(event_log.next_sequence(), 18);
+        let replayed = event_log.read_chunk(12..18, 100);
+        assert_eq!(replayed.len(), 6);
+        let ids: Vec<_> = replayed
+            .iter()
+            .map(|(sequence, event)| {
+                assert_eq!(u64::from(event.id.unwrap()), *sequence);
+                event.id.unwrap()
+            })
+            .collect();
+        assert_eq!(ids, (12..18).collect::<Vec<_>>());
+        assert!(matches!(replayed[0].1.data, SseData::BlockAdded { .. }));
+
+        // New events continue the sequence.
+        event_log.append(&[(18, block_added(&mut rng, 2, 6))]);
+        assert_eq!(
+            event_log.replay_start_for_block_height(6),
+            ReplayStart::Sequence(18)
+        );
+    }
+
+    #[test]
+    fn should_prune_oldest_events() {
+        let _ = logging::init();
+        let mut rng = TestRng::new();
+        let tempdir = tempfile::tempdir().unwrap();
+
+        let event_log = EventLog::open(tempdir.path(), 4).unwrap();
+        for height in 0..4 {
+            let id = height as Id * 2;
+            event_log.append(&[(id, block_added(&mut rng, 0, height))]);
+            event_log.append(&[(id + 1, SseData::random_deploy_expired(&mut rng))]);
+        }
+
+        // Only the events of the two highest blocks are retained.
+        let retained = event_log.read_chunk(0..u64::MAX, 100);
+        let ids: Vec<_> = retained
+            .iter()
+            .map(|(_, event)| event.id.unwrap())
+            .collect();
+        assert_eq!(ids, vec![4, 5, 6, 7]);
+        assert_eq!(
+            event_log.replay_start_for_block_height(1),
+            ReplayStart::Unavailable
+        );
+        assert_eq!(
+            event_log.replay_start_for_block_height(2),
+            ReplayStart::Sequence(4)
+        );
+        // The era's first block has been pruned.
+        assert_eq!(
+            event_log.replay_start_for_era(EraId::new(0)),
+            ReplayStart::Unavailable
+        );
+    }
+
+    #[test]
+    fn should_read_in_bounded_chunks() {
+        let _ = logging::init();
+        let mut rng = TestRng::new();
+        let tempdir = tempfile::tempdir().unwrap();
+
+        // A single batch larger than the log only retains its newest events.
+        let event_log = EventLog::open(tempdir.path(), 10).unwrap();
+        let events: Vec<_> = (0..12)
+            .map(|id| (id, SseData::random_deploy_expired(&mut rng)))
+            .collect();
+        event_log.append(&events);
+        assert_eq!(event_log.next_sequence(), 12);
+
+        let read_ids = |sequences: Range<u64>, max_count: usize| -> Vec<Id> {
+            event_log
+                .read_chunk(sequences, max_count)
+                .into_iter()
+                .map(|(_, event)| event.id.unwrap())
+                .collect()
+        };
+        assert_eq!(read_ids(0..12, 4), vec![2, 3, 4, 5]);
+        assert_eq!(read_ids(6..12, 4), vec![6, 7, 8, 9]);
+        assert_eq!(read_ids(10..12, 4), vec![10, 11]);
+        // Events outside the requested range are not returned.
+        assert_eq!(read_ids(3..5, 4), vec![3, 4]);
+        assert!(read_ids(12..20, 4).is_empty());
+    }
+}
@@ -1,19 +1,28 @@
+use std::{ops::Range, sync::Arc};
+
 use futures::{future, Future, FutureExt};
 use tokio::{
     select,
     sync::{broadcast, mpsc, oneshot},
     task,
 };
-use tracing::{info, trace};
+use tracing::{info, trace, warn};
 use wheelbuf::WheelBuf;
 
 use casper_types::ProtocolVersion;
 
 use super::{
+    event_log::EventLog,
     sse_server::{BroadcastChannelMessage, Id, NewSubscriberInfo, ServerSentEvent},
     Config, EventIndex, SseData,
 };
 
+/// The maximum number of events added to the event log in a single transaction.
+const MAX_EVENT_LOG_BATCH_SIZE: usize = 100;
+
+/// The maximum number of events read from the event log at a time when replaying it to a client.
+const REPLAY_CHUNK_SIZE: usize = 100;
+
 /// Run the HTTP server.
 ///
 /// * `server_with_shutdown` is the actual server as a future which can be gracefully shut down.
@@ -25,6 +34,10 @@ use super::{
 /// * `new_subscriber_info_receiver` is used to notify the server of the details of a new client
 ///   having subscribed to the event stream.  It allows the server to populate that client's stream
 ///   with the requested number of historical events.
+/// * `event_log` is the persisted log of events, if enabled, to which received events are added in
+///   batches, and from which events are replayed to clients which requested replay from a given
+///   block height or era.
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn run(
     config: Config,
     api_version: ProtocolVersion,
@@ -33,6 +46,7 @@ pub(super) async fn run(
     mut data_receiver: mpsc::UnboundedReceiver<(EventIndex, SseData)>,
     broadcaster: broadcast::Sender<BroadcastChannelMessage>,
     mut new_subscriber_info_receiver: mpsc::UnboundedReceiver<NewSubscriberInfo>,
+    event_log: Option<Arc<EventLog>>,
 ) {
     let server_joiner = task::spawn(server_with_shutdown);
 
@@ -49,13 +63,18 @@ pub(super) async fn run(
             select! {
                 maybe_new_subscriber = new_subscriber_info_receiver.recv() => {
                     if let Some(subscriber) = maybe_new_subscriber {
-                        // First send the client the `ApiVersion` event.  We don't care if this
-                        // errors - the client may have disconnected already.
-                        let _ = subscriber
-                            .initial_events_sender
-                            .send(ServerSentEvent::initial_event(api_version));
+                        // If the client requested replay from a given block height or era, provide
+                        // the logged events from that point up to the latest one.  Any which are
+                        // also broadcast after the client subscribed are skipped by the client's
+                        // handler.
+                        let replay = subscriber.replay_from.and_then(|sequence| {
+                            event_log.as_ref().map(|event_log| {
+                                (Arc::clone(event_log), sequence..event_log.next_sequence())
+                            })
+                        });
                         // If the client supplied a "start_from" index, provide the buffered events.
                         // If they requested more than is buffered, just provide the whole buffer.
+                        let mut buffered_events = vec![];
                         if let Some(start_index) = subscriber.start_from {
                             // If the buffer's first event ID is in the range [0, buffer size) or
                             // (Id::MAX - buffer size, Id::MAX], then the events in the buffer are
@@ -73,32 +92,73 @@ pub(super) async fn run(
                                     id > Id::MAX - buffer_size || id < buffer_size
                                 })
                                 .unwrap_or_default();
-                            for event in buffer.iter().skip_while(|event| {
-                                if in_wraparound_zone {
-                                    event.id.unwrap().wrapping_add(buffer_size)
-                                        < start_index.wrapping_add(buffer_size)
-                                } else {
-                                    event.id.unwrap() < start_index
-                                }
-                            }) {
-                                // As per sending `SSE_INITIAL_EVENT`, we don't care if this errors.
-                                let _ = subscriber.initial_events_sender.send(event.clone());
-                            }
+                            buffered_events.extend(
+                                buffer
+                                    .iter()
+                                    .skip_while(|event| {
+                                        if in_wraparound_zone {
+                                            event.id.unwrap().wrapping_add(buffer_size)
+                                                < start_index.wrapping_add(buffer_size)
+                                        } else {
+                                            event.id.unwrap() < start_index
+                                        }
+                                    })
+                                    .cloned(),
+                            );
                         }
+                        // Send the initial events from a separate task, so that a slow client only
+                        // holds up its own stream.
+                        task::spawn(send_initial_events(
+                            subscriber.initial_events_sender,
+                            api_version,
+                            replay,
+                            buffered_events,
+                        ));
                     }
                 }
 
                 maybe_data = data_receiver.recv() => {
                     match maybe_data {
-                        Some((event_index, data)) => {
-                            // Buffer the data and broadcast it to subscribed clients.
-                            trace!("Event stream server received {:?}", data);
-                            let event = ServerSentEvent { id: Some(event_index), data };
-                            buffer.push(event.clone());
-                            let message = BroadcastChannelMessage::ServerSentEvent(event);
-                            // This can validly fail if there are no connected clients, so don't log
-                            // the error.
-                            let _ = broadcaster.send(message);
+                        Some(first_data) => {
+                            // Take any further pending data too, so that it can be added to the
+                            // event log in a single transaction.
+                            let mut batch = vec![first_data];
+                            while batch.len() < MAX_EVENT_LOG_BATCH_SIZE {
+                                match data_receiver.try_recv() {
+                                    Ok(data) => batch.push(data),
+                                    Err(_) => break,
+                                }
+                            }
+
+                            // Log the data before broadcasting it, so that a client subscribing
+                            // afterwards and requesting replay doesn't miss any of it.
+                            if let Some(event_log) = event_log.as_ref() {
+                                let loggable: Vec<_> = batch
+                                    .iter()
+                                    .filter(|(_, data)| !matches!(data, SseData::Shutdown))
+                                    .cloned()
+                                    .collect();
+                                if !loggable.is_empty() {
+                                    let event_log = Arc::clone(event_log);
+                                    if let Err(error) =
+                                        task::spawn_blocking(move || event_log.append(&loggable))
+                                            .await
+                                    {
+                                        warn!(%error, "failed to add events to sse event log");
+                                    }
+                                }
+                            }
+
+                            for (event_index, data) in batch {
+                                // Buffer the data and broadcast it to subscribed clients.
+                                trace!("Event stream server received {:?}", data);
+                                let event = ServerSentEvent { id: Some(event_index), data };
+                                buffer.push(event.clone());
+                                let message = BroadcastChannelMessage::ServerSentEvent(event);
+                                // This can validly fail if there are no connected clients, so don't
+                                // log the error.
+                                let _ = broadcaster.send(message);
+                            }
                         }
                         None => {
                             // The data sender has been dropped - exit the loop.
@@ -121,3 +181,57 @@ pub(super) async fn run(
 
     trace!("Event stream server stopped");
 }
+
+/// Sends the initial events to a new client's handler: the `ApiVersion` event, followed by the
+/// requested range of logged events, if any, followed by the given buffered events.
+///
+/// The logged events are read in chunks of at most `REPLAY_CHUNK_SIZE`, and the next chunk is only
+/// read once the client's handler has taken enough of the previous one.
+async fn send_initial_events(
+    initial_events_sender: mpsc::Sender<ServerSentEvent>,
+    api_version: ProtocolVersion,
+    replay: Option<(Arc<EventLog>, Range<u64>)>,
+    buffered_events: Vec<ServerSentEvent>,
+) {
+    // We don't care if any of the sends error - the client may have disconnected already.
+    if initial_events_sender
+        .send(ServerSentEvent::initial_event(api_version))
+        .await
+        .is_err()
+    {
+        return;
+    }
+
+    if let Some((event_log, mut sequences)) = replay {
+        while !sequences.is_empty() {
+            let chunk_sequences = sequences.clone();
+            let cloned_event_log = Arc::clone(&event_log);
+            let chunk = match task::spawn_blocking(move || {
+                cloned_event_log.read_chunk(chunk_sequences, REPLAY_CHUNK_SIZE)
+            })
+            .await
+            {
+                Ok(chunk) => chunk,
+                Err(error) => {
+                    warn!(%error, "failed to read sse event log");
+                    return;
+                }
+            };
+            match chunk.last() {
+                Some((last_sequence, _)) => sequences.start = last_sequence + 1,
+                None => break,
+            }
+            for (_, event) in chunk {
+                if initial_events_sender.send(event).await.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+
+    for event in buffered_events {
+        if initial_events_sender.send(event).await.is_err() {
+            return;
+        }
+    }
+}
@@ -2,6 +2,7 @@
 
 use std::{
     collections::{HashMap, HashSet},
+    convert::TryFrom,
     net::SocketAddr,
     sync::{Arc, RwLock},
 };
@@ -18,9 +19,7 @@ use tokio::sync::{
     broadcast::{self, error::RecvError},
     mpsc,
 };
-use tokio_stream::wrappers::{
-    errors::BroadcastStreamRecvError, BroadcastStream, UnboundedReceiverStream,
-};
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream, ReceiverStream};
 use tracing::{debug, error, info, warn};
 use warp::{
     addr,
@@ -32,12 +31,18 @@ use warp::{
     Filter, Reply,
 };
 
+use casper_execution_engine::core::engine_state::ExecutableDeployItem;
 #[cfg(test)]
 use casper_types::testing::TestRng;
 use casper_types::{
-    EraId, ExecutionEffect, ExecutionResult, ProtocolVersion, PublicKey, TimeDiff, Timestamp,
+    checksummed_hex, AsymmetricType, ContractHash, ContractPackageHash, EraId, ExecutionEffect,
+    ExecutionResult, HashAddr, Key, ProtocolVersion, PublicKey, TimeDiff, Timestamp,
 };
 
+use super::{
+    event_log::{EventLog, ReplayStart},
+    ws_server::{self, FlowControl},
+};
 use crate::types::{BlockHash, Deploy, DeployHash, FinalitySignature, JsonBlock};
 #[cfg(test)]
 use crate::{testing, types::Block};
@@ -53,6 +58,20 @@ pub const SSE_API_DEPLOYS_PATH: &str = "deploys";
 pub const SSE_API_SIGNATURES_PATH: &str = "sigs";
 /// The URL query string field name.
 pub const QUERY_FIELD: &str = "start_from";
+/// The URL query string field name for replaying events from a given block height.
+pub const BLOCK_HEIGHT_QUERY_FIELD: &str = "start_from_height";
+/// The URL query string field name for replaying events from the start of a given era.
+pub const ERA_QUERY_FIELD: &str = "start_from_era";
+/// The URL query string field name for only receiving events relating to deploys sent from the
+/// given account.
+pub const ACCOUNT_QUERY_FIELD: &str = "account";
+/// The URL query string field name for only receiving events relating to the given contract.
+pub const CONTRACT_QUERY_FIELD: &str = "contract";
+/// The URL query string field name for only receiving events of the given comma-separated types.
+pub const EVENT_TYPE_QUERY_FIELD: &str = "event_type";
+
+/// The capacity of the channel over which a new client's handler receives its initial events.
+const INITIAL_EVENTS_CHANNEL_SIZE: usize = 100;
 
 /// The filter associated with `/events/main` path.
 const MAIN_FILTER: [EventFilter; 5] = [
@@ -254,13 +273,27 @@ pub(super) enum BroadcastChannelMessage {
     Shutdown,
 }
 
+/// The point from which a client has requested the stream start.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub(super) enum StartFrom {
+    /// The buffered event with the given ID.
+    EventId(Id),
+    /// The `BlockAdded` event of the block at the given height.
+    BlockHeight(u64),
+    /// The `BlockAdded` event of the first block of the given era.
+    Era(EraId),
+}
+
 /// Passed to the server whenever a new client subscribes.
 pub(super) struct NewSubscriberInfo {
     /// The event ID from which the stream should start for this client.
     pub(super) start_from: Option<Id>,
+    /// The sequence number in the event log from which the stream should start for this client.
+    pub(super) replay_from: Option<u64>,
     /// A channel to send the initial events to the client's handler.  This will always send the
-    /// ApiVersion as the first event, and then any buffered events as indicated by `start_from`.
-    pub(super) initial_events_sender: mpsc::UnboundedSender<ServerSentEvent>,
+    /// ApiVersion as the first event, and then any buffered or logged events as indicated by
+    /// `start_from` or `replay_from`.
+    pub(super) initial_events_sender: mpsc::Sender<ServerSentEvent>,
 }
 
 /// A filter for event types a client has subscribed to receive.
@@ -275,6 +308,102 @@ pub(super) enum EventFilter {
     Step,
 }
 
+impl EventFilter {
+    /// Parses the name of an `SseData` variant into the corresponding `EventFilter`.
+    fn from_event_type(event_type: &str) -> Option<Self> {
+        match event_type {
+            "BlockAdded" => Some(EventFilter::BlockAdded),
+            "DeployAccepted" => Some(EventFilter::DeployAccepted),
+            "DeployProcessed" => Some(EventFilter::DeployProcessed),
+            "DeployExpired" => Some(EventFilter::DeployExpired),
+            "Fault" => Some(EventFilter::Fault),
+            "FinalitySignature" => Some(EventFilter::FinalitySignature),
+            "Step" => Some(EventFilter::Step),
+            _ => None,
+        }
+    }
+}
+
+/// Filters on the content of events, requested by a client via the query string.
+///
+/// These are applied in addition to the `EventFilter`s associated with the URL path.
+#[derive(Clone, Default, Eq, PartialEq, Debug)]
+pub(super) struct ContentFilter {
+    /// If `Some`, only events of these types are included.
+    event_types: Option<Vec<EventFilter>>,
+    /// If `Some`, only events relating to deploys sent from this account are included.
+    account: Option<PublicKey>,
+    /// If `Some`, only events relating to the contract or contract package with this address are
+    /// included.
+    contract: Option<HashAddr>,
+}
+
+impl ContentFilter {
+    /// Returns `true` if the filter includes the given event.
+    ///
+    /// `ApiVersion` and `Shutdown` events are always included.
+    pub(super) fn should_include(&self, data: &SseData) -> bool {
+        if matches!(data, SseData::ApiVersion(_) | SseData::Shutdown) {
+            return true;
+        }
+        if let Some(event_types) = &self.event_types {
+            if !data.should_include(event_types) {
+                return false;
+            }
+        }
+        if let Some(account) = &self.account {
+            let maybe_initiator = match data {
+                SseData::DeployAccepted { deploy } => Some(deploy.header().account()),
+                SseData::DeployProcessed { account, .. } => Some(&**account),
+                _ => None,
+            };
+            if maybe_initiator != Some(account) {
+                return false;
+            }
+        }
+        if let Some(contract) = &self.contract {
+            let relates_to_contract = match data {
+                SseData::DeployAccepted { deploy } => [deploy.payment(), deploy.session()]
+                    .iter()
+                    .any(|item| stored_contract_address(item) == Some(*contract)),
+                SseData::DeployProcessed {
+                    execution_result, ..
+                } => match &**execution_result {
+                    ExecutionResult::Failure { effect, .. }
+                    | ExecutionResult::Success { effect, .. } => touches_contract(effect, contract),
+                },
+                SseData::Step {
+                    execution_effect, ..
+                } => touches_contract(execution_effect, contract),
+                _ => false,
+            };
+            if !relates_to_contract {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Returns the address of the contract or contract package called by hash by `item`, if any.
+fn stored_contract_address(item: &ExecutableDeployItem) -> Option<HashAddr> {
+    match item {
+        ExecutableDeployItem::StoredContractByHash { hash, .. } => Some(hash.value()),
+        ExecutableDeployItem::StoredVersionedContractByHash { hash, .. } => Some(hash.value()),
+        _ => None,
+    }
+}
+
+/// Returns `true` if the execution effect reads or writes the key of the contract or contract
+/// package with the given address.
+pub(super) fn touches_contract(execution_effect: &ExecutionEffect, contract: &HashAddr) -> bool {
+    let contract_key = Key::Hash(*contract).to_formatted_string();
+    execution_effect
+        .transforms
+        .iter()
+        .any(|transform_entry| transform_entry.key == contract_key)
+}
+
 /// Filters the `event`, mapping it to a warp event, or `None` if it should be filtered out.
 async fn filter_map_server_sent_event(
     event: &ServerSentEvent,
@@ -345,26 +474,69 @@ pub(super) fn get_filter(path_param: &str) -> Option<&'static [EventFilter]> {
     }
 }
 
-/// Extracts the starting event ID from the provided query, or `None` if `query` is empty.
+/// Extracts the starting point and content filter from the provided query.
 ///
-/// If `query` is not empty, returns a 422 response if `query` doesn't have exactly one entry,
-/// "starts_from" mapped to a value representing an event ID.
-fn parse_query(query: HashMap<String, String>) -> Result<Option<Id>, Response> {
-    if query.is_empty() {
-        return Ok(None);
+/// Returns a 422 response if `query` has more than one of "start_from" mapped to a value
+/// representing an event ID, "start_from_height" mapped to a value representing a block height or
+/// "start_from_era" mapped to a value representing an era ID, or if it has any entries other than
+/// these and "account" mapped to a hex-encoded public key, "contract" mapped to a contract or
+/// contract package address and "event_type" mapped to comma-separated event type names.
+fn parse_query(
+    query: HashMap<String, String>,
+) -> Result<(Option<StartFrom>, ContentFilter), Response> {
+    let mut maybe_start_from = None;
+    let mut content_filter = ContentFilter::default();
+    for (field, value) in &query {
+        let start_from = match field.as_str() {
+            QUERY_FIELD => value.parse::<Id>().ok().map(StartFrom::EventId),
+            BLOCK_HEIGHT_QUERY_FIELD => value.parse::<u64>().ok().map(StartFrom::BlockHeight),
+            ERA_QUERY_FIELD => value
+                .parse::<u64>()
+                .ok()
+                .map(|era_id| StartFrom::Era(EraId::new(era_id))),
+            ACCOUNT_QUERY_FIELD => {
+                let account = PublicKey::from_hex(value).map_err(|_| create_422())?;
+                content_filter.account = Some(account);
+                continue;
+            }
+            CONTRACT_QUERY_FIELD => {
+                let contract = parse_contract_address(value).ok_or_else(create_422)?;
+                content_filter.contract = Some(contract);
+                continue;
+            }
+            EVENT_TYPE_QUERY_FIELD => {
+                let event_types = value
+                    .split(',')
+                    .map(EventFilter::from_event_type)
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(create_422)?;
+                content_filter.event_types = Some(event_types);
+                continue;
+            }
+            _ => None,
+        };
+        if start_from.is_none() || maybe_start_from.is_some() {
+            return Err(create_422());
+        }
+        maybe_start_from = start_from;
     }
+    Ok((maybe_start_from, content_filter))
+}
 
-    if query.len() > 1 {
-        return Err(create_422());
+/// Parses a contract or contract package address, given either as hex or as a formatted
+/// `ContractHash`, `ContractPackageHash` or `Key::Hash`.
+pub(super) fn parse_contract_address(value: &str) -> Option<HashAddr> {
+    if let Ok(contract_hash) = ContractHash::from_formatted_str(value) {
+        return Some(contract_hash.value());
     }
-
-    match query
-        .get(QUERY_FIELD)
-        .and_then(|id_str| id_str.parse::<Id>().ok())
-    {
-        Some(id) => Ok(Some(id)),
-        None => Err(create_422()),
+    if let Ok(contract_package_hash) = ContractPackageHash::from_formatted_str(value) {
+        return Some(contract_package_hash.value());
     }
+    if let Ok(Key::Hash(address)) = Key::from_formatted_str(value) {
+        return Some(address);
+    }
+    let bytes = checksummed_hex::decode(value).ok()?;
+    HashAddr::try_from(bytes.as_slice()).ok()
 }
 
 /// Creates a 404 response with a useful error message in the body.
@@ -384,16 +556,33 @@ fn create_404() -> Response {
 /// string.
 fn create_422() -> Response {
     let mut response = Response::new(Body::from(format!(
-        "invalid query: expected single field '{}=<EVENT ID>'\n",
-        QUERY_FIELD
+        "invalid query: expected at most one of '{}=<EVENT ID>', '{}=<BLOCK HEIGHT>' or \
+        '{}=<ERA ID>', optionally with '{}=<PUBLIC KEY>', '{}=<CONTRACT HASH>' and \
+        '{}=<EVENT TYPE>[,<EVENT TYPE>...]'\n",
+        QUERY_FIELD,
+        BLOCK_HEIGHT_QUERY_FIELD,
+        ERA_QUERY_FIELD,
+        ACCOUNT_QUERY_FIELD,
+        CONTRACT_QUERY_FIELD,
+        EVENT_TYPE_QUERY_FIELD
     )));
     *response.status_mut() = StatusCode::UNPROCESSABLE_ENTITY;
     response
 }
 
+/// Creates a 410 response (Gone) to be returned if the events from the requested block height or
+/// era are not held in the event log.
+fn create_410() -> Response {
+    let mut response = Response::new(Body::from(
+        "events from the requested block height or era are not available\n",
+    ));
+    *response.status_mut() = StatusCode::GONE;
+    response
+}
+
 /// Creates a 503 response (Service Unavailable) to be returned if the server has too many
 /// subscribers.
-fn create_503() -> Response {
+pub(super) fn create_503() -> Response {
     let mut response = Response::new(Body::from("server has reached limit of subscribers"));
     *response.status_mut() = StatusCode::SERVICE_UNAVAILABLE;
     response
@@ -407,8 +596,14 @@ pub(super) struct ChannelsAndFilter {
 
 impl ChannelsAndFilter {
     /// Creates the message-passing channels required to run the event-stream server and the warp
-    /// filter for the event-stream server.
-    pub(super) fn new(broadcast_channel_size: usize, max_concurrent_subscribers: u32) -> Self {
+    /// filter for the event-stream server, covering both the SSE and WebSocket endpoints.
+    pub(super) fn new(
+        broadcast_channel_size: usize,
+        max_concurrent_subscribers: u32,
+        event_log: Option<Arc<EventLog>>,
+        api_version: ProtocolVersion,
+        flow_control: FlowControl,
+    ) -> Self {
         // Create a channel to broadcast new events to all subscribed clients' streams.
         let (event_broadcaster, _) = broadcast::channel(broadcast_channel_size);
         let cloned_broadcaster = event_broadcaster.clone();
@@ -441,18 +636,43 @@ impl ChannelsAndFilter {
                 None => return create_404(),
             };
 
-            let start_from = match parse_query(query) {
-                Ok(maybe_id) => maybe_id,
+            let (maybe_start_from, content_filter) = match parse_query(query) {
+                Ok(parsed_query) => parsed_query,
                 Err(error_response) => return error_response,
             };
 
+            // Work out where in the event log any requested replay starts.
+            let replay_start = match (maybe_start_from, event_log.as_ref()) {
+                (Some(StartFrom::BlockHeight(block_height)), Some(event_log)) => {
+                    event_log.replay_start_for_block_height(block_height)
+                }
+                (Some(StartFrom::Era(era_id)), Some(event_log)) => {
+                    event_log.replay_start_for_era(era_id)
+                }
+                (Some(StartFrom::BlockHeight(_) | StartFrom::Era(_)), None) => {
+                    ReplayStart::Unavailable
+                }
+                (Some(StartFrom::EventId(_)) | None, _) => ReplayStart::Live,
+            };
+            let replay_from = match replay_start {
+                ReplayStart::Sequence(sequence) => Some(sequence),
+                ReplayStart::Live => None,
+                ReplayStart::Unavailable => return create_410(),
+            };
+            let start_from = match maybe_start_from {
+                Some(StartFrom::EventId(id)) => Some(id),
+                _ => None,
+            };
+
             // Create a channel for the client's handler to receive the stream of initial events.
-            let (initial_events_sender, initial_events_receiver) = mpsc::unbounded_channel();
+            let (initial_events_sender, initial_events_receiver) =
+                mpsc::channel(INITIAL_EVENTS_CHANNEL_SIZE);
 
             // Supply the server with the sender part of the channel along with the client's
             // requested starting point.
             let new_subscriber_info = NewSubscriberInfo {
                 start_from,
+                replay_from,
                 initial_events_sender,
             };
             if new_subscriber_info_sender
@@ -469,18 +689,30 @@ impl ChannelsAndFilter {
                 initial_events_receiver,
                 ongoing_events_receiver,
                 event_filter,
+                content_filter,
                 remote_address,
             )))
             .into_response()
         };
 
-        let sse_filter = warp::get()
-            .and(path(SSE_API_ROOT_PATH))
-            .and(path::param::<String>())
-            .and(path::end())
-            .and(warp::query())
-            .and(addr::remote())
-            .map(serve)
+        // The WebSocket filter must be tried first, as its path would otherwise be handled as an
+        // invalid SSE path.
+        let ws_filter = ws_server::create_ws_filter(
+            event_broadcaster.clone(),
+            api_version,
+            max_concurrent_subscribers,
+            flow_control,
+        );
+
+        let sse_filter = ws_filter
+            .or(warp::get()
+                .and(path(SSE_API_ROOT_PATH))
+                .and(path::param::<String>())
+                .and(path::end())
+                .and(warp::query())
+                .and(addr::remote())
+                .map(serve))
+            .unify()
             .or_else(|_| async move { Ok::<_, Rejection>((create_404(),)) })
             .boxed();
 
@@ -504,12 +736,13 @@ impl ChannelsAndFilter {
 /// variant via the channel).  This channel will receive all SSEs created from the moment the client
 /// subscribed to the server's event stream.
 ///
-/// It also takes an `EventFilter` which causes events to which the client didn't subscribe to be
-/// skipped.
+/// It also takes an `EventFilter` and a `ContentFilter` which cause events to which the client
+/// didn't subscribe to be skipped before they are serialized.
 fn stream_to_client(
-    initial_events: mpsc::UnboundedReceiver<ServerSentEvent>,
+    initial_events: mpsc::Receiver<ServerSentEvent>,
     ongoing_events: broadcast::Receiver<BroadcastChannelMessage>,
     event_filter: &'static [EventFilter],
+    content_filter: ContentFilter,
     remote_address: String,
 ) -> impl Stream<Item = Result<WarpServerSentEvent, RecvError>> + 'static {
     // Keep a record of the IDs of the events delivered via the `initial_events` receiver.
@@ -548,8 +781,9 @@ fn stream_to_client(
         .take_while(|result| future::ready(!matches!(result, Err(RecvError::Closed))));
 
     // Serve the initial events followed by the ongoing ones, filtering as dictated by the
-    // `event_filter`.
-    UnboundedReceiverStream::new(initial_events)
+    // `event_filter` and `content_filter`.
+    let content_filter = Arc::new(content_filter);
+    ReceiverStream::new(initial_events)
         .map(move |event| {
             if let Some(id) = event.id {
                 let _ = initial_stream_ids.write().unwrap().insert(id);
@@ -557,10 +791,14 @@ fn stream_to_client(
             Ok(event)
         })
         .chain(ongoing_stream)
-        .filter_map(move |result| async move {
-            match result {
-                Ok(event) => filter_map_server_sent_event(&event, event_filter).await,
-                Err(error) => Some(Err(error)),
+        .filter_map(move |result| {
+            let content_filter = Arc::clone(&content_filter);
+            async move {
+                match result {
+                    Ok(event) if !content_filter.should_include(&event.data) => None,
+                    Ok(event) => filter_map_server_sent_event(&event, event_filter).await,
+                    Err(error) => Some(Err(error)),
+                }
             }
         })
 }
@@ -569,7 +807,7 @@ fn stream_to_client(
 mod tests {
     use std::iter;
 
-    use casper_types::testing::TestRng;
+    use casper_types::{testing::TestRng, Transform, TransformEntry};
 
     use super::*;
     use crate::logging;
@@ -592,6 +830,105 @@ mod tests {
         );
     }
 
+    /// Parses the given query string into a `ContentFilter`.
+    fn content_filter(query: &[(&str, String)]) -> ContentFilter {
+        let query = query
+            .iter()
+            .map(|(field, value)| (field.to_string(), value.clone()))
+            .collect();
+        let (maybe_start_from, content_filter) = parse_query(query).unwrap();
+        assert!(maybe_start_from.is_none());
+        content_filter
+    }
+
+    /// This test checks that events are filtered by the account, contract and event types given in
+    /// the query string.
+    #[test]
+    fn should_filter_by_content() {
+        let mut rng = TestRng::new();
+
+        let api_version = SseData::random_api_version(&mut rng);
+        let block_added = SseData::random_block_added(&mut rng);
+        let (deploy_accepted, deploy) = SseData::random_deploy_accepted(&mut rng);
+        let deploy_processed = SseData::random_deploy_processed(&mut rng);
+        let unrelated_step = SseData::random_step(&mut rng);
+        let contract = [7; 32];
+        let step = match SseData::random_step(&mut rng) {
+            SseData::Step {
+                era_id,
+                mut execution_effect,
+            } => {
+                execution_effect.transforms.push(TransformEntry {
+                    key: Key::Hash(contract).to_formatted_string(),
+                    transform: Transform::Identity,
+                });
+                SseData::Step {
+                    era_id,
+                    execution_effect,
+                }
+            }
+            _ => unreachable!(),
+        };
+
+        let all_events = [
+            &api_version,
+            &block_added,
+            &deploy_accepted,
+            &deploy_processed,
+            &unrelated_step,
+            &step,
+            &SseData::Shutdown,
+        ];
+        let included = |content_filter: &ContentFilter| -> Vec<&SseData> {
+            all_events
+                .iter()
+                .copied()
+                .filter(|data| content_filter.should_include(data))
+                .collect()
+        };
+
+        assert_eq!(included(&content_filter(&[])), all_events.to_vec());
+
+        let by_account =
+            content_filter(&[(ACCOUNT_QUERY_FIELD, deploy.header().account().to_hex())]);
+        assert_eq!(
+            included(&by_account),
+            vec![&api_version, &deploy_accepted, &SseData::Shutdown]
+        );
+
+        for formatted_contract in [
+            base16::encode_lower(&contract),
+            Key::Hash(contract).to_formatted_string(),
+            ContractHash::new(contract).to_formatted_string(),
+        ] {
+            let by_contract = content_filter(&[(CONTRACT_QUERY_FIELD, formatted_contract)]);
+            assert_eq!(
+                included(&by_contract),
+                vec![&api_version, &step, &SseData::Shutdown]
+            );
+        }
+
+        let by_event_type =
+            content_filter(&[(EVENT_TYPE_QUERY_FIELD, "BlockAdded,Step".to_string())]);
+        assert_eq!(
+            included(&by_event_type),
+            vec![
+                &api_version,
+                &block_added,
+                &unrelated_step,
+                &step,
+                &SseData::Shutdown
+            ]
+        );
+
+        // All filters must be satisfied.
+        let combined = content_filter(&[
+            (EVENT_TYPE_QUERY_FIELD, "DeployProcessed".to_string()),
+            (ACCOUNT_QUERY_FIELD, deploy.header().account().to_hex()),
+        ]);
+        assert_eq!(included(&combined), vec![&api_version, &SseData::Shutdown]);
+    }
+
     /// This test checks that events with correct IDs (i.e. all types have an ID except for
     /// `ApiVersion`) are filtered properly.
     #[tokio::test]
@@ -833,13 +1170,14 @@ mod tests {
                 &mut deploys,
             );
 
-            let (initial_events_sender, initial_events_receiver) = mpsc::unbounded_channel();
+            let (initial_events_sender, initial_events_receiver) =
+                mpsc::channel(initial_events.len());
             let (ongoing_events_sender, ongoing_events_receiver) =
                 broadcast::channel(NUM_INITIAL_EVENTS + NUM_ONGOING_EVENTS + 1);
 
             // Send all the events.
             for event in initial_events.iter().cloned() {
-                initial_events_sender.send(event).unwrap();
+                initial_events_sender.try_send(event).unwrap();
             }
             for event in ongoing_events.iter().cloned() {
                 let _ = ongoing_events_sender
@@ -855,6 +1193,7 @@ mod tests {
                 initial_events_receiver,
                 ongoing_events_receiver,
                 get_filter(path_filter).unwrap(),
+                ContentFilter::default(),
                 "127.0.0.1:3456".to_string(),
             )
             .collect()
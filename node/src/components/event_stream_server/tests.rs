@@ -27,7 +27,8 @@ use casper_types::testing::TestRng;
 use super::*;
 use crate::{logging, testing::assert_schema};
 use sse_server::{
-    DeployAccepted, Id, QUERY_FIELD, SSE_API_DEPLOYS_PATH as DEPLOYS_PATH,
+    DeployAccepted, Id, ACCOUNT_QUERY_FIELD, BLOCK_HEIGHT_QUERY_FIELD, CONTRACT_QUERY_FIELD,
+    ERA_QUERY_FIELD, EVENT_TYPE_QUERY_FIELD, QUERY_FIELD, SSE_API_DEPLOYS_PATH as DEPLOYS_PATH,
     SSE_API_MAIN_PATH as MAIN_PATH, SSE_API_ROOT_PATH as ROOT_PATH,
     SSE_API_SIGNATURES_PATH as SIGS_PATH,
 };
@@ -82,6 +83,9 @@ struct ServerBehavior {
     /// If `Some`, sets the `max_concurrent_subscribers` server config value, otherwise uses the
     /// config default.
     max_concurrent_subscribers: Option<u32>,
+    /// If `Some`, sets the `event_log_length` server config value, otherwise the event log is
+    /// disabled.
+    event_log_length: Option<u32>,
     clients: Vec<ClientSyncBehavior>,
 }
 
@@ -94,6 +98,7 @@ impl ServerBehavior {
             has_delay_between_events: true,
             repeat_events: false,
             max_concurrent_subscribers: None,
+            event_log_length: None,
             clients: Vec::new(),
         }
     }
@@ -107,6 +112,7 @@ impl ServerBehavior {
             has_delay_between_events: false,
             repeat_events: true,
             max_concurrent_subscribers: None,
+            event_log_length: None,
             clients: Vec::new(),
         }
     }
@@ -124,6 +130,11 @@ impl ServerBehavior {
         self.max_concurrent_subscribers = Some(count);
     }
 
+    /// Sets the `event_log_length` server config value.
+    fn set_event_log_length(&mut self, length: u32) {
+        self.event_log_length = Some(length);
+    }
+
     /// Waits for all clients which specified they wanted to join just before the given event ID.
     async fn wait_for_clients(&self, id: Id) {
         for client_behavior in &self.clients {
@@ -262,6 +273,7 @@ impl TestFixture {
             max_concurrent_subscribers: server_behavior
                 .max_concurrent_subscribers
                 .unwrap_or(Config::default().max_concurrent_subscribers),
+            event_log_length: server_behavior.event_log_length,
             ..Default::default()
         };
         let mut server = EventStreamServer::new(
@@ -955,11 +967,28 @@ async fn should_handle_bad_url_query() {
         format!("{}?{}=0&extra=1", main_url, QUERY_FIELD),
         format!("{}?{}=0&extra=1", deploys_url, QUERY_FIELD),
         format!("{}?{}=0&extra=1", sigs_url, QUERY_FIELD),
+        format!("{}?{}=-1", main_url, BLOCK_HEIGHT_QUERY_FIELD),
+        format!(
+            "{}?{}=0&{}=0",
+            main_url, BLOCK_HEIGHT_QUERY_FIELD, ERA_QUERY_FIELD
+        ),
+        format!("{}?{}=not-integer", main_url, ERA_QUERY_FIELD),
+        format!("{}?{}=0&{}=0", main_url, QUERY_FIELD, ERA_QUERY_FIELD),
+        format!("{}?{}=not-a-key", main_url, ACCOUNT_QUERY_FIELD),
+        format!("{}?{}=00", main_url, CONTRACT_QUERY_FIELD),
+        format!("{}?{}=BlockAdded,Unknown", main_url, EVENT_TYPE_QUERY_FIELD),
     ];
 
     let expected_body = format!(
-        "invalid query: expected single field '{}=<EVENT ID>'",
-        QUERY_FIELD
+        "invalid query: expected at most one of '{}=<EVENT ID>', '{}=<BLOCK HEIGHT>' or \
+        '{}=<ERA ID>', optionally with '{}=<PUBLIC KEY>', '{}=<CONTRACT HASH>' and \
+        '{}=<EVENT TYPE>[,<EVENT TYPE>...]'",
+        QUERY_FIELD,
+        BLOCK_HEIGHT_QUERY_FIELD,
+        ERA_QUERY_FIELD,
+        ACCOUNT_QUERY_FIELD,
+        CONTRACT_QUERY_FIELD,
+        EVENT_TYPE_QUERY_FIELD
     );
     for url in &urls {
         let response = reqwest::get(url).await.unwrap();
@@ -980,6 +1009,89 @@ async fn should_handle_bad_url_query() {
     fixture.stop_server().await;
 }
 
+/// Check that a client can replay events from a given block height, including events emitted
+/// before the server restarted.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn should_replay_events_from_block_height_across_restart() {
+    let mut rng = crate::new_rng();
+    let mut fixture = TestFixture::new(&mut rng);
+
+    // The block added events are every seventh event, starting with the first.
+    const REPLAY_FROM_EVENT: usize = 49;
+    let block_height = match &fixture.events[REPLAY_FROM_EVENT] {
+        SseData::BlockAdded { block, .. } => block.header.height,
+        _ => panic!("should be a block added event"),
+    };
+
+    let replayed_events = {
+        // Run the first server to emit and log the 100 events.
+        let mut server_behavior = ServerBehavior::new();
+        server_behavior.set_event_log_length(EVENT_COUNT);
+        let barrier = server_behavior.add_client_sync_before_event(0);
+        let server_address = fixture.run_server(server_behavior).await;
+
+        let url = url(server_address, MAIN_PATH, None);
+        let (_expected_events, final_id) = fixture.all_filtered_events(MAIN_PATH);
+        let _ = subscribe(&url, barrier, final_id, "client 1")
+            .await
+            .unwrap();
+        fixture.stop_server().await;
+        fixture
+            .filtered_events(MAIN_PATH, fixture.first_event_id + REPLAY_FROM_EVENT as Id)
+            .0
+    };
+
+    // Start a new server, and subscribe from the block height just before it emits its first event.
+    let mut server_behavior = ServerBehavior::new();
+    server_behavior.set_event_log_length(EVENT_COUNT);
+    let barrier = server_behavior.add_client_sync_before_event(EVENT_COUNT + 1);
+    let server_address = fixture.run_server(server_behavior).await;
+
+    let url = format!(
+        "http://{}/{}/{}?{}={}",
+        server_address, ROOT_PATH, MAIN_PATH, BLOCK_HEIGHT_QUERY_FIELD, block_height
+    );
+    let (new_events, final_id) = fixture.all_filtered_events(MAIN_PATH);
+    let received_events = subscribe(&url, barrier, final_id, "client 2")
+        .await
+        .unwrap();
+    fixture.stop_server().await;
+
+    // The client should receive the replayed events from the first run followed by the new ones.
+    let expected_events: Vec<_> = replayed_events
+        .into_iter()
+        .chain(new_events.into_iter().skip(1))
+        .collect();
+    assert_eq!(received_events, expected_events);
+}
+
+/// Check that a client requesting replay from a block height gets a helpful error response if the
+/// event log is disabled.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn should_reject_replay_from_block_height_without_event_log() {
+    let mut rng = crate::new_rng();
+    let mut fixture = TestFixture::new(&mut rng);
+
+    let server_address = fixture.run_server(ServerBehavior::new()).await;
+
+    for query_field in [BLOCK_HEIGHT_QUERY_FIELD, ERA_QUERY_FIELD] {
+        let url = format!(
+            "http://{}/{}/{}?{}=0",
+            server_address, ROOT_PATH, MAIN_PATH, query_field
+        );
+        let response = reqwest::get(&url).await.unwrap();
+        assert_eq!(response.status(), StatusCode::GONE, "URL: {}", url);
+        assert_eq!(
+            response.text().await.unwrap().trim(),
+            "events from the requested block height or era are not available",
+            "URL: {}",
+            url
+        );
+    }
+
+    fixture.stop_server().await;
+}
+
 /// Check that a server which restarts continues from the previous numbering of event IDs.
 async fn should_persist_event_ids(path: &str) {
     let mut rng = crate::new_rng();
@@ -0,0 +1,634 @@
+//! Types and functions used by the http server to manage WebSocket subscriptions.
+//!
+//! A WebSocket client multiplexes several subscriptions, each to a single topic, over one
+//! connection.  Messages in both directions are JSON-encoded text frames.
+//!
+//! Each subscription has its own flow control: no more than `max_unacked_events` events are sent
+//! for a subscription until the client acknowledges them, with later events queued in the
+//! meantime.  A client which lets too many events queue up for a subscription is disconnected.
+//! The number of subscriptions a client may hold at once is limited too.
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    net::SocketAddr,
+};
+
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    select,
+    sync::broadcast::{self, error::RecvError},
+};
+use tracing::{debug, info, warn};
+use warp::{
+    addr,
+    filters::BoxedFilter,
+    path,
+    reply::Response,
+    ws::{Message, WebSocket, Ws},
+    Filter, Reply,
+};
+
+use casper_types::{ExecutionResult, HashAddr, ProtocolVersion, PublicKey};
+
+use super::{
+    sse_server::{
+        self, parse_contract_address, BroadcastChannelMessage, Id, ServerSentEvent,
+        SSE_API_ROOT_PATH,
+    },
+    SseData,
+};
+
+/// The URL path part to connect to the WebSocket endpoint.
+pub const WS_API_PATH: &str = "ws";
+
+/// Identifies a subscription within a single WebSocket connection.  It is chosen by the client.
+pub(super) type SubscriptionId = u32;
+
+/// The topic of a subscription, as requested by a client.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub(super) enum Topic {
+    /// `BlockAdded` events.
+    Blocks,
+    /// `FinalitySignature` events.
+    FinalitySignatures,
+    /// `DeployProcessed` events of deploys whose effects read or write the key of the given
+    /// contract or contract package.
+    ContractEvents {
+        /// The contract or contract package hash, as hex or a formatted string.
+        contract: String,
+    },
+    /// `DeployProcessed` events of deploys sent from the given account which made transfers.
+    AccountTransfers {
+        /// The account's public key.
+        account: PublicKey,
+    },
+}
+
+/// A message sent from a client to the server.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(tag = "action", rename_all = "snake_case", deny_unknown_fields)]
+pub(super) enum ClientMessage {
+    /// Subscribe to a topic under the given ID.
+    Subscribe {
+        subscription_id: SubscriptionId,
+        topic: Topic,
+    },
+    /// Cancel the subscription with the given ID.
+    Unsubscribe { subscription_id: SubscriptionId },
+    /// Acknowledge all events sent for the subscription up to and including the given event.
+    Ack {
+        subscription_id: SubscriptionId,
+        event_id: Id,
+    },
+}
+
+/// A message sent from the server to a client.
+#[derive(Serialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(super) enum ServerMessage<'a> {
+    /// The version of this node's API server.  This is always the first message sent.
+    ApiVersion { api_version: ProtocolVersion },
+    /// The subscription was created.
+    Subscribed { subscription_id: SubscriptionId },
+    /// The subscription was cancelled.
+    Unsubscribed { subscription_id: SubscriptionId },
+    /// An event for the subscription.
+    Event {
+        subscription_id: SubscriptionId,
+        event_id: Id,
+        data: &'a SseData,
+    },
+    /// The client's last message was invalid.
+    Error { message: String },
+    /// The node is about to shut down.
+    Shutdown,
+}
+
+/// A topic with its parameters parsed.
+#[derive(Clone, Debug)]
+enum ResolvedTopic {
+    Blocks,
+    FinalitySignatures,
+    ContractEvents(HashAddr),
+    AccountTransfers(PublicKey),
+}
+
+impl ResolvedTopic {
+    fn new(topic: Topic) -> Result<Self, String> {
+        match topic {
+            Topic::Blocks => Ok(ResolvedTopic::Blocks),
+            Topic::FinalitySignatures => Ok(ResolvedTopic::FinalitySignatures),
+            Topic::ContractEvents { contract } => parse_contract_address(&contract)
+                .map(ResolvedTopic::ContractEvents)
+                .ok_or_else(|| format!("invalid contract hash '{}'", contract)),
+            Topic::AccountTransfers { account } => Ok(ResolvedTopic::AccountTransfers(account)),
+        }
+    }
+
+    fn should_include(&self, data: &SseData) -> bool {
+        match (self, data) {
+            (ResolvedTopic::Blocks, SseData::BlockAdded { .. }) => true,
+            (ResolvedTopic::FinalitySignatures, SseData::FinalitySignature(_)) => true,
+            (
+                ResolvedTopic::ContractEvents(contract),
+                SseData::DeployProcessed {
+                    execution_result, ..
+                },
+            ) => match &**execution_result {
+                ExecutionResult::Failure { effect, .. }
+                | ExecutionResult::Success { effect, .. } => {
+                    sse_server::touches_contract(effect, contract)
+                }
+            },
+            (
+                ResolvedTopic::AccountTransfers(account),
+                SseData::DeployProcessed {
+                    account: initiator,
+                    execution_result,
+                    ..
+                },
+            ) => {
+                let transfers = match &**execution_result {
+                    ExecutionResult::Failure { transfers, .. }
+                    | ExecutionResult::Success { transfers, .. } => transfers,
+                };
+                **initiator == *account && !transfers.is_empty()
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A single subscription of a client.
+#[derive(Debug)]
+struct Subscription {
+    topic: ResolvedTopic,
+    /// The IDs of the events sent but not yet acknowledged, oldest first.
+    unacked: VecDeque<Id>,
+    /// The events waiting to be sent, oldest first.
+    pending: VecDeque<ServerSentEvent>,
+}
+
+/// The limits applied to a WebSocket client and each of its subscriptions.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct FlowControl {
+    /// The maximum number of events sent but not yet acknowledged.
+    pub(super) max_unacked_events: u32,
+    /// The maximum number of events waiting to be sent before the client is disconnected.
+    pub(super) max_pending_events: u32,
+    /// The maximum number of subscriptions held at once.
+    pub(super) max_subscriptions: u32,
+}
+
+/// Creates the warp filter for the WebSocket endpoint.
+pub(super) fn create_ws_filter(
+    broadcaster: broadcast::Sender<BroadcastChannelMessage>,
+    api_version: ProtocolVersion,
+    max_concurrent_subscribers: u32,
+    flow_control: FlowControl,
+) -> BoxedFilter<(Response,)> {
+    warp::get()
+        .and(path(SSE_API_ROOT_PATH))
+        .and(path(WS_API_PATH))
+        .and(path::end())
+        .and(warp::ws())
+        .and(addr::remote())
+        .map(move |ws: Ws, maybe_remote_address: Option<SocketAddr>| {
+            let remote_address = match maybe_remote_address {
+                Some(address) => address.to_string(),
+                None => "unknown".to_string(),
+            };
+
+            // WebSocket clients count towards the same limit as SSE clients.
+            if broadcaster.receiver_count() >= max_concurrent_subscribers as usize {
+                info!(
+                    %remote_address,
+                    %max_concurrent_subscribers,
+                    "event stream server has max subscribers: rejecting new websocket client"
+                );
+                return sse_server::create_503();
+            }
+
+            let ongoing_events = broadcaster.subscribe();
+            ws.on_upgrade(move |socket| {
+                serve_client(
+                    socket,
+                    ongoing_events,
+                    api_version,
+                    flow_control,
+                    remote_address,
+                )
+            })
+            .into_response()
+        })
+        .boxed()
+}
+
+/// Serves a single WebSocket client until it disconnects, or the server shuts down.
+async fn serve_client(
+    socket: WebSocket,
+    mut ongoing_events: broadcast::Receiver<BroadcastChannelMessage>,
+    api_version: ProtocolVersion,
+    flow_control: FlowControl,
+    remote_address: String,
+) {
+    let (mut sender, mut receiver) = socket.split();
+    let mut subscriptions: BTreeMap<SubscriptionId, Subscription> = BTreeMap::new();
+    let mut outgoing = vec![to_message(&ServerMessage::ApiVersion { api_version })];
+
+    loop {
+        for message in outgoing.drain(..) {
+            if sender.send(message).await.is_err() {
+                debug!(%remote_address, "websocket client disconnected");
+                return;
+            }
+        }
+
+        select! {
+            maybe_message = receiver.next() => {
+                let text = match maybe_message {
+                    Some(Ok(message)) if message.is_close() => break,
+                    Some(Ok(message)) => match message.to_str() {
+                        Ok(text) => text.to_string(),
+                        // Ignore pings, pongs and binary messages.
+                        Err(()) => continue,
+                    },
+                    Some(Err(_)) | None => break,
+                };
+                match serde_json::from_str::<ClientMessage>(&text) {
+                    Ok(client_message) => handle_client_message(
+                        client_message,
+                        &mut subscriptions,
+                        flow_control.max_subscriptions,
+                        &mut outgoing,
+                    ),
+                    Err(error) => outgoing.push(error_message(format!(
+                        "invalid message: {}",
+                        error
+                    ))),
+                }
+            }
+
+            result = ongoing_events.recv() => {
+                match result {
+                    Ok(BroadcastChannelMessage::ServerSentEvent(event)) => {
+                        for subscription in subscriptions.values_mut() {
+                            if subscription.topic.should_include(&event.data) {
+                                subscription.pending.push_back(event.clone());
+                            }
+                        }
+                    }
+                    Ok(BroadcastChannelMessage::Shutdown) | Err(RecvError::Closed) => {
+                        let _ = sender.send(to_message(&ServerMessage::Shutdown)).await;
+                        break;
+                    }
+                    Err(RecvError::Lagged(lagged_count)) => {
+                        info!(
+                            %remote_address,
+                            %lagged_count,
+                            "websocket client lagged: dropping connection to client",
+                        );
+                        break;
+                    }
+                }
+            }
+        }
+
+        for (subscription_id, subscription) in subscriptions.iter_mut() {
+            if subscription.pending.len() > flow_control.max_pending_events as usize {
+                info!(
+                    %remote_address,
+                    %subscription_id,
+                    "websocket client not acknowledging events: dropping connection to client",
+                );
+                let _ = sender
+                    .send(to_message(&error_message(format!(
+                        "too many unacknowledged events for subscription {}",
+                        subscription_id
+                    ))))
+                    .await;
+                let _ = sender.close().await;
+                return;
+            }
+            while subscription.unacked.len() < flow_control.max_unacked_events as usize {
+                let event = match subscription.pending.pop_front() {
+                    Some(event) => event,
+                    None => break,
+                };
+                let event_id = match event.id {
+                    Some(event_id) => event_id,
+                    None => continue,
+                };
+                outgoing.push(to_message(&ServerMessage::Event {
+                    subscription_id: *subscription_id,
+                    event_id,
+                    data: &event.data,
+                }));
+                subscription.unacked.push_back(event_id);
+            }
+        }
+    }
+
+    let _ = sender.close().await;
+}
+
+/// Applies the client's message to its subscriptions, of which it may hold at most
+/// `max_subscriptions`, adding any responses to `outgoing`.
+fn handle_client_message(
+    client_message: ClientMessage,
+    subscriptions: &mut BTreeMap<SubscriptionId, Subscription>,
+    max_subscriptions: u32,
+    outgoing: &mut Vec<Message>,
+) {
+    match client_message {
+        ClientMessage::Subscribe {
+            subscription_id,
+            topic,
+        } => {
+            if subscriptions.contains_key(&subscription_id) {
+                outgoing.push(error_message(format!(
+                    "subscription {} already exists",
+                    subscription_id
+                )));
+                return;
+            }
+            if subscriptions.len() >= max_subscriptions as usize {
+                outgoing.push(error_message(format!(
+                    "cannot hold more than {} subscriptions",
+                    max_subscriptions
+                )));
+                return;
+            }
+            match ResolvedTopic::new(topic) {
+                Ok(topic) => {
+                    let _ = subscriptions.insert(
+                        subscription_id,
+                        Subscription {
+                            topic,
+                            unacked: VecDeque::new(),
+                            pending: VecDeque::new(),
+                        },
+                    );
+                    outgoing.push(to_message(&ServerMessage::Subscribed { subscription_id }));
+                }
+                Err(message) => outgoing.push(error_message(message)),
+            }
+        }
+        ClientMessage::Unsubscribe { subscription_id } => {
+            if subscriptions.remove(&subscription_id).is_some() {
+                outgoing.push(to_message(&ServerMessage::Unsubscribed { subscription_id }));
+            } else {
+                outgoing.push(unknown_subscription(subscription_id));
+            }
+        }
+        ClientMessage::Ack {
+            subscription_id,
+            event_id,
+        } => {
+            let subscription = match subscriptions.get_mut(&subscription_id) {
+                Some(subscription) => subscription,
+                None => {
+                    outgoing.push(unknown_subscription(subscription_id));
+                    return;
+                }
+            };
+            match subscription.unacked.iter().position(|id| *id == event_id) {
+                Some(index) => {
+                    let _ = subscription.unacked.drain(..=index);
+                }
+                None => outgoing.push(error_message(format!(
+                    "event {} is not awaiting acknowledgement for subscription {}",
+                    event_id, subscription_id
+                ))),
+            }
+        }
+    }
+}
+
+fn to_message(server_message: &ServerMessage) -> Message {
+    match serde_json::to_string(server_message) {
+        Ok(json) => Message::text(json),
+        Err(error) => {
+            warn!(%error, ?server_message, "failed to jsonify websocket message");
+            Message::text(String::new())
+        }
+    }
+}
+
+fn error_message(message: String) -> Message {
+    to_message(&ServerMessage::Error { message })
+}
+
+fn unknown_subscription(subscription_id: SubscriptionId) -> Message {
+    error_message(format!("no subscription {}", subscription_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+
+    use casper_types::{testing::TestRng, AsymmetricType};
+
+    use super::*;
+
+    const FLOW_CONTROL: FlowControl = FlowControl {
+        max_unacked_events: 2,
+        max_pending_events: 10,
+        max_subscriptions: 3,
+    };
+
+    /// Receives the next message from the client and parses it as JSON.
+    async fn recv_json(client: &mut warp::test::WsClient) -> serde_json::Value {
+        let message = client.recv().await.unwrap();
+        serde_json::from_str(message.to_str().unwrap()).unwrap()
+    }
+
+    /// This test checks that client messages are parsed from their documented JSON form.
+    #[test]
+    fn should_parse_client_messages() {
+        let mut rng = TestRng::new();
+        let account = PublicKey::random(&mut rng);
+
+        let json = format!(
+            r#"{{"action":"subscribe","subscription_id":1,"topic":{{"type":"account_transfers","account":"{}"}}}}"#,
+            account.to_hex()
+        );
+        assert_eq!(
+            serde_json::from_str::<ClientMessage>(&json).unwrap(),
+            ClientMessage::Subscribe {
+                subscription_id: 1,
+                topic: Topic::AccountTransfers { account },
+            }
+        );
+
+        let json = r#"{"action":"ack","subscription_id":2,"event_id":7}"#;
+        assert_eq!(
+            serde_json::from_str::<ClientMessage>(json).unwrap(),
+            ClientMessage::Ack {
+                subscription_id: 2,
+                event_id: 7,
+            }
+        );
+
+        let json = r#"{"action":"subscribe","subscription_id":3,"topic":{"type":"unknown"}}"#;
+        assert!(serde_json::from_str::<ClientMessage>(json).is_err());
+    }
+
+    /// This test checks that a subscription with an invalid contract hash or a duplicate ID is
+    /// rejected, and that acknowledging an unknown event is reported as an error.
+    #[test]
+    fn should_reject_invalid_client_messages() {
+        let mut subscriptions = BTreeMap::new();
+        let mut outgoing = vec![];
+
+        handle_client_message(
+            ClientMessage::Subscribe {
+                subscription_id: 1,
+                topic: Topic::ContractEvents {
+                    contract: "not a hash".to_string(),
+                },
+            },
+            &mut subscriptions,
+            FLOW_CONTROL.max_subscriptions,
+            &mut outgoing,
+        );
+        assert!(subscriptions.is_empty());
+
+        for _ in 0..2 {
+            handle_client_message(
+                ClientMessage::Subscribe {
+                    subscription_id: 1,
+                    topic: Topic::Blocks,
+                },
+                &mut subscriptions,
+                FLOW_CONTROL.max_subscriptions,
+                &mut outgoing,
+            );
+        }
+        assert_eq!(subscriptions.len(), 1);
+
+        handle_client_message(
+            ClientMessage::Ack {
+                subscription_id: 1,
+                event_id: 0,
+            },
+            &mut subscriptions,
+            FLOW_CONTROL.max_subscriptions,
+            &mut outgoing,
+        );
+
+        let types: Vec<String> = outgoing
+            .iter()
+            .map(|message| {
+                let json: serde_json::Value = serde_json::from_str(message.to_str().unwrap())
+                    .expect("should parse server message");
+                json["type"].as_str().unwrap().to_string()
+            })
+            .collect();
+        assert_eq!(types, vec!["error", "subscribed", "error", "error"]);
+    }
+
+    /// This test checks that a client can't hold more than `max_subscriptions` subscriptions, but
+    /// can subscribe again once it has unsubscribed.
+    #[test]
+    fn should_limit_subscriptions_per_client() {
+        let subscribe = |subscription_id| ClientMessage::Subscribe {
+            subscription_id,
+            topic: Topic::Blocks,
+        };
+        let max_subscriptions = FLOW_CONTROL.max_subscriptions;
+        let client_messages = (0..=max_subscriptions)
+            .map(subscribe)
+            .chain(iter::once(ClientMessage::Unsubscribe {
+                subscription_id: 0,
+            }))
+            .chain(iter::once(subscribe(max_subscriptions)));
+
+        let mut subscriptions = BTreeMap::new();
+        let mut outgoing = vec![];
+        for client_message in client_messages {
+            handle_client_message(
+                client_message,
+                &mut subscriptions,
+                max_subscriptions,
+                &mut outgoing,
+            );
+        }
+
+        let types: Vec<String> = outgoing
+            .iter()
+            .map(|message| {
+                let json: serde_json::Value = serde_json::from_str(message.to_str().unwrap())
+                    .expect("should parse server message");
+                json["type"].as_str().unwrap().to_string()
+            })
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                "subscribed",
+                "subscribed",
+                "subscribed",
+                "error",
+                "unsubscribed",
+                "subscribed"
+            ]
+        );
+    }
+
+    /// This test checks that no more than `max_unacked_events` events are sent for a subscription
+    /// until the client acknowledges them.
+    #[tokio::test]
+    async fn should_apply_flow_control() {
+        let mut rng = TestRng::new();
+        let api_version = ProtocolVersion::from_parts(1, 2, 3);
+        let (broadcaster, _) = broadcast::channel(10);
+        let filter = create_ws_filter(broadcaster.clone(), api_version, 10, FLOW_CONTROL);
+
+        let mut client = warp::test::ws()
+            .path(&format!("/{}/{}", SSE_API_ROOT_PATH, WS_API_PATH))
+            .handshake(filter)
+            .await
+            .expect("should connect");
+
+        assert_eq!(recv_json(&mut client).await["type"], "api_version");
+        client.send_text(r#"{"action":"subscribe","subscription_id":5,"topic":{"type":"blocks"}}"#);
+        assert_eq!(recv_json(&mut client).await["type"], "subscribed");
+
+        for id in 0..3 {
+            let event = ServerSentEvent {
+                id: Some(id),
+                data: SseData::random_block_added(&mut rng),
+            };
+            broadcaster
+                .send(BroadcastChannelMessage::ServerSentEvent(event))
+                .unwrap();
+            // Events the subscription doesn't cover should not be sent.
+            let event = ServerSentEvent {
+                id: Some(100 + id),
+                data: SseData::random_finality_signature(&mut rng),
+            };
+            broadcaster
+                .send(BroadcastChannelMessage::ServerSentEvent(event))
+                .unwrap();
+        }
+
+        for expected_id in 0..2 {
+            let json = recv_json(&mut client).await;
+            assert_eq!(json["type"], "event");
+            assert_eq!(json["subscription_id"], 5);
+            assert_eq!(json["event_id"], expected_id);
+        }
+
+        // The third event is only sent once the first two are acknowledged.
+        client.send_text(r#"{"action":"ack","subscription_id":5,"event_id":1}"#);
+        let json = recv_json(&mut client).await;
+        assert_eq!(json["type"], "event");
+        assert_eq!(json["event_id"], 2);
+
+        broadcaster.send(BroadcastChannelMessage::Shutdown).unwrap();
+        assert_eq!(recv_json(&mut client).await["type"], "shutdown");
+    }
+}
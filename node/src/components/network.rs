@@ -26,6 +26,7 @@
 mod bincode_format;
 pub(crate) mod blocklist;
 mod chain_info;
+mod compression_format;
 mod config;
 mod counting_format;
 mod error;
@@ -92,6 +93,7 @@ pub(crate) use self::{
 use self::{
     blocklist::BlocklistJustification,
     chain_info::ChainInfo,
+    compression_format::CompressionFormat,
     counting_format::{ConnectionId, CountingFormat, Role},
     error::{ConnectionError, Result},
     event::{IncomingConnection, OutgoingConnection},
@@ -152,6 +154,9 @@ const PING_RETRIES: u16 = 5;
 pub(crate) struct OutgoingHandle<P> {
     #[data_size(skip)] // Unfortunately, there is no way to inspect an `UnboundedSender`.
     sender: UnboundedSender<MessageQueueItem<P>>,
+    /// Queue of consensus messages to a validator, sent ahead of everything in `sender`'s queue.
+    #[data_size(skip)]
+    priority_sender: UnboundedSender<MessageQueueItem<P>>,
     peer_addr: SocketAddr,
 }
 
@@ -318,9 +323,21 @@ where
             }
         }
 
+        for seed in &self.cfg.dns_seeds {
+            match utils::resolve_all_addresses(seed) {
+                Ok(seed_addresses) => {
+                    info!(%seed, count = seed_addresses.len(), "resolved DNS seed");
+                    known_addresses.extend(seed_addresses);
+                }
+                Err(ref err) => {
+                    warn!(%seed, err=display_error(err), "failed to resolve DNS seed");
+                }
+            }
+        }
+
         // Assert we have at least one known address in the config.
         if known_addresses.is_empty() {
-            warn!("no known addresses provided via config or all failed DNS resolution");
+            warn!("no known addresses or DNS seeds configured, or all failed DNS resolution");
             return Err(Error::EmptyKnownHosts);
         }
 
@@ -386,11 +403,13 @@ where
         let mut effects = self.process_dial_requests(dial_requests);
 
         // Start broadcasting our public listening address.
-        effects.extend(
-            effect_builder
-                .set_timeout(self.cfg.initial_gossip_delay.into())
-                .event(|_| Event::GossipOurAddress),
-        );
+        if self.cfg.enable_peer_exchange {
+            effects.extend(
+                effect_builder
+                    .set_timeout(self.cfg.initial_gossip_delay.into())
+                    .event(|_| Event::GossipOurAddress),
+            );
+        }
 
         // Start regular housekeeping of the outgoing connections.
         effects.extend(
@@ -499,11 +518,24 @@ where
                 error!(kind=%msg.classify(), node_id=%dest, "sending unsafe message to syncing node");
             }
 
-            if let Err(msg) = connection.sender.send((msg, opt_responder)) {
+            // Consensus messages between validators skip ahead of bulk traffic such as trie and
+            // block transfers, so that syncing peers can't starve consensus on a shared connection.
+            let is_priority =
+                msg.is_consensus() && self.outgoing_limiter.is_active_or_upcoming_validator(&dest);
+            let (sender, queued_counter) = if is_priority {
+                (
+                    &connection.priority_sender,
+                    &self.net_metrics.queued_priority_messages,
+                )
+            } else {
+                (&connection.sender, &self.net_metrics.queued_messages)
+            };
+
+            if let Err(msg) = sender.send((msg, opt_responder)) {
                 // We lost the connection, but that fact has not reached us yet.
                 warn!(our_id=%self.context.our_id(), %dest, ?msg, "dropped outgoing message, lost connection");
             } else {
-                self.net_metrics.queued_messages.inc();
+                queued_counter.inc();
             }
         } else {
             // We are not connected, so the reconnection is likely already in progress.
@@ -760,7 +792,12 @@ where
                 info!("new outgoing connection established");
 
                 let (sender, receiver) = mpsc::unbounded_channel();
-                let handle = OutgoingHandle { sender, peer_addr };
+                let (priority_sender, priority_receiver) = mpsc::unbounded_channel();
+                let handle = OutgoingHandle {
+                    sender,
+                    priority_sender,
+                    peer_addr,
+                };
 
                 let request = self
                     .outgoing_manager
@@ -787,10 +824,12 @@ where
                 effects.extend(
                     tasks::message_sender(
                         receiver,
+                        priority_receiver,
                         sink,
                         self.outgoing_limiter
                             .create_handle(peer_id, peer_consensus_public_key),
                         self.net_metrics.queued_messages.clone(),
+                        self.net_metrics.queued_priority_messages.clone(),
                     )
                     .instrument(span)
                     .event(move |_| Event::OutgoingDropped {
@@ -1238,6 +1277,10 @@ where
                     effects
                 }
                 Event::PeerAddressReceived(gossiped_address) => {
+                    if !self.cfg.enable_peer_exchange {
+                        debug!(%gossiped_address, "peer exchange disabled, ignoring address");
+                        return Effects::new();
+                    }
                     let requests = self.outgoing_manager.learn_addr(
                         gossiped_address.into(),
                         false,
@@ -1324,7 +1367,7 @@ pub(crate) type FullTransport<P> = tokio_serde::Framed<
     FramedTransport,
     Message<P>,
     Arc<Message<P>>,
-    CountingFormat<BincodeFormat>,
+    CountingFormat<CompressionFormat<BincodeFormat>>,
 >;
 
 pub(crate) type FramedTransport = tokio_util::codec::Framed<Transport, LengthDelimitedCodec>;
@@ -1332,19 +1375,29 @@ pub(crate) type FramedTransport = tokio_util::codec::Framed<Transport, LengthDel
 /// Constructs a new full transport on a stream.
 ///
 /// A full transport contains the framing as well as the encoding scheme used to send messages.
+/// Messages are compressed if `compression_threshold` is given, which it must be if and only if
+/// both peers support compression.
 fn full_transport<P>(
     metrics: Weak<Metrics>,
     connection_id: ConnectionId,
     framed: FramedTransport,
     role: Role,
+    compression_threshold: Option<usize>,
+    maximum_net_message_size: u32,
 ) -> FullTransport<P>
 where
     for<'de> P: Serialize + Deserialize<'de>,
     for<'de> Message<P>: Serialize + Deserialize<'de>,
 {
+    let compression_format = CompressionFormat::new(
+        metrics.clone(),
+        compression_threshold,
+        maximum_net_message_size as usize,
+        BincodeFormat::default(),
+    );
     tokio_serde::Framed::new(
         framed,
-        CountingFormat::new(metrics, connection_id, role, BincodeFormat::default()),
+        CountingFormat::new(metrics, connection_id, role, compression_format),
     )
 }
 
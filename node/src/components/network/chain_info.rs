@@ -52,6 +52,7 @@ impl ChainInfo {
         consensus_keys: Option<&NodeKeyPair>,
         connection_id: ConnectionId,
         is_syncing: bool,
+        supports_compression: bool,
     ) -> Message<P> {
         Message::Handshake {
             network_name: self.network_name.clone(),
@@ -61,6 +62,7 @@ impl ChainInfo {
                 .map(|key_pair| ConsensusCertificate::create(connection_id, key_pair)),
             is_syncing,
             chainspec_hash: Some(self.chainspec_hash),
+            supports_compression,
         }
     }
 }
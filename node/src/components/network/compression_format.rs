@@ -0,0 +1,209 @@
+//! Optional zstd compression of network messages, negotiated per connection.
+//!
+//! Peers announce in their handshakes whether they support compression.  Once both do, every frame
+//! sent over the connection carries a leading byte telling whether the rest of it is compressed.
+//! Messages serializing to at least the sender's compression threshold (typically trie chunks,
+//! blocks and large consensus units) are compressed, while smaller ones, which would gain little,
+//! are sent as they are.  If either peer lacks support, frames are left untouched.
+
+use std::{
+    io,
+    pin::Pin,
+    sync::{Arc, Weak},
+};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use pin_project::pin_project;
+use tokio_serde::{Deserializer, Serializer};
+
+use super::{Message, Metrics};
+
+/// Leading byte of a frame whose remainder is not compressed.
+const UNCOMPRESSED: u8 = 0;
+
+/// Leading byte of a frame whose remainder is compressed.
+const COMPRESSED: u8 = 1;
+
+/// A serializer/deserializer wrapper compressing and decompressing large messages.
+#[pin_project]
+#[derive(Debug)]
+pub struct CompressionFormat<F> {
+    /// The actual serializer performing the work.
+    #[pin]
+    inner: F,
+    /// The size from which serialized messages are compressed, if compression was negotiated.
+    compression_threshold: Option<usize>,
+    /// The largest size a message may decompress to.
+    maximum_message_size: usize,
+    /// Metrics to update.
+    metrics: Weak<Metrics>,
+}
+
+impl<F> CompressionFormat<F> {
+    /// Creates a new compression format.
+    ///
+    /// `compression_threshold` must be `Some` if and only if both peers support compression.
+    pub(super) fn new(
+        metrics: Weak<Metrics>,
+        compression_threshold: Option<usize>,
+        maximum_message_size: usize,
+        inner: F,
+    ) -> Self {
+        Self {
+            inner,
+            compression_threshold,
+            maximum_message_size,
+            metrics,
+        }
+    }
+}
+
+/// Prefixes `contents` with the given leading byte.
+fn frame(leading_byte: u8, contents: &[u8]) -> Bytes {
+    let mut frame = BytesMut::with_capacity(contents.len() + 1);
+    frame.put_u8(leading_byte);
+    frame.extend_from_slice(contents);
+    frame.freeze()
+}
+
+impl<F, P> Serializer<Arc<Message<P>>> for CompressionFormat<F>
+where
+    F: Serializer<Arc<Message<P>>, Error = io::Error>,
+{
+    type Error = io::Error;
+
+    fn serialize(self: Pin<&mut Self>, item: &Arc<Message<P>>) -> Result<Bytes, Self::Error> {
+        let this = self.project();
+        let serialized = F::serialize(this.inner, item)?;
+
+        let compression_threshold = match this.compression_threshold {
+            Some(compression_threshold) => *compression_threshold,
+            None => return Ok(serialized),
+        };
+        if serialized.len() < compression_threshold {
+            return Ok(frame(UNCOMPRESSED, &serialized));
+        }
+
+        let compressed = zstd::bulk::compress(&serialized, zstd::DEFAULT_COMPRESSION_LEVEL)?;
+        if compressed.len() >= serialized.len() {
+            return Ok(frame(UNCOMPRESSED, &serialized));
+        }
+        Metrics::record_compressed_out(
+            this.metrics,
+            serialized.len() as u64,
+            compressed.len() as u64,
+        );
+        Ok(frame(COMPRESSED, &compressed))
+    }
+}
+
+impl<F, P> Deserializer<Message<P>> for CompressionFormat<F>
+where
+    F: Deserializer<Message<P>, Error = io::Error>,
+{
+    type Error = io::Error;
+
+    fn deserialize(self: Pin<&mut Self>, src: &BytesMut) -> Result<Message<P>, Self::Error> {
+        let this = self.project();
+        if this.compression_threshold.is_none() {
+            return F::deserialize(this.inner, src);
+        }
+
+        match src.split_first() {
+            Some((&UNCOMPRESSED, contents)) => {
+                F::deserialize(this.inner, &BytesMut::from(contents))
+            }
+            Some((&COMPRESSED, contents)) => {
+                // Bounding the decompressed size guards against decompression bombs.
+                let decompressed = zstd::bulk::decompress(contents, *this.maximum_message_size)
+                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+                Metrics::record_compressed_in(
+                    this.metrics,
+                    decompressed.len() as u64,
+                    contents.len() as u64,
+                );
+                F::deserialize(this.inner, &BytesMut::from(decompressed.as_slice()))
+            }
+            Some((leading_byte, _)) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid compression flag {}", leading_byte),
+            )),
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "missing compression flag",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::network::BincodeFormat;
+
+    fn format(compression_threshold: Option<usize>) -> CompressionFormat<BincodeFormat> {
+        CompressionFormat::new(
+            Weak::new(),
+            compression_threshold,
+            1024 * 1024,
+            BincodeFormat::default(),
+        )
+    }
+
+    fn roundtrip(
+        format: &mut CompressionFormat<BincodeFormat>,
+        payload: Vec<u8>,
+    ) -> (Bytes, Vec<u8>) {
+        let message = Arc::new(Message::Payload(payload));
+        let serialized = Pin::new(&mut *format).serialize(&message).unwrap();
+        let deserialized: Message<Vec<u8>> = Pin::new(&mut *format)
+            .deserialize(&BytesMut::from(&serialized[..]))
+            .unwrap();
+        match deserialized {
+            Message::Payload(payload) => (serialized, payload),
+            other => panic!("unexpected message {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_compress_only_large_messages() {
+        let mut format = format(Some(1_000));
+
+        let small_payload = vec![7; 100];
+        let (serialized, payload) = roundtrip(&mut format, small_payload.clone());
+        assert_eq!(serialized[0], UNCOMPRESSED);
+        assert_eq!(payload, small_payload);
+
+        let large_payload = vec![7; 100_000];
+        let (serialized, payload) = roundtrip(&mut format, large_payload.clone());
+        assert_eq!(serialized[0], COMPRESSED);
+        assert!(serialized.len() < 1_000);
+        assert_eq!(payload, large_payload);
+    }
+
+    #[test]
+    fn should_leave_frames_untouched_without_negotiated_compression() {
+        let mut format = format(None);
+        let large_payload = vec![7; 100_000];
+
+        let (serialized, payload) = roundtrip(&mut format, large_payload.clone());
+        let expected = BincodeFormat::default()
+            .serialize_arbitrary(&Message::Payload(large_payload.clone()))
+            .unwrap();
+        assert_eq!(&serialized[..], &expected[..]);
+        assert_eq!(payload, large_payload);
+    }
+
+    #[test]
+    fn should_reject_oversized_decompression() {
+        let mut sender = format(Some(0));
+        let mut receiver =
+            CompressionFormat::new(Weak::new(), Some(0), 1_000, BincodeFormat::default());
+
+        let message = Arc::new(Message::Payload(vec![7u8; 100_000]));
+        let serialized = Pin::new(&mut sender).serialize(&message).unwrap();
+        let result: Result<Message<Vec<u8>>, _> =
+            Pin::new(&mut receiver).deserialize(&BytesMut::from(&serialized[..]));
+        assert!(result.is_err());
+    }
+}
@@ -32,12 +32,20 @@ const DEFAULT_MAX_ADDR_PENDING_TIME: TimeDiff = TimeDiff::from_seconds(60);
 /// Default timeout during which the handshake needs to be completed.
 const DEFAULT_HANDSHAKE_TIMEOUT: TimeDiff = TimeDiff::from_seconds(20);
 
+/// Default setting for gossiping addresses with peers.
+const DEFAULT_ENABLE_PEER_EXCHANGE: bool = true;
+
+/// Default size from which messages are compressed.
+const DEFAULT_COMPRESSION_THRESHOLD: u32 = 16 * 1024;
+
 impl Default for Config {
     fn default() -> Self {
         Config {
             bind_address: DEFAULT_BIND_ADDRESS.to_string(),
             public_address: DEFAULT_PUBLIC_ADDRESS.to_string(),
             known_addresses: Vec::new(),
+            dns_seeds: Vec::new(),
+            enable_peer_exchange: DEFAULT_ENABLE_PEER_EXCHANGE,
             min_peers_for_initialization: DEFAULT_MIN_PEERS_FOR_INITIALIZATION,
             gossip_interval: DEFAULT_GOSSIP_INTERVAL,
             initial_gossip_delay: DEFAULT_INITIAL_GOSSIP_DELAY,
@@ -52,6 +60,7 @@ impl Default for Config {
             tarpit_chance: 0.2,
             max_in_flight_demands: 50,
             blocklist_retain_duration: TimeDiff::from_seconds(600),
+            compression_threshold: DEFAULT_COMPRESSION_THRESHOLD,
             identity: None,
         }
     }
@@ -83,6 +92,16 @@ pub struct Config {
     pub public_address: String,
     /// Known address of a node on the network used for joining.
     pub known_addresses: Vec<String>,
+    /// Host names, with ports, whose DNS records list addresses of nodes used for joining.
+    ///
+    /// Unlike for known addresses, every address a seed resolves to is used.
+    #[serde(default)]
+    pub dns_seeds: Vec<String>,
+    /// Whether to gossip our public address to peers and to learn the addresses gossiped by them.
+    ///
+    /// If disabled, only known addresses, DNS seeds and peers connecting to us are connected to.
+    #[serde(default = "default_enable_peer_exchange")]
+    pub enable_peer_exchange: bool,
     /// Minimum number of fully-connected peers to consider component initialized.
     pub min_peers_for_initialization: u16,
     /// Interval in milliseconds used for gossiping.
@@ -111,6 +130,10 @@ pub struct Config {
     pub max_in_flight_demands: u32,
     /// Duration peers are kept on the block list, before being redeemed.
     pub blocklist_retain_duration: TimeDiff,
+    /// Size in bytes from which messages to peers supporting compression are sent compressed.
+    /// Compression is disabled if 0.
+    #[serde(default = "default_compression_threshold")]
+    pub compression_threshold: u32,
     /// Network identity configuration option.
     ///
     /// An identity will be automatically generated when starting up a node if this option is
@@ -118,6 +141,14 @@ pub struct Config {
     pub identity: Option<IdentityConfig>,
 }
 
+fn default_enable_peer_exchange() -> bool {
+    DEFAULT_ENABLE_PEER_EXCHANGE
+}
+
+fn default_compression_threshold() -> u32 {
+    DEFAULT_COMPRESSION_THRESHOLD
+}
+
 #[cfg(test)]
 /// Reduced gossip interval for local testing.
 const DEFAULT_TEST_GOSSIP_INTERVAL: TimeDiff = TimeDiff::from_seconds(1);
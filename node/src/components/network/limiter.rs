@@ -91,15 +91,9 @@ impl Limiter {
     }
 
     pub(super) fn is_validator_in_era(&self, era: EraId, peer_id: &NodeId) -> bool {
-        let public_key = match self.data.connected_validators.read() {
-            Ok(connected_validators) => match connected_validators.get(peer_id) {
-                None => return false,
-                Some(public_key) => public_key.clone(),
-            },
-            Err(_) => {
-                error!("could not read from connected_validators of limiter, lock poisoned");
-                return false;
-            }
+        let public_key = match self.connected_validator_key(peer_id) {
+            None => return false,
+            Some(public_key) => public_key,
         };
 
         match self.validator_matrix.is_validator_in_era(era, &public_key) {
@@ -111,6 +105,26 @@ impl Limiter {
         }
     }
 
+    /// Returns `true` if the given peer is connected as an active or upcoming validator.
+    pub(super) fn is_active_or_upcoming_validator(&self, peer_id: &NodeId) -> bool {
+        self.connected_validator_key(peer_id)
+            .map_or(false, |public_key| {
+                self.validator_matrix
+                    .is_active_or_upcoming_validator(&public_key)
+            })
+    }
+
+    /// Returns the consensus key of the given peer, if it is a connected validator.
+    fn connected_validator_key(&self, peer_id: &NodeId) -> Option<PublicKey> {
+        match self.data.connected_validators.read() {
+            Ok(connected_validators) => connected_validators.get(peer_id).cloned(),
+            Err(_) => {
+                error!("could not read from connected_validators of limiter, lock poisoned");
+                None
+            }
+        }
+    }
+
     pub(super) fn debug_inspect_unspent_allowance(&self) -> Option<i64> {
         Some(task::block_in_place(move || {
             Handle::current().block_on(async move { self.data.resources.lock().await.available })
@@ -353,6 +367,29 @@ mod tests {
         assert!(start.elapsed() < SHORT_TIME);
     }
 
+    #[test]
+    fn should_recognize_connected_validators() {
+        let mut rng = crate::new_rng();
+
+        let secret_key = SecretKey::random(&mut rng);
+        let consensus_key = PublicKey::from(&secret_key);
+        let validator_matrix = ValidatorMatrix::new_with_validator(Arc::new(secret_key));
+        let limiter = Limiter::new(1_000, new_wait_time_sec(), validator_matrix);
+
+        let validator_id = NodeId::random(&mut rng);
+        let non_validator_id = NodeId::random(&mut rng);
+        let _validator_handle = limiter.create_handle(validator_id, Some(consensus_key));
+        let _non_validator_handle =
+            limiter.create_handle(non_validator_id, Some(PublicKey::random(&mut rng)));
+
+        assert!(limiter.is_active_or_upcoming_validator(&validator_id));
+        assert!(!limiter.is_active_or_upcoming_validator(&non_validator_id));
+        assert!(!limiter.is_active_or_upcoming_validator(&NodeId::random(&mut rng)));
+
+        limiter.remove_connected_validator(&validator_id);
+        assert!(!limiter.is_active_or_upcoming_validator(&validator_id));
+    }
+
     #[tokio::test]
     async fn inactive_validator_limited() {
         let rng = &mut crate::new_rng();
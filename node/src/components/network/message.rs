@@ -55,6 +55,9 @@ pub(crate) enum Message<P> {
         /// Hash of the chainspec the node is running.
         #[serde(default)]
         chainspec_hash: Option<Digest>,
+        /// True if the node supports compressed messages.
+        #[serde(default)]
+        supports_compression: bool,
     },
     /// A ping request.
     Ping {
@@ -81,6 +84,12 @@ impl<P: Payload> Message<P> {
         }
     }
 
+    /// Determines whether or not a message is consensus traffic.
+    #[inline]
+    pub(super) fn is_consensus(&self) -> bool {
+        matches!(self.classify(), MessageKind::Consensus)
+    }
+
     /// Determines whether or not a message is low priority.
     #[inline]
     pub(super) fn is_low_priority(&self) -> bool {
@@ -293,16 +302,18 @@ impl<P: Display> Display for Message<P> {
                 consensus_certificate,
                 is_syncing,
                 chainspec_hash,
+                supports_compression,
             } => {
                 write!(
                     f,
-                    "handshake: {}, public addr: {}, protocol_version: {}, consensus_certificate: {}, is_syncing: {}, chainspec_hash: {}",
+                    "handshake: {}, public addr: {}, protocol_version: {}, consensus_certificate: {}, is_syncing: {}, chainspec_hash: {}, supports_compression: {}",
                     network_name,
                     public_addr,
                     protocol_version,
                     OptDisplay::new(consensus_certificate.as_ref(), "none"),
                     is_syncing,
-                    OptDisplay::new(chainspec_hash.as_ref(), "none")
+                    OptDisplay::new(chainspec_hash.as_ref(), "none"),
+                    supports_compression
                 )
             }
             Message::Ping { nonce } => write!(f, "ping({})", nonce),
@@ -464,6 +475,7 @@ mod specimen_support {
                         consensus_certificate: LargestSpecimen::largest_specimen(estimator, cache),
                         is_syncing: LargestSpecimen::largest_specimen(estimator, cache),
                         chainspec_hash: LargestSpecimen::largest_specimen(estimator, cache),
+                        supports_compression: LargestSpecimen::largest_specimen(estimator, cache),
                     },
                     MessageDiscriminants::Ping => Message::Ping {
                         nonce: LargestSpecimen::largest_specimen(estimator, cache),
@@ -768,6 +780,7 @@ mod tests {
             consensus_certificate: Some(ConsensusCertificate::random(&mut rng)),
             is_syncing: false,
             chainspec_hash: Some(Digest::hash("example-chainspec")),
+            supports_compression: true,
         };
 
         let legacy_handshake: V1_0_0_Message = roundtrip_message(&modern_handshake);
@@ -802,6 +815,7 @@ mod tests {
             consensus_certificate,
             is_syncing,
             chainspec_hash,
+            supports_compression,
         } = modern_handshake
         {
             assert_eq!(network_name, "example-handshake");
@@ -809,7 +823,8 @@ mod tests {
             assert_eq!(protocol_version, ProtocolVersion::V1_0_0);
             assert!(consensus_certificate.is_none());
             assert!(!is_syncing);
-            assert!(chainspec_hash.is_none())
+            assert!(chainspec_hash.is_none());
+            assert!(!supports_compression);
         } else {
             panic!("did not expect modern handshake to deserialize to anything but")
         }
@@ -826,6 +841,7 @@ mod tests {
             consensus_certificate,
             is_syncing,
             chainspec_hash,
+            supports_compression,
         } = modern_handshake
         {
             assert!(!is_syncing);
@@ -834,7 +850,8 @@ mod tests {
             assert_eq!(protocol_version, ProtocolVersion::V1_0_0);
             assert!(consensus_certificate.is_none());
             assert!(!is_syncing);
-            assert!(chainspec_hash.is_none())
+            assert!(chainspec_hash.is_none());
+            assert!(!supports_compression);
         } else {
             panic!("did not expect modern handshake to deserialize to anything but")
         }
@@ -851,6 +868,7 @@ mod tests {
             consensus_certificate,
             is_syncing,
             chainspec_hash,
+            supports_compression,
         } = modern_handshake
         {
             assert_eq!(network_name, "example-handshake");
@@ -878,7 +896,8 @@ mod tests {
                 .unwrap()
             );
             assert!(!is_syncing);
-            assert!(chainspec_hash.is_none())
+            assert!(chainspec_hash.is_none());
+            assert!(!supports_compression);
         } else {
             panic!("did not expect modern handshake to deserialize to anything but")
         }
@@ -895,6 +914,7 @@ mod tests {
             consensus_certificate,
             is_syncing,
             chainspec_hash,
+            supports_compression,
         } = modern_handshake
         {
             assert!(!is_syncing);
@@ -922,7 +942,8 @@ mod tests {
                 .unwrap()
             );
             assert!(!is_syncing);
-            assert!(chainspec_hash.is_none())
+            assert!(chainspec_hash.is_none());
+            assert!(!supports_compression);
         } else {
             panic!("did not expect modern handshake to deserialize to anything but")
         }
@@ -15,6 +15,8 @@ pub(super) struct Metrics {
     pub(super) direct_message_requests: IntCounter,
     /// Number of messages still waiting to be sent out (broadcast and direct).
     pub(super) queued_messages: IntGauge,
+    /// Number of consensus messages to validators still waiting to be sent out ahead of others.
+    pub(super) queued_priority_messages: IntGauge,
     /// Number of connected peers.
     pub(super) peers: IntGauge,
 
@@ -115,6 +117,15 @@ pub(super) struct Metrics {
     /// Total time spent delaying incoming traffic from non-validators due to limiter, in seconds.
     pub(super) accumulated_incoming_limiter_delay: Counter,
 
+    /// Volume in bytes of outgoing messages sent compressed, before compression.
+    pub(super) out_bytes_compressed_original: IntCounter,
+    /// Volume in bytes of outgoing messages sent compressed, after compression.
+    pub(super) out_bytes_compressed: IntCounter,
+    /// Volume in bytes of incoming messages received compressed, after decompression.
+    pub(super) in_bytes_compressed_original: IntCounter,
+    /// Volume in bytes of incoming messages received compressed, before decompression.
+    pub(super) in_bytes_compressed: IntCounter,
+
     /// Registry instance.
     registry: Registry,
 }
@@ -132,6 +143,10 @@ impl Metrics {
             "net_queued_direct_messages",
             "number of messages waiting to be sent out",
         )?;
+        let queued_priority_messages = IntGauge::new(
+            "net_queued_priority_messages",
+            "number of consensus messages to validators waiting to be sent out ahead of others",
+        )?;
         let peers = IntGauge::new("peers", "number of connected peers")?;
 
         let out_count_protocol = IntCounter::new(
@@ -337,9 +352,27 @@ impl Metrics {
             "seconds spent delaying incoming traffic from non-validators due to limiter, in seconds."
         )?;
 
+        let out_bytes_compressed_original = IntCounter::new(
+            "net_out_bytes_compressed_original",
+            "volume in bytes of outgoing messages sent compressed, before compression",
+        )?;
+        let out_bytes_compressed = IntCounter::new(
+            "net_out_bytes_compressed",
+            "volume in bytes of outgoing messages sent compressed, after compression",
+        )?;
+        let in_bytes_compressed_original = IntCounter::new(
+            "net_in_bytes_compressed_original",
+            "volume in bytes of incoming messages received compressed, after decompression",
+        )?;
+        let in_bytes_compressed = IntCounter::new(
+            "net_in_bytes_compressed",
+            "volume in bytes of incoming messages received compressed, before decompression",
+        )?;
+
         registry.register(Box::new(broadcast_requests.clone()))?;
         registry.register(Box::new(direct_message_requests.clone()))?;
         registry.register(Box::new(queued_messages.clone()))?;
+        registry.register(Box::new(queued_priority_messages.clone()))?;
         registry.register(Box::new(peers.clone()))?;
 
         registry.register(Box::new(out_count_protocol.clone()))?;
@@ -398,10 +431,16 @@ impl Metrics {
         registry.register(Box::new(accumulated_outgoing_limiter_delay.clone()))?;
         registry.register(Box::new(accumulated_incoming_limiter_delay.clone()))?;
 
+        registry.register(Box::new(out_bytes_compressed_original.clone()))?;
+        registry.register(Box::new(out_bytes_compressed.clone()))?;
+        registry.register(Box::new(in_bytes_compressed_original.clone()))?;
+        registry.register(Box::new(in_bytes_compressed.clone()))?;
+
         Ok(Metrics {
             broadcast_requests,
             direct_message_requests,
             queued_messages,
+            queued_priority_messages,
             peers,
             out_count_protocol,
             out_count_consensus,
@@ -452,10 +491,31 @@ impl Metrics {
             requests_for_trie_finished,
             accumulated_outgoing_limiter_delay,
             accumulated_incoming_limiter_delay,
+            out_bytes_compressed_original,
+            out_bytes_compressed,
+            in_bytes_compressed_original,
+            in_bytes_compressed,
             registry: registry.clone(),
         })
     }
 
+    /// Records an outgoing message sent compressed, with its size before and after compression.
+    pub(crate) fn record_compressed_out(this: &Weak<Self>, original_size: u64, size: u64) {
+        if let Some(metrics) = this.upgrade() {
+            metrics.out_bytes_compressed_original.inc_by(original_size);
+            metrics.out_bytes_compressed.inc_by(size);
+        }
+    }
+
+    /// Records an incoming message received compressed, with its size before and after
+    /// decompression.
+    pub(crate) fn record_compressed_in(this: &Weak<Self>, original_size: u64, size: u64) {
+        if let Some(metrics) = this.upgrade() {
+            metrics.in_bytes_compressed_original.inc_by(original_size);
+            metrics.in_bytes_compressed.inc_by(size);
+        }
+    }
+
     /// Records an outgoing payload.
     pub(crate) fn record_payload_out(this: &Weak<Self>, kind: MessageKind, size: u64) {
         if let Some(metrics) = this.upgrade() {
@@ -591,6 +651,7 @@ impl Drop for Metrics {
         unregister_metric!(self.registry, self.broadcast_requests);
         unregister_metric!(self.registry, self.direct_message_requests);
         unregister_metric!(self.registry, self.queued_messages);
+        unregister_metric!(self.registry, self.queued_priority_messages);
         unregister_metric!(self.registry, self.peers);
 
         unregister_metric!(self.registry, self.out_count_protocol);
@@ -648,5 +709,10 @@ impl Drop for Metrics {
 
         unregister_metric!(self.registry, self.accumulated_outgoing_limiter_delay);
         unregister_metric!(self.registry, self.accumulated_incoming_limiter_delay);
+
+        unregister_metric!(self.registry, self.out_bytes_compressed_original);
+        unregister_metric!(self.registry, self.out_bytes_compressed);
+        unregister_metric!(self.registry, self.in_bytes_compressed_original);
+        unregister_metric!(self.registry, self.in_bytes_compressed);
     }
 }
@@ -81,6 +81,8 @@ struct HandshakeOutcome {
     peer_consensus_public_key: Option<PublicKey>,
     /// Holds the information whether the remote node is syncing.
     is_peer_syncing: bool,
+    /// Whether the remote node supports compressed messages.
+    peer_supports_compression: bool,
 }
 
 /// Low-level TLS connection function.
@@ -163,6 +165,7 @@ where
             public_addr,
             peer_consensus_public_key,
             is_peer_syncing: is_syncing,
+            peer_supports_compression,
         }) => {
             if let Some(ref public_key) = peer_consensus_public_key {
                 Span::current().record("consensus_key", &field::display(public_key));
@@ -179,6 +182,8 @@ where
                 connection_id,
                 framed_transport,
                 Role::Dialer,
+                context.negotiated_compression_threshold(peer_supports_compression),
+                context.chain_info.maximum_net_message_size,
             );
             let (sink, _stream) = full_transport.split();
 
@@ -236,6 +241,8 @@ where
     max_in_flight_demands: usize,
     /// Flag indicating whether this node is syncing.
     is_syncing: AtomicBool,
+    /// The size from which messages are compressed, if we support compression.
+    compression_threshold: Option<usize>,
 }
 
 impl<REv> NetworkContext<REv> {
@@ -253,6 +260,13 @@ impl<REv> NetworkContext<REv> {
             cfg.max_in_flight_demands as usize
         };
 
+        // Regard a compression threshold of `0` as disabling compression.
+        let compression_threshold = if cfg.compression_threshold == 0 {
+            None
+        } else {
+            Some(cfg.compression_threshold as usize)
+        };
+
         let Identity {
             secret_key,
             tls_certificate,
@@ -277,6 +291,7 @@ impl<REv> NetworkContext<REv> {
             tarpit_chance: cfg.tarpit_chance,
             max_in_flight_demands,
             is_syncing: AtomicBool::new(false),
+            compression_threshold,
         }
     }
 
@@ -318,6 +333,13 @@ impl<REv> NetworkContext<REv> {
     pub(crate) fn is_syncing(&self) -> &AtomicBool {
         &self.is_syncing
     }
+
+    /// Returns the size from which messages sent to a peer are compressed, or `None` if either we
+    /// or the peer don't support compression.
+    fn negotiated_compression_threshold(&self, peer_supports_compression: bool) -> Option<usize> {
+        self.compression_threshold
+            .filter(|_| peer_supports_compression)
+    }
 }
 
 /// Handles an incoming connection.
@@ -362,6 +384,7 @@ where
             public_addr,
             peer_consensus_public_key,
             is_peer_syncing: _,
+            peer_supports_compression,
         }) => {
             if let Some(ref public_key) = peer_consensus_public_key {
                 Span::current().record("consensus_key", &field::display(public_key));
@@ -373,6 +396,8 @@ where
                 connection_id,
                 framed_transport,
                 Role::Listener,
+                context.negotiated_compression_threshold(peer_supports_compression),
+                context.chain_info.maximum_net_message_size,
             );
 
             let (_sink, stream) = full_transport.split();
@@ -474,6 +499,7 @@ where
         context.node_key_pair.as_ref(),
         connection_id,
         context.is_syncing.load(Ordering::SeqCst),
+        context.compression_threshold.is_some(),
     );
 
     let serialized_handshake_message = Pin::new(&mut encoder)
@@ -513,6 +539,7 @@ where
         consensus_certificate,
         is_syncing,
         chainspec_hash,
+        supports_compression,
     } = remote_message
     {
         debug!(%protocol_version, "handshake received");
@@ -571,6 +598,7 @@ where
             public_addr,
             peer_consensus_public_key,
             is_peer_syncing: is_syncing,
+            peer_supports_compression: supports_compression,
         })
     } else {
         // Received a non-handshake, this is an error.
@@ -793,17 +821,32 @@ where
 
 /// Network message sender.
 ///
-/// Reads from a channel and sends all messages, until the stream is closed or an error occurs.
+/// Reads from two channels and sends all messages, until both streams are closed or an error
+/// occurs.  Messages in `priority_queue` are always sent before any waiting in `queue`, so that
+/// consensus traffic is never held up behind bulk transfers.
 pub(super) async fn message_sender<P>(
     mut queue: UnboundedReceiver<MessageQueueItem<P>>,
+    mut priority_queue: UnboundedReceiver<MessageQueueItem<P>>,
     mut sink: SplitSink<FullTransport<P>, Arc<Message<P>>>,
     limiter: LimiterHandle,
     counter: IntGauge,
+    priority_counter: IntGauge,
 ) where
     P: Payload,
 {
-    while let Some((message, opt_responder)) = queue.recv().await {
-        counter.dec();
+    loop {
+        let (message, opt_responder) = tokio::select! {
+            biased;
+            Some(item) = priority_queue.recv() => {
+                priority_counter.dec();
+                item
+            }
+            Some(item) = queue.recv() => {
+                counter.dec();
+                item
+            }
+            else => break,
+        };
 
         let estimated_wire_size = match BincodeFormat::default().0.serialized_size(&*message) {
             Ok(size) => size as u32,
@@ -833,7 +876,11 @@ pub(super) async fn message_sender<P>(
                 "message send failed, closing outgoing connection"
             );
 
-            // To ensure, metrics are up to date, we close the queue and drain it.
+            // To ensure, metrics are up to date, we close the queues and drain them.
+            priority_queue.close();
+            while priority_queue.recv().await.is_some() {
+                priority_counter.dec();
+            }
             queue.close();
             while queue.recv().await.is_some() {
                 counter.dec();
@@ -25,8 +25,7 @@ use futures::join;
 use tracing::{error, info, warn};
 
 use casper_execution_engine::core::engine_state::{
-    self, BalanceRequest, BalanceResult, GetBidsRequest, GetEraValidatorsError, QueryRequest,
-    QueryResult,
+    BalanceRequest, BalanceResult, GetBidsRequest, GetEraValidatorsError, QueryRequest, QueryResult,
 };
 use casper_hashing::Digest;
 use casper_types::{system::auction::EraValidators, Key, ProtocolVersion, URef};
@@ -34,8 +33,8 @@ use casper_types::{system::auction::EraValidators, Key, ProtocolVersion, URef};
 use super::Component;
 use crate::{
     components::{
-        contract_runtime::EraValidatorsRequest, ComponentState, InitializedComponent,
-        PortBoundComponent,
+        contract_runtime::{EraValidatorsRequest, QueryOrigin, StateQueryError, StateRootAgeCheck},
+        ComponentState, InitializedComponent, PortBoundComponent,
     },
     effect::{
         requests::{
@@ -139,17 +138,23 @@ impl RpcServer {
         &mut self,
         effect_builder: EffectBuilder<REv>,
         state_root_hash: Digest,
+        block_height: Option<u64>,
         base_key: Key,
         path: Vec<String>,
-        responder: Responder<Result<QueryResult, engine_state::Error>>,
+        responder: Responder<Result<QueryResult, StateQueryError>>,
     ) -> Effects<Event> {
         let query = QueryRequest::new(state_root_hash, base_key, path);
-        effect_builder
-            .query_global_state(query)
-            .event(move |result| Event::QueryGlobalStateResult {
-                result,
-                main_responder: responder,
-            })
+        async move {
+            let state_root_age_check =
+                resolve_state_root_age_check(effect_builder, state_root_hash, block_height).await;
+            effect_builder
+                .query_global_state(query, state_root_age_check)
+                .await
+        }
+        .event(move |result| Event::QueryGlobalStateResult {
+            result,
+            main_responder: responder,
+        })
     }
 
     fn handle_era_validators<REv: ReactorEventT>(
@@ -161,7 +166,7 @@ impl RpcServer {
     ) -> Effects<Event> {
         let request = EraValidatorsRequest::new(state_root_hash, protocol_version);
         effect_builder
-            .get_era_validators_from_contract_runtime(request)
+            .get_era_validators_from_contract_runtime(request, QueryOrigin::Client)
             .event(move |result| Event::QueryEraValidatorsResult {
                 result,
                 main_responder: responder,
@@ -172,19 +177,49 @@ impl RpcServer {
         &mut self,
         effect_builder: EffectBuilder<REv>,
         state_root_hash: Digest,
+        block_height: Option<u64>,
         purse_uref: URef,
-        responder: Responder<Result<BalanceResult, engine_state::Error>>,
+        responder: Responder<Result<BalanceResult, StateQueryError>>,
     ) -> Effects<Event> {
         let query = BalanceRequest::new(state_root_hash, purse_uref);
-        effect_builder
-            .get_balance(query)
-            .event(move |result| Event::GetBalanceResult {
-                result,
-                main_responder: responder,
-            })
+        async move {
+            let state_root_age_check =
+                resolve_state_root_age_check(effect_builder, state_root_hash, block_height).await;
+            effect_builder
+                .get_balance(query, state_root_age_check)
+                .await
+        }
+        .event(move |result| Event::GetBalanceResult {
+            result,
+            main_responder: responder,
+        })
     }
 }
 
+/// Determines how the age of a state root given in an RPC request is checked.
+///
+/// If the request doesn't identify the block whose state root is queried, the height of the
+/// highest stored block with that state root is looked up, so that no query can bypass the limit
+/// on the age of queried state roots.
+async fn resolve_state_root_age_check<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    state_root_hash: Digest,
+    block_height: Option<u64>,
+) -> StateRootAgeCheck {
+    let maybe_block_height = match block_height {
+        Some(block_height) => Some(block_height),
+        None => {
+            effect_builder
+                .get_block_height_for_state_root_hash_from_storage(state_root_hash)
+                .await
+        }
+    };
+    maybe_block_height.map_or(
+        StateRootAgeCheck::UnknownHeight,
+        StateRootAgeCheck::AtHeight,
+    )
+}
+
 impl<REv> Component<REv> for RpcServer
 where
     REv: ReactorEventT,
@@ -258,10 +293,18 @@ where
                     }),
                 Event::RpcRequest(RpcRequest::QueryGlobalState {
                     state_root_hash,
+                    block_height,
                     base_key,
                     path,
                     responder,
-                }) => self.handle_query(effect_builder, state_root_hash, base_key, path, responder),
+                }) => self.handle_query(
+                    effect_builder,
+                    state_root_hash,
+                    block_height,
+                    base_key,
+                    path,
+                    responder,
+                ),
                 Event::RpcRequest(RpcRequest::QueryEraValidators {
                     state_root_hash,
                     protocol_version,
@@ -286,11 +329,16 @@ where
                 }
                 Event::RpcRequest(RpcRequest::GetBalance {
                     state_root_hash,
+                    block_height,
                     purse_uref,
                     responder,
-                }) => {
-                    self.handle_get_balance(effect_builder, state_root_hash, purse_uref, responder)
-                }
+                }) => self.handle_get_balance(
+                    effect_builder,
+                    state_root_hash,
+                    block_height,
+                    purse_uref,
+                    responder,
+                ),
                 Event::RpcRequest(RpcRequest::GetDeploy {
                     hash,
                     responder,
@@ -454,6 +502,7 @@ where
                 cfg.qps_limit,
                 cfg.max_body_bytes,
                 cfg.cors_origin.clone(),
+                cfg.max_bundle_length,
             ));
             Some(())
         } else {
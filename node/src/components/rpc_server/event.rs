@@ -11,6 +11,7 @@ use casper_execution_engine::core::engine_state::{
 use casper_types::{system::auction::EraValidators, Transfer};
 
 use crate::{
+    components::contract_runtime::StateQueryError,
     effect::{requests::RpcRequest, Responder},
     types::{BlockHash, Deploy, DeployHash, DeployMetadataExt, NodeId},
 };
@@ -26,8 +27,8 @@ pub(crate) enum Event {
         main_responder: Responder<Option<Vec<Transfer>>>,
     },
     QueryGlobalStateResult {
-        result: Result<QueryResult, engine_state::Error>,
-        main_responder: Responder<Result<QueryResult, engine_state::Error>>,
+        result: Result<QueryResult, StateQueryError>,
+        main_responder: Responder<Result<QueryResult, StateQueryError>>,
     },
     QueryEraValidatorsResult {
         result: Result<EraValidators, GetEraValidatorsError>,
@@ -47,8 +48,8 @@ pub(crate) enum Event {
         main_responder: Responder<BTreeMap<NodeId, String>>,
     },
     GetBalanceResult {
-        result: Result<BalanceResult, engine_state::Error>,
-        main_responder: Responder<Result<BalanceResult, engine_state::Error>>,
+        result: Result<BalanceResult, StateQueryError>,
+        main_responder: Responder<Result<BalanceResult, StateQueryError>>,
     },
 }
 
@@ -7,13 +7,17 @@ use super::{
     rpcs::{
         account::PutDeploy,
         chain::{
-            GetBlock, GetBlockTransfers, GetEraInfoBySwitchBlock, GetEraSummary, GetStateRootHash,
+            GetBlock, GetBlockTransfers, GetEraExecutionStats, GetEraInfoBySwitchBlock,
+            GetEraSummary, GetStateRootHash,
         },
         docs::ListRpcs,
-        info::{GetChainspec, GetDeploy, GetPeers, GetStatus, GetValidatorChanges},
+        info::{
+            GetChainspec, GetDeploy, GetPeers, GetStatus, GetTransactionReceipt,
+            GetValidatorChanges,
+        },
         state::{
             GetAccountInfo, GetAuctionInfo, GetBalance, GetDictionaryItem, GetItem, GetTrie,
-            QueryBalance, QueryGlobalState,
+            GetUnbonds, QueryBalance, QueryGlobalState,
         },
         RpcWithOptionalParams, RpcWithParams, RpcWithoutParams,
     },
@@ -45,11 +49,14 @@ pub(super) async fn run<REv: ReactorEventT>(
     GetBalance::register_as_handler(effect_builder, api_version, &mut handlers);
     GetAccountInfo::register_as_handler(effect_builder, api_version, &mut handlers);
     GetDeploy::register_as_handler(effect_builder, api_version, &mut handlers);
+    GetTransactionReceipt::register_as_handler(effect_builder, api_version, &mut handlers);
     GetPeers::register_as_handler(effect_builder, api_version, &mut handlers);
     GetStatus::register_as_handler(effect_builder, api_version, &mut handlers);
     GetEraInfoBySwitchBlock::register_as_handler(effect_builder, api_version, &mut handlers);
     GetEraSummary::register_as_handler(effect_builder, api_version, &mut handlers);
+    GetEraExecutionStats::register_as_handler(effect_builder, api_version, &mut handlers);
     GetAuctionInfo::register_as_handler(effect_builder, api_version, &mut handlers);
+    GetUnbonds::register_as_handler(effect_builder, api_version, &mut handlers);
     GetTrie::register_as_handler(effect_builder, api_version, &mut handlers);
     GetValidatorChanges::register_as_handler(effect_builder, api_version, &mut handlers);
     ListRpcs::register_as_handler(effect_builder, api_version, &mut handlers);
@@ -12,19 +12,21 @@ use once_cell::sync::Lazy;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use casper_execution_engine::core::engine_state::{self, QueryResult};
+use casper_execution_engine::core::engine_state::QueryResult;
 use casper_hashing::Digest;
-use casper_types::{Key, ProtocolVersion, Transfer};
+use casper_types::{EraId, Key, ProtocolVersion, Transfer};
 
 use super::{
     docs::{DocExample, DOCS_EXAMPLE_PROTOCOL_VERSION},
     Error, ErrorCode, ReactorEventT, ReservedErrorCode, RpcRequest, RpcWithOptionalParams,
+    RpcWithParams,
 };
 use crate::{
+    components::contract_runtime::StateQueryError,
     effect::EffectBuilder,
     reactor::QueueKind,
     rpcs::{common, state},
-    types::{Block, BlockHash, BlockWithMetadata, JsonBlock},
+    types::{Block, BlockHash, BlockWithMetadata, EraExecutionStats, JsonBlock},
 };
 pub use era_summary::EraSummary;
 use era_summary::ERA_SUMMARY;
@@ -69,6 +71,15 @@ static GET_ERA_SUMMARY_RESULT: Lazy<GetEraSummaryResult> = Lazy::new(|| GetEraSu
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     era_summary: ERA_SUMMARY.clone(),
 });
+static GET_ERA_EXECUTION_STATS_PARAMS: Lazy<GetEraExecutionStatsParams> =
+    Lazy::new(|| GetEraExecutionStatsParams {
+        era_id: EraExecutionStats::doc_example().era_id(),
+    });
+static GET_ERA_EXECUTION_STATS_RESULT: Lazy<GetEraExecutionStatsResult> =
+    Lazy::new(|| GetEraExecutionStatsResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        era_execution_stats: EraExecutionStats::doc_example().clone(),
+    });
 
 /// Identifier for possible ways to retrieve a block.
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, JsonSchema)]
@@ -470,6 +481,67 @@ impl RpcWithOptionalParams for GetEraSummary {
     }
 }
 
+/// Params for "chain_get_era_execution_stats" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetEraExecutionStatsParams {
+    /// The era to retrieve the execution statistics of.
+    pub era_id: EraId,
+}
+
+impl DocExample for GetEraExecutionStatsParams {
+    fn doc_example() -> &'static Self {
+        &GET_ERA_EXECUTION_STATS_PARAMS
+    }
+}
+
+/// Result for "chain_get_era_execution_stats" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetEraExecutionStatsResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The era execution statistics.
+    pub era_execution_stats: EraExecutionStats,
+}
+
+impl DocExample for GetEraExecutionStatsResult {
+    fn doc_example() -> &'static Self {
+        &GET_ERA_EXECUTION_STATS_RESULT
+    }
+}
+
+/// "chain_get_era_execution_stats" RPC.
+pub struct GetEraExecutionStats {}
+
+#[async_trait]
+impl RpcWithParams for GetEraExecutionStats {
+    const METHOD: &'static str = "chain_get_era_execution_stats";
+    type RequestParams = GetEraExecutionStatsParams;
+    type ResponseResult = GetEraExecutionStatsResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        match effect_builder
+            .get_era_execution_stats_from_storage(params.era_id)
+            .await
+        {
+            Some(era_execution_stats) => Ok(Self::ResponseResult {
+                api_version,
+                era_execution_stats,
+            }),
+            None => Err(Error::new(
+                ErrorCode::NoSuchEraExecutionStats,
+                format!("no blocks of {} executed by this node", params.era_id),
+            )),
+        }
+    }
+}
+
 pub(super) async fn get_block_with_metadata<REv: ReactorEventT>(
     maybe_id: Option<BlockIdentifier>,
     only_from_available_block_range: bool,
@@ -548,7 +620,7 @@ async fn get_era_summary<REv: ReactorEventT>(
     async fn handle_query_result<REv: ReactorEventT>(
         effect_builder: EffectBuilder<REv>,
         block: &Block,
-        result: Result<QueryResult, engine_state::Error>,
+        result: Result<QueryResult, StateQueryError>,
     ) -> Result<EraSummary, Error> {
         let (value, proofs) =
             state::handle_query_result(effect_builder, *block.state_root_hash(), result).await?;
@@ -566,6 +638,7 @@ async fn get_era_summary<REv: ReactorEventT>(
         .make_request(
             |responder| RpcRequest::QueryGlobalState {
                 state_root_hash: *block.state_root_hash(),
+                block_height: Some(block.height()),
                 base_key: Key::EraSummary,
                 path: vec![],
                 responder,
@@ -582,6 +655,7 @@ async fn get_era_summary<REv: ReactorEventT>(
         .make_request(
             |responder| RpcRequest::QueryGlobalState {
                 state_root_hash: *block.state_root_hash(),
+                block_height: Some(block.height()),
                 base_key: Key::EraInfo(block.header().era_id()),
                 path: vec![],
                 responder,
@@ -44,10 +44,18 @@ pub(super) static MERKLE_PROOF: Lazy<String> = Lazy::new(|| {
 pub(super) async fn run_query_and_encode<REv: ReactorEventT>(
     effect_builder: EffectBuilder<REv>,
     state_root_hash: Digest,
+    block_height: Option<u64>,
     base_key: Key,
     path: Vec<String>,
 ) -> Result<(StoredValue, String), Error> {
-    let (value, proofs) = state::run_query(effect_builder, state_root_hash, base_key, path).await?;
+    let (value, proofs) = state::run_query(
+        effect_builder,
+        state_root_hash,
+        block_height,
+        base_key,
+        path,
+    )
+    .await?;
     encode_query_success(value, proofs)
 }
 
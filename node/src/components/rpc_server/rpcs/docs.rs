@@ -18,12 +18,15 @@ use casper_types::ProtocolVersion;
 use super::{
     account::PutDeploy,
     chain::{
-        GetBlock, GetBlockTransfers, GetEraInfoBySwitchBlock, GetEraSummary, GetStateRootHash,
+        GetBlock, GetBlockTransfers, GetEraExecutionStats, GetEraInfoBySwitchBlock, GetEraSummary,
+        GetStateRootHash,
+    },
+    info::{
+        GetChainspec, GetDeploy, GetPeers, GetStatus, GetTransactionReceipt, GetValidatorChanges,
     },
-    info::{GetChainspec, GetDeploy, GetPeers, GetStatus, GetValidatorChanges},
     state::{
-        GetAccountInfo, GetAuctionInfo, GetBalance, GetDictionaryItem, GetItem, QueryBalance,
-        QueryGlobalState,
+        GetAccountInfo, GetAuctionInfo, GetBalance, GetDictionaryItem, GetItem, GetUnbonds,
+        QueryBalance, QueryGlobalState,
     },
     Error, ReactorEventT, RpcWithOptionalParams, RpcWithParams, RpcWithoutParams,
 };
@@ -70,6 +73,9 @@ pub(crate) static OPEN_RPC_SCHEMA: Lazy<OpenRpcSchema> = Lazy::new(|| {
 
     schema.push_with_params::<PutDeploy>("receives a Deploy to be executed by the network");
     schema.push_with_params::<GetDeploy>("returns a Deploy from the network");
+    schema.push_with_params::<GetTransactionReceipt>(
+        "returns the receipt of a Deploy executed by the node",
+    );
     schema.push_with_params::<GetAccountInfo>("returns an Account from the network");
     schema.push_with_params::<GetDictionaryItem>("returns an item from a Dictionary");
     schema.push_with_params::<QueryGlobalState>(
@@ -105,10 +111,16 @@ pub(crate) static OPEN_RPC_SCHEMA: Lazy<OpenRpcSchema> = Lazy::new(|| {
         "returns the bids and validators as of either a specific block (by height or hash), or \
         the most recently added block",
     );
+    schema.push_with_params::<GetUnbonds>(
+        "returns the pending unbonding requests made by a validator or delegator",
+    );
     schema.push_with_optional_params::<GetEraSummary>(
         "returns the era summary at either a specific block (by height or hash), or the most \
         recently added block",
     );
+    schema.push_with_params::<GetEraExecutionStats>(
+        "returns the aggregate statistics of the blocks of an era executed by the node",
+    );
 
     schema
 });
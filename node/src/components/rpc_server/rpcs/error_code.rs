@@ -35,6 +35,15 @@ pub enum ErrorCode {
     FailedToGetTrie = -32011,
     /// The requested state root hash was not found.
     NoSuchStateRoot = -32012,
+    /// No execution statistics were found for the requested era.
+    NoSuchEraExecutionStats = -32013,
+    /// No receipt was found for the requested deploy.
+    NoSuchTransactionReceipt = -32014,
+    /// The requested state root is older than this node permits queries against, or isn't that of
+    /// any block known to the node.
+    StateRootTooOld = -32015,
+    /// The node is serving too many queries of global state to accept another.
+    QueryQueueFull = -32016,
 }
 
 impl From<ErrorCode> for (i64, &'static str) {
@@ -59,6 +68,14 @@ impl From<ErrorCode> for (i64, &'static str) {
             }
             ErrorCode::FailedToGetTrie => (error_code as i64, "Failed to get trie"),
             ErrorCode::NoSuchStateRoot => (error_code as i64, "No such state root"),
+            ErrorCode::NoSuchEraExecutionStats => {
+                (error_code as i64, "No such era execution stats")
+            }
+            ErrorCode::NoSuchTransactionReceipt => {
+                (error_code as i64, "No such transaction receipt")
+            }
+            ErrorCode::StateRootTooOld => (error_code as i64, "State root too old"),
+            ErrorCode::QueryQueueFull => (error_code as i64, "Query queue full"),
         }
     }
 }
@@ -11,7 +11,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
-use casper_types::{EraId, ExecutionResult, ProtocolVersion, PublicKey};
+use casper_types::{EraId, ExecutionResult, ProtocolVersion, PublicKey, TransactionReceipt};
 
 use super::{
     docs::{DocExample, DOCS_EXAMPLE_PROTOCOL_VERSION},
@@ -40,6 +40,15 @@ static GET_DEPLOY_RESULT: Lazy<GetDeployResult> = Lazy::new(|| GetDeployResult {
     }],
     block_hash_and_height: None,
 });
+static GET_TRANSACTION_RECEIPT_PARAMS: Lazy<GetTransactionReceiptParams> =
+    Lazy::new(|| GetTransactionReceiptParams {
+        deploy_hash: TransactionReceipt::example().deploy_hash.into(),
+    });
+static GET_TRANSACTION_RECEIPT_RESULT: Lazy<GetTransactionReceiptResult> =
+    Lazy::new(|| GetTransactionReceiptResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        transaction_receipt: TransactionReceipt::example().clone(),
+    });
 static GET_PEERS_RESULT: Lazy<GetPeersResult> = Lazy::new(|| GetPeersResult {
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     peers: GetStatusResult::doc_example().peers.clone(),
@@ -177,6 +186,67 @@ impl RpcWithParams for GetDeploy {
     }
 }
 
+/// Params for "info_get_transaction_receipt" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetTransactionReceiptParams {
+    /// The deploy hash.
+    pub deploy_hash: DeployHash,
+}
+
+impl DocExample for GetTransactionReceiptParams {
+    fn doc_example() -> &'static Self {
+        &GET_TRANSACTION_RECEIPT_PARAMS
+    }
+}
+
+/// Result for "info_get_transaction_receipt" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetTransactionReceiptResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The receipt of the executed deploy.
+    pub transaction_receipt: TransactionReceipt,
+}
+
+impl DocExample for GetTransactionReceiptResult {
+    fn doc_example() -> &'static Self {
+        &GET_TRANSACTION_RECEIPT_RESULT
+    }
+}
+
+/// "info_get_transaction_receipt" RPC.
+pub struct GetTransactionReceipt {}
+
+#[async_trait]
+impl RpcWithParams for GetTransactionReceipt {
+    const METHOD: &'static str = "info_get_transaction_receipt";
+    type RequestParams = GetTransactionReceiptParams;
+    type ResponseResult = GetTransactionReceiptResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        match effect_builder
+            .get_transaction_receipt_from_storage(params.deploy_hash)
+            .await
+        {
+            Some(transaction_receipt) => Ok(Self::ResponseResult {
+                api_version,
+                transaction_receipt,
+            }),
+            None => Err(Error::new(
+                ErrorCode::NoSuchTransactionReceipt,
+                format!("{} not executed by this node", params.deploy_hash),
+            )),
+        }
+    }
+}
+
 /// Result for "info_get_peers" RPC response.
 #[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -11,8 +11,8 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use casper_execution_engine::core::engine_state::Error as EngineStateError;
-use casper_json_rpc::ReservedErrorCode;
-use casper_types::{ExecutionResult, ProtocolVersion};
+use casper_json_rpc::{RequestHandlersBuilder, ReservedErrorCode};
+use casper_types::{ExecutionErrorCode, ExecutionResult, ProtocolVersion};
 
 use super::{
     chain::BlockIdentifier,
@@ -21,7 +21,7 @@ use super::{
     Error, ErrorCode, ReactorEventT, RpcWithParams,
 };
 use crate::{
-    components::contract_runtime::SpeculativeExecutionState,
+    components::contract_runtime::{SpeculativeExecutionState, StateQueryError},
     effect::EffectBuilder,
     types::{Block, BlockHash, Deploy},
 };
@@ -35,6 +35,17 @@ static SPECULATIVE_EXEC_RESULT: Lazy<SpeculativeExecResult> = Lazy::new(|| Specu
     block_hash: *Block::doc_example().hash(),
     execution_result: ExecutionResult::example().clone(),
 });
+static SPECULATIVE_EXEC_BUNDLE_PARAMS: Lazy<SpeculativeExecBundleParams> =
+    Lazy::new(|| SpeculativeExecBundleParams {
+        block_identifier: Some(BlockIdentifier::Hash(*Block::doc_example().hash())),
+        deploys: vec![Deploy::doc_example().clone()],
+    });
+static SPECULATIVE_EXEC_BUNDLE_RESULT: Lazy<SpeculativeExecBundleResult> =
+    Lazy::new(|| SpeculativeExecBundleResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        block_hash: *Block::doc_example().hash(),
+        execution_results: vec![ExecutionResult::example().clone()],
+    });
 
 /// Params for "speculative_exec" RPC request.
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
@@ -71,6 +82,45 @@ impl DocExample for SpeculativeExecResult {
     }
 }
 
+/// Params for "speculative_exec_bundle" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SpeculativeExecBundleParams {
+    /// Block hash on top of which to execute the deploys.
+    pub block_identifier: Option<BlockIdentifier>,
+    /// Deploys to execute, in order.  Each is executed on top of the effects of those before it.
+    ///
+    /// Note that every deploy is validated against the global state of the given block, not
+    /// against the state left by the deploys before it, so e.g. a deploy whose account is created
+    /// or funded by an earlier deploy of the bundle is rejected.
+    pub deploys: Vec<Deploy>,
+}
+
+impl DocExample for SpeculativeExecBundleParams {
+    fn doc_example() -> &'static Self {
+        &SPECULATIVE_EXEC_BUNDLE_PARAMS
+    }
+}
+
+/// Result for "speculative_exec_bundle" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SpeculativeExecBundleResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// Hash of the block on top of which the deploys were executed.
+    pub block_hash: BlockHash,
+    /// Results of the executions, in the order of the given deploys.
+    pub execution_results: Vec<ExecutionResult>,
+}
+
+impl DocExample for SpeculativeExecBundleResult {
+    fn doc_example() -> &'static Self {
+        &SPECULATIVE_EXEC_BUNDLE_RESULT
+    }
+}
+
 /// "speculative_exec" RPC
 pub struct SpeculativeExec {}
 
@@ -101,6 +151,7 @@ impl RpcWithParams for SpeculativeExec {
         let block_hash = *block.hash();
         let execution_prestate = SpeculativeExecutionState {
             state_root_hash: *block.state_root_hash(),
+            block_height: block.height(),
             block_time: block.timestamp(),
             protocol_version: block.protocol_version(),
         };
@@ -130,50 +181,229 @@ impl RpcWithParams for SpeculativeExec {
                 ErrorCode::NoSuchBlock,
                 "block hash not found".to_string(),
             )),
-            Err(error) => {
-                let rpc_error = match error {
-                    EngineStateError::RootNotFound(_) => Error::new(ErrorCode::NoSuchStateRoot, ""),
-                    EngineStateError::WasmPreprocessing(error) => {
-                        Error::new(ErrorCode::InvalidDeploy, error.to_string())
-                    }
-                    EngineStateError::InvalidDeployItemVariant(error) => {
-                        Error::new(ErrorCode::InvalidDeploy, error)
-                    }
-                    EngineStateError::InvalidProtocolVersion(_) => Error::new(
-                        ErrorCode::InvalidDeploy,
-                        format!("deploy used invalid protocol version {}", error),
-                    ),
-                    EngineStateError::Deploy => Error::new(ErrorCode::InvalidDeploy, ""),
-                    EngineStateError::Genesis(_)
-                    | EngineStateError::WasmSerialization(_)
-                    | EngineStateError::Exec(_)
-                    | EngineStateError::Storage(_)
-                    | EngineStateError::Authorization
-                    | EngineStateError::InsufficientPayment
-                    | EngineStateError::GasConversionOverflow
-                    | EngineStateError::Finalization
-                    | EngineStateError::Bytesrepr(_)
-                    | EngineStateError::Mint(_)
-                    | EngineStateError::InvalidKeyVariant
-                    | EngineStateError::ProtocolUpgrade(_)
-                    | EngineStateError::CommitError(_)
-                    | EngineStateError::MissingSystemContractRegistry
-                    | EngineStateError::MissingSystemContractHash(_)
-                    | EngineStateError::RuntimeStackOverflow
-                    | EngineStateError::FailedToGetWithdrawKeys
-                    | EngineStateError::FailedToGetStoredWithdraws
-                    | EngineStateError::FailedToGetWithdrawPurses
-                    | EngineStateError::FailedToRetrieveUnbondingDelay
-                    | EngineStateError::FailedToRetrieveEraId => {
-                        Error::new(ReservedErrorCode::InternalError, error.to_string())
-                    }
-                    _ => Error::new(
-                        ReservedErrorCode::InternalError,
-                        format!("Unhandled engine state error: {}", error),
-                    ),
+            Err(error) => Err(state_query_error_to_rpc_error(error)),
+        }
+    }
+}
+
+/// "speculative_exec_bundle" RPC
+pub struct SpeculativeExecBundle {}
+
+impl SpeculativeExecBundle {
+    /// Registers this RPC as the handler for "speculative_exec_bundle" requests, rejecting bundles
+    /// of more than `max_bundle_length` deploys before any of them is validated or executed.
+    pub(in crate::components::rpc_server) fn register_as_handler_with_max_bundle_length<
+        REv: ReactorEventT,
+    >(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        max_bundle_length: u32,
+        handlers_builder: &mut RequestHandlersBuilder,
+    ) {
+        let handler = move |maybe_params| async move {
+            let params = Self::try_parse_params(maybe_params)?;
+            check_bundle_length(&params.deploys, max_bundle_length)?;
+            Self::do_handle_request(effect_builder, api_version, params).await
+        };
+        handlers_builder.register_handler(Self::METHOD, Arc::new(handler))
+    }
+}
+
+/// Returns an error if the bundle is empty or holds more than `max_bundle_length` deploys.
+fn check_bundle_length(deploys: &[Deploy], max_bundle_length: u32) -> Result<(), Error> {
+    if deploys.is_empty() {
+        return Err(Error::new(
+            ErrorCode::InvalidDeploy,
+            "no deploys to execute".to_string(),
+        ));
+    }
+    if deploys.len() > max_bundle_length as usize {
+        return Err(Error::new(
+            ErrorCode::InvalidDeploy,
+            format!(
+                "bundle of {} deploys exceeds the maximum of {}",
+                deploys.len(),
+                max_bundle_length
+            ),
+        ));
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl RpcWithParams for SpeculativeExecBundle {
+    const METHOD: &'static str = "speculative_exec_bundle";
+    type RequestParams = SpeculativeExecBundleParams;
+    type ResponseResult = SpeculativeExecBundleResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let SpeculativeExecBundleParams {
+            block_identifier: maybe_block_id,
+            deploys,
+        } = params;
+        let deploys: Vec<Arc<Deploy>> = deploys.into_iter().map(Arc::new).collect();
+        let only_from_available_block_range = true;
+
+        let block = common::get_block(
+            maybe_block_id,
+            only_from_available_block_range,
+            effect_builder,
+        )
+        .await?;
+        let block_hash = *block.hash();
+        let execution_prestate = SpeculativeExecutionState {
+            state_root_hash: *block.state_root_hash(),
+            block_height: block.height(),
+            block_time: block.timestamp(),
+            protocol_version: block.protocol_version(),
+        };
+
+        // The deploy acceptor checks the account of each deploy against the block's state root.
+        // The effects of the deploys executed earlier in the bundle are never committed, so they
+        // can't be taken into account here.
+        for deploy in &deploys {
+            let accept_deploy_result = effect_builder
+                .try_accept_deploy(Arc::clone(deploy), Some(Box::new(block.header().clone())))
+                .await;
+
+            if let Err(error) = accept_deploy_result {
+                return Err(Error::new(
+                    ErrorCode::InvalidDeploy,
+                    format!("deploy {}: {}", deploy.hash(), error),
+                ));
+            }
+        }
+
+        let result = effect_builder
+            .speculative_execute_deploy_bundle(execution_prestate, deploys)
+            .await;
+
+        match result {
+            Ok(Some(execution_results)) => {
+                let result = Self::ResponseResult {
+                    api_version,
+                    block_hash,
+                    execution_results,
                 };
-                Err(rpc_error)
+                Ok(result)
             }
+            // The execution engine returned other than exactly one result for one of the deploys.
+            Ok(None) => Err(Error::new(
+                ReservedErrorCode::InternalError,
+                "failed to get a single execution result for each deploy".to_string(),
+            )),
+            Err(error) => Err(state_query_error_to_rpc_error(error)),
+        }
+    }
+}
+
+/// Converts an error returned by the contract runtime into the RPC error to return to the client.
+fn state_query_error_to_rpc_error(error: StateQueryError) -> Error {
+    match error {
+        StateQueryError::StateRootTooOld { .. }
+        | StateQueryError::StateRootHeightUnknown { .. } => {
+            Error::new(ErrorCode::StateRootTooOld, error.to_string())
+        }
+        StateQueryError::EngineState(error) => engine_state_error_to_rpc_error(error),
+    }
+}
+
+/// Converts an error returned by the execution engine into the RPC error to return to the client.
+///
+/// The "data" field of the RPC error describes the error, while the "details" field holds the
+/// stable [`ExecutionErrorCode`] identifying it.
+fn engine_state_error_to_rpc_error(error: EngineStateError) -> Error {
+    let error_code: ExecutionErrorCode = error.error_code();
+    let rpc_error = match error {
+        EngineStateError::RootNotFound(_) => Error::new(ErrorCode::NoSuchStateRoot, ""),
+        EngineStateError::WasmPreprocessing(error) => {
+            Error::new(ErrorCode::InvalidDeploy, error.to_string())
+        }
+        EngineStateError::InvalidDeployItemVariant(error) => {
+            Error::new(ErrorCode::InvalidDeploy, error)
+        }
+        EngineStateError::InvalidProtocolVersion(_) => Error::new(
+            ErrorCode::InvalidDeploy,
+            format!("deploy used invalid protocol version {}", error),
+        ),
+        EngineStateError::Deploy => Error::new(ErrorCode::InvalidDeploy, ""),
+        EngineStateError::Genesis(_)
+        | EngineStateError::WasmSerialization(_)
+        | EngineStateError::Exec(_)
+        | EngineStateError::Storage(_)
+        | EngineStateError::Authorization
+        | EngineStateError::InsufficientPayment
+        | EngineStateError::GasConversionOverflow
+        | EngineStateError::Finalization
+        | EngineStateError::Bytesrepr(_)
+        | EngineStateError::Mint(_)
+        | EngineStateError::InvalidKeyVariant
+        | EngineStateError::ProtocolUpgrade(_)
+        | EngineStateError::CommitError(_)
+        | EngineStateError::MissingSystemContractRegistry
+        | EngineStateError::MissingSystemContractHash(_)
+        | EngineStateError::RuntimeStackOverflow
+        | EngineStateError::FailedToGetWithdrawKeys
+        | EngineStateError::FailedToGetStoredWithdraws
+        | EngineStateError::FailedToGetWithdrawPurses
+        | EngineStateError::FailedToRetrieveUnbondingDelay
+        | EngineStateError::FailedToRetrieveEraId => {
+            Error::new(ReservedErrorCode::InternalError, error.to_string())
+        }
+        _ => Error::new(
+            ReservedErrorCode::InternalError,
+            format!("Unhandled engine state error: {}", error),
+        ),
+    };
+    rpc_error.with_details(error_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_hashing::Digest;
+    use casper_types::testing::TestRng;
+
+    use super::*;
+
+    #[test]
+    fn should_check_bundle_length() {
+        let mut rng = TestRng::new();
+        let max_bundle_length = 3;
+        let deploys: Vec<Deploy> = (0..=max_bundle_length)
+            .map(|_| Deploy::random(&mut rng))
+            .collect();
+
+        assert!(check_bundle_length(&[], max_bundle_length).is_err());
+        for length in 1..=max_bundle_length as usize {
+            assert!(check_bundle_length(&deploys[..length], max_bundle_length).is_ok());
         }
+        assert!(check_bundle_length(&deploys, max_bundle_length).is_err());
+    }
+
+    #[test]
+    fn should_return_engine_state_error_code_as_details() {
+        let error = EngineStateError::RootNotFound(Digest::default());
+        let expected_details = serde_json::to_value(error.error_code()).unwrap();
+
+        let rpc_error = serde_json::to_value(engine_state_error_to_rpc_error(error)).unwrap();
+        assert_eq!(rpc_error["data"], serde_json::Value::String(String::new()));
+        assert_eq!(rpc_error["details"], expected_details);
+    }
+
+    #[test]
+    fn should_reject_execution_on_old_state_root() {
+        let error = StateQueryError::StateRootTooOld {
+            state_root_hash: Digest::default(),
+            block_height: 1,
+            tip_height: 100,
+            max_age: 10,
+        };
+        let expected_error = Error::new(ErrorCode::StateRootTooOld, error.to_string());
+
+        assert_eq!(state_query_error_to_rpc_error(error), expected_error);
     }
 }
@@ -12,7 +12,7 @@ use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info, warn};
 
 use casper_execution_engine::{
-    core::engine_state::{self, BalanceResult, GetBidsResult, QueryResult},
+    core::engine_state::{self, BalanceResult, GetBidsResult, GetUnbondsRequest, QueryResult},
     storage::trie::merkle_proof::TrieMerkleProof,
 };
 use casper_hashing::Digest;
@@ -20,11 +20,13 @@ use casper_json_rpc::ReservedErrorCode;
 use casper_types::{
     account::AccountHash,
     bytesrepr::{Bytes, ToBytes},
-    CLValue, Key, ProtocolVersion, PublicKey, SecretKey, StoredValue as DomainStoredValue, URef,
-    U512,
+    system::auction::UnbondingPurse,
+    CLValue, EraId, Key, ProtocolVersion, PublicKey, SecretKey, StoredValue as DomainStoredValue,
+    URef, U512,
 };
 
 use crate::{
+    components::contract_runtime::StateQueryError,
     effect::EffectBuilder,
     reactor::QueueKind,
     rpcs::{
@@ -66,6 +68,39 @@ static GET_AUCTION_INFO_RESULT: Lazy<GetAuctionInfoResult> = Lazy::new(|| GetAuc
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     auction_state: AuctionState::doc_example().clone(),
 });
+static GET_UNBONDS_PARAMS: Lazy<GetUnbondsParams> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([0; 32]).unwrap();
+    let public_key = PublicKey::from(&secret_key);
+    GetUnbondsParams {
+        public_key,
+        block_identifier: Some(BlockIdentifier::Hash(*Block::doc_example().hash())),
+    }
+});
+static GET_UNBONDS_RESULT: Lazy<GetUnbondsResult> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([0; 32]).unwrap();
+    let public_key = PublicKey::from(&secret_key);
+    let bonding_purse = URef::from_formatted_str(
+        "uref-09480c3248ef76b603d386f3f4f8a5f87f597d4eaffd475433f861af187ab5db-007",
+    )
+    .unwrap();
+    let era_of_creation = EraId::new(41);
+    let unbonding_purse = UnbondingPurse::new(
+        bonding_purse,
+        public_key.clone(),
+        public_key,
+        era_of_creation,
+        U512::from(1_000_000_000),
+        None,
+    );
+    GetUnbondsResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        state_root_hash: *Block::doc_example().header().state_root_hash(),
+        unbonds: vec![PendingUnbond {
+            unbonding_purse,
+            era_available: era_of_creation + 7,
+        }],
+    }
+});
 static GET_ACCOUNT_INFO_PARAMS: Lazy<GetAccountInfoParams> = Lazy::new(|| {
     let secret_key = SecretKey::ed25519_from_bytes([0; 32]).unwrap();
     let public_key = PublicKey::from(&secret_key);
@@ -197,6 +232,7 @@ impl RpcWithParams for GetItem {
         let (stored_value, merkle_proof) = common::run_query_and_encode(
             effect_builder,
             params.state_root_hash,
+            None,
             base_key,
             params.path,
         )
@@ -279,6 +315,7 @@ impl RpcWithParams for GetBalance {
             .make_request(
                 |responder| RpcRequest::GetBalance {
                     state_root_hash: params.state_root_hash,
+                    block_height: None,
                     purse_uref,
                     responder,
                 },
@@ -296,6 +333,10 @@ impl RpcWithParams for GetBalance {
                 ));
             }
             Err(error) => {
+                if let Some(rpc_error) = rejected_query_error(&error) {
+                    info!("get-balance rejected: {}", error);
+                    return Err(rpc_error);
+                }
                 info!("get-balance failed to execute: {}", error);
                 return Err(Error::new(
                     ErrorCode::GetBalanceFailedToExecute,
@@ -398,8 +439,8 @@ impl RpcWithOptionalParams for GetAuctionInfo {
             )
             .await;
 
-        let bids = match get_bids_result {
-            Ok(GetBidsResult::Success { bids }) => bids,
+        let (bids, bid_metadata) = match get_bids_result {
+            Ok(GetBidsResult::Success { bids, bid_metadata }) => (bids, bid_metadata),
             Ok(GetBidsResult::RootNotFound) => {
                 error!(
                     block_hash=?block.hash(),
@@ -458,7 +499,13 @@ impl RpcWithOptionalParams for GetAuctionInfo {
             }
         };
 
-        let auction_state = AuctionState::new(state_root_hash, block_height, era_validators, bids);
+        let auction_state = AuctionState::new(
+            state_root_hash,
+            block_height,
+            era_validators,
+            bids,
+            bid_metadata,
+        );
 
         let result = Self::ResponseResult {
             api_version,
@@ -468,6 +515,129 @@ impl RpcWithOptionalParams for GetAuctionInfo {
     }
 }
 
+/// Params for "state_get_unbonds" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetUnbondsParams {
+    /// The public key of the validator or delegator which made the unbonding requests.
+    pub public_key: PublicKey,
+    /// The block identifier.
+    pub block_identifier: Option<BlockIdentifier>,
+}
+
+impl DocExample for GetUnbondsParams {
+    fn doc_example() -> &'static Self {
+        &GET_UNBONDS_PARAMS
+    }
+}
+
+/// An unbonding request which has not yet been paid out.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PendingUnbond {
+    /// The unbonding request as stored in global state.
+    pub unbonding_purse: UnbondingPurse,
+    /// The era in which the amount is paid out, or redelegated if the request is a redelegation.
+    pub era_available: EraId,
+}
+
+/// Result for "state_get_unbonds" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetUnbondsResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The state root hash the unbonding requests were read from.
+    pub state_root_hash: Digest,
+    /// The pending unbonding requests, ordered by validator and then from oldest to newest.
+    pub unbonds: Vec<PendingUnbond>,
+}
+
+impl DocExample for GetUnbondsResult {
+    fn doc_example() -> &'static Self {
+        &GET_UNBONDS_RESULT
+    }
+}
+
+/// "state_get_unbonds" RPC.
+pub struct GetUnbonds {}
+
+#[async_trait]
+impl RpcWithParams for GetUnbonds {
+    const METHOD: &'static str = "state_get_unbonds";
+    type RequestParams = GetUnbondsParams;
+    type ResponseResult = GetUnbondsResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        // This RPC request is restricted by the block availability index.
+        let only_from_available_block_range = true;
+
+        let block = common::get_block(
+            params.block_identifier,
+            only_from_available_block_range,
+            effect_builder,
+        )
+        .await?;
+
+        let state_root_hash = *block.header().state_root_hash();
+        let get_unbonds_request = GetUnbondsRequest::new(state_root_hash, params.public_key);
+
+        let pending_unbonds = match effect_builder.get_unbonds(get_unbonds_request).await {
+            Ok(engine_state::GetUnbondsResult::Success { pending_unbonds }) => pending_unbonds,
+            Ok(engine_state::GetUnbondsResult::RootNotFound) => {
+                error!(
+                    block_hash=?block.hash(),
+                    ?state_root_hash,
+                    "root not found while trying to get unbonds"
+                );
+                return Err(Error::new(
+                    ReservedErrorCode::InternalError,
+                    format!(
+                        "root not found when getting unbonds at block {:?}",
+                        block.hash().inner()
+                    ),
+                ));
+            }
+            Err(error) => {
+                error!(
+                    block_hash=?block.hash(),
+                    ?state_root_hash,
+                    ?error,
+                    "failed to get unbonds"
+                );
+                return Err(Error::new(
+                    ReservedErrorCode::InternalError,
+                    format!(
+                        "error getting unbonds at block {:?}: {}",
+                        block.hash().inner(),
+                        error
+                    ),
+                ));
+            }
+        };
+
+        let unbonds = pending_unbonds
+            .into_iter()
+            .map(|pending_unbond| PendingUnbond {
+                unbonding_purse: pending_unbond.unbonding_purse().clone(),
+                era_available: pending_unbond.era_available(),
+            })
+            .collect();
+
+        let result = Self::ResponseResult {
+            api_version,
+            state_root_hash,
+            unbonds,
+        };
+        Ok(result)
+    }
+}
+
 /// Identifier of an account.
 #[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 #[serde(deny_unknown_fields, untagged)]
@@ -547,8 +717,14 @@ impl RpcWithParams for GetAccountInfo {
             };
             Key::Account(account_hash)
         };
-        let (stored_value, merkle_proof) =
-            common::run_query_and_encode(effect_builder, state_root_hash, base_key, vec![]).await?;
+        let (stored_value, merkle_proof) = common::run_query_and_encode(
+            effect_builder,
+            state_root_hash,
+            Some(block.height()),
+            base_key,
+            vec![],
+        )
+        .await?;
 
         let account = if let StoredValue::Account(account) = stored_value {
             account
@@ -744,8 +920,14 @@ impl RpcWithParams for GetDictionaryItem {
                 };
 
                 let empty_path = Vec::new();
-                let (value, _proofs) =
-                    run_query(effect_builder, params.state_root_hash, base_key, empty_path).await?;
+                let (value, _proofs) = run_query(
+                    effect_builder,
+                    params.state_root_hash,
+                    None,
+                    base_key,
+                    empty_path,
+                )
+                .await?;
                 params
                     .dictionary_identifier
                     .get_dictionary_address(Some(value))?
@@ -874,9 +1056,17 @@ impl RpcWithParams for QueryGlobalState {
             }
         };
 
-        let (stored_value, merkle_proof) =
-            common::run_query_and_encode(effect_builder, state_root_hash, base_key, params.path)
-                .await?;
+        let block_height = maybe_block_header
+            .as_ref()
+            .map(|block_header| block_header.height);
+        let (stored_value, merkle_proof) = common::run_query_and_encode(
+            effect_builder,
+            state_root_hash,
+            block_height,
+            base_key,
+            params.path,
+        )
+        .await?;
 
         let result = Self::ResponseResult {
             api_version,
@@ -946,7 +1136,7 @@ impl RpcWithParams for QueryBalance {
         api_version: ProtocolVersion,
         params: Self::RequestParams,
     ) -> Result<Self::ResponseResult, Error> {
-        let state_root_hash = match params.state_identifier {
+        let (state_root_hash, block_height) = match params.state_identifier {
             None => match effect_builder
                 .get_highest_complete_block_header_from_storage()
                 .await
@@ -957,13 +1147,18 @@ impl RpcWithParams for QueryBalance {
                         "query-balance failed to retrieve highest block header",
                     ))
                 }
-                Some(block_header) => *block_header.state_root_hash(),
+                Some(block_header) => {
+                    (*block_header.state_root_hash(), Some(block_header.height()))
+                }
             },
             Some(state_identifier) => {
-                let (state_root_hash, _) =
+                let (state_root_hash, maybe_block_header) =
                     get_state_root_hash_and_optional_header(effect_builder, state_identifier)
                         .await?;
-                state_root_hash
+                (
+                    state_root_hash,
+                    maybe_block_header.map(|block_header| block_header.height),
+                )
             }
         };
 
@@ -972,13 +1167,16 @@ impl RpcWithParams for QueryBalance {
                 let account = get_account(
                     effect_builder,
                     state_root_hash,
+                    block_height,
                     account_public_key.to_account_hash(),
                 )
                 .await?;
                 account.main_purse()
             }
             PurseIdentifier::MainPurseUnderAccountHash(account_hash) => {
-                let account = get_account(effect_builder, state_root_hash, account_hash).await?;
+                let account =
+                    get_account(effect_builder, state_root_hash, block_height, account_hash)
+                        .await?;
                 account.main_purse()
             }
             PurseIdentifier::PurseUref(purse_uref) => purse_uref,
@@ -989,6 +1187,7 @@ impl RpcWithParams for QueryBalance {
             .make_request(
                 |responder| RpcRequest::GetBalance {
                     state_root_hash,
+                    block_height,
                     purse_uref,
                     responder,
                 },
@@ -1013,6 +1212,10 @@ impl RpcWithParams for QueryBalance {
                 ));
             }
             Err(error) => {
+                if let Some(rpc_error) = rejected_query_error(&error) {
+                    info!("query-balance rejected: {}", error);
+                    return Err(rpc_error);
+                }
                 info!("query-balance failed to execute: {}", error);
                 return Err(Error::new(
                     ErrorCode::GetBalanceFailedToExecute,
@@ -1110,6 +1313,7 @@ type QuerySuccess = (
 pub(super) async fn run_query<REv: ReactorEventT>(
     effect_builder: EffectBuilder<REv>,
     state_root_hash: Digest,
+    block_height: Option<u64>,
     base_key: Key,
     path: Vec<String>,
 ) -> Result<QuerySuccess, Error> {
@@ -1117,6 +1321,7 @@ pub(super) async fn run_query<REv: ReactorEventT>(
         .make_request(
             |responder| RpcRequest::QueryGlobalState {
                 state_root_hash,
+                block_height,
                 base_key,
                 path,
                 responder,
@@ -1133,7 +1338,7 @@ pub(super) async fn run_query<REv: ReactorEventT>(
 pub(super) async fn handle_query_result<REv: ReactorEventT>(
     effect_builder: EffectBuilder<REv>,
     state_root_hash: Digest,
-    query_result: Result<QueryResult, engine_state::Error>,
+    query_result: Result<QueryResult, StateQueryError>,
 ) -> Result<QuerySuccess, Error> {
     match query_result {
         Ok(QueryResult::Success { value, proofs }) => Ok((*value, proofs)),
@@ -1155,6 +1360,10 @@ pub(super) async fn handle_query_result<REv: ReactorEventT>(
             ))
         }
         Err(error) => {
+            if let Some(rpc_error) = rejected_query_error(&error) {
+                info!(%error, "query rejected");
+                return Err(rpc_error);
+            }
             info!(?error, "query failed to execute");
             Err(Error::new(
                 ErrorCode::QueryFailedToExecute,
@@ -1164,14 +1373,31 @@ pub(super) async fn handle_query_result<REv: ReactorEventT>(
     }
 }
 
+/// Returns the error to send in the JSON-RPC response if the node declined to run a query, either
+/// as the queried state root is too old or of unknown age, or as too many queries are waiting to
+/// run.  Returns `None` if the query failed for any other reason.
+fn rejected_query_error(error: &StateQueryError) -> Option<Error> {
+    let error_code = match error {
+        StateQueryError::StateRootTooOld { .. }
+        | StateQueryError::StateRootHeightUnknown { .. } => ErrorCode::StateRootTooOld,
+        StateQueryError::EngineState(engine_state::Error::QueryQueueFull { .. }) => {
+            ErrorCode::QueryQueueFull
+        }
+        StateQueryError::EngineState(_) => return None,
+    };
+    Some(Error::new(error_code, error.to_string()))
+}
+
 async fn get_account<REv: ReactorEventT>(
     effect_builder: EffectBuilder<REv>,
     state_root_hash: Digest,
+    block_height: Option<u64>,
     account_hash: AccountHash,
 ) -> Result<JsonAccount, Error> {
     let (stored_value, _) = common::run_query_and_encode(
         effect_builder,
         state_root_hash,
+        block_height,
         Key::Account(account_hash),
         vec![],
     )
@@ -1231,3 +1457,37 @@ pub(super) async fn get_state_root_hash_and_optional_header<REv: ReactorEventT>(
         GlobalStateIdentifier::StateRootHash(state_root_hash) => Ok((state_root_hash, None)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_reject_query_of_old_state_root() {
+        let state_root_hash = Digest::hash([1; 32]);
+        let errors = [
+            StateQueryError::StateRootTooOld {
+                state_root_hash,
+                block_height: 1,
+                tip_height: 100,
+                max_age: 10,
+            },
+            StateQueryError::StateRootHeightUnknown {
+                state_root_hash,
+                max_age: 10,
+            },
+        ];
+        for error in &errors {
+            assert_eq!(
+                rejected_query_error(error),
+                Some(Error::new(ErrorCode::StateRootTooOld, error.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn should_not_reject_failed_query() {
+        let error = StateQueryError::from(engine_state::Error::RootNotFound(Digest::hash([1; 32])));
+        assert_eq!(rejected_query_error(&error), None);
+    }
+}
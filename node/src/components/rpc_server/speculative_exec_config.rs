@@ -11,6 +11,8 @@ const DEFAULT_QPS_LIMIT: u64 = 1;
 const DEFAULT_MAX_BODY_BYTES: u32 = 2_621_440;
 /// Default CORS origin.
 const DEFAULT_CORS_ORIGIN: &str = "";
+/// Default maximum number of deploys in a bundle executed by "speculative_exec_bundle".
+const DEFAULT_MAX_BUNDLE_LENGTH: u32 = 10;
 
 /// JSON-RPC HTTP server configuration.
 #[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
@@ -27,6 +29,9 @@ pub struct Config {
     pub max_body_bytes: u32,
     /// CORS origin.
     pub cors_origin: String,
+    /// Maximum number of deploys in a bundle executed by "speculative_exec_bundle".
+    #[serde(default = "default_max_bundle_length")]
+    pub max_bundle_length: u32,
 }
 
 impl Config {
@@ -38,6 +43,7 @@ impl Config {
             qps_limit: DEFAULT_QPS_LIMIT,
             max_body_bytes: DEFAULT_MAX_BODY_BYTES,
             cors_origin: DEFAULT_CORS_ORIGIN.to_string(),
+            max_bundle_length: DEFAULT_MAX_BUNDLE_LENGTH,
         }
     }
 }
@@ -47,3 +53,7 @@ impl Default for Config {
         Config::new()
     }
 }
+
+fn default_max_bundle_length() -> u32 {
+    DEFAULT_MAX_BUNDLE_LENGTH
+}
@@ -6,7 +6,10 @@ use casper_types::ProtocolVersion;
 use super::ReactorEventT;
 use crate::{
     effect::EffectBuilder,
-    rpcs::{speculative_exec::SpeculativeExec, RpcWithParams},
+    rpcs::{
+        speculative_exec::{SpeculativeExec, SpeculativeExecBundle},
+        RpcWithParams,
+    },
 };
 
 /// The URL path for all JSON-RPC requests.
@@ -22,9 +25,16 @@ pub(super) async fn run<REv: ReactorEventT>(
     qps_limit: u64,
     max_body_bytes: u32,
     cors_origin: String,
+    max_bundle_length: u32,
 ) {
     let mut handlers = RequestHandlersBuilder::new();
     SpeculativeExec::register_as_handler(effect_builder, api_version, &mut handlers);
+    SpeculativeExecBundle::register_as_handler_with_max_bundle_length(
+        effect_builder,
+        api_version,
+        max_bundle_length,
+        &mut handlers,
+    );
     let handlers = handlers.build();
 
     match cors_origin.as_str() {
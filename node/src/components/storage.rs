@@ -30,6 +30,7 @@
 //! The storage component itself is panic free and in general reports three classes of errors:
 //! Corruption, temporary resource exhaustion and potential bugs.
 
+mod block_body_archive;
 pub(crate) mod disjoint_sequences;
 mod error;
 mod lmdb_ext;
@@ -71,11 +72,13 @@ use tracing::{debug, error, info, trace, warn};
 use casper_hashing::Digest;
 use casper_types::{
     bytesrepr::{FromBytes, ToBytes},
-    EraId, ExecutionResult, ProtocolVersion, PublicKey, Timestamp, Transfer, Transform,
+    EraId, ExecutionResult, ProtocolVersion, PublicKey, Timestamp, TransactionReceipt, Transfer,
+    Transform,
 };
 
 use crate::{
     components::{
+        contract_runtime::ExecutionArtifacts,
         fetcher::{FetchItem, FetchResponse},
         Component,
     },
@@ -94,13 +97,14 @@ use crate::{
         BlockExecutionResultsOrChunk, BlockExecutionResultsOrChunkId, BlockHash,
         BlockHashAndHeight, BlockHashHeightAndEra, BlockHeader, BlockHeaderWithMetadata,
         BlockSignatures, BlockWithMetadata, Deploy, DeployHash, DeployHeader, DeployId,
-        DeployMetadata, DeployMetadataExt, DeployWithFinalizedApprovals, FinalitySignature,
-        FinalizedApprovals, FinalizedBlock, LegacyDeploy, MaxTtl, NodeId, SyncLeap,
-        SyncLeapIdentifier, ValueOrChunk,
+        DeployMetadata, DeployMetadataExt, DeployWithFinalizedApprovals, EraExecutionStats,
+        FinalitySignature, FinalizedApprovals, FinalizedBlock, LegacyDeploy, MaxTtl, NodeId,
+        SyncLeap, SyncLeapIdentifier, ValueOrChunk,
     },
     utils::{display_error, WithDir},
     NodeRng,
 };
+use block_body_archive::BlockBodyArchive;
 use disjoint_sequences::{DisjointSequences, Sequence};
 pub use error::FatalStorageError;
 use error::GetRequestError;
@@ -129,9 +133,13 @@ const DEFAULT_MAX_DEPLOY_METADATA_STORE_SIZE: usize = 300 * GIB;
 /// Default max state store size.
 const DEFAULT_MAX_STATE_STORE_SIZE: usize = 10 * GIB;
 /// Maximum number of allowed dbs.
-const MAX_DB_COUNT: u32 = 9;
+const MAX_DB_COUNT: u32 = 12;
 /// Key under which completed blocks are to be stored.
 const COMPLETED_BLOCKS_STORAGE_KEY: &[u8] = b"completed_blocks_disjoint_sequences";
+/// Key under which the lowest height of blocks whose bodies weren't archived yet is stored.
+const LOWEST_UNARCHIVED_HEIGHT_STORAGE_KEY: &[u8] = b"lowest_unarchived_block_body_height";
+/// Maximum number of blocks whose bodies are moved into the archive in a single transaction.
+const BLOCK_BODY_ARCHIVE_BATCH_SIZE: usize = 1000;
 /// Name of the file created when initializing a force resync.
 const FORCE_RESYNC_FILE_NAME: &str = "force_resync";
 
@@ -171,6 +179,13 @@ pub struct Storage {
     /// The block body database.
     #[data_size(skip)]
     block_body_db: Database,
+    /// The archive holding the bodies of old blocks, if archiving is enabled.
+    block_body_archive: Option<BlockBodyArchive>,
+    /// The number of eras after which block bodies are moved from the block body database into
+    /// the archive, if archiving is enabled.
+    archive_block_bodies_after_eras: Option<u64>,
+    /// The lowest height of a block whose body may not have been considered for archiving yet.
+    lowest_unarchived_height: u64,
     /// The approvals hashes database.
     #[data_size(skip)]
     approvals_hashes_db: Database,
@@ -192,10 +207,21 @@ pub struct Storage {
     /// The finalized approvals database.
     #[data_size(skip)]
     finalized_approvals_db: Database,
+    /// The era execution statistics database.
+    #[data_size(skip)]
+    era_execution_stats_db: Database,
+    /// The transaction receipts database.
+    #[data_size(skip)]
+    transaction_receipts_db: Database,
+    /// The execution artifacts database.
+    #[data_size(skip)]
+    execution_artifacts_db: Database,
     /// A map of block height to block ID.
     block_height_index: BTreeMap<u64, BlockHash>,
     /// A map of era ID to switch block ID.
     switch_block_era_id_index: BTreeMap<EraId, BlockHash>,
+    /// A map of state root hash to the height of the highest block with that state root.
+    state_root_height_index: BTreeMap<Digest, u64>,
     /// A map of deploy hashes to hashes, heights and era IDs of blocks containing them.
     deploy_hash_index: BTreeMap<DeployHash, BlockHashHeightAndEra>,
     /// Runs of completed blocks known in storage.
@@ -384,6 +410,14 @@ impl Storage {
 
         root = network_subdir;
 
+        // Open the block body archive before reindexing, as it may hold bodies of indexed blocks.
+        // An existing archive is always opened, even if archiving has since been disabled.
+        let block_body_archive = if config.archive_block_bodies_after_eras.is_some() {
+            Some(BlockBodyArchive::open(&root)?)
+        } else {
+            BlockBodyArchive::open_existing(&root)?
+        };
+
         // Calculate the upper bound for the memory map that is potentially used.
         let total_size = config
             .max_block_store_size
@@ -417,11 +451,18 @@ impl Storage {
         let block_body_db = env.create_db(Some("block_body"), DatabaseFlags::empty())?;
         let approvals_hashes_db =
             env.create_db(Some("approvals_hashes"), DatabaseFlags::empty())?;
+        let era_execution_stats_db =
+            env.create_db(Some("era_execution_stats"), DatabaseFlags::empty())?;
+        let transaction_receipts_db =
+            env.create_db(Some("transaction_receipts"), DatabaseFlags::empty())?;
+        let execution_artifacts_db =
+            env.create_db(Some("execution_artifacts"), DatabaseFlags::empty())?;
 
         // We now need to restore the block-height index. Log messages allow timing here.
         info!("indexing block store");
         let mut block_height_index = BTreeMap::new();
         let mut switch_block_era_id_index = BTreeMap::new();
+        let mut state_root_height_index = BTreeMap::new();
         let mut deploy_hash_index = BTreeMap::new();
         let mut block_txn = env.begin_rw_txn()?;
         let mut cursor = block_txn.open_rw_cursor(block_header_db)?;
@@ -436,8 +477,12 @@ impl Storage {
             let (_, raw_val) = row?;
             let mut body_txn = env.begin_ro_txn()?;
             let block_header: BlockHeader = lmdb_ext::deserialize(raw_val)?;
-            let maybe_block_body =
-                get_body_for_block_header(&mut body_txn, block_header.body_hash(), block_body_db);
+            let maybe_block_body = get_body_for_block_header(
+                &mut body_txn,
+                block_header.body_hash(),
+                block_body_db,
+                block_body_archive.as_ref(),
+            );
             if let Some(invalid_era) = hard_reset_to_start_of_era {
                 // Remove blocks that are in to-be-upgraded eras, but have obsolete protocol
                 // versions - they were most likely created before the upgrade and should be
@@ -462,6 +507,7 @@ impl Storage {
             insert_to_block_header_indices(
                 &mut block_height_index,
                 &mut switch_block_era_id_index,
+                &mut state_root_height_index,
                 &block_header,
             )?;
 
@@ -493,6 +539,12 @@ impl Storage {
 
         initialize_block_metadata_db(&env, &block_metadata_db, &deleted_block_hashes_raw)?;
         initialize_deploy_metadata_db(&env, &deploy_metadata_db, &deleted_deploy_hashes)?;
+        initialize_transaction_receipts_db(&env, &transaction_receipts_db, &deleted_deploy_hashes)?;
+        initialize_era_execution_stats_db(
+            &env,
+            &era_execution_stats_db,
+            hard_reset_to_start_of_era,
+        )?;
 
         let metrics = registry.map(Metrics::new).transpose()?;
 
@@ -501,6 +553,9 @@ impl Storage {
             env: Rc::new(env),
             block_header_db,
             block_body_db,
+            block_body_archive,
+            archive_block_bodies_after_eras: config.archive_block_bodies_after_eras,
+            lowest_unarchived_height: 0,
             block_metadata_db,
             approvals_hashes_db,
             deploy_db,
@@ -508,8 +563,12 @@ impl Storage {
             transfer_db,
             state_store_db,
             finalized_approvals_db,
+            era_execution_stats_db,
+            transaction_receipts_db,
+            execution_artifacts_db,
             block_height_index,
             switch_block_era_id_index,
+            state_root_height_index,
             deploy_hash_index,
             completed_blocks: Default::default(),
             activation_era,
@@ -521,6 +580,15 @@ impl Storage {
             metrics,
         };
 
+        if let Some(raw) =
+            component.read_state_store(&Cow::Borrowed(LOWEST_UNARCHIVED_HEIGHT_STORAGE_KEY))?
+        {
+            let (lowest_unarchived_height, _) =
+                u64::from_vec(raw).map_err(FatalStorageError::UnexpectedDeserializationFailure)?;
+            component.lowest_unarchived_height = lowest_unarchived_height;
+        }
+        component.archive_old_block_bodies()?;
+
         if force_resync {
             let force_resync_file_path = component.root_path().join(FORCE_RESYNC_FILE_NAME);
             // Check if resync is already in progress. Force resync will kick
@@ -1137,6 +1205,12 @@ impl Storage {
                     .read_block_header_by_height(block_height, only_from_available_block_range)?;
                 responder.respond(maybe_header).ignore()
             }
+            StorageRequest::GetBlockHeightForStateRootHash {
+                state_root_hash,
+                responder,
+            } => responder
+                .respond(self.state_root_height_index.get(&state_root_hash).copied())
+                .ignore(),
             StorageRequest::PutBlockHeader {
                 block_header,
                 responder,
@@ -1171,19 +1245,49 @@ impl Storage {
             StorageRequest::GetKeyBlockHeightForActivationPoint { responder } => {
                 // If we haven't already cached the height, try to retrieve the key block header.
                 if self.key_block_height_for_activation_point.is_none() {
-                    let mut txn = self.env.begin_ro_txn()?;
-                    let key_block_era = self.activation_era.predecessor().unwrap_or_default();
-                    let key_block_header =
-                        match self.get_switch_block_header_by_era_id(&mut txn, key_block_era)? {
-                            Some(block_header) => block_header,
-                            None => return Ok(responder.respond(None).ignore()),
-                        };
-                    self.key_block_height_for_activation_point = Some(key_block_header.height());
+                    self.key_block_height_for_activation_point =
+                        self.read_key_block_height_for_activation_point()?;
                 }
                 responder
                     .respond(self.key_block_height_for_activation_point)
                     .ignore()
             }
+            StorageRequest::PutBlockExecutionStats {
+                block_execution_stats,
+                responder,
+            } => responder
+                .respond(self.put_block_execution_stats(*block_execution_stats)?)
+                .ignore(),
+            StorageRequest::GetEraExecutionStats { era_id, responder } => responder
+                .respond(self.read_era_execution_stats(era_id)?)
+                .ignore(),
+            StorageRequest::PutTransactionReceipts {
+                transaction_receipts,
+                responder,
+            } => {
+                self.put_transaction_receipts(&transaction_receipts)?;
+                responder.respond(()).ignore()
+            }
+            StorageRequest::GetTransactionReceipt {
+                deploy_hash,
+                responder,
+            } => responder
+                .respond(self.read_transaction_receipt(&deploy_hash)?)
+                .ignore(),
+            StorageRequest::PutExecutionArtifacts {
+                execution_artifacts,
+                max_stored_heights,
+                responder,
+            } => {
+                self.put_execution_artifacts(&execution_artifacts, max_stored_heights)?;
+                responder.respond(()).ignore()
+            }
+            StorageRequest::GetExecutionArtifacts {
+                block_height,
+                responder,
+            } => responder
+                .respond(self.read_execution_artifacts(block_height)?)
+                .ignore(),
         })
     }
 
@@ -1282,6 +1386,120 @@ impl Storage {
         Ok(true)
     }
 
+    /// Adds the statistics of executing a block to the execution statistics of its era.
+    ///
+    /// Returns `false` if the block was already included.
+    fn put_block_execution_stats(
+        &mut self,
+        block_execution_stats: EraExecutionStats,
+    ) -> Result<bool, FatalStorageError> {
+        let key = era_execution_stats_key(block_execution_stats.era_id());
+        let mut txn = self.env.begin_rw_txn()?;
+        let era_execution_stats =
+            match txn.get_value::<_, EraExecutionStats>(self.era_execution_stats_db, &key)? {
+                Some(mut era_execution_stats) => {
+                    if !era_execution_stats.add_block(block_execution_stats) {
+                        return Ok(false);
+                    }
+                    era_execution_stats
+                }
+                None => block_execution_stats,
+            };
+        let _ = txn.put_value(
+            self.era_execution_stats_db,
+            &key,
+            &era_execution_stats,
+            true,
+        )?;
+        txn.commit()?;
+        Ok(true)
+    }
+
+    /// Retrieves the execution statistics of an era.
+    fn read_era_execution_stats(
+        &self,
+        era_id: EraId,
+    ) -> Result<Option<EraExecutionStats>, FatalStorageError> {
+        let mut txn = self.env.begin_ro_txn()?;
+        let maybe_era_execution_stats = txn.get_value(
+            self.era_execution_stats_db,
+            &era_execution_stats_key(era_id),
+        )?;
+        Ok(maybe_era_execution_stats)
+    }
+
+    /// Stores the receipts of executed deploys, replacing any stored for the same deploys.
+    fn put_transaction_receipts(
+        &mut self,
+        transaction_receipts: &[TransactionReceipt],
+    ) -> Result<(), FatalStorageError> {
+        let mut txn = self.env.begin_rw_txn()?;
+        for transaction_receipt in transaction_receipts {
+            let deploy_hash = DeployHash::from(transaction_receipt.deploy_hash);
+            let _ = txn.put_value_bytesrepr(
+                self.transaction_receipts_db,
+                &deploy_hash,
+                transaction_receipt,
+                true,
+            )?;
+        }
+        txn.commit()?;
+        Ok(())
+    }
+
+    /// Retrieves the receipt of an executed deploy.
+    fn read_transaction_receipt(
+        &self,
+        deploy_hash: &DeployHash,
+    ) -> Result<Option<TransactionReceipt>, FatalStorageError> {
+        let mut txn = self.env.begin_ro_txn()?;
+        let maybe_transaction_receipt =
+            txn.get_value_bytesrepr(self.transaction_receipts_db, deploy_hash)?;
+        Ok(maybe_transaction_receipt)
+    }
+
+    /// Stores the artifacts of executing a block, replacing any stored for the same height, and
+    /// removes those of heights more than `max_stored_heights - 1` below it.
+    fn put_execution_artifacts(
+        &mut self,
+        execution_artifacts: &ExecutionArtifacts,
+        max_stored_heights: u64,
+    ) -> Result<(), FatalStorageError> {
+        let block_height = execution_artifacts.block_height();
+        let lowest_kept_key = (block_height + 1)
+            .saturating_sub(max_stored_heights)
+            .to_be_bytes();
+        let mut txn = self.env.begin_rw_txn()?;
+        let _ = txn.put_value(
+            self.execution_artifacts_db,
+            &block_height.to_be_bytes(),
+            execution_artifacts,
+            true,
+        )?;
+        let mut cursor = txn.open_rw_cursor(self.execution_artifacts_db)?;
+        for row in cursor.iter() {
+            let (raw_key, _) = row?;
+            if raw_key >= lowest_kept_key.as_slice() {
+                break;
+            }
+            cursor.del(WriteFlags::empty())?;
+        }
+        drop(cursor);
+        txn.commit()?;
+        Ok(())
+    }
+
+    /// Retrieves the stored artifacts of executing the block at the given height.
+    fn read_execution_artifacts(
+        &self,
+        block_height: u64,
+    ) -> Result<Option<ExecutionArtifacts>, FatalStorageError> {
+        let mut txn = self.env.begin_ro_txn()?;
+        let maybe_execution_artifacts =
+            txn.get_value(self.execution_artifacts_db, &block_height.to_be_bytes())?;
+        Ok(maybe_execution_artifacts)
+    }
+
     /// Retrieves a block by hash.
     pub fn read_block(&self, block_hash: &BlockHash) -> Result<Option<Block>, FatalStorageError> {
         self.get_single_block(&mut self.env.begin_ro_txn()?, block_hash)
@@ -1296,7 +1514,13 @@ impl Storage {
                 return Ok(false);
             }
         };
-        Ok(txn.value_exists(self.block_body_db, block_header.body_hash())?)
+        Ok(
+            txn.value_exists(self.block_body_db, block_header.body_hash())?
+                || self
+                    .block_body_archive
+                    .as_ref()
+                    .map_or(false, |archive| archive.contains(block_header.body_hash())),
+        )
     }
 
     /// Retrieves a approvals hashes by block hash.
@@ -1428,6 +1652,48 @@ impl Storage {
         Ok(Some((finalized_block, deploys)))
     }
 
+    /// Retrieves the height of the key block for the current protocol version's activation point,
+    /// i.e. the height of the final block of the previous protocol version.
+    pub(crate) fn read_key_block_height_for_activation_point(
+        &self,
+    ) -> Result<Option<u64>, FatalStorageError> {
+        let mut txn = self.env.begin_ro_txn()?;
+        let key_block_era = self.activation_era.predecessor().unwrap_or_default();
+        Ok(self
+            .get_switch_block_header_by_era_id(&mut txn, key_block_era)?
+            .map(|key_block_header| key_block_header.height()))
+    }
+
+    /// Retrieves the deploy with the given hash, with its finalized approvals if any.
+    pub(crate) fn read_deploy_with_finalized_approvals(
+        &self,
+        deploy_hash: &DeployHash,
+    ) -> Result<Option<DeployWithFinalizedApprovals>, FatalStorageError> {
+        let mut txn = self.env.begin_ro_txn()?;
+        Ok(self.get_deploy_with_finalized_approvals(&mut txn, deploy_hash)?)
+    }
+
+    /// Retrieves the metadata of the given deploy, holding its execution results, if any.
+    pub(crate) fn read_deploy_metadata(
+        &self,
+        deploy_hash: &DeployHash,
+    ) -> Result<Option<DeployMetadata>, FatalStorageError> {
+        let mut txn = self.env.begin_ro_txn()?;
+        self.get_deploy_metadata(&mut txn, deploy_hash)
+    }
+
+    /// Retrieves the stored execution result of the given deploy in the given block, if any.
+    pub(crate) fn read_execution_result(
+        &self,
+        deploy_hash: &DeployHash,
+        block_hash: &BlockHash,
+    ) -> Result<Option<ExecutionResult>, FatalStorageError> {
+        let mut txn = self.env.begin_ro_txn()?;
+        Ok(self
+            .get_deploy_metadata(&mut txn, deploy_hash)?
+            .and_then(|mut metadata| metadata.execution_results.remove(block_hash)))
+    }
+
     /// Writes a block to storage, updating indices as necessary.
     ///
     /// Returns `Ok(true)` if the block has been successfully written, `Ok(false)` if a part of it
@@ -1440,6 +1706,7 @@ impl Storage {
         let wrote = self.write_validated_block(&mut txn, block)?;
         if wrote {
             txn.commit()?;
+            self.archive_block_bodies_after_write(block)?;
         }
         Ok(wrote)
     }
@@ -1460,6 +1727,7 @@ impl Storage {
             // Update the `completed_blocks` index only if the block was actually stored.
             let _ = self.mark_block_complete(block.height())?;
             txn.commit()?;
+            self.archive_block_bodies_after_write(block)?;
         }
         Ok(wrote)
     }
@@ -1577,6 +1845,7 @@ impl Storage {
             insert_to_block_header_indices(
                 &mut self.block_height_index,
                 &mut self.switch_block_era_id_index,
+                &mut self.state_root_height_index,
                 block.header(),
             )?;
             insert_to_deploy_index(
@@ -2098,6 +2367,7 @@ impl Storage {
             insert_to_block_header_indices(
                 &mut self.block_height_index,
                 &mut self.switch_block_era_id_index,
+                &mut self.state_root_height_index,
                 block_header,
             )?;
         }
@@ -2115,6 +2385,130 @@ impl Storage {
             .map_err(Into::into)
     }
 
+    /// Retrieves a single block body, from either the block body database or the archive.
+    fn get_single_block_body<Tx: Transaction>(
+        &self,
+        txn: &mut Tx,
+        block_body_hash: &Digest,
+    ) -> Result<Option<BlockBody>, FatalStorageError> {
+        get_body_for_block_header(
+            txn,
+            block_body_hash,
+            self.block_body_db,
+            self.block_body_archive.as_ref(),
+        )
+    }
+
+    /// Returns the highest era whose blocks should have their bodies archived, if any.
+    fn highest_archivable_era(&self) -> Option<EraId> {
+        self.block_body_archive.as_ref()?;
+        let archive_after_eras = self.archive_block_bodies_after_eras?;
+        let highest_switch_block_era = self.switch_block_era_id_index.keys().next_back()?;
+        highest_switch_block_era
+            .value()
+            .checked_sub(archive_after_eras)
+            .map(EraId::from)
+    }
+
+    /// Archives the body of the given block if it is old enough and, if it is a switch block, the
+    /// bodies of all blocks which became old enough to be archived by it.
+    fn archive_block_bodies_after_write(&mut self, block: &Block) -> Result<(), FatalStorageError> {
+        if self
+            .highest_archivable_era()
+            .map_or(false, |era_id| block.header().era_id() <= era_id)
+        {
+            self.archive_block_bodies(&[*block.hash()])?;
+        }
+        if block.header().is_switch_block() {
+            self.archive_old_block_bodies()?;
+        }
+        Ok(())
+    }
+
+    /// Moves the bodies of all blocks from archivable eras which weren't considered yet from the
+    /// block body database into the archive.
+    fn archive_old_block_bodies(&mut self) -> Result<(), FatalStorageError> {
+        let highest_archivable_era = match self.highest_archivable_era() {
+            Some(era_id) => era_id,
+            None => return Ok(()),
+        };
+        let highest_archivable_switch_block_hash = match self
+            .switch_block_era_id_index
+            .range(..=highest_archivable_era)
+            .next_back()
+        {
+            Some((_, block_hash)) => *block_hash,
+            None => return Ok(()),
+        };
+        let highest_archivable_height =
+            match self.read_block_header_by_hash(&highest_archivable_switch_block_hash)? {
+                Some(block_header) => block_header.height(),
+                None => return Ok(()),
+            };
+        if highest_archivable_height < self.lowest_unarchived_height {
+            return Ok(());
+        }
+
+        let block_hashes: Vec<BlockHash> = self
+            .block_height_index
+            .range(self.lowest_unarchived_height..=highest_archivable_height)
+            .map(|(_, block_hash)| *block_hash)
+            .collect();
+        for chunk in block_hashes.chunks(BLOCK_BODY_ARCHIVE_BATCH_SIZE) {
+            self.archive_block_bodies(chunk)?;
+        }
+        self.lowest_unarchived_height = highest_archivable_height + 1;
+        self.persist_lowest_unarchived_height()
+    }
+
+    /// Persists the lowest height of blocks whose bodies weren't archived yet to the database, so
+    /// that archiving doesn't rescan the whole chain after a restart.
+    fn persist_lowest_unarchived_height(&self) -> Result<(), FatalStorageError> {
+        let serialized = self
+            .lowest_unarchived_height
+            .to_bytes()
+            .map_err(FatalStorageError::UnexpectedSerializationFailure)?;
+        self.write_state_store(
+            Cow::Borrowed(LOWEST_UNARCHIVED_HEIGHT_STORAGE_KEY),
+            &serialized,
+        )
+    }
+
+    /// Moves the bodies of the given blocks from the block body database into the archive.
+    fn archive_block_bodies(
+        &mut self,
+        block_hashes: &[BlockHash],
+    ) -> Result<(), FatalStorageError> {
+        let env = Rc::clone(&self.env);
+        let mut txn = env.begin_rw_txn()?;
+        let mut block_bodies = BTreeMap::new();
+        for block_hash in block_hashes {
+            let block_header = match self.get_single_block_header(&mut txn, block_hash)? {
+                Some(block_header) => block_header,
+                None => continue,
+            };
+            let maybe_block_body: Option<BlockBody> =
+                txn.get_value(self.block_body_db, block_header.body_hash())?;
+            if let Some(block_body) = maybe_block_body {
+                let _ = block_bodies.insert(*block_header.body_hash(), block_body);
+            }
+        }
+        if block_bodies.is_empty() {
+            return Ok(());
+        }
+
+        // The archive is synced before the bodies are deleted, so they can't get lost on a crash.
+        if let Some(archive) = self.block_body_archive.as_mut() {
+            archive.append(&block_bodies)?;
+        }
+        for block_body_hash in block_bodies.keys() {
+            txn.del(self.block_body_db, block_body_hash, None)?;
+        }
+        txn.commit()?;
+        debug!(count = block_bodies.len(), "archived block bodies");
+        Ok(())
+    }
+
     /// Retrieves a block header by hash.
     fn read_block_header_by_hash(
         &self,
@@ -2142,8 +2536,7 @@ impl Storage {
                 return Ok(None);
             }
         };
-        let maybe_block_body =
-            get_body_for_block_header(txn, block_header.body_hash(), self.block_body_db);
+        let maybe_block_body = self.get_single_block_body(txn, block_header.body_hash());
         let block_body = match maybe_block_body? {
             Some(block_body) => block_body,
             None => {
@@ -2529,8 +2922,7 @@ impl Storage {
             Some(block_header) => block_header,
             None => return Ok(None),
         };
-        let maybe_block_body =
-            get_body_for_block_header(txn, block_header.body_hash(), self.block_body_db);
+        let maybe_block_body = self.get_single_block_body(txn, block_header.body_hash());
         let block_body = match maybe_block_body? {
             Some(block_body) => block_body,
             None => {
@@ -2655,12 +3047,13 @@ where
     bincode::deserialize(raw).map_err(GetRequestError::MalformedIncomingItemId)
 }
 
-/// Inserts the relevant entries to the two indices.
+/// Inserts the relevant entries to the block header indices.
 ///
-/// If a duplicate entry is encountered, neither index is updated and an error is returned.
+/// If a duplicate entry is encountered, no index is updated and an error is returned.
 fn insert_to_block_header_indices(
     block_height_index: &mut BTreeMap<u64, BlockHash>,
     switch_block_era_id_index: &mut BTreeMap<EraId, BlockHash>,
+    state_root_height_index: &mut BTreeMap<Digest, u64>,
     block_header: &BlockHeader,
 ) -> Result<(), FatalStorageError> {
     let block_hash = block_header.block_hash();
@@ -2692,6 +3085,11 @@ fn insert_to_block_header_indices(
     }
 
     let _ = block_height_index.insert(block_header.height(), block_hash);
+    // Blocks without deploys leave the state root unchanged, so several blocks may share one.
+    let height = state_root_height_index
+        .entry(*block_header.state_root_hash())
+        .or_insert(block_header.height());
+    *height = (*height).max(block_header.height());
     Ok(())
 }
 
@@ -2814,6 +3212,11 @@ pub struct Config {
     pub enable_mem_deduplication: bool,
     /// How many loads before memory duplication checks for dead references.
     pub mem_pool_prune_interval: u16,
+    /// The number of eras after which the bodies of blocks are moved out of the block store and
+    /// into a compressed archive file.
+    ///
+    /// If `None`, block bodies are never archived.
+    pub archive_block_bodies_after_eras: Option<u64>,
 }
 
 impl Default for Config {
@@ -2827,6 +3230,7 @@ impl Default for Config {
             max_state_store_size: DEFAULT_MAX_STATE_STORE_SIZE,
             enable_mem_deduplication: true,
             mem_pool_prune_interval: 4096,
+            archive_block_bodies_after_eras: None,
         }
     }
 }
@@ -2994,13 +3398,21 @@ fn initialize_block_body_db(
     Ok(())
 }
 
-/// Retrieves the block body for the given block header.
+/// Retrieves the block body for the given block header, falling back to the block body archive if
+/// it is not in the block body database.
 fn get_body_for_block_header<Tx: Transaction>(
     txn: &mut Tx,
     block_body_hash: &Digest,
     block_body_db: Database,
-) -> Result<Option<BlockBody>, LmdbExtError> {
-    txn.get_value(block_body_db, block_body_hash)
+    block_body_archive: Option<&BlockBodyArchive>,
+) -> Result<Option<BlockBody>, FatalStorageError> {
+    if let Some(block_body) = txn.get_value(block_body_db, block_body_hash)? {
+        return Ok(Some(block_body));
+    }
+    match block_body_archive {
+        Some(archive) => archive.get(block_body_hash),
+        None => Ok(None),
+    }
 }
 
 /// Purges stale entries from the block metadata database.
@@ -3039,6 +3451,62 @@ fn initialize_block_metadata_db(
     Ok(())
 }
 
+/// Returns the key of an era's entry in the era execution statistics database.
+///
+/// The era ID is big-endian encoded so that entries are ordered by era.
+fn era_execution_stats_key(era_id: EraId) -> [u8; 8] {
+    era_id.value().to_be_bytes()
+}
+
+/// Purges the execution statistics of eras whose blocks were removed by a hard reset.
+fn initialize_era_execution_stats_db(
+    env: &Environment,
+    era_execution_stats_db: &Database,
+    hard_reset_to_start_of_era: Option<EraId>,
+) -> Result<(), FatalStorageError> {
+    let invalid_era = match hard_reset_to_start_of_era {
+        Some(invalid_era) => invalid_era,
+        None => return Ok(()),
+    };
+
+    let mut txn = env.begin_rw_txn()?;
+    let mut cursor = txn.open_rw_cursor(*era_execution_stats_db)?;
+    for row in cursor.iter() {
+        let (raw_key, _) = row?;
+        if raw_key >= era_execution_stats_key(invalid_era).as_slice() {
+            cursor.del(WriteFlags::empty())?;
+        }
+    }
+    drop(cursor);
+    txn.commit()?;
+
+    info!(%invalid_era, "era execution stats database initialized");
+    Ok(())
+}
+
+/// Purges the receipts of deploys whose blocks were removed by a hard reset.
+fn initialize_transaction_receipts_db(
+    env: &Environment,
+    transaction_receipts_db: &Database,
+    deleted_deploy_hashes: &HashSet<DeployHash>,
+) -> Result<(), LmdbExtError> {
+    if !deleted_deploy_hashes.is_empty() {
+        let mut txn = env.begin_rw_txn()?;
+        for deleted_deploy_hash in deleted_deploy_hashes {
+            if txn
+                .del(*transaction_receipts_db, deleted_deploy_hash, None)
+                .is_err()
+            {
+                debug!(%deleted_deploy_hash, "no transaction receipt to purge");
+            }
+        }
+        txn.commit()?;
+    }
+
+    info!("transaction receipts database initialized");
+    Ok(())
+}
+
 /// Purges stale entries from the deploy metadata database.
 fn initialize_deploy_metadata_db(
     env: &Environment,
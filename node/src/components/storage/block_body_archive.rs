@@ -0,0 +1,203 @@
+//! An append-only archive of compressed block bodies.
+//!
+//! Block bodies of blocks from old eras are rarely read, but would otherwise stay in LMDB forever.
+//! The archive allows storage to move such bodies out of LMDB into a single file, in which each
+//! record consists of the block body hash, the length of the compressed body and the
+//! DEFLATE-compressed, bincode-serialized body itself.
+//!
+//! The index mapping body hashes to record locations is kept in memory and rebuilt on startup by
+//! walking the record headers, seeking past the payloads.  A record which was only partially
+//! written, e.g. due to a crash, is truncated from the end of the file when the archive is opened.
+
+use std::{
+    collections::BTreeMap,
+    convert::TryFrom,
+    fs::{File, OpenOptions},
+    io::{self, BufReader, ErrorKind, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+use datasize::DataSize;
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
+use tracing::warn;
+
+use casper_hashing::Digest;
+
+use super::{lmdb_ext, FatalStorageError};
+use crate::types::BlockBody;
+
+/// Filename of the block body archive created by the storage component.
+const BLOCK_BODY_ARCHIVE_FILENAME: &str = "block_body.archive";
+
+/// The length of a record header: the block body hash followed by the payload length as a
+/// little-endian `u32`.
+const RECORD_HEADER_LENGTH: usize = Digest::LENGTH + 4;
+
+/// The location of a compressed block body within the archive file.
+#[derive(Clone, Copy, DataSize, Debug, PartialEq, Eq)]
+struct RecordLocation {
+    /// Offset of the compressed payload from the start of the file.
+    offset: u64,
+    /// Length of the compressed payload.
+    length: u32,
+}
+
+/// An append-only, seekable archive of compressed block bodies.
+#[derive(DataSize, Debug)]
+pub(super) struct BlockBodyArchive {
+    /// Path of the archive file.
+    path: PathBuf,
+    /// Index of archived block bodies by their hash.
+    index: BTreeMap<Digest, RecordLocation>,
+    /// The length of the archive file, i.e. the offset at which the next record will be written.
+    file_length: u64,
+}
+
+impl BlockBodyArchive {
+    /// Opens the archive in the given directory if it exists, rebuilding its index.
+    pub(super) fn open_existing(root: &Path) -> Result<Option<Self>, FatalStorageError> {
+        if root.join(BLOCK_BODY_ARCHIVE_FILENAME).exists() {
+            Self::open(root).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Opens the archive in the given directory, creating it if it doesn't exist, and rebuilds its
+    /// index.
+    pub(super) fn open(root: &Path) -> Result<Self, FatalStorageError> {
+        let path = root.join(BLOCK_BODY_ARCHIVE_FILENAME);
+        let to_fatal = |error| FatalStorageError::BlockBodyArchive(path.clone(), error);
+
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .open(&path)
+            .map_err(to_fatal)?;
+        let actual_length = file.metadata().map_err(to_fatal)?.len();
+
+        let mut reader = BufReader::new(&file);
+        let mut index = BTreeMap::new();
+        let mut file_length = 0;
+        let mut header = [0; RECORD_HEADER_LENGTH];
+        while file_length + RECORD_HEADER_LENGTH as u64 <= actual_length {
+            reader.read_exact(&mut header).map_err(to_fatal)?;
+            let (hash_bytes, length_bytes) = header.split_at(Digest::LENGTH);
+            let block_body_hash = Digest::try_from(hash_bytes)
+                .map_err(|error| to_fatal(io::Error::new(ErrorKind::InvalidData, error)))?;
+            let mut length = [0; 4];
+            length.copy_from_slice(length_bytes);
+            let length = u32::from_le_bytes(length);
+
+            let offset = file_length + RECORD_HEADER_LENGTH as u64;
+            if offset + u64::from(length) > actual_length {
+                break;
+            }
+            reader
+                .seek(SeekFrom::Current(i64::from(length)))
+                .map_err(to_fatal)?;
+            let _ = index.insert(block_body_hash, RecordLocation { offset, length });
+            file_length = offset + u64::from(length);
+        }
+
+        if file_length < actual_length {
+            warn!(
+                path = %path.display(),
+                "truncating incomplete record from the end of the block body archive"
+            );
+            file.set_len(file_length).map_err(to_fatal)?;
+        }
+
+        Ok(BlockBodyArchive {
+            path,
+            index,
+            file_length,
+        })
+    }
+
+    /// Returns `true` if the block body with the given hash is archived.
+    pub(super) fn contains(&self, block_body_hash: &Digest) -> bool {
+        self.index.contains_key(block_body_hash)
+    }
+
+    /// Reads the block body with the given hash from the archive.
+    pub(super) fn get(
+        &self,
+        block_body_hash: &Digest,
+    ) -> Result<Option<BlockBody>, FatalStorageError> {
+        let location = match self.index.get(block_body_hash) {
+            Some(location) => *location,
+            None => return Ok(None),
+        };
+        let to_fatal = |error| FatalStorageError::BlockBodyArchive(self.path.clone(), error);
+
+        let mut file = File::open(&self.path).map_err(to_fatal)?;
+        file.seek(SeekFrom::Start(location.offset))
+            .map_err(to_fatal)?;
+        let mut serialized = vec![];
+        DeflateDecoder::new(file.take(u64::from(location.length)))
+            .read_to_end(&mut serialized)
+            .map_err(to_fatal)?;
+        Ok(Some(lmdb_ext::deserialize(&serialized)?))
+    }
+
+    /// Appends the given block bodies to the archive, skipping any which are already archived.
+    ///
+    /// The archive file is synced to disk before returning, so the bodies can safely be removed
+    /// from LMDB afterwards.
+    pub(super) fn append<'a, I>(&mut self, block_bodies: I) -> Result<(), FatalStorageError>
+    where
+        I: IntoIterator<Item = (&'a Digest, &'a BlockBody)>,
+    {
+        let mut records = vec![];
+        let mut new_locations = BTreeMap::new();
+        let mut next_offset = self.file_length;
+        for (block_body_hash, block_body) in block_bodies {
+            if self.contains(block_body_hash) || new_locations.contains_key(block_body_hash) {
+                continue;
+            }
+            let compressed = self.compress(block_body)?;
+            let length = u32::try_from(compressed.len()).map_err(|_| {
+                FatalStorageError::BlockBodyArchive(
+                    self.path.clone(),
+                    io::Error::new(ErrorKind::InvalidInput, "compressed block body too large"),
+                )
+            })?;
+            records.extend_from_slice(block_body_hash.as_ref());
+            records.extend_from_slice(&length.to_le_bytes());
+            records.extend_from_slice(&compressed);
+            let offset = next_offset + RECORD_HEADER_LENGTH as u64;
+            let _ = new_locations.insert(*block_body_hash, RecordLocation { offset, length });
+            next_offset = offset + u64::from(length);
+        }
+
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        let to_fatal = |error| FatalStorageError::BlockBodyArchive(self.path.clone(), error);
+        let mut file = OpenOptions::new()
+            .write(true)
+            .open(&self.path)
+            .map_err(to_fatal)?;
+        file.seek(SeekFrom::Start(self.file_length))
+            .map_err(to_fatal)?;
+        file.write_all(&records).map_err(to_fatal)?;
+        file.sync_all().map_err(to_fatal)?;
+
+        self.index.extend(new_locations);
+        self.file_length = next_offset;
+        Ok(())
+    }
+
+    /// Serializes and compresses the given block body.
+    fn compress(&self, block_body: &BlockBody) -> Result<Vec<u8>, FatalStorageError> {
+        let serialized = lmdb_ext::serialize(block_body)?;
+        let mut encoder = DeflateEncoder::new(vec![], Compression::default());
+        encoder
+            .write_all(&serialized)
+            .and_then(|_| encoder.finish())
+            .map_err(|error| FatalStorageError::BlockBodyArchive(self.path.clone(), error))
+    }
+}
@@ -21,6 +21,9 @@ pub enum FatalStorageError {
     /// Failure to create the root database directory.
     #[error("failed to create database directory `{}`: {}", .0.display(), .1)]
     CreateDatabaseDirectory(PathBuf, io::Error),
+    /// Filesystem error while operating on the block body archive.
+    #[error("block body archive error at `{}`: {}", .0.display(), .1)]
+    BlockBodyArchive(PathBuf, io::Error),
     /// Found a duplicate block-at-height index entry.
     #[error("duplicate entries for block at height {height}: {first} / {second}")]
     DuplicateBlockIndex {
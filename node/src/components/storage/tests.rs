@@ -16,17 +16,20 @@ use smallvec::smallvec;
 use casper_types::{
     generate_ed25519_keypair, system::auction::UnbondingPurse, testing::TestRng, AccessRights,
     EraId, ExecutionEffect, ExecutionResult, Key, ProtocolVersion, PublicKey, SecretKey, TimeDiff,
-    Transfer, Transform, TransformEntry, URef, U512,
+    TransactionReceipt, Transfer, Transform, TransformEntry, URef, U512,
 };
 
 use super::{
-    initialize_block_metadata_db,
+    initialize_block_metadata_db, initialize_transaction_receipts_db,
     lmdb_ext::{deserialize_internal, serialize_internal, TransactionExt, WriteTransactionExt},
     move_storage_files_to_network_subdir, should_move_storage_files_to_network_subdir, Config,
     Storage, FORCE_RESYNC_FILE_NAME,
 };
 use crate::{
-    components::fetcher::{FetchItem, FetchResponse},
+    components::{
+        contract_runtime::ExecutionArtifacts,
+        fetcher::{FetchItem, FetchResponse},
+    },
     effect::{
         requests::{MarkBlockCompletedRequest, StorageRequest},
         Multiple,
@@ -36,8 +39,8 @@ use crate::{
         sync_leap_validation_metadata::SyncLeapValidationMetaData, AvailableBlockRange, Block,
         BlockHash, BlockHashAndHeight, BlockHashHeightAndEra, BlockHeader, BlockHeaderWithMetadata,
         BlockSignatures, Chainspec, ChainspecRawBytes, Deploy, DeployHash, DeployMetadata,
-        DeployMetadataExt, DeployWithFinalizedApprovals, FinalitySignature, LegacyDeploy,
-        SyncLeapIdentifier, TestBlockBuilder,
+        DeployMetadataExt, DeployWithFinalizedApprovals, EraExecutionStats, FinalitySignature,
+        LegacyDeploy, SyncLeapIdentifier, TestBlockBuilder,
     },
     utils::{Loadable, WithDir},
 };
@@ -57,6 +60,7 @@ fn new_config(harness: &ComponentHarness<UnitTestEvent>) -> Config {
         max_state_store_size: 50 * MIB,
         enable_mem_deduplication: true,
         mem_pool_prune_interval: 4,
+        archive_block_bodies_after_eras: None,
     }
 }
 
@@ -1422,6 +1426,91 @@ fn persist_blocks_deploys_and_deploy_metadata_across_instantiations() {
     );
 }
 
+#[test]
+fn should_archive_old_block_bodies() {
+    let blocks_count = 8_usize;
+    let blocks_per_era = 3;
+    let mut harness = ComponentHarness::default();
+    let mut cfg = new_config(&harness);
+    cfg.archive_block_bodies_after_eras = Some(1);
+    let cfg = WithDir::new(harness.tmp.path(), cfg);
+    let new_storage = |cfg: &WithDir<Config>| {
+        Storage::new(
+            cfg,
+            None,
+            ProtocolVersion::V1_0_0,
+            EraId::default(),
+            "test",
+            MAX_TTL.into(),
+            RECENT_ERA_COUNT,
+            None,
+            false,
+        )
+        .expect("could not create storage component fixture")
+    };
+    let mut storage = new_storage(&cfg);
+
+    // Create and store 8 blocks, 0-2 in era 0, 3-5 in era 1, and 6,7 in era 2.
+    let blocks: Vec<Block> = (0..blocks_count)
+        .map(|height| {
+            let is_switch = height % blocks_per_era == blocks_per_era - 1;
+            let deploy = Deploy::random(&mut harness.rng);
+            Block::random_with_specifics(
+                &mut harness.rng,
+                EraId::from(height as u64 / 3),
+                height as u64,
+                ProtocolVersion::V1_0_0,
+                is_switch,
+                iter::once(&deploy),
+            )
+        })
+        .collect();
+    for block in &blocks {
+        assert!(put_complete_block(
+            &mut harness,
+            &mut storage,
+            Arc::new(block.clone())
+        ));
+    }
+
+    // The highest switch block is in era 1, so only the bodies of blocks in era 0 are archived.
+    let is_in_block_body_db = |storage: &Storage, block: &Block| {
+        let mut txn = storage.env.begin_ro_txn().unwrap();
+        txn.value_exists(storage.block_body_db, block.header().body_hash())
+            .unwrap()
+    };
+    for block in &blocks {
+        assert_eq!(
+            is_in_block_body_db(&storage, block),
+            block.header().era_id() > EraId::from(0)
+        );
+        assert_eq!(
+            get_block(&mut harness, &mut storage, *block.hash()).as_ref(),
+            Some(block)
+        );
+    }
+
+    assert_eq!(storage.lowest_unarchived_height, 3);
+
+    // Archiving resumes from the persisted height after a restart.
+    drop(storage);
+    let storage = new_storage(&cfg);
+    assert_eq!(storage.lowest_unarchived_height, 3);
+
+    // Archived bodies are still served after a restart, even with archiving disabled.
+    drop(storage);
+    let mut cfg = cfg.value().clone();
+    cfg.archive_block_bodies_after_eras = None;
+    let mut storage = new_storage(&WithDir::new(harness.tmp.path(), cfg));
+    for block in &blocks {
+        assert_eq!(
+            get_block(&mut harness, &mut storage, *block.hash()).as_ref(),
+            Some(block)
+        );
+        assert!(storage.block_exists(block.hash()).unwrap());
+    }
+}
+
 #[test]
 fn should_hard_reset() {
     let blocks_count = 8_usize;
@@ -2235,3 +2324,303 @@ fn should_initialize_block_metadata_db() {
     assert_signatures(&storage, *block_3.hash(), vec![]);
     assert_signatures(&storage, *block_4.hash(), vec![]);
 }
+
+fn put_block_execution_stats(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    block_execution_stats: EraExecutionStats,
+) -> bool {
+    harness.send_request(storage, move |responder| {
+        StorageRequest::PutBlockExecutionStats {
+            block_execution_stats: Box::new(block_execution_stats),
+            responder,
+        }
+        .into()
+    })
+}
+
+fn get_era_execution_stats(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    era_id: EraId,
+) -> Option<EraExecutionStats> {
+    harness.send_request(storage, move |responder| {
+        StorageRequest::GetEraExecutionStats { era_id, responder }.into()
+    })
+}
+
+/// Creates the statistics of executing a random block, with the given number of deploys of which
+/// the first fails, each costing 100 gas.
+fn random_block_execution_stats(
+    rng: &mut TestRng,
+    era_id: u64,
+    height: u64,
+    is_switch: bool,
+    deploy_count: usize,
+) -> EraExecutionStats {
+    let deploys: Vec<_> = iter::repeat_with(|| Deploy::random(rng))
+        .take(deploy_count)
+        .collect();
+    let block = Block::random_with_specifics(
+        rng,
+        EraId::new(era_id),
+        height,
+        ProtocolVersion::V1_0_0,
+        is_switch,
+        deploys.iter(),
+    );
+    let execution_results: Vec<_> = deploys
+        .iter()
+        .enumerate()
+        .map(|(index, deploy)| {
+            let execution_result = if index == 0 {
+                ExecutionResult::Failure {
+                    effect: Default::default(),
+                    transfers: vec![],
+                    cost: U512::from(100),
+                    error_message: "failed".to_string(),
+                    error_code: None,
+                }
+            } else {
+                ExecutionResult::Success {
+                    effect: Default::default(),
+                    transfers: vec![],
+                    cost: U512::from(100),
+                }
+            };
+            (*deploy.hash(), deploy.header().clone(), execution_result)
+        })
+        .collect();
+    EraExecutionStats::from_executed_block(
+        &block,
+        &execution_results,
+        TimeDiff::from_millis(height * 10),
+    )
+}
+
+#[test]
+fn should_aggregate_era_execution_stats() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+    let era_id = EraId::new(3);
+
+    assert!(get_era_execution_stats(&mut harness, &mut storage, era_id).is_none());
+
+    let first_block_stats = random_block_execution_stats(&mut harness.rng, 3, 10, false, 3);
+    assert!(put_block_execution_stats(
+        &mut harness,
+        &mut storage,
+        first_block_stats.clone()
+    ));
+    assert_eq!(
+        get_era_execution_stats(&mut harness, &mut storage, era_id),
+        Some(first_block_stats.clone())
+    );
+
+    // A block already included is not counted twice.
+    assert!(!put_block_execution_stats(
+        &mut harness,
+        &mut storage,
+        first_block_stats
+    ));
+
+    let switch_block_stats = random_block_execution_stats(&mut harness.rng, 3, 11, true, 2);
+    assert!(put_block_execution_stats(
+        &mut harness,
+        &mut storage,
+        switch_block_stats
+    ));
+
+    let era_execution_stats = get_era_execution_stats(&mut harness, &mut storage, era_id)
+        .expect("should have era execution stats");
+    assert_eq!(era_execution_stats.era_id(), era_id);
+    assert_eq!(era_execution_stats.highest_block_height(), 11);
+    assert!(era_execution_stats.is_complete());
+    assert_eq!(era_execution_stats.block_count(), 2);
+    assert_eq!(
+        era_execution_stats.deploy_count() + era_execution_stats.transfer_count(),
+        5
+    );
+    assert_eq!(era_execution_stats.failed_count(), 2);
+    assert_eq!(era_execution_stats.total_gas(), U512::from(500));
+    assert_eq!(
+        era_execution_stats.total_execution_time(),
+        TimeDiff::from_millis(210)
+    );
+    assert_eq!(
+        era_execution_stats.average_block_execution_time(),
+        TimeDiff::from_millis(105)
+    );
+
+    assert!(get_era_execution_stats(&mut harness, &mut storage, era_id.successor()).is_none());
+}
+
+#[test]
+fn should_purge_era_execution_stats_on_hard_reset() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    for era_id in 0..3 {
+        let block_execution_stats =
+            random_block_execution_stats(&mut harness.rng, era_id, era_id * 3, false, 1);
+        assert!(put_block_execution_stats(
+            &mut harness,
+            &mut storage,
+            block_execution_stats
+        ));
+    }
+    drop(storage);
+
+    let mut storage = storage_fixture_with_hard_reset(&harness, EraId::new(1));
+    assert!(get_era_execution_stats(&mut harness, &mut storage, EraId::new(0)).is_some());
+    assert!(get_era_execution_stats(&mut harness, &mut storage, EraId::new(1)).is_none());
+    assert!(get_era_execution_stats(&mut harness, &mut storage, EraId::new(2)).is_none());
+}
+
+fn put_transaction_receipts(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    transaction_receipts: Vec<TransactionReceipt>,
+) {
+    harness.send_request(storage, move |responder| {
+        StorageRequest::PutTransactionReceipts {
+            transaction_receipts,
+            responder,
+        }
+        .into()
+    })
+}
+
+fn get_transaction_receipt(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    deploy_hash: DeployHash,
+) -> Option<TransactionReceipt> {
+    harness.send_request(storage, move |responder| {
+        StorageRequest::GetTransactionReceipt {
+            deploy_hash,
+            responder,
+        }
+        .into()
+    })
+}
+
+#[test]
+fn should_store_and_purge_transaction_receipts() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let transaction_receipts: Vec<TransactionReceipt> =
+        iter::repeat_with(|| harness.rng.gen()).take(3).collect();
+    let deploy_hashes: Vec<DeployHash> = transaction_receipts
+        .iter()
+        .map(|transaction_receipt| transaction_receipt.deploy_hash.into())
+        .collect();
+    assert!(get_transaction_receipt(&mut harness, &mut storage, deploy_hashes[0]).is_none());
+
+    put_transaction_receipts(&mut harness, &mut storage, transaction_receipts.clone());
+    for (deploy_hash, transaction_receipt) in deploy_hashes.iter().zip(&transaction_receipts) {
+        assert_eq!(
+            get_transaction_receipt(&mut harness, &mut storage, *deploy_hash).as_ref(),
+            Some(transaction_receipt)
+        );
+    }
+
+    // Storing a receipt for the same deploy replaces the previous one.
+    let mut replacement: TransactionReceipt = harness.rng.gen();
+    replacement.deploy_hash = transaction_receipts[0].deploy_hash;
+    put_transaction_receipts(&mut harness, &mut storage, vec![replacement.clone()]);
+    assert_eq!(
+        get_transaction_receipt(&mut harness, &mut storage, deploy_hashes[0]),
+        Some(replacement)
+    );
+
+    let to_be_purged = HashSet::from_iter([deploy_hashes[1]]);
+    initialize_transaction_receipts_db(
+        &storage.env,
+        &storage.transaction_receipts_db,
+        &to_be_purged,
+    )
+    .unwrap();
+    assert!(get_transaction_receipt(&mut harness, &mut storage, deploy_hashes[0]).is_some());
+    assert!(get_transaction_receipt(&mut harness, &mut storage, deploy_hashes[1]).is_none());
+    assert!(get_transaction_receipt(&mut harness, &mut storage, deploy_hashes[2]).is_some());
+}
+
+fn put_execution_artifacts(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    execution_artifacts: ExecutionArtifacts,
+    max_stored_heights: u64,
+) {
+    harness.send_request(storage, move |responder| {
+        StorageRequest::PutExecutionArtifacts {
+            execution_artifacts: Box::new(execution_artifacts),
+            max_stored_heights,
+            responder,
+        }
+        .into()
+    })
+}
+
+fn get_execution_artifacts(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    block_height: u64,
+) -> Option<ExecutionArtifacts> {
+    harness.send_request(storage, move |responder| {
+        StorageRequest::GetExecutionArtifacts {
+            block_height,
+            responder,
+        }
+        .into()
+    })
+}
+
+#[test]
+fn should_store_execution_artifacts_of_recent_heights() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+    let max_stored_heights = 2;
+
+    let mut block_hashes = vec![];
+    for height in 0..4 {
+        let (_, execution_artifacts) = ExecutionArtifacts::random(&mut harness.rng, height);
+        block_hashes.push(
+            *execution_artifacts
+                .block_and_execution_results()
+                .block
+                .hash(),
+        );
+        put_execution_artifacts(
+            &mut harness,
+            &mut storage,
+            execution_artifacts,
+            max_stored_heights,
+        );
+    }
+
+    assert!(get_execution_artifacts(&mut harness, &mut storage, 0).is_none());
+    assert!(get_execution_artifacts(&mut harness, &mut storage, 1).is_none());
+    for height in 2..4 {
+        let stored = get_execution_artifacts(&mut harness, &mut storage, height)
+            .expect("should have execution artifacts");
+        assert_eq!(stored.block_height(), height);
+        assert_eq!(
+            stored.block_and_execution_results().block.hash(),
+            &block_hashes[height as usize]
+        );
+    }
+
+    // Storing artifacts for the same height replaces the previous ones.
+    let (_, replacement) = ExecutionArtifacts::random(&mut harness.rng, 3);
+    let replacement_hash = *replacement.block_and_execution_results().block.hash();
+    put_execution_artifacts(&mut harness, &mut storage, replacement, max_stored_heights);
+    let stored = get_execution_artifacts(&mut harness, &mut storage, 3)
+        .expect("should have execution artifacts");
+    assert_eq!(
+        stored.block_and_execution_results().block.hash(),
+        &replacement_hash
+    );
+    assert!(get_execution_artifacts(&mut harness, &mut storage, 2).is_some());
+}
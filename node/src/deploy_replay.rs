@@ -0,0 +1,193 @@
+//! Offline record-and-replay of the execution of a single deploy.
+//!
+//! Recording executes a stored deploy on top of a given state root, capturing every global state
+//! value read during execution.  Together with the deploy, its block's timestamp and proposer and
+//! the execution result, these form a self-contained [`DeployFixture`], from which the deploy can
+//! later be re-executed without access to the node's databases, e.g. to reproduce a bug report.
+
+use prometheus::Registry;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use casper_execution_engine::{
+    core::engine_state::EngineState,
+    shared::newtypes::CorrelationId,
+    storage::{error::Error as GlobalStateError, global_state::in_memory::InMemoryGlobalState},
+};
+use casper_hashing::Digest;
+use casper_types::{ExecutionResult, Key, ProtocolVersion, PublicKey, StoredValue, Timestamp};
+
+use crate::{
+    components::{
+        contract_runtime::{self, BlockExecutionError, ConfigError, ContractRuntime},
+        storage::{FatalStorageError, Storage},
+    },
+    reactor::main_reactor::Config,
+    types::{BlockHash, Chainspec, Deploy, DeployHash},
+    utils::WithDir,
+};
+
+/// Error returned when a deploy could not be recorded or replayed.
+#[derive(Debug, Error)]
+pub(crate) enum Error {
+    /// The deploy is not in storage.
+    #[error("deploy {0} not found in storage")]
+    MissingDeploy(DeployHash),
+    /// The deploy has not been executed in any stored block.
+    #[error("deploy {0} has not been executed in any stored block")]
+    NotExecuted(DeployHash),
+    /// The block in which the deploy was executed is not in storage.
+    #[error("block {0} not found in storage")]
+    MissingBlock(BlockHash),
+    /// The fixture was recorded under a different protocol version than the one the node is
+    /// configured to run.
+    #[error(
+        "fixture was recorded under protocol version {fixture_protocol_version}, but the node is \
+         running {protocol_version}"
+    )]
+    UnsupportedProtocolVersion {
+        /// The protocol version of the fixture.
+        fixture_protocol_version: ProtocolVersion,
+        /// The protocol version of the node.
+        protocol_version: ProtocolVersion,
+    },
+    /// Error accessing storage.
+    #[error(transparent)]
+    Storage(#[from] FatalStorageError),
+    /// Error initializing the contract runtime.
+    #[error(transparent)]
+    ContractRuntime(#[from] ConfigError),
+    /// Error building global state from the recorded values.
+    #[error(transparent)]
+    GlobalState(#[from] GlobalStateError),
+    /// Error executing the deploy.
+    #[error(transparent)]
+    Execution(#[from] BlockExecutionError),
+}
+
+/// A self-contained record of the execution of a single deploy.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct DeployFixture {
+    /// The protocol version under which the deploy was executed.
+    protocol_version: ProtocolVersion,
+    /// The state root hash the deploy was executed on top of.
+    state_root_hash: Digest,
+    /// The timestamp of the block in which the deploy was executed.
+    block_time: Timestamp,
+    /// The proposer of the block in which the deploy was executed.
+    proposer: PublicKey,
+    /// The deploy, with its finalized approvals.
+    deploy: Deploy,
+    /// The global state values read while executing the deploy.
+    reads: Vec<(Key, StoredValue)>,
+    /// The result of executing the deploy.
+    execution_result: ExecutionResult,
+}
+
+impl DeployFixture {
+    /// Returns the recorded result of executing the deploy.
+    pub(crate) fn execution_result(&self) -> &ExecutionResult {
+        &self.execution_result
+    }
+}
+
+/// Executes the stored deploy with the given hash on top of `state_root_hash`, recording every
+/// global state value read.
+///
+/// The deploy is executed with the timestamp and proposer of the block in which it was executed.
+pub(crate) fn record_deploy(
+    config: WithDir<Config>,
+    chainspec: &Chainspec,
+    state_root_hash: Digest,
+    deploy_hash: DeployHash,
+) -> Result<DeployFixture, Error> {
+    let (root_dir, config) = config.into_parts();
+    let protocol_version = chainspec.protocol_version();
+    let registry = Registry::new();
+    let storage = Storage::new(
+        &WithDir::new(root_dir, config.storage.clone()),
+        None,
+        protocol_version,
+        chainspec.protocol_config.activation_point.era_id(),
+        &chainspec.network_config.name,
+        chainspec.deploy_config.max_ttl.into(),
+        chainspec.core_config.recent_era_count(),
+        None,
+        false,
+    )?;
+    let contract_runtime =
+        ContractRuntime::from_chainspec(&storage, &config.contract_runtime, chainspec, &registry)?;
+
+    let deploy = storage
+        .read_deploy_with_finalized_approvals(&deploy_hash)?
+        .ok_or(Error::MissingDeploy(deploy_hash))?
+        .into_naive();
+    let block_hash = storage
+        .read_deploy_metadata(&deploy_hash)?
+        .and_then(|metadata| metadata.execution_results.into_keys().next())
+        .ok_or(Error::NotExecuted(deploy_hash))?;
+    let block = storage
+        .read_block(&block_hash)?
+        .ok_or(Error::MissingBlock(block_hash))?;
+    let block_time = block.header().timestamp();
+    let proposer = block.body().proposer().clone();
+
+    let engine_state = EngineState::new(
+        contract_runtime
+            .engine_state()
+            .get_state()
+            .create_recording(),
+        contract_runtime::engine_config_from_chainspec(chainspec, &config.contract_runtime),
+    );
+    let execution_result = contract_runtime::execute_deploy(
+        &engine_state,
+        protocol_version,
+        state_root_hash,
+        block_time,
+        proposer.clone(),
+        deploy.clone(),
+    )?;
+    let reads = engine_state.into_inner().take_reads().into_iter().collect();
+
+    Ok(DeployFixture {
+        protocol_version,
+        state_root_hash,
+        block_time,
+        proposer,
+        deploy,
+        reads,
+        execution_result,
+    })
+}
+
+/// Re-executes the deploy recorded in `fixture` on top of global state holding only the recorded
+/// values, returning the execution result.
+pub(crate) fn replay_deploy(
+    config: &Config,
+    chainspec: &Chainspec,
+    fixture: &DeployFixture,
+) -> Result<ExecutionResult, Error> {
+    let protocol_version = chainspec.protocol_version();
+    if fixture.protocol_version != protocol_version {
+        return Err(Error::UnsupportedProtocolVersion {
+            fixture_protocol_version: fixture.protocol_version,
+            protocol_version,
+        });
+    }
+
+    let (global_state, state_root_hash) =
+        InMemoryGlobalState::from_pairs(CorrelationId::new(), &fixture.reads)?;
+    let engine_state = EngineState::new(
+        global_state,
+        contract_runtime::engine_config_from_chainspec(chainspec, &config.contract_runtime),
+    );
+    let execution_result = contract_runtime::execute_deploy(
+        &engine_state,
+        protocol_version,
+        state_root_hash,
+        fixture.block_time,
+        fixture.proposer.clone(),
+        fixture.deploy.clone(),
+    )?;
+    Ok(execution_result)
+}
@@ -116,17 +116,17 @@ use tracing::{debug, error, warn};
 
 use casper_execution_engine::{
     core::engine_state::{
-        self, era_validators::GetEraValidatorsError, BalanceRequest, BalanceResult, GetBidsRequest,
-        GetBidsResult, QueryRequest, QueryResult,
+        self, era_validators::GetEraValidatorsError, BalanceRequest, BalanceResult, EngineConfig,
+        GetBidsRequest, GetBidsResult, GetUnbondsRequest, GetUnbondsResult, QueryRequest,
+        QueryResult,
     },
-    shared::execution_journal::ExecutionJournal,
     storage::trie::TrieRaw,
 };
 use casper_hashing::Digest;
 use casper_types::{
     account::Account, bytesrepr::Bytes, system::auction::EraValidators, Contract, ContractPackage,
-    EraId, ExecutionEffect, ExecutionResult, Key, PublicKey, TimeDiff, Timestamp, Transfer, URef,
-    U512,
+    EraId, ExecutionEffect, ExecutionResult, Key, PublicKey, TimeDiff, Timestamp,
+    TransactionReceipt, Transfer, URef, U512,
 };
 
 use crate::{
@@ -136,7 +136,10 @@ use crate::{
             TrieAccumulatorError, TrieAccumulatorResponse,
         },
         consensus::{ClContext, EraDump, ProposedBlock, ValidatorChange},
-        contract_runtime::{ContractRuntimeError, EraValidatorsRequest},
+        contract_runtime::{
+            ContractRuntimeError, EraValidatorsRequest, ExecutionArtifacts, QueryOrigin,
+            StateQueryError, StateRootAgeCheck,
+        },
         deploy_acceptor,
         diagnostics_port::StopAtSpec,
         fetcher::{FetchItem, FetchResult},
@@ -150,9 +153,9 @@ use crate::{
         appendable_block::AppendableBlock, ApprovalsHashes, AvailableBlockRange, Block,
         BlockExecutionResultsOrChunk, BlockExecutionResultsOrChunkId, BlockHash, BlockHeader,
         BlockSignatures, BlockWithMetadata, ChainspecRawBytes, Deploy, DeployHash, DeployHeader,
-        DeployId, DeployMetadataExt, DeployWithFinalizedApprovals, FinalitySignature,
-        FinalitySignatureId, FinalizedApprovals, FinalizedBlock, LegacyDeploy, MetaBlock,
-        MetaBlockState, NodeId, TrieOrChunk, TrieOrChunkId,
+        DeployId, DeployMetadataExt, DeployWithFinalizedApprovals, EraExecutionStats,
+        FinalitySignature, FinalitySignatureId, FinalizedApprovals, FinalizedBlock, LegacyDeploy,
+        MetaBlock, MetaBlockState, NodeId, TrieOrChunk, TrieOrChunkId,
     },
     utils::{fmt_limit::FmtLimit, SharedFlag, Source},
 };
@@ -1010,7 +1013,7 @@ impl<REv> EffectBuilder<REv> {
     pub(crate) async fn announce_commit_step_success(
         self,
         era_id: EraId,
-        execution_journal: ExecutionJournal,
+        execution_effect: ExecutionEffect,
     ) where
         REv: From<ContractRuntimeAnnouncement>,
     {
@@ -1018,7 +1021,7 @@ impl<REv> EffectBuilder<REv> {
             .schedule(
                 ContractRuntimeAnnouncement::CommitStepSuccess {
                     era_id,
-                    execution_effect: ExecutionEffect::from(&execution_journal),
+                    execution_effect,
                 },
                 QueueKind::ContractRuntime,
             )
@@ -1114,6 +1117,112 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Adds the statistics of executing a block to the execution statistics of its era in storage.
+    pub(crate) async fn put_block_execution_stats_to_storage(
+        self,
+        block_execution_stats: EraExecutionStats,
+    ) -> bool
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::PutBlockExecutionStats {
+                block_execution_stats: Box::new(block_execution_stats),
+                responder,
+            },
+            QueueKind::ToStorage,
+        )
+        .await
+    }
+
+    /// Gets the execution statistics of the given era from storage.
+    pub(crate) async fn get_era_execution_stats_from_storage(
+        self,
+        era_id: EraId,
+    ) -> Option<EraExecutionStats>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetEraExecutionStats { era_id, responder },
+            QueueKind::FromStorage,
+        )
+        .await
+    }
+
+    /// Puts the receipts of executed deploys into storage.
+    pub(crate) async fn put_transaction_receipts_to_storage(
+        self,
+        transaction_receipts: Vec<TransactionReceipt>,
+    ) where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::PutTransactionReceipts {
+                transaction_receipts,
+                responder,
+            },
+            QueueKind::ToStorage,
+        )
+        .await
+    }
+
+    /// Puts the artifacts of executing a block into storage, keeping those of at most
+    /// `max_stored_heights` block heights up to and including its own.
+    pub(crate) async fn put_execution_artifacts_to_storage(
+        self,
+        execution_artifacts: ExecutionArtifacts,
+        max_stored_heights: u64,
+    ) where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::PutExecutionArtifacts {
+                execution_artifacts: Box::new(execution_artifacts),
+                max_stored_heights,
+                responder,
+            },
+            QueueKind::ToStorage,
+        )
+        .await
+    }
+
+    /// Gets the stored artifacts of executing the block at the given height, if any.
+    pub(crate) async fn get_execution_artifacts_from_storage(
+        self,
+        block_height: u64,
+    ) -> Option<ExecutionArtifacts>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetExecutionArtifacts {
+                block_height,
+                responder,
+            },
+            QueueKind::FromStorage,
+        )
+        .await
+    }
+
+    /// Gets the receipt of an executed deploy from storage.
+    pub(crate) async fn get_transaction_receipt_from_storage(
+        self,
+        deploy_hash: DeployHash,
+    ) -> Option<TransactionReceipt>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetTransactionReceipt {
+                deploy_hash,
+                responder,
+            },
+            QueueKind::FromStorage,
+        )
+        .await
+    }
+
     /// Gets the requested block from the linear block store.
     pub(crate) async fn get_block_from_storage(self, block_hash: BlockHash) -> Option<Block>
     where
@@ -1200,6 +1309,24 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Gets the height of the highest stored block with the given state root hash.
+    pub(crate) async fn get_block_height_for_state_root_hash_from_storage(
+        self,
+        state_root_hash: Digest,
+    ) -> Option<u64>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetBlockHeightForStateRootHash {
+                state_root_hash,
+                responder,
+            },
+            QueueKind::FromStorage,
+        )
+        .await
+    }
+
     /// Gets the requested signature for a given block hash.
     pub(crate) async fn get_signature_from_storage(
         self,
@@ -1879,16 +2006,21 @@ impl<REv> EffectBuilder<REv> {
     }
 
     /// Requests a query be executed on the Contract Runtime component.
+    ///
+    /// Unless unchecked, the query is rejected if the queried state root is older than the
+    /// configured limit, or if too many queries are already waiting to run.
     pub(crate) async fn query_global_state(
         self,
         query_request: QueryRequest,
-    ) -> Result<QueryResult, engine_state::Error>
+        state_root_age_check: StateRootAgeCheck,
+    ) -> Result<QueryResult, StateQueryError>
     where
         REv: From<ContractRuntimeRequest>,
     {
         self.make_request(
             |responder| ContractRuntimeRequest::Query {
                 query_request,
+                state_root_age_check,
                 responder,
             },
             QueueKind::ContractRuntime,
@@ -1906,7 +2038,10 @@ impl<REv> EffectBuilder<REv> {
         REv: From<ContractRuntimeRequest>,
     {
         let query_request = QueryRequest::new(state_root_hash, account_key, vec![]);
-        match self.query_global_state(query_request).await {
+        match self
+            .query_global_state(query_request, StateRootAgeCheck::Unchecked)
+            .await
+        {
             Ok(QueryResult::Success { value, .. }) => value.as_account().cloned(),
             Ok(_) | Err(_) => None,
         }
@@ -1922,7 +2057,10 @@ impl<REv> EffectBuilder<REv> {
         REv: From<ContractRuntimeRequest>,
     {
         let balance_request = BalanceRequest::new(state_root_hash, main_purse);
-        match self.get_balance(balance_request).await {
+        match self
+            .get_balance(balance_request, StateRootAgeCheck::Unchecked)
+            .await
+        {
             Ok(balance_result) => {
                 if let Some(motes) = balance_result.motes() {
                     return Some(*motes);
@@ -1944,7 +2082,10 @@ impl<REv> EffectBuilder<REv> {
         REv: From<ContractRuntimeRequest>,
     {
         let query_request = QueryRequest::new(state_root_hash, query_key, path);
-        match self.query_global_state(query_request).await {
+        match self
+            .query_global_state(query_request, StateRootAgeCheck::Unchecked)
+            .await
+        {
             Ok(QueryResult::Success { value, .. }) => {
                 // TODO: Extending `StoredValue` with an `into_contract` would reduce cloning here.
                 value.as_contract().map(|c| Box::new(c.clone()))
@@ -1964,7 +2105,10 @@ impl<REv> EffectBuilder<REv> {
         REv: From<ContractRuntimeRequest>,
     {
         let query_request = QueryRequest::new(state_root_hash, query_key, path);
-        match self.query_global_state(query_request).await {
+        match self
+            .query_global_state(query_request, StateRootAgeCheck::Unchecked)
+            .await
+        {
             Ok(QueryResult::Success { value, .. }) => {
                 value.as_contract_package().map(|pkg| Box::new(pkg.clone()))
             }
@@ -1973,16 +2117,21 @@ impl<REv> EffectBuilder<REv> {
     }
 
     /// Requests a query be executed on the Contract Runtime component.
+    ///
+    /// Unless unchecked, the query is rejected if the queried state root is older than the
+    /// configured limit, or if too many queries are already waiting to run.
     pub(crate) async fn get_balance(
         self,
         balance_request: BalanceRequest,
-    ) -> Result<BalanceResult, engine_state::Error>
+        state_root_age_check: StateRootAgeCheck,
+    ) -> Result<BalanceResult, StateQueryError>
     where
         REv: From<ContractRuntimeRequest>,
     {
         self.make_request(
             |responder| ContractRuntimeRequest::GetBalance {
                 balance_request,
+                state_root_age_check,
                 responder,
             },
             QueueKind::ContractRuntime,
@@ -1992,16 +2141,22 @@ impl<REv> EffectBuilder<REv> {
 
     /// Returns a map of validators weights for all eras as known from `root_hash`.
     ///
-    /// This operation is read only.
+    /// This operation is read only.  Unless made by the node itself, it is rejected if too many
+    /// queries are already waiting to run.
     pub(crate) async fn get_era_validators_from_contract_runtime(
         self,
         request: EraValidatorsRequest,
+        origin: QueryOrigin,
     ) -> Result<EraValidators, GetEraValidatorsError>
     where
         REv: From<ContractRuntimeRequest>,
     {
         self.make_request(
-            |responder| ContractRuntimeRequest::GetEraValidators { request, responder },
+            |responder| ContractRuntimeRequest::GetEraValidators {
+                request,
+                origin,
+                responder,
+            },
             QueueKind::ContractRuntime,
         )
         .await
@@ -2025,6 +2180,25 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Requests the pending unbonding requests of a public key from the Contract Runtime
+    /// component.
+    pub(crate) async fn get_unbonds(
+        self,
+        get_unbonds_request: GetUnbondsRequest,
+    ) -> Result<GetUnbondsResult, engine_state::Error>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::GetUnbonds {
+                get_unbonds_request,
+                responder,
+            },
+            QueueKind::ContractRuntime,
+        )
+        .await
+    }
+
     /// Returns the value of the execution results checksum stored in the ChecksumRegistry for the
     /// given state root hash.
     pub(crate) async fn get_execution_results_checksum(
@@ -2044,6 +2218,18 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Returns the configuration the execution engine is running with.
+    pub(crate) async fn get_engine_config(self) -> EngineConfig
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::GetEngineConfig { responder },
+            QueueKind::ContractRuntime,
+        )
+        .await
+    }
+
     /// Get our public key from consensus, and if we're a validator, the next round length.
     pub(crate) async fn consensus_status(self) -> Option<(PublicKey, Option<TimeDiff>)>
     where
@@ -2188,7 +2374,7 @@ impl<REv> EffectBuilder<REv> {
         self,
         execution_prestate: SpeculativeExecutionState,
         deploy: Arc<Deploy>,
-    ) -> Result<Option<ExecutionResult>, engine_state::Error>
+    ) -> Result<Option<ExecutionResult>, StateQueryError>
     where
         REv: From<ContractRuntimeRequest>,
     {
@@ -2203,6 +2389,27 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Requests execution of a sequence of deploys against a single forked state, each seeing the
+    /// effects of those before it, without commiting their effects.
+    pub(crate) async fn speculative_execute_deploy_bundle(
+        self,
+        execution_prestate: SpeculativeExecutionState,
+        deploys: Vec<Arc<Deploy>>,
+    ) -> Result<Option<Vec<ExecutionResult>>, StateQueryError>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::SpeculativeDeployBundleExecution {
+                execution_prestate,
+                deploys,
+                responder,
+            },
+            QueueKind::ContractRuntime,
+        )
+        .await
+    }
+
     /// Reads block execution results (or chunk) from Storage component.
     pub(crate) async fn get_block_execution_results_or_chunk_from_storage(
         self,
@@ -21,14 +21,16 @@ use casper_execution_engine::{
         balance::{BalanceRequest, BalanceResult},
         era_validators::GetEraValidatorsError,
         get_bids::{GetBidsRequest, GetBidsResult},
+        get_unbonds::{GetUnbondsRequest, GetUnbondsResult},
         query::{QueryRequest, QueryResult},
+        EngineConfig,
     },
     storage::trie::TrieRaw,
 };
 use casper_hashing::Digest;
 use casper_types::{
     bytesrepr::Bytes, system::auction::EraValidators, EraId, ExecutionResult, Key, ProtocolVersion,
-    PublicKey, TimeDiff, Timestamp, Transfer, URef,
+    PublicKey, TimeDiff, Timestamp, TransactionReceipt, Transfer, URef,
 };
 
 use crate::{
@@ -46,7 +48,10 @@ use crate::{
         network::NetworkInsights,
         upgrade_watcher::NextUpgrade,
     },
-    contract_runtime::{ContractRuntimeError, SpeculativeExecutionState},
+    contract_runtime::{
+        ContractRuntimeError, ExecutionArtifacts, QueryOrigin, SpeculativeExecutionState,
+        StateQueryError, StateRootAgeCheck,
+    },
     effect::{AutoClosingResponder, Responder},
     reactor::main_reactor::ReactorState,
     rpcs::docs::OpenRpcSchema,
@@ -54,9 +59,9 @@ use crate::{
         appendable_block::AppendableBlock, ApprovalsHashes, AvailableBlockRange, Block,
         BlockExecutionResultsOrChunk, BlockExecutionResultsOrChunkId, BlockHash, BlockHeader,
         BlockSignatures, BlockWithMetadata, ChainspecRawBytes, Deploy, DeployHash, DeployHeader,
-        DeployId, DeployMetadataExt, DeployWithFinalizedApprovals, FinalitySignature,
-        FinalitySignatureId, FinalizedApprovals, FinalizedBlock, LegacyDeploy, MetaBlockState,
-        NodeId, StatusFeed, TrieOrChunk, TrieOrChunkId,
+        DeployId, DeployMetadataExt, DeployWithFinalizedApprovals, EraExecutionStats,
+        FinalitySignature, FinalitySignatureId, FinalizedApprovals, FinalizedBlock, LegacyDeploy,
+        MetaBlockState, NodeId, StatusFeed, TrieOrChunk, TrieOrChunkId,
     },
     utils::{DisplayIter, Source},
 };
@@ -344,6 +349,14 @@ pub(crate) enum StorageRequest {
         /// local storage.
         responder: Responder<Option<BlockHeader>>,
     },
+    /// Retrieve the height of the highest stored block with the given state root hash.
+    GetBlockHeightForStateRootHash {
+        /// The state root hash.
+        state_root_hash: Digest,
+        /// Responder to call with the result.  Returns `None` if no stored block has the given
+        /// state root hash.
+        responder: Responder<Option<u64>>,
+    },
     /// Retrieve all transfers in a block with given hash.
     GetBlockTransfers {
         /// Hash of block to get transfers of.
@@ -498,6 +511,55 @@ pub(crate) enum StorageRequest {
     },
     /// Retrieve the height of the final block of the previous protocol version, if known.
     GetKeyBlockHeightForActivationPoint { responder: Responder<Option<u64>> },
+    /// Add the statistics of executing a block to the execution statistics of its era.
+    PutBlockExecutionStats {
+        /// The statistics of the executed block.
+        block_execution_stats: Box<EraExecutionStats>,
+        /// Responder to call with the result.  Returns `true` if the statistics were added, or
+        /// `false` if the block was already included.
+        responder: Responder<bool>,
+    },
+    /// Retrieve the execution statistics of an era.
+    GetEraExecutionStats {
+        /// The era to retrieve the execution statistics of.
+        era_id: EraId,
+        /// Responder to call with the result.  Returns `None` if no block of the era has been
+        /// executed by this node.
+        responder: Responder<Option<EraExecutionStats>>,
+    },
+    /// Store the receipts of executed deploys.
+    PutTransactionReceipts {
+        /// The receipts to store, replacing any stored for the same deploys.
+        transaction_receipts: Vec<TransactionReceipt>,
+        /// Responder to call when done storing.
+        responder: Responder<()>,
+    },
+    /// Retrieve the receipt of an executed deploy.
+    GetTransactionReceipt {
+        /// Hash of the deploy to retrieve the receipt of.
+        deploy_hash: DeployHash,
+        /// Responder to call with the result.  Returns `None` if this node has not executed the
+        /// deploy.
+        responder: Responder<Option<TransactionReceipt>>,
+    },
+    /// Store the artifacts of executing a block.
+    PutExecutionArtifacts {
+        /// The artifacts to store, replacing any stored for the same block height.
+        execution_artifacts: Box<ExecutionArtifacts>,
+        /// The number of block heights, up to and including that of the executed block, whose
+        /// artifacts are kept.  Those of lower heights are removed.
+        max_stored_heights: u64,
+        /// Responder to call when done storing.
+        responder: Responder<()>,
+    },
+    /// Retrieve the artifacts of executing the block at a given height.
+    GetExecutionArtifacts {
+        /// Height of the executed block.
+        block_height: u64,
+        /// Responder to call with the result.  Returns `None` if no artifacts are stored for the
+        /// height.
+        responder: Responder<Option<ExecutionArtifacts>>,
+    },
 }
 
 impl Display for StorageRequest {
@@ -533,6 +595,15 @@ impl Display for StorageRequest {
             StorageRequest::GetBlockHeaderByHeight { block_height, .. } => {
                 write!(formatter, "get header for height {}", block_height)
             }
+            StorageRequest::GetBlockHeightForStateRootHash {
+                state_root_hash, ..
+            } => {
+                write!(
+                    formatter,
+                    "get block height for state root {}",
+                    state_root_hash
+                )
+            }
             StorageRequest::GetBlockTransfers { block_hash, .. } => {
                 write!(formatter, "get transfers for {}", block_hash)
             }
@@ -620,6 +691,45 @@ impl Display for StorageRequest {
                     "get key block height for current activation point"
                 )
             }
+            StorageRequest::PutBlockExecutionStats {
+                block_execution_stats,
+                ..
+            } => {
+                write!(formatter, "put {}", block_execution_stats)
+            }
+            StorageRequest::GetEraExecutionStats { era_id, .. } => {
+                write!(formatter, "get execution stats for {}", era_id)
+            }
+            StorageRequest::PutTransactionReceipts {
+                transaction_receipts,
+                ..
+            } => {
+                write!(
+                    formatter,
+                    "put {} transaction receipts",
+                    transaction_receipts.len()
+                )
+            }
+            StorageRequest::GetTransactionReceipt { deploy_hash, .. } => {
+                write!(formatter, "get transaction receipt for {}", deploy_hash)
+            }
+            StorageRequest::PutExecutionArtifacts {
+                execution_artifacts,
+                ..
+            } => {
+                write!(
+                    formatter,
+                    "put execution artifacts for height {}",
+                    execution_artifacts.block_height()
+                )
+            }
+            StorageRequest::GetExecutionArtifacts { block_height, .. } => {
+                write!(
+                    formatter,
+                    "get execution artifacts for height {}",
+                    block_height
+                )
+            }
         }
     }
 }
@@ -698,12 +808,15 @@ pub(crate) enum RpcRequest {
     QueryGlobalState {
         /// The state root hash.
         state_root_hash: Digest,
+        /// The height of the block whose state root is queried, if known.  Otherwise it is looked
+        /// up from storage.
+        block_height: Option<u64>,
         /// Hex-encoded `casper_types::Key`.
         base_key: Key,
         /// The path components starting from the key as base.
         path: Vec<String>,
         /// Responder to call with the result.
-        responder: Responder<Result<QueryResult, engine_state::Error>>,
+        responder: Responder<Result<QueryResult, StateQueryError>>,
     },
     /// Query the global state at the given root hash.
     QueryEraValidators {
@@ -726,10 +839,13 @@ pub(crate) enum RpcRequest {
     GetBalance {
         /// The state root hash.
         state_root_hash: Digest,
+        /// The height of the block whose state root is queried, if known.  Otherwise it is looked
+        /// up from storage.
+        block_height: Option<u64>,
         /// The purse URef.
         purse_uref: URef,
         /// Responder to call with the result.
-        responder: Responder<Result<BalanceResult, engine_state::Error>>,
+        responder: Responder<Result<BalanceResult, StateQueryError>>,
     },
     /// Return the specified deploy and metadata if it exists, else `None`.
     GetDeploy {
@@ -862,22 +978,28 @@ pub(crate) enum ContractRuntimeRequest {
         /// Query request.
         #[serde(skip_serializing)]
         query_request: QueryRequest,
+        /// How the age of the queried state root is checked against the configured limit.
+        state_root_age_check: StateRootAgeCheck,
         /// Responder to call with the query result.
-        responder: Responder<Result<QueryResult, engine_state::Error>>,
+        responder: Responder<Result<QueryResult, StateQueryError>>,
     },
     /// A balance request.
     GetBalance {
         /// Balance request.
         #[serde(skip_serializing)]
         balance_request: BalanceRequest,
+        /// How the age of the queried state root is checked against the configured limit.
+        state_root_age_check: StateRootAgeCheck,
         /// Responder to call with the balance result.
-        responder: Responder<Result<BalanceResult, engine_state::Error>>,
+        responder: Responder<Result<BalanceResult, StateQueryError>>,
     },
     /// Returns validator weights.
     GetEraValidators {
         /// Get validators weights request.
         #[serde(skip_serializing)]
         request: EraValidatorsRequest,
+        /// Who the request is made for.
+        origin: QueryOrigin,
         /// Responder to call with the result.
         responder: Responder<Result<EraValidators, GetEraValidatorsError>>,
     },
@@ -889,6 +1011,14 @@ pub(crate) enum ContractRuntimeRequest {
         /// Responder to call with the result.
         responder: Responder<Result<GetBidsResult, engine_state::Error>>,
     },
+    /// Return the pending unbonding requests of a public key at a given state root hash.
+    GetUnbonds {
+        /// Get unbonds request.
+        #[serde(skip_serializing)]
+        get_unbonds_request: GetUnbondsRequest,
+        /// Responder to call with the result.
+        responder: Responder<Result<GetUnbondsResult, engine_state::Error>>,
+    },
     /// Returns the value of the execution results checksum stored in the ChecksumRegistry for the
     /// given state root hash.
     GetExecutionResultsChecksum {
@@ -923,7 +1053,21 @@ pub(crate) enum ContractRuntimeRequest {
         /// Deploy to execute.
         deploy: Arc<Deploy>,
         /// Results
-        responder: Responder<Result<Option<ExecutionResult>, engine_state::Error>>,
+        responder: Responder<Result<Option<ExecutionResult>, StateQueryError>>,
+    },
+    /// Execute a sequence of deploys against a single forked state without commiting results
+    SpeculativeDeployBundleExecution {
+        /// Hash of a block on top of which to execute the deploys.
+        execution_prestate: SpeculativeExecutionState,
+        /// Deploys to execute, in order.
+        deploys: Vec<Arc<Deploy>>,
+        /// Results, in the order of the deploys.
+        responder: Responder<Result<Option<Vec<ExecutionResult>>, StateQueryError>>,
+    },
+    /// Returns the configuration the execution engine is running with.
+    GetEngineConfig {
+        /// Responder to call with the result.
+        responder: Responder<EngineConfig>,
     },
 }
 
@@ -949,6 +1093,12 @@ impl Display for ContractRuntimeRequest {
             } => {
                 write!(formatter, "get bids request: {:?}", get_bids_request)
             }
+            ContractRuntimeRequest::GetUnbonds {
+                get_unbonds_request,
+                ..
+            } => {
+                write!(formatter, "get unbonds request: {:?}", get_unbonds_request)
+            }
             ContractRuntimeRequest::GetExecutionResultsChecksum {
                 state_root_hash, ..
             } => write!(
@@ -979,6 +1129,21 @@ impl Display for ContractRuntimeRequest {
                     execution_prestate.state_root_hash
                 )
             }
+            ContractRuntimeRequest::SpeculativeDeployBundleExecution {
+                execution_prestate,
+                deploys,
+                ..
+            } => {
+                write!(
+                    formatter,
+                    "Execute bundle of {} deploys on {}",
+                    deploys.len(),
+                    execution_prestate.state_root_hash
+                )
+            }
+            ContractRuntimeRequest::GetEngineConfig { .. } => {
+                write!(formatter, "get engine config")
+            }
         }
     }
 }
@@ -22,10 +22,12 @@
 )]
 #![allow(clippy::bool_comparison)]
 
+mod chain_audit;
 pub mod cli;
 pub(crate) mod components;
 mod config_migration;
 mod data_migration;
+mod deploy_replay;
 pub(crate) mod effect;
 pub mod logging;
 pub(crate) mod protocol;
@@ -73,7 +73,7 @@ use crate::{
     },
     types::{
         Block, BlockHash, Chainspec, ChainspecRawBytes, Deploy, FinalitySignature, MetaBlock,
-        MetaBlockState, SyncHandling, TrieOrChunk, ValidatorMatrix,
+        MetaBlockState, SyncHandling, TrieOrChunk, TrustedCheckpoint, ValidatorMatrix,
     },
     utils::{Source, WithDir},
     NodeRng,
@@ -174,6 +174,7 @@ pub(crate) struct MainReactor {
     //   ambient settings / data / load-bearing config
     validator_matrix: ValidatorMatrix,
     trusted_hash: Option<BlockHash>,
+    trusted_checkpoint: Option<TrustedCheckpoint>,
     chainspec: Arc<Chainspec>,
     chainspec_raw_bytes: Arc<ChainspecRawBytes>,
 
@@ -1002,9 +1003,10 @@ impl reactor::Reactor for MainReactor {
         let protocol_version = chainspec.protocol_config.version;
 
         let trusted_hash = config.value().node.trusted_hash;
+        let trusted_checkpoint = config.value().node.trusted_checkpoint.clone();
         let (root_dir, config) = config.into_parts();
         let (our_secret_key, our_public_key) = config.consensus.load_keys(&root_dir)?;
-        let validator_matrix = ValidatorMatrix::new(
+        let mut validator_matrix = ValidatorMatrix::new(
             chainspec.core_config.finality_threshold_fraction,
             chainspec
                 .protocol_config
@@ -1016,6 +1018,21 @@ impl reactor::Reactor for MainReactor {
             our_public_key.clone(),
             chainspec.core_config.auction_delay,
         );
+        if let Some(trusted_checkpoint) = &trusted_checkpoint {
+            warn!(
+                block_hash = %trusted_checkpoint.block_hash,
+                state_root_hash = %trusted_checkpoint.state_root_hash,
+                era_id = %trusted_checkpoint.era_id,
+                validator_count = trusted_checkpoint.validator_weights.len(),
+                "MainReactor: joining from trusted checkpoint; history will not be verified back \
+                to genesis, and its block, state root and validator weights are assumed to be \
+                part of the canonical chain"
+            );
+            validator_matrix.register_validator_weights(
+                trusted_checkpoint.era_id,
+                trusted_checkpoint.validator_weights.clone(),
+            );
+        }
 
         let storage_config = WithDir::new(&root_dir, config.storage.clone());
 
@@ -1032,33 +1049,11 @@ impl reactor::Reactor for MainReactor {
             config.node.force_resync,
         )?;
 
-        let max_delegators_per_validator =
-            if chainspec.core_config.max_delegators_per_validator == 0 {
-                None
-            } else {
-                Some(chainspec.core_config.max_delegators_per_validator)
-            };
-
-        let contract_runtime = ContractRuntime::new(
-            protocol_version,
-            storage.root_path(),
+        let contract_runtime = ContractRuntime::from_chainspec(
+            &storage,
             &config.contract_runtime,
-            chainspec.wasm_config,
-            chainspec.system_costs_config,
-            chainspec.core_config.max_associated_keys,
-            chainspec.core_config.max_runtime_call_stack_height,
-            chainspec.core_config.minimum_delegation_amount,
-            chainspec.protocol_config.activation_point,
-            chainspec.core_config.prune_batch_size,
-            chainspec.core_config.strict_argument_checking,
-            chainspec.core_config.vesting_schedule_period.millis(),
-            max_delegators_per_validator,
+            &chainspec,
             registry,
-            chainspec.core_config.administrators.clone(),
-            chainspec.core_config.allow_auction_bids,
-            chainspec.core_config.allow_unrestricted_transfers,
-            chainspec.core_config.refund_handling,
-            chainspec.core_config.fee_handling,
         )?;
 
         let network = Network::new(
@@ -1193,6 +1188,7 @@ impl reactor::Reactor for MainReactor {
             idle_tolerance: config.node.idle_tolerance,
             control_logic_default_delay: config.node.control_logic_default_delay,
             trusted_hash,
+            trusted_checkpoint,
             validator_matrix,
             sync_handling: config.node.sync_handling,
             signature_gossip_tracker: SignatureGossipTracker::new(),
@@ -17,7 +17,9 @@ use crate::{
         main_reactor::{MainEvent, MainReactor},
         wrap_effects,
     },
-    types::{ActivationPoint, BlockHash, NodeId, SyncLeap, SyncLeapIdentifier},
+    types::{
+        ActivationPoint, BlockHash, EraValidatorWeights, NodeId, SyncLeap, SyncLeapIdentifier,
+    },
     NodeRng,
 };
 
@@ -363,6 +365,10 @@ impl MainReactor {
             "CatchUp: leap received"
         );
 
+        if let Err(msg) = self.check_trusted_checkpoint(&sync_leap) {
+            return CatchUpInstruction::Fatal(msg);
+        }
+
         for validator_weights in sync_leap.era_validator_weights(
             self.validator_matrix.fault_tolerance_threshold(),
             &self.chainspec.protocol_config,
@@ -391,6 +397,55 @@ impl MainReactor {
         CatchUpInstruction::Do(self.control_logic_default_delay.into(), effects)
     }
 
+    /// Checks that the given sync leap agrees with the configured trusted checkpoint, if any.
+    fn check_trusted_checkpoint(&self, sync_leap: &SyncLeap) -> Result<(), String> {
+        let trusted_checkpoint = match &self.trusted_checkpoint {
+            Some(trusted_checkpoint) => trusted_checkpoint,
+            None => return Ok(()),
+        };
+
+        let trusted_header = &sync_leap.trusted_block_header;
+        if trusted_header.block_hash() == trusted_checkpoint.block_hash {
+            if *trusted_header.state_root_hash() != trusted_checkpoint.state_root_hash {
+                return Err(format!(
+                    "CatchUp: trusted checkpoint block {} has state root hash {}, not {} as \
+                    configured",
+                    trusted_checkpoint.block_hash,
+                    trusted_header.state_root_hash(),
+                    trusted_checkpoint.state_root_hash
+                ));
+            }
+            if trusted_header.era_id() != trusted_checkpoint.era_id {
+                return Err(format!(
+                    "CatchUp: trusted checkpoint block {} is in era {}, not {} as configured",
+                    trusted_checkpoint.block_hash,
+                    trusted_header.era_id(),
+                    trusted_checkpoint.era_id
+                ));
+            }
+        }
+
+        let fault_tolerance_threshold = self.validator_matrix.fault_tolerance_threshold();
+        let expected_weights = EraValidatorWeights::new(
+            trusted_checkpoint.era_id,
+            trusted_checkpoint.validator_weights.clone(),
+            fault_tolerance_threshold,
+        );
+        let mismatch = sync_leap
+            .era_validator_weights(fault_tolerance_threshold, &self.chainspec.protocol_config)
+            .any(|validator_weights| {
+                validator_weights.era_id() == trusted_checkpoint.era_id
+                    && validator_weights != expected_weights
+            });
+        if mismatch {
+            return Err(format!(
+                "CatchUp: validator weights of era {} differ from those of the trusted checkpoint",
+                trusted_checkpoint.era_id
+            ));
+        }
+        Ok(())
+    }
+
     fn catch_up_block_sync(
         &mut self,
         effect_builder: EffectBuilder<MainEvent>,
@@ -1,10 +1,10 @@
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
-use tracing::error;
+use tracing::{error, warn};
 
 use crate::{
     logging::LoggingConfig,
-    types::{Chainspec, NodeConfig},
+    types::{Chainspec, NodeConfig, SyncHandling},
     BlockAccumulatorConfig, BlockSynchronizerConfig, BlockValidatorConfig, ConsensusConfig,
     ContractRuntimeConfig, DeployAcceptorConfig, DeployBufferConfig, DiagnosticsPortConfig,
     EventStreamServerConfig, FetcherConfig, GossipConfig, NetworkConfig, RestServerConfig,
@@ -69,5 +69,92 @@ impl Config {
             );
             self.deploy_acceptor.timestamp_leeway = chainspec.deploy_config.max_timestamp_leeway;
         }
+
+        if let Some(trusted_checkpoint) = &self.node.trusted_checkpoint {
+            if let Some(trusted_hash) = self.node.trusted_hash {
+                if trusted_hash != trusted_checkpoint.block_hash {
+                    error!(
+                        %trusted_hash,
+                        checkpoint_block_hash = %trusted_checkpoint.block_hash,
+                        "setting value for 'node.trusted_hash' to the block hash of \
+                        'node.trusted_checkpoint'",
+                    );
+                }
+            }
+            self.node.trusted_hash = Some(trusted_checkpoint.block_hash);
+
+            match self.node.sync_handling {
+                SyncHandling::Genesis => {
+                    warn!(
+                        sync_handling = ?self.node.sync_handling,
+                        "setting value for 'node.sync_handling' to 'ttl', as a node joining from \
+                        'node.trusted_checkpoint' only syncs the history it needs to validate",
+                    );
+                    self.node.sync_handling = SyncHandling::Ttl;
+                }
+                SyncHandling::Ttl => {}
+                SyncHandling::NoSync => {
+                    warn!(
+                        "'node.sync_handling' is 'nosync', so this node joining from \
+                        'node.trusted_checkpoint' will never participate in consensus; set it to \
+                        'ttl' for the node to be able to validate",
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+
+    use casper_hashing::Digest;
+    use casper_types::{testing::TestRng, EraId, PublicKey, U512};
+
+    use super::*;
+    use crate::types::{BlockHash, TrustedCheckpoint};
+
+    #[test]
+    fn trusted_checkpoint_should_override_trusted_hash_and_sync_handling() {
+        let mut rng = TestRng::new();
+        let trusted_checkpoint = TrustedCheckpoint {
+            block_hash: BlockHash::random(&mut rng),
+            state_root_hash: Digest::hash([1]),
+            era_id: EraId::from(3),
+            validator_weights: iter::once((PublicKey::random(&mut rng), U512::from(100))).collect(),
+        };
+        let mut node_config = NodeConfig {
+            trusted_hash: Some(BlockHash::random(&mut rng)),
+            sync_handling: SyncHandling::Genesis,
+            trusted_checkpoint: Some(trusted_checkpoint.clone()),
+            ..NodeConfig::default()
+        };
+
+        // The checkpoint must survive being written to and read from a config file.
+        let encoded = toml::to_string(&node_config).unwrap();
+        node_config = toml::from_str(&encoded).unwrap();
+        assert_eq!(
+            node_config.trusted_checkpoint,
+            Some(trusted_checkpoint.clone())
+        );
+
+        let mut config = Config {
+            node: node_config,
+            ..Config::default()
+        };
+        let chainspec = Chainspec::random(&mut rng);
+        config.ensure_valid(&chainspec);
+        assert_eq!(
+            config.node.trusted_hash,
+            Some(trusted_checkpoint.block_hash)
+        );
+        // Syncing the TTL's worth of history keeps the node able to validate.
+        assert!(config.node.sync_handling.is_sync_to_ttl());
+
+        // An explicit 'nosync' is kept.
+        config.node.sync_handling = SyncHandling::NoSync;
+        config.ensure_valid(&chainspec);
+        assert!(config.node.sync_handling.is_no_sync());
     }
 }
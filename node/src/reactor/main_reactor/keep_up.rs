@@ -12,7 +12,7 @@ use crate::{
     components::{
         block_accumulator::{SyncIdentifier, SyncInstruction},
         block_synchronizer::BlockSynchronizerProgress,
-        contract_runtime::EraValidatorsRequest,
+        contract_runtime::{EraValidatorsRequest, QueryOrigin},
         storage::HighestOrphanedBlockResult,
         sync_leaper,
         sync_leaper::{LeapActivityError, LeapState},
@@ -337,14 +337,20 @@ impl MainReactor {
                 global_states_metadata.before_protocol_version,
             );
             let before_era_validators_result = effect_builder
-                .get_era_validators_from_contract_runtime(before_era_validators_request)
+                .get_era_validators_from_contract_runtime(
+                    before_era_validators_request,
+                    QueryOrigin::Node,
+                )
                 .await;
             let after_era_validators_request = EraValidatorsRequest::new(
                 global_states_metadata.after_state_hash,
                 global_states_metadata.after_protocol_version,
             );
             let after_era_validators_result = effect_builder
-                .get_era_validators_from_contract_runtime(after_era_validators_request)
+                .get_era_validators_from_contract_runtime(
+                    after_era_validators_request,
+                    QueryOrigin::Node,
+                )
                 .await;
 
             // Check the results.
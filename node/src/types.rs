@@ -7,6 +7,7 @@ mod block_hash_height_and_era;
 pub mod chainspec;
 mod chunkable;
 mod deploy;
+mod era_execution_stats;
 pub mod error;
 mod exit_code;
 pub mod json_compatibility;
@@ -50,10 +51,11 @@ pub(crate) use deploy::{
     DeployFootprint, DeployHashWithApprovals, DeployId, DeployMetadata, DeployMetadataExt,
     DeployWithFinalizedApprovals, FinalizedApprovals, LegacyDeploy,
 };
+pub use era_execution_stats::EraExecutionStats;
 pub use error::BlockValidationError;
 pub use exit_code::ExitCode;
 pub(crate) use max_ttl::MaxTtl;
-pub use node_config::{NodeConfig, SyncHandling};
+pub use node_config::{NodeConfig, SyncHandling, TrustedCheckpoint};
 pub(crate) use node_id::NodeId;
 pub use peers_map::PeersMap;
 pub use status_feed::{ChainspecInfo, GetStatusResult, StatusFeed};
@@ -43,6 +43,7 @@ pub(crate) struct AppendableBlock {
     transfers: Vec<DeployHashWithApprovals>,
     deploy_and_transfer_set: HashSet<DeployHash>,
     timestamp: Timestamp,
+    total_transfers: usize,
     #[data_size(skip)]
     total_gas: Gas,
     total_size: usize,
@@ -58,6 +59,7 @@ impl AppendableBlock {
             transfers: Vec::new(),
             timestamp,
             deploy_and_transfer_set: HashSet::new(),
+            total_transfers: 0,
             total_gas: Gas::zero(),
             total_size: 0,
             total_approvals: 0,
@@ -108,13 +110,14 @@ impl AppendableBlock {
         {
             return Err(AddError::InvalidDeploy);
         }
-        if self.has_max_transfer_count() {
+        if self.would_exceed_transfer_count(footprint.transfer_count) {
             return Err(AddError::TransferCount);
         }
         if self.would_exceed_approval_limits(transfer.approvals().len()) {
             return Err(AddError::ApprovalCount);
         }
         self.deploy_and_transfer_set.insert(*transfer.deploy_hash());
+        self.total_transfers += footprint.transfer_count;
         self.total_approvals += transfer.approvals().len();
         self.transfers.push(transfer);
         Ok(())
@@ -193,10 +196,11 @@ impl AppendableBlock {
         self.timestamp
     }
 
-    /// Returns `true` if the number of transfers is already the maximum allowed count, i.e. no
-    /// more transfers can be added to this block.
-    fn has_max_transfer_count(&self) -> bool {
-        self.transfers.len() == self.deploy_config.block_max_transfer_count as usize
+    /// Returns `true` if adding `additional_transfers` native transfers would exceed the maximum
+    /// allowed count.  Each transfer of a batch counts separately.
+    fn would_exceed_transfer_count(&self, additional_transfers: usize) -> bool {
+        self.total_transfers + additional_transfers
+            > self.deploy_config.block_max_transfer_count as usize
     }
 
     /// Returns `true` if the number of deploys is already the maximum allowed count, i.e. no more
@@ -213,7 +217,7 @@ impl AppendableBlock {
         let remaining_approval_slots =
             self.deploy_config.block_max_approval_count as usize - self.total_approvals;
         let remaining_deploy_slots = self.deploy_config.block_max_transfer_count as usize
-            - self.transfers.len()
+            - self.total_transfers
             + self.deploy_config.block_max_deploy_count as usize
             - self.deploys.len();
         // safe to subtract because the chainspec is validated at load time
@@ -254,9 +258,47 @@ impl Display for AppendableBlock {
 mod tests {
     use super::*;
 
+    use crate::types::Deploy;
+
     impl AppendableBlock {
         pub(crate) fn deploy_and_transfer_set(&self) -> &HashSet<DeployHash> {
             &self.deploy_and_transfer_set
         }
     }
+
+    fn add(appendable_block: &mut AppendableBlock, deploy: &Deploy) -> Result<(), AddError> {
+        let footprint = deploy.footprint().expect("should have footprint");
+        appendable_block.add(DeployHashWithApprovals::from(deploy), &footprint)
+    }
+
+    #[test]
+    fn should_count_each_batched_transfer_against_transfer_limit() {
+        let mut rng = crate::new_rng();
+        let deploy_config = DeployConfig {
+            block_max_transfer_count: 5,
+            ..DeployConfig::default()
+        };
+        let multi_transfer = Deploy::random_valid_multi_transfer(&mut rng, 4);
+        let second_multi_transfer = Deploy::random_valid_multi_transfer(&mut rng, 2);
+        let single_multi_transfer = Deploy::random_valid_multi_transfer(&mut rng, 1);
+        let transfer = Deploy::random_valid_native_transfer(&mut rng);
+        let mut appendable_block = AppendableBlock::new(deploy_config, Timestamp::now());
+
+        add(&mut appendable_block, &multi_transfer).expect("should add batch of 4 transfers");
+        assert_eq!(appendable_block.total_transfers, 4);
+
+        // A second batch of 2 would exceed the limit of 5 transfers, but a single one still fits.
+        assert!(matches!(
+            add(&mut appendable_block, &second_multi_transfer),
+            Err(AddError::TransferCount)
+        ));
+        add(&mut appendable_block, &single_multi_transfer).expect("should add single transfer");
+        assert_eq!(appendable_block.total_transfers, 5);
+        assert_eq!(appendable_block.transfers.len(), 2);
+
+        assert!(matches!(
+            add(&mut appendable_block, &transfer),
+            Err(AddError::TransferCount)
+        ));
+    }
 }
@@ -7,11 +7,13 @@ mod chainspec_raw_bytes;
 mod core_config;
 mod deploy_config;
 mod error;
+mod genesis_builder;
 mod global_state_update;
 mod highway_config;
 mod network_config;
 mod parse_toml;
 mod protocol_config;
+mod validation;
 
 use std::{fmt::Debug, path::Path, sync::Arc};
 
@@ -19,7 +21,7 @@ use datasize::DataSize;
 #[cfg(test)]
 use rand::Rng;
 use serde::Serialize;
-use tracing::{error, info, warn};
+use tracing::{error, info};
 
 use casper_execution_engine::{
     core::engine_state::{
@@ -43,10 +45,12 @@ pub use self::{
     core_config::{ConsensusProtocolName, CoreConfig, LegacyRequiredFinality},
     deploy_config::DeployConfig,
     error::Error,
+    genesis_builder::{GenesisBuilder, GenesisBuilderError},
     global_state_update::GlobalStateUpdate,
     highway_config::HighwayConfig,
     network_config::NetworkConfig,
     protocol_config::ProtocolConfig,
+    validation::ValidationError,
 };
 use crate::{components::network::generate_largest_serialized_message, utils::Loadable};
 
@@ -94,52 +98,63 @@ impl Chainspec {
     #[tracing::instrument(ret, level = "info", skip(self), fields(hash=%self.hash()))]
     pub fn is_valid(&self) -> bool {
         info!("begin chainspec validation");
+        self.protocol_config.is_valid() && validation::log_problems(&self.validate())
+    }
+
+    /// Returns every problem found in the values set in the chainspec, including those only
+    /// apparent when considering several sections together.
+    ///
+    /// The chainspec should not be used if any of the returned problems
+    /// [is fatal](ValidationError::is_fatal).
+    pub fn validate(&self) -> Vec<ValidationError> {
+        let mut problems = vec![];
+
         // Ensure the size of the largest message generated under these chainspec settings does not
         // exceed the configured message size limit.
-        let serialized = generate_largest_serialized_message(self);
+        let required_message_size = generate_largest_serialized_message(self).len()
+            + CHAINSPEC_NETWORK_MESSAGE_SAFETY_MARGIN;
+        if required_message_size > self.network_config.maximum_net_message_size as usize {
+            problems.push(ValidationError::MaximumNetMessageSizeTooSmall {
+                required: required_message_size,
+                configured: self.network_config.maximum_net_message_size,
+            });
+        }
 
-        if serialized.len() + CHAINSPEC_NETWORK_MESSAGE_SAFETY_MARGIN
-            > self.network_config.maximum_net_message_size as usize
-        {
-            warn!(calculated_length=serialized.len(), configured_maximum=self.network_config.maximum_net_message_size,
-                "config value [network][maximum_net_message_size] is too small to accomodate the maximum message size",
-            );
-            return false;
+        problems.extend(self.core_config.validate());
+
+        if self.core_config.consensus_protocol == ConsensusProtocolName::Highway {
+            if self.core_config.minimum_block_time > self.highway_config.maximum_round_length {
+                problems.push(ValidationError::MinimumBlockTimeExceedsMaximumRoundLength {
+                    minimum_block_time: self.core_config.minimum_block_time,
+                    maximum_round_length: self.highway_config.maximum_round_length,
+                });
+            }
+            problems.extend(self.highway_config.validate());
         }
 
-        if self.core_config.unbonding_delay <= self.core_config.auction_delay {
-            warn!(
-                "unbonding delay is set to {} but it should be greater than the auction delay (currently set to {})",
-                self.core_config.unbonding_delay, self.core_config.auction_delay);
-            return false;
+        problems.extend(self.deploy_config.validate());
+
+        // A native transfer is charged a fixed cost, which must fit within a block.
+        let wasmless_transfer_cost = self.system_costs_config.wasmless_transfer_cost();
+        if u64::from(wasmless_transfer_cost) > self.deploy_config.block_gas_limit {
+            problems.push(ValidationError::TransferExceedsBlockGasLimit {
+                wasmless_transfer_cost,
+                block_gas_limit: self.deploy_config.block_gas_limit,
+            });
         }
 
-        // If the era duration is set to zero, we will treat it as explicitly stating that eras
-        // should be defined by height only.
-        if self.core_config.era_duration.millis() > 0
-            && self.core_config.era_duration
-                < self.core_config.minimum_block_time * self.core_config.minimum_era_height
+        if self.wasm_config.max_memory == 0
+            || self.wasm_config.max_memory > validation::MAX_WASM_MEMORY_PAGES
         {
-            warn!("era duration is less than minimum era height * block time!");
+            problems.push(ValidationError::WasmMaxMemoryOutOfRange(
+                self.wasm_config.max_memory,
+            ));
         }
-
-        if self.core_config.consensus_protocol == ConsensusProtocolName::Highway {
-            if self.core_config.minimum_block_time > self.highway_config.maximum_round_length {
-                error!(
-                    minimum_block_time = %self.core_config.minimum_block_time,
-                    maximum_round_length = %self.highway_config.maximum_round_length,
-                    "minimum_block_time must be less or equal than maximum_round_length",
-                );
-                return false;
-            }
-            if !self.highway_config.is_valid() {
-                return false;
-            }
+        if self.wasm_config.max_stack_height == 0 {
+            problems.push(ValidationError::WasmMaxStackHeightZero);
         }
 
-        self.protocol_config.is_valid()
-            && self.core_config.is_valid()
-            && self.deploy_config.is_valid()
+        problems
     }
 
     /// Serializes `self` and hashes the resulting bytes.
@@ -501,6 +516,46 @@ mod tests {
         assert_eq!(spec.wasm_config, *EXPECTED_GENESIS_WASM_COSTS);
     }
 
+    #[test]
+    fn should_report_problems_spanning_sections() {
+        let (mut chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_resources("local");
+        assert!(chainspec.validate().is_empty());
+
+        chainspec.deploy_config.max_deploy_size = chainspec.deploy_config.max_block_size + 1;
+        chainspec.deploy_config.block_gas_limit = 1;
+        chainspec.wasm_config.max_memory = 0;
+        let problems = chainspec.validate();
+        assert_eq!(
+            problems,
+            vec![
+                ValidationError::DeployLargerThanBlock {
+                    max_deploy_size: chainspec.deploy_config.max_deploy_size,
+                    max_block_size: chainspec.deploy_config.max_block_size,
+                },
+                ValidationError::TransferExceedsBlockGasLimit {
+                    wasmless_transfer_cost: chainspec.system_costs_config.wasmless_transfer_cost(),
+                    block_gas_limit: 1,
+                },
+                ValidationError::WasmMaxMemoryOutOfRange(0),
+            ]
+        );
+        assert!(!chainspec.is_valid());
+    }
+
+    #[test]
+    fn should_only_warn_of_short_era_duration() {
+        let (mut chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_resources("local");
+        chainspec.core_config.era_duration = TimeDiff::from_millis(1);
+
+        let problems = chainspec.validate();
+        assert!(matches!(
+            problems.as_slice(),
+            [ValidationError::EraDurationTooShort { .. }]
+        ));
+        assert!(!problems[0].is_fatal());
+        assert!(chainspec.is_valid());
+    }
+
     #[ignore = "We probably need to reconsider our approach here"]
     #[test]
     fn check_bundled_spec() {
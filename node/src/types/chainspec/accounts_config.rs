@@ -92,6 +92,13 @@ impl AccountsConfig {
         }
     }
 
+    /// Encodes `self` in the format of accounts.toml.
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        // Going via a `toml::Value` ensures plain values are written ahead of tables, which the
+        // format requires, however the fields are ordered.
+        toml::to_string_pretty(&toml::Value::try_from(self)?)
+    }
+
     /// Returns `Self` and the raw bytes of the file.
     ///
     /// If the file doesn't exist, returns `Ok` with an empty `AccountsConfig` and `None` bytes.
@@ -199,4 +206,13 @@ mod tests {
         let accounts_config = AccountsConfig::random(&mut rng);
         bytesrepr::test_serialization_roundtrip(&accounts_config);
     }
+
+    #[test]
+    fn toml_roundtrip() {
+        let (accounts_config, _) =
+            AccountsConfig::from_dir(crate::utils::RESOURCES_PATH.join("local")).unwrap();
+        let encoded = accounts_config.to_toml().unwrap();
+        let decoded: AccountsConfig = toml::from_str(&encoded).unwrap();
+        assert_eq!(accounts_config, decoded);
+    }
 }
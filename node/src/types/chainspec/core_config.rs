@@ -1,8 +1,8 @@
 use std::collections::BTreeSet;
 
-use tracing::{error, warn};
-
-use casper_execution_engine::core::engine_state::engine_config::{FeeHandling, RefundHandling};
+use casper_execution_engine::core::engine_state::engine_config::{
+    FeeHandling, RefundHandling, DEFAULT_VALIDATOR_EVICTION_THRESHOLD,
+};
 #[cfg(test)]
 use casper_types::testing::TestRng;
 use casper_types::{
@@ -24,6 +24,8 @@ use serde::{
 
 use casper_types::{system::auction::VESTING_SCHEDULE_LENGTH_MILLIS, ProtocolVersion, TimeDiff};
 
+use super::validation::{self, ValidationError};
+
 /// Configuration values associated with the core protocol.
 #[derive(Clone, DataSize, PartialEq, Eq, Serialize, Deserialize, Debug)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
@@ -87,6 +89,11 @@ pub struct CoreConfig {
     /// Enables strict arguments checking when calling a contract.
     pub strict_argument_checking: bool,
 
+    /// Enables checking that the entry point targeted by a stored contract call exists before
+    /// executing the deploy.
+    #[serde(default)]
+    pub entry_point_precheck: bool,
+
     /// How many peers to simultaneously ask when sync leaping.
     pub simultaneous_peer_requests: u8,
 
@@ -102,6 +109,27 @@ pub struct CoreConfig {
     pub(crate) allow_auction_bids: bool,
     /// Allows unrestricted transfers between users.
     pub(crate) allow_unrestricted_transfers: bool,
+    /// The minimum balance, in motes, an account's main purse must retain after a transfer made
+    /// from it during session execution (0 means no minimum).
+    #[serde(default)]
+    pub(crate) minimum_remaining_balance: u64,
+    /// The number of consecutive eras a validator must be inactive for before its bid is
+    /// deactivated (0 means validators are never evicted).
+    #[serde(default = "default_validator_eviction_threshold")]
+    pub(crate) validator_eviction_threshold: u64,
+    /// Omits writes which leave a value unchanged from execution effects, and coalesces
+    /// consecutive writes to the same key.  If `false`, every write is recorded.
+    #[serde(default)]
+    pub(crate) deduplicate_writes: bool,
+    /// Records a deploy whose execution hits an internal error as failed, charged as for an error
+    /// in its payment code, rather than aborting execution of the block.  If `false`, such an
+    /// error fails the whole block.
+    #[serde(default)]
+    pub(crate) isolate_deploy_failures: bool,
+    /// Records the code identifying the error of each failed deploy in the execution results of
+    /// a block.  If `false`, only the error message is recorded.
+    #[serde(default)]
+    pub(crate) record_execution_error_codes: bool,
     /// If set to false then consensus doesn't compute rewards and always uses 0.
     pub(crate) compute_rewards: bool,
     /// Administrative accounts are valid option for a private chain only.
@@ -124,47 +152,59 @@ impl CoreConfig {
     /// Returns `false` if unbonding delay is not greater than auction delay to ensure
     /// that `recent_era_count()` yields a value of at least 1.
     pub fn is_valid(&self) -> bool {
+        validation::log_problems(&self.validate())
+    }
+
+    /// Returns the problems found in the values set in the config.
+    pub(super) fn validate(&self) -> Vec<ValidationError> {
+        let mut problems = vec![];
+
         if self.unbonding_delay <= self.auction_delay {
-            warn!(
-                unbonding_delay = self.unbonding_delay,
-                auction_delay = self.auction_delay,
-                "unbonding delay should be greater than auction delay",
-            );
-            return false;
+            problems.push(ValidationError::UnbondingDelayNotAfterAuctionDelay {
+                unbonding_delay: self.unbonding_delay,
+                auction_delay: self.auction_delay,
+            });
         }
 
         // If the era duration is set to zero, we will treat it as explicitly stating that eras
         // should be defined by height only.  Warn only.
         if self.era_duration.millis() > 0
             && self.era_duration.millis()
-                < self.minimum_era_height * self.minimum_block_time.millis()
+                < self
+                    .minimum_era_height
+                    .saturating_mul(self.minimum_block_time.millis())
         {
-            warn!("era duration is less than minimum era height * round length!");
+            problems.push(ValidationError::EraDurationTooShort {
+                era_duration: self.era_duration,
+                minimum_era_height: self.minimum_era_height,
+                minimum_block_time: self.minimum_block_time,
+            });
         }
 
         if self.finality_threshold_fraction <= Ratio::new(0, 1)
             || self.finality_threshold_fraction >= Ratio::new(1, 1)
         {
-            error!(
-                ftf = %self.finality_threshold_fraction,
-                "finality threshold fraction is not in the range (0, 1)",
-            );
-            return false;
+            problems.push(ValidationError::FinalityThresholdFractionOutOfRange(
+                self.finality_threshold_fraction,
+            ));
         }
 
-        if self.vesting_schedule_period > TimeDiff::from_millis(VESTING_SCHEDULE_LENGTH_MILLIS) {
-            error!(
-                vesting_schedule_millis = self.vesting_schedule_period.millis(),
-                max_millis = VESTING_SCHEDULE_LENGTH_MILLIS,
-                "vesting schedule period too long",
-            );
-            return false;
+        let maximum_vesting_schedule_period = TimeDiff::from_millis(VESTING_SCHEDULE_LENGTH_MILLIS);
+        if self.vesting_schedule_period > maximum_vesting_schedule_period {
+            problems.push(ValidationError::VestingSchedulePeriodTooLong {
+                vesting_schedule_period: self.vesting_schedule_period,
+                maximum: maximum_vesting_schedule_period,
+            });
         }
 
-        true
+        problems
     }
 }
 
+fn default_validator_eviction_threshold() -> u64 {
+    DEFAULT_VALIDATOR_EVICTION_THRESHOLD
+}
+
 #[cfg(test)]
 impl CoreConfig {
     /// Generates a random instance using a `TestRng`.
@@ -190,10 +230,16 @@ impl CoreConfig {
         let minimum_delegation_amount = rng.gen::<u32>() as u64;
         let prune_batch_size = rng.gen_range(0..100);
         let strict_argument_checking = rng.gen();
+        let entry_point_precheck = rng.gen();
         let simultaneous_peer_requests = rng.gen_range(3..100);
         let consensus_protocol = rng.gen();
         let allow_auction_bids = rng.gen();
         let allow_unrestricted_transfers = rng.gen();
+        let minimum_remaining_balance = rng.gen::<u32>() as u64;
+        let validator_eviction_threshold = rng.gen_range(0..10);
+        let deduplicate_writes = rng.gen();
+        let isolate_deploy_failures = rng.gen();
+        let record_execution_error_codes = rng.gen();
         let compute_rewards = rng.gen();
         let administrators = (0..rng.gen_range(0..=10u32))
             .map(|_| PublicKey::random(rng))
@@ -228,12 +274,18 @@ impl CoreConfig {
             minimum_delegation_amount,
             prune_batch_size,
             strict_argument_checking,
+            entry_point_precheck,
             simultaneous_peer_requests,
             consensus_protocol,
             max_delegators_per_validator: 0,
             allow_auction_bids,
             administrators,
             allow_unrestricted_transfers,
+            minimum_remaining_balance,
+            validator_eviction_threshold,
+            deduplicate_writes,
+            isolate_deploy_failures,
+            record_execution_error_codes,
             compute_rewards,
             refund_handling,
             fee_handling,
@@ -273,6 +325,12 @@ impl ToBytes for CoreConfig {
         buffer.extend(self.administrators.to_bytes()?);
         buffer.extend(self.refund_handling.to_bytes()?);
         buffer.extend(self.fee_handling.to_bytes()?);
+        buffer.extend(self.entry_point_precheck.to_bytes()?);
+        buffer.extend(self.minimum_remaining_balance.to_bytes()?);
+        buffer.extend(self.validator_eviction_threshold.to_bytes()?);
+        buffer.extend(self.deduplicate_writes.to_bytes()?);
+        buffer.extend(self.isolate_deploy_failures.to_bytes()?);
+        buffer.extend(self.record_execution_error_codes.to_bytes()?);
         Ok(buffer)
     }
 
@@ -305,6 +363,12 @@ impl ToBytes for CoreConfig {
             + self.administrators.serialized_length()
             + self.refund_handling.serialized_length()
             + self.fee_handling.serialized_length()
+            + self.entry_point_precheck.serialized_length()
+            + self.minimum_remaining_balance.serialized_length()
+            + self.validator_eviction_threshold.serialized_length()
+            + self.deduplicate_writes.serialized_length()
+            + self.isolate_deploy_failures.serialized_length()
+            + self.record_execution_error_codes.serialized_length()
     }
 }
 
@@ -337,6 +401,12 @@ impl FromBytes for CoreConfig {
         let (administrative_accounts, remainder) = FromBytes::from_bytes(remainder)?;
         let (refund_handling, remainder) = FromBytes::from_bytes(remainder)?;
         let (fee_handling, remainder) = FromBytes::from_bytes(remainder)?;
+        let (entry_point_precheck, remainder) = bool::from_bytes(remainder)?;
+        let (minimum_remaining_balance, remainder) = u64::from_bytes(remainder)?;
+        let (validator_eviction_threshold, remainder) = u64::from_bytes(remainder)?;
+        let (deduplicate_writes, remainder) = bool::from_bytes(remainder)?;
+        let (isolate_deploy_failures, remainder) = bool::from_bytes(remainder)?;
+        let (record_execution_error_codes, remainder) = bool::from_bytes(remainder)?;
         let config = CoreConfig {
             era_duration,
             minimum_era_height,
@@ -355,11 +425,17 @@ impl FromBytes for CoreConfig {
             minimum_delegation_amount,
             prune_batch_size,
             strict_argument_checking,
+            entry_point_precheck,
             simultaneous_peer_requests,
             consensus_protocol,
             max_delegators_per_validator,
             allow_auction_bids,
             allow_unrestricted_transfers,
+            minimum_remaining_balance,
+            validator_eviction_threshold,
+            deduplicate_writes,
+            isolate_deploy_failures,
+            record_execution_error_codes,
             compute_rewards,
             administrators: administrative_accounts,
             refund_handling,
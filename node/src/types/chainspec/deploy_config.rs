@@ -17,6 +17,8 @@ use casper_types::{
     Motes, TimeDiff, U512,
 };
 
+use super::validation::{self, ValidationError};
+
 /// Configuration values associated with deploys.
 #[derive(Copy, Clone, DataSize, PartialEq, Eq, Serialize, Deserialize, Debug)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
@@ -40,16 +42,33 @@ pub struct DeployConfig {
 impl DeployConfig {
     /// Validates `DeployConfig` parameters
     pub fn is_valid(&self) -> bool {
+        validation::log_problems(&self.validate())
+    }
+
+    /// Returns the problems found in the values set in the config.
+    pub(super) fn validate(&self) -> Vec<ValidationError> {
+        let mut problems = vec![];
+
         // the total number of deploys + transfers should not exceed the number of approvals because
         // each deploy or transfer needs at least one approval to be valid
-        if let Some(total_deploy_and_transfer_slots) = self
-            .block_max_deploy_count
-            .checked_add(self.block_max_transfer_count)
-        {
-            self.block_max_approval_count >= total_deploy_and_transfer_slots
-        } else {
-            false
+        let total_deploy_and_transfer_slots =
+            u64::from(self.block_max_deploy_count) + u64::from(self.block_max_transfer_count);
+        if u64::from(self.block_max_approval_count) < total_deploy_and_transfer_slots {
+            problems.push(ValidationError::TooFewApprovalsPerBlock {
+                block_max_approval_count: self.block_max_approval_count,
+                block_max_deploy_count: self.block_max_deploy_count,
+                block_max_transfer_count: self.block_max_transfer_count,
+            });
         }
+
+        if self.max_deploy_size > self.max_block_size {
+            problems.push(ValidationError::DeployLargerThanBlock {
+                max_deploy_size: self.max_deploy_size,
+                max_block_size: self.max_block_size,
+            });
+        }
+
+        problems
     }
 }
 
@@ -0,0 +1,238 @@
+//! A builder of the accounts set up at genesis, for creating the accounts.toml of a new network.
+
+use std::collections::BTreeSet;
+
+use thiserror::Error;
+
+use casper_execution_engine::core::engine_state::genesis::AdministratorAccount;
+use casper_types::{system::auction::DelegationRate, Motes, PublicKey};
+
+use super::{AccountConfig, AccountsConfig, DelegatorConfig, ValidatorConfig};
+
+/// Error returned by [`GenesisBuilder::build`] if the accounts declared don't form a valid genesis.
+#[derive(Debug, Error)]
+pub enum GenesisBuilderError {
+    /// The same public key was declared more than once.
+    #[error("account {0} is declared more than once")]
+    DuplicateAccount(Box<PublicKey>),
+
+    /// A validator was declared with nothing bonded.
+    #[error("validator {0} has a bonded amount of zero")]
+    ZeroBondedAmount(Box<PublicKey>),
+
+    /// A delegator delegates to a key not declared as a validator.
+    #[error("delegator {delegator} delegates to {validator}, which is not a genesis validator")]
+    UnknownValidator {
+        /// The delegator's public key.
+        delegator: Box<PublicKey>,
+        /// The public key delegated to.
+        validator: Box<PublicKey>,
+    },
+
+    /// No validators were declared, so the network could never produce a block.
+    #[error("no genesis validators declared")]
+    NoValidators,
+}
+
+/// Builds the [`AccountsConfig`] of a new network from declarations of its validators,
+/// delegators, regular accounts and administrators.
+///
+/// The resulting config can be written out as accounts.toml via [`AccountsConfig::to_toml`].
+#[derive(Default, Debug)]
+pub struct GenesisBuilder {
+    accounts: Vec<AccountConfig>,
+    delegators: Vec<DelegatorConfig>,
+    administrators: Vec<AdministratorAccount>,
+}
+
+impl GenesisBuilder {
+    /// Returns a new builder without any accounts declared.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares a regular account with the given balance.
+    pub fn with_account(mut self, public_key: PublicKey, balance: Motes) -> Self {
+        self.accounts
+            .push(AccountConfig::new(public_key, balance, None));
+        self
+    }
+
+    /// Declares a genesis validator with the given balance, bonding `bonded_amount`.
+    pub fn with_validator(
+        mut self,
+        public_key: PublicKey,
+        balance: Motes,
+        bonded_amount: Motes,
+        delegation_rate: DelegationRate,
+    ) -> Self {
+        let validator = ValidatorConfig::new(bonded_amount, delegation_rate);
+        self.accounts
+            .push(AccountConfig::new(public_key, balance, Some(validator)));
+        self
+    }
+
+    /// Declares a delegator with the given balance, delegating `delegated_amount` to the genesis
+    /// validator `validator_public_key`.
+    pub fn with_delegator(
+        mut self,
+        validator_public_key: PublicKey,
+        delegator_public_key: PublicKey,
+        balance: Motes,
+        delegated_amount: Motes,
+    ) -> Self {
+        self.delegators.push(DelegatorConfig::new(
+            validator_public_key,
+            delegator_public_key,
+            balance,
+            delegated_amount,
+        ));
+        self
+    }
+
+    /// Declares an administrator account with the given balance, for use on private chains.
+    pub fn with_administrator(mut self, public_key: PublicKey, balance: Motes) -> Self {
+        self.administrators
+            .push(AdministratorAccount::new(public_key, balance));
+        self
+    }
+
+    /// Checks the declared accounts and returns them as an `AccountsConfig`.
+    pub fn build(self) -> Result<AccountsConfig, GenesisBuilderError> {
+        let mut public_keys = BTreeSet::new();
+        let declared_keys = self
+            .accounts
+            .iter()
+            .map(|account| &account.public_key)
+            .chain(
+                self.delegators
+                    .iter()
+                    .map(|delegator| &delegator.delegator_public_key),
+            )
+            .chain(
+                self.administrators
+                    .iter()
+                    .map(|administrator| administrator.public_key()),
+            );
+        for public_key in declared_keys {
+            if !public_keys.insert(public_key) {
+                return Err(GenesisBuilderError::DuplicateAccount(Box::new(
+                    public_key.clone(),
+                )));
+            }
+        }
+
+        let mut validators = BTreeSet::new();
+        for account in &self.accounts {
+            if let Some(validator) = &account.validator {
+                if validator.bonded_amount().value().is_zero() {
+                    return Err(GenesisBuilderError::ZeroBondedAmount(Box::new(
+                        account.public_key.clone(),
+                    )));
+                }
+                validators.insert(&account.public_key);
+            }
+        }
+        if validators.is_empty() {
+            return Err(GenesisBuilderError::NoValidators);
+        }
+
+        if let Some(delegator) = self
+            .delegators
+            .iter()
+            .find(|delegator| !validators.contains(&delegator.validator_public_key))
+        {
+            return Err(GenesisBuilderError::UnknownValidator {
+                delegator: Box::new(delegator.delegator_public_key.clone()),
+                validator: Box::new(delegator.validator_public_key.clone()),
+            });
+        }
+
+        let GenesisBuilder {
+            mut accounts,
+            mut delegators,
+            mut administrators,
+        } = self;
+        // Sort as when read from accounts.toml, so that a config round-trips unchanged.
+        accounts.sort_unstable();
+        delegators.sort_unstable();
+        administrators.sort_unstable();
+        Ok(AccountsConfig::new(accounts, delegators, administrators))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{testing::TestRng, U512};
+
+    use super::*;
+
+    fn motes(amount: u64) -> Motes {
+        Motes::new(U512::from(amount))
+    }
+
+    #[test]
+    fn toml_roundtrip() {
+        let mut rng = TestRng::new();
+        let validator_1 = PublicKey::random(&mut rng);
+        let validator_2 = PublicKey::random(&mut rng);
+
+        let accounts_config = GenesisBuilder::new()
+            .with_validator(validator_1.clone(), motes(1_000), motes(500), 10)
+            .with_validator(validator_2.clone(), motes(2_000), motes(700), 0)
+            .with_account(PublicKey::random(&mut rng), motes(3_000))
+            .with_delegator(
+                validator_1,
+                PublicKey::random(&mut rng),
+                motes(100),
+                motes(50),
+            )
+            .with_delegator(
+                validator_2,
+                PublicKey::random(&mut rng),
+                motes(200),
+                motes(60),
+            )
+            .with_administrator(PublicKey::random(&mut rng), motes(4_000))
+            .build()
+            .unwrap();
+
+        let encoded = accounts_config.to_toml().unwrap();
+        let decoded: AccountsConfig = toml::from_str(&encoded).unwrap();
+        assert_eq!(accounts_config, decoded);
+    }
+
+    #[test]
+    fn should_reject_invalid_declarations() {
+        let mut rng = TestRng::new();
+        let validator = PublicKey::random(&mut rng);
+        let other = PublicKey::random(&mut rng);
+
+        assert!(matches!(
+            GenesisBuilder::new()
+                .with_validator(validator.clone(), motes(1), motes(1), 0)
+                .with_account(validator.clone(), motes(1))
+                .build(),
+            Err(GenesisBuilderError::DuplicateAccount(_))
+        ));
+        assert!(matches!(
+            GenesisBuilder::new()
+                .with_validator(validator.clone(), motes(1), motes(0), 0)
+                .build(),
+            Err(GenesisBuilderError::ZeroBondedAmount(_))
+        ));
+        assert!(matches!(
+            GenesisBuilder::new()
+                .with_account(validator.clone(), motes(1))
+                .build(),
+            Err(GenesisBuilderError::NoValidators)
+        ));
+        assert!(matches!(
+            GenesisBuilder::new()
+                .with_validator(validator.clone(), motes(1), motes(1), 0)
+                .with_delegator(other, PublicKey::random(&mut rng), motes(1), motes(1))
+                .build(),
+            Err(GenesisBuilderError::UnknownValidator { .. })
+        ));
+    }
+}
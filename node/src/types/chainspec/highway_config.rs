@@ -3,7 +3,6 @@ use num::rational::Ratio;
 #[cfg(test)]
 use rand::Rng;
 use serde::{Deserialize, Serialize};
-use tracing::error;
 
 #[cfg(test)]
 use casper_types::testing::TestRng;
@@ -12,6 +11,8 @@ use casper_types::{
     TimeDiff,
 };
 
+use super::validation::{self, ValidationError};
+
 /// Configuration values relevant to Highway consensus.
 #[derive(Copy, Clone, DataSize, PartialEq, Eq, Serialize, Deserialize, Debug)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
@@ -28,15 +29,18 @@ pub struct HighwayConfig {
 impl HighwayConfig {
     /// Checks whether the values set in the config make sense and returns `false` if they don't.
     pub fn is_valid(&self) -> bool {
+        validation::log_problems(&self.validate())
+    }
+
+    /// Returns the problems found in the values set in the config.
+    pub(super) fn validate(&self) -> Vec<ValidationError> {
+        let mut problems = vec![];
         if self.reduced_reward_multiplier > Ratio::new(1, 1) {
-            error!(
-                rrm = %self.reduced_reward_multiplier,
-                "reduced reward multiplier is not in the range [0, 1]",
-            );
-            return false;
+            problems.push(ValidationError::ReducedRewardMultiplierOutOfRange(
+                self.reduced_reward_multiplier,
+            ));
         }
-
-        true
+        problems
     }
 }
 
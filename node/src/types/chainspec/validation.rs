@@ -0,0 +1,173 @@
+//! Validation of the values set in a chainspec, including checks spanning several sections.
+
+use num::rational::Ratio;
+use thiserror::Error;
+use tracing::{error, warn};
+
+use casper_types::TimeDiff;
+
+/// The maximum number of 64kB Wasm pages addressable by a 32-bit Wasm module.
+pub(super) const MAX_WASM_MEMORY_PAGES: u32 = 65_536;
+
+/// A problem found in the values set in a chainspec.
+///
+/// Each message names the offending chainspec entries and how to fix them.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum ValidationError {
+    /// The largest message generated under the chainspec doesn't fit in a network message.
+    #[error(
+        "[network].maximum_net_message_size is {configured} bytes but the largest message \
+        generated under this chainspec needs {required} bytes; increase it to at least {required}"
+    )]
+    MaximumNetMessageSizeTooSmall {
+        /// The size required, including a safety margin.
+        required: usize,
+        /// The configured maximum size.
+        configured: u32,
+    },
+
+    /// The unbonding delay is not greater than the auction delay.
+    #[error(
+        "[core].unbonding_delay is {unbonding_delay} eras but must be greater than \
+        [core].auction_delay ({auction_delay} eras); increase unbonding_delay or decrease \
+        auction_delay"
+    )]
+    UnbondingDelayNotAfterAuctionDelay {
+        /// The configured unbonding delay.
+        unbonding_delay: u64,
+        /// The configured auction delay.
+        auction_delay: u64,
+    },
+
+    /// Eras can't be as short as the era duration, given the minimum era height and block time.
+    ///
+    /// This is not fatal: eras will simply last longer than the era duration.
+    #[error(
+        "[core].era_duration is {era_duration} but [core].minimum_era_height \
+        ({minimum_era_height}) blocks at [core].minimum_block_time ({minimum_block_time}) take \
+        longer; increase era_duration, or set it to 0 to define eras by height only"
+    )]
+    EraDurationTooShort {
+        /// The configured era duration.
+        era_duration: TimeDiff,
+        /// The configured minimum era height.
+        minimum_era_height: u64,
+        /// The configured minimum block time.
+        minimum_block_time: TimeDiff,
+    },
+
+    /// The finality threshold fraction is not in the range (0, 1).
+    #[error(
+        "[core].finality_threshold_fraction is {0} but must be greater than 0 and less than 1"
+    )]
+    FinalityThresholdFractionOutOfRange(Ratio<u64>),
+
+    /// The vesting schedule period is longer than the vesting schedule supports.
+    #[error(
+        "[core].vesting_schedule_period is {vesting_schedule_period} but must be at most \
+        {maximum}"
+    )]
+    VestingSchedulePeriodTooLong {
+        /// The configured vesting schedule period.
+        vesting_schedule_period: TimeDiff,
+        /// The longest supported vesting schedule period.
+        maximum: TimeDiff,
+    },
+
+    /// The Highway reduced reward multiplier is not in the range [0, 1].
+    #[error("[highway].reduced_reward_multiplier is {0} but must be between 0 and 1 inclusive")]
+    ReducedRewardMultiplierOutOfRange(Ratio<u64>),
+
+    /// Under Highway, the minimum block time exceeds the maximum round length.
+    #[error(
+        "[core].minimum_block_time is {minimum_block_time} but must be at most \
+        [highway].maximum_round_length ({maximum_round_length}); decrease minimum_block_time or \
+        increase maximum_round_length"
+    )]
+    MinimumBlockTimeExceedsMaximumRoundLength {
+        /// The configured minimum block time.
+        minimum_block_time: TimeDiff,
+        /// The configured maximum round length.
+        maximum_round_length: TimeDiff,
+    },
+
+    /// A block can hold more deploys and transfers than approvals, though each needs at least one.
+    #[error(
+        "[deploys].block_max_approval_count is {block_max_approval_count} but each of the up to \
+        {block_max_deploy_count} deploys and {block_max_transfer_count} transfers in a block needs \
+        at least one approval; increase block_max_approval_count to at least {}",
+        total_slots(.block_max_deploy_count, .block_max_transfer_count)
+    )]
+    TooFewApprovalsPerBlock {
+        /// The configured maximum number of approvals per block.
+        block_max_approval_count: u32,
+        /// The configured maximum number of deploys per block.
+        block_max_deploy_count: u32,
+        /// The configured maximum number of transfers per block.
+        block_max_transfer_count: u32,
+    },
+
+    /// A deploy of the maximum size wouldn't fit in a block.
+    #[error(
+        "[deploys].max_deploy_size is {max_deploy_size} bytes but blocks hold at most \
+        [deploys].max_block_size ({max_block_size}) bytes; decrease max_deploy_size or increase \
+        max_block_size"
+    )]
+    DeployLargerThanBlock {
+        /// The configured maximum deploy size.
+        max_deploy_size: u32,
+        /// The configured maximum block size.
+        max_block_size: u32,
+    },
+
+    /// A single native transfer would exceed the block gas limit.
+    #[error(
+        "[system_costs].wasmless_transfer_cost is {wasmless_transfer_cost} but \
+        [deploys].block_gas_limit is only {block_gas_limit}, so no transfer fits in a block; \
+        increase block_gas_limit or decrease wasmless_transfer_cost"
+    )]
+    TransferExceedsBlockGasLimit {
+        /// The configured cost of a native transfer.
+        wasmless_transfer_cost: u32,
+        /// The configured block gas limit.
+        block_gas_limit: u64,
+    },
+
+    /// The Wasm memory limit is zero or beyond what a 32-bit Wasm module can address.
+    #[error(
+        "[wasm].max_memory is {0} pages but must be between 1 and {} pages of 64kB",
+        MAX_WASM_MEMORY_PAGES
+    )]
+    WasmMaxMemoryOutOfRange(u32),
+
+    /// The Wasm stack height limit is zero, so no contract could run.
+    #[error("[wasm].max_stack_height is 0 but must be at least 1")]
+    WasmMaxStackHeightZero,
+}
+
+impl ValidationError {
+    /// Returns `true` if the chainspec can't be used with this problem, or `false` if it is merely
+    /// questionable.
+    pub fn is_fatal(&self) -> bool {
+        !matches!(self, ValidationError::EraDurationTooShort { .. })
+    }
+}
+
+/// Returns the number of deploys and transfers a block can hold.
+fn total_slots(block_max_deploy_count: &u32, block_max_transfer_count: &u32) -> u64 {
+    u64::from(*block_max_deploy_count) + u64::from(*block_max_transfer_count)
+}
+
+/// Logs each of the given problems, returning `true` if none of them is fatal.
+pub(super) fn log_problems(problems: &[ValidationError]) -> bool {
+    let mut is_valid = true;
+    for problem in problems {
+        if problem.is_fatal() {
+            error!(%problem, "invalid chainspec");
+            is_valid = false;
+        } else {
+            warn!(%problem, "questionable chainspec");
+        }
+    }
+    is_valid
+}
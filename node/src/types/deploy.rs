@@ -246,11 +246,13 @@ impl Deploy {
         };
         let size_estimate = self.serialized_length();
         let is_transfer = self.session.is_transfer();
+        let transfer_count = self.session.transfer_args().len();
         Ok(DeployFootprint {
             header,
             gas_estimate,
             size_estimate,
             is_transfer,
+            transfer_count,
         })
     }
 
@@ -375,7 +377,10 @@ impl Deploy {
             });
         }
 
-        let session_args_length = self.session().args().serialized_length();
+        let session_args_length = match self.session() {
+            ExecutableDeployItem::MultiTransfer { transfers } => transfers.serialized_length(),
+            session => session.args().serialized_length(),
+        };
         if session_args_length > config.session_args_max_length as usize {
             debug!(
                 session_args_length,
@@ -389,32 +394,48 @@ impl Deploy {
         }
 
         if self.session().is_transfer() {
-            let item = self.session().clone();
-            let attempted = item
-                .args()
-                .get(ARG_AMOUNT)
-                .ok_or_else(|| {
-                    debug!("missing transfer 'amount' runtime argument");
-                    DeployConfigurationFailure::MissingTransferAmount
-                })?
-                .clone()
-                .into_t::<U512>()
-                .map_err(|_| {
-                    debug!("failed to parse transfer 'amount' runtime argument as a U512");
-                    DeployConfigurationFailure::FailedToParseTransferAmount
-                })?;
-            let minimum = U512::from(config.native_transfer_minimum_motes);
-            if attempted < minimum {
+            let transfers = self.session().transfer_args();
+            if transfers.is_empty() {
+                debug!("multi-transfer has no transfers");
+                return Err(DeployConfigurationFailure::EmptyMultiTransfer);
+            }
+            if transfers.len() > config.block_max_transfer_count as usize {
                 debug!(
-                    minimum = %config.native_transfer_minimum_motes,
-                    amount = %attempted,
-                    "insufficient transfer amount"
+                    transfer_count = transfers.len(),
+                    block_max_transfer_count = config.block_max_transfer_count,
+                    "multi-transfer exceeds block transfer limit"
                 );
-                return Err(DeployConfigurationFailure::InsufficientTransferAmount {
-                    minimum: Box::new(minimum),
-                    attempted: Box::new(attempted),
+                return Err(DeployConfigurationFailure::ExcessiveMultiTransfer {
+                    max_transfer_count: config.block_max_transfer_count,
+                    got: transfers.len(),
                 });
             }
+            for args in transfers {
+                let attempted = args
+                    .get(ARG_AMOUNT)
+                    .ok_or_else(|| {
+                        debug!("missing transfer 'amount' runtime argument");
+                        DeployConfigurationFailure::MissingTransferAmount
+                    })?
+                    .clone()
+                    .into_t::<U512>()
+                    .map_err(|_| {
+                        debug!("failed to parse transfer 'amount' runtime argument as a U512");
+                        DeployConfigurationFailure::FailedToParseTransferAmount
+                    })?;
+                let minimum = U512::from(config.native_transfer_minimum_motes);
+                if attempted < minimum {
+                    debug!(
+                        minimum = %config.native_transfer_minimum_motes,
+                        amount = %attempted,
+                        "insufficient transfer amount"
+                    );
+                    return Err(DeployConfigurationFailure::InsufficientTransferAmount {
+                        minimum: Box::new(minimum),
+                        attempted: Box::new(attempted),
+                    });
+                }
+            }
         }
 
         Ok(())
@@ -786,6 +807,32 @@ impl Deploy {
         )
     }
 
+    /// Returns a random deploy for a batch of `transfer_count` native transfers.
+    pub(crate) fn random_valid_multi_transfer(rng: &mut TestRng, transfer_count: usize) -> Self {
+        let deploy = Self::random_valid_native_transfer(rng);
+        let transfers = (0..transfer_count)
+            .map(|_| {
+                runtime_args! {
+                    "amount" => *MAX_PAYMENT,
+                    "target" => PublicKey::random(rng).to_account_hash(),
+                }
+            })
+            .collect();
+        let session = ExecutableDeployItem::MultiTransfer { transfers };
+        let secret_key = SecretKey::random(rng);
+        Deploy::new(
+            deploy.header.timestamp(),
+            deploy.header.ttl(),
+            deploy.header.gas_price(),
+            vec![],
+            deploy.header.chain_name().to_string(),
+            deploy.payment,
+            session,
+            &secret_key,
+            None,
+        )
+    }
+
     /// Returns a random invalid deploy without a payment amount specified.
     pub(crate) fn random_without_payment_amount(rng: &mut TestRng) -> Self {
         let payment = ExecutableDeployItem::ModuleBytes {
@@ -1772,4 +1819,45 @@ mod tests {
             )
         )
     }
+
+    #[test]
+    fn not_acceptable_due_to_excessive_multi_transfer() {
+        let mut rng = crate::new_rng();
+        let chain_name = "net-1";
+        let deploy_config = DeployConfig {
+            block_max_transfer_count: 2,
+            ..DeployConfig::default()
+        };
+        let mut deploy = create_deploy(
+            &mut rng,
+            deploy_config.max_ttl,
+            deploy_config.max_dependencies as usize,
+            chain_name,
+        );
+
+        let transfers = (0..3)
+            .map(|_| {
+                runtime_args! {
+                    "amount" => U512::from(deploy_config.native_transfer_minimum_motes),
+                    "target" => PublicKey::random(&mut rng).to_account_hash(),
+                }
+            })
+            .collect();
+        deploy.session = ExecutableDeployItem::MultiTransfer { transfers };
+
+        let current_timestamp = deploy.header().timestamp();
+        assert_eq!(
+            Err(DeployConfigurationFailure::ExcessiveMultiTransfer {
+                max_transfer_count: 2,
+                got: 3,
+            }),
+            deploy.is_config_compliant(
+                chain_name,
+                &deploy_config,
+                DEFAULT_MAX_ASSOCIATED_KEYS,
+                TimeDiff::default(),
+                current_timestamp
+            )
+        )
+    }
 }
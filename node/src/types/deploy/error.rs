@@ -127,6 +127,19 @@ pub enum DeployConfigurationFailure {
         attempted: Box<U512>,
     },
 
+    /// A multi-transfer deploy without any transfers.
+    #[error("multi-transfer has no transfers")]
+    EmptyMultiTransfer,
+
+    /// A multi-transfer deploy with more transfers than fit in a block.
+    #[error("multi-transfer of {got} transfers exceeds block limit of {max_transfer_count}")]
+    ExcessiveMultiTransfer {
+        /// The maximum number of transfers in a block.
+        max_transfer_count: u32,
+        /// The number of transfers in the multi-transfer.
+        got: usize,
+    },
+
     /// The amount of approvals on the deploy exceeds the max_associated_keys limit.
     #[error("number of associated keys {got} exceeds the maximum {max_associated_keys}")]
     ExcessiveApprovals {
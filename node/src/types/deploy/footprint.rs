@@ -12,4 +12,7 @@ pub(crate) struct Footprint {
     pub(crate) gas_estimate: Gas,
     pub(crate) size_estimate: usize,
     pub(crate) is_transfer: bool,
+    /// The number of native transfers made by the deploy, each of which counts against the block's
+    /// transfer limit; more than one if the deploy is a batch of transfers.
+    pub(crate) transfer_count: usize,
 }
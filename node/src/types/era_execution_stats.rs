@@ -0,0 +1,186 @@
+// TODO - remove once schemars stops causing warning.
+#![allow(clippy::field_reassign_with_default)]
+
+use std::fmt::{self, Display, Formatter};
+
+use datasize::DataSize;
+use once_cell::sync::Lazy;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use casper_types::{EraId, ExecutionResult, TimeDiff, U512};
+
+use crate::{
+    rpcs::docs::DocExample,
+    types::{Block, DeployHash, DeployHeader},
+};
+
+static ERA_EXECUTION_STATS: Lazy<EraExecutionStats> = Lazy::new(|| EraExecutionStats {
+    era_id: EraId::new(42),
+    highest_block_height: 1_022,
+    is_complete: true,
+    block_count: 23,
+    deploy_count: 118,
+    transfer_count: 2_051,
+    failed_count: 7,
+    total_gas: U512::from(1_234_567_890_000u64),
+    total_execution_time: TimeDiff::from_millis(9_200),
+    average_block_execution_time: TimeDiff::from_millis(400),
+});
+
+/// Aggregate statistics of the blocks of an era executed by this node.
+///
+/// The statistics are updated as each block is executed, and are complete once the era's switch
+/// block has been executed.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema, DataSize)]
+#[serde(deny_unknown_fields)]
+pub struct EraExecutionStats {
+    /// The era the statistics relate to.
+    era_id: EraId,
+    /// The height of the highest block included in the statistics.
+    highest_block_height: u64,
+    /// Whether the era's switch block is included, i.e. whether the statistics are final.
+    is_complete: bool,
+    /// The number of blocks executed.
+    block_count: u64,
+    /// The number of non-transfer deploys executed.
+    deploy_count: u64,
+    /// The number of transfers executed.
+    transfer_count: u64,
+    /// The number of deploys and transfers whose execution failed.
+    failed_count: u64,
+    /// The total gas consumed by the deploys and transfers.
+    total_gas: U512,
+    /// The total time spent executing the deploys and transfers of the blocks.
+    total_execution_time: TimeDiff,
+    /// The average time spent executing the deploys and transfers of a block.
+    average_block_execution_time: TimeDiff,
+}
+
+impl EraExecutionStats {
+    /// Returns the statistics of executing a single block.
+    pub(crate) fn from_executed_block(
+        block: &Block,
+        execution_results: &[(DeployHash, DeployHeader, ExecutionResult)],
+        execution_time: TimeDiff,
+    ) -> Self {
+        let mut total_gas = U512::zero();
+        let mut failed_count = 0;
+        for (_, _, execution_result) in execution_results {
+            match execution_result {
+                ExecutionResult::Failure { cost, .. } => {
+                    total_gas += *cost;
+                    failed_count += 1;
+                }
+                ExecutionResult::Success { cost, .. } => total_gas += *cost,
+            }
+        }
+        EraExecutionStats {
+            era_id: block.header().era_id(),
+            highest_block_height: block.height(),
+            is_complete: block.header().is_switch_block(),
+            block_count: 1,
+            deploy_count: block.body().deploy_hashes().len() as u64,
+            transfer_count: block.body().transfer_hashes().len() as u64,
+            failed_count,
+            total_gas,
+            total_execution_time: execution_time,
+            average_block_execution_time: execution_time,
+        }
+    }
+
+    /// Adds the statistics of a later block of the same era.
+    ///
+    /// Returns `false` without any change if `block_stats` relate to a different era, or to a block
+    /// which is already included.
+    pub(crate) fn add_block(&mut self, block_stats: EraExecutionStats) -> bool {
+        if block_stats.era_id != self.era_id
+            || block_stats.highest_block_height <= self.highest_block_height
+        {
+            return false;
+        }
+        self.highest_block_height = block_stats.highest_block_height;
+        self.is_complete = block_stats.is_complete;
+        self.block_count += block_stats.block_count;
+        self.deploy_count += block_stats.deploy_count;
+        self.transfer_count += block_stats.transfer_count;
+        self.failed_count += block_stats.failed_count;
+        self.total_gas += block_stats.total_gas;
+        self.total_execution_time += block_stats.total_execution_time;
+        self.average_block_execution_time = self.total_execution_time / self.block_count;
+        true
+    }
+
+    /// Returns the era the statistics relate to.
+    pub fn era_id(&self) -> EraId {
+        self.era_id
+    }
+
+    /// Returns the height of the highest block included in the statistics.
+    pub fn highest_block_height(&self) -> u64 {
+        self.highest_block_height
+    }
+
+    /// Returns `true` if the era's switch block is included.
+    pub fn is_complete(&self) -> bool {
+        self.is_complete
+    }
+
+    /// Returns the number of blocks executed.
+    pub fn block_count(&self) -> u64 {
+        self.block_count
+    }
+
+    /// Returns the number of non-transfer deploys executed.
+    pub fn deploy_count(&self) -> u64 {
+        self.deploy_count
+    }
+
+    /// Returns the number of transfers executed.
+    pub fn transfer_count(&self) -> u64 {
+        self.transfer_count
+    }
+
+    /// Returns the number of deploys and transfers whose execution failed.
+    pub fn failed_count(&self) -> u64 {
+        self.failed_count
+    }
+
+    /// Returns the total gas consumed.
+    pub fn total_gas(&self) -> U512 {
+        self.total_gas
+    }
+
+    /// Returns the total time spent executing deploys and transfers.
+    pub fn total_execution_time(&self) -> TimeDiff {
+        self.total_execution_time
+    }
+
+    /// Returns the average time spent executing the deploys and transfers of a block.
+    pub fn average_block_execution_time(&self) -> TimeDiff {
+        self.average_block_execution_time
+    }
+}
+
+impl Display for EraExecutionStats {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "execution stats for {} up to block {}: {} blocks, {} deploys, {} transfers, {} \
+            failed, {} gas",
+            self.era_id,
+            self.highest_block_height,
+            self.block_count,
+            self.deploy_count,
+            self.transfer_count,
+            self.failed_count,
+            self.total_gas
+        )
+    }
+}
+
+impl DocExample for EraExecutionStats {
+    fn doc_example() -> &'static Self {
+        &ERA_EXECUTION_STATS
+    }
+}
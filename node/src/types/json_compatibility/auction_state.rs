@@ -10,6 +10,7 @@ use serde::{Deserialize, Serialize};
 
 use casper_hashing::Digest;
 use casper_types::{
+    bytesrepr::Bytes,
     system::auction::{Bid, Bids, DelegationRate, Delegator, EraValidators},
     AccessRights, EraId, PublicKey, SecretKey, URef, U512,
 };
@@ -66,7 +67,16 @@ static AUCTION_INFO: Lazy<AuctionState> = Lazy::new(|| {
     let height: u64 = 10;
     let era_validators = EraValidators::doc_example().clone();
     let bids = Bids::doc_example().clone();
-    AuctionState::new(state_root_hash, height, era_validators, bids)
+    let bid_metadata = bids
+        .keys()
+        .map(|public_key| {
+            (
+                public_key.clone(),
+                Bytes::from(b"name=Example Validator".to_vec()),
+            )
+        })
+        .collect();
+    AuctionState::new(state_root_hash, height, era_validators, bids, bid_metadata)
 });
 
 /// A validator's weight.
@@ -109,6 +119,10 @@ pub struct JsonBid {
     delegators: Vec<JsonDelegator>,
     /// Is this an inactive validator.
     inactive: bool,
+    /// Hex-encoded metadata published by the validator, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<String>")]
+    metadata: Option<Bytes>,
 }
 
 impl From<Bid> for JsonBid {
@@ -128,6 +142,7 @@ impl From<Bid> for JsonBid {
             delegation_rate: *bid.delegation_rate(),
             delegators: json_delegators,
             inactive: bid.inactive(),
+            metadata: None,
         }
     }
 }
@@ -161,6 +176,7 @@ impl AuctionState {
         block_height: u64,
         era_validators: EraValidators,
         bids: Bids,
+        mut bid_metadata: BTreeMap<PublicKey, Bytes>,
     ) -> Self {
         let mut json_era_validators: Vec<JsonEraValidators> = Vec::new();
         for (era_id, validator_weights) in era_validators.iter() {
@@ -179,7 +195,8 @@ impl AuctionState {
 
         let mut json_bids: Vec<JsonBids> = Vec::new();
         for (public_key, bid) in bids.iter() {
-            let json_bid = JsonBid::from(bid.clone());
+            let mut json_bid = JsonBid::from(bid.clone());
+            json_bid.metadata = bid_metadata.remove(public_key);
             json_bids.push(JsonBids {
                 public_key: public_key.clone(),
                 bid: json_bid,
@@ -1,9 +1,12 @@
+use std::collections::BTreeMap;
+
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
-use crate::types::BlockHash;
+use casper_hashing::Digest;
+use casper_types::{EraId, PublicKey, TimeDiff, U512};
 
-use casper_types::TimeDiff;
+use crate::types::BlockHash;
 
 const DEFAULT_IDLE_TOLERANCE: &str = "20min";
 const DEFAULT_MAX_ATTEMPTS: usize = 3;
@@ -41,6 +44,24 @@ impl SyncHandling {
     }
 }
 
+/// A block, its state and its era's validators, trusted to be part of the chain without verifying
+/// any history before it.
+///
+/// A node joining from a trusted checkpoint syncs forward from it only, so it needs neither the
+/// blocks nor the global state preceding it.
+#[derive(DataSize, Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct TrustedCheckpoint {
+    /// Hash of the checkpoint block.
+    pub block_hash: BlockHash,
+    /// Global state root hash of the checkpoint block.
+    pub state_root_hash: Digest,
+    /// Era of the checkpoint block.
+    pub era_id: EraId,
+    /// Weights of the validators of the checkpoint block's era.
+    pub validator_weights: BTreeMap<PublicKey, U512>,
+}
+
 /// Node fast-sync configuration.
 #[derive(DataSize, Debug, Deserialize, Serialize, Clone)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
@@ -74,6 +95,13 @@ pub struct NodeConfig {
 
     /// Maximum time a node will wait for an upgrade to commit.
     pub upgrade_timeout: TimeDiff,
+
+    /// Checkpoint to join from without verifying history back to genesis, if any.
+    ///
+    /// If set, `trusted_hash` is replaced by the checkpoint's block hash, and a `sync_handling` of
+    /// `Genesis` by `Ttl`.  With `NoSync`, the node never participates in consensus.
+    // Kept last, as TOML requires tables to follow all plain values.
+    pub trusted_checkpoint: Option<TrustedCheckpoint>,
 }
 
 impl Default for NodeConfig {
@@ -87,6 +115,7 @@ impl Default for NodeConfig {
             force_resync: false,
             shutdown_for_upgrade_timeout: DEFAULT_SHUTDOWN_FOR_UPGRADE_TIMEOUT.parse().unwrap(),
             upgrade_timeout: DEFAULT_UPGRADE_TIMEOUT.parse().unwrap(),
+            trusted_checkpoint: None,
         }
     }
 }
@@ -118,6 +118,27 @@ pub(crate) fn resolve_address(address: &str) -> Result<SocketAddr, ResolveAddres
         })
 }
 
+/// Parses a network address from a string, with DNS resolution, returning every address found.
+///
+/// Unlike [`resolve_address`], this is suitable for host names with several DNS records, such as
+/// DNS seeds listing many nodes.
+pub(crate) fn resolve_all_addresses(address: &str) -> Result<Vec<SocketAddr>, ResolveAddressError> {
+    let addresses: Vec<_> = address
+        .to_socket_addrs()
+        .map_err(|err| ResolveAddressError {
+            address: address.to_string(),
+            kind: ResolveAddressErrorKind::ErrorResolving(err),
+        })?
+        .collect();
+    if addresses.is_empty() {
+        return Err(ResolveAddressError {
+            address: address.to_string(),
+            kind: ResolveAddressErrorKind::NoAddressFound,
+        });
+    }
+    Ok(addresses)
+}
+
 /// An error starting one of the HTTP servers.
 #[derive(Debug, Error)]
 pub(crate) enum ListeningError {
@@ -458,7 +479,15 @@ mod tests {
 
     use crate::utils::SharedFlag;
 
-    use super::{wait_for_arc_drop, xor};
+    use super::{resolve_all_addresses, wait_for_arc_drop, xor};
+
+    #[test]
+    fn resolves_all_addresses() {
+        let addresses = resolve_all_addresses("127.0.0.1:34553").unwrap();
+        assert_eq!(addresses, vec![([127, 0, 0, 1], 34553).into()]);
+
+        assert!(resolve_all_addresses("127.0.0.1").is_err());
+    }
 
     #[test]
     fn xor_works() {
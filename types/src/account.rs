@@ -612,6 +612,25 @@ mod tests {
         assert!(AccountHash::from_formatted_str(invalid_hex).is_err());
     }
 
+    #[test]
+    fn account_hash_from_checksummed_str() {
+        let account_hash = AccountHash([0xab; 32]);
+        let encoded = account_hash.to_checksummed_formatted_string();
+        assert_ne!(encoded, account_hash.to_formatted_string());
+        let decoded = AccountHash::from_formatted_str(&encoded).unwrap();
+        assert_eq!(account_hash, decoded);
+
+        // Swap the case of the first character of the hash to break the checksum.
+        let index = ACCOUNT_HASH_FORMATTED_STRING_PREFIX.len();
+        let mut mistyped = encoded.into_bytes();
+        mistyped[index] ^= 0x20;
+        let mistyped = String::from_utf8(mistyped).unwrap();
+        assert!(matches!(
+            AccountHash::from_formatted_str(&mistyped),
+            Err(FromStrError::InvalidChecksum { index: 0 })
+        ));
+    }
+
     #[test]
     fn account_hash_serde_roundtrip() {
         let account_hash = AccountHash([255; 32]);
@@ -56,13 +56,25 @@ impl AccountHash {
         )
     }
 
-    /// Parses a string formatted as per `Self::to_formatted_string()` into an `AccountHash`.
+    /// Formats the `AccountHash` for users getting and putting, using mixed-case checksummed hex
+    /// so that typos can be detected when the string is parsed.
+    pub fn to_checksummed_formatted_string(self) -> String {
+        format!(
+            "{}{}",
+            ACCOUNT_HASH_FORMATTED_STRING_PREFIX,
+            checksummed_hex::encode(&self.0),
+        )
+    }
+
+    /// Parses a string formatted as per `Self::to_formatted_string()` or
+    /// `Self::to_checksummed_formatted_string()` into an `AccountHash`.
     pub fn from_formatted_str(input: &str) -> Result<Self, FromStrError> {
         let remainder = input
             .strip_prefix(ACCOUNT_HASH_FORMATTED_STRING_PREFIX)
             .ok_or(FromStrError::InvalidPrefix)?;
-        let bytes =
-            <[u8; ACCOUNT_HASH_LENGTH]>::try_from(checksummed_hex::decode(remainder)?.as_ref())?;
+        let bytes = <[u8; ACCOUNT_HASH_LENGTH]>::try_from(
+            checksummed_hex::decode_checked(remainder)?.as_ref(),
+        )?;
         Ok(AccountHash(bytes))
     }
 
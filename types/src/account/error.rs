@@ -4,6 +4,8 @@ use core::{
     fmt::{self, Display, Formatter},
 };
 
+use crate::checksummed_hex;
+
 // This error type is not intended to be used by third party crates.
 #[doc(hidden)]
 #[derive(Debug, Eq, PartialEq)]
@@ -19,6 +21,11 @@ pub enum FromStrError {
     Hex(base16::DecodeError),
     /// The hash is the wrong length.
     Hash(TryFromSliceError),
+    /// The hash is mixed-case hex, but its case does not match the checksum.
+    InvalidChecksum {
+        /// The index of the first character whose case does not match the checksum.
+        index: usize,
+    },
 }
 
 impl From<base16::DecodeError> for FromStrError {
@@ -27,6 +34,17 @@ impl From<base16::DecodeError> for FromStrError {
     }
 }
 
+impl From<checksummed_hex::Error> for FromStrError {
+    fn from(error: checksummed_hex::Error) -> Self {
+        match error {
+            checksummed_hex::Error::InvalidHex(error) => FromStrError::Hex(error),
+            checksummed_hex::Error::InvalidChecksum { index } => {
+                FromStrError::InvalidChecksum { index }
+            }
+        }
+    }
+}
+
 impl From<TryFromSliceError> for FromStrError {
     fn from(error: TryFromSliceError) -> Self {
         FromStrError::Hash(error)
@@ -41,6 +59,11 @@ impl Display for FromStrError {
                 write!(f, "failed to decode address portion from hex: {}", error)
             }
             FromStrError::Hash(error) => write!(f, "address portion is wrong length: {}", error),
+            FromStrError::InvalidChecksum { index } => write!(
+                f,
+                "address portion has invalid checksum at character {}",
+                index
+            ),
         }
     }
 }
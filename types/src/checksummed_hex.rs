@@ -1,9 +1,17 @@
 //! Checksummed hex encoding following an [EIP-55][1]-like scheme.
 //!
+//! As specified in [CEP-57][2], the case of each alphabetic character in the encoded form is
+//! determined by the bits of the Blake2b hash of the encoded bytes, allowing typos in addresses to
+//! be detected when they are parsed.
+//!
 //! [1]: https://eips.ethereum.org/EIPS/eip-55
+//! [2]: https://github.com/casper-network/ceps/blob/master/text/0057-checksummed-addresses.md
 
-use alloc::vec::Vec;
-use core::ops::RangeInclusive;
+use alloc::{string::String, vec::Vec};
+use core::{
+    fmt::{self, Display, Formatter},
+    ops::RangeInclusive,
+};
 
 use base16;
 
@@ -55,6 +63,41 @@ fn encode_iter<'a, T: 'a + AsRef<[u8]>>(input: &'a T) -> impl Iterator<Item = ch
     })
 }
 
+/// Returns the bytes encoded as hexadecimal with mixed-case based checksums.
+///
+/// Inputs longer than [`SMALL_BYTES_COUNT`] bytes are still encoded with checksums, but their
+/// checksums are not verified by [`decode`].
+pub fn encode<T: AsRef<[u8]>>(input: T) -> String {
+    encode_iter(&input).collect()
+}
+
+/// Error returned when decoding checksummed hex.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The input is not valid hex.
+    InvalidHex(base16::DecodeError),
+    /// The input is valid hex, but the case of the character at `index` does not match the
+    /// checksum, indicating the input has been mistyped.
+    InvalidChecksum {
+        /// The index of the first character whose case does not match the checksum.
+        index: usize,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidHex(error) => write!(formatter, "invalid hex: {}", error),
+            Error::InvalidChecksum { index } => {
+                write!(formatter, "invalid checksum at character {}", index)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
 /// Returns true if all chars in a string are uppercase or lowercase.
 /// Returns false if the string is mixed case or if there are no alphabetic chars.
 fn string_is_same_case<T: AsRef<[u8]>>(s: T) -> bool {
@@ -92,26 +135,38 @@ fn string_is_same_case<T: AsRef<[u8]>>(s: T) -> bool {
 /// [1]: https://eips.ethereum.org/EIPS/eip-55
 pub fn decode<T: AsRef<[u8]>>(input: T) -> Result<Vec<u8>, base16::DecodeError> {
     let bytes = base16::decode(input.as_ref())?;
+    find_checksum_mismatch(input.as_ref(), &bytes).map_or(Ok(bytes), |(index, expected)| {
+        Err(base16::DecodeError::InvalidByte {
+            index,
+            byte: expected as u8,
+        })
+    })
+}
+
+/// Decodes a mixed-case hexadecimal string as per [`decode`], distinguishing a checksum mismatch
+/// from invalid hex in the returned error.
+pub fn decode_checked<T: AsRef<[u8]>>(input: T) -> Result<Vec<u8>, Error> {
+    let bytes = base16::decode(input.as_ref()).map_err(Error::InvalidHex)?;
+    find_checksum_mismatch(input.as_ref(), &bytes).map_or(Ok(bytes), |(index, _)| {
+        Err(Error::InvalidChecksum { index })
+    })
+}
 
+/// Returns the index of the first character of `input` whose case doesn't match the checksum of
+/// `bytes`, along with the correctly-cased character.
+fn find_checksum_mismatch(input: &[u8], bytes: &[u8]) -> Option<(usize, char)> {
     // If the string was not small or not mixed case, don't verify the checksum.
-    if bytes.len() > SMALL_BYTES_COUNT || string_is_same_case(input.as_ref()) {
-        return Ok(bytes);
+    if bytes.len() > SMALL_BYTES_COUNT || string_is_same_case(input) {
+        return None;
     }
 
     encode_iter(&bytes)
-        .zip(input.as_ref().iter())
+        .zip(input.iter())
         .enumerate()
-        .try_for_each(|(index, (expected_case_hex_char, &input_hex_char))| {
-            if expected_case_hex_char as u8 == input_hex_char {
-                Ok(())
-            } else {
-                Err(base16::DecodeError::InvalidByte {
-                    index,
-                    byte: expected_case_hex_char as u8,
-                })
-            }
-        })?;
-    Ok(bytes)
+        .find(|(_, (expected_case_hex_char, &input_hex_char))| {
+            *expected_case_hex_char as u8 != input_hex_char
+        })
+        .map(|(index, (expected_case_hex_char, _))| (index, expected_case_hex_char))
 }
 
 #[cfg(test)]
@@ -212,10 +267,35 @@ mod tests {
             );
 
             // Assert decoding using `checksummed_hex::decode` returns the expected error.
-            prop_assert_eq!(expected_error.unwrap(), decode(&mutated).unwrap_err())
+            let expected_error = expected_error.unwrap();
+            let index = match &expected_error {
+                base16::DecodeError::InvalidByte { index, .. } => *index,
+                error => panic!("unexpected error {:?}", error),
+            };
+            prop_assert_eq!(expected_error, decode(&mutated).unwrap_err());
+
+            // Assert decoding using `checksummed_hex::decode_checked` reports a checksum error.
+            prop_assert_eq!(
+                Error::InvalidChecksum { index },
+                decode_checked(&mutated).unwrap_err()
+            )
         }
     }
 
+    #[proptest]
+    fn encode_roundtrip(input: Vec<u8>) {
+        prop_assert_eq!(input.clone(), decode_checked(encode(&input)).unwrap());
+    }
+
+    #[test]
+    fn should_distinguish_invalid_hex_from_invalid_checksum() {
+        assert!(matches!(decode_checked("0g"), Err(Error::InvalidHex(_))));
+        assert!(matches!(
+            decode_checked("A1a2"),
+            Err(Error::InvalidChecksum { .. })
+        ));
+    }
+
     #[proptest]
     fn hex_roundtrip_sanity(input: Vec<u8>) {
         prop_assert!(matches!(
@@ -154,6 +154,11 @@ pub enum FromStrError {
     AccountHash(account::FromStrError),
     /// Error when parsing an uref.
     URef(uref::FromStrError),
+    /// Error when the case of a mixed-case hex string does not match its checksum.
+    InvalidChecksum {
+        /// The index of the first character whose case does not match the checksum.
+        index: usize,
+    },
 }
 
 impl From<base16::DecodeError> for FromStrError {
@@ -162,6 +167,17 @@ impl From<base16::DecodeError> for FromStrError {
     }
 }
 
+impl From<checksummed_hex::Error> for FromStrError {
+    fn from(error: checksummed_hex::Error) -> Self {
+        match error {
+            checksummed_hex::Error::InvalidHex(error) => FromStrError::Hex(error),
+            checksummed_hex::Error::InvalidChecksum { index } => {
+                FromStrError::InvalidChecksum { index }
+            }
+        }
+    }
+}
+
 impl From<TryFromSliceForAccountHashError> for FromStrError {
     fn from(error: TryFromSliceForAccountHashError) -> Self {
         FromStrError::Account(error)
@@ -197,6 +213,9 @@ impl Display for FromStrError {
                 write!(f, "account hash from string error: {:?}", error)
             }
             FromStrError::URef(error) => write!(f, "uref from string error: {:?}", error),
+            FromStrError::InvalidChecksum { index } => {
+                write!(f, "invalid checksum at character {}", index)
+            }
         }
     }
 }
@@ -364,13 +383,23 @@ impl ContractHash {
         )
     }
 
-    /// Parses a string formatted as per `Self::to_formatted_string()` into a
-    /// `ContractHash`.
+    /// Formats the `ContractHash` for users getting and putting, using mixed-case checksummed hex
+    /// so that typos can be detected when the string is parsed.
+    pub fn to_checksummed_formatted_string(self) -> String {
+        format!(
+            "{}{}",
+            CONTRACT_STRING_PREFIX,
+            checksummed_hex::encode(&self.0),
+        )
+    }
+
+    /// Parses a string formatted as per `Self::to_formatted_string()` or
+    /// `Self::to_checksummed_formatted_string()` into a `ContractHash`.
     pub fn from_formatted_str(input: &str) -> Result<Self, FromStrError> {
         let remainder = input
             .strip_prefix(CONTRACT_STRING_PREFIX)
             .ok_or(FromStrError::InvalidPrefix)?;
-        let bytes = HashAddr::try_from(checksummed_hex::decode(remainder)?.as_ref())?;
+        let bytes = HashAddr::try_from(checksummed_hex::decode_checked(remainder)?.as_ref())?;
         Ok(ContractHash(bytes))
     }
 }
@@ -512,8 +541,18 @@ impl ContractPackageHash {
         format!("{}{}", PACKAGE_STRING_PREFIX, base16::encode_lower(&self.0),)
     }
 
-    /// Parses a string formatted as per `Self::to_formatted_string()` into a
-    /// `ContractPackageHash`.
+    /// Formats the `ContractPackageHash` for users getting and putting, using mixed-case
+    /// checksummed hex so that typos can be detected when the string is parsed.
+    pub fn to_checksummed_formatted_string(self) -> String {
+        format!(
+            "{}{}",
+            PACKAGE_STRING_PREFIX,
+            checksummed_hex::encode(&self.0),
+        )
+    }
+
+    /// Parses a string formatted as per `Self::to_formatted_string()` or
+    /// `Self::to_checksummed_formatted_string()` into a `ContractPackageHash`.
     pub fn from_formatted_str(input: &str) -> Result<Self, FromStrError> {
         let remainder = input
             .strip_prefix(PACKAGE_STRING_PREFIX)
@@ -523,7 +562,7 @@ impl ContractPackageHash {
             .strip_prefix(PACKAGE_STRING_LEGACY_EXTRA_PREFIX)
             .unwrap_or(remainder);
 
-        let bytes = HashAddr::try_from(checksummed_hex::decode(hex_addr)?.as_ref())?;
+        let bytes = HashAddr::try_from(checksummed_hex::decode_checked(hex_addr)?.as_ref())?;
         Ok(ContractPackageHash(bytes))
     }
 }
@@ -1917,6 +1956,33 @@ mod tests {
         assert_eq!(&bytes, &contract_hash.as_bytes());
     }
 
+    #[test]
+    fn contract_hashes_from_checksummed_str() {
+        let contract_hash = ContractHash([0xab; 32]);
+        let encoded = contract_hash.to_checksummed_formatted_string();
+        assert_ne!(encoded, contract_hash.to_formatted_string());
+        assert_eq!(
+            ContractHash::from_formatted_str(&encoded).unwrap(),
+            contract_hash
+        );
+
+        let contract_package_hash = ContractPackageHash([0xab; 32]);
+        let encoded = contract_package_hash.to_checksummed_formatted_string();
+        assert_eq!(
+            ContractPackageHash::from_formatted_str(&encoded).unwrap(),
+            contract_package_hash
+        );
+
+        // Swap the case of the first character of the hash to break the checksum.
+        let mut mistyped = encoded.into_bytes();
+        mistyped[PACKAGE_STRING_PREFIX.len()] ^= 0x20;
+        let mistyped = String::from_utf8(mistyped).unwrap();
+        assert!(matches!(
+            ContractPackageHash::from_formatted_str(&mistyped),
+            Err(FromStrError::InvalidChecksum { index: 0 })
+        ));
+    }
+
     #[test]
     fn contract_hash_from_str() {
         let contract_hash = ContractHash([3; 32]);
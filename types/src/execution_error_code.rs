@@ -0,0 +1,194 @@
+//! Stable numeric codes identifying the errors which can be raised while executing a deploy.
+
+// TODO - remove once schemars stops causing warning.
+#![allow(clippy::field_reassign_with_default)]
+
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+#[cfg(feature = "datasize")]
+use datasize::DataSize;
+use num_derive::{FromPrimitive, ToPrimitive};
+use num_traits::{FromPrimitive, ToPrimitive};
+use rand::{
+    distributions::{Distribution, Standard},
+    Rng,
+};
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH, U8_SERIALIZED_LENGTH},
+    ApiError,
+};
+
+/// The broad class of an execution error.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    FromPrimitive,
+    ToPrimitive,
+)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[repr(u8)]
+pub enum ErrorCategory {
+    /// The deploy or the state it was executed against did not satisfy a precondition of
+    /// execution, e.g. a missing contract or an invalid Wasm module.
+    Precondition = 0,
+    /// The caller lacked the permissions required by the attempted operation.
+    Authorization = 1,
+    /// Execution exceeded its gas limit.
+    OutOfGas = 2,
+    /// Execution was reverted with an [`ApiError`], either by the called code or by a system
+    /// contract.  The accompanying code is the `u32` representation of that [`ApiError`].
+    Revert = 3,
+    /// The executed code trapped, e.g. by misusing a host function.
+    RuntimeTrap = 4,
+    /// An unexpected failure internal to the execution engine.
+    Internal = 5,
+}
+
+impl Display for ErrorCategory {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            ErrorCategory::Precondition => write!(formatter, "precondition"),
+            ErrorCategory::Authorization => write!(formatter, "authorization"),
+            ErrorCategory::OutOfGas => write!(formatter, "out-of-gas"),
+            ErrorCategory::Revert => write!(formatter, "revert"),
+            ErrorCategory::RuntimeTrap => write!(formatter, "runtime-trap"),
+            ErrorCategory::Internal => write!(formatter, "internal"),
+        }
+    }
+}
+
+/// A stable numeric identifier of an execution error, along with its category.
+///
+/// Codes are unique within a category and never reassigned, so clients can rely on them rather
+/// than on the wording of error messages.  For the [`ErrorCategory::Revert`] category the code is
+/// the `u32` representation of the [`ApiError`] the execution reverted with.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct ExecutionErrorCode {
+    category: ErrorCategory,
+    code: u32,
+}
+
+impl ExecutionErrorCode {
+    /// Constructs a new `ExecutionErrorCode`.
+    pub const fn new(category: ErrorCategory, code: u32) -> Self {
+        ExecutionErrorCode { category, code }
+    }
+
+    /// Returns the category of the error.
+    pub fn category(&self) -> ErrorCategory {
+        self.category
+    }
+
+    /// Returns the numeric code of the error.
+    pub fn code(&self) -> u32 {
+        self.code
+    }
+}
+
+impl From<ApiError> for ExecutionErrorCode {
+    fn from(api_error: ApiError) -> Self {
+        ExecutionErrorCode::new(ErrorCategory::Revert, u32::from(api_error))
+    }
+}
+
+impl ToBytes for ErrorCategory {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.to_u8().ok_or(bytesrepr::Error::Formatting)?.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for ErrorCategory {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        let category = FromPrimitive::from_u8(tag).ok_or(bytesrepr::Error::Formatting)?;
+        Ok((category, remainder))
+    }
+}
+
+impl Distribution<ErrorCategory> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> ErrorCategory {
+        match rng.gen_range(0..6) {
+            0 => ErrorCategory::Precondition,
+            1 => ErrorCategory::Authorization,
+            2 => ErrorCategory::OutOfGas,
+            3 => ErrorCategory::Revert,
+            4 => ErrorCategory::RuntimeTrap,
+            _ => ErrorCategory::Internal,
+        }
+    }
+}
+
+impl ToBytes for ExecutionErrorCode {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.extend(self.category.to_bytes()?);
+        buffer.extend(self.code.to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH + U32_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for ExecutionErrorCode {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (category, remainder) = ErrorCategory::from_bytes(bytes)?;
+        let (code, remainder) = u32::from_bytes(remainder)?;
+        Ok((ExecutionErrorCode { category, code }, remainder))
+    }
+}
+
+impl Distribution<ExecutionErrorCode> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> ExecutionErrorCode {
+        ExecutionErrorCode::new(rng.gen(), rng.gen())
+    }
+}
+
+impl Display for ExecutionErrorCode {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "{}:{}", self.category, self.code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bytesrepr, testing::TestRng};
+
+    #[test]
+    fn should_use_api_error_value_as_revert_code() {
+        let error_code = ExecutionErrorCode::from(ApiError::User(1));
+        assert_eq!(error_code.category(), ErrorCategory::Revert);
+        assert_eq!(error_code.code(), u32::from(ApiError::User(1)));
+        assert_eq!(error_code.to_string(), "revert:65537");
+    }
+
+    #[test]
+    fn bytesrepr_roundtrip() {
+        let mut rng = TestRng::new();
+        let error_code: ExecutionErrorCode = rng.gen();
+        bytesrepr::test_serialization_roundtrip(&error_code);
+    }
+}
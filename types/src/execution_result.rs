@@ -8,8 +8,11 @@
 #![allow(clippy::field_reassign_with_default)]
 
 use core::convert::TryFrom;
+#[cfg(not(any(feature = "std", test)))]
+use core::fmt::{self, Debug, Display, Formatter};
 
 use alloc::{
+    borrow::Cow,
     boxed::Box,
     format,
     string::{String, ToString},
@@ -30,7 +33,9 @@ use rand::{
 };
 #[cfg(feature = "json-schema")]
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(any(feature = "std", test))]
+use thiserror::Error;
 
 #[cfg(feature = "json-schema")]
 use crate::KEY_HASH_LENGTH;
@@ -38,7 +43,8 @@ use crate::{
     account::AccountHash,
     bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
     system::auction::{Bid, EraInfo, UnbondingPurse, WithdrawPurse},
-    CLValue, DeployInfo, NamedKey, Transfer, TransferAddr, U128, U256, U512,
+    CLValue, DeployInfo, ExecutionErrorCode, Key, NamedKey, Transfer, TransferAddr, U128, U256,
+    U512,
 };
 
 #[derive(FromPrimitive, ToPrimitive, Debug)]
@@ -46,6 +52,7 @@ use crate::{
 enum ExecutionResultTag {
     Failure = 0,
     Success = 1,
+    FailureWithErrorCode = 2,
 }
 
 impl TryFrom<u8> for ExecutionResultTag {
@@ -95,6 +102,7 @@ enum TransformTag {
     AddKeys = 16,
     Failure = 17,
     WriteUnbonding = 18,
+    Prune = 19,
 }
 
 impl TryFrom<u8> for TransformTag {
@@ -151,10 +159,10 @@ static EXECUTION_RESULT: Lazy<ExecutionResult> = Lazy::new(|| {
 });
 
 /// The result of executing a single deploy.
-#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
+#[derive(Clone, Eq, PartialEq, Debug)]
 #[cfg_attr(feature = "datasize", derive(DataSize))]
 #[cfg_attr(feature = "json-schema", derive(JsonSchema))]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "json-schema", schemars(deny_unknown_fields))]
 pub enum ExecutionResult {
     /// The result of a failed execution.
     Failure {
@@ -166,6 +174,9 @@ pub enum ExecutionResult {
         cost: U512,
         /// The error message associated with executing the deploy.
         error_message: String,
+        /// The stable code identifying the error associated with executing the deploy, if it was
+        /// recorded.
+        error_code: Option<ExecutionErrorCode>,
     },
     /// The result of a successful execution.
     Success {
@@ -193,7 +204,15 @@ impl ExecutionResult {
                 transfers: _,
                 cost: _,
                 error_message: _,
+                error_code: None,
             } => ExecutionResultTag::Failure,
+            ExecutionResult::Failure {
+                effect: _,
+                transfers: _,
+                cost: _,
+                error_message: _,
+                error_code: Some(_),
+            } => ExecutionResultTag::FailureWithErrorCode,
             ExecutionResult::Success {
                 effect: _,
                 transfers: _,
@@ -203,6 +222,191 @@ impl ExecutionResult {
     }
 }
 
+/// The serde representation of an [`ExecutionResult`] in human-readable formats such as JSON.
+///
+/// The error code of a failure is omitted when it was not recorded.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+enum HumanReadableExecutionResult<'a> {
+    Failure {
+        effect: Cow<'a, ExecutionEffect>,
+        transfers: Cow<'a, [TransferAddr]>,
+        cost: U512,
+        error_message: Cow<'a, str>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error_code: Option<ExecutionErrorCode>,
+    },
+    Success {
+        effect: Cow<'a, ExecutionEffect>,
+        transfers: Cow<'a, [TransferAddr]>,
+        cost: U512,
+    },
+}
+
+/// The serde representation of an [`ExecutionResult`] in binary formats such as bincode.
+///
+/// A failure without an error code keeps the encoding used before error codes were added, so that
+/// previously stored execution results remain readable.  New variants must only be appended.
+#[derive(Serialize, Deserialize)]
+enum BinaryExecutionResult<'a> {
+    Failure {
+        effect: Cow<'a, ExecutionEffect>,
+        transfers: Cow<'a, [TransferAddr]>,
+        cost: U512,
+        error_message: Cow<'a, str>,
+    },
+    Success {
+        effect: Cow<'a, ExecutionEffect>,
+        transfers: Cow<'a, [TransferAddr]>,
+        cost: U512,
+    },
+    FailureWithErrorCode {
+        effect: Cow<'a, ExecutionEffect>,
+        transfers: Cow<'a, [TransferAddr]>,
+        cost: U512,
+        error_message: Cow<'a, str>,
+        error_code: ExecutionErrorCode,
+    },
+}
+
+impl Serialize for ExecutionResult {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            ExecutionResult::Failure {
+                effect,
+                transfers,
+                cost,
+                error_message,
+                error_code,
+            } => {
+                let effect = Cow::Borrowed(effect);
+                let transfers = Cow::Borrowed(transfers.as_slice());
+                let error_message = Cow::Borrowed(error_message.as_str());
+                if serializer.is_human_readable() {
+                    HumanReadableExecutionResult::Failure {
+                        effect,
+                        transfers,
+                        cost: *cost,
+                        error_message,
+                        error_code: *error_code,
+                    }
+                    .serialize(serializer)
+                } else if let Some(error_code) = error_code {
+                    BinaryExecutionResult::FailureWithErrorCode {
+                        effect,
+                        transfers,
+                        cost: *cost,
+                        error_message,
+                        error_code: *error_code,
+                    }
+                    .serialize(serializer)
+                } else {
+                    BinaryExecutionResult::Failure {
+                        effect,
+                        transfers,
+                        cost: *cost,
+                        error_message,
+                    }
+                    .serialize(serializer)
+                }
+            }
+            ExecutionResult::Success {
+                effect,
+                transfers,
+                cost,
+            } => {
+                let effect = Cow::Borrowed(effect);
+                let transfers = Cow::Borrowed(transfers.as_slice());
+                if serializer.is_human_readable() {
+                    HumanReadableExecutionResult::Success {
+                        effect,
+                        transfers,
+                        cost: *cost,
+                    }
+                    .serialize(serializer)
+                } else {
+                    BinaryExecutionResult::Success {
+                        effect,
+                        transfers,
+                        cost: *cost,
+                    }
+                    .serialize(serializer)
+                }
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ExecutionResult {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let execution_result = match HumanReadableExecutionResult::deserialize(deserializer)? {
+                HumanReadableExecutionResult::Failure {
+                    effect,
+                    transfers,
+                    cost,
+                    error_message,
+                    error_code,
+                } => ExecutionResult::Failure {
+                    effect: effect.into_owned(),
+                    transfers: transfers.into_owned(),
+                    cost,
+                    error_message: error_message.into_owned(),
+                    error_code,
+                },
+                HumanReadableExecutionResult::Success {
+                    effect,
+                    transfers,
+                    cost,
+                } => ExecutionResult::Success {
+                    effect: effect.into_owned(),
+                    transfers: transfers.into_owned(),
+                    cost,
+                },
+            };
+            return Ok(execution_result);
+        }
+
+        let execution_result = match BinaryExecutionResult::deserialize(deserializer)? {
+            BinaryExecutionResult::Failure {
+                effect,
+                transfers,
+                cost,
+                error_message,
+            } => ExecutionResult::Failure {
+                effect: effect.into_owned(),
+                transfers: transfers.into_owned(),
+                cost,
+                error_message: error_message.into_owned(),
+                error_code: None,
+            },
+            BinaryExecutionResult::Success {
+                effect,
+                transfers,
+                cost,
+            } => ExecutionResult::Success {
+                effect: effect.into_owned(),
+                transfers: transfers.into_owned(),
+                cost,
+            },
+            BinaryExecutionResult::FailureWithErrorCode {
+                effect,
+                transfers,
+                cost,
+                error_message,
+                error_code,
+            } => ExecutionResult::Failure {
+                effect: effect.into_owned(),
+                transfers: transfers.into_owned(),
+                cost,
+                error_message: error_message.into_owned(),
+                error_code: Some(error_code),
+            },
+        };
+        Ok(execution_result)
+    }
+}
+
 impl Distribution<ExecutionResult> for Standard {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> ExecutionResult {
         let op_count = rng.gen_range(0..6);
@@ -240,6 +444,7 @@ impl Distribution<ExecutionResult> for Standard {
                 transfers,
                 cost: rng.gen::<u64>().into(),
                 error_message: format!("Error message {}", rng.gen::<u64>()),
+                error_code: if rng.gen() { Some(rng.gen()) } else { None },
             }
         } else {
             ExecutionResult::Success {
@@ -263,11 +468,17 @@ impl ToBytes for ExecutionResult {
                 transfers,
                 cost,
                 error_message,
+                error_code,
             } => {
                 buffer.extend(effect.to_bytes()?);
                 buffer.extend(transfers.to_bytes()?);
                 buffer.extend(cost.to_bytes()?);
                 buffer.extend(error_message.to_bytes()?);
+                // A failure without an error code keeps the layout it had before error codes were
+                // added, so that it hashes identically.
+                if let Some(error_code) = error_code {
+                    buffer.extend(error_code.to_bytes()?);
+                }
             }
             ExecutionResult::Success {
                 effect,
@@ -290,11 +501,15 @@ impl ToBytes for ExecutionResult {
                     transfers,
                     cost,
                     error_message,
+                    error_code,
                 } => {
                     execution_effect.serialized_length()
                         + transfers.serialized_length()
                         + cost.serialized_length()
                         + error_message.serialized_length()
+                        + error_code
+                            .as_ref()
+                            .map_or(0, ExecutionErrorCode::serialized_length)
                 }
                 ExecutionResult::Success {
                     effect: execution_effect,
@@ -323,6 +538,22 @@ impl FromBytes for ExecutionResult {
                     transfers,
                     cost,
                     error_message,
+                    error_code: None,
+                };
+                Ok((execution_result, remainder))
+            }
+            ExecutionResultTag::FailureWithErrorCode => {
+                let (effect, remainder) = ExecutionEffect::from_bytes(remainder)?;
+                let (transfers, remainder) = Vec::<TransferAddr>::from_bytes(remainder)?;
+                let (cost, remainder) = U512::from_bytes(remainder)?;
+                let (error_message, remainder) = String::from_bytes(remainder)?;
+                let (error_code, remainder) = ExecutionErrorCode::from_bytes(remainder)?;
+                let execution_result = ExecutionResult::Failure {
+                    effect,
+                    transfers,
+                    cost,
+                    error_message,
+                    error_code: Some(error_code),
                 };
                 Ok((execution_result, remainder))
             }
@@ -389,6 +620,119 @@ impl FromBytes for ExecutionEffect {
     }
 }
 
+/// The version of the JSON format of [`VersionedExecutionEffect`].
+///
+/// This is incremented whenever the JSON representation of an [`ExecutionEffect`], or of any type
+/// it contains, changes in a way which isn't backwards compatible.  The versions are:
+///
+/// * 1: the format of `ExecutionEffect` as of casper-types 3.0.0.
+/// * 2: adds [`Transform::Prune`].
+pub const EXECUTION_EFFECT_JSON_VERSION: u32 = 2;
+
+/// The oldest version of the JSON format which [`VersionedExecutionEffect::from_json`] accepts.
+///
+/// Each version so far only extends the previous one, so older versions are still readable.
+const OLDEST_READABLE_EXECUTION_EFFECT_JSON_VERSION: u32 = 1;
+
+/// An [`ExecutionEffect`] tagged with the version of its JSON format.
+///
+/// This is the stable, machine-readable export of the effects of executing a deploy for
+/// consumption outside the node, e.g. by the sidecar, event stream clients and auditors.  Its
+/// format is documented by its JSON schema and is independent of the bytesrepr encoding.
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct VersionedExecutionEffect {
+    /// The version of the JSON format.
+    version: u32,
+    /// The execution effect.
+    effect: ExecutionEffect,
+}
+
+impl VersionedExecutionEffect {
+    /// Wraps the given effect in the current version of the JSON format.
+    pub fn new(effect: ExecutionEffect) -> Self {
+        VersionedExecutionEffect {
+            version: EXECUTION_EFFECT_JSON_VERSION,
+            effect,
+        }
+    }
+
+    /// Returns the version of the JSON format.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Returns the execution effect.
+    pub fn effect(&self) -> &ExecutionEffect {
+        &self.effect
+    }
+
+    /// Consumes `self`, returning the execution effect.
+    pub fn into_effect(self) -> ExecutionEffect {
+        self.effect
+    }
+
+    /// Encodes `self` as JSON.
+    pub fn to_json(&self) -> Result<String, ExecutionEffectJsonError> {
+        serde_json::to_string(self)
+            .map_err(|error| ExecutionEffectJsonError::Json(error.to_string()))
+    }
+
+    /// Decodes an instance from JSON, rejecting any version of the format newer than the current
+    /// one or older than the oldest one it can read.
+    pub fn from_json(json: &str) -> Result<Self, ExecutionEffectJsonError> {
+        #[derive(Deserialize)]
+        struct Version {
+            version: u32,
+        }
+
+        let Version { version } = serde_json::from_str(json)
+            .map_err(|error| ExecutionEffectJsonError::Json(error.to_string()))?;
+        if !(OLDEST_READABLE_EXECUTION_EFFECT_JSON_VERSION..=EXECUTION_EFFECT_JSON_VERSION)
+            .contains(&version)
+        {
+            return Err(ExecutionEffectJsonError::UnsupportedVersion(version));
+        }
+        serde_json::from_str(json)
+            .map_err(|error| ExecutionEffectJsonError::Json(error.to_string()))
+    }
+}
+
+impl From<ExecutionEffect> for VersionedExecutionEffect {
+    fn from(effect: ExecutionEffect) -> Self {
+        VersionedExecutionEffect::new(effect)
+    }
+}
+
+/// Error encoding or decoding a [`VersionedExecutionEffect`] as JSON.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(any(feature = "std", test), derive(Error))]
+#[non_exhaustive]
+pub enum ExecutionEffectJsonError {
+    /// The JSON is malformed or doesn't describe an execution effect.
+    #[cfg_attr(
+        any(feature = "std", test),
+        error("invalid execution effect JSON: {0}")
+    )]
+    Json(String),
+    /// The JSON is of a version of the format which can't be read, e.g. one newer than
+    /// [`EXECUTION_EFFECT_JSON_VERSION`].
+    #[cfg_attr(
+        any(feature = "std", test),
+        error("unsupported execution effect JSON version {0}")
+    )]
+    UnsupportedVersion(u32),
+}
+
+#[cfg(not(any(feature = "std", test)))]
+impl Display for ExecutionEffectJsonError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self, formatter)
+    }
+}
+
 /// An operation performed while executing a deploy.
 #[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
 #[cfg_attr(feature = "datasize", derive(DataSize))]
@@ -554,6 +898,12 @@ pub enum Transform {
     Failure(String),
     /// Writes the given Unbonding to global state.
     WriteUnbonding(Vec<UnbondingPurse>),
+    /// Prunes the given key from global state.
+    ///
+    /// Added in version 2 of the execution effect JSON format and encoded under bytesrepr tag 19.
+    /// Deploys executed before it was added never have it in their effects, but clients matching
+    /// on `Transform` must handle it.
+    Prune(#[cfg_attr(feature = "json-schema", schemars(with = "String"))] Key),
 }
 
 impl Transform {
@@ -578,6 +928,7 @@ impl Transform {
             Transform::AddKeys(_) => TransformTag::AddKeys,
             Transform::Failure(_) => TransformTag::Failure,
             Transform::WriteUnbonding(_) => TransformTag::WriteUnbonding,
+            Transform::Prune(_) => TransformTag::Prune,
         }
     }
 }
@@ -638,6 +989,9 @@ impl ToBytes for Transform {
             Transform::WriteUnbonding(value) => {
                 buffer.extend(value.to_bytes()?);
             }
+            Transform::Prune(key) => {
+                buffer.extend(key.to_bytes()?);
+            }
         }
         Ok(buffer)
     }
@@ -663,6 +1017,7 @@ impl ToBytes for Transform {
             Transform::WriteBid(value) => value.serialized_length(),
             Transform::WriteWithdraw(value) => value.serialized_length(),
             Transform::WriteUnbonding(value) => value.serialized_length(),
+            Transform::Prune(key) => key.serialized_length(),
         };
         U8_SERIALIZED_LENGTH + body_len
     }
@@ -738,6 +1093,10 @@ impl FromBytes for Transform {
                     <Vec<UnbondingPurse> as FromBytes>::from_bytes(remainder)?;
                 Ok((Transform::WriteUnbonding(unbonding_purses), remainder))
             }
+            TransformTag::Prune => {
+                let (key, remainder) = Key::from_bytes(remainder)?;
+                Ok((Transform::Prune(key), remainder))
+            }
         }
     }
 }
@@ -745,7 +1104,7 @@ impl FromBytes for Transform {
 impl Distribution<Transform> for Standard {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Transform {
         // TODO - include WriteDeployInfo and WriteTransfer as options
-        match rng.gen_range(0..13) {
+        match rng.gen_range(0..14) {
             0 => Transform::Identity,
             1 => Transform::WriteCLValue(CLValue::from_t(true).unwrap()),
             2 => Transform::WriteAccount(AccountHash::new(rng.gen())),
@@ -768,6 +1127,7 @@ impl Distribution<Transform> for Standard {
                 Transform::AddKeys(named_keys)
             }
             12 => Transform::Failure(rng.gen::<u64>().to_string()),
+            13 => Transform::Prune(rng.gen::<Key>()),
             _ => unreachable!(),
         }
     }
@@ -775,9 +1135,22 @@ impl Distribution<Transform> for Standard {
 
 #[cfg(test)]
 mod tests {
+    use std::fs;
+
     use rand::{rngs::SmallRng, Rng, SeedableRng};
+    #[cfg(feature = "json-schema")]
+    use schemars::schema_for;
+    use serde_json::Value;
 
     use super::*;
+    use crate::{
+        system::auction::SeigniorageAllocation, AccessRights, ApiError, DeployHash, EraId,
+        PublicKey, SecretKey, URef,
+    };
+
+    const GOLDEN_EFFECT_PATH: &str = "tests/fixtures/execution_effect_v2.json";
+    #[cfg(feature = "json-schema")]
+    const GOLDEN_SCHEMA_PATH: &str = "tests/fixtures/execution_effect_schema.json";
 
     fn get_rng() -> SmallRng {
         let mut seed = [0u8; 32];
@@ -798,4 +1171,244 @@ mod tests {
         let execution_result: ExecutionResult = rng.gen();
         bytesrepr::test_serialization_roundtrip(&execution_result);
     }
+
+    /// The bincode encoding of `ExecutionResult` before error codes were added.
+    #[derive(Serialize)]
+    enum LegacyExecutionResult {
+        Failure {
+            effect: ExecutionEffect,
+            transfers: Vec<TransferAddr>,
+            cost: U512,
+            error_message: String,
+        },
+    }
+
+    fn failure(error_code: Option<ExecutionErrorCode>) -> ExecutionResult {
+        ExecutionResult::Failure {
+            effect: ExecutionEffect::default(),
+            transfers: vec![TransferAddr::new([1; 32])],
+            cost: U512::from(2),
+            error_message: "failed".to_string(),
+            error_code,
+        }
+    }
+
+    #[test]
+    fn bincode_should_read_failure_without_error_code() {
+        let legacy = LegacyExecutionResult::Failure {
+            effect: ExecutionEffect::default(),
+            transfers: vec![TransferAddr::new([1; 32])],
+            cost: U512::from(2),
+            error_message: "failed".to_string(),
+        };
+        let legacy_bytes = bincode::serialize(&legacy).unwrap();
+        assert_eq!(bincode::serialize(&failure(None)).unwrap(), legacy_bytes);
+        let decoded: ExecutionResult = bincode::deserialize(&legacy_bytes).unwrap();
+        assert_eq!(decoded, failure(None));
+
+        let with_error_code = failure(Some(ExecutionErrorCode::from(ApiError::User(1))));
+        let bytes = bincode::serialize(&with_error_code).unwrap();
+        let decoded: ExecutionResult = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(decoded, with_error_code);
+    }
+
+    #[test]
+    fn bytesrepr_should_keep_layout_of_failure_without_error_code() {
+        let without_error_code = failure(None).to_bytes().unwrap();
+        assert_eq!(without_error_code[0], ExecutionResultTag::Failure as u8);
+
+        let error_code = ExecutionErrorCode::from(ApiError::User(1));
+        let with_error_code = failure(Some(error_code)).to_bytes().unwrap();
+        assert_eq!(
+            with_error_code[0],
+            ExecutionResultTag::FailureWithErrorCode as u8
+        );
+        assert_eq!(
+            with_error_code[1..],
+            [&without_error_code[1..], &error_code.to_bytes().unwrap()].concat()
+        );
+    }
+
+    #[test]
+    fn json_should_omit_unrecorded_error_code() {
+        let json = serde_json::to_value(failure(None)).unwrap();
+        assert!(json["Failure"].get("error_code").is_none());
+        let decoded: ExecutionResult = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, failure(None));
+
+        let with_error_code = failure(Some(ExecutionErrorCode::from(ApiError::User(1))));
+        let json = serde_json::to_value(&with_error_code).unwrap();
+        assert_eq!(
+            json["Failure"]["error_code"],
+            serde_json::json!({ "category": "Revert", "code": 65537 })
+        );
+        let decoded: ExecutionResult = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, with_error_code);
+    }
+
+    fn read_golden_file(path: &str) -> Value {
+        let path = format!("{}/{}", env!("CARGO_MANIFEST_DIR"), path);
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    /// Returns an effect containing every variant of `Transform`, built from fixed values so that
+    /// its JSON encoding is stable.  It must be extended whenever a variant is added.
+    fn effect_with_every_transform() -> ExecutionEffect {
+        let public_key =
+            |seed: u8| PublicKey::from(&SecretKey::ed25519_from_bytes([seed; 32]).unwrap());
+        let uref = |seed: u8| URef::new([seed; 32], AccessRights::READ_ADD_WRITE);
+        let deploy_hash = DeployHash::new([3; 32]);
+        let account_hash = AccountHash::new([4; 32]);
+        let mut era_info = EraInfo::new();
+        era_info
+            .seigniorage_allocations_mut()
+            .push(SeigniorageAllocation::validator(
+                public_key(1),
+                U512::from(5),
+            ));
+        era_info
+            .seigniorage_allocations_mut()
+            .push(SeigniorageAllocation::delegator(
+                public_key(2),
+                public_key(1),
+                U512::from(6),
+            ));
+
+        let transforms = vec![
+            Transform::Identity,
+            Transform::WriteCLValue(CLValue::from_t(7_u64).unwrap()),
+            Transform::WriteAccount(account_hash),
+            Transform::WriteContractWasm,
+            Transform::WriteContract,
+            Transform::WriteContractPackage,
+            Transform::WriteDeployInfo(DeployInfo::new(
+                deploy_hash,
+                &[TransferAddr::new([8; 32])],
+                account_hash,
+                uref(9),
+                U512::from(10),
+            )),
+            Transform::WriteEraInfo(era_info),
+            Transform::WriteTransfer(Transfer::new(
+                deploy_hash,
+                account_hash,
+                Some(AccountHash::new([11; 32])),
+                uref(9),
+                uref(12),
+                U512::from(13),
+                U512::from(14),
+                Some(15),
+            )),
+            Transform::WriteBid(Box::new(Bid::unlocked(
+                public_key(1),
+                uref(16),
+                U512::from(17),
+                18,
+            ))),
+            Transform::WriteWithdraw(vec![WithdrawPurse::new(
+                uref(16),
+                public_key(1),
+                public_key(2),
+                EraId::new(19),
+                U512::from(20),
+            )]),
+            Transform::AddInt32(-21),
+            Transform::AddUInt64(22),
+            Transform::AddUInt128(U128::from(23)),
+            Transform::AddUInt256(U256::from(24)),
+            Transform::AddUInt512(U512::from(25)),
+            Transform::AddKeys(vec![NamedKey {
+                name: "key".to_string(),
+                key: Key::Hash([26; 32]).to_formatted_string(),
+            }]),
+            Transform::Failure("failure".to_string()),
+            Transform::WriteUnbonding(vec![UnbondingPurse::new(
+                uref(16),
+                public_key(1),
+                public_key(2),
+                EraId::new(27),
+                U512::from(28),
+                Some(public_key(29)),
+            )]),
+            Transform::Prune(Key::Hash([30; 32])),
+        ];
+        let key = Key::Hash([31; 32]).to_formatted_string();
+
+        ExecutionEffect {
+            operations: vec![
+                Operation {
+                    key: key.clone(),
+                    kind: OpKind::Read,
+                },
+                Operation {
+                    key: key.clone(),
+                    kind: OpKind::Write,
+                },
+                Operation {
+                    key: key.clone(),
+                    kind: OpKind::Add,
+                },
+                Operation {
+                    key: key.clone(),
+                    kind: OpKind::NoOp,
+                },
+            ],
+            transforms: transforms
+                .into_iter()
+                .map(|transform| TransformEntry {
+                    key: key.clone(),
+                    transform,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn json_export_should_match_golden_file() {
+        let versioned_effect = VersionedExecutionEffect::new(effect_with_every_transform());
+        let expected = read_golden_file(GOLDEN_EFFECT_PATH);
+        assert_eq!(serde_json::to_value(&versioned_effect).unwrap(), expected);
+
+        let decoded = VersionedExecutionEffect::from_json(&expected.to_string()).unwrap();
+        assert_eq!(decoded, versioned_effect);
+        let encoded = versioned_effect.to_json().unwrap();
+        assert_eq!(
+            VersionedExecutionEffect::from_json(&encoded).unwrap(),
+            versioned_effect
+        );
+    }
+
+    #[test]
+    fn json_export_should_read_older_version() {
+        let effect = ExecutionEffect::new(vec![TransformEntry {
+            key: Key::Hash([1; 32]).to_formatted_string(),
+            transform: Transform::AddUInt64(2),
+        }]);
+        let mut json = serde_json::to_value(VersionedExecutionEffect::new(effect.clone())).unwrap();
+        json["version"] = Value::from(1);
+
+        let decoded = VersionedExecutionEffect::from_json(&json.to_string()).unwrap();
+        assert_eq!(decoded.version(), 1);
+        assert_eq!(decoded.into_effect(), effect);
+    }
+
+    #[test]
+    fn json_export_should_reject_unsupported_version() {
+        let mut json = read_golden_file(GOLDEN_EFFECT_PATH);
+        json["version"] = Value::from(EXECUTION_EFFECT_JSON_VERSION + 1);
+        json["new_field"] = Value::from(1);
+        assert_eq!(
+            VersionedExecutionEffect::from_json(&json.to_string()),
+            Err(ExecutionEffectJsonError::UnsupportedVersion(
+                EXECUTION_EFFECT_JSON_VERSION + 1
+            ))
+        );
+    }
+
+    #[cfg(feature = "json-schema")]
+    #[test]
+    fn json_schema_should_match_golden_file() {
+        let schema = serde_json::to_value(schema_for!(VersionedExecutionEffect)).unwrap();
+        assert_eq!(schema, read_golden_file(GOLDEN_SCHEMA_PATH));
+    }
 }
@@ -25,14 +25,15 @@ use rand::{
 use serde::{de::Error as SerdeError, Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{
-    account::{self, AccountHash, ACCOUNT_HASH_LENGTH},
+    account::{self, AccountHash, ACCOUNT_HASH_FORMATTED_STRING_PREFIX, ACCOUNT_HASH_LENGTH},
     bytesrepr::{self, Error, FromBytes, ToBytes, U64_SERIALIZED_LENGTH},
     checksummed_hex,
     contract_wasm::ContractWasmHash,
     contracts::{ContractHash, ContractPackageHash},
-    uref::{self, URef, URefAddr, UREF_SERIALIZED_LENGTH},
-    DeployHash, EraId, Tagged, TransferAddr, TransferFromStrError, DEPLOY_HASH_LENGTH,
-    TRANSFER_ADDR_LENGTH, UREF_ADDR_LENGTH,
+    transfer::TRANSFER_ADDR_FORMATTED_STRING_PREFIX,
+    uref::{self, URef, URefAddr, UREF_FORMATTED_STRING_PREFIX, UREF_SERIALIZED_LENGTH},
+    AccessRights, DeployHash, EraId, Tagged, TransferAddr, TransferFromStrError,
+    DEPLOY_HASH_LENGTH, TRANSFER_ADDR_LENGTH, UREF_ADDR_LENGTH,
 };
 
 const HASH_PREFIX: &str = "hash-";
@@ -47,6 +48,8 @@ const SYSTEM_CONTRACT_REGISTRY_PREFIX: &str = "system-contract-registry-";
 const ERA_SUMMARY_PREFIX: &str = "era-summary-";
 const CHAINSPEC_REGISTRY_PREFIX: &str = "chainspec-registry-";
 const CHECKSUM_REGISTRY_PREFIX: &str = "checksum-registry-";
+const BID_METADATA_PREFIX: &str = "bid-metadata-";
+const DISPLAY_PREFIX: &str = "Key::";
 
 /// The number of bytes in a Blake2b hash
 pub const BLAKE2B_DIGEST_LENGTH: usize = 32;
@@ -80,6 +83,7 @@ const KEY_CHAINSPEC_REGISTRY_SERIALIZED_LENGTH: usize =
     KEY_ID_SERIALIZED_LENGTH + PADDING_BYTES.len();
 const KEY_CHECKSUM_REGISTRY_SERIALIZED_LENGTH: usize =
     KEY_ID_SERIALIZED_LENGTH + PADDING_BYTES.len();
+const KEY_BID_METADATA_SERIALIZED_LENGTH: usize = KEY_ID_SERIALIZED_LENGTH + KEY_HASH_LENGTH;
 
 /// An alias for [`Key`]s hash variant.
 pub type HashAddr = [u8; KEY_HASH_LENGTH];
@@ -106,6 +110,7 @@ pub enum KeyTag {
     Unbond = 12,
     ChainspecRegistry = 13,
     ChecksumRegistry = 14,
+    BidMetadata = 15,
 }
 
 /// The type under which data (e.g. [`CLValue`](crate::CLValue)s, smart contracts, user accounts)
@@ -145,6 +150,8 @@ pub enum Key {
     ChainspecRegistry,
     /// A `Key` variant under which we store a registry of checksums.
     ChecksumRegistry,
+    /// A `Key` under which we store validator-provided bid metadata.
+    BidMetadata(AccountHash),
 }
 
 /// Errors produced when converting a `String` into a `Key`.
@@ -181,6 +188,8 @@ pub enum FromStrError {
     ChainspecRegistry(String),
     /// Checksum registry error.
     ChecksumRegistry(String),
+    /// Bid metadata parse error.
+    BidMetadata(String),
     /// Unknown prefix.
     UnknownPrefix,
 }
@@ -239,6 +248,9 @@ impl Display for FromStrError {
             FromStrError::ChecksumRegistry(error) => {
                 write!(f, "checksum-registry-key from string error: {}", error)
             }
+            FromStrError::BidMetadata(error) => {
+                write!(f, "bid-metadata-key from string error: {}", error)
+            }
             FromStrError::UnknownPrefix => write!(f, "unknown prefix for key"),
         }
     }
@@ -264,6 +276,7 @@ impl Key {
             Key::Unbond(_) => String::from("Key::Unbond"),
             Key::ChainspecRegistry => String::from("Key::ChainspecRegistry"),
             Key::ChecksumRegistry => String::from("Key::ChecksumRegistry"),
+            Key::BidMetadata(_) => String::from("Key::BidMetadata"),
         }
     }
 
@@ -347,6 +360,13 @@ impl Key {
                     base16::encode_lower(&PADDING_BYTES)
                 )
             }
+            Key::BidMetadata(account_hash) => {
+                format!(
+                    "{}{}",
+                    BID_METADATA_PREFIX,
+                    base16::encode_lower(&account_hash)
+                )
+            }
         }
     }
 
@@ -409,6 +429,14 @@ impl Key {
             return Ok(Key::Balance(uref_addr));
         }
 
+        if let Some(hex) = input.strip_prefix(BID_METADATA_PREFIX) {
+            let hash = checksummed_hex::decode(hex)
+                .map_err(|error| FromStrError::BidMetadata(error.to_string()))?;
+            let account_hash = <[u8; ACCOUNT_HASH_LENGTH]>::try_from(hash.as_ref())
+                .map_err(|error| FromStrError::BidMetadata(error.to_string()))?;
+            return Ok(Key::BidMetadata(AccountHash::new(account_hash)));
+        }
+
         if let Some(hex) = input.strip_prefix(BID_PREFIX) {
             let hash = checksummed_hex::decode(hex)
                 .map_err(|error| FromStrError::Bid(error.to_string()))?;
@@ -477,6 +505,54 @@ impl Key {
         Err(FromStrError::UnknownPrefix)
     }
 
+    /// Parses a string formatted as per `Display` for `Key`, e.g. `Key::Hash(<hex>)`, by
+    /// rewriting it into the equivalent formatted string.
+    fn from_display_str(input: &str) -> Result<Key, FromStrError> {
+        let (variant, inner) = input
+            .strip_prefix(DISPLAY_PREFIX)
+            .and_then(|remainder| remainder.strip_suffix(')'))
+            .and_then(|remainder| remainder.split_once('('))
+            .ok_or(FromStrError::UnknownPrefix)?;
+        let prefix = match variant {
+            "Account" => ACCOUNT_HASH_FORMATTED_STRING_PREFIX,
+            "Hash" => HASH_PREFIX,
+            "URef" => {
+                let (addr, access_rights) = inner
+                    .split_once(", ")
+                    .ok_or(FromStrError::URef(uref::FromStrError::MissingSuffix))?;
+                let access_rights = access_rights_from_display_str(access_rights)
+                    .ok_or(FromStrError::URef(uref::FromStrError::InvalidAccessRights))?;
+                let formatted = format!(
+                    "{}{}-{:03o}",
+                    UREF_FORMATTED_STRING_PREFIX,
+                    addr,
+                    access_rights.bits()
+                );
+                return Key::from_formatted_str(&formatted);
+            }
+            "Transfer" => TRANSFER_ADDR_FORMATTED_STRING_PREFIX,
+            "DeployInfo" => DEPLOY_INFO_PREFIX,
+            "EraInfo" => {
+                let era_id = inner.strip_prefix("era ").ok_or_else(|| {
+                    FromStrError::EraInfo("missing 'era ' before era id".to_string())
+                })?;
+                return Key::from_formatted_str(&format!("{}{}", ERA_INFO_PREFIX, era_id));
+            }
+            "Balance" => BALANCE_PREFIX,
+            "Bid" => BID_PREFIX,
+            "Withdraw" => WITHDRAW_PREFIX,
+            "Dictionary" => DICTIONARY_PREFIX,
+            "SystemContractRegistry" => SYSTEM_CONTRACT_REGISTRY_PREFIX,
+            "EraSummary" => ERA_SUMMARY_PREFIX,
+            "Unbond" => UNBOND_PREFIX,
+            "ChainspecRegistry" => CHAINSPEC_REGISTRY_PREFIX,
+            "ChecksumRegistry" => CHECKSUM_REGISTRY_PREFIX,
+            "BidMetadata" => BID_METADATA_PREFIX,
+            _ => return Err(FromStrError::UnknownPrefix),
+        };
+        Key::from_formatted_str(&format!("{}{}", prefix, inner))
+    }
+
     /// Returns the inner bytes of `self` if `self` is of type [`Key::Account`], otherwise returns
     /// `None`.
     pub fn into_account(self) -> Option<AccountHash> {
@@ -621,6 +697,7 @@ impl Display for Key {
                     base16::encode_lower(&PADDING_BYTES)
                 )
             }
+            Key::BidMetadata(account_hash) => write!(f, "Key::BidMetadata({})", account_hash),
         }
     }
 }
@@ -631,6 +708,36 @@ impl Debug for Key {
     }
 }
 
+/// Parses a `Key` from either its formatted string form (as per [`Key::to_formatted_string`]) or
+/// its `Display` form.
+impl FromStr for Key {
+    type Err = FromStrError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        if input.starts_with(DISPLAY_PREFIX) {
+            Key::from_display_str(input)
+        } else {
+            Key::from_formatted_str(input)
+        }
+    }
+}
+
+/// Returns the access rights named as per `Display` for `AccessRights`.
+fn access_rights_from_display_str(input: &str) -> Option<AccessRights> {
+    let access_rights = match input {
+        "NONE" => AccessRights::NONE,
+        "READ" => AccessRights::READ,
+        "WRITE" => AccessRights::WRITE,
+        "ADD" => AccessRights::ADD,
+        "READ_ADD" => AccessRights::READ_ADD,
+        "READ_WRITE" => AccessRights::READ_WRITE,
+        "ADD_WRITE" => AccessRights::ADD_WRITE,
+        "READ_ADD_WRITE" => AccessRights::READ_ADD_WRITE,
+        _ => return None,
+    };
+    Some(access_rights)
+}
+
 impl Tagged<KeyTag> for Key {
     fn tag(&self) -> KeyTag {
         match self {
@@ -649,6 +756,7 @@ impl Tagged<KeyTag> for Key {
             Key::Unbond(_) => KeyTag::Unbond,
             Key::ChainspecRegistry => KeyTag::ChainspecRegistry,
             Key::ChecksumRegistry => KeyTag::ChecksumRegistry,
+            Key::BidMetadata(_) => KeyTag::BidMetadata,
         }
     }
 }
@@ -722,6 +830,7 @@ impl ToBytes for Key {
             Key::Unbond(_) => KEY_UNBOND_SERIALIZED_LENGTH,
             Key::ChainspecRegistry => KEY_CHAINSPEC_REGISTRY_SERIALIZED_LENGTH,
             Key::ChecksumRegistry => KEY_CHECKSUM_REGISTRY_SERIALIZED_LENGTH,
+            Key::BidMetadata(_) => KEY_BID_METADATA_SERIALIZED_LENGTH,
         }
     }
 
@@ -739,6 +848,7 @@ impl ToBytes for Key {
             Key::Withdraw(account_hash) => account_hash.write_bytes(writer),
             Key::Dictionary(addr) => addr.write_bytes(writer),
             Key::Unbond(account_hash) => account_hash.write_bytes(writer),
+            Key::BidMetadata(account_hash) => account_hash.write_bytes(writer),
             Key::SystemContractRegistry
             | Key::EraSummary
             | Key::ChainspecRegistry
@@ -811,6 +921,10 @@ impl FromBytes for Key {
                 let (_, rem) = <[u8; 32]>::from_bytes(remainder)?;
                 Ok((Key::ChecksumRegistry, rem))
             }
+            tag if tag == KeyTag::BidMetadata as u8 => {
+                let (account_hash, rem) = AccountHash::from_bytes(remainder)?;
+                Ok((Key::BidMetadata(account_hash), rem))
+            }
             _ => Err(Error::Formatting),
         }
     }
@@ -836,12 +950,13 @@ fn please_add_to_distribution_impl(key: Key) {
         Key::Unbond(_) => unimplemented!(),
         Key::ChainspecRegistry => unimplemented!(),
         Key::ChecksumRegistry => unimplemented!(),
+        Key::BidMetadata(_) => unimplemented!(),
     }
 }
 
 impl Distribution<Key> for Standard {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Key {
-        match rng.gen_range(0..=14) {
+        match rng.gen_range(0..=15) {
             0 => Key::Account(rng.gen()),
             1 => Key::Hash(rng.gen()),
             2 => Key::URef(rng.gen()),
@@ -857,6 +972,7 @@ impl Distribution<Key> for Standard {
             12 => Key::Unbond(rng.gen()),
             13 => Key::ChainspecRegistry,
             14 => Key::ChecksumRegistry,
+            15 => Key::BidMetadata(rng.gen()),
             _ => unreachable!(),
         }
     }
@@ -882,6 +998,7 @@ mod serde_helpers {
         Unbond(String),
         ChainspecRegistry(String),
         ChecksumRegistry(String),
+        BidMetadata(String),
     }
 
     impl From<&Key> for HumanReadable {
@@ -905,6 +1022,7 @@ mod serde_helpers {
                 Key::Unbond(_) => HumanReadable::Unbond(formatted_string),
                 Key::ChainspecRegistry => HumanReadable::ChainspecRegistry(formatted_string),
                 Key::ChecksumRegistry => HumanReadable::ChecksumRegistry(formatted_string),
+                Key::BidMetadata(_) => HumanReadable::BidMetadata(formatted_string),
             }
         }
     }
@@ -928,7 +1046,8 @@ mod serde_helpers {
                 | HumanReadable::EraSummary(formatted_string)
                 | HumanReadable::Unbond(formatted_string)
                 | HumanReadable::ChainspecRegistry(formatted_string)
-                | HumanReadable::ChecksumRegistry(formatted_string) => {
+                | HumanReadable::ChecksumRegistry(formatted_string)
+                | HumanReadable::BidMetadata(formatted_string) => {
                     Key::from_formatted_str(&formatted_string)
                 }
             }
@@ -952,6 +1071,7 @@ mod serde_helpers {
         Unbond(&'a AccountHash),
         ChainspecRegistry,
         ChecksumRegistry,
+        BidMetadata(&'a AccountHash),
     }
 
     impl<'a> From<&'a Key> for BinarySerHelper<'a> {
@@ -972,6 +1092,7 @@ mod serde_helpers {
                 Key::Unbond(account_hash) => BinarySerHelper::Unbond(account_hash),
                 Key::ChainspecRegistry => BinarySerHelper::ChainspecRegistry,
                 Key::ChecksumRegistry => BinarySerHelper::ChecksumRegistry,
+                Key::BidMetadata(account_hash) => BinarySerHelper::BidMetadata(account_hash),
             }
         }
     }
@@ -993,6 +1114,7 @@ mod serde_helpers {
         Unbond(AccountHash),
         ChainspecRegistry,
         ChecksumRegistry,
+        BidMetadata(AccountHash),
     }
 
     impl From<BinaryDeserHelper> for Key {
@@ -1013,6 +1135,7 @@ mod serde_helpers {
                 BinaryDeserHelper::Unbond(account_hash) => Key::Unbond(account_hash),
                 BinaryDeserHelper::ChainspecRegistry => Key::ChainspecRegistry,
                 BinaryDeserHelper::ChecksumRegistry => Key::ChecksumRegistry,
+                BinaryDeserHelper::BidMetadata(account_hash) => Key::BidMetadata(account_hash),
             }
         }
     }
@@ -1047,13 +1170,7 @@ mod tests {
     use serde_json::json;
 
     use super::*;
-    use crate::{
-        account::ACCOUNT_HASH_FORMATTED_STRING_PREFIX,
-        bytesrepr::{Error, FromBytes},
-        transfer::TRANSFER_ADDR_FORMATTED_STRING_PREFIX,
-        uref::UREF_FORMATTED_STRING_PREFIX,
-        AccessRights, URef,
-    };
+    use crate::bytesrepr::{Error, FromBytes};
 
     const ACCOUNT_KEY: Key = Key::Account(AccountHash::new([42; 32]));
     const HASH_KEY: Key = Key::Hash([42; 32]);
@@ -1070,6 +1187,7 @@ mod tests {
     const UNBOND_KEY: Key = Key::Unbond(AccountHash::new([42; 32]));
     const CHAINSPEC_REGISTRY_KEY: Key = Key::ChainspecRegistry;
     const CHECKSUM_REGISTRY_KEY: Key = Key::ChecksumRegistry;
+    const BID_METADATA_KEY: Key = Key::BidMetadata(AccountHash::new([42; 32]));
     const KEYS: &[Key] = &[
         ACCOUNT_KEY,
         HASH_KEY,
@@ -1086,6 +1204,7 @@ mod tests {
         UNBOND_KEY,
         CHAINSPEC_REGISTRY_KEY,
         CHECKSUM_REGISTRY_KEY,
+        BID_METADATA_KEY,
     ];
     const HEX_STRING: &str = "2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a";
 
@@ -1202,6 +1321,10 @@ mod tests {
                 base16::encode_lower(&PADDING_BYTES),
             )
         );
+        assert_eq!(
+            format!("{}", BID_METADATA_KEY),
+            format!("Key::BidMetadata({})", HEX_STRING)
+        );
     }
 
     #[test]
@@ -1268,6 +1391,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn should_parse_key_from_display_and_formatted_string() {
+        let zeros = [0; BLAKE2B_DIGEST_LENGTH];
+        let more_keys = [
+            Key::URef(URef::new([42; 32], AccessRights::NONE)),
+            Key::URef(URef::new([42; 32], AccessRights::READ_ADD_WRITE)),
+            Key::EraInfo(EraId::new(0)),
+            Key::Account(AccountHash::new(zeros)),
+        ];
+        for key in KEYS.iter().chain(more_keys.iter()) {
+            let string = key.to_string();
+            assert_eq!(Key::from_str(&string).unwrap(), *key, "{string}");
+
+            let string = key.to_formatted_string();
+            assert_eq!(Key::from_str(&string).unwrap(), *key, "{string}");
+        }
+    }
+
+    #[test]
+    fn should_fail_to_parse_key_from_invalid_display_string() {
+        for invalid in [
+            "Key::",
+            "Key::Unknown(00)",
+            "Key::Hash(2a2a)",
+            "Key::Hash(2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a",
+            "Key::EraInfo(42)",
+            "Key::URef(2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a)",
+            "Key::URef(2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a, UNKNOWN)",
+        ] {
+            assert!(Key::from_str(invalid).is_err(), "{invalid}");
+        }
+    }
+
     #[test]
     fn should_fail_to_parse_key_from_str() {
         assert!(
@@ -1334,6 +1490,10 @@ mod tests {
             .unwrap_err()
             .to_string()
             .starts_with("checksum-registry-key from string error: "));
+        assert!(Key::from_formatted_str(BID_METADATA_PREFIX)
+            .unwrap_err()
+            .to_string()
+            .starts_with("bid-metadata-key from string error: "));
         let invalid_prefix = "a-0000000000000000000000000000000000000000000000000000000000000000";
         assert_eq!(
             Key::from_formatted_str(invalid_prefix)
@@ -1393,6 +1553,7 @@ mod tests {
                 "ChecksumRegistry":
                     format!("checksum-registry-{}", base16::encode_lower(&PADDING_BYTES))
             }),
+            json!({ "BidMetadata": format!("bid-metadata-{}", HEX_STRING) }),
         ];
 
         assert_eq!(
@@ -1444,5 +1605,6 @@ mod tests {
         round_trip(&Key::Unbond(AccountHash::new(zeros)));
         round_trip(&Key::ChainspecRegistry);
         round_trip(&Key::ChecksumRegistry);
+        round_trip(&Key::BidMetadata(AccountHash::new(zeros)));
     }
 }
@@ -34,6 +34,7 @@ pub mod contracts;
 pub mod crypto;
 mod deploy_info;
 mod era_id;
+mod execution_error_code;
 mod execution_result;
 #[cfg(any(feature = "std", test))]
 pub mod file_utils;
@@ -54,6 +55,7 @@ mod tagged;
 #[cfg(any(feature = "testing", test))]
 pub mod testing;
 mod timestamp;
+mod transaction_receipt;
 mod transfer;
 mod transfer_result;
 mod uint;
@@ -76,8 +78,10 @@ pub use contracts::{
 };
 pub use crypto::*;
 pub use deploy_info::DeployInfo;
+pub use execution_error_code::{ErrorCategory, ExecutionErrorCode};
 pub use execution_result::{
-    ExecutionEffect, ExecutionResult, OpKind, Operation, Transform, TransformEntry,
+    ExecutionEffect, ExecutionEffectJsonError, ExecutionResult, OpKind, Operation, Transform,
+    TransformEntry, VersionedExecutionEffect, EXECUTION_EFFECT_JSON_VERSION,
 };
 pub use gas::Gas;
 pub use json_pretty_printer::json_pretty_print;
@@ -98,6 +102,7 @@ pub use tagged::Tagged;
 #[cfg(any(feature = "std", test))]
 pub use timestamp::serde_option_time_diff;
 pub use timestamp::{TimeDiff, Timestamp};
+pub use transaction_receipt::{TransactionReceipt, BLOCK_HASH_LENGTH};
 pub use transfer::{
     DeployHash, FromStrError as TransferFromStrError, Transfer, TransferAddr, DEPLOY_HASH_LENGTH,
     TRANSFER_ADDR_LENGTH,
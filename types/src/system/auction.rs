@@ -5,6 +5,7 @@ mod delegator;
 mod entry_points;
 mod era_info;
 mod error;
+mod rewards_destination;
 mod seigniorage_recipient;
 mod unbonding_purse;
 mod withdraw_purse;
@@ -17,6 +18,7 @@ pub use delegator::Delegator;
 pub use entry_points::auction_entry_points;
 pub use era_info::{EraInfo, SeigniorageAllocation};
 pub use error::Error;
+pub use rewards_destination::RewardsDestination;
 pub use seigniorage_recipient::SeigniorageRecipient;
 pub use unbonding_purse::UnbondingPurse;
 pub use withdraw_purse::WithdrawPurse;
@@ -49,5 +51,9 @@ pub type SeigniorageRecipientsSnapshot = BTreeMap<EraId, SeigniorageRecipients>;
 /// Validators and delegators mapped to their unbonding purses.
 pub type UnbondingPurses = BTreeMap<AccountHash, Vec<UnbondingPurse>>;
 
+/// Stakes, identified by their validator and, for a delegation, their delegator, mapped to the
+/// destination chosen for their era rewards where it is not the default.
+pub type RewardsDestinations = BTreeMap<(PublicKey, Option<PublicKey>), RewardsDestination>;
+
 /// Validators and delegators mapped to their withdraw purses.
 pub type WithdrawPurses = BTreeMap<AccountHash, Vec<WithdrawPurse>>;
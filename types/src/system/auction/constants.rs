@@ -16,6 +16,9 @@ pub const DELEGATION_RATE_DENOMINATOR: DelegationRate = 100;
 /// fractions, and small enough for many block rewards to fit into a u64.
 pub const BLOCK_REWARD: u64 = 1_000_000_000_000;
 
+/// Maximum length in bytes of the metadata a validator can attach to its bid.
+pub const MAX_BID_METADATA_LENGTH: usize = 1024;
+
 /// Named constant for `amount`.
 pub const ARG_AMOUNT: &str = "amount";
 /// Named constant for `delegation_rate`.
@@ -58,6 +61,10 @@ pub const ARG_UNBONDING_DELAY: &str = "unbonding_delay";
 pub const ARG_ERA_END_TIMESTAMP_MILLIS: &str = "era_end_timestamp_millis";
 /// Named constant for `evicted_validators`;
 pub const ARG_EVICTED_VALIDATORS: &str = "evicted_validators";
+/// Named constant for `metadata`.
+pub const ARG_METADATA: &str = "metadata";
+/// Named constant for `rewards_destination`.
+pub const ARG_REWARDS_DESTINATION: &str = "rewards_destination";
 
 /// Named constant for method `get_era_validators`.
 pub const METHOD_GET_ERA_VALIDATORS: &str = "get_era_validators";
@@ -81,6 +88,14 @@ pub const METHOD_DISTRIBUTE: &str = "distribute";
 pub const METHOD_READ_ERA_ID: &str = "read_era_id";
 /// Named constant for method `activate_bid`.
 pub const METHOD_ACTIVATE_BID: &str = "activate_bid";
+/// Named constant for method `set_bid_metadata`.
+pub const METHOD_SET_BID_METADATA: &str = "set_bid_metadata";
+/// Named constant for method `set_rewards_destination`.
+pub const METHOD_SET_REWARDS_DESTINATION: &str = "set_rewards_destination";
+/// Named constant for method `set_delegator_rewards_destination`.
+pub const METHOD_SET_DELEGATOR_REWARDS_DESTINATION: &str = "set_delegator_rewards_destination";
+/// Named constant for method `cancel_unbonding`.
+pub const METHOD_CANCEL_UNBONDING: &str = "cancel_unbonding";
 
 /// Storage for `EraId`.
 pub const ERA_ID_KEY: &str = "era_id";
@@ -96,3 +111,7 @@ pub const AUCTION_DELAY_KEY: &str = "auction_delay";
 pub const LOCKED_FUNDS_PERIOD_KEY: &str = "locked_funds_period";
 /// Unbonding delay expressed in eras.
 pub const UNBONDING_DELAY_KEY: &str = "unbonding_delay";
+/// Storage for the number of consecutive eras each validator has been reported inactive.
+pub const INACTIVE_ERAS_KEY: &str = "inactive_eras";
+/// Storage for the destinations chosen for era rewards, where not the default.
+pub const REWARDS_DESTINATIONS_KEY: &str = "rewards_destinations";
@@ -327,6 +327,18 @@ pub enum Error {
     /// assert_eq!(49, Error::TransferToAdministrator as u8);
     /// ```
     TransferToAdministrator = 49,
+    /// Supplied bid metadata exceeds the maximum allowed length.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(50, Error::BidMetadataTooLarge as u8);
+    /// ```
+    BidMetadataTooLarge = 50,
+    /// Attempted to cancel more than is pending unbonding.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(51, Error::CancelUnbondingTooLarge as u8);
+    /// ```
+    CancelUnbondingTooLarge = 51,
 }
 
 impl Display for Error {
@@ -382,6 +394,10 @@ impl Display for Error {
             Error::AuctionBidsDisabled => formatter.write_str("Auction bids are disabled"),
             Error::GetAccumulationPurse => formatter.write_str("Get accumulation purse error"),
             Error::TransferToAdministrator => formatter.write_str("Transfer to administrator error"),
+            Error::BidMetadataTooLarge => formatter.write_str("Bid metadata is too large"),
+            Error::CancelUnbondingTooLarge => {
+                formatter.write_str("Cancelled amount exceeds the amount pending unbonding")
+            }
         }
     }
 }
@@ -463,6 +479,8 @@ impl TryFrom<u8> for Error {
             d if d == Error::AuctionBidsDisabled as u8 => Ok(Error::AuctionBidsDisabled),
             d if d == Error::GetAccumulationPurse as u8 => Ok(Error::GetAccumulationPurse),
             d if d == Error::TransferToAdministrator as u8 => Ok(Error::TransferToAdministrator),
+            d if d == Error::BidMetadataTooLarge as u8 => Ok(Error::BidMetadataTooLarge),
+            d if d == Error::CancelUnbondingTooLarge as u8 => Ok(Error::CancelUnbondingTooLarge),
             _ => Err(TryFromU8ForError(())),
         }
     }
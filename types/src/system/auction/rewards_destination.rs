@@ -0,0 +1,89 @@
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+#[cfg(feature = "datasize")]
+use datasize::DataSize;
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    CLType, CLTyped,
+};
+
+/// Where the era rewards earned by a validator's or delegator's stake are paid.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[repr(u8)]
+pub enum RewardsDestination {
+    /// Rewards are added to the stake, compounding it.
+    Stake = 0,
+    /// Rewards are paid into the main purse of the staker's account, leaving the stake unchanged.
+    MainPurse = 1,
+}
+
+impl Default for RewardsDestination {
+    fn default() -> Self {
+        RewardsDestination::Stake
+    }
+}
+
+impl TryFrom<u8> for RewardsDestination {
+    type Error = bytesrepr::Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            d if d == RewardsDestination::Stake as u8 => Ok(RewardsDestination::Stake),
+            d if d == RewardsDestination::MainPurse as u8 => Ok(RewardsDestination::MainPurse),
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+impl CLTyped for RewardsDestination {
+    fn cl_type() -> CLType {
+        CLType::U8
+    }
+}
+
+impl ToBytes for RewardsDestination {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        (*self as u8).to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        writer.push(*self as u8);
+        Ok(())
+    }
+}
+
+impl FromBytes for RewardsDestination {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        let rewards_destination = RewardsDestination::try_from(tag)?;
+        Ok((rewards_destination, remainder))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::TryFrom;
+
+    use crate::{bytesrepr, system::auction::RewardsDestination, CLValue};
+
+    #[test]
+    fn serialization_roundtrip() {
+        for rewards_destination in [RewardsDestination::Stake, RewardsDestination::MainPurse] {
+            bytesrepr::test_serialization_roundtrip(&rewards_destination);
+            let cl_value = CLValue::from_t(rewards_destination).unwrap();
+            assert_eq!(cl_value.into_t::<u8>().unwrap(), rewards_destination as u8);
+        }
+        assert!(RewardsDestination::try_from(2).is_err());
+    }
+}
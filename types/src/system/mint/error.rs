@@ -154,6 +154,13 @@ pub enum Error {
     /// assert_eq!(22, Error::DisabledUnrestrictedTransfers as u8);
     DisabledUnrestrictedTransfers = 22,
 
+    /// Transfer would leave the source account's main purse below the minimum remaining balance.
+    /// ```
+    /// # use casper_types::system::mint::Error;
+    /// assert_eq!(23, Error::InsufficientRemainingBalance as u8);
+    /// ```
+    InsufficientRemainingBalance = 23,
+
     #[cfg(test)]
     #[doc(hidden)]
     Sentinel,
@@ -209,6 +216,9 @@ impl TryFrom<u8> for Error {
             d if d == Error::DisabledUnrestrictedTransfers as u8 => {
                 Ok(Error::DisabledUnrestrictedTransfers)
             }
+            d if d == Error::InsufficientRemainingBalance as u8 => {
+                Ok(Error::InsufficientRemainingBalance)
+            }
             _ => Err(TryFromU8ForError(())),
         }
     }
@@ -269,6 +279,9 @@ impl Display for Error {
             Error::DisabledUnrestrictedTransfers => {
                 formatter.write_str("Disabled unrestricted transfers")
             }
+            Error::InsufficientRemainingBalance => {
+                formatter.write_str("Insufficient remaining balance")
+            }
             #[cfg(test)]
             Error::Sentinel => formatter.write_str("Sentinel error"),
         }
@@ -0,0 +1,235 @@
+// TODO - remove once schemars stops causing warning.
+#![allow(clippy::field_reassign_with_default)]
+
+use alloc::{string::String, vec::Vec};
+use core::convert::TryFrom;
+
+#[cfg(feature = "datasize")]
+use datasize::DataSize;
+#[cfg(feature = "json-schema")]
+use once_cell::sync::Lazy;
+use rand::{
+    distributions::{Distribution, Standard},
+    Rng,
+};
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+use serde::{de::Error as SerdeError, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    checksummed_hex, DeployHash, EraId, ExecutionResult, TransferAddr, U512,
+};
+
+/// The length in bytes of a block hash.
+pub const BLOCK_HASH_LENGTH: usize = 32;
+
+#[cfg(feature = "json-schema")]
+static TRANSACTION_RECEIPT: Lazy<TransactionReceipt> = Lazy::new(|| TransactionReceipt {
+    deploy_hash: DeployHash::new([1; 32]),
+    block_hash: [2; BLOCK_HASH_LENGTH],
+    block_height: 1_022,
+    era_id: EraId::new(42),
+    execution_result: ExecutionResult::example().clone(),
+    gas_price: 1,
+    payment_amount: Some(U512::from(2_500_000_000u64)),
+    fee: Some(U512::from(2_500_000_000u64)),
+    refund: Some(U512::zero()),
+});
+
+/// A summary of the outcome of executing a deploy, bundling its execution result with the fee
+/// charged for it and the block which included it.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct TransactionReceipt {
+    /// The hash of the deploy.
+    pub deploy_hash: DeployHash,
+    /// The hash of the block in which the deploy was executed.
+    #[serde(with = "block_hash_serde")]
+    #[cfg_attr(
+        feature = "json-schema",
+        schemars(with = "String", description = "Hex-encoded block hash.")
+    )]
+    pub block_hash: [u8; BLOCK_HASH_LENGTH],
+    /// The height of the block in which the deploy was executed.
+    pub block_height: u64,
+    /// The era of the block in which the deploy was executed.
+    pub era_id: EraId,
+    /// The result of executing the deploy, including its effects and the transfers it made.
+    pub execution_result: ExecutionResult,
+    /// The gas price the deploy was executed at.
+    pub gas_price: u64,
+    /// The amount of motes made available for payment, or `None` if it was set by custom payment
+    /// code.
+    pub payment_amount: Option<U512>,
+    /// The motes charged for the execution of the deploy, net of any refund, as settled by the
+    /// execution engine, or `None` if unknown.  Zero if the deploy failed a precondition before
+    /// payment was taken.
+    pub fee: Option<U512>,
+    /// The motes refunded to the payer, as settled by the execution engine, or `None` if unknown.
+    /// Zero if unspent payment was burned, or if the deploy failed a precondition before payment
+    /// was taken.
+    pub refund: Option<U512>,
+}
+
+impl TransactionReceipt {
+    // This method is not intended to be used by third party crates.
+    #[doc(hidden)]
+    #[cfg(feature = "json-schema")]
+    pub fn example() -> &'static Self {
+        &TRANSACTION_RECEIPT
+    }
+
+    /// Returns the gas consumed executing the deploy.
+    pub fn gas_consumed(&self) -> U512 {
+        match &self.execution_result {
+            ExecutionResult::Failure { cost, .. } | ExecutionResult::Success { cost, .. } => *cost,
+        }
+    }
+
+    /// Returns the transfers made by the deploy.
+    pub fn transfers(&self) -> &[TransferAddr] {
+        match &self.execution_result {
+            ExecutionResult::Failure { transfers, .. }
+            | ExecutionResult::Success { transfers, .. } => transfers,
+        }
+    }
+
+    /// Returns `true` if the deploy executed successfully.
+    pub fn is_success(&self) -> bool {
+        matches!(self.execution_result, ExecutionResult::Success { .. })
+    }
+}
+
+impl ToBytes for TransactionReceipt {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.deploy_hash.serialized_length()
+            + self.block_hash.serialized_length()
+            + self.block_height.serialized_length()
+            + self.era_id.serialized_length()
+            + self.execution_result.serialized_length()
+            + self.gas_price.serialized_length()
+            + self.payment_amount.serialized_length()
+            + self.fee.serialized_length()
+            + self.refund.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.deploy_hash.write_bytes(writer)?;
+        self.block_hash.write_bytes(writer)?;
+        self.block_height.write_bytes(writer)?;
+        self.era_id.write_bytes(writer)?;
+        self.execution_result.write_bytes(writer)?;
+        self.gas_price.write_bytes(writer)?;
+        self.payment_amount.write_bytes(writer)?;
+        self.fee.write_bytes(writer)?;
+        self.refund.write_bytes(writer)
+    }
+}
+
+impl FromBytes for TransactionReceipt {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (deploy_hash, remainder) = DeployHash::from_bytes(bytes)?;
+        let (block_hash, remainder) = <[u8; BLOCK_HASH_LENGTH]>::from_bytes(remainder)?;
+        let (block_height, remainder) = u64::from_bytes(remainder)?;
+        let (era_id, remainder) = EraId::from_bytes(remainder)?;
+        let (execution_result, remainder) = ExecutionResult::from_bytes(remainder)?;
+        let (gas_price, remainder) = u64::from_bytes(remainder)?;
+        let (payment_amount, remainder) = Option::<U512>::from_bytes(remainder)?;
+        let (fee, remainder) = Option::<U512>::from_bytes(remainder)?;
+        let (refund, remainder) = Option::<U512>::from_bytes(remainder)?;
+        let transaction_receipt = TransactionReceipt {
+            deploy_hash,
+            block_hash,
+            block_height,
+            era_id,
+            execution_result,
+            gas_price,
+            payment_amount,
+            fee,
+            refund,
+        };
+        Ok((transaction_receipt, remainder))
+    }
+}
+
+impl Distribution<TransactionReceipt> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> TransactionReceipt {
+        let payment_amount = if rng.gen() {
+            Some(U512::from(rng.gen::<u64>()))
+        } else {
+            None
+        };
+        TransactionReceipt {
+            deploy_hash: rng.gen(),
+            block_hash: rng.gen(),
+            block_height: rng.gen(),
+            era_id: EraId::new(rng.gen()),
+            execution_result: rng.gen(),
+            gas_price: rng.gen(),
+            payment_amount,
+            fee: payment_amount,
+            refund: payment_amount.map(|_| U512::zero()),
+        }
+    }
+}
+
+/// Serializes the block hash as a hex string in human-readable formats.
+mod block_hash_serde {
+    use super::*;
+
+    pub(super) fn serialize<S: Serializer>(
+        block_hash: &[u8; BLOCK_HASH_LENGTH],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            base16::encode_lower(block_hash).serialize(serializer)
+        } else {
+            block_hash.serialize(serializer)
+        }
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<[u8; BLOCK_HASH_LENGTH], D::Error> {
+        if deserializer.is_human_readable() {
+            let hex_string = String::deserialize(deserializer)?;
+            let vec_bytes =
+                checksummed_hex::decode(hex_string.as_bytes()).map_err(SerdeError::custom)?;
+            <[u8; BLOCK_HASH_LENGTH]>::try_from(vec_bytes.as_ref()).map_err(SerdeError::custom)
+        } else {
+            <[u8; BLOCK_HASH_LENGTH]>::deserialize(deserializer)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestRng;
+
+    #[test]
+    fn bytesrepr_roundtrip() {
+        let mut rng = TestRng::new();
+        let transaction_receipt: TransactionReceipt = rng.gen();
+        bytesrepr::test_serialization_roundtrip(&transaction_receipt);
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let mut rng = TestRng::new();
+        let transaction_receipt: TransactionReceipt = rng.gen();
+        let json_string = serde_json::to_string_pretty(&transaction_receipt).unwrap();
+        assert!(json_string.contains(&base16::encode_lower(&transaction_receipt.block_hash)));
+        let decoded: TransactionReceipt = serde_json::from_str(&json_string).unwrap();
+        assert_eq!(decoded, transaction_receipt);
+    }
+}